@@ -20,10 +20,14 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use tari_core::base_node::state_machine_service::states::{
-    StateInfo,
-    StateInfo::{BlockSync, Connecting, HeaderSync, HorizonSync, Listening, StartUp, SyncFailed},
+use tari_core::base_node::{
+    state_machine_service::states::{
+        StateInfo,
+        StateInfo::{BlockSync, Connecting, HeaderSync, HorizonSync, Listening, StartUp, SyncFailed},
+    },
+    sync::{SyncProgress, SyncStage},
 };
+use tari_utilities::ByteArray;
 
 use crate::tari_rpc as grpc;
 
@@ -40,3 +44,36 @@ impl From<&StateInfo> for grpc::BaseNodeState {
         }
     }
 }
+
+impl From<SyncStage> for grpc::SyncStage {
+    fn from(stage: SyncStage) -> Self {
+        match stage {
+            SyncStage::Headers => grpc::SyncStage::Headers,
+            SyncStage::HorizonKernels => grpc::SyncStage::HorizonKernels,
+            SyncStage::HorizonOutputs => grpc::SyncStage::HorizonOutputs,
+            SyncStage::Blocks => grpc::SyncStage::Blocks,
+        }
+    }
+}
+
+impl From<&SyncProgress> for grpc::SyncStageProgress {
+    #[allow(clippy::cast_possible_truncation)]
+    fn from(progress: &SyncProgress) -> Self {
+        Self {
+            stage: grpc::SyncStage::from(progress.stage).into(),
+            sync_peer: progress.sync_peer.to_vec(),
+            start: progress.start,
+            current: progress.current,
+            total: progress.total,
+            items_received: progress.items_received,
+            bytes_received: progress.bytes_received,
+            elapsed_ms: progress.elapsed.as_millis() as u64,
+            network_time_ms: progress.network_time.as_millis() as u64,
+            validation_time_ms: progress.validation_time.as_millis() as u64,
+            items_per_second: progress.items_per_second().unwrap_or_default(),
+            bytes_per_second: progress.bytes_per_second().unwrap_or_default(),
+            validations_per_second: progress.validations_per_second().unwrap_or_default(),
+            eta_seconds: progress.eta().map(|eta| eta.as_secs()).unwrap_or_default(),
+        }
+    }
+}
@@ -23,7 +23,7 @@
 use std::{
     cmp::{max, min},
     collections::HashMap,
-    convert::TryInto,
+    convert::{TryFrom, TryInto},
     fs,
     fs::File,
     io,
@@ -47,7 +47,8 @@ use minotari_wallet::{
     },
     transaction_service::{
         handle::{TransactionEvent, TransactionServiceHandle},
-        storage::models::WalletTransaction,
+        offline_signing::{SignedTransaction, UnsignedTransaction},
+        storage::models::{BatchRecipient, PaymentSchedule, PaymentScheduleStatus, WalletTransaction},
     },
     utxo_scanner_service::handle::UtxoScannerEvent,
     TransactionStage,
@@ -93,6 +94,7 @@ use tari_core::{
             UnblindedOutput,
             WalletOutput,
         },
+        transaction_protocol::adaptor_signature::AdaptorSignature,
         CryptoFactories,
     },
 };
@@ -107,9 +109,14 @@ use tari_key_manager::{
     SeedWords,
 };
 use tari_p2p::{auto_update::AutoUpdateConfig, peer_seeds::SeedPeer, PeerSeedsConfig};
-use tari_script::{push_pubkey_script, CheckSigSchnorrSignature};
+use tari_script::{push_pubkey_script, CheckSigSchnorrSignature, ExecutionStack, TariScript};
 use tari_shutdown::Shutdown;
-use tari_utilities::{encoding::MBase58, hex::Hex, ByteArray, SafePassword};
+use tari_utilities::{
+    encoding::MBase58,
+    hex::{from_hex, Hex},
+    ByteArray,
+    SafePassword,
+};
 use tokio::{
     sync::{broadcast, mpsc},
     time::{sleep, timeout},
@@ -119,16 +126,27 @@ use super::error::CommandError;
 use crate::{
     automation::{
         utils::{
+            create_multisig_output_dir,
             create_pre_mine_output_dir,
             get_file_name,
+            move_session_file_to_dir,
             move_session_file_to_session_dir,
+            multisig_out_dir,
             out_dir,
             read_and_verify,
+            read_and_verify_in_dir,
             read_session_info,
             read_verify_session_info,
+            read_verify_session_info_in_dir,
             write_json_object_to_file_as_line,
             write_to_json_file,
         },
+        MultisigSpendStep1SessionInfo,
+        MultisigSpendStep2OutputsForLeader,
+        MultisigSpendStep2OutputsForSelf,
+        MultisigSpendStep3OutputsForParties,
+        MultisigSpendStep3OutputsForSelf,
+        MultisigSpendStep4OutputsForLeader,
         PreMineSpendStep1SessionInfo,
         PreMineSpendStep2OutputsForLeader,
         PreMineSpendStep2OutputsForSelf,
@@ -143,7 +161,7 @@ use crate::{
         Step4OutputsForLeader,
     },
     cli::{CliCommands, CliRecipientInfo, MakeItRainTransactionType},
-    init::init_wallet,
+    init::{get_or_prompt_backup_passphrase, init_wallet, ViewKeyFile},
     recovery::{get_seed_from_seed_words, wallet_recovery},
     utils::db::{get_custom_base_node_peer_from_db, CUSTOM_BASE_NODE_ADDRESS_KEY, CUSTOM_BASE_NODE_PUBLIC_KEY_KEY},
     wallet_modes::PeerConfig,
@@ -167,6 +185,7 @@ pub async fn send_tari(
     mut wallet_transaction_service: TransactionServiceHandle,
     fee_per_gram: u64,
     amount: MicroMinotari,
+    selection_criteria: UtxoSelectionCriteria,
     destination: TariAddress,
     payment_id: PaymentId,
 ) -> Result<TxId, CommandError> {
@@ -174,7 +193,7 @@ pub async fn send_tari(
         .send_transaction(
             destination,
             amount,
-            UtxoSelectionCriteria::default(),
+            selection_criteria,
             OutputFeatures::default(),
             fee_per_gram * uT,
             payment_id,
@@ -555,7 +574,15 @@ pub async fn make_it_rain(
                     // Send transaction
                     let tx_id = match transaction_type {
                         MakeItRainTransactionType::Interactive => {
-                            send_tari(tx_service, fee, amount, address.clone(), payment_id_clone).await
+                            send_tari(
+                                tx_service,
+                                fee,
+                                amount,
+                                UtxoSelectionCriteria::default(),
+                                address.clone(),
+                                payment_id_clone,
+                            )
+                            .await
                         },
                         MakeItRainTransactionType::StealthOneSided => {
                             send_one_sided_to_stealth_address(
@@ -735,7 +762,7 @@ pub async fn monitor_transactions(
 pub async fn command_runner(
     config: &WalletConfig,
     commands: Vec<CliCommands>,
-    wallet: WalletSqlite,
+    mut wallet: WalletSqlite,
 ) -> Result<bool, CommandError> {
     let wait_stage = config.command_send_wait_stage;
 
@@ -1987,6 +2014,9 @@ pub async fn command_runner(
                     transaction_service.clone(),
                     config.fee_per_gram,
                     args.amount,
+                    UtxoSelectionCriteria::default()
+                        .with_account(args.account)
+                        .with_label(args.input_label),
                     args.destination,
                     PaymentId::open_from_str(&args.payment_id),
                 )
@@ -1999,12 +2029,63 @@ pub async fn command_runner(
                     Err(e) => eprintln!("SendMinotari error! {}", e),
                 }
             },
+            SendBatch(args) => {
+                let recipients = match read_batch_recipients_from_csv_file(&args.input_file) {
+                    Ok(recipients) => recipients,
+                    Err(e) => {
+                        eprintln!("SendBatch error! {}", e);
+                        continue;
+                    },
+                };
+                let selection_criteria = UtxoSelectionCriteria::default()
+                    .with_account(args.account)
+                    .with_label(args.input_label);
+                if args.estimate_only {
+                    let total = match BatchRecipient::total_amount(&recipients) {
+                        Some(total) => total,
+                        None => {
+                            eprintln!("SendBatch error! The total amount of the recipients overflows");
+                            continue;
+                        },
+                    };
+                    match output_service
+                        .fee_estimate(total, selection_criteria, config.fee_per_gram * uT, 1, recipients.len())
+                        .await
+                    {
+                        Ok(fee) => println!(
+                            "Paying {} to {} recipients costs an estimated fee of {}",
+                            total,
+                            recipients.len(),
+                            fee
+                        ),
+                        Err(e) => eprintln!("SendBatch error! {}", e),
+                    }
+                    continue;
+                }
+                match transaction_service
+                    .send_batch(
+                        recipients,
+                        selection_criteria,
+                        config.fee_per_gram * uT,
+                        PaymentId::open_from_str(&args.payment_id),
+                    )
+                    .await
+                {
+                    Ok(tx_id) => {
+                        debug!(target: LOG_TARGET, "send-batch concluded with tx_id {}", tx_id);
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("SendBatch error! {}", e),
+                }
+            },
             SendOneSidedToStealthAddress(args) => {
                 match send_one_sided_to_stealth_address(
                     transaction_service.clone(),
                     config.fee_per_gram,
                     args.amount,
-                    UtxoSelectionCriteria::default(),
+                    UtxoSelectionCriteria::default()
+                        .with_account(args.account)
+                        .with_label(args.input_label),
                     args.destination,
                     PaymentId::open_from_str(&args.payment_id),
                 )
@@ -2058,6 +2139,95 @@ pub async fn command_runner(
                     Err(e) => eprintln!("CoinSplit error! {}", e),
                 }
             },
+            BumpFee(args) => match transaction_service.bump_fee(args.tx_id.into(), args.fee_per_gram).await {
+                Ok(tx_id) => {
+                    tx_ids.push(tx_id);
+                    debug!(target: LOG_TARGET, "bump-fee concluded with tx_id {}", tx_id);
+                    println!("Transaction {} replaced by transaction {}", args.tx_id, tx_id);
+                },
+                Err(e) => eprintln!("BumpFee error! {}", e),
+            },
+            PrepareUnsignedTransaction(args) => match transaction_service
+                .prepare_unsigned_transaction(
+                    args.destination,
+                    args.amount,
+                    UtxoSelectionCriteria::default(),
+                    config.fee_per_gram * uT,
+                    PaymentId::open_from_str(&args.payment_id),
+                )
+                .await
+            {
+                Ok(unsigned_transaction) => {
+                    let tx_id = unsigned_transaction.tx_id;
+                    match write_to_json_file(&args.output_file, true, unsigned_transaction) {
+                        Ok(()) => println!(
+                            "Unsigned transaction {} written to '{}', sign it with the wallet holding the spend key",
+                            tx_id,
+                            args.output_file.display()
+                        ),
+                        Err(e) => eprintln!("PrepareUnsignedTransaction error! {}", e),
+                    }
+                },
+                Err(e) => eprintln!("PrepareUnsignedTransaction error! {}", e),
+            },
+            SignUnsignedTransaction(args) => {
+                let unsigned_transaction = match read_session_info::<UnsignedTransaction>(args.input_file.clone()) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        eprintln!("SignUnsignedTransaction error! {}", e);
+                        continue;
+                    },
+                };
+                match transaction_service
+                    .sign_unsigned_transaction(unsigned_transaction)
+                    .await
+                {
+                    Ok(signed_transaction) => {
+                        let tx_id = signed_transaction.tx_id;
+                        match write_to_json_file(&args.output_file, true, signed_transaction) {
+                            Ok(()) => println!(
+                                "Signed transaction {} written to '{}', finalise it with the wallet that prepared it",
+                                tx_id,
+                                args.output_file.display()
+                            ),
+                            Err(e) => eprintln!("SignUnsignedTransaction error! {}", e),
+                        }
+                    },
+                    Err(e) => eprintln!("SignUnsignedTransaction error! {}", e),
+                }
+            },
+            FinaliseSignedTransaction(args) => {
+                let signed_transaction = match read_session_info::<SignedTransaction>(args.input_file.clone()) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        eprintln!("FinaliseSignedTransaction error! {}", e);
+                        continue;
+                    },
+                };
+                match transaction_service
+                    .finalise_signed_transaction(signed_transaction)
+                    .await
+                {
+                    Ok(tx_id) => {
+                        tx_ids.push(tx_id);
+                        debug!(target: LOG_TARGET, "finalise-signed-transaction concluded with tx_id {}", tx_id);
+                        println!("Signed transaction {} finalised and broadcast", tx_id);
+                    },
+                    Err(e) => eprintln!("FinaliseSignedTransaction error! {}", e),
+                }
+            },
+            AbandonUnsignedTransaction(args) => {
+                match transaction_service
+                    .abandon_unsigned_transaction(args.tx_id.into())
+                    .await
+                {
+                    Ok(()) => println!(
+                        "Unsigned transaction {} abandoned, its inputs were released",
+                        args.tx_id
+                    ),
+                    Err(e) => eprintln!("AbandonUnsignedTransaction error! {}", e),
+                }
+            },
             Whois(args) => {
                 let public_key = args.public_key.into();
                 let emoji_id = EmojiId::from(&public_key).to_string();
@@ -2120,6 +2290,17 @@ pub async fn command_runner(
                 },
                 Err(e) => eprintln!("ExportTx error! {}", e),
             },
+            ExportTxHistory(args) => match wallet.export_transaction_history(args.format).await {
+                Ok(history) => {
+                    if let Some(file) = args.output_file {
+                        fs::write(&file, history)?;
+                        println!("Transaction history written to {}", file.display());
+                    } else {
+                        print!("{}", history);
+                    }
+                },
+                Err(e) => eprintln!("ExportTxHistory error! {}", e),
+            },
             ImportTx(args) => {
                 match load_tx_from_csv_file(args.input_file) {
                     Ok(txs) => {
@@ -2242,7 +2423,9 @@ pub async fn command_runner(
                     transaction_service.clone(),
                     config.fee_per_gram,
                     args.amount,
-                    UtxoSelectionCriteria::default(),
+                    UtxoSelectionCriteria::default()
+                        .with_account(args.account)
+                        .with_label(args.input_label),
                     args.destination,
                     PaymentId::open_from_str(&args.payment_id),
                 )
@@ -2300,131 +2483,1039 @@ pub async fn command_runner(
                 },
                 Err(e) => eprintln!("FinaliseShaAtomicSwap error! {}", e),
             },
-
-            RevalidateWalletDb => {
-                if let Err(e) = output_service
-                    .revalidate_all_outputs()
+            InitAdaptorSwap(args) => {
+                match transaction_service
+                    .send_adaptor_swap_transaction(
+                        args.destination,
+                        args.amount,
+                        args.adaptor_point.into(),
+                        args.refund_delay,
+                        UtxoSelectionCriteria::default(),
+                        config.fee_per_gram * uT,
+                        PaymentId::open_from_str(&args.payment_id),
+                    )
                     .await
-                    .map_err(CommandError::OutputManagerError)
                 {
-                    eprintln!("RevalidateWalletDb error! {}", e);
+                    Ok((tx_id, adaptor_signature, output)) => {
+                        debug!(target: LOG_TARGET, "minotari adaptor swap tx_id {}", tx_id);
+                        println!("Public nonce: {}", adaptor_signature.public_nonce().to_hex());
+                        println!("Pre-signature: {}", adaptor_signature.pre_signature().to_hex());
+                        println!("Output hash: {}", output.hash().to_hex());
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("InitAdaptorSwap error! {}", e),
                 }
-                if let Err(e) = transaction_service
-                    .revalidate_all_transactions()
+            },
+            ClaimAdaptorSwap(args) => {
+                let hash = match FixedHash::try_from(args.output_hash) {
+                    Ok(hash) => hash,
+                    Err(e) => {
+                        eprintln!("ClaimAdaptorSwap error! {}", e);
+                        continue;
+                    },
+                };
+                let (pre_signature, adaptor_secret) = match (
+                    PrivateKey::from_canonical_bytes(&args.pre_signature),
+                    PrivateKey::from_canonical_bytes(&args.adaptor_secret),
+                ) {
+                    (Ok(pre_signature), Ok(adaptor_secret)) => (pre_signature, adaptor_secret),
+                    (Err(e), _) | (_, Err(e)) => {
+                        eprintln!("ClaimAdaptorSwap error! {}", e);
+                        continue;
+                    },
+                };
+                match transaction_service
+                    .claim_adaptor_swap(
+                        hash,
+                        AdaptorSignature::new(args.public_nonce.into(), pre_signature),
+                        adaptor_secret,
+                        config.fee_per_gram.into(),
+                    )
                     .await
-                    .map_err(CommandError::TransactionServiceError)
                 {
-                    eprintln!("RevalidateWalletDb error! {}", e);
+                    Ok(tx_id) => {
+                        debug!(target: LOG_TARGET, "claiming minotari adaptor swap tx_id {}", tx_id);
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("ClaimAdaptorSwap error! {}", e),
                 }
             },
-            RegisterValidatorNode(args) => {
-                let tx_id = register_validator_node(
-                    args.amount,
-                    transaction_service.clone(),
-                    args.validator_node_public_key.into(),
-                    Signature::new(
-                        args.validator_node_public_nonce.into(),
-                        RistrettoSecretKey::from_vec(&args.validator_node_signature)?,
-                    ),
-                    UtxoSelectionCriteria::default(),
-                    config.fee_per_gram * uT,
-                    PaymentId::open_from_str(&args.payment_id),
-                )
-                .await?;
-                debug!(target: LOG_TARGET, "Registering VN tx_id {}", tx_id);
-                tx_ids.push(tx_id);
+            RevealAdaptorSwapSecret(args) => {
+                let hash = match FixedHash::try_from(args.output_hash) {
+                    Ok(hash) => hash,
+                    Err(e) => {
+                        eprintln!("RevealAdaptorSwapSecret error! {}", e);
+                        continue;
+                    },
+                };
+                let input_data = match ExecutionStack::from_bytes(&args.input_data) {
+                    Ok(input_data) => input_data,
+                    Err(e) => {
+                        eprintln!("RevealAdaptorSwapSecret error! {}", e);
+                        continue;
+                    },
+                };
+                match transaction_service.reveal_adaptor_swap_secret(hash, input_data).await {
+                    Ok(adaptor_secret) => println!("Adaptor secret: {}", adaptor_secret.to_hex()),
+                    Err(e) => eprintln!("RevealAdaptorSwapSecret error! {}", e),
+                }
             },
-            CreateTlsCerts => match generate_self_signed_certs() {
-                Ok((cacert, cert, private_key)) => {
-                    print_warning();
-
-                    write_cert_to_disk(config.config_dir.clone(), "wallet_ca.pem", &cacert)?;
-                    write_cert_to_disk(config.config_dir.clone(), "server.pem", &cert)?;
-                    write_cert_to_disk(config.config_dir.clone(), "server.key", &private_key)?;
-
-                    println!();
-                    println!("Certificates generated successfully.");
-                    println!(
-                        "To continue configuration move the `wallet_ca.pem` to the client service's \
-                         `application/config/` directory. Restart the base node with the configuration \
-                         grpc_tls_enabled=true"
-                    );
-                    println!();
+            RefundAdaptorSwap(args) => match FixedHash::try_from(args.output_hash) {
+                Ok(hash) => match transaction_service
+                    .refund_adaptor_swap(hash, config.fee_per_gram.into())
+                    .await
+                {
+                    Ok(tx_id) => {
+                        debug!(target: LOG_TARGET, "refunding minotari adaptor swap tx_id {}", tx_id);
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("RefundAdaptorSwap error! {}", e),
                 },
-                Err(err) => eprintln!("Error generating certificates: {}", err),
+                Err(e) => eprintln!("RefundAdaptorSwap error! {}", e),
             },
-            Sync(args) => {
-                let mut utxo_scanner = wallet.utxo_scanner_service.clone();
-                let mut receiver = utxo_scanner.get_event_receiver();
-
-                if !online {
-                    match wait_for_comms(&connectivity_requester).await {
-                        Ok(..) => {
-                            online = true;
-                        },
-                        Err(e) => {
-                            eprintln!("Sync error! {}", e);
-                            continue;
-                        },
+            ListAdaptorSwaps => match transaction_service.get_adaptor_swaps().await {
+                Ok(swaps) => {
+                    for swap in swaps {
+                        println!(
+                            "{} {} {} {} (refund height {})",
+                            swap.output_hash, swap.role, swap.status, swap.amount, swap.refund_height
+                        );
                     }
+                },
+                Err(e) => eprintln!("ListAdaptorSwaps error! {}", e),
+            },
+            MultisigAccountKey(args) => {
+                if let WalletType::Ledger(_) = *key_manager_service.get_wallet_type().await {
+                    eprintln!("\nError: Multi-signature accounts are not supported for 'Ledger' wallets!\n");
+                    break;
                 }
-
-                loop {
-                    match receiver.recv().await {
-                        Ok(event) => match event {
-                            UtxoScannerEvent::ConnectingToBaseNode(_) => {
-                                println!("Connecting to base node...");
-                            },
-                            UtxoScannerEvent::ConnectedToBaseNode(_, _) => {
-                                println!("Connected to base node");
-                            },
-                            UtxoScannerEvent::ConnectionFailedToBaseNode { .. } => {
-                                println!("Failed to connect to base node");
-                            },
-                            UtxoScannerEvent::ScanningRoundFailed {
-                                num_retries,
-                                retry_limit,
-                                error,
-                            } => {
-                                println!(
-                                    "Scanning round failed. Retries: {}/{}. Error: {}",
-                                    num_retries, retry_limit, error
-                                );
-                            },
-                            UtxoScannerEvent::Progress {
-                                current_height,
-                                tip_height,
-                            } => {
-                                println!("Progress: {}/{}", current_height, tip_height);
-                                if current_height >= args.sync_to_height && args.sync_to_height > 0 {
-                                    break;
-                                }
-                            },
-                            UtxoScannerEvent::Completed {
-                                final_height,
-                                num_recovered,
-                                value_recovered,
-                                time_taken,
-                            } => {
-                                println!(
-                                    "Completed! Height: {}, UTXOs recovered: {}, Value recovered: {}, Time taken: {}",
-                                    final_height,
-                                    num_recovered,
-                                    value_recovered,
-                                    time_taken.as_secs()
-                                );
-
-                                break;
-                            },
-                            UtxoScannerEvent::ScanningFailed => {
-                                println!("Scanning failed");
-                                break;
+                match output_service.get_multisig_account_key(args.name).await {
+                    Ok(key) => {
+                        println!("Multi-signature account key: {}", key.to_hex());
+                        println!("Share this key with the other parties to create the account");
+                    },
+                    Err(e) => eprintln!("MultisigAccountKey error! {}", e),
+                }
+            },
+            CreateMultisigAccount(args) => {
+                if let WalletType::Ledger(_) = *key_manager_service.get_wallet_type().await {
+                    eprintln!("\nError: Multi-signature accounts are not supported for 'Ledger' wallets!\n");
+                    break;
+                }
+                let public_keys = args.public_keys.into_iter().map(PublicKey::from).collect();
+                let view_key_share = match args.view_key_share.map(|v| PrivateKey::from_canonical_bytes(&v)) {
+                    Some(Ok(share)) => Some(share),
+                    Some(Err(e)) => {
+                        eprintln!("CreateMultisigAccount error! Invalid view key share: {}", e);
+                        continue;
+                    },
+                    None => None,
+                };
+                match output_service
+                    .create_multisig_account(args.name, args.threshold, public_keys, view_key_share)
+                    .await
+                {
+                    Ok((account, view_key_shares)) => {
+                        println!(
+                            "Created {}-of-{} multi-signature account '{}' with view key {}",
+                            account.threshold,
+                            account.public_keys.len(),
+                            account.name,
+                            account.view_public_key.to_hex()
+                        );
+                        for (public_key, share) in view_key_shares {
+                            println!("  view key share for {}: {}", public_key.to_hex(), share.to_hex());
+                        }
+                    },
+                    Err(e) => eprintln!("CreateMultisigAccount error! {}", e),
+                }
+            },
+            ListMultisigAccounts => match output_service.get_multisig_accounts().await {
+                Ok(accounts) => {
+                    for account in accounts {
+                        println!(
+                            "{} ({}-of-{}, view key {})",
+                            account.name,
+                            account.threshold,
+                            account.public_keys.len(),
+                            account.view_public_key.to_hex()
+                        );
+                        for key in &account.public_keys {
+                            println!("  key {}", key.to_hex());
+                        }
+                        let outputs = match output_service.validate_multisig_outputs(account.name.clone()).await {
+                            Ok(outputs) => outputs,
+                            Err(e) => {
+                                eprintln!("  Could not validate outputs with the base node: {}", e);
+                                output_service.get_multisig_outputs(account.name.clone()).await?
                             },
-                        },
-                        Err(e) => {
-                            eprintln!("Sync error! {}", e);
-                            break;
-                        },
+                        };
+                        for output in outputs.iter().filter(|v| !v.spent) {
+                            match output.spent_in_tx_id {
+                                Some(tx_id) => println!(
+                                    "  output {} {} (mined at height {}, being spent in transaction {})",
+                                    output.hash, output.value, output.mined_height, tx_id
+                                ),
+                                None => println!(
+                                    "  output {} {} (mined at height {})",
+                                    output.hash, output.value, output.mined_height
+                                ),
+                            }
+                        }
+                    }
+                },
+                Err(e) => eprintln!("ListMultisigAccounts error! {}", e),
+            },
+            SendToMultisigAccount(args) => {
+                let public_keys = args.public_keys.into_iter().map(PublicKey::from).collect();
+                match transaction_service
+                    .send_to_multisig_account(
+                        args.threshold,
+                        public_keys,
+                        args.view_key.into(),
+                        args.amount,
+                        UtxoSelectionCriteria::default(),
+                        config.fee_per_gram * uT,
+                        PaymentId::open_from_str(&args.payment_id),
+                    )
+                    .await
+                {
+                    Ok((tx_id, output_hash)) => {
+                        debug!(target: LOG_TARGET, "send-to-multisig-account concluded with tx_id {}", tx_id);
+                        println!("Multi-signature account output hash: {}", output_hash.to_hex());
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("SendToMultisigAccount error! {}", e),
+                }
+            },
+            MultisigSpendSessionInfo(args) => {
+                if let WalletType::Ledger(_) = *key_manager_service.get_wallet_type().await {
+                    eprintln!("\nError: Multi-signature accounts are not supported for 'Ledger' wallets!\n");
+                    break;
+                }
+
+                let output_hash = match FixedHash::try_from(args.output_hash) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        eprintln!("\nError: Invalid output hash! {}\n", e);
+                        break;
+                    },
+                };
+                let account = match output_service.get_multisig_accounts().await {
+                    Ok(accounts) => accounts.into_iter().find(|v| v.name == args.account_name),
+                    Err(e) => {
+                        eprintln!("\nError: {}\n", e);
+                        break;
+                    },
+                };
+                let account = match account {
+                    Some(v) => v,
+                    None => {
+                        eprintln!("\nError: Multi-signature account '{}' not found!\n", args.account_name);
+                        break;
+                    },
+                };
+                let output = match output_service.validate_multisig_outputs(account.name.clone()).await {
+                    Ok(outputs) => outputs.into_iter().find(|v| v.hash == output_hash && !v.spent),
+                    Err(e) => {
+                        eprintln!("\nError: Could not validate outputs with the base node! {}\n", e);
+                        break;
+                    },
+                };
+                let output = match output {
+                    Some(v) => v,
+                    None => {
+                        eprintln!(
+                            "\nError: '{}' is not an unspent output of multi-signature account '{}'!\n",
+                            output_hash, account.name
+                        );
+                        break;
+                    },
+                };
+                if let Some(tx_id) = output.spent_in_tx_id {
+                    println!(
+                        "Warning: Output '{}' is already being spent in transaction {}",
+                        output_hash, tx_id
+                    );
+                }
+
+                let (session_id, out_dir) = match create_multisig_output_dir() {
+                    Ok(values) => values,
+                    Err(e) => {
+                        eprintln!("\nError: {}\n", e);
+                        return Ok(false);
+                    },
+                };
+                let session_info = MultisigSpendStep1SessionInfo {
+                    session_id: session_id.clone(),
+                    threshold: account.threshold,
+                    public_keys: account.public_keys,
+                    output_hash,
+                    commitment: output.commitment,
+                    value: output.value,
+                    recipient_address: args.recipient_address,
+                    fee_per_gram: args.fee_per_gram,
+                };
+
+                let out_file = out_dir.join(get_file_name(SPEND_SESSION_INFO, None));
+                write_to_json_file(&out_file, true, session_info)?;
+                println!();
+                println!("Concluded step 1 'multisig-spend-session-info'");
+                println!("Your session ID is:                 '{}'", session_id);
+                println!("Your session's output directory is: '{}'", out_dir.display());
+                println!("Session info saved to:              '{}'", out_file.display());
+                println!(
+                    "Send '{}' to {} other signing parties for step 2",
+                    get_file_name(SPEND_SESSION_INFO, None),
+                    account.threshold - 1
+                );
+                println!();
+            },
+            MultisigSpendPartyDetails(args) => {
+                if let WalletType::Ledger(_) = *key_manager_service.get_wallet_type().await {
+                    eprintln!("\nError: Multi-signature accounts are not supported for 'Ledger' wallets!\n");
+                    break;
+                }
+
+                if args.alias.is_empty() || args.alias.contains(" ") {
+                    eprintln!("\nError: Alias cannot contain spaces!\n");
+                    break;
+                }
+                if args.alias.chars().any(|c| !c.is_alphanumeric() && c != '_') {
+                    eprintln!("\nError: Alias contains invalid characters! Only alphanumeric and '_' are allowed.\n");
+                    break;
+                }
+
+                // Read session info
+                let session_info = read_session_info::<MultisigSpendStep1SessionInfo>(args.input_file.clone())?;
+                // Find our account and verify that our UTXO scanner found the output being spent
+                let account = match output_service.get_multisig_accounts().await {
+                    Ok(accounts) => accounts
+                        .into_iter()
+                        .find(|v| v.threshold == session_info.threshold && v.public_keys == session_info.public_keys),
+                    Err(e) => {
+                        eprintln!("\nError: {}\n", e);
+                        break;
+                    },
+                };
+                let account = match account {
+                    Some(v) => v,
+                    None => {
+                        eprintln!("\nError: No multi-signature account in this wallet matches the session!\n");
+                        break;
+                    },
+                };
+                let outputs = output_service.get_multisig_outputs(account.name.clone()).await?;
+                if !outputs.iter().any(|v| {
+                    v.hash == session_info.output_hash &&
+                        v.commitment == session_info.commitment &&
+                        v.value == session_info.value &&
+                        !v.spent
+                }) {
+                    eprintln!(
+                        "\nError: Output '{}' is not a known unspent output of multi-signature account '{}'!\n",
+                        session_info.output_hash, account.name
+                    );
+                    break;
+                }
+
+                let script_nonce_key = key_manager_service.get_random_key().await?;
+                let sender_offset_key = key_manager_service.get_random_key().await?;
+                let sender_offset_nonce = key_manager_service.get_random_key().await?;
+                let shared_secret = key_manager_service
+                    .get_diffie_hellman_shared_secret(
+                        &sender_offset_key.key_id,
+                        session_info
+                            .recipient_address
+                            .public_view_key()
+                            .ok_or(CommandError::InvalidArgument("Missing public view key".to_string()))?,
+                    )
+                    .await?;
+                let shared_secret_public_key = PublicKey::from_canonical_bytes(shared_secret.as_bytes())?;
+                let public_script_key = key_manager_service
+                    .get_public_key_at_key_id(&account.script_key_id)
+                    .await?;
+                let script_input_signature = key_manager_service
+                    .sign_script_message(&account.script_key_id, session_info.commitment.as_bytes())
+                    .await?;
+
+                let out_dir = multisig_out_dir(&session_info.session_id)?;
+                let out_file_leader = out_dir.join(get_file_name(SPEND_STEP_2_LEADER, Some(args.alias.clone())));
+                write_json_object_to_file_as_line(&out_file_leader, true, session_info.clone())?;
+                write_json_object_to_file_as_line(&out_file_leader, false, MultisigSpendStep2OutputsForLeader {
+                    alias: args.alias.clone(),
+                    public_script_key,
+                    script_input_signature,
+                    public_script_nonce_key: script_nonce_key.pub_key,
+                    public_sender_offset_key: sender_offset_key.pub_key,
+                    public_sender_offset_nonce_key: sender_offset_nonce.pub_key,
+                    dh_shared_secret_public_key: shared_secret_public_key,
+                })?;
+
+                let out_file_self = out_dir.join(get_file_name(SPEND_STEP_2_SELF, None));
+                write_json_object_to_file_as_line(&out_file_self, true, session_info.clone())?;
+                write_json_object_to_file_as_line(&out_file_self, false, MultisigSpendStep2OutputsForSelf {
+                    alias: args.alias.clone(),
+                    script_key_id: account.script_key_id,
+                    script_nonce_key_id: script_nonce_key.key_id,
+                    sender_offset_key_id: sender_offset_key.key_id,
+                    sender_offset_nonce_key_id: sender_offset_nonce.key_id,
+                })?;
+
+                println!();
+                println!("Concluded step 2 'multisig-spend-party-details'");
+                println!("Your session's output directory is '{}'", out_dir.display());
+                move_session_file_to_dir(&out_dir, &args.input_file)?;
+                println!(
+                    "Send '{}' to leader for step 3",
+                    get_file_name(SPEND_STEP_2_LEADER, Some(args.alias))
+                );
+                println!();
+            },
+            MultisigSpendEncumberAggregateUtxo(args) => {
+                if let WalletType::Ledger(_) = *key_manager_service.get_wallet_type().await {
+                    eprintln!("\nError: Multi-signature accounts are not supported for 'Ledger' wallets!\n");
+                    break;
+                }
+
+                // Read session info
+                let out_dir = multisig_out_dir(&args.session_id)?;
+                let session_info =
+                    read_verify_session_info_in_dir::<MultisigSpendStep1SessionInfo>(&out_dir, &args.session_id)?;
+
+                // Read and verify party info
+                let mut party_info = Vec::with_capacity(args.input_file_names.len());
+                for file_name in args.input_file_names {
+                    party_info.push(read_and_verify_in_dir::<MultisigSpendStep2OutputsForLeader, _>(
+                        &out_dir,
+                        &args.session_id,
+                        &file_name,
+                        &session_info,
+                    )?);
+                }
+                // The leader signs with its own key share when encumbering
+                if party_info.len() + 1 != usize::from(session_info.threshold) {
+                    eprintln!(
+                        "\nError: A {}-of-{} account needs details from {} other parties, received {}!\n",
+                        session_info.threshold,
+                        session_info.public_keys.len(),
+                        session_info.threshold - 1,
+                        party_info.len()
+                    );
+                    break;
+                }
+
+                #[allow(clippy::mutable_key_type)]
+                let mut input_shares = HashMap::new();
+                let mut script_signature_public_nonces = Vec::with_capacity(party_info.len());
+                let mut sender_offset_public_key_shares = Vec::with_capacity(party_info.len());
+                let mut metadata_ephemeral_public_key_shares = Vec::with_capacity(party_info.len());
+                let mut dh_shared_secret_shares = Vec::with_capacity(party_info.len());
+                let mut error = false;
+                for party in &party_info {
+                    if !session_info.public_keys.contains(&party.public_script_key) {
+                        eprintln!(
+                            "\nError: '{}' is not a party of the multi-signature account!\n",
+                            party.alias
+                        );
+                        error = true;
+                        break;
+                    }
+                    input_shares.insert(party.public_script_key.clone(), party.script_input_signature.clone());
+                    script_signature_public_nonces.push(party.public_script_nonce_key.clone());
+                    sender_offset_public_key_shares.push(party.public_sender_offset_key.clone());
+                    metadata_ephemeral_public_key_shares.push(party.public_sender_offset_nonce_key.clone());
+                    dh_shared_secret_shares.push(party.dh_shared_secret_public_key.clone());
+                }
+                if error {
+                    break;
+                }
+
+                let (tx_id, outputs_for_parties) = match encumber_aggregate_utxo(
+                    transaction_service.clone(),
+                    session_info.fee_per_gram,
+                    session_info.commitment.clone(),
+                    input_shares,
+                    script_signature_public_nonces,
+                    sender_offset_public_key_shares,
+                    metadata_ephemeral_public_key_shares,
+                    dh_shared_secret_shares,
+                    session_info.recipient_address.clone(),
+                    0,
+                    UseOutput::FromBlockchain(session_info.output_hash),
+                    PaymentId::open_from_str(&args.payment_id),
+                )
+                .await
+                {
+                    Ok((
+                        tx_id,
+                        transaction,
+                        script_pubkey,
+                        total_metadata_ephemeral_public_key,
+                        total_script_nonce,
+                        shared_secret,
+                    )) => (tx_id, MultisigSpendStep3OutputsForParties {
+                        input_stack: transaction.body.inputs()[0].clone().input_data,
+                        input_script: transaction.body.inputs()[0].script().unwrap().clone(),
+                        total_script_key: script_pubkey,
+                        script_signature_ephemeral_commitment: transaction.body.inputs()[0]
+                            .script_signature
+                            .ephemeral_commitment()
+                            .clone(),
+                        script_signature_ephemeral_pubkey: total_script_nonce,
+                        output_commitment: transaction.body.outputs()[0].commitment().clone(),
+                        sender_offset_pubkey: transaction.body.outputs()[0].clone().sender_offset_public_key,
+                        metadata_signature_ephemeral_commitment: transaction.body.outputs()[0]
+                            .metadata_signature
+                            .ephemeral_commitment()
+                            .clone(),
+                        metadata_signature_ephemeral_pubkey: total_metadata_ephemeral_public_key,
+                        encrypted_data: transaction.body.outputs()[0].clone().encrypted_data,
+                        output_features: transaction.body.outputs()[0].clone().features,
+                        shared_secret,
+                    }),
+                    Err(e) => {
+                        eprintln!("\nError: Encumber aggregate transaction error! {}\n", e);
+                        break;
+                    },
+                };
+
+                let out_file = out_dir.join(get_file_name(SPEND_STEP_3_SELF, None));
+                write_json_object_to_file_as_line(&out_file, true, session_info.clone())?;
+                write_json_object_to_file_as_line(&out_file, false, MultisigSpendStep3OutputsForSelf { tx_id })?;
+
+                let out_file = out_dir.join(get_file_name(SPEND_STEP_3_PARTIES, None));
+                write_json_object_to_file_as_line(&out_file, true, session_info.clone())?;
+                write_json_object_to_file_as_line(&out_file, false, outputs_for_parties)?;
+
+                println!();
+                println!("Concluded step 3 'multisig-spend-encumber-aggregate-utxo'");
+                println!(
+                    "Send '{}' to parties for step 4",
+                    get_file_name(SPEND_STEP_3_PARTIES, None)
+                );
+                println!();
+            },
+            MultisigSpendInputOutputSigs(args) => {
+                if let WalletType::Ledger(_) = *key_manager_service.get_wallet_type().await {
+                    eprintln!("\nError: Multi-signature accounts are not supported for 'Ledger' wallets!\n");
+                    break;
+                }
+
+                // Read session info
+                let out_dir = multisig_out_dir(&args.session_id)?;
+                let session_info =
+                    read_verify_session_info_in_dir::<MultisigSpendStep1SessionInfo>(&out_dir, &args.session_id)?;
+                // Read leader input
+                let leader_info = read_and_verify_in_dir::<MultisigSpendStep3OutputsForParties, _>(
+                    &out_dir,
+                    &args.session_id,
+                    &get_file_name(SPEND_STEP_3_PARTIES, None),
+                    &session_info,
+                )?;
+                // Read own party info
+                let party_info = read_and_verify_in_dir::<MultisigSpendStep2OutputsForSelf, _>(
+                    &out_dir,
+                    &args.session_id,
+                    &get_file_name(SPEND_STEP_2_SELF, None),
+                    &session_info,
+                )?;
+
+                // Script signature
+                let challenge = TransactionInput::build_script_signature_challenge(
+                    &TransactionInputVersion::get_current_version(),
+                    &leader_info.script_signature_ephemeral_commitment,
+                    &leader_info.script_signature_ephemeral_pubkey,
+                    &leader_info.input_script,
+                    &leader_info.input_stack,
+                    &leader_info.total_script_key,
+                    &session_info.commitment,
+                );
+                let script_signature = match key_manager_service
+                    .sign_with_nonce_and_challenge(
+                        &party_info.script_key_id,
+                        &party_info.script_nonce_key_id,
+                        &challenge,
+                    )
+                    .await
+                {
+                    Ok(signature) => signature,
+                    Err(e) => {
+                        eprintln!("\nError: Script signature SignMessage error! {}\n", e);
+                        break;
+                    },
+                };
+
+                // Verify that the output pays the session's recipient
+                let shared_secret = match DiffieHellmanSharedSecret::<PublicKey>::from_canonical_bytes(
+                    leader_info.shared_secret.as_bytes(),
+                ) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        eprintln!("\nError: Could not create shared secret from canonical bytes! {}\n", e);
+                        break;
+                    },
+                };
+                let encryption_key = shared_secret_to_output_encryption_key(&shared_secret)?;
+                let (committed_value, commitment_mask_private_key, _payment_id) = match EncryptedData::decrypt_data(
+                    &encryption_key,
+                    &leader_info.output_commitment,
+                    &leader_info.encrypted_data,
+                ) {
+                    Ok((value, mask, id)) => (value, mask, id),
+                    Err(e) => {
+                        eprintln!("\nError: Could not decrypt data! {}\n", e);
+                        break;
+                    },
+                };
+                if committed_value >= session_info.value {
+                    eprintln!(
+                        "\nError: Output value {} does not leave a fee from the spent value {}!\n",
+                        committed_value, session_info.value
+                    );
+                    break;
+                }
+                let commitment_mask_key_id = &key_manager_service
+                    .import_key(commitment_mask_private_key.clone())
+                    .await?;
+                if let Err(e) = key_manager_service
+                    .verify_mask(
+                        &leader_info.output_commitment,
+                        commitment_mask_key_id,
+                        committed_value.as_u64(),
+                    )
+                    .await
+                {
+                    eprintln!("\nError: Could not verify mask! {}\n", e);
+                    break;
+                }
+                let script_spending_key = key_manager_service
+                    .stealth_address_script_spending_key(
+                        commitment_mask_key_id,
+                        session_info.recipient_address.public_spend_key(),
+                    )
+                    .await?;
+                let script = push_pubkey_script(&script_spending_key);
+
+                // Metadata signature
+                let script_offset = key_manager_service
+                    .get_script_offset(&vec![party_info.script_key_id.clone()], &vec![party_info
+                        .sender_offset_key_id
+                        .clone()])
+                    .await?;
+                let challenge = TransactionOutput::build_metadata_signature_challenge(
+                    &TransactionOutputVersion::get_current_version(),
+                    &script,
+                    &leader_info.output_features,
+                    &leader_info.sender_offset_pubkey,
+                    &leader_info.metadata_signature_ephemeral_commitment,
+                    &leader_info.metadata_signature_ephemeral_pubkey,
+                    &leader_info.output_commitment,
+                    &Covenant::default(),
+                    &leader_info.encrypted_data,
+                    MicroMinotari::zero(),
+                );
+                let metadata_signature = match key_manager_service
+                    .sign_with_nonce_and_challenge(
+                        &party_info.sender_offset_key_id,
+                        &party_info.sender_offset_nonce_key_id,
+                        &challenge,
+                    )
+                    .await
+                {
+                    Ok(signature) => signature,
+                    Err(e) => {
+                        eprintln!("\nError: Metadata signature SignMessage error! {}\n", e);
+                        break;
+                    },
+                };
+
+                if script_signature.get_signature() == Signature::default().get_signature() ||
+                    metadata_signature.get_signature() == Signature::default().get_signature()
+                {
+                    eprintln!("\nError: Script and/or metadata signatures not created!\n");
+                    break;
+                }
+
+                let out_file = out_dir.join(get_file_name(SPEND_STEP_4_LEADER, Some(party_info.alias.clone())));
+                write_json_object_to_file_as_line(&out_file, true, session_info.clone())?;
+                write_json_object_to_file_as_line(&out_file, false, MultisigSpendStep4OutputsForLeader {
+                    alias: party_info.alias.clone(),
+                    script_signature,
+                    metadata_signature,
+                    script_offset,
+                })?;
+
+                println!();
+                println!("Concluded step 4 'multisig-spend-input-output-sigs'");
+                println!(
+                    "Send '{}' to leader for step 5",
+                    get_file_name(SPEND_STEP_4_LEADER, Some(party_info.alias))
+                );
+                println!();
+            },
+            MultisigSpendAggregateTransaction(args) => {
+                if let WalletType::Ledger(_) = *key_manager_service.get_wallet_type().await {
+                    eprintln!("\nError: Multi-signature accounts are not supported for 'Ledger' wallets!\n");
+                    break;
+                }
+
+                // Read session info
+                let out_dir = multisig_out_dir(&args.session_id)?;
+                let session_info =
+                    read_verify_session_info_in_dir::<MultisigSpendStep1SessionInfo>(&out_dir, &args.session_id)?;
+
+                // Read other parties info
+                let mut party_info = Vec::with_capacity(args.input_file_names.len());
+                for file_name in args.input_file_names {
+                    party_info.push(read_and_verify_in_dir::<MultisigSpendStep4OutputsForLeader, _>(
+                        &out_dir,
+                        &args.session_id,
+                        &file_name,
+                        &session_info,
+                    )?);
+                }
+                // Read own party info
+                let leader_info = read_and_verify_in_dir::<MultisigSpendStep3OutputsForSelf, _>(
+                    &out_dir,
+                    &args.session_id,
+                    &get_file_name(SPEND_STEP_3_SELF, None),
+                    &session_info,
+                )?;
+
+                let mut metadata_signatures = Vec::with_capacity(party_info.len());
+                let mut script_signatures = Vec::with_capacity(party_info.len());
+                let mut offset = PrivateKey::default();
+                for party in &party_info {
+                    metadata_signatures.push(party.metadata_signature.clone());
+                    script_signatures.push(party.script_signature.clone());
+                    offset = &offset + &party.script_offset;
+                }
+
+                match finalise_aggregate_utxo(
+                    transaction_service.clone(),
+                    leader_info.tx_id.as_u64(),
+                    metadata_signatures,
+                    script_signatures,
+                    offset,
+                )
+                .await
+                {
+                    Ok(tx_id) => {
+                        println!();
+                        println!("Concluded step 5 'multisig-spend-aggregate-transaction'");
+                        println!("Transaction ID: {}", tx_id);
+                        println!();
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => {
+                        eprintln!("\nError: Error completing transaction! {}\n", e);
+                        break;
+                    },
+                }
+            },
+            CreateWalletAccount(args) => match output_service.create_wallet_account(args.name).await {
+                Ok((account, address)) => {
+                    println!("Created wallet account '{}'", account.name);
+                    println!("Address: {}", address.to_base58());
+                },
+                Err(e) => eprintln!("CreateWalletAccount error! {}", e),
+            },
+            ListWalletAccounts => match output_service.get_wallet_accounts().await {
+                Ok(accounts) => {
+                    if accounts.is_empty() {
+                        println!("This wallet has no named accounts");
+                    }
+                    for (account, address) in accounts {
+                        println!("{}", account.name);
+                        println!("  address {}", address.to_base58());
+                        match output_service.get_wallet_account_balance(account.name.clone()).await {
+                            Ok(balance) => println!("  {}", balance),
+                            Err(e) => eprintln!("  Could not get the account balance: {}", e),
+                        }
+                    }
+                },
+                Err(e) => eprintln!("ListWalletAccounts error! {}", e),
+            },
+            ListWalletAccountTransactions(args) => {
+                let transactions = match transaction_service.get_wallet_account_transactions(args.account).await {
+                    Ok(transactions) => transactions,
+                    Err(e) => {
+                        eprintln!("ListWalletAccountTransactions error! {}", e);
+                        continue;
+                    },
+                };
+                let labels = match transaction_service.get_transaction_labels().await {
+                    Ok(labels) => labels,
+                    Err(e) => {
+                        eprintln!("ListWalletAccountTransactions error! {}", e);
+                        continue;
+                    },
+                };
+                let mut transactions = transactions.into_values().collect::<Vec<_>>();
+                transactions.sort_by_key(|tx| tx.timestamp);
+                for tx in transactions {
+                    println!(
+                        "{} {} {} {} (fee {}) {} {}",
+                        tx.timestamp.format("%Y-%m-%d %H:%M:%S"),
+                        tx.tx_id,
+                        tx.direction,
+                        tx.amount,
+                        tx.fee,
+                        tx.status,
+                        labels.get(&tx.tx_id).map(String::as_str).unwrap_or_default()
+                    );
+                }
+            },
+            TransferBetweenWalletAccounts(args) => {
+                match transaction_service
+                    .transfer_between_wallet_accounts(
+                        args.from,
+                        args.to,
+                        args.amount,
+                        config.fee_per_gram * uT,
+                        PaymentId::open_from_str(&args.payment_id),
+                    )
+                    .await
+                {
+                    Ok(tx_id) => {
+                        debug!(
+                            target: LOG_TARGET,
+                            "transfer-between-wallet-accounts concluded with tx_id {}", tx_id
+                        );
+                        tx_ids.push(tx_id);
+                    },
+                    Err(e) => eprintln!("TransferBetweenWalletAccounts error! {}", e),
+                }
+            },
+            ListOutputs(args) => match output_service.get_unspent_outputs().await {
+                Ok(outputs) => {
+                    let outputs = outputs
+                        .into_iter()
+                        .filter(|o| args.label.is_none() || o.label == args.label)
+                        .collect::<Vec<_>>();
+                    for output in &outputs {
+                        println!(
+                            "{} {} {}{}",
+                            output.commitment.to_hex(),
+                            output.wallet_output.value,
+                            output.label.as_deref().unwrap_or("<unlabelled>"),
+                            if output.frozen { " (frozen)" } else { "" }
+                        );
+                    }
+                    let sum: MicroMinotari = outputs.iter().map(|o| o.wallet_output.value).sum();
+                    println!("Total number of UTXOs: {}", outputs.len());
+                    println!("Total value of UTXOs: {}", sum);
+                },
+                Err(e) => eprintln!("ListOutputs error! {}", e),
+            },
+            LabelOutputs(args) => match output_service.set_outputs_label(args.commitments, args.label).await {
+                Ok(_) => println!("Outputs labelled"),
+                Err(e) => eprintln!("LabelOutputs error! {}", e),
+            },
+            FreezeOutputs(args) => match output_service.set_outputs_frozen(args.commitments, true).await {
+                Ok(_) => println!("Outputs frozen"),
+                Err(e) => eprintln!("FreezeOutputs error! {}", e),
+            },
+            UnfreezeOutputs(args) => match output_service.set_outputs_frozen(args.commitments, false).await {
+                Ok(_) => println!("Outputs unfrozen"),
+                Err(e) => eprintln!("UnfreezeOutputs error! {}", e),
+            },
+            LabelTransaction(args) => {
+                match transaction_service
+                    .set_transaction_label(args.tx_id.into(), args.label)
+                    .await
+                {
+                    Ok(_) => println!("Transaction labelled"),
+                    Err(e) => eprintln!("LabelTransaction error! {}", e),
+                }
+            },
+            AddPaymentSchedule(args) => {
+                let schedule = PaymentSchedule::new(
+                    args.destination,
+                    args.amount,
+                    config.fee_per_gram * uT,
+                    args.interval,
+                    args.start,
+                )
+                .with_payment_id(PaymentId::open_from_str(&args.payment_id))
+                .with_max_fee(args.max_fee)
+                .with_end_time(args.end)
+                .with_max_missed_payments(args.max_missed_payments);
+                match transaction_service.add_payment_schedule(schedule).await {
+                    Ok(id) => println!("Payment schedule #{} added", id),
+                    Err(e) => eprintln!("AddPaymentSchedule error! {}", e),
+                }
+            },
+            ListPaymentSchedules => match transaction_service.get_payment_schedules().await {
+                Ok(schedules) => {
+                    if schedules.is_empty() {
+                        println!("This wallet has no payment schedules");
+                    }
+                    for schedule in schedules {
+                        println!(
+                            "#{} {} to {} every {}s, {}",
+                            schedule.id,
+                            schedule.amount,
+                            schedule.destination.to_base58(),
+                            schedule.interval.as_secs(),
+                            schedule.status
+                        );
+                        if schedule.status == PaymentScheduleStatus::Active {
+                            println!("  next payment at {}", schedule.next_payment);
+                        }
+                        if let Some(end_time) = schedule.end_time {
+                            println!("  ends at {}", end_time);
+                        }
+                        if let Some(max_fee) = schedule.max_fee {
+                            println!("  maximum fee {}", max_fee);
+                        }
+                        println!("  {} payments made", schedule.payments_made);
+                        if let Some(tx_id) = schedule.last_tx_id {
+                            println!("  last payment TxId {}", tx_id);
+                        }
+                        if let Some(error) = schedule.last_error {
+                            println!(
+                                "  last attempt failed: {} ({} failed attempts in a row)",
+                                error, schedule.failed_attempts
+                            );
+                        }
+                        if let Some(retry_at) = schedule.retry_at {
+                            println!("  retrying at {}", retry_at);
+                        }
+                    }
+                },
+                Err(e) => eprintln!("ListPaymentSchedules error! {}", e),
+            },
+            CancelPaymentSchedule(args) => match transaction_service.cancel_payment_schedule(args.id).await {
+                Ok(_) => println!("Payment schedule #{} cancelled", args.id),
+                Err(e) => eprintln!("CancelPaymentSchedule error! {}", e),
+            },
+
+            RevalidateWalletDb => {
+                if let Err(e) = output_service
+                    .revalidate_all_outputs()
+                    .await
+                    .map_err(CommandError::OutputManagerError)
+                {
+                    eprintln!("RevalidateWalletDb error! {}", e);
+                }
+                if let Err(e) = transaction_service
+                    .revalidate_all_transactions()
+                    .await
+                    .map_err(CommandError::TransactionServiceError)
+                {
+                    eprintln!("RevalidateWalletDb error! {}", e);
+                }
+            },
+            RegisterValidatorNode(args) => {
+                let tx_id = register_validator_node(
+                    args.amount,
+                    transaction_service.clone(),
+                    args.validator_node_public_key.into(),
+                    Signature::new(
+                        args.validator_node_public_nonce.into(),
+                        RistrettoSecretKey::from_vec(&args.validator_node_signature)?,
+                    ),
+                    UtxoSelectionCriteria::default(),
+                    config.fee_per_gram * uT,
+                    PaymentId::open_from_str(&args.payment_id),
+                )
+                .await?;
+                debug!(target: LOG_TARGET, "Registering VN tx_id {}", tx_id);
+                tx_ids.push(tx_id);
+            },
+            CreateTlsCerts => match generate_self_signed_certs() {
+                Ok((cacert, cert, private_key)) => {
+                    print_warning();
+
+                    write_cert_to_disk(config.config_dir.clone(), "wallet_ca.pem", &cacert)?;
+                    write_cert_to_disk(config.config_dir.clone(), "server.pem", &cert)?;
+                    write_cert_to_disk(config.config_dir.clone(), "server.key", &private_key)?;
+
+                    println!();
+                    println!("Certificates generated successfully.");
+                    println!(
+                        "To continue configuration move the `wallet_ca.pem` to the client service's \
+                         `application/config/` directory. Restart the base node with the configuration \
+                         grpc_tls_enabled=true"
+                    );
+                    println!();
+                },
+                Err(err) => eprintln!("Error generating certificates: {}", err),
+            },
+            Sync(args) => {
+                let mut utxo_scanner = wallet.utxo_scanner_service.clone();
+                let mut receiver = utxo_scanner.get_event_receiver();
+
+                if !online {
+                    match wait_for_comms(&connectivity_requester).await {
+                        Ok(..) => {
+                            online = true;
+                        },
+                        Err(e) => {
+                            eprintln!("Sync error! {}", e);
+                            continue;
+                        },
+                    }
+                }
+
+                loop {
+                    match receiver.recv().await {
+                        Ok(event) => match event {
+                            UtxoScannerEvent::ConnectingToBaseNode(_) => {
+                                println!("Connecting to base node...");
+                            },
+                            UtxoScannerEvent::ConnectedToBaseNode(_, _) => {
+                                println!("Connected to base node");
+                            },
+                            UtxoScannerEvent::ConnectionFailedToBaseNode { .. } => {
+                                println!("Failed to connect to base node");
+                            },
+                            UtxoScannerEvent::ScanningRoundFailed {
+                                num_retries,
+                                retry_limit,
+                                error,
+                            } => {
+                                println!(
+                                    "Scanning round failed. Retries: {}/{}. Error: {}",
+                                    num_retries, retry_limit, error
+                                );
+                            },
+                            UtxoScannerEvent::Progress {
+                                current_height,
+                                tip_height,
+                            } => {
+                                println!("Progress: {}/{}", current_height, tip_height);
+                                if current_height >= args.sync_to_height && args.sync_to_height > 0 {
+                                    break;
+                                }
+                            },
+                            UtxoScannerEvent::Completed {
+                                final_height,
+                                num_recovered,
+                                value_recovered,
+                                time_taken,
+                            } => {
+                                println!(
+                                    "Completed! Height: {}, UTXOs recovered: {}, Value recovered: {}, Time taken: {}",
+                                    final_height,
+                                    num_recovered,
+                                    value_recovered,
+                                    time_taken.as_secs()
+                                );
+
+                                break;
+                            },
+                            UtxoScannerEvent::ScanningFailed => {
+                                println!("Scanning failed");
+                                break;
+                            },
+                        },
+                        Err(e) => {
+                            eprintln!("Sync error! {}", e);
+                            break;
+                        },
                     }
                 }
                 println!("Starting validation process");
@@ -2441,6 +3532,8 @@ pub async fn command_runner(
                             OutputManagerEvent::TxoValidationAlreadyBusy(_) => {
                                 println!("Validation already busy");
                             },
+                            OutputManagerEvent::OutputsConsolidated { .. } |
+                            OutputManagerEvent::OutputConsolidationFailed(_) => {},
                             _ => {
                                 println!("Validation failed");
                                 break;
@@ -2467,12 +3560,6 @@ pub async fn command_runner(
                 let private_view_key_hex = wallet.key_manager_service.get_private_view_key().await?.to_hex();
                 let spend_key_hex = spend_key.pub_key.to_hex();
                 let output_file = args.output_file;
-                #[derive(Serialize)]
-                struct ViewKeyFile {
-                    view_key: String,
-                    public_view_key: String,
-                    spend_key: String,
-                }
                 let view_key_file = ViewKeyFile {
                     view_key: private_view_key_hex.clone(),
                     public_view_key: view_key_hex.clone(),
@@ -2489,6 +3576,66 @@ pub async fn command_runner(
                     println!("Spend key: {}", spend_key_hex);
                 }
             },
+            CreateBackup(args) => match get_or_prompt_backup_passphrase(args.passphrase) {
+                Ok(passphrase) => match wallet.create_backup(&passphrase).await {
+                    Ok(backup) => {
+                        fs::write(&args.output_file, backup)?;
+                        println!("Wallet backup written to {}", args.output_file.display());
+                    },
+                    Err(e) => eprintln!("CreateBackup error! {}", e),
+                },
+                Err(e) => eprintln!("CreateBackup error! {}", e),
+            },
+            AssembleScript(args) => {
+                let source = match args.input_file {
+                    Some(file) => fs::read_to_string(file)?,
+                    None => args.script,
+                };
+                match TariScript::from_str(&source) {
+                    Ok(script) => {
+                        println!("Script hex: {}", script.to_hex());
+                        println!();
+                        print!("{}", script.to_pretty_string());
+                        println!();
+                        println!("{}", script.analyze());
+                    },
+                    Err(e) => eprintln!("AssembleScript error! {}", e),
+                }
+            },
+            DisassembleScript(args) => match TariScript::from_hex(&args.script) {
+                Ok(script) => {
+                    print!("{}", script.to_pretty_string());
+                    println!();
+                    println!("{}", script.analyze());
+                },
+                Err(e) => eprintln!("DisassembleScript error! {}", e),
+            },
+            CompileCovenant(args) => {
+                let source = match args.input_file {
+                    Some(file) => fs::read_to_string(file)?,
+                    None => args.covenant,
+                };
+                match Covenant::from_str(&source) {
+                    Ok(covenant) => {
+                        println!("Covenant hex: {}", covenant.to_bytes().to_hex());
+                        match covenant.decompile() {
+                            Ok(text) => println!("{}", text),
+                            Err(e) => eprintln!("CompileCovenant error! {}", e),
+                        }
+                    },
+                    Err(e) => eprintln!("CompileCovenant error! {}", e),
+                }
+            },
+            DecompileCovenant(args) => {
+                let covenant = from_hex(&args.covenant)
+                    .map_err(|e| e.to_string())
+                    .and_then(|bytes| Covenant::from_bytes(&mut bytes.as_slice()).map_err(|e| e.to_string()))
+                    .and_then(|covenant| covenant.decompile().map_err(|e| e.to_string()));
+                match covenant {
+                    Ok(text) => println!("{}", text),
+                    Err(e) => eprintln!("DecompileCovenant error! {}", e),
+                }
+            },
             ImportPaperWallet(args) => {
                 let temp_path = config
                     .db_file
@@ -2602,6 +3749,8 @@ pub async fn command_runner(
                                 OutputManagerEvent::TxoValidationAlreadyBusy(_) => {
                                     println!("Validation already busy");
                                 },
+                                OutputManagerEvent::OutputsConsolidated { .. } |
+                                OutputManagerEvent::OutputConsolidationFailed(_) => {},
                                 _ => {
                                     println!("Validation failed");
                                     break;
@@ -2967,6 +4116,52 @@ fn load_tx_from_csv_file(file_path: PathBuf) -> Result<Vec<WalletTransaction>, C
     Ok(results)
 }
 
+/// Reads the recipients of a batch payment from a CSV file, one `address,amount[,payment_id]` record per line
+fn read_batch_recipients_from_csv_file(file_path: &Path) -> Result<Vec<BatchRecipient>, CommandError> {
+    let file = File::open(file_path).map_err(|e| CommandError::CSVFile(e.to_string()))?;
+    read_batch_recipients(file)
+}
+
+/// Reads batch payment recipients from CSV records of `address,amount[,payment_id]`, without a header. Empty lines
+/// and lines starting with `#` are ignored, and a payment id containing commas must be quoted.
+fn read_batch_recipients<R: io::Read>(reader: R) -> Result<Vec<BatchRecipient>, CommandError> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .comment(Some(b'#'))
+        .trim(csv::Trim::All)
+        .from_reader(reader);
+    let mut recipients = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(|e| CommandError::CSVFile(e.to_string()))?;
+        let line = record.position().map(|p| p.line()).unwrap_or_default();
+        if record.len() < 2 || record.len() > 3 {
+            return Err(CommandError::CSVFile(format!(
+                "Expected address,amount[,payment_id] on line {}",
+                line
+            )));
+        }
+        let address = TariAddress::from_str(&record[0])
+            .map_err(|_| CommandError::CSVFile(format!("Invalid address on line {}", line)))?;
+        let amount = MicroMinotari::from_str(&record[1])
+            .map_err(|_| CommandError::CSVFile(format!("Invalid amount on line {}", line)))?;
+        let payment_id = record
+            .get(2)
+            .filter(|p| !p.is_empty())
+            .map(PaymentId::open_from_str)
+            .unwrap_or(PaymentId::Empty);
+        recipients.push(BatchRecipient {
+            address,
+            amount,
+            payment_id,
+        });
+    }
+    if recipients.is_empty() {
+        return Err(CommandError::CSVFile("No recipients found".to_string()));
+    }
+    Ok(recipients)
+}
+
 #[allow(dead_code)]
 fn write_json_file<P: AsRef<Path>, T: Serialize>(path: P, data: &T) -> Result<(), CommandError> {
     fs::create_dir_all(path.as_ref().parent().unwrap()).map_err(|e| CommandError::JsonFile(e.to_string()))?;
@@ -2999,3 +4194,60 @@ async fn get_tip_height(wallet: &WalletSqlite) -> Option<u64> {
         None => None,
     }
 }
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_common::configuration::Network;
+    use tari_common_types::{
+        tari_address::TariAddress,
+        types::{PrivateKey, PublicKey},
+    };
+    use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::encrypted_data::PaymentId};
+    use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
+
+    use super::read_batch_recipients;
+
+    fn random_address() -> TariAddress {
+        TariAddress::new_dual_address_with_default_features(
+            PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+            PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+            Network::LocalNet,
+        )
+    }
+
+    #[test]
+    fn it_reads_batch_recipients() {
+        let first = random_address();
+        let second = random_address();
+        let csv = format!(
+            "# address,amount,payment_id\n{}, 1000\n\n{},2.5T,\"rent, March\"\n",
+            first.to_base58(),
+            second.to_base58()
+        );
+
+        let recipients = read_batch_recipients(csv.as_bytes()).unwrap();
+        assert_eq!(recipients.len(), 2);
+        assert_eq!(recipients[0].address, first);
+        assert_eq!(recipients[0].amount, MicroMinotari::from(1000));
+        assert_eq!(recipients[0].payment_id, PaymentId::Empty);
+        assert_eq!(recipients[1].address, second);
+        assert_eq!(recipients[1].amount, MicroMinotari::from(2_500_000));
+        assert_eq!(recipients[1].payment_id, PaymentId::open_from_str("rent, March"));
+    }
+
+    #[test]
+    fn it_rejects_invalid_batch_recipients() {
+        let address = random_address().to_base58();
+        for csv in [
+            "".to_string(),
+            "# no recipients\n".to_string(),
+            format!("{},not an amount\n", address),
+            format!("{}\n", address),
+            format!("{},1000,payment id,extra\n", address),
+            "not an address,1000\n".to_string(),
+        ] {
+            assert!(read_batch_recipients(csv.as_bytes()).is_err(), "{}", csv);
+        }
+    }
+}
@@ -30,7 +30,7 @@ use serde::{Deserialize, Serialize};
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::TxId,
-    types::{Commitment, PrivateKey, PublicKey, Signature},
+    types::{Commitment, FixedHash, PrivateKey, PublicKey, Signature},
 };
 use tari_core::transactions::{
     key_manager::TariKeyId,
@@ -146,6 +146,79 @@ struct Step4OutputsForLeader {
     script_offset: PrivateKey,
 }
 
+// Multi-signature account spend step 1 outputs for all with `MultisigSpendSessionInfo`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct MultisigSpendStep1SessionInfo {
+    session_id: String,
+    threshold: u8,
+    public_keys: Vec<PublicKey>,
+    output_hash: FixedHash,
+    commitment: Commitment,
+    value: MicroMinotari,
+    recipient_address: TariAddress,
+    fee_per_gram: MicroMinotari,
+}
+
+impl SessionId for MultisigSpendStep1SessionInfo {
+    fn session_id(&self) -> String {
+        self.session_id.clone()
+    }
+}
+
+// Multi-signature account spend step 2 outputs for self with `MultisigSpendPartyDetails`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct MultisigSpendStep2OutputsForSelf {
+    alias: String,
+    script_key_id: TariKeyId,
+    script_nonce_key_id: TariKeyId,
+    sender_offset_key_id: TariKeyId,
+    sender_offset_nonce_key_id: TariKeyId,
+}
+
+// Multi-signature account spend step 2 outputs for leader with `MultisigSpendPartyDetails`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct MultisigSpendStep2OutputsForLeader {
+    alias: String,
+    public_script_key: PublicKey,
+    script_input_signature: CheckSigSchnorrSignature,
+    public_script_nonce_key: PublicKey,
+    public_sender_offset_key: PublicKey,
+    public_sender_offset_nonce_key: PublicKey,
+    dh_shared_secret_public_key: PublicKey,
+}
+
+// Multi-signature account spend step 3 outputs for self with `MultisigSpendEncumberAggregateUtxo`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct MultisigSpendStep3OutputsForSelf {
+    tx_id: TxId,
+}
+
+// Multi-signature account spend step 3 outputs for parties with `MultisigSpendEncumberAggregateUtxo`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct MultisigSpendStep3OutputsForParties {
+    input_stack: ExecutionStack,
+    input_script: TariScript,
+    total_script_key: PublicKey,
+    script_signature_ephemeral_commitment: Commitment,
+    script_signature_ephemeral_pubkey: PublicKey,
+    output_commitment: Commitment,
+    sender_offset_pubkey: PublicKey,
+    metadata_signature_ephemeral_commitment: Commitment,
+    metadata_signature_ephemeral_pubkey: PublicKey,
+    encrypted_data: EncryptedData,
+    output_features: OutputFeatures,
+    shared_secret: PublicKey,
+}
+
+// Multi-signature account spend step 4 outputs for leader with `MultisigSpendInputOutputSigs`
+#[derive(Clone, Debug, Default, Serialize, Deserialize, PartialEq, Eq)]
+struct MultisigSpendStep4OutputsForLeader {
+    alias: String,
+    script_signature: Signature,
+    metadata_signature: Signature,
+    script_offset: PrivateKey,
+}
+
 trait SessionId {
     fn session_id(&self) -> String;
 }
@@ -153,13 +153,35 @@ pub(crate) fn out_dir(session_id: &str) -> Result<PathBuf, CommandError> {
     Ok(base_dir.join("tari_pre_mine").join("spend").join(session_id))
 }
 
+/// Create a unique session-based output directory for a multi-signature account spend
+pub(crate) fn create_multisig_output_dir() -> Result<(String, PathBuf), CommandError> {
+    let mut session_id = PrivateKey::random(&mut OsRng).to_monero_base58();
+    session_id.truncate(16);
+    let out_dir = multisig_out_dir(&session_id)?;
+    fs::create_dir_all(out_dir.clone())
+        .map_err(|e| CommandError::JsonFile(format!("{} ({})", e, out_dir.display())))?;
+    Ok((session_id, out_dir))
+}
+
+/// Return the output directory for the multi-signature account spend session
+pub(crate) fn multisig_out_dir(session_id: &str) -> Result<PathBuf, CommandError> {
+    let base_dir = dirs_next::document_dir().ok_or(CommandError::InvalidArgument(
+        "Could not find cache directory".to_string(),
+    ))?;
+    Ok(base_dir.join("tari_multisig").join("spend").join(session_id))
+}
+
 /// Move the session file to the session directory
 pub(crate) fn move_session_file_to_session_dir(session_id: &str, input_file: &PathBuf) -> Result<(), CommandError> {
-    let out_dir = out_dir(session_id)?;
+    move_session_file_to_dir(&out_dir(session_id)?, input_file)
+}
+
+/// Move the session file to the given session directory
+pub(crate) fn move_session_file_to_dir(out_dir: &Path, input_file: &Path) -> Result<(), CommandError> {
     let session_file = out_dir.join(get_file_name(SPEND_SESSION_INFO, None));
-    if input_file != &session_file {
-        fs::copy(input_file.clone(), session_file.clone())?;
-        fs::remove_file(input_file.clone())?;
+    if input_file != session_file {
+        fs::copy(input_file, &session_file)?;
+        fs::remove_file(input_file)?;
         println!(
             "Session info file '{}' moved to '{}'",
             input_file.display(),
@@ -171,7 +193,15 @@ pub(crate) fn move_session_file_to_session_dir(session_id: &str, input_file: &Pa
 
 /// Read the session info from the session directory and verify the supplied session ID
 pub(crate) fn read_verify_session_info<T: DeserializeOwned + SessionId>(session_id: &str) -> Result<T, CommandError> {
-    let file_path = out_dir(session_id)?.join(get_file_name(SPEND_SESSION_INFO, None));
+    read_verify_session_info_in_dir(&out_dir(session_id)?, session_id)
+}
+
+/// Read the session info from the given session directory and verify the supplied session ID
+pub(crate) fn read_verify_session_info_in_dir<T: DeserializeOwned + SessionId>(
+    out_dir: &Path,
+    session_id: &str,
+) -> Result<T, CommandError> {
+    let file_path = out_dir.join(get_file_name(SPEND_SESSION_INFO, None));
     let session_info = json_from_file_single_object::<_, T>(&file_path, None)?;
     if session_info.session_id() != session_id {
         return Err(CommandError::InvalidArgument(format!(
@@ -193,15 +223,24 @@ pub(crate) fn read_and_verify<T: DeserializeOwned>(
     file_name: &str,
     session_info: &PreMineSpendStep1SessionInfo,
 ) -> Result<T, CommandError> {
-    let out_dir = out_dir(session_id)?;
-    let header = json_from_file_single_object::<_, PreMineSpendStep1SessionInfo>(
+    read_and_verify_in_dir(&out_dir(session_id)?, session_id, file_name, session_info)
+}
+
+/// Read the inputs from the given session directory and verify the header
+pub(crate) fn read_and_verify_in_dir<T: DeserializeOwned, S: DeserializeOwned + PartialEq + SessionId>(
+    out_dir: &Path,
+    session_id: &str,
+    file_name: &str,
+    session_info: &S,
+) -> Result<T, CommandError> {
+    let header = json_from_file_single_object::<_, S>(
         &out_dir.join(file_name),
         Some(PartialRead {
             lines_to_read: 1,
             lines_to_skip: 0,
         }),
     )?;
-    if session_id != header.session_id {
+    if session_id != header.session_id() {
         return Err(CommandError::InvalidArgument(format!(
             "Session ID in header for file '{}' mismatch",
             file_name
@@ -30,8 +30,9 @@ use std::{
 use chrono::{DateTime, Utc};
 use clap::{Args, Parser, Subcommand};
 use minotari_app_utilities::{common_cli_args::CommonCliArgs, utilities::UniPublicKey};
+use minotari_wallet::transaction_service::history_export::TransactionHistoryFormat;
 use tari_common::configuration::{ConfigOverrideProvider, Network};
-use tari_common_types::tari_address::TariAddress;
+use tari_common_types::{tari_address::TariAddress, types::Commitment};
 use tari_comms::multiaddr::Multiaddr;
 use tari_core::transactions::{tari_amount, tari_amount::MicroMinotari};
 use tari_key_manager::SeedWords;
@@ -94,6 +95,16 @@ pub struct Cli {
     pub view_private_key: Option<String>,
     #[clap(long)]
     pub spend_key: Option<String>,
+    /// Create a watch-only wallet from the file written by `export-view-key-and-spend-key`
+    #[clap(long)]
+    pub view_key_file: Option<PathBuf>,
+    /// Restore the transaction history, contacts, labels and outputs from a file written by `create-backup`. The
+    /// wallet then only scans the chain from the height the backup was made at.
+    #[clap(long)]
+    pub restore_backup: Option<PathBuf>,
+    /// The passphrase of the wallet backup. If not supplied, it is prompted for.
+    #[clap(long, env = "MINOTARI_WALLET_BACKUP_PASSPHRASE", hide_env_values = true)]
+    pub backup_passphrase: Option<SafePassword>,
 }
 
 impl ConfigOverrideProvider for Cli {
@@ -143,12 +154,19 @@ pub enum CliCommands {
     PreMineSpendAggregateTransaction(PreMineSpendAggregateTransactionArgs),
     PreMineSpendBackupUtxo(PreMineSpendBackupUtxoArgs),
     SendOneSidedToStealthAddress(SendMinotariArgs),
+    SendBatch(SendBatchArgs),
     MakeItRain(MakeItRainArgs),
     CoinSplit(CoinSplitArgs),
+    BumpFee(BumpFeeArgs),
+    PrepareUnsignedTransaction(PrepareUnsignedTransactionArgs),
+    SignUnsignedTransaction(SignUnsignedTransactionArgs),
+    FinaliseSignedTransaction(FinaliseSignedTransactionArgs),
+    AbandonUnsignedTransaction(AbandonUnsignedTransactionArgs),
     DiscoverPeer(DiscoverPeerArgs),
     Whois(WhoisArgs),
     ExportUtxos(ExportUtxosArgs),
     ExportTx(ExportTxArgs),
+    ExportTxHistory(ExportTxHistoryArgs),
     ImportTx(ImportTxArgs),
     ExportSpentUtxos(ExportUtxosArgs),
     CountUtxos,
@@ -158,12 +176,43 @@ pub enum CliCommands {
     InitShaAtomicSwap(SendMinotariArgs),
     FinaliseShaAtomicSwap(FinaliseShaAtomicSwapArgs),
     ClaimShaAtomicSwapRefund(ClaimShaAtomicSwapRefundArgs),
+    InitAdaptorSwap(InitAdaptorSwapArgs),
+    ClaimAdaptorSwap(ClaimAdaptorSwapArgs),
+    RevealAdaptorSwapSecret(RevealAdaptorSwapSecretArgs),
+    RefundAdaptorSwap(RefundAdaptorSwapArgs),
+    ListAdaptorSwaps,
+    MultisigAccountKey(MultisigAccountKeyArgs),
+    CreateMultisigAccount(CreateMultisigAccountArgs),
+    ListMultisigAccounts,
+    SendToMultisigAccount(SendToMultisigAccountArgs),
+    MultisigSpendSessionInfo(MultisigSpendSessionInfoArgs),
+    MultisigSpendPartyDetails(MultisigSpendPartyDetailsArgs),
+    MultisigSpendEncumberAggregateUtxo(MultisigSpendEncumberAggregateUtxoArgs),
+    MultisigSpendInputOutputSigs(MultisigSpendInputOutputSigsArgs),
+    MultisigSpendAggregateTransaction(MultisigSpendAggregateTransactionArgs),
+    CreateWalletAccount(CreateWalletAccountArgs),
+    ListWalletAccounts,
+    ListWalletAccountTransactions(ListWalletAccountTransactionsArgs),
+    TransferBetweenWalletAccounts(TransferBetweenWalletAccountsArgs),
+    ListOutputs(ListOutputsArgs),
+    LabelOutputs(LabelOutputsArgs),
+    FreezeOutputs(FreezeOutputsArgs),
+    UnfreezeOutputs(FreezeOutputsArgs),
+    LabelTransaction(LabelTransactionArgs),
+    AddPaymentSchedule(AddPaymentScheduleArgs),
+    ListPaymentSchedules,
+    CancelPaymentSchedule(CancelPaymentScheduleArgs),
     RevalidateWalletDb,
     RegisterValidatorNode(RegisterValidatorNodeArgs),
     CreateTlsCerts,
     Sync(SyncArgs),
     ExportViewKeyAndSpendKey(ExportViewKeyAndSpendKeyArgs),
+    CreateBackup(CreateBackupArgs),
     ImportPaperWallet(ImportPaperWalletArgs),
+    AssembleScript(AssembleScriptArgs),
+    DisassembleScript(DisassembleScriptArgs),
+    CompileCovenant(CompileCovenantArgs),
+    DecompileCovenant(DecompileCovenantArgs),
 }
 
 #[derive(Debug, Args, Clone)]
@@ -177,6 +226,30 @@ pub struct SendMinotariArgs {
     pub destination: TariAddress,
     #[clap(short, long, default_value = "<No message>")]
     pub payment_id: String,
+    /// The wallet account to spend from, instead of the default account
+    #[clap(long)]
+    pub account: Option<String>,
+    /// Only spend outputs carrying this label
+    #[clap(long)]
+    pub input_label: Option<String>,
+}
+
+/// Pays all recipients listed in a CSV file in a single one-sided transaction. Each line of the file holds
+/// `address,amount[,payment_id]`; empty lines and lines starting with `#` are ignored.
+#[derive(Debug, Args, Clone)]
+pub struct SendBatchArgs {
+    pub input_file: PathBuf,
+    #[clap(short, long, default_value = "Batch payment")]
+    pub payment_id: String,
+    /// The wallet account to spend from, instead of the default account
+    #[clap(long)]
+    pub account: Option<String>,
+    /// Only spend outputs carrying this label
+    #[clap(long)]
+    pub input_label: Option<String>,
+    /// Only print the estimated fee of the transaction, without sending it
+    #[clap(long)]
+    pub estimate_only: bool,
 }
 
 #[derive(Debug, Args, Clone)]
@@ -368,6 +441,45 @@ pub struct CoinSplitArgs {
     pub payment_id: String,
 }
 
+/// Replaces an unmined one-sided or self-spend transaction with one paying a higher fee
+#[derive(Debug, Args, Clone)]
+pub struct BumpFeeArgs {
+    pub tx_id: u64,
+    #[clap(short, long)]
+    pub fee_per_gram: MicroMinotari,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct PrepareUnsignedTransactionArgs {
+    pub amount: MicroMinotari,
+    pub destination: TariAddress,
+    #[clap(short, long, default_value = "<No message>")]
+    pub payment_id: String,
+    /// The file the unsigned transaction is written to, to be signed by the wallet holding the spend key
+    #[clap(short, long)]
+    pub output_file: PathBuf,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct SignUnsignedTransactionArgs {
+    #[clap(short, long)]
+    pub input_file: PathBuf,
+    /// The file the signed transaction is written to, to be finalised by the wallet that prepared it
+    #[clap(short, long)]
+    pub output_file: PathBuf,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct FinaliseSignedTransactionArgs {
+    #[clap(short, long)]
+    pub input_file: PathBuf,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct AbandonUnsignedTransactionArgs {
+    pub tx_id: u64,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct WhoisArgs {
     pub public_key: UniPublicKey,
@@ -387,12 +499,63 @@ pub struct ExportTxArgs {
     pub output_file: Option<PathBuf>,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct ExportTxHistoryArgs {
+    /// The file to write the history to. If not supplied, it is printed.
+    #[clap(short, long)]
+    pub output_file: Option<PathBuf>,
+    /// Either `csv` or `json`
+    #[clap(short, long, default_value = "csv")]
+    pub format: TransactionHistoryFormat,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct ExportViewKeyAndSpendKeyArgs {
     #[clap(short, long)]
     pub output_file: Option<PathBuf>,
 }
 
+#[derive(Debug, Args, Clone)]
+pub struct CreateBackupArgs {
+    #[clap(short, long)]
+    pub output_file: PathBuf,
+    /// The passphrase to encrypt the backup with. If not supplied, it is prompted for.
+    #[clap(long, env = "MINOTARI_WALLET_BACKUP_PASSPHRASE", hide_env_values = true)]
+    pub passphrase: Option<SafePassword>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct AssembleScriptArgs {
+    /// The script in assembly syntax, e.g. "CheckHeightVerify(100) PushPubKey(<public key>)"
+    #[clap(default_value = "")]
+    pub script: String,
+    /// Read the script from this file instead
+    #[clap(short, long)]
+    pub input_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct DisassembleScriptArgs {
+    /// The hex encoded script
+    pub script: String,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct CompileCovenantArgs {
+    /// The covenant in the syntax of the covenant! macro, e.g. "absolute_height(@uint(42))"
+    #[clap(default_value = "")]
+    pub covenant: String,
+    /// Read the covenant from this file instead
+    #[clap(short, long)]
+    pub input_file: Option<PathBuf>,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct DecompileCovenantArgs {
+    /// The hex encoded covenant
+    pub covenant: String,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct ImportPaperWalletArgs {
     #[clap(short, long, default_value = "")]
@@ -449,6 +612,228 @@ pub struct ClaimShaAtomicSwapRefundArgs {
     pub payment_id: String,
 }
 
+/// Locks funds in an adaptor signature atomic swap with the destination
+#[derive(Debug, Args, Clone)]
+pub struct InitAdaptorSwapArgs {
+    pub amount: MicroMinotari,
+    pub destination: TariAddress,
+    /// The adaptor point `T = t·G`, where `t` is the secret the destination reveals by claiming the funds
+    pub adaptor_point: UniPublicKey,
+    /// The number of blocks after which the funds can be refunded if they have not been claimed
+    #[clap(long, default_value_t = 720)]
+    pub refund_delay: u64,
+    #[clap(short, long, default_value = "Adaptor signature atomic swap")]
+    pub payment_id: String,
+}
+
+/// Claims the funds of an adaptor signature atomic swap by completing the adaptor signature with the adaptor secret
+#[derive(Debug, Args, Clone)]
+pub struct ClaimAdaptorSwapArgs {
+    #[clap(short, long, parse(try_from_str = parse_hex))]
+    pub output_hash: Vec<u8>,
+    /// The public nonce of the adaptor signature
+    #[clap(long)]
+    pub public_nonce: UniPublicKey,
+    /// The hex encoded pre-signature of the adaptor signature
+    #[clap(long, parse(try_from_str = parse_hex))]
+    pub pre_signature: Vec<u8>,
+    /// The hex encoded discrete log of the adaptor point
+    #[clap(long, parse(try_from_str = parse_hex))]
+    pub adaptor_secret: Vec<u8>,
+}
+
+/// Extracts the adaptor secret of a swap this wallet initiated from the input that claimed it
+#[derive(Debug, Args, Clone)]
+pub struct RevealAdaptorSwapSecretArgs {
+    #[clap(short, long, parse(try_from_str = parse_hex))]
+    pub output_hash: Vec<u8>,
+    /// The hex encoded input data of the input that spent the swap output
+    #[clap(long, parse(try_from_str = parse_hex))]
+    pub input_data: Vec<u8>,
+}
+
+/// Refunds the funds of an adaptor signature atomic swap this wallet initiated, after the refund height
+#[derive(Debug, Args, Clone)]
+pub struct RefundAdaptorSwapArgs {
+    #[clap(short, long, parse(try_from_str = parse_hex))]
+    pub output_hash: Vec<u8>,
+}
+
+/// Creates a named account with its own address, derived from the wallet seed
+#[derive(Debug, Args, Clone)]
+pub struct CreateWalletAccountArgs {
+    pub name: String,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ListWalletAccountTransactionsArgs {
+    /// The wallet account to list the transactions of, instead of the default account
+    #[clap(long)]
+    pub account: Option<String>,
+}
+
+/// Sends funds from one wallet account to another
+#[derive(Debug, Args, Clone)]
+pub struct TransferBetweenWalletAccountsArgs {
+    pub amount: MicroMinotari,
+    /// The wallet account to spend from, instead of the default account
+    #[clap(long)]
+    pub from: Option<String>,
+    /// The wallet account to pay to, instead of the default account
+    #[clap(long)]
+    pub to: Option<String>,
+    #[clap(short, long, default_value = "<No message>")]
+    pub payment_id: String,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct ListOutputsArgs {
+    /// Only list the unspent outputs carrying this label
+    #[clap(long)]
+    pub label: Option<String>,
+}
+
+/// Labels outputs, e.g. to keep customer deposits apart from operating funds
+#[derive(Debug, Args, Clone)]
+pub struct LabelOutputsArgs {
+    #[clap(parse(try_from_str = parse_commitment), required = true)]
+    pub commitments: Vec<Commitment>,
+    /// The label to set. The label is cleared if this is omitted.
+    #[clap(long)]
+    pub label: Option<String>,
+}
+
+/// Freezes or unfreezes outputs. Frozen outputs are never selected as transaction inputs.
+#[derive(Debug, Args, Clone)]
+pub struct FreezeOutputsArgs {
+    #[clap(parse(try_from_str = parse_commitment), required = true)]
+    pub commitments: Vec<Commitment>,
+}
+
+fn parse_commitment(s: &str) -> Result<Commitment, CliParseError> {
+    Commitment::from_hex(s).map_err(|e| CliParseError::HexError(format!("{}", e)))
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct LabelTransactionArgs {
+    pub tx_id: u64,
+    /// The label to set. The label is cleared if this is omitted.
+    #[clap(long)]
+    pub label: Option<String>,
+}
+
+/// Adds a payment schedule, which the wallet pays with one-sided payments while it is running
+#[derive(Debug, Args, Clone)]
+pub struct AddPaymentScheduleArgs {
+    pub amount: MicroMinotari,
+    pub destination: TariAddress,
+    /// The number of seconds between payments, 0 for a single payment
+    #[clap(long, parse(try_from_str = parse_duration), default_value = "0")]
+    pub interval: Duration,
+    /// The time of the first payment, e.g. `2025-01-31T09:00:00Z`
+    #[clap(long, parse(try_from_str = parse_start_time), default_value = "now")]
+    pub start: DateTime<Utc>,
+    /// No payments are made after this time
+    #[clap(long, parse(try_from_str = parse_start_time))]
+    pub end: Option<DateTime<Utc>>,
+    /// A payment is held back while its estimated fee is higher than this
+    #[clap(long)]
+    pub max_fee: Option<MicroMinotari>,
+    /// The number of payments missed while the wallet was not running that are still made, older ones are skipped
+    #[clap(long, default_value = "0")]
+    pub max_missed_payments: u32,
+    #[clap(short, long, default_value = "<No message>")]
+    pub payment_id: String,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct CancelPaymentScheduleArgs {
+    pub id: u32,
+}
+
+/// Shows this wallet's public key for a multi-signature account, to share with the other parties
+#[derive(Debug, Args, Clone)]
+pub struct MultisigAccountKeyArgs {
+    /// The name this wallet will give the account
+    pub name: String,
+}
+
+/// Creates an N-of-M multi-signature account shared with other wallets. The coordinator, who holds the first public
+/// key, creates the account first and hands the other parties their view key shares.
+#[derive(Debug, Args, Clone)]
+pub struct CreateMultisigAccountArgs {
+    pub name: String,
+    /// The number of parties that must sign to spend from the account
+    #[clap(long)]
+    pub threshold: u8,
+    /// The public keys of all parties, in the same order for every party. One of them must be the key given out by
+    /// `multisig-account-key` for the account name.
+    #[clap(long)]
+    pub public_keys: Vec<UniPublicKey>,
+    /// This party's view key share, given out by the coordinator
+    #[clap(long, parse(try_from_str = parse_hex))]
+    pub view_key_share: Option<Vec<u8>>,
+}
+
+/// Sends funds to an N-of-M multi-signature account
+#[derive(Debug, Args, Clone)]
+pub struct SendToMultisigAccountArgs {
+    pub amount: MicroMinotari,
+    #[clap(long)]
+    pub threshold: u8,
+    #[clap(long)]
+    pub public_keys: Vec<UniPublicKey>,
+    /// The view key of the account
+    #[clap(long)]
+    pub view_key: UniPublicKey,
+    #[clap(short, long, default_value = "<No message>")]
+    pub payment_id: String,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct MultisigSpendSessionInfoArgs {
+    #[clap(long)]
+    pub account_name: String,
+    #[clap(long, parse(try_from_str = parse_hex))]
+    pub output_hash: Vec<u8>,
+    #[clap(long)]
+    pub recipient_address: TariAddress,
+    #[clap(long)]
+    pub fee_per_gram: MicroMinotari,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct MultisigSpendPartyDetailsArgs {
+    #[clap(long)]
+    pub input_file: PathBuf,
+    #[clap(long)]
+    pub alias: String,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct MultisigSpendEncumberAggregateUtxoArgs {
+    #[clap(long)]
+    pub session_id: String,
+    #[clap(long)]
+    pub input_file_names: Vec<String>,
+    #[clap(short, long, default_value = "Spend multi-signature account UTXO")]
+    pub payment_id: String,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct MultisigSpendInputOutputSigsArgs {
+    #[clap(long)]
+    pub session_id: String,
+}
+
+#[derive(Debug, Args, Clone)]
+pub struct MultisigSpendAggregateTransactionArgs {
+    #[clap(long)]
+    pub session_id: String,
+    #[clap(long)]
+    pub input_file_names: Vec<String>,
+}
+
 #[derive(Debug, Args, Clone)]
 pub struct RegisterValidatorNodeArgs {
     pub amount: MicroMinotari,
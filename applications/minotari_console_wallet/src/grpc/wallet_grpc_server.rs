@@ -23,8 +23,10 @@
 use std::{
     convert::{TryFrom, TryInto},
     str::FromStr,
+    time::Duration,
 };
 
+use chrono::{DateTime, Utc};
 use futures::{
     channel::mpsc::{self, Sender},
     future,
@@ -35,7 +37,11 @@ use minotari_app_grpc::tari_rpc::{
     self,
     payment_recipient::PaymentType,
     wallet_server,
+    AssembleScriptRequest,
+    AssembleScriptResponse,
     CheckConnectivityResponse,
+    ClaimAdaptorSwapRequest,
+    ClaimAdaptorSwapResponse,
     ClaimHtlcRefundRequest,
     ClaimHtlcRefundResponse,
     ClaimShaAtomicSwapRequest,
@@ -43,10 +49,19 @@ use minotari_app_grpc::tari_rpc::{
     CoinSplitRequest,
     CoinSplitResponse,
     CommitmentSignature,
+    CompileCovenantRequest,
+    CompileCovenantResponse,
+    CovenantFilterStep,
     CreateBurnTransactionRequest,
     CreateBurnTransactionResponse,
     CreateTemplateRegistrationRequest,
     CreateTemplateRegistrationResponse,
+    DecompileCovenantRequest,
+    DecompileCovenantResponse,
+    DisassembleScriptRequest,
+    DisassembleScriptResponse,
+    DryRunCovenantRequest,
+    DryRunCovenantResponse,
     GetAddressResponse,
     GetBalanceRequest,
     GetBalanceResponse,
@@ -62,10 +77,16 @@ use minotari_app_grpc::tari_rpc::{
     GetVersionResponse,
     ImportUtxosRequest,
     ImportUtxosResponse,
+    RefundAdaptorSwapRequest,
+    RefundAdaptorSwapResponse,
     RegisterValidatorNodeRequest,
     RegisterValidatorNodeResponse,
     RevalidateRequest,
     RevalidateResponse,
+    RevealAdaptorSwapSecretRequest,
+    RevealAdaptorSwapSecretResponse,
+    SendAdaptorSwapRequest,
+    SendAdaptorSwapResponse,
     SendShaAtomicSwapRequest,
     SendShaAtomicSwapResponse,
     SetBaseNodeRequest,
@@ -88,18 +109,20 @@ use minotari_wallet::{
     output_manager_service::{handle::OutputManagerHandle, UtxoSelectionCriteria},
     transaction_service::{
         handle::TransactionServiceHandle,
-        storage::models::{self, WalletTransaction},
+        history_export::TransactionHistoryFormat,
+        storage::models::{self, BatchRecipient, WalletTransaction},
     },
     WalletSqlite,
 };
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::TxId,
-    types::{BlockHash, PublicKey, Signature},
+    types::{BlockHash, Commitment, PrivateKey, PublicKey, Signature},
 };
 use tari_comms::{multiaddr::Multiaddr, types::CommsPublicKey, CommsNode};
 use tari_core::{
     consensus::{ConsensusBuilderError, ConsensusConstants, ConsensusManager},
+    covenants::Covenant,
     transactions::{
         tari_amount::{MicroMinotari, T},
         transaction_components::{
@@ -108,11 +131,14 @@ use tari_core::{
             OutputFeatures,
             OutputType,
             SideChainFeature,
+            TransactionInput,
+            TransactionOutput,
             UnblindedOutput,
         },
+        transaction_protocol::adaptor_signature::AdaptorSignature,
     },
 };
-use tari_script::script;
+use tari_script::{script, ExecutionStack, TariScript};
 use tari_utilities::{hex::Hex, ByteArray};
 use tokio::{sync::broadcast, task};
 use tonic::{Request, Response, Status};
@@ -123,6 +149,7 @@ use crate::{
 };
 
 const LOG_TARGET: &str = "wallet::ui::grpc";
+const DEFAULT_ADAPTOR_SWAP_REFUND_DELAY: u64 = 720;
 
 async fn send_transaction_event(
     transaction_event: TransactionEvent,
@@ -270,9 +297,15 @@ impl wallet_server::Wallet for WalletGrpcServer {
         Ok(Response::new(SetBaseNodeResponse {}))
     }
 
-    async fn get_balance(&self, _request: Request<GetBalanceRequest>) -> Result<Response<GetBalanceResponse>, Status> {
+    async fn get_balance(&self, request: Request<GetBalanceRequest>) -> Result<Response<GetBalanceResponse>, Status> {
+        let message = request.into_inner();
         let mut output_service = self.get_output_manager_service();
-        let balance = match output_service.get_balance().await {
+        let balance = if message.account.is_empty() {
+            output_service.get_balance().await
+        } else {
+            output_service.get_wallet_account_balance(message.account).await
+        };
+        let balance = match balance {
             Ok(b) => b,
             Err(e) => return Err(Status::not_found(format!("GetBalance error! {}", e))),
         };
@@ -503,6 +536,177 @@ impl wallet_server::Wallet for WalletGrpcServer {
         }))
     }
 
+    async fn send_adaptor_swap_transaction(
+        &self,
+        request: Request<SendAdaptorSwapRequest>,
+    ) -> Result<Response<SendAdaptorSwapResponse>, Status> {
+        let message = request.into_inner();
+        let recipient = message
+            .recipient
+            .ok_or_else(|| Status::internal("Request is malformed".to_string()))?;
+        let address = TariAddress::from_str(&recipient.address)
+            .map_err(|_| Status::internal("Destination address is malformed".to_string()))?;
+        let adaptor_point = PublicKey::from_canonical_bytes(&message.adaptor_point)
+            .map_err(|_| Status::invalid_argument("Adaptor point is malformed".to_string()))?;
+        let refund_delay = if message.refund_delay == 0 {
+            DEFAULT_ADAPTOR_SWAP_REFUND_DELAY
+        } else {
+            message.refund_delay
+        };
+
+        let mut transaction_service = self.get_transaction_service();
+        let response = match transaction_service
+            .send_adaptor_swap_transaction(
+                address.clone(),
+                recipient.amount.into(),
+                adaptor_point,
+                refund_delay,
+                UtxoSelectionCriteria::default(),
+                recipient.fee_per_gram.into(),
+                PaymentId::from_bytes(&recipient.payment_id),
+            )
+            .await
+        {
+            Ok((tx_id, adaptor_signature, output)) => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Transaction broadcast: {}, hash {}",
+                    tx_id,
+                    output.hash().to_hex()
+                );
+                SendAdaptorSwapResponse {
+                    transaction_id: tx_id.as_u64(),
+                    adaptor_signature: Some(tari_rpc::Signature {
+                        public_nonce: adaptor_signature.public_nonce().to_vec(),
+                        signature: adaptor_signature.pre_signature().to_vec(),
+                    }),
+                    output_hash: output.hash().to_hex(),
+                    is_success: true,
+                    failure_message: Default::default(),
+                }
+            },
+            Err(e) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to send adaptor - XTR atomic swap for address `{}`: {}", address, e
+                );
+                SendAdaptorSwapResponse {
+                    transaction_id: Default::default(),
+                    adaptor_signature: None,
+                    output_hash: "".to_string(),
+                    is_success: false,
+                    failure_message: e.to_string(),
+                }
+            },
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn claim_adaptor_swap_transaction(
+        &self,
+        request: Request<ClaimAdaptorSwapRequest>,
+    ) -> Result<Response<ClaimAdaptorSwapResponse>, Status> {
+        let message = request.into_inner();
+        let output = BlockHash::from_hex(&message.output_hash)
+            .map_err(|_| Status::internal("Output hash is malformed".to_string()))?;
+        let adaptor_signature = message
+            .adaptor_signature
+            .ok_or_else(|| Status::invalid_argument("Adaptor signature is missing".to_string()))?;
+        let adaptor_signature = AdaptorSignature::new(
+            PublicKey::from_canonical_bytes(&adaptor_signature.public_nonce)
+                .map_err(|_| Status::invalid_argument("Adaptor signature is malformed".to_string()))?,
+            PrivateKey::from_canonical_bytes(&adaptor_signature.signature)
+                .map_err(|_| Status::invalid_argument("Adaptor signature is malformed".to_string()))?,
+        );
+        let adaptor_secret = PrivateKey::from_canonical_bytes(&message.adaptor_secret)
+            .map_err(|_| Status::invalid_argument("Adaptor secret is malformed".to_string()))?;
+        debug!(target: LOG_TARGET, "Trying to claim adaptor swap with hash {}", output.to_hex());
+
+        let mut transaction_service = self.get_transaction_service();
+        let response = match transaction_service
+            .claim_adaptor_swap(output, adaptor_signature, adaptor_secret, message.fee_per_gram.into())
+            .await
+        {
+            Ok(tx_id) => TransferResult {
+                address: Default::default(),
+                transaction_id: tx_id.as_u64(),
+                is_success: true,
+                failure_message: Default::default(),
+            },
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to claim adaptor - XTR atomic swap: {}", e);
+                TransferResult {
+                    address: Default::default(),
+                    transaction_id: Default::default(),
+                    is_success: false,
+                    failure_message: e.to_string(),
+                }
+            },
+        };
+
+        Ok(Response::new(ClaimAdaptorSwapResponse {
+            results: Some(response),
+        }))
+    }
+
+    async fn reveal_adaptor_swap_secret(
+        &self,
+        request: Request<RevealAdaptorSwapSecretRequest>,
+    ) -> Result<Response<RevealAdaptorSwapSecretResponse>, Status> {
+        let message = request.into_inner();
+        let output = BlockHash::from_hex(&message.output_hash)
+            .map_err(|_| Status::internal("Output hash is malformed".to_string()))?;
+        let input_data = ExecutionStack::from_bytes(&message.input_data)
+            .map_err(|_| Status::invalid_argument("Input data is malformed".to_string()))?;
+
+        let mut transaction_service = self.get_transaction_service();
+        let adaptor_secret = transaction_service
+            .reveal_adaptor_swap_secret(output, input_data)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(RevealAdaptorSwapSecretResponse {
+            adaptor_secret: adaptor_secret.to_vec(),
+        }))
+    }
+
+    async fn refund_adaptor_swap_transaction(
+        &self,
+        request: Request<RefundAdaptorSwapRequest>,
+    ) -> Result<Response<RefundAdaptorSwapResponse>, Status> {
+        let message = request.into_inner();
+        let output = BlockHash::from_hex(&message.output_hash)
+            .map_err(|_| Status::internal("Output hash is malformed".to_string()))?;
+        debug!(target: LOG_TARGET, "Trying to refund adaptor swap with hash {}", output.to_hex());
+
+        let mut transaction_service = self.get_transaction_service();
+        let response = match transaction_service
+            .refund_adaptor_swap(output, message.fee_per_gram.into())
+            .await
+        {
+            Ok(tx_id) => TransferResult {
+                address: Default::default(),
+                transaction_id: tx_id.as_u64(),
+                is_success: true,
+                failure_message: Default::default(),
+            },
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Failed to refund adaptor swap transaction: {}", e);
+                TransferResult {
+                    address: Default::default(),
+                    transaction_id: Default::default(),
+                    is_success: false,
+                    failure_message: e.to_string(),
+                }
+            },
+        };
+
+        Ok(Response::new(RefundAdaptorSwapResponse {
+            results: Some(response),
+        }))
+    }
+
     async fn transfer(&self, request: Request<TransferRequest>) -> Result<Response<TransferResponse>, Status> {
         let message = request.into_inner();
         let recipients = message
@@ -519,14 +723,19 @@ impl wallet_server::Wallet for WalletGrpcServer {
                     dest.fee_per_gram,
                     dest.payment_type,
                     dest.payment_id,
+                    dest.account,
+                    dest.input_label,
                 ))
             })
             .collect::<Result<Vec<_>, _>>()
             .map_err(Status::invalid_argument)?;
 
         let mut transfers = Vec::new();
-        for (hex_address, address, amount, fee_per_gram, payment_type, payment_id) in recipients {
+        for (hex_address, address, amount, fee_per_gram, payment_type, payment_id, account, input_label) in recipients {
             let payment_id = PaymentId::from_bytes(&payment_id);
+            let selection_criteria = UtxoSelectionCriteria::default()
+                .with_account(Some(account).filter(|a| !a.is_empty()))
+                .with_label(Some(input_label).filter(|l| !l.is_empty()));
             let mut transaction_service = self.get_transaction_service();
             transfers.push(async move {
                 (
@@ -536,7 +745,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                             .send_transaction(
                                 address,
                                 amount.into(),
-                                UtxoSelectionCriteria::default(),
+                                selection_criteria,
                                 OutputFeatures::default(),
                                 fee_per_gram.into(),
                                 payment_id,
@@ -547,7 +756,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                             .send_one_sided_transaction(
                                 address,
                                 amount.into(),
-                                UtxoSelectionCriteria::default(),
+                                selection_criteria,
                                 OutputFeatures::default(),
                                 fee_per_gram.into(),
                                 payment_id,
@@ -558,7 +767,7 @@ impl wallet_server::Wallet for WalletGrpcServer {
                             .send_one_sided_to_stealth_address_transaction(
                                 address,
                                 amount.into(),
-                                UtxoSelectionCriteria::default(),
+                                selection_criteria,
                                 OutputFeatures::default(),
                                 fee_per_gram.into(),
                                 payment_id,
@@ -752,17 +961,23 @@ impl wallet_server::Wallet for WalletGrpcServer {
 
     async fn get_completed_transactions(
         &self,
-        _request: Request<GetCompletedTransactionsRequest>,
+        request: Request<GetCompletedTransactionsRequest>,
     ) -> Result<Response<Self::GetCompletedTransactionsStream>, Status> {
         debug!(
             target: LOG_TARGET,
             "GetAllCompletedTransactions: Incoming GRPC request"
         );
+        let message = request.into_inner();
         let mut transaction_service = self.get_transaction_service();
-        let transactions = transaction_service
-            .get_completed_transactions()
-            .await
-            .map_err(|err| Status::not_found(format!("No completed transactions found: {:?}", err)))?;
+        let transactions = if message.account.is_empty() {
+            transaction_service.get_completed_transactions().await
+        } else {
+            transaction_service
+                .get_wallet_account_transactions(Some(message.account))
+                .await
+        };
+        let transactions =
+            transactions.map_err(|err| Status::not_found(format!("No completed transactions found: {:?}", err)))?;
         debug!(
             target: LOG_TARGET,
             "GetAllCompletedTransactions: Found {} completed transactions",
@@ -956,6 +1171,422 @@ impl wallet_server::Wallet for WalletGrpcServer {
         }
     }
 
+    async fn bump_fee(
+        &self,
+        request: Request<tari_rpc::BumpFeeRequest>,
+    ) -> Result<Response<tari_rpc::BumpFeeResponse>, Status> {
+        let message = request.into_inner();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming gRPC request to Bump Fee (TxId: {}) to {} per gram", message.tx_id, message.fee_per_gram,
+        );
+        let mut transaction_service = self.get_transaction_service();
+
+        match transaction_service
+            .bump_fee(message.tx_id.into(), message.fee_per_gram.into())
+            .await
+        {
+            Ok(tx_id) => Ok(Response::new(tari_rpc::BumpFeeResponse {
+                tx_id: tx_id.as_u64(),
+                is_success: true,
+                failure_message: "".to_string(),
+            })),
+            Err(e) => Ok(Response::new(tari_rpc::BumpFeeResponse {
+                tx_id: 0,
+                is_success: false,
+                failure_message: e.to_string(),
+            })),
+        }
+    }
+
+    async fn create_wallet_account(
+        &self,
+        request: Request<tari_rpc::CreateWalletAccountRequest>,
+    ) -> Result<Response<tari_rpc::CreateWalletAccountResponse>, Status> {
+        let message = request.into_inner();
+        debug!(
+            target: LOG_TARGET,
+            "Incoming gRPC request to create wallet account '{}'", message.name
+        );
+        let mut output_service = self.get_output_manager_service();
+
+        match output_service.create_wallet_account(message.name).await {
+            Ok((account, address)) => Ok(Response::new(tari_rpc::CreateWalletAccountResponse {
+                name: account.name,
+                address: address.to_vec(),
+                is_success: true,
+                failure_message: "".to_string(),
+            })),
+            Err(e) => Ok(Response::new(tari_rpc::CreateWalletAccountResponse {
+                is_success: false,
+                failure_message: e.to_string(),
+                ..Default::default()
+            })),
+        }
+    }
+
+    async fn list_wallet_accounts(
+        &self,
+        _: Request<tari_rpc::Empty>,
+    ) -> Result<Response<tari_rpc::ListWalletAccountsResponse>, Status> {
+        let mut output_service = self.get_output_manager_service();
+        let accounts = output_service
+            .get_wallet_accounts()
+            .await
+            .map_err(|e| Status::internal(format!("ListWalletAccounts error! {}", e)))?;
+
+        let mut result = Vec::with_capacity(accounts.len());
+        for (account, address) in accounts {
+            let balance = output_service
+                .get_wallet_account_balance(account.name.clone())
+                .await
+                .map_err(|e| Status::internal(format!("ListWalletAccounts error! {}", e)))?;
+            result.push(tari_rpc::WalletAccount {
+                name: account.name,
+                address: address.to_vec(),
+                balance: Some(GetBalanceResponse {
+                    available_balance: balance.available_balance.0,
+                    pending_incoming_balance: balance.pending_incoming_balance.0,
+                    pending_outgoing_balance: balance.pending_outgoing_balance.0,
+                    timelocked_balance: balance.time_locked_balance.unwrap_or_default().0,
+                }),
+            });
+        }
+
+        Ok(Response::new(tari_rpc::ListWalletAccountsResponse { accounts: result }))
+    }
+
+    async fn transfer_between_wallet_accounts(
+        &self,
+        request: Request<tari_rpc::TransferBetweenWalletAccountsRequest>,
+    ) -> Result<Response<tari_rpc::TransferBetweenWalletAccountsResponse>, Status> {
+        let message = request.into_inner();
+        let from_account = Some(message.from_account).filter(|a| !a.is_empty());
+        let to_account = Some(message.to_account).filter(|a| !a.is_empty());
+        debug!(
+            target: LOG_TARGET,
+            "Incoming gRPC request to transfer {} from wallet account {:?} to {:?}",
+            message.amount,
+            from_account,
+            to_account
+        );
+        let mut transaction_service = self.get_transaction_service();
+
+        match transaction_service
+            .transfer_between_wallet_accounts(
+                from_account,
+                to_account,
+                message.amount.into(),
+                message.fee_per_gram.into(),
+                PaymentId::from_bytes(&message.payment_id),
+            )
+            .await
+        {
+            Ok(tx_id) => Ok(Response::new(tari_rpc::TransferBetweenWalletAccountsResponse {
+                tx_id: tx_id.as_u64(),
+                is_success: true,
+                failure_message: "".to_string(),
+            })),
+            Err(e) => Ok(Response::new(tari_rpc::TransferBetweenWalletAccountsResponse {
+                tx_id: 0,
+                is_success: false,
+                failure_message: e.to_string(),
+            })),
+        }
+    }
+
+    async fn list_outputs(
+        &self,
+        request: Request<tari_rpc::ListOutputsRequest>,
+    ) -> Result<Response<tari_rpc::ListOutputsResponse>, Status> {
+        let message = request.into_inner();
+        let mut output_service = self.get_output_manager_service();
+        let outputs = output_service
+            .get_unspent_outputs()
+            .await
+            .map_err(|e| Status::internal(format!("ListOutputs error! {}", e)))?;
+
+        let outputs = outputs
+            .into_iter()
+            .filter(|o| message.label.is_empty() || o.label.as_deref() == Some(message.label.as_str()))
+            .map(|o| tari_rpc::LabelledOutput {
+                commitment: o.commitment.to_vec(),
+                value: o.wallet_output.value.as_u64(),
+                label: o.label.unwrap_or_default(),
+                frozen: o.frozen,
+            })
+            .collect();
+
+        Ok(Response::new(tari_rpc::ListOutputsResponse { outputs }))
+    }
+
+    async fn set_outputs_label(
+        &self,
+        request: Request<tari_rpc::SetOutputsLabelRequest>,
+    ) -> Result<Response<tari_rpc::SetOutputsLabelResponse>, Status> {
+        let message = request.into_inner();
+        let commitments = message
+            .commitments
+            .iter()
+            .map(|c| Commitment::from_canonical_bytes(c.as_slice()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(format!("Malformed commitment: {}", e)))?;
+        let label = Some(message.label).filter(|l| !l.is_empty());
+        let mut output_service = self.get_output_manager_service();
+
+        match output_service.set_outputs_label(commitments, label).await {
+            Ok(_) => Ok(Response::new(tari_rpc::SetOutputsLabelResponse {
+                is_success: true,
+                failure_message: "".to_string(),
+            })),
+            Err(e) => Ok(Response::new(tari_rpc::SetOutputsLabelResponse {
+                is_success: false,
+                failure_message: e.to_string(),
+            })),
+        }
+    }
+
+    async fn set_outputs_frozen(
+        &self,
+        request: Request<tari_rpc::SetOutputsFrozenRequest>,
+    ) -> Result<Response<tari_rpc::SetOutputsFrozenResponse>, Status> {
+        let message = request.into_inner();
+        let commitments = message
+            .commitments
+            .iter()
+            .map(|c| Commitment::from_canonical_bytes(c.as_slice()))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(format!("Malformed commitment: {}", e)))?;
+        let mut output_service = self.get_output_manager_service();
+
+        match output_service.set_outputs_frozen(commitments, message.frozen).await {
+            Ok(_) => Ok(Response::new(tari_rpc::SetOutputsFrozenResponse {
+                is_success: true,
+                failure_message: "".to_string(),
+            })),
+            Err(e) => Ok(Response::new(tari_rpc::SetOutputsFrozenResponse {
+                is_success: false,
+                failure_message: e.to_string(),
+            })),
+        }
+    }
+
+    async fn set_transaction_label(
+        &self,
+        request: Request<tari_rpc::SetTransactionLabelRequest>,
+    ) -> Result<Response<tari_rpc::SetTransactionLabelResponse>, Status> {
+        let message = request.into_inner();
+        let label = Some(message.label).filter(|l| !l.is_empty());
+        let mut transaction_service = self.get_transaction_service();
+
+        match transaction_service
+            .set_transaction_label(message.tx_id.into(), label)
+            .await
+        {
+            Ok(_) => Ok(Response::new(tari_rpc::SetTransactionLabelResponse {
+                is_success: true,
+                failure_message: "".to_string(),
+            })),
+            Err(e) => Ok(Response::new(tari_rpc::SetTransactionLabelResponse {
+                is_success: false,
+                failure_message: e.to_string(),
+            })),
+        }
+    }
+
+    async fn export_transaction_history(
+        &self,
+        request: Request<tari_rpc::ExportTransactionHistoryRequest>,
+    ) -> Result<Response<tari_rpc::ExportTransactionHistoryResponse>, Status> {
+        let format = match request.into_inner().format() {
+            tari_rpc::export_transaction_history_request::Format::Csv => TransactionHistoryFormat::Csv,
+            tari_rpc::export_transaction_history_request::Format::Json => TransactionHistoryFormat::Json,
+        };
+        let mut wallet = self.wallet.clone();
+        let history = wallet
+            .export_transaction_history(format)
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?;
+
+        Ok(Response::new(tari_rpc::ExportTransactionHistoryResponse { history }))
+    }
+
+    async fn send_batch(
+        &self,
+        request: Request<tari_rpc::SendBatchRequest>,
+    ) -> Result<Response<tari_rpc::SendBatchResponse>, Status> {
+        let message = request.into_inner();
+        let recipients = message
+            .recipients
+            .into_iter()
+            .enumerate()
+            .map(|(idx, recipient)| -> Result<_, String> {
+                let address = TariAddress::from_str(&recipient.address)
+                    .map_err(|_| format!("Recipient address at index {} is malformed", idx))?;
+                Ok(BatchRecipient {
+                    address,
+                    amount: recipient.amount.into(),
+                    payment_id: PaymentId::from_bytes(&recipient.payment_id),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(Status::invalid_argument)?;
+        if recipients.is_empty() {
+            return Err(Status::invalid_argument("No recipients provided"));
+        }
+        let total = BatchRecipient::total_amount(&recipients)
+            .ok_or_else(|| Status::invalid_argument("The total amount of the recipients overflows"))?;
+        let selection_criteria = UtxoSelectionCriteria::default()
+            .with_account(Some(message.account).filter(|a| !a.is_empty()))
+            .with_label(Some(message.input_label).filter(|l| !l.is_empty()));
+        let fee_per_gram = MicroMinotari::from(message.fee_per_gram);
+
+        if message.estimate_only {
+            let mut output_manager = self.get_output_manager_service();
+            return match output_manager
+                .fee_estimate(total, selection_criteria, fee_per_gram, 1, recipients.len())
+                .await
+            {
+                Ok(fee) => Ok(Response::new(tari_rpc::SendBatchResponse {
+                    tx_id: 0,
+                    fee: fee.as_u64(),
+                    is_success: true,
+                    failure_message: "".to_string(),
+                })),
+                Err(e) => Ok(Response::new(tari_rpc::SendBatchResponse {
+                    tx_id: 0,
+                    fee: 0,
+                    is_success: false,
+                    failure_message: e.to_string(),
+                })),
+            };
+        }
+
+        let mut transaction_service = self.get_transaction_service();
+        match transaction_service
+            .send_batch(
+                recipients,
+                selection_criteria,
+                fee_per_gram,
+                PaymentId::from_bytes(&message.payment_id),
+            )
+            .await
+        {
+            Ok(tx_id) => {
+                let fee = match transaction_service.get_completed_transaction(tx_id).await {
+                    Ok(tx) => tx.fee.as_u64(),
+                    Err(_) => 0,
+                };
+                Ok(Response::new(tari_rpc::SendBatchResponse {
+                    tx_id: tx_id.as_u64(),
+                    fee,
+                    is_success: true,
+                    failure_message: "".to_string(),
+                }))
+            },
+            Err(e) => Ok(Response::new(tari_rpc::SendBatchResponse {
+                tx_id: 0,
+                fee: 0,
+                is_success: false,
+                failure_message: e.to_string(),
+            })),
+        }
+    }
+
+    async fn add_payment_schedule(
+        &self,
+        request: Request<tari_rpc::AddPaymentScheduleRequest>,
+    ) -> Result<Response<tari_rpc::AddPaymentScheduleResponse>, Status> {
+        let message = request.into_inner();
+        let destination = TariAddress::from_str(&message.destination)
+            .map_err(|_| Status::invalid_argument("Destination address is malformed"))?;
+        let start_time = match message.start_time {
+            0 => Utc::now(),
+            t => DateTime::from_timestamp(t as i64, 0).ok_or_else(|| Status::invalid_argument("Invalid start time"))?,
+        };
+        let end_time = match message.end_time {
+            0 => None,
+            t => Some(
+                DateTime::from_timestamp(t as i64, 0).ok_or_else(|| Status::invalid_argument("Invalid end time"))?,
+            ),
+        };
+        let schedule = models::PaymentSchedule::new(
+            destination,
+            message.amount.into(),
+            message.fee_per_gram.into(),
+            Duration::from_secs(message.interval),
+            start_time,
+        )
+        .with_payment_id(PaymentId::from_bytes(&message.payment_id))
+        .with_max_fee(Some(message.max_fee).filter(|f| *f > 0).map(MicroMinotari::from))
+        .with_end_time(end_time)
+        .with_max_missed_payments(message.max_missed_payments);
+
+        let mut transaction_service = self.get_transaction_service();
+        match transaction_service.add_payment_schedule(schedule).await {
+            Ok(id) => Ok(Response::new(tari_rpc::AddPaymentScheduleResponse {
+                id,
+                is_success: true,
+                failure_message: "".to_string(),
+            })),
+            Err(e) => Ok(Response::new(tari_rpc::AddPaymentScheduleResponse {
+                id: 0,
+                is_success: false,
+                failure_message: e.to_string(),
+            })),
+        }
+    }
+
+    async fn list_payment_schedules(
+        &self,
+        _: Request<tari_rpc::ListPaymentSchedulesRequest>,
+    ) -> Result<Response<tari_rpc::ListPaymentSchedulesResponse>, Status> {
+        let mut transaction_service = self.get_transaction_service();
+        let schedules = transaction_service
+            .get_payment_schedules()
+            .await
+            .map_err(|e| Status::internal(e.to_string()))?
+            .into_iter()
+            .map(|s| tari_rpc::PaymentSchedule {
+                id: s.id,
+                destination: s.destination.to_base58(),
+                amount: s.amount.as_u64(),
+                payment_id: s.payment_id.to_bytes(),
+                fee_per_gram: s.fee_per_gram.as_u64(),
+                max_fee: s.max_fee.map(|f| f.as_u64()).unwrap_or_default(),
+                interval: s.interval.as_secs(),
+                next_payment: s.next_payment.timestamp() as u64,
+                end_time: s.end_time.map(|t| t.timestamp() as u64).unwrap_or_default(),
+                status: s.status.to_string(),
+                payments_made: s.payments_made,
+                last_tx_id: s.last_tx_id.map(|tx_id| tx_id.as_u64()).unwrap_or_default(),
+                last_error: s.last_error.unwrap_or_default(),
+                max_missed_payments: s.max_missed_payments,
+                failed_attempts: s.failed_attempts,
+                retry_at: s.retry_at.map(|t| t.timestamp() as u64).unwrap_or_default(),
+            })
+            .collect();
+        Ok(Response::new(tari_rpc::ListPaymentSchedulesResponse { schedules }))
+    }
+
+    async fn cancel_payment_schedule(
+        &self,
+        request: Request<tari_rpc::CancelPaymentScheduleRequest>,
+    ) -> Result<Response<tari_rpc::CancelPaymentScheduleResponse>, Status> {
+        let message = request.into_inner();
+        let mut transaction_service = self.get_transaction_service();
+        match transaction_service.cancel_payment_schedule(message.id).await {
+            Ok(_) => Ok(Response::new(tari_rpc::CancelPaymentScheduleResponse {
+                is_success: true,
+                failure_message: "".to_string(),
+            })),
+            Err(e) => Ok(Response::new(tari_rpc::CancelPaymentScheduleResponse {
+                is_success: false,
+                failure_message: e.to_string(),
+            })),
+        }
+    }
+
     async fn create_template_registration(
         &self,
         request: Request<CreateTemplateRegistrationRequest>,
@@ -1064,6 +1695,103 @@ impl wallet_server::Wallet for WalletGrpcServer {
         };
         Ok(Response::new(response))
     }
+
+    async fn assemble_script(
+        &self,
+        request: Request<AssembleScriptRequest>,
+    ) -> Result<Response<AssembleScriptResponse>, Status> {
+        let script = TariScript::from_str(&request.into_inner().source)
+            .map_err(|e| Status::invalid_argument(format!("Invalid script: {}", e)))?;
+        Ok(Response::new(AssembleScriptResponse {
+            script: script.to_bytes(),
+            canonical: script.to_string(),
+            pretty: script.to_pretty_string(),
+        }))
+    }
+
+    async fn disassemble_script(
+        &self,
+        request: Request<DisassembleScriptRequest>,
+    ) -> Result<Response<DisassembleScriptResponse>, Status> {
+        let script = TariScript::from_bytes(&request.into_inner().script)
+            .map_err(|e| Status::invalid_argument(format!("Invalid script: {}", e)))?;
+        Ok(Response::new(DisassembleScriptResponse {
+            canonical: script.to_string(),
+            pretty: script.to_pretty_string(),
+        }))
+    }
+
+    async fn compile_covenant(
+        &self,
+        request: Request<CompileCovenantRequest>,
+    ) -> Result<Response<CompileCovenantResponse>, Status> {
+        let covenant = Covenant::from_str(&request.into_inner().source)
+            .map_err(|e| Status::invalid_argument(format!("Invalid covenant: {}", e)))?;
+        let canonical = covenant
+            .decompile()
+            .map_err(|e| Status::internal(format!("Could not decompile covenant: {}", e)))?;
+        Ok(Response::new(CompileCovenantResponse {
+            covenant: covenant.to_bytes(),
+            canonical,
+        }))
+    }
+
+    async fn decompile_covenant(
+        &self,
+        request: Request<DecompileCovenantRequest>,
+    ) -> Result<Response<DecompileCovenantResponse>, Status> {
+        let covenant = Covenant::from_bytes(&mut request.into_inner().covenant.as_slice())
+            .map_err(|e| Status::invalid_argument(format!("Invalid covenant: {}", e)))?;
+        let canonical = covenant
+            .decompile()
+            .map_err(|e| Status::invalid_argument(format!("Invalid covenant: {}", e)))?;
+        Ok(Response::new(DecompileCovenantResponse { canonical }))
+    }
+
+    // Output indexes and filter depths are bounded by the transaction and covenant sizes
+    #[allow(clippy::cast_possible_truncation)]
+    async fn dry_run_covenant(
+        &self,
+        request: Request<DryRunCovenantRequest>,
+    ) -> Result<Response<DryRunCovenantResponse>, Status> {
+        let message = request.into_inner();
+        let input = message
+            .input
+            .ok_or_else(|| Status::invalid_argument("No input provided"))
+            .and_then(|input| {
+                TransactionInput::try_from(input).map_err(|e| Status::invalid_argument(format!("Invalid input: {}", e)))
+            })?;
+        let covenant = input.covenant().map_err(|e| {
+            Status::invalid_argument(format!("Input is missing the data of the output it spends: {}", e))
+        })?;
+        let outputs = message
+            .outputs
+            .into_iter()
+            .map(TransactionOutput::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid output: {}", e)))?;
+
+        let input_mined_height = Some(message.input_mined_height).filter(|height| *height > 0);
+        let dry_run = covenant.dry_run(message.block_height, input_mined_height, &input, &outputs);
+        let to_indexes = |indexes: &[usize]| indexes.iter().map(|i| *i as u32).collect::<Vec<_>>();
+        Ok(Response::new(DryRunCovenantResponse {
+            is_satisfied: dry_run.is_satisfied(),
+            matching_outputs: to_indexes(&dry_run.matching_outputs),
+            steps: dry_run
+                .steps
+                .iter()
+                .map(|step| CovenantFilterStep {
+                    depth: step.depth as u32,
+                    filter: step.filter.to_string(),
+                    source: step.source.clone(),
+                    applied_to: to_indexes(&step.applied_to),
+                    matched: step.matched.as_deref().map(to_indexes).unwrap_or_default(),
+                    failed: step.matched.is_none(),
+                })
+                .collect(),
+            failure_reason: dry_run.failure_reason().unwrap_or_default(),
+        }))
+    }
 }
 
 async fn handle_completed_tx(
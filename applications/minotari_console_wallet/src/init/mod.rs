@@ -22,7 +22,14 @@
 
 #![allow(dead_code, unused)]
 
-use std::{fs, io, path::PathBuf, str::FromStr, sync::Arc, time::Instant};
+use std::{
+    fs,
+    io,
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+    time::Instant,
+};
 
 use crossterm::terminal::{disable_raw_mode, enable_raw_mode, is_raw_mode_enabled};
 use digest::crypto_common::rand_core::OsRng;
@@ -45,6 +52,7 @@ use minotari_wallet::{
 use rand::prelude::SliceRandom;
 use rpassword::prompt_password_stdout;
 use rustyline::Editor;
+use serde::{Deserialize, Serialize};
 use tari_common::{
     configuration::{
         bootstrap::{grpc_default_port, prompt, ApplicationType},
@@ -104,6 +112,21 @@ pub enum WalletBoot {
     ViewAndSpendKey,
 }
 
+/// The keys written by `export-view-key-and-spend-key`, from which a watch-only wallet can be created
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ViewKeyFile {
+    pub view_key: String,
+    pub public_view_key: String,
+    pub spend_key: String,
+}
+
+/// Read the private view key and public spend key from a file written by `export-view-key-and-spend-key`
+pub fn read_view_key_file(file: &Path) -> Result<ViewKeyFile, ExitError> {
+    let contents = fs::read_to_string(file)
+        .map_err(|e| ExitError::new(ExitCode::IOError, format!("{} ({})", e, file.display())))?;
+    serde_json::from_str(contents.trim()).map_err(|e| ExitError::new(ExitCode::ConfigError, e))
+}
+
 /// Get and confirm a passphrase from the user, with feedback
 /// This is intended to be used for new or changed passphrases
 ///
@@ -250,6 +273,14 @@ pub fn get_or_prompt_password(
     Ok(password)
 }
 
+/// Gets the wallet backup passphrase if provided, otherwise prompts for it to be typed in.
+pub fn get_or_prompt_backup_passphrase(arg_passphrase: Option<SafePassword>) -> Result<SafePassword, ExitError> {
+    match arg_passphrase {
+        Some(passphrase) => Ok(passphrase),
+        None => prompt_password("Wallet backup passphrase: "),
+    }
+}
+
 fn prompt_password(prompt: &str) -> Result<SafePassword, ExitError> {
     let password = prompt_password_stdout(prompt).map_err(|e| ExitError::new(ExitCode::IOError, e))?;
 
@@ -747,7 +778,7 @@ fn boot(cli: &Cli, wallet_config: &WalletConfig) -> Result<WalletBoot, ExitError
         return Ok(WalletBoot::Recovery);
     }
 
-    if !wallet_exists && cli.view_private_key.is_some() && cli.spend_key.is_some() {
+    if !wallet_exists && (cli.view_key_file.is_some() || (cli.view_private_key.is_some() && cli.spend_key.is_some())) {
         return Ok(WalletBoot::ViewAndSpendKey);
     }
 
@@ -774,7 +805,7 @@ fn boot(cli: &Cli, wallet_config: &WalletConfig) -> Result<WalletBoot, ExitError
         loop {
             println!("1. Create a new wallet.");
             println!("2. Recover wallet from seed words or hardware device.");
-            println!("3. Create a read-only wallet using a view key.");
+            println!("3. Create a watch-only wallet using a view key and public spend key.");
             let readline = rl.readline(">> ");
             match readline {
                 Ok(line) => {
@@ -875,12 +906,9 @@ pub fn prompt_wallet_type(
                 prompt_public_key("Enter spend key: ").expect("Spend key provided was invalid")
             };
 
-            Some(WalletType::ProvidedKeys(ProvidedKeysWallet {
-                view_key,
-                public_spend_key: spend_key,
-                private_spend_key: None,
-                private_comms_key: None,
-            }))
+            Some(WalletType::ProvidedKeys(ProvidedKeysWallet::watch_only(
+                view_key, spend_key,
+            )))
         },
         WalletBoot::New | WalletBoot::Recovery => {
             #[cfg(not(feature = "ledger"))]
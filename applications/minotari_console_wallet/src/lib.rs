@@ -34,6 +34,8 @@ mod ui;
 mod utils;
 mod wallet_modes;
 
+use std::fs;
+
 pub use cli::{
     BurnMinotariArgs,
     Cli,
@@ -72,7 +74,15 @@ use tokio::runtime::Runtime;
 use wallet_modes::{command_mode, grpc_mode, recovery_mode, script_mode, tui_mode, WalletMode};
 
 pub use crate::config::ApplicationConfig;
-use crate::init::{boot_with_password, confirm_direct_only_send, confirm_seed_words, prompt_wallet_type, wallet_mode};
+use crate::init::{
+    boot_with_password,
+    confirm_direct_only_send,
+    confirm_seed_words,
+    get_or_prompt_backup_passphrase,
+    prompt_wallet_type,
+    read_view_key_file,
+    wallet_mode,
+};
 
 pub const LOG_TARGET: &str = "wallet::console_wallet::main";
 
@@ -108,6 +118,9 @@ pub fn run_wallet(shutdown: &mut Shutdown, runtime: Runtime, config: &mut Applic
         profile_with_tokio_console: false,
         view_private_key: None,
         spend_key: None,
+        view_key_file: None,
+        restore_backup: None,
+        backup_passphrase: None,
     };
 
     run_wallet_with_cli(shutdown, runtime, config, cli)
@@ -136,12 +149,19 @@ pub fn run_wallet_with_cli(
     // check for recovery based on existence of wallet file
     let (mut boot_mode, password) = boot_with_password(&cli, &config.wallet)?;
 
+    let (view_private_key, spend_key) = match cli.view_key_file {
+        Some(ref file) => {
+            let keys = read_view_key_file(file)?;
+            (Some(keys.view_key), Some(keys.spend_key))
+        },
+        None => (cli.view_private_key.clone(), cli.spend_key.clone()),
+    };
     let wallet_type = prompt_wallet_type(
         boot_mode,
         &config.wallet,
         cli.non_interactive_mode,
-        cli.view_private_key.clone(),
-        cli.spend_key.clone(),
+        view_private_key,
+        spend_key,
     );
 
     let recovery_seed = get_recovery_seed(boot_mode, &cli, &wallet_type)?;
@@ -217,6 +237,17 @@ pub fn run_wallet_with_cli(
         };
     }
 
+    // Import the backup before the base node is set, so that the wallet only scans from the backup height
+    if let Some(ref backup_file) = cli.restore_backup {
+        let backup = fs::read(backup_file).map_err(|e| ExitError::new(ExitCode::IOError, e))?;
+        let passphrase = get_or_prompt_backup_passphrase(cli.backup_passphrase.clone())?;
+        let restored = runtime.block_on(wallet.restore_backup(&backup, &passphrase))?;
+        println!(
+            "Restored {} transactions, {} contacts and {} outputs from the wallet backup.",
+            restored.transactions, restored.contacts, restored.outputs
+        );
+    }
+
     // Check if there is an in progress recovery in the wallet's database
     if wallet.is_recovery_in_progress()? {
         println!("A Wallet Recovery was found to be in progress, continuing.");
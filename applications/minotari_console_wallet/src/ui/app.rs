@@ -42,6 +42,7 @@ use crate::{
             menu::Menu,
             network_tab::NetworkTab,
             notification_tab::NotificationTab,
+            payment_schedules_tab::PaymentSchedulesTab,
             receive_tab::ReceiveTab,
             register_template_tab::RegisterTemplateTab,
             send_tab::SendTab,
@@ -100,21 +101,23 @@ impl<B: Backend> App<B> {
             wallet_config,
         );
 
-        let tabs = TabsContainer::<B>::new(title.clone())
+        let wallet_type = app_state
+            .get_wallet_type()
+            .await
+            .map_err(|e| ExitError::new(ExitCode::WalletError, e))?;
+        let is_watch_only = wallet_type.is_watch_only();
+        let mut tabs = TabsContainer::<B>::new(title.clone())
             .add("Transactions".into(), Box::new(TransactionsTab::new()))
-            .add(
-                "Send".into(),
-                Box::new(SendTab::new(
-                    &app_state,
-                    app_state
-                        .get_wallet_type()
-                        .await
-                        .map_err(|e| ExitError::new(ExitCode::WalletError, e))?,
-                )),
-            )
-            .add("Receive".into(), Box::new(ReceiveTab::new()))
-            .add("Burn".into(), Box::new(BurnTab::new(&app_state)))
-            .add("Templates".into(), Box::new(RegisterTemplateTab::new(&app_state)))
+            .add("Send".into(), Box::new(SendTab::new(&app_state, wallet_type)))
+            .add("Receive".into(), Box::new(ReceiveTab::new()));
+        // A watch-only wallet cannot burn funds, pay for template registrations or make scheduled payments
+        if !is_watch_only {
+            tabs = tabs
+                .add("Burn".into(), Box::new(BurnTab::new(&app_state)))
+                .add("Templates".into(), Box::new(RegisterTemplateTab::new(&app_state)))
+                .add("Schedules".into(), Box::new(PaymentSchedulesTab::new()));
+        }
+        let tabs = tabs
             .add("Contacts".into(), Box::new(ContactsTab::new()))
             .add("Network".into(), Box::new(NetworkTab::new(base_node_selected)))
             .add("Events".into(), Box::new(EventsComponent::new()))
@@ -0,0 +1,402 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use chrono::Local;
+use minotari_wallet::transaction_service::storage::models::{PaymentSchedule, PaymentScheduleStatus};
+use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::encrypted_data::PaymentId};
+use tokio::runtime::Handle;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Span, Spans},
+    widgets::{Block, Borders, Clear, ListItem, Paragraph, Wrap},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use crate::{
+    ui::{
+        components::{Component, KeyHandled},
+        state::AppState,
+        widgets::{centered_rect_absolute, draw_dialog, MultiColumnList, WindowedListState},
+        MAX_WIDTH,
+    },
+    utils::formatting::display_compressed_string,
+};
+
+pub struct PaymentSchedulesTab {
+    input_mode: ScheduleInputMode,
+    show_new_schedule: bool,
+    address_field: String,
+    amount_field: String,
+    interval_field: String,
+    error_message: Option<String>,
+    schedules_list_state: WindowedListState,
+    confirmation_dialog: bool,
+}
+
+impl PaymentSchedulesTab {
+    pub fn new() -> Self {
+        Self {
+            input_mode: ScheduleInputMode::None,
+            show_new_schedule: false,
+            address_field: String::new(),
+            amount_field: String::new(),
+            interval_field: String::new(),
+            error_message: None,
+            schedules_list_state: WindowedListState::new(),
+            confirmation_dialog: false,
+        }
+    }
+
+    fn draw_schedules<B>(&mut self, f: &mut Frame<B>, area: Rect, app_state: &AppState)
+    where B: Backend {
+        let block = Block::default().borders(Borders::ALL).title(Span::styled(
+            "Payment Schedules",
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ));
+        f.render_widget(block, area);
+        let list_areas = Layout::default()
+            .constraints([Constraint::Length(1), Constraint::Min(42)].as_ref())
+            .margin(1)
+            .split(area);
+
+        let instructions = Paragraph::new(Spans::from(vec![
+            Span::raw("Use "),
+            Span::styled("Up↑/Down↓ Keys", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to select a schedule, "),
+            Span::styled("C", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to (c)ancel a schedule and "),
+            Span::styled("N", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(" to create a (n)ew schedule. Payments are made while the wallet is running."),
+        ]))
+        .wrap(Wrap { trim: true });
+        f.render_widget(instructions, list_areas[0]);
+        self.schedules_list_state
+            .set_num_items(app_state.get_payment_schedules().len());
+        let mut list_state = self
+            .schedules_list_state
+            .update_list_state((list_areas[1].height as usize).saturating_sub(3));
+        let window = self.schedules_list_state.get_start_end();
+        let windowed_view = app_state.get_payment_schedules_slice(window.0, window.1);
+
+        let column_list = PaymentSchedulesTab::create_column_view(windowed_view);
+        column_list.render(f, list_areas[1], &mut list_state);
+    }
+
+    // Helper function to create the column list to be rendered
+    pub fn create_column_view(windowed_view: &[PaymentSchedule]) -> MultiColumnList<Vec<ListItem>> {
+        let mut column0_items = Vec::new();
+        let mut column1_items = Vec::new();
+        let mut column2_items = Vec::new();
+        let mut column3_items = Vec::new();
+        let mut column4_items = Vec::new();
+        let mut column5_items = Vec::new();
+        let mut column6_items = Vec::new();
+        for s in windowed_view {
+            column0_items.push(ListItem::new(Span::raw(format!("#{}", s.id))));
+            column1_items.push(ListItem::new(Span::raw(display_compressed_string(
+                s.destination.to_base58(),
+                8,
+                8,
+            ))));
+            column2_items.push(ListItem::new(Span::raw(format!("{}", s.amount))));
+            column3_items.push(ListItem::new(Span::raw(if s.interval.is_zero() {
+                "Once".to_string()
+            } else {
+                format!("{}s", s.interval.as_secs())
+            })));
+            column4_items.push(ListItem::new(Span::raw(match s.status {
+                PaymentScheduleStatus::Active => {
+                    format!("{}", s.next_payment.with_timezone(&Local).format("%Y-%m-%d %H:%M:%S"))
+                },
+                _ => String::new(),
+            })));
+            column5_items.push(ListItem::new(Span::raw(format!("{}", s.payments_made))));
+            let status_style = match (s.status, &s.last_error) {
+                (PaymentScheduleStatus::Active, Some(_)) => Style::default().fg(Color::Red),
+                (PaymentScheduleStatus::Active, None) => Style::default().fg(Color::Green),
+                (PaymentScheduleStatus::Failed, _) => Style::default().fg(Color::Red),
+                _ => Style::default().fg(Color::Gray),
+            };
+            let status = match (s.status, &s.last_error) {
+                (PaymentScheduleStatus::Active, Some(error)) => format!("Failing: {}", error),
+                (status, _) => status.to_string(),
+            };
+            column6_items.push(ListItem::new(Span::styled(status, status_style)));
+        }
+        let column_list = MultiColumnList::new()
+            .highlight_style(Style::default().add_modifier(Modifier::BOLD).fg(Color::Magenta))
+            .heading_style(Style::default().fg(Color::Magenta))
+            .max_width(MAX_WIDTH)
+            .add_column(Some("Id"), Some(6), column0_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Destination"), Some(19), column1_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Amount"), Some(18), column2_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Interval"), Some(10), column3_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Next Payment"), Some(20), column4_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Paid"), Some(5), column5_items)
+            .add_column(None, Some(1), Vec::new())
+            .add_column(Some("Status"), None, column6_items);
+
+        column_list
+    }
+
+    // casting here is okay as we only use it to draw widths
+    #[allow(clippy::cast_possible_truncation)]
+    fn draw_new_schedule<B>(&mut self, f: &mut Frame<B>, area: Rect)
+    where B: Backend {
+        let popup_area = centered_rect_absolute(120, 13, area);
+
+        f.render_widget(Clear, popup_area);
+
+        let block = Block::default().borders(Borders::ALL).title(Span::styled(
+            "New Payment Schedule",
+            Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+        ));
+        f.render_widget(block, popup_area);
+        let vert_chunks = Layout::default()
+            .constraints(
+                [
+                    Constraint::Length(2),
+                    Constraint::Length(3),
+                    Constraint::Length(3),
+                    Constraint::Length(3),
+                ]
+                .as_ref(),
+            )
+            .margin(1)
+            .split(popup_area);
+
+        let instructions = Paragraph::new(Spans::from(vec![
+            Span::raw("Press "),
+            Span::styled("Enter", Style::default().add_modifier(Modifier::BOLD)),
+            Span::raw(
+                " to move to the next field and to save the schedule. The first payment is made at the next check.",
+            ),
+        ]))
+        .block(Block::default());
+        f.render_widget(instructions, vert_chunks[0]);
+
+        let fields = [
+            (ScheduleInputMode::Address, "Tari Address:", &self.address_field),
+            (ScheduleInputMode::Amount, "Amount:", &self.amount_field),
+            (
+                ScheduleInputMode::Interval,
+                "Seconds between payments (0 for a single payment):",
+                &self.interval_field,
+            ),
+        ];
+        for (i, (mode, title, value)) in fields.iter().enumerate() {
+            let input = Paragraph::new(value.as_str())
+                .style(if *mode == self.input_mode {
+                    Style::default().fg(Color::Magenta)
+                } else {
+                    Style::default()
+                })
+                .block(Block::default().borders(Borders::ALL).title(*title));
+            f.render_widget(input, vert_chunks[i + 1]);
+            if *mode == self.input_mode {
+                f.set_cursor(
+                    // Put cursor past the end of the input text
+                    vert_chunks[i + 1].x + value.width() as u16 + 1,
+                    // Move one line down, from the border to the input line
+                    vert_chunks[i + 1].y + 1,
+                );
+            }
+        }
+    }
+
+    fn save_new_schedule(&mut self, app_state: &mut AppState) {
+        self.input_mode = ScheduleInputMode::None;
+        self.show_new_schedule = false;
+
+        let amount = match self.amount_field.parse::<MicroMinotari>() {
+            Ok(amount) => amount,
+            Err(_) => {
+                self.error_message = Some("Amount should be an integer\nPress Enter to continue.".to_string());
+                return;
+            },
+        };
+        let interval = match self.interval_field.trim() {
+            "" => Duration::ZERO,
+            interval => match interval.parse::<u64>() {
+                Ok(seconds) => Duration::from_secs(seconds),
+                Err(_) => {
+                    self.error_message =
+                        Some("The interval should be a number of seconds\nPress Enter to continue.".to_string());
+                    return;
+                },
+            },
+        };
+        if let Err(e) = Handle::current().block_on(app_state.add_payment_schedule(
+            self.address_field.clone(),
+            amount,
+            interval,
+            PaymentId::open_from_str("Scheduled payment"),
+        )) {
+            self.error_message = Some(e.to_string() + "\nPress Enter to continue.");
+        }
+
+        self.address_field = "".to_string();
+        self.amount_field = "".to_string();
+        self.interval_field = "".to_string();
+    }
+
+    fn on_key_confirmation_dialog(&mut self, c: char, app_state: &mut AppState) -> KeyHandled {
+        if self.confirmation_dialog {
+            if 'n' == c {
+                self.confirmation_dialog = false;
+                return KeyHandled::Handled;
+            } else if 'y' == c {
+                if let Some(id) = self
+                    .schedules_list_state
+                    .selected()
+                    .and_then(|i| app_state.get_payment_schedule(i))
+                    .map(|s| s.id)
+                {
+                    if let Err(e) = Handle::current().block_on(app_state.cancel_payment_schedule(id)) {
+                        self.error_message = Some(e.to_string() + "\nPress Enter to continue.");
+                    }
+                }
+                self.confirmation_dialog = false;
+                return KeyHandled::Handled;
+            } else {
+                // dont care
+            }
+        }
+
+        KeyHandled::NotHandled
+    }
+
+    fn on_key_new_schedule(&mut self, c: char, app_state: &mut AppState) -> KeyHandled {
+        if !self.show_new_schedule {
+            return KeyHandled::NotHandled;
+        }
+        match (self.input_mode, c) {
+            (ScheduleInputMode::None, _) => return KeyHandled::NotHandled,
+            (ScheduleInputMode::Address, '\n') => self.input_mode = ScheduleInputMode::Amount,
+            (ScheduleInputMode::Amount, '\n') => self.input_mode = ScheduleInputMode::Interval,
+            (ScheduleInputMode::Interval, '\n') => self.save_new_schedule(app_state),
+            (ScheduleInputMode::Address, c) => self.address_field.push(c),
+            (ScheduleInputMode::Amount, c) => self.amount_field.push(c),
+            (ScheduleInputMode::Interval, c) => self.interval_field.push(c),
+        }
+
+        KeyHandled::Handled
+    }
+}
+
+impl<B: Backend> Component<B> for PaymentSchedulesTab {
+    fn draw(&mut self, f: &mut Frame<B>, area: Rect, app_state: &AppState) {
+        self.draw_schedules(f, area, app_state);
+        if self.show_new_schedule {
+            self.draw_new_schedule(f, area);
+        }
+
+        if let Some(msg) = self.error_message.clone() {
+            draw_dialog(f, area, "Error!".to_string(), msg, Color::Red, 120, 9);
+        }
+
+        if self.confirmation_dialog {
+            draw_dialog(
+                f,
+                area,
+                "Confirm Cancel".to_string(),
+                "Are you sure you want to cancel this payment schedule?\n(Y)es / (N)o".to_string(),
+                Color::Red,
+                120,
+                9,
+            );
+        }
+    }
+
+    fn on_key(&mut self, app_state: &mut AppState, c: char) {
+        if self.error_message.is_some() {
+            if '\n' == c {
+                self.error_message = None;
+            }
+            return;
+        }
+
+        if self.on_key_confirmation_dialog(c, app_state) == KeyHandled::Handled {
+            return;
+        }
+
+        if self.on_key_new_schedule(c, app_state) == KeyHandled::Handled {
+            return;
+        }
+
+        match c {
+            'c' => {
+                if self
+                    .schedules_list_state
+                    .selected()
+                    .and_then(|i| app_state.get_payment_schedule(i))
+                    .map_or(false, |s| s.status == PaymentScheduleStatus::Active)
+                {
+                    self.confirmation_dialog = true;
+                }
+            },
+            'n' => {
+                self.show_new_schedule = true;
+                self.input_mode = ScheduleInputMode::Address;
+            },
+            _ => {},
+        }
+    }
+
+    fn on_up(&mut self, app_state: &mut AppState) {
+        self.schedules_list_state
+            .set_num_items(app_state.get_payment_schedules().len());
+        self.schedules_list_state.previous();
+    }
+
+    fn on_down(&mut self, app_state: &mut AppState) {
+        self.schedules_list_state
+            .set_num_items(app_state.get_payment_schedules().len());
+        self.schedules_list_state.next();
+    }
+
+    fn on_esc(&mut self, _: &mut AppState) {
+        if self.confirmation_dialog {
+            return;
+        }
+        self.input_mode = ScheduleInputMode::None;
+        if self.show_new_schedule {
+            self.show_new_schedule = false;
+        } else {
+            self.schedules_list_state.select(None);
+        }
+    }
+
+    fn on_backspace(&mut self, _app_state: &mut AppState) {
+        match self.input_mode {
+            ScheduleInputMode::Address => {
+                let _ = self.address_field.pop();
+            },
+            ScheduleInputMode::Amount => {
+                let _ = self.amount_field.pop();
+            },
+            ScheduleInputMode::Interval => {
+                let _ = self.interval_field.pop();
+            },
+            ScheduleInputMode::None => {},
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ScheduleInputMode {
+    None,
+    Address,
+    Amount,
+    Interval,
+}
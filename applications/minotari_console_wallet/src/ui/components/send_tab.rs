@@ -112,17 +112,28 @@ impl SendTab {
         ])];
 
         let mut send_instructions = vec![];
-        if let WalletType::DerivedKeys | WalletType::ProvidedKeys(_) = self.wallet_type {
+        if self.wallet_type.is_watch_only() {
             send_instructions.append(&mut vec![
-                Span::raw("Press "),
-                Span::styled("S", Style::default().add_modifier(Modifier::BOLD)),
-                Span::raw(" to send a normal transaction, "),
+                Span::raw("This is a watch-only wallet, use the "),
+                Span::styled(
+                    "prepare-unsigned-transaction",
+                    Style::default().add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(" command to create a transaction for offline signing"),
+            ]);
+        } else {
+            if let WalletType::DerivedKeys | WalletType::ProvidedKeys(_) = self.wallet_type {
+                send_instructions.append(&mut vec![
+                    Span::raw("Press "),
+                    Span::styled("S", Style::default().add_modifier(Modifier::BOLD)),
+                    Span::raw(" to send a normal transaction, "),
+                ]);
+            }
+            send_instructions.append(&mut vec![
+                Span::styled("O", Style::default().add_modifier(Modifier::BOLD)),
+                Span::raw(" to send a one-sided transaction"),
             ]);
         }
-        send_instructions.append(&mut vec![
-            Span::styled("O", Style::default().add_modifier(Modifier::BOLD)),
-            Span::raw(" to send a one-sided transaction"),
-        ]);
         instructions.push(Spans::from(send_instructions));
 
         let instructions = Paragraph::new(instructions)
@@ -568,6 +579,11 @@ impl<B: Backend> Component<B> for SendTab {
             'f' => self.send_input_mode = SendInputMode::Fee,
             'p' => self.send_input_mode = SendInputMode::PaymentId,
             's' | 'o' => {
+                if self.wallet_type.is_watch_only() {
+                    self.error_message =
+                        Some("A watch-only wallet cannot send transactions\nPress Enter to continue.".to_string());
+                    return;
+                }
                 if let WalletType::Ledger(_) = self.wallet_type {
                     // If we're a ledger wallet, then ignore interactive send requests
                     if c == 's' {
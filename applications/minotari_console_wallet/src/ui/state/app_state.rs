@@ -28,7 +28,7 @@ use std::{
     time::{Duration, Instant},
 };
 
-use chrono::{DateTime, Local, NaiveDateTime};
+use chrono::{DateTime, Local, NaiveDateTime, Utc};
 use log::*;
 use minotari_wallet::{
     base_node_service::{handle::BaseNodeEventReceiver, service::BaseNodeState},
@@ -36,7 +36,7 @@ use minotari_wallet::{
     output_manager_service::{handle::OutputManagerEventReceiver, service::Balance, UtxoSelectionCriteria},
     transaction_service::{
         handle::TransactionEventReceiver,
-        storage::models::{CompletedTransaction, TxCancellationReason},
+        storage::models::{CompletedTransaction, PaymentSchedule, TxCancellationReason},
     },
     util::wallet_identity::WalletIdentity,
     utxo_scanner_service::handle::UtxoScannerHandle,
@@ -176,6 +176,14 @@ impl AppState {
         Ok(())
     }
 
+    pub async fn refresh_payment_schedules_state(&mut self) -> Result<(), UiError> {
+        let mut inner = self.inner.write().await;
+        inner.refresh_payment_schedules_state().await?;
+        drop(inner);
+        self.update_cache().await;
+        Ok(())
+    }
+
     pub async fn refresh_connected_peers_state(&mut self) -> Result<(), UiError> {
         self.check_connectivity().await;
         let mut inner = self.inner.write().await;
@@ -288,6 +296,44 @@ impl AppState {
         Ok(())
     }
 
+    /// Adds a payment schedule whose first payment is due now, paid at the default fee per gram
+    pub async fn add_payment_schedule(
+        &mut self,
+        address: String,
+        amount: MicroMinotari,
+        interval: Duration,
+        payment_id: PaymentId,
+    ) -> Result<(), UiError> {
+        let mut inner = self.inner.write().await;
+        let address = TariAddress::from_str(&address).map_err(|_| UiError::PublicKeyParseError)?;
+
+        let schedule = PaymentSchedule::new(
+            address,
+            amount,
+            self.wallet_config.fee_per_gram.into(),
+            interval,
+            Utc::now(),
+        )
+        .with_payment_id(payment_id);
+        inner.wallet.transaction_service.add_payment_schedule(schedule).await?;
+
+        inner.refresh_payment_schedules_state().await?;
+        drop(inner);
+        self.update_cache().await;
+        Ok(())
+    }
+
+    pub async fn cancel_payment_schedule(&mut self, id: u32) -> Result<(), UiError> {
+        let mut inner = self.inner.write().await;
+
+        inner.wallet.transaction_service.cancel_payment_schedule(id).await?;
+
+        inner.refresh_payment_schedules_state().await?;
+        drop(inner);
+        self.update_cache().await;
+        Ok(())
+    }
+
     pub async fn send_transaction(
         &mut self,
         address: String,
@@ -487,6 +533,25 @@ impl AppState {
         &self.cached_data.contacts[start..end]
     }
 
+    pub fn get_payment_schedules(&self) -> &[PaymentSchedule] {
+        self.cached_data.payment_schedules.as_slice()
+    }
+
+    pub fn get_payment_schedule(&self, index: usize) -> Option<&PaymentSchedule> {
+        self.cached_data.payment_schedules.get(index)
+    }
+
+    pub fn get_payment_schedules_slice(&self, start: usize, end: usize) -> &[PaymentSchedule] {
+        if self.cached_data.payment_schedules.is_empty() ||
+            start > end ||
+            end > self.cached_data.payment_schedules.len()
+        {
+            return &[];
+        }
+
+        &self.cached_data.payment_schedules[start..end]
+    }
+
     pub fn get_burnt_proofs_slice(&self, start: usize, end: usize) -> &[UiBurntProof] {
         if self.cached_data.burnt_proofs.is_empty() || start >= end {
             return &[];
@@ -906,6 +971,16 @@ impl AppStateInner {
         Ok(())
     }
 
+    pub async fn refresh_payment_schedules_state(&mut self) -> Result<(), UiError> {
+        // Newest first
+        let mut schedules = self.wallet.transaction_service.get_payment_schedules().await?;
+        schedules.reverse();
+
+        self.data.payment_schedules = schedules;
+        self.updated = true;
+        Ok(())
+    }
+
     pub async fn refresh_network_id(&mut self) -> Result<(), UiError> {
         let wallet_id = self.wallet.get_wallet_id().await?;
         let qr_link = format!(
@@ -1252,6 +1327,7 @@ struct AppStateData {
     my_identity: MyIdentity,
     contacts: Vec<UiContact>,
     burnt_proofs: Vec<UiBurntProof>,
+    payment_schedules: Vec<PaymentSchedule>,
     connected_peers: Vec<Peer>,
     balance: Balance,
     base_node_state: BaseNodeState,
@@ -1332,6 +1408,7 @@ impl AppStateData {
             my_identity: identity,
             contacts: Vec::new(),
             burnt_proofs: vec![],
+            payment_schedules: Vec::new(),
             connected_peers: Vec::new(),
             balance: Balance::zero(),
             base_node_state: BaseNodeState::default(),
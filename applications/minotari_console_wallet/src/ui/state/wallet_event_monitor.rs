@@ -173,6 +173,18 @@ impl WalletEventMonitor {
                                     self.trigger_full_tx_state_refresh().await;
                                     self.trigger_balance_refresh();
                                 },
+                                TransactionEvent::ScheduledPaymentSent{schedule_id, tx_id} => {
+                                    self.trigger_payment_schedules_refresh().await;
+                                    self.add_notification(
+                                        format!("Scheduled Payment Sent - Schedule #{}, TxId: {}", schedule_id, tx_id)
+                                    ).await;
+                                },
+                                TransactionEvent::ScheduledPaymentFailed{schedule_id, reason} => {
+                                    self.trigger_payment_schedules_refresh().await;
+                                    self.add_notification(
+                                        format!("Scheduled Payment Failed - Schedule #{}: {}", schedule_id, reason)
+                                    ).await;
+                                },
                                 // Only the above variants trigger state refresh
                                 _ => (),
                             }
@@ -266,8 +278,18 @@ impl WalletEventMonitor {
                     match result {
                         Ok(msg) => {
                             trace!(target: LOG_TARGET, "Output Manager Service Callback Handler event {:?}", msg);
-                            if let OutputManagerEvent::TxoValidationSuccess(_) = &*msg {
-                                self.trigger_balance_refresh();
+                            match &*msg {
+                                OutputManagerEvent::TxoValidationSuccess(_) => self.trigger_balance_refresh(),
+                                OutputManagerEvent::OutputsConsolidated { tx_id, num_inputs, .. } => {
+                                    self.add_notification(
+                                        format!("Consolidated {} outputs - TxId: {}", num_inputs, tx_id)
+                                    ).await;
+                                    self.trigger_balance_refresh();
+                                },
+                                OutputManagerEvent::OutputConsolidationFailed(reason) => {
+                                    self.add_notification(format!("Output consolidation failed: {}", reason)).await;
+                                },
+                                _ => {},
                             }
                         },
                         Err(broadcast::error::RecvError::Lagged(n)) => {
@@ -395,4 +417,12 @@ impl WalletEventMonitor {
             warn!(target: LOG_TARGET, "Error refresh contacts state: {}", e);
         }
     }
+
+    async fn trigger_payment_schedules_refresh(&mut self) {
+        let mut inner = self.app_state_inner.write().await;
+
+        if let Err(e) = inner.refresh_payment_schedules_state().await {
+            warn!(target: LOG_TARGET, "Error refresh payment schedules state: {}", e);
+        }
+    }
 }
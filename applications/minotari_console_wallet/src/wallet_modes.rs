@@ -529,6 +529,8 @@ async fn run_grpc(
 mod test {
     use std::path::Path;
 
+    use minotari_wallet::transaction_service::history_export::TransactionHistoryFormat;
+
     use crate::{cli::CliCommands, wallet_modes::parse_command_file};
 
     #[test]
@@ -579,6 +581,8 @@ mod test {
 
             export-tx 123456789 --output-file pie.txt
 
+            export-tx-history --format json --output-file history.json
+
             import-tx --input-file pie_this_message.txt
 
             # End of script file
@@ -600,6 +604,7 @@ mod test {
         let mut coin_split = false;
         let mut discover_peer = false;
         let mut export_tx = false;
+        let mut export_tx_history = false;
         let mut import_tx = false;
         let mut whois = false;
         for command in commands {
@@ -614,8 +619,17 @@ mod test {
                 CliCommands::PreMineSpendInputOutputSigs(_) => pre_mine_spend_input_output_sigs = true,
                 CliCommands::PreMineSpendAggregateTransaction(_) => pre_mine_spend_aggregate_transaction = true,
                 CliCommands::SendOneSidedToStealthAddress(_) => {},
+                CliCommands::SendBatch(_) => {},
+                CliCommands::AddPaymentSchedule(_) => {},
+                CliCommands::ListPaymentSchedules => {},
+                CliCommands::CancelPaymentSchedule(_) => {},
                 CliCommands::MakeItRain(_) => make_it_rain = true,
                 CliCommands::CoinSplit(_) => coin_split = true,
+                CliCommands::BumpFee(_) => {},
+                CliCommands::PrepareUnsignedTransaction(_) => {},
+                CliCommands::SignUnsignedTransaction(_) => {},
+                CliCommands::FinaliseSignedTransaction(_) => {},
+                CliCommands::AbandonUnsignedTransaction(_) => {},
                 CliCommands::DiscoverPeer(_) => discover_peer = true,
                 CliCommands::Whois(_) => whois = true,
                 CliCommands::ExportUtxos(_) => {},
@@ -625,6 +639,12 @@ mod test {
                         export_tx = true
                     }
                 },
+                CliCommands::ExportTxHistory(args) => {
+                    if args.format == TransactionHistoryFormat::Json && args.output_file == Some("history.json".into())
+                    {
+                        export_tx_history = true
+                    }
+                },
                 CliCommands::ImportTx(args) => {
                     if args.input_file == Path::new("pie_this_message.txt") {
                         import_tx = true
@@ -638,12 +658,40 @@ mod test {
                 CliCommands::InitShaAtomicSwap(_) => {},
                 CliCommands::FinaliseShaAtomicSwap(_) => {},
                 CliCommands::ClaimShaAtomicSwapRefund(_) => {},
+                CliCommands::InitAdaptorSwap(_) => {},
+                CliCommands::ClaimAdaptorSwap(_) => {},
+                CliCommands::RevealAdaptorSwapSecret(_) => {},
+                CliCommands::RefundAdaptorSwap(_) => {},
+                CliCommands::ListAdaptorSwaps => {},
+                CliCommands::MultisigAccountKey(_) => {},
+                CliCommands::CreateMultisigAccount(_) => {},
+                CliCommands::ListMultisigAccounts => {},
+                CliCommands::SendToMultisigAccount(_) => {},
+                CliCommands::MultisigSpendSessionInfo(_) => {},
+                CliCommands::MultisigSpendPartyDetails(_) => {},
+                CliCommands::MultisigSpendEncumberAggregateUtxo(_) => {},
+                CliCommands::MultisigSpendInputOutputSigs(_) => {},
+                CliCommands::MultisigSpendAggregateTransaction(_) => {},
+                CliCommands::CreateWalletAccount(_) => {},
+                CliCommands::ListWalletAccounts => {},
+                CliCommands::ListWalletAccountTransactions(_) => {},
+                CliCommands::TransferBetweenWalletAccounts(_) => {},
+                CliCommands::ListOutputs(_) => {},
+                CliCommands::LabelOutputs(_) => {},
+                CliCommands::FreezeOutputs(_) => {},
+                CliCommands::UnfreezeOutputs(_) => {},
+                CliCommands::LabelTransaction(_) => {},
                 CliCommands::RevalidateWalletDb => {},
                 CliCommands::RegisterValidatorNode(_) => {},
                 CliCommands::CreateTlsCerts => {},
                 CliCommands::PreMineSpendBackupUtxo(_) => {},
                 CliCommands::Sync(_) => {},
                 CliCommands::ExportViewKeyAndSpendKey(_) => {},
+                CliCommands::CreateBackup(_) => {},
+                CliCommands::AssembleScript(_) => {},
+                CliCommands::DisassembleScript(_) => {},
+                CliCommands::CompileCovenant(_) => {},
+                CliCommands::DecompileCovenant(_) => {},
             }
         }
         assert!(
@@ -661,6 +709,7 @@ mod test {
                 discover_peer &&
                 whois &&
                 export_tx &&
+                export_tx_history &&
                 import_tx
         );
     }
@@ -0,0 +1,90 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::PathBuf;
+
+use clap::{Parser, Subcommand};
+use tari_common::configuration::Network;
+use tari_core::proof_of_work::PowAlgorithm;
+
+use crate::scenario::ManipulationStrategy;
+
+/// Replays synthetic or historic block production scenarios through the difficulty adjustment algorithm and
+/// reports the resulting block times.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Cli {
+    /// The network whose consensus constants are simulated
+    #[clap(long, default_value = "mainnet")]
+    pub network: Network,
+    /// The height used to select the consensus constants of the network
+    #[clap(long, default_value_t = 0)]
+    pub height: u64,
+    /// Overrides the difficulty block window of the consensus constants
+    #[clap(long)]
+    pub block_window: Option<u64>,
+    /// Overrides the RandomX target block time (seconds) of the consensus constants
+    #[clap(long)]
+    pub randomx_target_time: Option<u64>,
+    /// Overrides the SHA3x target block time (seconds) of the consensus constants
+    #[clap(long)]
+    pub sha3x_target_time: Option<u64>,
+    /// The initial RandomX network hash rate in hashes per second
+    #[clap(long, default_value_t = 500_000_000.0)]
+    pub randomx_hashrate: f64,
+    /// The initial SHA3x network hash rate in hashes per second
+    #[clap(long, default_value_t = 500_000_000_000_000.0)]
+    pub sha3x_hashrate: f64,
+    /// The number of blocks to simulate. Replays simulate one block for every historic header.
+    #[clap(long, default_value_t = 10_000)]
+    pub blocks: u64,
+    /// The number of blocks in each interval of the report
+    #[clap(long, default_value_t = 500)]
+    pub report_interval: u64,
+    /// Seed for the random number generator, so that runs can be reproduced
+    #[clap(long, default_value_t = 0)]
+    pub seed: u64,
+    /// Writes every simulated block to this CSV file
+    #[clap(long)]
+    pub output: Option<PathBuf>,
+    #[clap(subcommand)]
+    pub scenario: ScenarioArgs,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ScenarioArgs {
+    /// Both algorithms keep a constant hash rate
+    Steady,
+    /// The hash rate of one algorithm is multiplied by `factor` from `at-height`, e.g. a factor of 0.5 is a 50% drop
+    HashrateChange {
+        /// The algorithm whose hash rate changes
+        #[clap(long)]
+        algo: PowAlgorithm,
+        /// The simulated height from which the change applies
+        #[clap(long)]
+        at_height: u64,
+        /// The optional simulated height from which the original hash rate is restored
+        #[clap(long)]
+        until_height: Option<u64>,
+        /// The hash rate multiplier
+        #[clap(long)]
+        factor: f64,
+    },
+    /// A miner controlling a share of one algorithm's hash rate manipulates the timestamps of the blocks it mines
+    TimestampManipulation {
+        /// The algorithm mined by the dishonest miner
+        #[clap(long)]
+        algo: PowAlgorithm,
+        /// The share (0.0 to 1.0) of the algorithm's hash rate controlled by the dishonest miner
+        #[clap(long)]
+        share: f64,
+        /// forward: timestamps at the future time limit, backward: timestamps just after the median timestamp
+        #[clap(long, default_value = "forward")]
+        strategy: ManipulationStrategy,
+    },
+    /// Derives the hash rate of each algorithm from a `header-stats` CSV export of a base node and replays it
+    Replay {
+        /// The CSV file written by the base node `header-stats` command
+        file: PathBuf,
+    },
+}
@@ -0,0 +1,126 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Replays block production scenarios through the difficulty adjustment algorithm, so that proposed consensus changes
+//! can be assessed before they are deployed. See the README for usage.
+
+mod cli;
+mod replay;
+mod report;
+mod scenario;
+mod simulator;
+
+use anyhow::anyhow;
+use clap::Parser;
+use tari_core::{
+    chain_storage::TargetDifficulties,
+    consensus::{
+        consensus_constants::PowAlgorithmConstants,
+        ConsensusConstants,
+        ConsensusConstantsBuilder,
+        ConsensusManager,
+    },
+    proof_of_work::PowAlgorithm,
+};
+
+use crate::{
+    cli::{Cli, ScenarioArgs},
+    replay::{historic_blocks, load_header_stats},
+    report::{print_report, write_csv},
+    scenario::{HashRateChange, HashRates, HistoricHashRates, Scenario, Steady, TimestampManipulation},
+    simulator::Simulator,
+};
+
+fn main() {
+    if let Err(err) = main_inner() {
+        eprintln!("{:#}", err);
+        std::process::exit(1);
+    }
+}
+
+fn main_inner() -> Result<(), anyhow::Error> {
+    let cli = Cli::parse();
+    let constants = consensus_constants(&cli)?;
+    let rules = ConsensusManager::builder(cli.network)
+        .add_consensus_constants(constants.clone())
+        .build()?;
+    let targets = TargetDifficulties::new(&rules, 0).map_err(|e| anyhow!(e))?;
+
+    let hash_rates = HashRates {
+        randomx: cli.randomx_hashrate,
+        sha3x: cli.sha3x_hashrate,
+    };
+    let mut historic = None;
+    let scenario: Box<dyn Scenario> = match cli.scenario {
+        ScenarioArgs::Steady => Box::new(Steady { hash_rates }),
+        ScenarioArgs::HashrateChange {
+            algo,
+            at_height,
+            until_height,
+            factor,
+        } => Box::new(HashRateChange {
+            hash_rates,
+            algo,
+            at_height,
+            until_height,
+            factor,
+        }),
+        ScenarioArgs::TimestampManipulation { algo, share, strategy } => Box::new(TimestampManipulation {
+            hash_rates,
+            algo,
+            share,
+            strategy,
+        }),
+        ScenarioArgs::Replay { ref file } => {
+            let headers = load_header_stats(file)?;
+            let scenario = HistoricHashRates::new(
+                &headers,
+                constants.pow_target_block_interval(PowAlgorithm::RandomX),
+                constants.pow_target_block_interval(PowAlgorithm::Sha3x),
+            );
+            historic = Some(historic_blocks(&headers));
+            Box::new(scenario)
+        },
+    };
+
+    println!(
+        "Network: {}, block window: {}, target times: RandomX {}s, Sha3x {}s",
+        cli.network,
+        constants.difficulty_block_window(),
+        constants.pow_target_block_interval(PowAlgorithm::RandomX),
+        constants.pow_target_block_interval(PowAlgorithm::Sha3x),
+    );
+    let num_blocks = scenario.num_blocks().unwrap_or(cli.blocks);
+    let mut simulator = Simulator::new(targets, &constants, cli.seed);
+    let blocks = simulator.run(scenario.as_ref(), num_blocks)?;
+
+    print_report(&blocks, cli.report_interval, historic.as_deref());
+    if let Some(output) = cli.output {
+        write_csv(&output, &blocks)?;
+        println!("Simulated blocks written to {}", output.display());
+    }
+    Ok(())
+}
+
+/// The consensus constants of the network at the given height, with the overrides given on the command line applied
+fn consensus_constants(cli: &Cli) -> Result<ConsensusConstants, anyhow::Error> {
+    let rules = ConsensusManager::builder(cli.network).build()?;
+    let constants = rules.consensus_constants(cli.height).clone();
+    let mut builder = ConsensusConstantsBuilder::new(cli.network).with_consensus_constants(constants.clone());
+    if let Some(block_window) = cli.block_window {
+        builder = builder.with_difficulty_block_window(block_window);
+    }
+    for (algo, target_time) in [
+        (PowAlgorithm::RandomX, cli.randomx_target_time),
+        (PowAlgorithm::Sha3x, cli.sha3x_target_time),
+    ] {
+        if let Some(target_time) = target_time {
+            builder = builder.add_proof_of_work(algo, PowAlgorithmConstants {
+                min_difficulty: constants.min_pow_difficulty(algo),
+                max_difficulty: constants.max_pow_difficulty(algo),
+                target_time,
+            });
+        }
+    }
+    Ok(builder.build())
+}
@@ -0,0 +1,138 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{convert::TryFrom, fs, path::Path};
+
+use anyhow::{anyhow, Context};
+use chrono::NaiveDateTime;
+use tari_core::proof_of_work::{Difficulty, PowAlgorithm};
+
+use crate::simulator::SimulatedBlock;
+
+/// A header loaded from a `header-stats` CSV export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HistoricHeader {
+    pub height: u64,
+    pub timestamp: u64,
+    pub algo: PowAlgorithm,
+    pub target_difficulty: u64,
+}
+
+/// Loads headers from a CSV file written by the base node `header-stats` command. The export must not be filtered by
+/// PoW algorithm, because the hash rates of both algorithms are needed for a replay.
+pub fn load_header_stats<P: AsRef<Path>>(path: P) -> Result<Vec<HistoricHeader>, anyhow::Error> {
+    let path = path.as_ref();
+    let contents = fs::read_to_string(path).with_context(|| format!("Could not read {}", path.display()))?;
+    parse_header_stats(&contents)
+}
+
+fn parse_header_stats(contents: &str) -> Result<Vec<HistoricHeader>, anyhow::Error> {
+    let mut lines = contents.lines().filter(|l| !l.trim().is_empty());
+    let header_row = lines.next().ok_or_else(|| anyhow!("The header stats file is empty"))?;
+    let columns = header_row.split(',').map(str::trim).collect::<Vec<_>>();
+    let column = |name: &str| {
+        columns
+            .iter()
+            .position(|c| *c == name)
+            .ok_or_else(|| anyhow!("The header stats file does not contain a '{}' column", name))
+    };
+    let height_col = column("Height")?;
+    let target_col = column("TargetDifficulty")?;
+    let algo_col = column("Algo")?;
+    let timestamp_col = column("Timestamp")?;
+
+    let mut headers = Vec::new();
+    for (i, line) in lines.enumerate() {
+        let values = line.split(',').map(str::trim).collect::<Vec<_>>();
+        let value = |col: usize| {
+            values
+                .get(col)
+                .copied()
+                .ok_or_else(|| anyhow!("Row {} has only {} columns", i + 1, values.len()))
+        };
+        let algo = value(algo_col)?
+            .to_lowercase()
+            .parse::<PowAlgorithm>()
+            .map_err(|e| anyhow!("Row {}: {}", i + 1, e))?;
+        headers.push(HistoricHeader {
+            height: value(height_col)?.parse().context("Invalid height")?,
+            timestamp: parse_timestamp(value(timestamp_col)?).with_context(|| format!("Row {}", i + 1))?,
+            algo,
+            target_difficulty: value(target_col)?.parse().context("Invalid target difficulty")?,
+        });
+    }
+    if headers.is_empty() {
+        return Err(anyhow!("The header stats file does not contain any headers"));
+    }
+    Ok(headers)
+}
+
+/// Converts historic headers into blocks, so that their block times can be reported alongside simulated blocks. The
+/// first header only provides the starting time and is not included.
+pub fn historic_blocks(headers: &[HistoricHeader]) -> Vec<SimulatedBlock> {
+    let mut last_found = [None, None];
+    let mut blocks = Vec::with_capacity(headers.len());
+    for (i, header) in headers.iter().enumerate() {
+        let algo_index = header.algo.as_u64() as usize;
+        if i > 0 {
+            let previous = headers[i - 1].timestamp;
+            blocks.push(SimulatedBlock {
+                height: header.height,
+                algo: header.algo,
+                found_at: header.timestamp,
+                timestamp: header.timestamp,
+                block_time: header.timestamp.saturating_sub(previous),
+                algo_block_time: header
+                    .timestamp
+                    .saturating_sub(last_found[algo_index].unwrap_or(previous)),
+                target_difficulty: Difficulty::from_u64(header.target_difficulty).unwrap_or_else(|_| Difficulty::min()),
+                hash_rate: 0.0,
+            });
+        }
+        last_found[algo_index] = Some(header.timestamp);
+    }
+    blocks
+}
+
+/// Parses timestamps in the `2024-05-01 12:00:00 UTC` format written by `header-stats`, or unix timestamps
+fn parse_timestamp(s: &str) -> Result<u64, anyhow::Error> {
+    if let Ok(ts) = s.parse::<u64>() {
+        return Ok(ts);
+    }
+    let datetime = NaiveDateTime::parse_from_str(s.trim_end_matches("UTC").trim(), "%Y-%m-%d %H:%M:%S")
+        .with_context(|| format!("Invalid timestamp '{}'", s))?;
+    u64::try_from(datetime.and_utc().timestamp()).map_err(|_| anyhow!("Timestamp '{}' is before the unix epoch", s))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_parses_header_stats_output() {
+        let csv = "Height,Achieved,TargetDifficulty,CalculatedDifficulty,SolveTime,NormalizedSolveTime,Algo,Timestamp,\
+                   Window,Acc.Monero,Acc.Sha3\n1,150,100,100,120,120,RandomX,2024-05-01 12:00:00 \
+                   UTC,90,100,0\n2,900,800,800,60,60,Sha3,2024-05-01 12:01:00 UTC,90,100,800\n";
+        let headers = parse_header_stats(csv).unwrap();
+        assert_eq!(headers, vec![
+            HistoricHeader {
+                height: 1,
+                timestamp: 1_714_564_800,
+                algo: PowAlgorithm::RandomX,
+                target_difficulty: 100,
+            },
+            HistoricHeader {
+                height: 2,
+                timestamp: 1_714_564_860,
+                algo: PowAlgorithm::Sha3x,
+                target_difficulty: 800,
+            },
+        ]);
+    }
+
+    #[test]
+    fn it_rejects_files_without_required_columns() {
+        let err = parse_header_stats("Height,Algo\n1,RandomX\n").unwrap_err();
+        assert!(err.to_string().contains("TargetDifficulty"));
+    }
+}
@@ -0,0 +1,149 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    convert::TryFrom,
+    fmt,
+    fs::File,
+    io::{BufWriter, Write},
+    path::Path,
+};
+
+use tari_core::proof_of_work::PowAlgorithm;
+
+use crate::simulator::SimulatedBlock;
+
+/// Statistics over a set of block times, in seconds
+#[derive(Debug, Clone, Default)]
+pub struct BlockTimeStats {
+    pub count: usize,
+    pub mean: f64,
+    pub median: u64,
+    pub p95: u64,
+    pub max: u64,
+}
+
+impl BlockTimeStats {
+    pub fn new(mut times: Vec<u64>) -> Self {
+        if times.is_empty() {
+            return Self::default();
+        }
+        times.sort_unstable();
+        let count = times.len();
+        Self {
+            count,
+            mean: times.iter().sum::<u64>() as f64 / count as f64,
+            median: times[count / 2],
+            p95: times[(count * 95 / 100).min(count - 1)],
+            max: times[count - 1],
+        }
+    }
+}
+
+impl fmt::Display for BlockTimeStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:>6} blocks, mean {:>7.1}s, median {:>5}s, p95 {:>5}s, max {:>6}s",
+            self.count, self.mean, self.median, self.p95, self.max
+        )
+    }
+}
+
+/// Block time statistics of a sequence of blocks, overall and per PoW algorithm
+#[derive(Debug, Clone)]
+pub struct Summary {
+    pub block_times: BlockTimeStats,
+    pub randomx: BlockTimeStats,
+    pub sha3x: BlockTimeStats,
+}
+
+impl Summary {
+    pub fn new(blocks: &[SimulatedBlock]) -> Self {
+        let algo_times = |algo: PowAlgorithm| {
+            blocks
+                .iter()
+                .filter(|b| b.algo == algo)
+                .map(|b| b.algo_block_time)
+                .collect()
+        };
+        Self {
+            block_times: BlockTimeStats::new(blocks.iter().map(|b| b.block_time).collect()),
+            randomx: BlockTimeStats::new(algo_times(PowAlgorithm::RandomX)),
+            sha3x: BlockTimeStats::new(algo_times(PowAlgorithm::Sha3x)),
+        }
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "  All:     {}", self.block_times)?;
+        writeln!(f, "  RandomX: {}", self.randomx)?;
+        writeln!(f, "  Sha3x:   {}", self.sha3x)
+    }
+}
+
+/// Prints a summary of the whole run and of every `interval` blocks, optionally alongside the historic blocks that
+/// were replayed.
+pub fn print_report(blocks: &[SimulatedBlock], interval: u64, historic: Option<&[SimulatedBlock]>) {
+    println!("Simulated block times");
+    print!("{}", Summary::new(blocks));
+    if let Some(historic) = historic {
+        println!("Historic block times");
+        print!("{}", Summary::new(historic));
+    }
+    if interval == 0 {
+        return;
+    }
+    println!();
+    println!(
+        "{:>10} {:>10} {:>12} {:>12} {:>22} {:>22}",
+        "From", "To", "Mean (s)", "RandomX (s)", "RandomX target", "Sha3x target"
+    );
+    let interval = usize::try_from(interval).unwrap_or(usize::MAX);
+    for chunk in blocks.chunks(interval) {
+        let summary = Summary::new(chunk);
+        let last_target = |algo: PowAlgorithm| {
+            chunk
+                .iter()
+                .rev()
+                .find(|b| b.algo == algo)
+                .map(|b| b.target_difficulty.as_u64().to_string())
+                .unwrap_or_else(|| "-".to_string())
+        };
+        println!(
+            "{:>10} {:>10} {:>12.1} {:>12.1} {:>22} {:>22}",
+            chunk[0].height,
+            chunk[chunk.len() - 1].height,
+            summary.block_times.mean,
+            summary.randomx.mean,
+            last_target(PowAlgorithm::RandomX),
+            last_target(PowAlgorithm::Sha3x),
+        );
+    }
+}
+
+/// Writes every simulated block to a CSV file
+pub fn write_csv<P: AsRef<Path>>(path: P, blocks: &[SimulatedBlock]) -> Result<(), anyhow::Error> {
+    let mut file = BufWriter::new(File::create(path)?);
+    writeln!(
+        file,
+        "Height,Algo,FoundAt,Timestamp,BlockTime,AlgoBlockTime,TargetDifficulty,HashRate"
+    )?;
+    for block in blocks {
+        writeln!(
+            file,
+            "{},{},{},{},{},{},{},{}",
+            block.height,
+            block.algo,
+            block.found_at,
+            block.timestamp,
+            block.block_time,
+            block.algo_block_time,
+            block.target_difficulty.as_u64(),
+            block.hash_rate
+        )?;
+    }
+    file.flush()?;
+    Ok(())
+}
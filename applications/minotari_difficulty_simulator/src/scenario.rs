@@ -0,0 +1,201 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{convert::TryFrom, fmt, str::FromStr};
+
+use rand::{rngs::StdRng, Rng};
+use tari_core::proof_of_work::PowAlgorithm;
+
+use crate::replay::HistoricHeader;
+
+/// The information available to a miner when choosing the timestamp of a block it found
+#[derive(Debug, Clone, Copy)]
+pub struct TimestampContext {
+    /// The algorithm the block was mined with
+    pub algo: PowAlgorithm,
+    /// The wall clock time at which the block was found
+    pub now: u64,
+    /// The timestamp must be greater than this median of the previous timestamps
+    pub median_timestamp: u64,
+    /// The timestamp must not exceed this future time limit
+    pub future_time_limit: u64,
+}
+
+impl TimestampContext {
+    /// The timestamp an honest miner would use
+    pub fn honest_timestamp(&self) -> u64 {
+        self.now.max(self.median_timestamp + 1)
+    }
+}
+
+/// A block production scenario
+pub trait Scenario {
+    /// The network hash rate, in hashes per second, of `algo` while mining the block at `height`
+    fn hash_rate(&self, algo: PowAlgorithm, height: u64) -> f64;
+
+    /// The timestamp placed in the header of the block described by `context`. Values outside of the consensus
+    /// limits are clamped by the simulator, as such blocks would not be accepted by the network.
+    fn timestamp(&self, context: &TimestampContext, _rng: &mut StdRng) -> u64 {
+        context.honest_timestamp()
+    }
+
+    /// The number of blocks in the scenario, if it has a natural length
+    fn num_blocks(&self) -> Option<u64> {
+        None
+    }
+}
+
+/// The initial hash rate of each algorithm
+#[derive(Debug, Clone, Copy)]
+pub struct HashRates {
+    pub randomx: f64,
+    pub sha3x: f64,
+}
+
+impl HashRates {
+    pub fn get(&self, algo: PowAlgorithm) -> f64 {
+        match algo {
+            PowAlgorithm::RandomX => self.randomx,
+            PowAlgorithm::Sha3x => self.sha3x,
+        }
+    }
+}
+
+/// Both algorithms keep a constant hash rate
+#[derive(Debug, Clone)]
+pub struct Steady {
+    pub hash_rates: HashRates,
+}
+
+impl Scenario for Steady {
+    fn hash_rate(&self, algo: PowAlgorithm, _height: u64) -> f64 {
+        self.hash_rates.get(algo)
+    }
+}
+
+/// The hash rate of one algorithm is multiplied by `factor` for the heights in `at_height..until_height`
+#[derive(Debug, Clone)]
+pub struct HashRateChange {
+    pub hash_rates: HashRates,
+    pub algo: PowAlgorithm,
+    pub at_height: u64,
+    pub until_height: Option<u64>,
+    pub factor: f64,
+}
+
+impl Scenario for HashRateChange {
+    fn hash_rate(&self, algo: PowAlgorithm, height: u64) -> f64 {
+        let hash_rate = self.hash_rates.get(algo);
+        let is_changed = height >= self.at_height && self.until_height.map_or(true, |until| height < until);
+        if algo == self.algo && is_changed {
+            hash_rate * self.factor
+        } else {
+            hash_rate
+        }
+    }
+}
+
+/// How a dishonest miner chooses its timestamps
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManipulationStrategy {
+    /// Timestamps as far in the future as the network accepts, lowering the difficulty
+    Forward,
+    /// Timestamps just after the median timestamp, raising the difficulty for honest miners
+    Backward,
+}
+
+impl FromStr for ManipulationStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "forward" => Ok(Self::Forward),
+            "backward" => Ok(Self::Backward),
+            other => Err(format!("unknown timestamp manipulation strategy '{}'", other)),
+        }
+    }
+}
+
+impl fmt::Display for ManipulationStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Forward => write!(f, "forward"),
+            Self::Backward => write!(f, "backward"),
+        }
+    }
+}
+
+/// A miner controlling `share` of the hash rate of `algo` manipulates the timestamps of the blocks it finds
+#[derive(Debug, Clone)]
+pub struct TimestampManipulation {
+    pub hash_rates: HashRates,
+    pub algo: PowAlgorithm,
+    pub share: f64,
+    pub strategy: ManipulationStrategy,
+}
+
+impl Scenario for TimestampManipulation {
+    fn hash_rate(&self, algo: PowAlgorithm, _height: u64) -> f64 {
+        self.hash_rates.get(algo)
+    }
+
+    fn timestamp(&self, context: &TimestampContext, rng: &mut StdRng) -> u64 {
+        if context.algo != self.algo || !rng.gen_bool(self.share.clamp(0.0, 1.0)) {
+            return context.honest_timestamp();
+        }
+        match self.strategy {
+            ManipulationStrategy::Forward => context.future_time_limit,
+            ManipulationStrategy::Backward => context.median_timestamp + 1,
+        }
+    }
+}
+
+/// Hash rates derived from historic headers. The hash rate of an algorithm at a height is estimated from the most
+/// recent target difficulty of that algorithm, which is the hash rate the network expected at the time.
+#[derive(Debug, Clone)]
+pub struct HistoricHashRates {
+    randomx: Vec<f64>,
+    sha3x: Vec<f64>,
+}
+
+impl HistoricHashRates {
+    pub fn new(headers: &[HistoricHeader], randomx_target_time: u64, sha3x_target_time: u64) -> Self {
+        let first_of = |algo: PowAlgorithm| {
+            headers
+                .iter()
+                .find(|h| h.algo == algo)
+                .map(|h| h.target_difficulty as f64)
+                .unwrap_or_default()
+        };
+        let mut randomx_difficulty = first_of(PowAlgorithm::RandomX);
+        let mut sha3x_difficulty = first_of(PowAlgorithm::Sha3x);
+        let mut randomx = Vec::with_capacity(headers.len());
+        let mut sha3x = Vec::with_capacity(headers.len());
+        for header in headers {
+            match header.algo {
+                PowAlgorithm::RandomX => randomx_difficulty = header.target_difficulty as f64,
+                PowAlgorithm::Sha3x => sha3x_difficulty = header.target_difficulty as f64,
+            }
+            randomx.push(randomx_difficulty / randomx_target_time as f64);
+            sha3x.push(sha3x_difficulty / sha3x_target_time as f64);
+        }
+        Self { randomx, sha3x }
+    }
+}
+
+impl Scenario for HistoricHashRates {
+    fn hash_rate(&self, algo: PowAlgorithm, height: u64) -> f64 {
+        let rates = match algo {
+            PowAlgorithm::RandomX => &self.randomx,
+            PowAlgorithm::Sha3x => &self.sha3x,
+        };
+        let index = usize::try_from(height)
+            .unwrap_or(usize::MAX)
+            .min(rates.len().saturating_sub(1));
+        rates.get(index).copied().unwrap_or_default()
+    }
+
+    fn num_blocks(&self) -> Option<u64> {
+        Some(self.randomx.len() as u64)
+    }
+}
@@ -0,0 +1,306 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::collections::VecDeque;
+
+use anyhow::anyhow;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use tari_core::{
+    chain_storage::TargetDifficulties,
+    consensus::ConsensusConstants,
+    proof_of_work::{Difficulty, DifficultyAdjustment, PowAlgorithm},
+};
+use tari_utilities::epoch_time::EpochTime;
+
+use crate::scenario::{Scenario, TimestampContext};
+
+const ALGOS: [PowAlgorithm; 2] = [PowAlgorithm::RandomX, PowAlgorithm::Sha3x];
+
+/// A block produced by the simulation
+#[derive(Debug, Clone)]
+pub struct SimulatedBlock {
+    pub height: u64,
+    pub algo: PowAlgorithm,
+    /// The wall clock time at which the block was found
+    pub found_at: u64,
+    /// The timestamp placed in the block header
+    pub timestamp: u64,
+    /// Wall clock seconds since the previous block
+    pub block_time: u64,
+    /// Wall clock seconds since the previous block of the same algorithm
+    pub algo_block_time: u64,
+    /// The target difficulty the block was mined at
+    pub target_difficulty: Difficulty,
+    /// The network hash rate of the algorithm while the block was mined
+    pub hash_rate: f64,
+}
+
+/// Consensus parameters of one algorithm
+#[derive(Debug, Clone, Copy)]
+pub struct AlgoParams {
+    pub target_time: u64,
+    pub min_difficulty: Difficulty,
+    pub max_difficulty: Difficulty,
+}
+
+impl AlgoParams {
+    pub fn from_constants(constants: &ConsensusConstants, algo: PowAlgorithm) -> Self {
+        Self {
+            target_time: constants.pow_target_block_interval(algo),
+            min_difficulty: constants.min_pow_difficulty(algo),
+            max_difficulty: constants.max_pow_difficulty(algo),
+        }
+    }
+}
+
+/// Simulates block production for both PoW algorithms, using [TargetDifficulties] to retarget after every block.
+/// The time taken by an algorithm to find a block is exponentially distributed with a mean of the target difficulty
+/// divided by the hash rate. The algorithm that finds a block first wins it.
+pub struct Simulator<T> {
+    targets: TargetDifficulties<T>,
+    randomx: AlgoParams,
+    sha3x: AlgoParams,
+    future_time_limit: u64,
+    median_timestamp_count: usize,
+    rng: StdRng,
+    now: u64,
+    timestamps: VecDeque<u64>,
+    last_found: [u64; 2],
+}
+
+impl<T: DifficultyAdjustment> Simulator<T> {
+    pub fn new(targets: TargetDifficulties<T>, constants: &ConsensusConstants, seed: u64) -> Self {
+        Self {
+            targets,
+            randomx: AlgoParams::from_constants(constants, PowAlgorithm::RandomX),
+            sha3x: AlgoParams::from_constants(constants, PowAlgorithm::Sha3x),
+            future_time_limit: constants.future_time_limit(),
+            median_timestamp_count: constants.median_timestamp_count(),
+            rng: StdRng::seed_from_u64(seed),
+            now: 0,
+            timestamps: VecDeque::new(),
+            last_found: [0; 2],
+        }
+    }
+
+    pub fn params(&self, algo: PowAlgorithm) -> &AlgoParams {
+        match algo {
+            PowAlgorithm::RandomX => &self.randomx,
+            PowAlgorithm::Sha3x => &self.sha3x,
+        }
+    }
+
+    /// Fills the difficulty windows with blocks found exactly on target at the initial hash rates of the scenario,
+    /// so that the simulation starts in equilibrium.
+    pub fn warm_up<S: Scenario + ?Sized>(&mut self, scenario: &S) -> Result<(), anyhow::Error> {
+        let mut warm_up_blocks = Vec::new();
+        for algo in ALGOS {
+            let params = *self.params(algo);
+            let difficulty = self.clamp_difficulty(algo, scenario.hash_rate(algo, 0) * params.target_time as f64);
+            while !self.targets.is_algo_full(algo) {
+                let timestamp = self.targets.get(algo).len() as u64 * params.target_time;
+                self.targets
+                    .add_back_for_algo(algo, EpochTime::from(timestamp), difficulty)
+                    .map_err(|e| anyhow!("Could not warm up the {} difficulty window: {}", algo, e))?;
+                warm_up_blocks.push((timestamp, algo));
+            }
+        }
+        warm_up_blocks.sort_unstable_by_key(|(timestamp, _)| *timestamp);
+        for (timestamp, algo) in warm_up_blocks {
+            self.push_timestamp(timestamp);
+            self.now = self.now.max(timestamp);
+            self.last_found[algo.as_u64() as usize] = timestamp;
+        }
+        Ok(())
+    }
+
+    /// Mines the block at `height`
+    pub fn next_block<S: Scenario + ?Sized>(
+        &mut self,
+        scenario: &S,
+        height: u64,
+    ) -> Result<SimulatedBlock, anyhow::Error> {
+        let mut winner = None;
+        for algo in ALGOS {
+            let hash_rate = scenario.hash_rate(algo, height);
+            if hash_rate <= 0.0 {
+                continue;
+            }
+            let params = self.params(algo);
+            let target_difficulty = self
+                .targets
+                .get(algo)
+                .calculate(params.min_difficulty, params.max_difficulty);
+            let mean_solve_time = target_difficulty.as_u64() as f64 / hash_rate;
+            // Inverse transform sampling of the exponential distribution
+            let solve_time = -(1.0 - self.rng.gen::<f64>()).ln() * mean_solve_time;
+            if winner.map_or(true, |(_, t, _, _)| solve_time < t) {
+                winner = Some((algo, solve_time, target_difficulty, hash_rate));
+            }
+        }
+        let (algo, solve_time, target_difficulty, hash_rate) =
+            winner.ok_or_else(|| anyhow!("No hash rate for any algorithm at height {}", height))?;
+
+        let block_time = (solve_time.ceil() as u64).max(1);
+        let previous = self.now;
+        self.now += block_time;
+        let context = TimestampContext {
+            algo,
+            now: self.now,
+            median_timestamp: self.median_timestamp(),
+            future_time_limit: self.now + self.future_time_limit,
+        };
+        let timestamp = scenario
+            .timestamp(&context, &mut self.rng)
+            .clamp(context.median_timestamp + 1, context.future_time_limit);
+        self.targets
+            .add_back_for_algo(algo, EpochTime::from(timestamp), target_difficulty)
+            .map_err(|e| anyhow!("Difficulty adjustment failed at height {}: {}", height, e))?;
+        self.push_timestamp(timestamp);
+
+        let last_found = &mut self.last_found[algo.as_u64() as usize];
+        let algo_block_time = self.now - *last_found;
+        *last_found = self.now;
+
+        Ok(SimulatedBlock {
+            height,
+            algo,
+            found_at: self.now,
+            timestamp,
+            block_time: self.now - previous,
+            algo_block_time,
+            target_difficulty,
+            hash_rate,
+        })
+    }
+
+    /// Runs the scenario for `num_blocks` blocks
+    pub fn run<S: Scenario + ?Sized>(
+        &mut self,
+        scenario: &S,
+        num_blocks: u64,
+    ) -> Result<Vec<SimulatedBlock>, anyhow::Error> {
+        self.warm_up(scenario)?;
+        (0..num_blocks)
+            .map(|height| self.next_block(scenario, height))
+            .collect()
+    }
+
+    fn clamp_difficulty(&self, algo: PowAlgorithm, difficulty: f64) -> Difficulty {
+        let params = self.params(algo);
+        let difficulty = difficulty.clamp(
+            params.min_difficulty.as_u64() as f64,
+            params.max_difficulty.as_u64() as f64,
+        );
+        Difficulty::from_u64(difficulty as u64).unwrap_or(params.min_difficulty)
+    }
+
+    fn push_timestamp(&mut self, timestamp: u64) {
+        if self.timestamps.len() == self.median_timestamp_count {
+            self.timestamps.pop_front();
+        }
+        self.timestamps.push_back(timestamp);
+    }
+
+    fn median_timestamp(&self) -> u64 {
+        let mut timestamps = self.timestamps.iter().copied().collect::<Vec<_>>();
+        timestamps.sort_unstable();
+        timestamps.get(timestamps.len() / 2).copied().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_common::configuration::Network;
+    use tari_core::consensus::ConsensusManager;
+
+    use super::*;
+    use crate::{
+        report::Summary,
+        scenario::{HashRateChange, HashRates, ManipulationStrategy, Steady, TimestampManipulation},
+    };
+
+    const HASH_RATES: HashRates = HashRates {
+        randomx: 500_000_000.0,
+        sha3x: 500_000_000_000_000.0,
+    };
+
+    fn simulator() -> Simulator<tari_core::proof_of_work::lwma_diff::LinearWeightedMovingAverage> {
+        let rules = ConsensusManager::builder(Network::MainNet).build().unwrap();
+        let targets = TargetDifficulties::new(&rules, 0).unwrap();
+        Simulator::new(targets, rules.consensus_constants(0), 1)
+    }
+
+    fn mean_algo_block_time(blocks: &[SimulatedBlock], algo: PowAlgorithm) -> f64 {
+        let times = blocks
+            .iter()
+            .filter(|b| b.algo == algo)
+            .map(|b| b.algo_block_time as f64)
+            .collect::<Vec<_>>();
+        times.iter().sum::<f64>() / times.len() as f64
+    }
+
+    #[test]
+    fn it_keeps_steady_hash_rates_on_target() {
+        let mut simulator = simulator();
+        let blocks = simulator.run(&Steady { hash_rates: HASH_RATES }, 5_000).unwrap();
+        let summary = Summary::new(&blocks);
+        // Mainnet targets a 120s block time, split evenly between the algorithms
+        assert!(
+            (summary.block_times.mean - 120.0).abs() < 12.0,
+            "{}",
+            summary.block_times.mean
+        );
+        for algo in ALGOS {
+            let mean = mean_algo_block_time(&blocks, algo);
+            assert!((mean - 240.0).abs() < 24.0, "{}: {}", algo, mean);
+        }
+    }
+
+    #[test]
+    fn it_recovers_from_a_hash_rate_drop() {
+        let mut simulator = simulator();
+        let scenario = HashRateChange {
+            hash_rates: HASH_RATES,
+            algo: PowAlgorithm::Sha3x,
+            at_height: 1_000,
+            until_height: None,
+            factor: 0.5,
+        };
+        let blocks = simulator.run(&scenario, 5_000).unwrap();
+        let first_sha3x_target = blocks
+            .iter()
+            .find(|b| b.algo == PowAlgorithm::Sha3x)
+            .unwrap()
+            .target_difficulty;
+        let last_sha3x_target = blocks
+            .iter()
+            .rev()
+            .find(|b| b.algo == PowAlgorithm::Sha3x)
+            .unwrap()
+            .target_difficulty;
+        assert!(last_sha3x_target < first_sha3x_target);
+        let mean = mean_algo_block_time(&blocks[3_000..], PowAlgorithm::Sha3x);
+        assert!((mean - 240.0).abs() < 36.0, "{}", mean);
+    }
+
+    #[test]
+    fn it_clamps_manipulated_timestamps_to_consensus_limits() {
+        let mut simulator = simulator();
+        let scenario = TimestampManipulation {
+            hash_rates: HASH_RATES,
+            algo: PowAlgorithm::RandomX,
+            share: 1.0,
+            strategy: ManipulationStrategy::Forward,
+        };
+        let blocks = simulator.run(&scenario, 1_000).unwrap();
+        let ftl = ConsensusManager::builder(Network::MainNet)
+            .build()
+            .unwrap()
+            .consensus_constants(0)
+            .future_time_limit();
+        for block in blocks.iter().filter(|b| b.algo == PowAlgorithm::RandomX) {
+            assert!(block.timestamp <= block.found_at + ftl);
+        }
+    }
+}
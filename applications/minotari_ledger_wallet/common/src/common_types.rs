@@ -109,6 +109,9 @@ pub enum Branch {
     Spend = 0x07,
     RandomKey = 0x08,
     PreMine = 0x09,
+    MultiSig = 0x0a,
+    AccountView = 0x0b,
+    AccountSpend = 0x0c,
 }
 
 impl Branch {
@@ -128,6 +131,9 @@ impl Branch {
             0x07 => Some(Branch::Spend),
             0x08 => Some(Branch::RandomKey),
             0x09 => Some(Branch::PreMine),
+            0x0a => Some(Branch::MultiSig),
+            0x0b => Some(Branch::AccountView),
+            0x0c => Some(Branch::AccountSpend),
             _ => None,
         }
     }
@@ -298,6 +304,9 @@ mod test {
             (0x07, Branch::Spend),
             (0x08, Branch::RandomKey),
             (0x09, Branch::PreMine),
+            (0x0a, Branch::MultiSig),
+            (0x0b, Branch::AccountView),
+            (0x0c, Branch::AccountSpend),
         ];
 
         for (expected_byte, branch) in &mappings {
@@ -342,6 +351,18 @@ mod test {
                     assert_eq!(branch.as_byte(), *expected_byte);
                     assert_eq!(Branch::from_byte(*expected_byte), Some(*branch));
                 },
+                Branch::MultiSig => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(Branch::from_byte(*expected_byte), Some(*branch));
+                },
+                Branch::AccountView => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(Branch::from_byte(*expected_byte), Some(*branch));
+                },
+                Branch::AccountSpend => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(Branch::from_byte(*expected_byte), Some(*branch));
+                },
             }
         }
     }
@@ -51,6 +51,7 @@ mod rewind_blockchain;
 mod search_kernel;
 mod search_utxo;
 mod status;
+mod sync_peers;
 mod test_peer_liveness;
 mod unban_all_peers;
 mod version;
@@ -114,6 +115,7 @@ pub enum Command {
     GetDbStats(get_db_stats::Args),
     GetPeer(get_peer::Args),
     ListPeers(list_peers::Args),
+    SyncPeers(sync_peers::Args),
     DialPeer(dial_peer::Args),
     PingPeer(ping_peer::Args),
     ResetOfflinePeers(reset_offline_peers::Args),
@@ -219,6 +221,7 @@ impl CommandContext {
                 Command::PingPeer(_) |
                 Command::DiscoverPeer(_) |
                 Command::ListPeers(_) |
+                Command::SyncPeers(_) |
                 Command::ListBannedPeers(_) |
                 Command::ListConnections(_) |
                 Command::GetNetworkStats(_) |
@@ -280,6 +283,7 @@ impl HandleCommand<Command> for CommandContext {
             Command::GetStateInfo(args) => self.handle_command(args).await,
             Command::GetNetworkStats(args) => self.handle_command(args).await,
             Command::ListPeers(args) => self.handle_command(args).await,
+            Command::SyncPeers(args) => self.handle_command(args).await,
             Command::DialPeer(args) => self.handle_command(args).await,
             Command::PingPeer(args) => self.handle_command(args).await,
             Command::AddPeer(args) => self.handle_command(args).await,
@@ -28,6 +28,7 @@ use chrono::{DateTime, Utc};
 use clap::Parser;
 use minotari_app_utilities::consts;
 use tari_comms::connection_manager::SelfLivenessStatus;
+use tari_core::base_node::sync::{format_duration, SyncProgress};
 use tokio::time;
 
 use super::{CommandContext, HandleCommand};
@@ -62,6 +63,10 @@ impl CommandContext {
         status_line.add_field("", format!("v{}", consts::APP_VERSION_NUMBER));
         status_line.add_field("", self.config.network());
         status_line.add_field("State", self.state_machine_info.borrow().state_info.short_desc());
+        let sync_progress = self.state_machine_info.borrow().state_info.sync_progress().cloned();
+        if let Some(progress) = sync_progress {
+            status_line.add_field("Sync", format_sync_progress(&progress));
+        }
 
         let metadata = self.node_service.get_metadata().await?;
         let height = metadata.best_block_height();
@@ -159,3 +164,16 @@ impl CommandContext {
         Ok(())
     }
 }
+
+/// A compact summary of the rates and time split of a sync stage, the position is already part of the state
+fn format_sync_progress(progress: &SyncProgress) -> String {
+    let elapsed = progress.elapsed.as_secs_f64().max(f64::EPSILON);
+    format!(
+        "{} {:.2} KiB/s, net {:.0}%/val {:.0}%, ETA {}",
+        progress.stage,
+        progress.bytes_per_second().unwrap_or_default() / 1024.0,
+        progress.network_time.as_secs_f64() / elapsed * 100.0,
+        progress.validation_time.as_secs_f64() / elapsed * 100.0,
+        progress.eta().map(format_duration).unwrap_or_else(|| "--".to_string()),
+    )
+}
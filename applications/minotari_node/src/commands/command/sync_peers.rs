@@ -0,0 +1,93 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::time::Duration;
+
+use anyhow::Error;
+use async_trait::async_trait;
+use clap::Parser;
+use tari_comms::peer_manager::PeerQuery;
+use tari_core::base_node::sync::{SyncPeerStats, SYNC_PEER_STATS_METADATA_KEY};
+use tari_utilities::epoch_time::EpochTime;
+
+use super::{CommandContext, HandleCommand};
+use crate::{table::Table, utils::format_duration_basic};
+
+/// Lists the peers this node has synced from, ranked by their historic sync performance
+#[derive(Debug, Parser)]
+pub struct Args {
+    /// The maximum number of peers to list
+    #[clap(short, long)]
+    limit: Option<usize>,
+}
+
+#[async_trait]
+impl HandleCommand<Args> for CommandContext {
+    async fn handle_command(&mut self, args: Args) -> Result<(), Error> {
+        self.list_sync_peers(args.limit).await
+    }
+}
+
+impl CommandContext {
+    pub async fn list_sync_peers(&self, limit: Option<usize>) -> Result<(), Error> {
+        let query = PeerQuery::new().select_where(|p| p.get_metadata(SYNC_PEER_STATS_METADATA_KEY).is_some());
+        let peers = self.comms.peer_manager().perform_query(query).await?;
+        let mut ranked = peers
+            .into_iter()
+            .filter_map(|peer| SyncPeerStats::from_peer(&peer).map(|stats| (peer, stats)))
+            .collect::<Vec<_>>();
+        ranked.sort_by(|(_, a), (_, b)| b.score().partial_cmp(&a.score()).unwrap_or(std::cmp::Ordering::Equal));
+        let num_peers = ranked.len();
+        let now = EpochTime::now().as_u64();
+
+        let mut table = Table::new();
+        table.set_titles(vec![
+            "Rank",
+            "NodeId",
+            "Score",
+            "Succeeded",
+            "Bans",
+            "Shortfalls",
+            "Throughput",
+            "Last attempt",
+            "Info",
+        ]);
+        for (rank, (peer, stats)) in ranked.into_iter().take(limit.unwrap_or(usize::MAX)).enumerate() {
+            let mut info = vec![];
+            if peer.is_banned() {
+                info.push("BANNED");
+            }
+            if peer.is_offline() {
+                info.push("OFFLINE");
+            }
+            table.add_row(row![
+                rank + 1,
+                peer.node_id,
+                format!("{:.2}", stats.score()),
+                format!("{}/{}", stats.successes, stats.attempts),
+                stats.bans,
+                stats.difficulty_shortfalls,
+                stats
+                    .items_per_second()
+                    .map(|rate| format!("{:.2} items/s", rate))
+                    .unwrap_or_else(|| "--".to_string()),
+                stats
+                    .last_attempt
+                    .map(|t| format!(
+                        "{} ago",
+                        format_duration_basic(Duration::from_secs(now.saturating_sub(t.as_u64())))
+                    ))
+                    .unwrap_or_else(|| "--".to_string()),
+                if info.is_empty() {
+                    "--".to_string()
+                } else {
+                    info.join(", ")
+                },
+            ]);
+        }
+        table.print_stdout();
+
+        println!("{} sync peer(s) with recorded sync history", num_peers);
+        Ok(())
+    }
+}
@@ -79,7 +79,7 @@ use crate::{
     builder::BaseNodeContext,
     grpc::{
         blocks::{block_fees, block_heights, block_size, GET_BLOCKS_MAX_HEIGHTS, GET_BLOCKS_PAGE_SIZE},
-        hash_rate::HashRateMovingAverage,
+        hash_rate::{AlgoHashRate, HashRateInterval, HashRateMovingAverage},
         helpers::{mean, median},
     },
     grpc_method::GrpcMethod,
@@ -94,6 +94,10 @@ const GET_TOKENS_IN_CIRCULATION_PAGE_SIZE: usize = 1_000;
 // number here to keep the node busy
 const GET_DIFFICULTY_MAX_HEIGHTS: u64 = 10_000;
 const GET_DIFFICULTY_PAGE_SIZE: usize = 1_000;
+// The maximum number of heights that can be summarised by a single GetHashRateHistory request
+const HASH_RATE_HISTORY_MAX_HEIGHTS: u64 = 100_000;
+// The number of blocks summarised by each hash rate history response if none is provided, about an hour of blocks
+const HASH_RATE_HISTORY_DEFAULT_INTERVAL: u64 = 30;
 // The maximum number of headers a client can request at a time. If the client requests more than
 // this, this is the maximum that will be returned.
 const LIST_HEADERS_MAX_NUM_HEADERS: u64 = 10_000;
@@ -197,6 +201,24 @@ pub fn obscure_error_if_true(report: bool, status: Status) -> Status {
     }
 }
 
+fn hash_rate_history_response(interval: &HashRateInterval) -> tari_rpc::HashRateHistoryResponse {
+    let to_grpc = |hash_rate: AlgoHashRate| tari_rpc::PowAlgoHashRate {
+        num_blocks: hash_rate.num_blocks,
+        estimated_hash_rate: hash_rate.estimated_hash_rate,
+        achieved_hash_rate: hash_rate.achieved_hash_rate,
+    };
+    tari_rpc::HashRateHistoryResponse {
+        start_height: interval.start_height(),
+        end_height: interval.end_height(),
+        start_timestamp: interval.start_timestamp(),
+        end_timestamp: interval.end_timestamp(),
+        randomx: Some(to_grpc(interval.hash_rate(PowAlgorithm::RandomX))),
+        sha3x: Some(to_grpc(interval.hash_rate(PowAlgorithm::Sha3x))),
+        merge_mined_randomx_blocks: interval.merge_mined_randomx_blocks(),
+        merge_mined_randomx_share: interval.merge_mined_randomx_share(),
+    }
+}
+
 pub async fn get_heights(
     request: &tari_rpc::HeightRequest,
     handler: LocalNodeCommsInterface,
@@ -210,6 +232,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
     type FetchMatchingUtxosStream = mpsc::Receiver<Result<tari_rpc::FetchMatchingUtxosResponse, Status>>;
     type GetActiveValidatorNodesStream = mpsc::Receiver<Result<tari_rpc::GetActiveValidatorNodesResponse, Status>>;
     type GetBlocksStream = mpsc::Receiver<Result<tari_rpc::HistoricalBlock, Status>>;
+    type GetHashRateHistoryStream = mpsc::Receiver<Result<tari_rpc::HashRateHistoryResponse, Status>>;
     type GetMempoolTransactionsStream = mpsc::Receiver<Result<tari_rpc::GetMempoolTransactionsResponse, Status>>;
     type GetNetworkDifficultyStream = mpsc::Receiver<Result<tari_rpc::NetworkDifficultyResponse, Status>>;
     type GetPeersStream = mpsc::Receiver<Result<tari_rpc::GetPeersResponse, Status>>;
@@ -364,6 +387,119 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
         Ok(Response::new(rx))
     }
 
+    async fn get_hash_rate_history(
+        &self,
+        request: Request<tari_rpc::HashRateHistoryRequest>,
+    ) -> Result<Response<Self::GetHashRateHistoryStream>, Status> {
+        self.check_method_enabled(GrpcMethod::GetHashRateHistory)?;
+        let report_error_flag = self.report_error_flag();
+        let request = request.into_inner();
+        trace!(
+            target: LOG_TARGET,
+            "Incoming GRPC request for GetHashRateHistory: from_tip: {:?} start_height: {:?} end_height: {:?} \
+             interval: {:?}",
+            request.from_tip,
+            request.start_height,
+            request.end_height,
+            request.interval
+        );
+        let mut handler = self.node_service.clone();
+        let (start_height, end_height) = block_heights(
+            handler.clone(),
+            request.start_height,
+            request.end_height,
+            request.from_tip,
+        )
+        .await
+        .map_err(|e| obscure_error_if_true(report_error_flag, e))?;
+        let num_requested = end_height.saturating_sub(start_height);
+        if num_requested > HASH_RATE_HISTORY_MAX_HEIGHTS {
+            return Err(obscure_error_if_true(
+                report_error_flag,
+                Status::invalid_argument(format!(
+                    "Number of heights requested exceeds maximum. Expected less than {} but got {}",
+                    HASH_RATE_HISTORY_MAX_HEIGHTS, num_requested
+                )),
+            ));
+        }
+        let interval = match request.interval {
+            0 => HASH_RATE_HISTORY_DEFAULT_INTERVAL,
+            interval => interval,
+        };
+
+        // The first interval starts at the timestamp of the block preceding it
+        let start_timestamp = match handler.get_header(start_height.saturating_sub(1)).await {
+            Ok(Some(header)) => header.header().timestamp.as_u64(),
+            Ok(None) => {
+                return Err(obscure_error_if_true(
+                    report_error_flag,
+                    Status::not_found(format!("Header not found at height {}", start_height.saturating_sub(1))),
+                ))
+            },
+            Err(err) => {
+                warn!(target: LOG_TARGET, "Base node service error: {:?}", err);
+                return Err(obscure_error_if_true(
+                    report_error_flag,
+                    Status::internal(format!("Error fetching header: {}", err)),
+                ));
+            },
+        };
+
+        let page_iter =
+            NonOverlappingIntegerPairIter::new(start_height, end_height.saturating_add(1), GET_DIFFICULTY_PAGE_SIZE)
+                .map_err(|e| obscure_error_if_true(report_error_flag, Status::invalid_argument(e)))?;
+        let consensus_rules = self.consensus_rules.clone();
+        let (mut tx, rx) = mpsc::channel(GET_DIFFICULTY_PAGE_SIZE);
+
+        debug!(
+            target: LOG_TARGET,
+            "Starting GetHashRateHistory request from {} to {} in intervals of {} blocks",
+            start_height,
+            end_height,
+            interval
+        );
+        task::spawn(async move {
+            let mut current = HashRateInterval::new(consensus_rules.clone(), start_timestamp);
+            for (start, end) in page_iter {
+                let headers = match handler.get_headers(start..=end).await {
+                    Ok(headers) => headers,
+                    Err(err) => {
+                        warn!(target: LOG_TARGET, "Base node service error: {:?}", err,);
+                        let _ = tx
+                            .send(Err(obscure_error_if_true(
+                                report_error_flag,
+                                Status::internal("Internal error when fetching headers"),
+                            )))
+                            .await;
+                        return;
+                    },
+                };
+
+                for chain_header in &headers {
+                    current.add(chain_header.header(), chain_header.accumulated_data().target_difficulty);
+                    if current.num_blocks() < interval {
+                        continue;
+                    }
+                    let next = HashRateInterval::new(consensus_rules.clone(), current.end_timestamp());
+                    let response = hash_rate_history_response(&std::mem::replace(&mut current, next));
+                    if let Err(err) = tx.send(Ok(response)).await {
+                        warn!(target: LOG_TARGET, "Error sending hash rate history via GRPC: {}", err);
+                        return;
+                    }
+                }
+            }
+            // Send the last, partial interval
+            if current.num_blocks() > 0 {
+                if let Err(err) = tx.send(Ok(hash_rate_history_response(&current))).await {
+                    warn!(target: LOG_TARGET, "Error sending hash rate history via GRPC: {}", err);
+                }
+            }
+        });
+
+        trace!(target: LOG_TARGET, "Sending GetHashRateHistory response stream to client");
+        Ok(Response::new(rx))
+    }
+
     async fn get_mempool_transactions(
         &self,
         request: Request<tari_rpc::GetMempoolTransactionsRequest>,
@@ -2058,6 +2194,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
             .state_info
             .clone();
         let short_desc = state.short_desc();
+        let stage_progress = state.sync_progress().map(tari_rpc::SyncStageProgress::from);
         let response = match state {
             StateInfo::HeaderSync(None) => tari_rpc::SyncProgressResponse {
                 tip_height: 0,
@@ -2065,6 +2202,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 state: tari_rpc::SyncState::HeaderStarting.into(),
                 short_desc,
                 initial_connected_peers: 0,
+                stage_progress,
             },
             StateInfo::HeaderSync(Some(info)) => tari_rpc::SyncProgressResponse {
                 tip_height: info.tip_height,
@@ -2072,6 +2210,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 state: tari_rpc::SyncState::Header.into(),
                 short_desc,
                 initial_connected_peers: 0,
+                stage_progress,
             },
             StateInfo::Connecting(_) => tari_rpc::SyncProgressResponse {
                 tip_height: 0,
@@ -2079,6 +2218,15 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 state: tari_rpc::SyncState::BlockStarting.into(),
                 short_desc,
                 initial_connected_peers: 0,
+                stage_progress,
+            },
+            StateInfo::HorizonSync(_) => tari_rpc::SyncProgressResponse {
+                tip_height: 0,
+                local_height: 0,
+                state: tari_rpc::SyncState::Horizon.into(),
+                short_desc,
+                initial_connected_peers: 0,
+                stage_progress,
             },
             StateInfo::BlockSync(info) => tari_rpc::SyncProgressResponse {
                 tip_height: info.tip_height,
@@ -2086,6 +2234,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 state: tari_rpc::SyncState::Block.into(),
                 short_desc,
                 initial_connected_peers: 0,
+                stage_progress,
             },
             _ => tari_rpc::SyncProgressResponse {
                 tip_height: 0,
@@ -2097,6 +2246,7 @@ impl tari_rpc::base_node_server::BaseNode for BaseNodeGrpcServer {
                 },
                 short_desc,
                 initial_connected_peers: state.get_initial_connected_peers(),
+                stage_progress,
             },
         };
         Ok(Response::new(response))
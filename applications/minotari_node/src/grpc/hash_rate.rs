@@ -20,11 +20,12 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::collections::VecDeque;
+use std::{collections::VecDeque, convert::TryFrom};
 
 use tari_core::{
+    blocks::BlockHeader,
     consensus::ConsensusManager,
-    proof_of_work::{Difficulty, PowAlgorithm},
+    proof_of_work::{monero_rx::MoneroPowData, Difficulty, PowAlgorithm},
 };
 
 /// The number of past blocks to be used on moving averages for (smooth) estimated hashrate
@@ -100,15 +101,179 @@ impl HashRateMovingAverage {
     }
 }
 
+/// The hash rate estimates of one PoW algorithm over an interval of blocks
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AlgoHashRate {
+    /// The number of blocks mined with the algorithm in the interval
+    pub num_blocks: u64,
+    /// The mean of the target difficulty divided by the target block time of the blocks in the interval, i.e. the hash
+    /// rate the difficulty adjustment algorithm expected
+    pub estimated_hash_rate: u64,
+    /// The total target difficulty of the blocks mined in the interval divided by the time the interval took, i.e. the
+    /// hash rate the network achieved
+    pub achieved_hash_rate: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+struct AlgoAccumulator {
+    num_blocks: u64,
+    sum_expected_hash_rates: u128,
+    sum_target_difficulties: u128,
+}
+
+impl AlgoAccumulator {
+    fn to_hash_rate(&self, duration: u64) -> AlgoHashRate {
+        let estimated_hash_rate = self
+            .sum_expected_hash_rates
+            .checked_div(u128::from(self.num_blocks))
+            .unwrap_or_default();
+        let achieved_hash_rate = self
+            .sum_target_difficulties
+            .checked_div(u128::from(duration))
+            .unwrap_or_default();
+        AlgoHashRate {
+            num_blocks: self.num_blocks,
+            estimated_hash_rate: u64::try_from(estimated_hash_rate).unwrap_or(u64::MAX),
+            achieved_hash_rate: u64::try_from(achieved_hash_rate).unwrap_or(u64::MAX),
+        }
+    }
+}
+
+/// Summarises the hash rate of both PoW algorithms over an interval of consecutive blocks
+#[derive(Debug, Clone)]
+pub struct HashRateInterval {
+    consensus_manager: ConsensusManager,
+    start_height: Option<u64>,
+    end_height: u64,
+    start_timestamp: u64,
+    end_timestamp: u64,
+    randomx: AlgoAccumulator,
+    sha3x: AlgoAccumulator,
+    merge_mined_randomx_blocks: u64,
+}
+
+impl HashRateInterval {
+    /// Starts a new interval, `start_timestamp` is the timestamp of the block preceding the interval
+    pub fn new(consensus_manager: ConsensusManager, start_timestamp: u64) -> Self {
+        Self {
+            consensus_manager,
+            start_height: None,
+            end_height: 0,
+            start_timestamp,
+            end_timestamp: start_timestamp,
+            randomx: AlgoAccumulator::default(),
+            sha3x: AlgoAccumulator::default(),
+            merge_mined_randomx_blocks: 0,
+        }
+    }
+
+    /// Adds the next block in the interval
+    pub fn add(&mut self, header: &BlockHeader, target_difficulty: Difficulty) {
+        let is_merge_mined = header.pow_algo() == PowAlgorithm::RandomX && self.is_merge_mined(header);
+        self.add_block(
+            header.height,
+            header.timestamp.as_u64(),
+            header.pow_algo(),
+            target_difficulty,
+            is_merge_mined,
+        );
+    }
+
+    /// A RandomX block is considered merge mined if its coinbase merkle proof shows the Monero block contains Monero
+    /// transactions, or its merge mining tag commits to other aux chains besides Tari, i.e. it was mined on a template
+    /// from a Monero node or a merge mining proxy rather than on a template made only to mine Tari.
+    fn is_merge_mined(&self, header: &BlockHeader) -> bool {
+        MoneroPowData::from_header(header, &self.consensus_manager)
+            .map(|pow_data| pow_data.has_monero_transactions() || pow_data.aux_chain_count() > 1)
+            .unwrap_or(false)
+    }
+
+    fn add_block(
+        &mut self,
+        height: u64,
+        timestamp: u64,
+        pow_algo: PowAlgorithm,
+        target_difficulty: Difficulty,
+        is_merge_mined: bool,
+    ) {
+        let target_time = self
+            .consensus_manager
+            .consensus_constants(height)
+            .pow_target_block_interval(pow_algo);
+        let accumulator = match pow_algo {
+            PowAlgorithm::RandomX => &mut self.randomx,
+            PowAlgorithm::Sha3x => &mut self.sha3x,
+        };
+        accumulator.num_blocks += 1;
+        accumulator.sum_expected_hash_rates += u128::from(target_difficulty.as_u64() / target_time);
+        accumulator.sum_target_difficulties += u128::from(target_difficulty.as_u64());
+        if is_merge_mined {
+            self.merge_mined_randomx_blocks += 1;
+        }
+        self.start_height.get_or_insert(height);
+        self.end_height = height;
+        // Timestamps are not strictly increasing, the interval ends at the latest one
+        self.end_timestamp = self.end_timestamp.max(timestamp);
+    }
+
+    /// The number of blocks in the interval
+    pub fn num_blocks(&self) -> u64 {
+        self.randomx.num_blocks + self.sha3x.num_blocks
+    }
+
+    /// The height of the first block in the interval
+    pub fn start_height(&self) -> u64 {
+        self.start_height.unwrap_or_default()
+    }
+
+    /// The height of the last block in the interval
+    pub fn end_height(&self) -> u64 {
+        self.end_height
+    }
+
+    /// The timestamp of the block preceding the interval
+    pub fn start_timestamp(&self) -> u64 {
+        self.start_timestamp
+    }
+
+    /// The latest timestamp of the blocks in the interval
+    pub fn end_timestamp(&self) -> u64 {
+        self.end_timestamp
+    }
+
+    pub fn hash_rate(&self, pow_algo: PowAlgorithm) -> AlgoHashRate {
+        // Avoid dividing by zero when all the timestamps in the interval are equal
+        let duration = self.end_timestamp.saturating_sub(self.start_timestamp).max(1);
+        match pow_algo {
+            PowAlgorithm::RandomX => self.randomx.to_hash_rate(duration),
+            PowAlgorithm::Sha3x => self.sha3x.to_hash_rate(duration),
+        }
+    }
+
+    /// The number of RandomX blocks in the interval that were merge mined with Monero
+    pub fn merge_mined_randomx_blocks(&self) -> u64 {
+        self.merge_mined_randomx_blocks
+    }
+
+    /// The share of RandomX blocks in the interval that were merge mined with Monero, between 0 and 1
+    #[allow(clippy::cast_precision_loss)]
+    pub fn merge_mined_randomx_share(&self) -> f64 {
+        if self.randomx.num_blocks == 0 {
+            return 0.0;
+        }
+        self.merge_mined_randomx_blocks as f64 / self.randomx.num_blocks as f64
+    }
+}
+
 #[cfg(test)]
 mod test {
     use tari_core::{
-        consensus::{ConsensusConstants, ConsensusManagerBuilder},
+        consensus::{ConsensusConstants, ConsensusManager, ConsensusManagerBuilder},
         proof_of_work::{Difficulty, PowAlgorithm},
     };
     use tari_p2p::Network;
 
-    use super::HashRateMovingAverage;
+    use super::{AlgoHashRate, HashRateInterval, HashRateMovingAverage};
 
     #[test]
     fn window_is_empty() {
@@ -179,12 +344,55 @@ mod test {
         }
     }
 
-    fn create_hash_rate_ma(pow_algo: PowAlgorithm) -> HashRateMovingAverage {
-        let consensus_manager = ConsensusManagerBuilder::new(Network::Esmeralda)
+    // Esmeralda has a target time of 240 secs for both algorithms
+    #[test]
+    fn interval_hash_rates() {
+        let mut interval = HashRateInterval::new(create_consensus_manager(), 1_000);
+        assert_eq!(interval.num_blocks(), 0);
+        assert_eq!(interval.hash_rate(PowAlgorithm::Sha3x), AlgoHashRate::default());
+
+        let difficulty = |d| Difficulty::from_u64(d).unwrap();
+        interval.add_block(10, 1_100, PowAlgorithm::Sha3x, difficulty(24_000), false);
+        interval.add_block(11, 1_200, PowAlgorithm::RandomX, difficulty(48_000), true);
+        interval.add_block(12, 1_300, PowAlgorithm::Sha3x, difficulty(48_000), false);
+        interval.add_block(13, 1_250, PowAlgorithm::RandomX, difficulty(72_000), false);
+        interval.add_block(14, 1_400, PowAlgorithm::RandomX, difficulty(120_000), true);
+
+        assert_eq!(interval.num_blocks(), 5);
+        assert_eq!(interval.start_height(), 10);
+        assert_eq!(interval.end_height(), 14);
+        assert_eq!(interval.end_timestamp(), 1_400);
+        assert_eq!(interval.hash_rate(PowAlgorithm::Sha3x), AlgoHashRate {
+            num_blocks: 2,
+            estimated_hash_rate: 150,
+            achieved_hash_rate: 180,
+        });
+        assert_eq!(interval.hash_rate(PowAlgorithm::RandomX), AlgoHashRate {
+            num_blocks: 3,
+            estimated_hash_rate: 333,
+            achieved_hash_rate: 600,
+        });
+        assert_eq!(interval.merge_mined_randomx_blocks(), 2);
+        assert!((interval.merge_mined_randomx_share() - 2.0 / 3.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn interval_without_elapsed_time() {
+        let mut interval = HashRateInterval::new(create_consensus_manager(), 1_000);
+        interval.add_block(1, 900, PowAlgorithm::Sha3x, Difficulty::from_u64(2_400).unwrap(), false);
+        assert_eq!(interval.hash_rate(PowAlgorithm::Sha3x).achieved_hash_rate, 2_400);
+        assert!(interval.merge_mined_randomx_share().abs() < f64::EPSILON);
+    }
+
+    fn create_consensus_manager() -> ConsensusManager {
+        ConsensusManagerBuilder::new(Network::Esmeralda)
             .add_consensus_constants(ConsensusConstants::esmeralda()[0].clone())
             .build()
-            .unwrap();
-        HashRateMovingAverage::new(pow_algo, consensus_manager)
+            .unwrap()
+    }
+
+    fn create_hash_rate_ma(pow_algo: PowAlgorithm) -> HashRateMovingAverage {
+        HashRateMovingAverage::new(pow_algo, create_consensus_manager())
     }
 
     fn assert_hash_rate(
@@ -40,6 +40,7 @@ pub enum GrpcMethod {
     CheckForUpdates,
     GetTokensInCirculation,
     GetNetworkDifficulty,
+    GetHashRateHistory,
     GetNewBlockTemplate,
     GetNewBlock,
     GetNewBlockWithCoinbases,
@@ -69,7 +70,7 @@ pub enum GrpcMethod {
 
 impl GrpcMethod {
     /// All the GRPC methods as a fixed array
-    pub const ALL_VARIANTS: [GrpcMethod; 36] = [
+    pub const ALL_VARIANTS: [GrpcMethod; 37] = [
         GrpcMethod::ListHeaders,
         GrpcMethod::GetHeaderByHash,
         GrpcMethod::GetBlocks,
@@ -81,6 +82,7 @@ impl GrpcMethod {
         GrpcMethod::CheckForUpdates,
         GrpcMethod::GetTokensInCirculation,
         GrpcMethod::GetNetworkDifficulty,
+        GrpcMethod::GetHashRateHistory,
         GrpcMethod::GetNewBlockTemplate,
         GrpcMethod::GetNewBlock,
         GrpcMethod::GetNewBlockWithCoinbases,
@@ -110,7 +112,7 @@ impl GrpcMethod {
 }
 
 impl IntoIterator for GrpcMethod {
-    type IntoIter = std::array::IntoIter<GrpcMethod, 36>;
+    type IntoIter = std::array::IntoIter<GrpcMethod, 37>;
     type Item = GrpcMethod;
 
     fn into_iter(self) -> Self::IntoIter {
@@ -136,6 +138,7 @@ impl FromStr for GrpcMethod {
             "check_for_updates" => Ok(GrpcMethod::CheckForUpdates),
             "get_tokens_in_circulation" => Ok(GrpcMethod::GetTokensInCirculation),
             "get_network_difficulty" => Ok(GrpcMethod::GetNetworkDifficulty),
+            "get_hash_rate_history" => Ok(GrpcMethod::GetHashRateHistory),
             "get_new_block_template" => Ok(GrpcMethod::GetNewBlockTemplate),
             "get_new_block" => Ok(GrpcMethod::GetNewBlock),
             "get_new_block_with_coinbases" => Ok(GrpcMethod::GetNewBlockWithCoinbases),
@@ -231,6 +234,7 @@ mod tests {
                 GrpcMethod::CheckForUpdates => count += 1,
                 GrpcMethod::GetTokensInCirculation => count += 1,
                 GrpcMethod::GetNetworkDifficulty => count += 1,
+                GrpcMethod::GetHashRateHistory => count += 1,
                 GrpcMethod::GetNewBlockTemplate => count += 1,
                 GrpcMethod::GetNewBlock => count += 1,
                 GrpcMethod::GetNewBlockWithCoinbases => count += 1,
@@ -0,0 +1,41 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::path::PathBuf;
+
+use clap::Parser;
+
+/// Executes a TariScript against an input stack and script context, and prints the stack and branch state after
+/// every opcode.
+#[derive(Parser, Debug)]
+#[clap(author, version, about, long_about = None)]
+pub struct Cli {
+    /// The script in assembly syntax, e.g. "Dup HashBlake256 PushHash(<hash>) EqualVerify"
+    pub script: Option<String>,
+    /// The hex encoded script, e.g. the script of a transaction output
+    #[clap(long, conflicts_with_all = &["script", "script-file"])]
+    pub script_hex: Option<String>,
+    /// Reads the script in assembly syntax from this file
+    #[clap(long, conflicts_with = "script")]
+    pub script_file: Option<PathBuf>,
+    /// The hex encoded input stack, e.g. the input data of a transaction input
+    #[clap(long)]
+    pub input_stack: Option<String>,
+    /// Items pushed onto the input stack, after the items of `--input-stack`, from the bottom to the top of the
+    /// stack. An item is an integer, or one of `hash:<hex>`, `scalar:<hex>`, `pubkey:<hex>`, `commitment:<hex>`,
+    /// `signature:<public nonce hex>:<signature hex>` or `script:<hex>`.
+    #[clap(long = "input", multiple_occurrences = true)]
+    pub inputs: Vec<String>,
+    /// The block height at which the script is executed
+    #[clap(long, default_value_t = 0)]
+    pub height: u64,
+    /// The block height at which the output the script belongs to was mined, used by relative height locks
+    #[clap(long)]
+    pub mined_height: Option<u64>,
+    /// The hex encoded hash of the block before the one the script is executed in
+    #[clap(long)]
+    pub prev_block_hash: Option<String>,
+    /// The hex encoded commitment of the output the script belongs to
+    #[clap(long)]
+    pub commitment: Option<String>,
+}
@@ -0,0 +1,94 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use anyhow::{anyhow, Context};
+use tari_crypto::ristretto::{pedersen::PedersenCommitment, RistrettoPublicKey, RistrettoSecretKey};
+use tari_script::{slice_to_hash, CheckSigSchnorrSignature, HashValue, StackItem, TariScript};
+use tari_utilities::hex::{from_hex, Hex};
+
+/// Parses a 32-byte hex value
+pub fn parse_hash(value: &str) -> Result<HashValue, anyhow::Error> {
+    let bytes = from_hex(value).map_err(|e| anyhow!("Invalid hex value `{}`: {}", value, e))?;
+    if bytes.len() != 32 {
+        return Err(anyhow!(
+            "Expected 32 hex encoded bytes, got {} in `{}`",
+            bytes.len(),
+            value
+        ));
+    }
+    Ok(slice_to_hash(&bytes))
+}
+
+/// Parses a stack item given as an integer, or as `<type>:<hex>`
+pub fn parse_stack_item(item: &str) -> Result<StackItem, anyhow::Error> {
+    let (kind, value) = match item.split_once(':') {
+        Some(parts) => parts,
+        None => {
+            let n = item
+                .parse::<i64>()
+                .with_context(|| format!("Invalid stack item `{}`, expected an integer or <type>:<hex>", item))?;
+            return Ok(StackItem::Number(n));
+        },
+    };
+    let stack_item = match kind {
+        "hash" => StackItem::Hash(parse_hash(value)?),
+        "scalar" => StackItem::Scalar(parse_hash(value)?),
+        "pubkey" => StackItem::PublicKey(
+            RistrettoPublicKey::from_hex(value).map_err(|e| anyhow!("Invalid public key `{}`: {}", value, e))?,
+        ),
+        "commitment" => StackItem::Commitment(
+            PedersenCommitment::from_hex(value).map_err(|e| anyhow!("Invalid commitment `{}`: {}", value, e))?,
+        ),
+        "signature" => {
+            let (nonce, signature) = value
+                .split_once(':')
+                .ok_or_else(|| anyhow!("Expected signature:<public nonce hex>:<signature hex>, got `{}`", item))?;
+            let nonce =
+                RistrettoPublicKey::from_hex(nonce).map_err(|e| anyhow!("Invalid public nonce `{}`: {}", nonce, e))?;
+            let signature = RistrettoSecretKey::from_hex(signature)
+                .map_err(|e| anyhow!("Invalid signature `{}`: {}", signature, e))?;
+            StackItem::Signature(CheckSigSchnorrSignature::new(nonce, signature))
+        },
+        "script" => {
+            StackItem::Script(TariScript::from_hex(value).map_err(|e| anyhow!("Invalid script `{}`: {}", value, e))?)
+        },
+        _ => return Err(anyhow!("Unknown stack item type `{}` in `{}`", kind, item)),
+    };
+    Ok(stack_item)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const KEY: &str = "6c9cb4d3e57351462122310fa22c90b1e6dfb528d64615363d1261a75da3e401";
+
+    #[test]
+    fn it_parses_stack_items() {
+        assert_eq!(parse_stack_item("-5").unwrap(), StackItem::Number(-5));
+        assert_eq!(
+            parse_stack_item(&format!("hash:{}", KEY)).unwrap(),
+            StackItem::Hash(parse_hash(KEY).unwrap())
+        );
+        assert_eq!(
+            parse_stack_item(&format!("pubkey:{}", KEY)).unwrap(),
+            StackItem::PublicKey(RistrettoPublicKey::from_hex(KEY).unwrap())
+        );
+        assert!(matches!(
+            parse_stack_item(&format!("signature:{}:{}", KEY, "00".repeat(32))).unwrap(),
+            StackItem::Signature(_)
+        ));
+        assert_eq!(
+            parse_stack_item("script:7c").unwrap(),
+            StackItem::Script(TariScript::from_hex("7c").unwrap())
+        );
+    }
+
+    #[test]
+    fn it_rejects_invalid_stack_items() {
+        assert!(parse_stack_item("five").is_err());
+        assert!(parse_stack_item("hash:abcd").is_err());
+        assert!(parse_stack_item(&format!("key:{}", KEY)).is_err());
+        assert!(parse_stack_item(&format!("signature:{}", KEY)).is_err());
+    }
+}
@@ -0,0 +1,90 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Executes a TariScript against a given input stack and script context, and prints the execution trace. See the
+//! README for usage.
+
+mod cli;
+mod input;
+
+use std::{fs, str::FromStr};
+
+use anyhow::{anyhow, Context};
+use clap::Parser;
+use tari_crypto::ristretto::pedersen::PedersenCommitment;
+use tari_script::{ExecutionStack, ScriptContext, TariScript};
+use tari_utilities::hex::Hex;
+
+use crate::{
+    cli::Cli,
+    input::{parse_hash, parse_stack_item},
+};
+
+fn main() {
+    match main_inner() {
+        Ok(true) => {},
+        Ok(false) => std::process::exit(2),
+        Err(err) => {
+            eprintln!("{:#}", err);
+            std::process::exit(1);
+        },
+    }
+}
+
+/// Returns whether the script executed successfully
+fn main_inner() -> Result<bool, anyhow::Error> {
+    let cli = Cli::parse();
+    let script = load_script(&cli)?;
+    let inputs = load_inputs(&cli)?;
+    let context = load_context(&cli)?;
+
+    println!("Script:");
+    print!("{}", script.to_pretty_string());
+    println!();
+    println!("Trace:");
+    let trace = script.execute_with_trace(&inputs, &context);
+    println!("{}", trace);
+    Ok(trace.result.is_ok())
+}
+
+fn load_script(cli: &Cli) -> Result<TariScript, anyhow::Error> {
+    if let Some(hex) = &cli.script_hex {
+        return TariScript::from_hex(hex).map_err(|e| anyhow!("Invalid script hex: {}", e));
+    }
+    let source = match (&cli.script, &cli.script_file) {
+        (Some(source), _) => source.clone(),
+        (None, Some(file)) => {
+            fs::read_to_string(file).with_context(|| format!("Could not read script file {}", file.display()))?
+        },
+        (None, None) => return Err(anyhow!("No script given, see --help")),
+    };
+    TariScript::from_str(&source).map_err(|e| anyhow!("Invalid script: {}", e))
+}
+
+fn load_inputs(cli: &Cli) -> Result<ExecutionStack, anyhow::Error> {
+    let stack = match &cli.input_stack {
+        Some(hex) => ExecutionStack::from_hex(hex).map_err(|e| anyhow!("Invalid input stack hex: {}", e))?,
+        None => ExecutionStack::default(),
+    };
+    let mut items = stack.as_slice().to_vec();
+    for input in &cli.inputs {
+        items.push(parse_stack_item(input)?);
+    }
+    Ok(ExecutionStack::new(items))
+}
+
+fn load_context(cli: &Cli) -> Result<ScriptContext, anyhow::Error> {
+    let prev_block_hash = match &cli.prev_block_hash {
+        Some(hash) => parse_hash(hash)?,
+        None => Default::default(),
+    };
+    let commitment = match &cli.commitment {
+        Some(hex) => PedersenCommitment::from_hex(hex).map_err(|e| anyhow!("Invalid commitment: {}", e))?,
+        None => PedersenCommitment::default(),
+    };
+    let context = ScriptContext::new(cli.height, &prev_block_hash, &commitment);
+    Ok(match cli.mined_height {
+        Some(mined_height) => context.with_mined_height(mined_height),
+        None => context,
+    })
+}
@@ -40,6 +40,9 @@ pub enum TransactionKeyManagerBranch {
     Spend = Branch::Spend as u8,
     RandomKey = Branch::RandomKey as u8,
     PreMine = Branch::PreMine as u8,
+    MultiSig = Branch::MultiSig as u8,
+    AccountView = Branch::AccountView as u8,
+    AccountSpend = Branch::AccountSpend as u8,
 }
 
 pub const DATA_ENCRYPTION: &str = "data encryption";
@@ -51,6 +54,9 @@ pub const SENDER_OFFSET: &str = "sender offset";
 pub const ONE_SIDED_SENDER_OFFSET: &str = "one sided sender offset";
 pub const RANDOM_KEY: &str = "random key";
 pub const PRE_MINE: &str = "pre-mine";
+pub const MULTI_SIG: &str = "multi-sig";
+pub const ACCOUNT_VIEW: &str = "account view";
+pub const ACCOUNT_SPEND: &str = "account spend";
 
 impl TransactionKeyManagerBranch {
     /// Warning: Changing these strings will affect the backwards compatibility of the wallet with older databases or
@@ -67,6 +73,9 @@ impl TransactionKeyManagerBranch {
             TransactionKeyManagerBranch::RandomKey => RANDOM_KEY.to_string(),
             TransactionKeyManagerBranch::Spend => WALLET_COMMS_AND_SPEND_KEY_BRANCH.to_string(),
             TransactionKeyManagerBranch::PreMine => PRE_MINE.to_string(),
+            TransactionKeyManagerBranch::MultiSig => MULTI_SIG.to_string(),
+            TransactionKeyManagerBranch::AccountView => ACCOUNT_VIEW.to_string(),
+            TransactionKeyManagerBranch::AccountSpend => ACCOUNT_SPEND.to_string(),
         }
     }
 
@@ -82,6 +91,9 @@ impl TransactionKeyManagerBranch {
             RANDOM_KEY => TransactionKeyManagerBranch::RandomKey,
             WALLET_COMMS_AND_SPEND_KEY_BRANCH => TransactionKeyManagerBranch::Spend,
             PRE_MINE => TransactionKeyManagerBranch::PreMine,
+            MULTI_SIG => TransactionKeyManagerBranch::MultiSig,
+            ACCOUNT_VIEW => TransactionKeyManagerBranch::AccountView,
+            ACCOUNT_SPEND => TransactionKeyManagerBranch::AccountSpend,
             _ => TransactionKeyManagerBranch::Nonce,
         }
     }
@@ -102,6 +114,9 @@ impl TransactionKeyManagerBranch {
             Some(Branch::Spend) => Some(TransactionKeyManagerBranch::Spend),
             Some(Branch::RandomKey) => Some(TransactionKeyManagerBranch::RandomKey),
             Some(Branch::PreMine) => Some(TransactionKeyManagerBranch::PreMine),
+            Some(Branch::MultiSig) => Some(TransactionKeyManagerBranch::MultiSig),
+            Some(Branch::AccountView) => Some(TransactionKeyManagerBranch::AccountView),
+            Some(Branch::AccountSpend) => Some(TransactionKeyManagerBranch::AccountSpend),
             None => None,
         }
     }
@@ -126,10 +141,13 @@ mod test {
     use crate::{
         key_branches::{
             TransactionKeyManagerBranch,
+            ACCOUNT_SPEND,
+            ACCOUNT_VIEW,
             COMMITMENT_MASK,
             DATA_ENCRYPTION,
             KERNEL_NONCE,
             METADATA_EPHEMERAL_NONCE,
+            MULTI_SIG,
             NONCE,
             ONE_SIDED_SENDER_OFFSET,
             PRE_MINE,
@@ -185,6 +203,17 @@ mod test {
                 RANDOM_KEY,
             ),
             (Branch::PreMine as u8, TransactionKeyManagerBranch::PreMine, PRE_MINE),
+            (Branch::MultiSig as u8, TransactionKeyManagerBranch::MultiSig, MULTI_SIG),
+            (
+                Branch::AccountView as u8,
+                TransactionKeyManagerBranch::AccountView,
+                ACCOUNT_VIEW,
+            ),
+            (
+                Branch::AccountSpend as u8,
+                TransactionKeyManagerBranch::AccountSpend,
+                ACCOUNT_SPEND,
+            ),
         ];
 
         for (expected_byte, branch, key) in &mappings {
@@ -249,6 +278,24 @@ mod test {
                     assert_eq!(&branch.get_branch_key(), *key);
                     assert_eq!(TransactionKeyManagerBranch::from_key(key), *branch);
                 },
+                TransactionKeyManagerBranch::MultiSig => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(TransactionKeyManagerBranch::from_byte(*expected_byte), Some(*branch));
+                    assert_eq!(&branch.get_branch_key(), *key);
+                    assert_eq!(TransactionKeyManagerBranch::from_key(key), *branch);
+                },
+                TransactionKeyManagerBranch::AccountView => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(TransactionKeyManagerBranch::from_byte(*expected_byte), Some(*branch));
+                    assert_eq!(&branch.get_branch_key(), *key);
+                    assert_eq!(TransactionKeyManagerBranch::from_key(key), *branch);
+                },
+                TransactionKeyManagerBranch::AccountSpend => {
+                    assert_eq!(branch.as_byte(), *expected_byte);
+                    assert_eq!(TransactionKeyManagerBranch::from_byte(*expected_byte), Some(*branch));
+                    assert_eq!(&branch.get_branch_key(), *key);
+                    assert_eq!(TransactionKeyManagerBranch::from_key(key), *branch);
+                },
             }
         }
     }
@@ -39,11 +39,28 @@ pub enum WalletType {
     ProvidedKeys(ProvidedKeysWallet),
 }
 
+impl WalletType {
+    /// A watch-only wallet knows the view key and public spend key, so it can scan for its outputs and keep track of
+    /// its balance and history, but it cannot spend
+    pub fn is_watch_only(&self) -> bool {
+        matches!(
+            self,
+            WalletType::ProvidedKeys(ProvidedKeysWallet {
+                private_spend_key: None,
+                ..
+            })
+        )
+    }
+}
+
 impl Display for WalletType {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
             WalletType::DerivedKeys => write!(f, "Derived wallet"),
             WalletType::Ledger(ledger_wallet) => write!(f, "Ledger({ledger_wallet})"),
+            WalletType::ProvidedKeys(provided_keys_wallet) if self.is_watch_only() => {
+                write!(f, "Watch-only ({provided_keys_wallet})")
+            },
             WalletType::ProvidedKeys(provided_keys_wallet) => write!(f, "Provided Keys ({provided_keys_wallet})"),
         }
     }
@@ -57,6 +74,18 @@ pub struct ProvidedKeysWallet {
     pub view_key: PrivateKey,
 }
 
+impl ProvidedKeysWallet {
+    /// A watch-only wallet from the private view key and public spend key, as exported by a full wallet
+    pub fn watch_only(view_key: PrivateKey, public_spend_key: PublicKey) -> Self {
+        Self {
+            public_spend_key,
+            private_spend_key: None,
+            private_comms_key: None,
+            view_key,
+        }
+    }
+}
+
 impl Display for ProvidedKeysWallet {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         write!(f, "public spend key {}", self.public_spend_key)?;
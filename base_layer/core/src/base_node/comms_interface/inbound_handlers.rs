@@ -273,7 +273,7 @@ where B: BlockchainBackend + 'static
                 }
                 let mut header = BlockHeader::from_previous(best_block_header.header());
                 let constants = self.consensus_manager.consensus_constants(header.height);
-                header.version = constants.blockchain_version();
+                header.version = self.consensus_manager.header_version_for_new_block(header.height);
                 header.pow.pow_algo = request.algo;
 
                 let constants_weight = constants
@@ -55,6 +55,7 @@ impl BlockSync {
             shared.config.blockchain_sync_config.clone(),
             shared.db.clone(),
             shared.connectivity.clone(),
+            shared.peer_manager.clone(),
             &mut self.sync_peers,
             shared.sync_validators.block_body.clone(),
         );
@@ -76,7 +77,7 @@ impl BlockSync {
         });
 
         let status_event_sender = shared.status_event_sender.clone();
-        synchronizer.on_progress(move |block, remote_tip_height, sync_peer| {
+        synchronizer.on_progress(move |block, remote_tip_height, sync_peer, progress| {
             let local_height = block.height();
             local_nci.publish_block_event(BlockEvent::ValidBlockAdded(
                 block.block().clone().into(),
@@ -87,11 +88,10 @@ impl BlockSync {
             tip_height_metric.set(local_height as i64);
             let _result = status_event_sender.send(StatusInfo {
                 bootstrapped,
-                state_info: StateInfo::BlockSync(BlockSyncInfo {
-                    tip_height: remote_tip_height,
-                    local_height,
-                    sync_peer: sync_peer.clone(),
-                }),
+                state_info: StateInfo::BlockSync(
+                    BlockSyncInfo::new(remote_tip_height, local_height, sync_peer.clone())
+                        .with_progress(progress.clone()),
+                ),
                 randomx_vm_cnt,
                 randomx_vm_flags,
             });
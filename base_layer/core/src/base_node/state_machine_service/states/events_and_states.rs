@@ -37,7 +37,7 @@ use crate::base_node::{
         Starting,
         Waiting,
     },
-    sync::{AttemptSyncResult, HorizonSyncInfo, SyncPeer},
+    sync::{format_duration, AttemptSyncResult, HorizonSyncInfo, SyncPeer, SyncProgress},
 };
 
 #[derive(Debug)]
@@ -225,6 +225,15 @@ impl StateInfo {
         }
     }
 
+    /// The detailed progress of the current sync stage, if syncing
+    pub fn sync_progress(&self) -> Option<&SyncProgress> {
+        match self {
+            Self::HeaderSync(Some(info)) | Self::BlockSync(info) => info.progress.as_ref(),
+            Self::HorizonSync(info) => info.progress.as_ref(),
+            _ => None,
+        }
+    }
+
     pub fn get_block_sync_info(&self) -> Option<BlockSyncInfo> {
         match self {
             Self::BlockSync(info) => Some(info.clone()),
@@ -304,6 +313,8 @@ pub struct BlockSyncInfo {
     pub tip_height: u64,
     pub local_height: u64,
     pub sync_peer: SyncPeer,
+    /// Detailed progress of the header or block sync stage
+    pub progress: Option<SyncProgress>,
 }
 
 impl BlockSyncInfo {
@@ -313,9 +324,15 @@ impl BlockSyncInfo {
             tip_height,
             local_height,
             sync_peer,
+            progress: None,
         }
     }
 
+    pub fn with_progress(mut self, progress: SyncProgress) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
     pub fn sync_progress_string_headers(&self) -> String {
         self.sync_progress("hdrs")
     }
@@ -326,7 +343,7 @@ impl BlockSyncInfo {
 
     fn sync_progress(&self, item: &str) -> String {
         format!(
-            "({}) {}/{} ({:.0}%){}{}",
+            "({}) {}/{} ({:.0}%){}{}{}",
             self.sync_peer.node_id().short_str(),
             self.local_height,
             self.tip_height,
@@ -339,12 +356,21 @@ impl BlockSyncInfo {
                 .calc_avg_latency()
                 .map(|avg| format!(", latency: {:.2?}", avg))
                 .unwrap_or_default(),
+            self.progress
+                .as_ref()
+                .and_then(|p| p.eta())
+                .map(|eta| format!(", ETA: {}", format_duration(eta)))
+                .unwrap_or_default(),
         )
     }
 }
 
 impl Display for BlockSyncInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result<(), std::fmt::Error> {
-        writeln!(f, "Syncing {}", self.sync_progress_string_blocks())
+        writeln!(f, "Syncing {}", self.sync_progress_string_blocks())?;
+        if let Some(progress) = &self.progress {
+            writeln!(f, "{}", progress)?;
+        }
+        Ok(())
     }
 }
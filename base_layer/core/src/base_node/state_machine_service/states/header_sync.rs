@@ -112,6 +112,7 @@ impl HeaderSyncState {
             shared.db.clone(),
             shared.consensus_rules.clone(),
             shared.connectivity.clone(),
+            shared.peer_manager.clone(),
             &mut self.sync_peers,
             shared.randomx_factory.clone(),
             &self.local_metadata,
@@ -131,12 +132,9 @@ impl HeaderSyncState {
         });
 
         let status_event_sender = shared.status_event_sender.clone();
-        synchronizer.on_progress(move |current_height, remote_tip_height, sync_peer| {
-            let details = BlockSyncInfo {
-                tip_height: remote_tip_height,
-                local_height: current_height,
-                sync_peer: sync_peer.clone(),
-            };
+        synchronizer.on_progress(move |current_height, remote_tip_height, sync_peer, progress| {
+            let details = BlockSyncInfo::new(remote_tip_height, current_height, sync_peer.clone())
+                .with_progress(progress.clone());
             let _result = status_event_sender.send(StatusInfo {
                 bootstrapped,
                 state_info: StateInfo::HeaderSync(Some(details)),
@@ -28,7 +28,7 @@ use crate::{
             states::{HeaderSyncState, StateEvent},
             BaseNodeStateMachine,
         },
-        sync::SyncPeer,
+        sync::{rank_sync_peers, SyncPeer},
     },
     chain_storage::BlockchainBackend,
 };
@@ -60,6 +60,8 @@ impl DecideNextSync {
             "Selecting a suitable sync peer from {} peer(s)",
             self.sync_peers.len()
         );
+        // Prefer the peers that synced best in the past
+        rank_sync_peers(&shared.peer_manager, &mut self.sync_peers).await;
 
         if local_metadata.pruning_horizon() > 0 {
             // Filter sync peers that claim to be able to provide blocks up until our pruned height
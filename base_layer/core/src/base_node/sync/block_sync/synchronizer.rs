@@ -28,14 +28,31 @@ use std::{
 
 use futures::StreamExt;
 use log::*;
-use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId, protocol::rpc::RpcClient, PeerConnection};
+use prost::Message;
+use tari_comms::{
+    connectivity::ConnectivityRequester,
+    peer_manager::NodeId,
+    protocol::rpc::RpcClient,
+    PeerConnection,
+    PeerManager,
+};
 use tari_utilities::hex::Hex;
 use tokio::task;
 
 use super::error::BlockSyncError;
 use crate::{
     base_node::{
-        sync::{ban::PeerBanManager, hooks::Hooks, rpc, SyncPeer},
+        sync::{
+            ban::PeerBanManager,
+            hooks::Hooks,
+            rank_sync_peers,
+            rpc,
+            update_sync_peer_stats,
+            SyncPeer,
+            SyncProgress,
+            SyncProgressTracker,
+            SyncStage,
+        },
         BlockchainSyncConfig,
     },
     blocks::{Block, ChainBlock},
@@ -54,6 +71,7 @@ pub struct BlockSynchronizer<'a, B> {
     config: BlockchainSyncConfig,
     db: AsyncBlockchainDb<B>,
     connectivity: ConnectivityRequester,
+    peer_manager: Arc<PeerManager>,
     sync_peers: &'a mut Vec<SyncPeer>,
     block_validator: Arc<dyn BlockBodyValidator<B>>,
     hooks: Hooks,
@@ -65,6 +83,7 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         config: BlockchainSyncConfig,
         db: AsyncBlockchainDb<B>,
         connectivity: ConnectivityRequester,
+        peer_manager: Arc<PeerManager>,
         sync_peers: &'a mut Vec<SyncPeer>,
         block_validator: Arc<dyn BlockBodyValidator<B>>,
     ) -> Self {
@@ -73,6 +92,7 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
             config,
             db,
             connectivity,
+            peer_manager,
             sync_peers,
             block_validator,
             hooks: Default::default(),
@@ -86,7 +106,7 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
     }
 
     pub fn on_progress<H>(&mut self, hook: H)
-    where H: Fn(Arc<ChainBlock>, u64, &SyncPeer) + Send + Sync + 'static {
+    where H: Fn(Arc<ChainBlock>, u64, &SyncPeer, &SyncProgress) + Send + Sync + 'static {
         self.hooks.add_on_progress_block_hook(hook);
     }
 
@@ -96,6 +116,8 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
     }
 
     pub async fn synchronize(&mut self) -> Result<(), BlockSyncError> {
+        // Try the peers that synced best in the past first
+        rank_sync_peers(&self.peer_manager, self.sync_peers).await;
         let mut max_latency = self.config.initial_max_sync_latency;
         let mut sync_round = 0;
         let mut latency_increases_counter = 0;
@@ -183,11 +205,24 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
                 target: LOG_TARGET,
                 "Attempting to synchronize blocks with `{}` latency: {:.2?}", node_id, latency
             );
+            let timer = Instant::now();
             match self.synchronize_blocks(sync_peer, client, max_latency).await {
-                Ok(_) => return Ok(()),
+                Ok(num_blocks) => {
+                    let elapsed = timer.elapsed();
+                    update_sync_peer_stats(&self.peer_manager, &node_id, |stats| {
+                        stats.record_success(num_blocks, elapsed)
+                    })
+                    .await;
+                    return Ok(());
+                },
                 Err(err) => {
                     warn!(target: LOG_TARGET, "{}", err);
                     let ban_reason = BlockSyncError::get_ban_reason(&err);
+                    let difficulty_shortfall = matches!(err, BlockSyncError::PeerDidNotSupplyAllClaimedBlocks(_));
+                    update_sync_peer_stats(&self.peer_manager, &node_id, |stats| {
+                        stats.record_failure(ban_reason.is_some(), difficulty_shortfall)
+                    })
+                    .await;
                     if let Some(reason) = ban_reason {
                         let duration = match reason.ban_duration {
                             BanPeriod::Short => self.config.short_ban_period,
@@ -227,7 +262,7 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         mut sync_peer: SyncPeer,
         mut client: rpc::BaseNodeSyncRpcClient,
         max_latency: Duration,
-    ) -> Result<(), BlockSyncError> {
+    ) -> Result<u64, BlockSyncError> {
         info!(target: LOG_TARGET, "Starting block sync from peer {}", sync_peer);
 
         let tip_header = self.db.fetch_last_header().await?;
@@ -238,7 +273,7 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
                 target: LOG_TARGET,
                 "Blocks already synchronized to height {}.", tip_header.height
             );
-            return Ok(());
+            return Ok(0);
         }
 
         let tip_hash = tip_header.hash();
@@ -267,10 +302,14 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
         let mut current_block = None;
         let mut last_sync_timer = Instant::now();
         let mut avg_latency = RollingAverageTime::new(20);
+        let mut progress =
+            SyncProgressTracker::new(SyncStage::Blocks, sync_peer.node_id().clone(), best_height, tip_height);
         while let Some(block_result) = block_stream.next().await {
             let latency = last_sync_timer.elapsed();
             avg_latency.add_sample(latency);
             let block_body_response = block_result?;
+            progress.record_received(latency, block_body_response.encoded_len());
+            let validation_timer = Instant::now();
 
             let header = self
                 .db
@@ -386,8 +425,9 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
             }
             // Includes time to add block to database, used to show blocks/s on status line
             sync_peer.add_sample(last_sync_timer.elapsed());
+            progress.record_validated(validation_timer.elapsed(), block.height());
             self.hooks
-                .call_on_progress_block_hooks(block.clone(), tip_height, &sync_peer);
+                .call_on_progress_block_hooks(block.clone(), tip_height, &sync_peer, &progress.progress());
 
             debug!(
                 target: LOG_TARGET,
@@ -430,7 +470,7 @@ impl<'a, B: BlockchainBackend + 'static> BlockSynchronizer<'a, B> {
 
         debug!(target: LOG_TARGET, "Completed block sync with peer `{}`", sync_peer);
 
-        Ok(())
+        Ok(progress.progress().items_received)
     }
 
     // Sync peers are also removed from the list of sync peers if the ban duration is longer than the short ban period.
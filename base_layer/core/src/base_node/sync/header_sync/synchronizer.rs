@@ -28,12 +28,14 @@ use std::{
 use futures::StreamExt;
 use log::*;
 use primitive_types::U256;
+use prost::Message;
 use tari_common_types::{chain_metadata::ChainMetadata, types::HashOutput};
 use tari_comms::{
     connectivity::ConnectivityRequester,
     peer_manager::NodeId,
     protocol::rpc::{RpcClient, RpcError},
     PeerConnection,
+    PeerManager,
 };
 use tari_utilities::hex::Hex;
 
@@ -43,9 +45,14 @@ use crate::{
         ban::PeerBanManager,
         header_sync::HEADER_SYNC_INITIAL_MAX_HEADERS,
         hooks::Hooks,
+        rank_sync_peers,
         rpc,
+        update_sync_peer_stats,
         BlockchainSyncConfig,
         SyncPeer,
+        SyncProgress,
+        SyncProgressTracker,
+        SyncStage,
     },
     blocks::{BlockHeader, ChainBlock, ChainHeader},
     chain_storage::{async_db::AsyncBlockchainDb, BlockchainBackend, ChainStorageError},
@@ -67,6 +74,7 @@ pub struct HeaderSynchronizer<'a, B> {
     db: AsyncBlockchainDb<B>,
     header_validator: BlockHeaderSyncValidator<B>,
     connectivity: ConnectivityRequester,
+    peer_manager: Arc<PeerManager>,
     sync_peers: &'a mut Vec<SyncPeer>,
     hooks: Hooks,
     local_cached_metadata: &'a ChainMetadata,
@@ -79,6 +87,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         db: AsyncBlockchainDb<B>,
         consensus_rules: ConsensusManager,
         connectivity: ConnectivityRequester,
+        peer_manager: Arc<PeerManager>,
         sync_peers: &'a mut Vec<SyncPeer>,
         randomx_factory: RandomXFactory,
         local_metadata: &'a ChainMetadata,
@@ -89,6 +98,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
             header_validator: BlockHeaderSyncValidator::new(db.clone(), consensus_rules, randomx_factory),
             db,
             connectivity,
+            peer_manager,
             sync_peers,
             hooks: Default::default(),
             local_cached_metadata: local_metadata,
@@ -102,7 +112,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
     }
 
     pub fn on_progress<H>(&mut self, hook: H)
-    where H: Fn(u64, u64, &SyncPeer) + Send + Sync + 'static {
+    where H: Fn(u64, u64, &SyncPeer, &SyncProgress) + Send + Sync + 'static {
         self.hooks.add_on_progress_header_hook(hook);
     }
 
@@ -119,6 +129,8 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
             "Synchronizing headers ({} candidate peers selected)",
             self.sync_peers.len()
         );
+        // Try the peers that synced best in the past first
+        rank_sync_peers(&self.peer_manager, self.sync_peers).await;
         let mut max_latency = self.config.initial_max_sync_latency;
         let mut latency_increases_counter = 0;
         loop {
@@ -153,10 +165,24 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         );
         let mut latency_counter = 0usize;
         for node_id in sync_peer_node_ids {
+            let timer = Instant::now();
             match self.connect_and_attempt_sync(&node_id, max_latency).await {
-                Ok((peer, sync_result)) => return Ok((peer, sync_result)),
+                Ok((peer, sync_result)) => {
+                    let elapsed = timer.elapsed();
+                    update_sync_peer_stats(&self.peer_manager, &node_id, |stats| {
+                        stats.record_success(sync_result.headers_synced, elapsed)
+                    })
+                    .await;
+                    return Ok((peer, sync_result));
+                },
                 Err(err) => {
                     let ban_reason = BlockHeaderSyncError::get_ban_reason(&err);
+                    let difficulty_shortfall =
+                        matches!(err, BlockHeaderSyncError::PeerSentInaccurateChainMetadata { .. });
+                    update_sync_peer_stats(&self.peer_manager, &node_id, |stats| {
+                        stats.record_failure(ban_reason.is_some(), difficulty_shortfall)
+                    })
+                    .await;
                     if let Some(reason) = ban_reason {
                         warn!(target: LOG_TARGET, "{}", err);
                         let duration = match reason.ban_duration {
@@ -289,24 +315,32 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
 
                 Ok(AttemptSyncResult {
                     headers_returned: peer_response.peer_headers.len() as u64,
+                    headers_synced: peer_response.peer_headers.len() as u64,
                     peer_fork_hash_index: peer_response.peer_fork_hash_index,
                     header_sync_status,
                 })
             },
             HeaderSyncStatus::Lagging(split_info) => {
-                self.hooks.call_on_progress_header_hooks(
-                    split_info
-                        .best_block_header
-                        .height()
-                        .checked_sub(split_info.reorg_steps_back)
-                        .unwrap_or_default(),
-                    sync_peer.claimed_chain_metadata().best_block_height(),
-                    sync_peer,
+                let local_height = split_info
+                    .best_block_header
+                    .height()
+                    .checked_sub(split_info.reorg_steps_back)
+                    .unwrap_or_default();
+                let remote_height = sync_peer.claimed_chain_metadata().best_block_height();
+                let progress = SyncProgressTracker::new(
+                    SyncStage::Headers,
+                    sync_peer.node_id().clone(),
+                    local_height,
+                    remote_height,
                 );
-                self.synchronize_headers(sync_peer.clone(), &mut client, *split_info, max_latency)
+                self.hooks
+                    .call_on_progress_header_hooks(local_height, remote_height, sync_peer, &progress.progress());
+                let headers_streamed = self
+                    .synchronize_headers(sync_peer.clone(), &mut client, *split_info, max_latency, progress)
                     .await?;
                 Ok(AttemptSyncResult {
                     headers_returned: peer_response.peer_headers.len() as u64,
+                    headers_synced: peer_response.peer_headers.len() as u64 + headers_streamed,
                     peer_fork_hash_index: peer_response.peer_fork_hash_index,
                     header_sync_status,
                 })
@@ -560,7 +594,8 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         client: &mut rpc::BaseNodeSyncRpcClient,
         split_info: ChainSplitInfo,
         max_latency: Duration,
-    ) -> Result<(), BlockHeaderSyncError> {
+        mut progress: SyncProgressTracker,
+    ) -> Result<u64, BlockHeaderSyncError> {
         info!(target: LOG_TARGET, "Starting header sync from peer {}", sync_peer);
         const COMMIT_EVERY_N_HEADERS: usize = 1000;
 
@@ -607,7 +642,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
             }
             // The pow is higher, we swapped to the higher chain, we have all the better chain headers, we can move on
             // to block sync.
-            return Ok(());
+            return Ok(0);
         }
 
         debug!(
@@ -634,10 +669,14 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
         let mut last_total_accumulated_difficulty = U256::zero();
         let mut avg_latency = RollingAverageTime::new(20);
         let mut prev_height: Option<u64> = None;
+        let mut num_headers_received = 0u64;
         while let Some(header) = header_stream.next().await {
             let latency = last_sync_timer.elapsed();
             avg_latency.add_sample(latency);
-            let header = BlockHeader::try_from(header?).map_err(BlockHeaderSyncError::ReceivedInvalidHeader)?;
+            let header = header?;
+            progress.record_received(latency, header.encoded_len());
+            let validation_timer = Instant::now();
+            let header = BlockHeader::try_from(header).map_err(BlockHeaderSyncError::ReceivedInvalidHeader)?;
             debug!(
                 target: LOG_TARGET,
                 "Validating header #{} (Pow: {}) with hash: ({}). Latency: {:.2?}",
@@ -678,6 +717,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
             }
             let current_height = header.height;
             last_total_accumulated_difficulty = self.header_validator.validate(header).await?;
+            num_headers_received += 1;
 
             if has_switched_to_new_chain {
                 // If we've switched to the new chain, we simply commit every COMMIT_EVERY_N_HEADERS headers
@@ -696,10 +736,12 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
 
             sync_peer.set_latency(latency);
             sync_peer.add_sample(last_sync_timer.elapsed());
+            progress.record_validated(validation_timer.elapsed(), current_height);
             self.hooks.call_on_progress_header_hooks(
                 current_height,
                 sync_peer.claimed_chain_metadata().best_block_height(),
                 &sync_peer,
+                &progress.progress(),
             );
 
             let last_avg_latency = avg_latency.calculate_average_with_min_samples(5);
@@ -747,7 +789,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
                         .accumulated_data()
                         .total_accumulated_difficulty
                 );
-                return Ok(());
+                return Ok(num_headers_received);
             }
         }
 
@@ -770,7 +812,7 @@ impl<'a, B: BlockchainBackend + 'static> HeaderSynchronizer<'a, B> {
             });
         }
 
-        Ok(())
+        Ok(num_headers_received)
     }
 
     async fn commit_pending_headers(&mut self) -> Result<ChainHeader, BlockHeaderSyncError> {
@@ -871,6 +913,8 @@ pub struct ChainSplitInfo {
 pub struct AttemptSyncResult {
     /// The number of headers that were returned.
     pub headers_returned: u64,
+    /// The total number of headers received from the peer, including those streamed after the chain split was found.
+    pub headers_synced: u64,
     /// The fork hash index of the remote peer.
     pub peer_fork_hash_index: u64,
     /// The header sync status.
@@ -171,7 +171,10 @@ impl<B: BlockchainBackend + 'static> BlockHeaderSyncValidator<B> {
 
         state.current_height = header.height;
         // Add a "more recent" datapoint onto the target difficulty
-        state.target_difficulties.add_back(&header, target_difficulty);
+        state
+            .target_difficulties
+            .add_back(&header, target_difficulty)
+            .map_err(|e| ChainStorageError::UnexpectedResult(e.to_string()))?;
 
         let accumulated_data = BlockHeaderAccumulatedData::builder(&state.previous_accum)
             .with_hash(header.hash())
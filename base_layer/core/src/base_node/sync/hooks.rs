@@ -25,15 +25,15 @@
 use std::sync::Arc;
 
 use crate::{
-    base_node::sync::{horizon_state_sync::HorizonSyncInfo, SyncPeer},
+    base_node::sync::{horizon_state_sync::HorizonSyncInfo, SyncPeer, SyncProgress},
     blocks::ChainBlock,
 };
 
 #[derive(Default)]
 pub(super) struct Hooks {
     on_starting: Vec<Box<dyn FnOnce(&SyncPeer) + Send + Sync>>,
-    on_progress_header: Vec<Box<dyn Fn(u64, u64, &SyncPeer) + Send + Sync>>,
-    on_progress_block: Vec<Box<dyn Fn(Arc<ChainBlock>, u64, &SyncPeer) + Send + Sync>>,
+    on_progress_header: Vec<Box<dyn Fn(u64, u64, &SyncPeer, &SyncProgress) + Send + Sync>>,
+    on_progress_block: Vec<Box<dyn Fn(Arc<ChainBlock>, u64, &SyncPeer, &SyncProgress) + Send + Sync>>,
     on_progress_horizon_sync: Vec<Box<dyn Fn(HorizonSyncInfo) + Send + Sync>>,
     on_complete: Vec<Box<dyn Fn(Arc<ChainBlock>, u64) + Send + Sync>>,
     on_rewind: Vec<Box<dyn Fn(Vec<Arc<ChainBlock>>) + Send + Sync>>,
@@ -50,25 +50,37 @@ impl Hooks {
     }
 
     pub fn add_on_progress_header_hook<H>(&mut self, hook: H)
-    where H: Fn(u64, u64, &SyncPeer) + Send + Sync + 'static {
+    where H: Fn(u64, u64, &SyncPeer, &SyncProgress) + Send + Sync + 'static {
         self.on_progress_header.push(Box::new(hook));
     }
 
-    pub fn call_on_progress_header_hooks(&self, local_height: u64, remote_height: u64, sync_peer: &SyncPeer) {
+    pub fn call_on_progress_header_hooks(
+        &self,
+        local_height: u64,
+        remote_height: u64,
+        sync_peer: &SyncPeer,
+        progress: &SyncProgress,
+    ) {
         self.on_progress_header
             .iter()
-            .for_each(|f| (*f)(local_height, remote_height, sync_peer));
+            .for_each(|f| (*f)(local_height, remote_height, sync_peer, progress));
     }
 
     pub fn add_on_progress_block_hook<H>(&mut self, hook: H)
-    where H: Fn(Arc<ChainBlock>, u64, &SyncPeer) + Send + Sync + 'static {
+    where H: Fn(Arc<ChainBlock>, u64, &SyncPeer, &SyncProgress) + Send + Sync + 'static {
         self.on_progress_block.push(Box::new(hook));
     }
 
-    pub fn call_on_progress_block_hooks(&self, block: Arc<ChainBlock>, remote_tip_height: u64, sync_peer: &SyncPeer) {
+    pub fn call_on_progress_block_hooks(
+        &self,
+        block: Arc<ChainBlock>,
+        remote_tip_height: u64,
+        sync_peer: &SyncPeer,
+        progress: &SyncProgress,
+    ) {
         self.on_progress_block
             .iter()
-            .for_each(|f| (*f)(block.clone(), remote_tip_height, sync_peer));
+            .for_each(|f| (*f)(block.clone(), remote_tip_height, sync_peer, progress));
     }
 
     pub fn add_on_progress_horizon_hook<H>(&mut self, hook: H)
@@ -24,23 +24,40 @@ use std::fmt::{Display, Formatter};
 
 use tari_comms::peer_manager::NodeId;
 
-use crate::base_node::sync::SyncPeer;
+use crate::base_node::sync::{format_duration, SyncPeer, SyncProgress};
 
 /// Info about the state of horizon sync
 #[derive(Clone, Debug, PartialEq)]
 pub struct HorizonSyncInfo {
     pub sync_peers: Vec<NodeId>,
     pub status: HorizonSyncStatus,
+    /// Detailed progress of the kernel or output sync stage
+    pub progress: Option<SyncProgress>,
 }
 
 impl HorizonSyncInfo {
     pub fn new(sync_peers: Vec<NodeId>, status: HorizonSyncStatus) -> HorizonSyncInfo {
-        HorizonSyncInfo { sync_peers, status }
+        HorizonSyncInfo {
+            sync_peers,
+            status,
+            progress: None,
+        }
+    }
+
+    pub fn with_progress(mut self, progress: SyncProgress) -> Self {
+        self.progress = Some(progress);
+        self
     }
 
     pub fn to_progress_string(&self) -> String {
         use HorizonSyncStatus::{Finalizing, Kernels, Outputs, Starting};
-        match self.status {
+        let eta = self
+            .progress
+            .as_ref()
+            .and_then(|p| p.eta())
+            .map(|eta| format!(", ETA: {}", format_duration(eta)))
+            .unwrap_or_default();
+        let description = match self.status {
             Starting => "Starting horizon sync".to_string(),
             Kernels {
                 current,
@@ -75,7 +92,8 @@ impl HorizonSyncInfo {
                 sync_peer.latency().unwrap_or_default()
             ),
             Finalizing => "Finalizing horizon sync".to_string(),
-        }
+        };
+        description + &eta
     }
 }
 
@@ -115,7 +133,11 @@ impl Display for HorizonSyncInfo {
                 )
             },
             HorizonSyncStatus::Finalizing => write!(f, "Finalizing horizon state synchronization"),
+        }?;
+        if let Some(progress) = &self.progress {
+            write!(f, "\n{}", progress)?;
         }
+        Ok(())
     }
 }
 #[derive(Clone, Debug, PartialEq, Eq)]
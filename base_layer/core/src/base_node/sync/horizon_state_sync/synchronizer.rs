@@ -29,6 +29,7 @@ use std::{
 
 use futures::StreamExt;
 use log::*;
+use prost::Message;
 use tari_common_types::types::{Commitment, FixedHash, RangeProofService};
 use tari_comms::{connectivity::ConnectivityRequester, peer_manager::NodeId, protocol::rpc::RpcClient, PeerConnection};
 use tari_crypto::commitment::HomomorphicCommitment;
@@ -46,6 +47,8 @@ use crate::{
         rpc::BaseNodeSyncRpcClient,
         BlockchainSyncConfig,
         SyncPeer,
+        SyncProgressTracker,
+        SyncStage,
     },
     blocks::{BlockHeader, ChainHeader, UpdateBlockAccumulatedData},
     chain_storage::{async_db::AsyncBlockchainDb, BlockchainBackend, ChainStorageError, MmrTree},
@@ -407,11 +410,18 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
             return Ok(());
         }
 
+        let mut progress = SyncProgressTracker::new(
+            SyncStage::HorizonKernels,
+            sync_peer.node_id().clone(),
+            local_num_kernels,
+            remote_num_kernels,
+        );
         let info = HorizonSyncInfo::new(vec![sync_peer.node_id().clone()], HorizonSyncStatus::Kernels {
             current: local_num_kernels,
             total: remote_num_kernels,
             sync_peer: sync_peer.clone(),
-        });
+        })
+        .with_progress(progress.progress());
         self.hooks.call_on_progress_horizon_hooks(info);
 
         debug!(
@@ -453,7 +463,10 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
         while let Some(kernel) = kernel_stream.next().await {
             let latency = last_sync_timer.elapsed();
             avg_latency.add_sample(latency);
-            let kernel: TransactionKernel = kernel?.try_into().map_err(HorizonSyncError::ConversionError)?;
+            let kernel = kernel?;
+            progress.record_received(latency, kernel.encoded_len());
+            let validation_timer = Instant::now();
+            let kernel: TransactionKernel = kernel.try_into().map_err(HorizonSyncError::ConversionError)?;
             kernel.verify_signature()?;
 
             kernel_hashes.push(kernel.hash());
@@ -526,12 +539,14 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
 
             sync_peer.set_latency(latency);
             sync_peer.add_sample(last_sync_timer.elapsed());
+            progress.record_validated(validation_timer.elapsed(), mmr_position);
             if mmr_position % 100 == 0 || mmr_position == self.num_kernels {
                 let info = HorizonSyncInfo::new(vec![sync_peer.node_id().clone()], HorizonSyncStatus::Kernels {
                     current: mmr_position,
                     total: self.num_kernels,
                     sync_peer: sync_peer.clone(),
-                });
+                })
+                .with_progress(progress.progress());
                 self.hooks.call_on_progress_horizon_hooks(info);
             }
 
@@ -592,11 +607,18 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
             }
         }
 
+        let mut progress = SyncProgressTracker::new(
+            SyncStage::HorizonOutputs,
+            sync_peer.node_id().clone(),
+            0,
+            self.num_outputs,
+        );
         let info = HorizonSyncInfo::new(vec![sync_peer.node_id().clone()], HorizonSyncStatus::Outputs {
             current: 0,
             total: self.num_outputs,
             sync_peer: sync_peer.clone(),
-        });
+        })
+        .with_progress(progress.progress());
         self.hooks.call_on_progress_horizon_hooks(info);
 
         let latency = client.get_last_request_latency();
@@ -631,6 +653,8 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
             let latency = last_sync_timer.elapsed();
             avg_latency.add_sample(latency);
             let res: SyncUtxosResponse = response?;
+            progress.record_received(latency, res.encoded_len());
+            let validation_timer = Instant::now();
 
             let output_header_hash = FixedHash::try_from(res.mined_header)
                 .map_err(|_| HorizonSyncError::IncorrectResponse("Peer sent no mined header".into()))?;
@@ -730,12 +754,15 @@ impl<'a, B: BlockchainBackend + 'static> HorizonStateSynchronization<'a, B> {
                 },
             }
 
+            progress.set_total(self.num_outputs);
+            progress.record_validated(validation_timer.elapsed(), utxo_counter);
             if utxo_counter % 100 == 0 {
                 let info = HorizonSyncInfo::new(vec![sync_peer.node_id().clone()], HorizonSyncStatus::Outputs {
                     current: utxo_counter,
                     total: self.num_outputs,
                     sync_peer: sync_peer.clone(),
-                });
+                })
+                .with_progress(progress.progress());
                 self.hooks.call_on_progress_horizon_hooks(info);
             }
             sync_peer.set_latency(latency);
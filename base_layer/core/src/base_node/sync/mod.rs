@@ -49,6 +49,22 @@ mod hooks;
 #[cfg(any(feature = "base_node", feature = "base_node_proto"))]
 pub mod rpc;
 
+#[cfg(feature = "base_node")]
+mod peer_stats;
+#[cfg(feature = "base_node")]
+pub use peer_stats::{
+    fetch_sync_peer_stats,
+    rank_sync_peers,
+    update_sync_peer_stats,
+    SyncPeerStats,
+    SYNC_PEER_STATS_METADATA_KEY,
+};
+
+#[cfg(feature = "base_node")]
+mod progress;
+#[cfg(feature = "base_node")]
+pub use progress::{format_duration, SyncProgress, SyncProgressTracker, SyncStage};
+
 #[cfg(feature = "base_node")]
 mod sync_peer;
 #[cfg(feature = "base_node")]
@@ -0,0 +1,201 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{cmp::Ordering, convert::TryFrom, time::Duration};
+
+use log::*;
+use serde::{Deserialize, Serialize};
+use tari_comms::peer_manager::{NodeId, Peer, PeerManager};
+use tari_utilities::epoch_time::EpochTime;
+
+use crate::base_node::sync::SyncPeer;
+
+const LOG_TARGET: &str = "c::bn::sync::peer_stats";
+
+/// The peer metadata key under which sync peer statistics are stored in the peer database
+pub const SYNC_PEER_STATS_METADATA_KEY: u8 = 2;
+
+/// Once this many sync attempts have been recorded, all counters are halved so that recent behaviour outweighs
+/// behaviour from long ago
+const MAX_RECORDED_ATTEMPTS: u64 = 50;
+
+/// Historic sync performance of a peer, persisted in the peer database across restarts
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SyncPeerStats {
+    pub attempts: u64,
+    pub successes: u64,
+    pub failures: u64,
+    /// The number of failed attempts for which the peer was banned
+    pub bans: u64,
+    /// The number of failed attempts in which the peer could not deliver the accumulated difficulty it advertised
+    pub difficulty_shortfalls: u64,
+    /// The number of items (headers or blocks) received in successful attempts
+    pub items_received: u64,
+    /// The time spent on successful attempts, in milliseconds
+    pub sync_time_ms: u64,
+    pub last_attempt: Option<EpochTime>,
+}
+
+impl SyncPeerStats {
+    pub fn from_peer(peer: &Peer) -> Option<Self> {
+        peer.get_metadata(SYNC_PEER_STATS_METADATA_KEY)
+            .and_then(|v| bincode::deserialize::<Self>(v).ok())
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let size = usize::try_from(bincode::serialized_size(self).unwrap())
+            .expect("The serialized size is larger than the platform allows");
+        let mut buf = Vec::with_capacity(size);
+        bincode::serialize_into(&mut buf, self).unwrap(); // this should not fail
+        buf
+    }
+
+    /// Records a successful sync attempt in which `num_items` items were received in `elapsed`
+    pub fn record_success(&mut self, num_items: u64, elapsed: Duration) {
+        self.record_attempt();
+        self.successes += 1;
+        self.items_received += num_items;
+        self.sync_time_ms += u64::try_from(elapsed.as_millis()).unwrap_or(u64::MAX);
+    }
+
+    /// Records a failed sync attempt
+    pub fn record_failure(&mut self, banned: bool, difficulty_shortfall: bool) {
+        self.record_attempt();
+        self.failures += 1;
+        if banned {
+            self.bans += 1;
+        }
+        if difficulty_shortfall {
+            self.difficulty_shortfalls += 1;
+        }
+    }
+
+    fn record_attempt(&mut self) {
+        if self.attempts >= MAX_RECORDED_ATTEMPTS {
+            self.attempts /= 2;
+            self.successes /= 2;
+            self.failures /= 2;
+            self.bans /= 2;
+            self.difficulty_shortfalls /= 2;
+            self.items_received /= 2;
+            self.sync_time_ms /= 2;
+        }
+        self.attempts += 1;
+        self.last_attempt = Some(EpochTime::now());
+    }
+
+    /// The average number of items per second received in successful attempts
+    pub fn items_per_second(&self) -> Option<f64> {
+        if self.items_received == 0 || self.sync_time_ms == 0 {
+            return None;
+        }
+        Some(self.items_received as f64 * 1000.0 / self.sync_time_ms as f64)
+    }
+
+    /// The estimated probability that the next sync attempt succeeds. Bans and difficulty shortfalls count as
+    /// additional failures. A peer without any history is given even odds.
+    pub fn reliability(&self) -> f64 {
+        let failures = self.failures + self.bans + self.difficulty_shortfalls;
+        (self.successes + 1) as f64 / (self.successes + failures + 2) as f64
+    }
+
+    /// The score used to rank sync peers, higher is better
+    pub fn score(&self) -> f64 {
+        self.reliability() * (2.0 + self.items_per_second().unwrap_or_default()).ln()
+    }
+}
+
+/// Loads the sync statistics of a peer from the peer database, returning default statistics if there are none
+pub async fn fetch_sync_peer_stats(peer_manager: &PeerManager, node_id: &NodeId) -> SyncPeerStats {
+    match peer_manager.find_by_node_id(node_id).await {
+        Ok(peer) => peer.as_ref().and_then(SyncPeerStats::from_peer).unwrap_or_default(),
+        Err(err) => {
+            warn!(target: LOG_TARGET, "Failed to load sync stats for peer {}: {}", node_id, err);
+            SyncPeerStats::default()
+        },
+    }
+}
+
+/// Applies `update` to the sync statistics of a peer and persists the result in the peer database
+pub async fn update_sync_peer_stats<F>(peer_manager: &PeerManager, node_id: &NodeId, update: F)
+where F: FnOnce(&mut SyncPeerStats) {
+    let mut stats = fetch_sync_peer_stats(peer_manager, node_id).await;
+    update(&mut stats);
+    if let Err(err) = peer_manager
+        .set_peer_metadata(node_id, SYNC_PEER_STATS_METADATA_KEY, stats.to_bytes())
+        .await
+    {
+        warn!(target: LOG_TARGET, "Failed to store sync stats for peer {}: {}", node_id, err);
+    }
+}
+
+/// Sorts sync peers by their historic sync performance, best first. Peers with equal scores keep their relative
+/// order.
+pub async fn rank_sync_peers(peer_manager: &PeerManager, sync_peers: &mut [SyncPeer]) {
+    let mut scores = Vec::with_capacity(sync_peers.len());
+    for sync_peer in sync_peers.iter() {
+        let stats = fetch_sync_peer_stats(peer_manager, sync_peer.node_id()).await;
+        scores.push((sync_peer.node_id().clone(), stats.score()));
+    }
+    let score_of = |node_id: &NodeId| {
+        scores
+            .iter()
+            .find(|(n, _)| n == node_id)
+            .map(|(_, score)| *score)
+            .unwrap_or_default()
+    };
+    sync_peers.sort_by(|a, b| {
+        score_of(b.node_id())
+            .partial_cmp(&score_of(a.node_id()))
+            .unwrap_or(Ordering::Equal)
+    });
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn it_serializes_to_and_from_bytes() {
+        let mut stats = SyncPeerStats::default();
+        stats.record_success(1_000, Duration::from_secs(10));
+        stats.record_failure(true, true);
+        let decoded = bincode::deserialize::<SyncPeerStats>(&stats.to_bytes()).unwrap();
+        assert_eq!(decoded, stats);
+        assert_eq!(decoded.attempts, 2);
+        assert_eq!(decoded.bans, 1);
+        assert_eq!(decoded.difficulty_shortfalls, 1);
+        assert_eq!(decoded.items_per_second(), Some(100.0));
+    }
+
+    #[test]
+    fn it_ranks_fast_reliable_peers_first() {
+        let unknown = SyncPeerStats::default();
+        let mut fast = SyncPeerStats::default();
+        fast.record_success(1_000, Duration::from_secs(10));
+        let mut slow = SyncPeerStats::default();
+        slow.record_success(1_000, Duration::from_secs(200));
+        let mut failing = SyncPeerStats::default();
+        failing.record_failure(false, false);
+        let mut banned = SyncPeerStats::default();
+        banned.record_failure(true, true);
+
+        assert!(fast.score() > slow.score());
+        assert!(slow.score() > unknown.score());
+        assert!(unknown.score() > failing.score());
+        assert!(failing.score() > banned.score());
+    }
+
+    #[test]
+    fn it_decays_old_history() {
+        let mut stats = SyncPeerStats::default();
+        for _ in 0..MAX_RECORDED_ATTEMPTS {
+            stats.record_failure(true, false);
+        }
+        assert_eq!(stats.attempts, MAX_RECORDED_ATTEMPTS);
+        stats.record_success(10, Duration::from_secs(1));
+        assert_eq!(stats.attempts, MAX_RECORDED_ATTEMPTS / 2 + 1);
+        assert_eq!(stats.failures, MAX_RECORDED_ATTEMPTS / 2);
+        assert_eq!(stats.successes, 1);
+    }
+}
@@ -0,0 +1,311 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    fmt::{Display, Formatter},
+    time::{Duration, Instant},
+};
+
+use tari_comms::peer_manager::NodeId;
+
+/// The stages of blockchain synchronization
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncStage {
+    Headers,
+    HorizonKernels,
+    HorizonOutputs,
+    Blocks,
+}
+
+impl SyncStage {
+    /// The name of the items synced in this stage
+    pub fn item_name(self) -> &'static str {
+        match self {
+            SyncStage::Headers => "hdrs",
+            SyncStage::HorizonKernels => "kernels",
+            SyncStage::HorizonOutputs => "outputs",
+            SyncStage::Blocks => "blks",
+        }
+    }
+}
+
+impl Display for SyncStage {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SyncStage::Headers => write!(f, "Headers"),
+            SyncStage::HorizonKernels => write!(f, "Horizon kernels"),
+            SyncStage::HorizonOutputs => write!(f, "Horizon outputs"),
+            SyncStage::Blocks => write!(f, "Blocks"),
+        }
+    }
+}
+
+/// A snapshot of the progress of a sync stage with a single sync peer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncProgress {
+    pub stage: SyncStage,
+    pub sync_peer: NodeId,
+    /// The position at which syncing from this sync peer started
+    pub start: u64,
+    /// The current position in the stage, i.e. a height for header and block sync, or the number of kernels or
+    /// outputs synced for horizon sync
+    pub current: u64,
+    /// The position at which the stage is complete
+    pub total: u64,
+    /// The number of items received from the sync peer since the stage started
+    pub items_received: u64,
+    /// The number of (encoded) bytes received from the sync peer since the stage started
+    pub bytes_received: u64,
+    /// The time since the stage started
+    pub elapsed: Duration,
+    /// The time spent waiting for items from the sync peer
+    pub network_time: Duration,
+    /// The time spent validating and storing received items
+    pub validation_time: Duration,
+}
+
+impl SyncProgress {
+    /// The number of items remaining until the stage is complete
+    pub fn remaining(&self) -> u64 {
+        self.total.saturating_sub(self.current)
+    }
+
+    pub fn percentage(&self) -> f64 {
+        if self.total == 0 {
+            return 100.0;
+        }
+        (self.current as f64 / self.total as f64 * 100.0).min(100.0)
+    }
+
+    /// The overall rate at which the stage progresses
+    pub fn items_per_second(&self) -> Option<f64> {
+        per_second(self.current.saturating_sub(self.start) as f64, self.elapsed)
+    }
+
+    /// The rate at which bytes are received from the sync peer
+    pub fn bytes_per_second(&self) -> Option<f64> {
+        per_second(self.bytes_received as f64, self.network_time)
+    }
+
+    /// The rate at which items are validated and stored, i.e. the rate at which items would be synced with no network
+    /// latency
+    pub fn validations_per_second(&self) -> Option<f64> {
+        per_second(self.items_received as f64, self.validation_time)
+    }
+
+    /// The estimated time until the stage is complete, at the current sync rate
+    pub fn eta(&self) -> Option<Duration> {
+        let remaining = self.remaining();
+        if remaining == 0 {
+            return Some(Duration::ZERO);
+        }
+        let rate = self.items_per_second()?;
+        Some(Duration::from_secs_f64(remaining as f64 / rate))
+    }
+}
+
+fn per_second(amount: f64, duration: Duration) -> Option<f64> {
+    let secs = duration.as_secs_f64();
+    if amount <= 0.0 || secs <= 0.0 {
+        None
+    } else {
+        Some(amount / secs)
+    }
+}
+
+/// Formats a duration as e.g. `1h 2m 3s`
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let (hours, mins, secs) = (secs / 3600, secs % 3600 / 60, secs % 60);
+    match (hours, mins) {
+        (0, 0) => format!("{}s", secs),
+        (0, _) => format!("{}m {}s", mins, secs),
+        _ => format!("{}h {}m {}s", hours, mins, secs),
+    }
+}
+
+fn format_bytes_per_second(bytes_per_second: f64) -> String {
+    if bytes_per_second >= 1024.0 * 1024.0 {
+        format!("{:.2} MiB/s", bytes_per_second / (1024.0 * 1024.0))
+    } else {
+        format!("{:.2} KiB/s", bytes_per_second / 1024.0)
+    }
+}
+
+impl Display for SyncProgress {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}/{} ({:.0}%) from {}",
+            self.stage,
+            self.current,
+            self.total,
+            self.percentage().floor(),
+            self.sync_peer.short_str()
+        )?;
+        if let Some(rate) = self.items_per_second() {
+            write!(f, ", {:.2} {}/s", rate, self.stage.item_name())?;
+        }
+        if let Some(rate) = self.bytes_per_second() {
+            write!(f, ", {}", format_bytes_per_second(rate))?;
+        }
+        if let Some(rate) = self.validations_per_second() {
+            write!(f, ", validating {:.2} {}/s", rate, self.stage.item_name())?;
+        }
+        let elapsed = self.elapsed.as_secs_f64();
+        if elapsed > 0.0 {
+            write!(
+                f,
+                ", network {:.0}% / validation {:.0}%",
+                self.network_time.as_secs_f64() / elapsed * 100.0,
+                self.validation_time.as_secs_f64() / elapsed * 100.0
+            )?;
+        }
+        if let Some(eta) = self.eta() {
+            write!(f, ", ETA {}", format_duration(eta))?;
+        }
+        Ok(())
+    }
+}
+
+/// Tracks the progress of a sync stage with a single sync peer
+#[derive(Debug, Clone)]
+pub struct SyncProgressTracker {
+    stage: SyncStage,
+    sync_peer: NodeId,
+    started: Instant,
+    start: u64,
+    current: u64,
+    total: u64,
+    items_received: u64,
+    bytes_received: u64,
+    network_time: Duration,
+    validation_time: Duration,
+}
+
+impl SyncProgressTracker {
+    pub fn new(stage: SyncStage, sync_peer: NodeId, current: u64, total: u64) -> Self {
+        Self {
+            stage,
+            sync_peer,
+            started: Instant::now(),
+            start: current,
+            current,
+            total,
+            items_received: 0,
+            bytes_received: 0,
+            network_time: Duration::ZERO,
+            validation_time: Duration::ZERO,
+        }
+    }
+
+    pub fn set_total(&mut self, total: u64) {
+        self.total = total;
+    }
+
+    /// Records that an item of `num_bytes` bytes was received after waiting for `elapsed`
+    pub fn record_received(&mut self, elapsed: Duration, num_bytes: usize) {
+        self.items_received += 1;
+        self.bytes_received += num_bytes as u64;
+        self.network_time += elapsed;
+    }
+
+    /// Records that the last received item took `elapsed` to validate and store, after which the stage is at
+    /// `current`
+    pub fn record_validated(&mut self, elapsed: Duration, current: u64) {
+        self.validation_time += elapsed;
+        self.current = current;
+    }
+
+    pub fn progress(&self) -> SyncProgress {
+        SyncProgress {
+            stage: self.stage,
+            sync_peer: self.sync_peer.clone(),
+            start: self.start,
+            current: self.current,
+            total: self.total,
+            items_received: self.items_received,
+            bytes_received: self.bytes_received,
+            elapsed: self.started.elapsed(),
+            network_time: self.network_time,
+            validation_time: self.validation_time,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn progress() -> SyncProgress {
+        SyncProgress {
+            stage: SyncStage::Headers,
+            sync_peer: NodeId::default(),
+            start: 0,
+            current: 1_000,
+            total: 5_000,
+            items_received: 1_000,
+            bytes_received: 500 * 1024,
+            elapsed: Duration::from_secs(10),
+            network_time: Duration::from_secs(4),
+            validation_time: Duration::from_secs(5),
+        }
+    }
+
+    #[test]
+    fn it_calculates_rates_and_eta() {
+        let progress = progress();
+        assert_eq!(progress.remaining(), 4_000);
+        assert!((progress.percentage() - 20.0).abs() < f64::EPSILON);
+        assert_eq!(progress.items_per_second(), Some(100.0));
+        assert_eq!(progress.bytes_per_second(), Some(128.0 * 1024.0));
+        assert_eq!(progress.validations_per_second(), Some(200.0));
+        assert_eq!(progress.eta(), Some(Duration::from_secs(40)));
+    }
+
+    #[test]
+    fn it_has_no_rates_or_eta_before_receiving_items() {
+        let progress = SyncProgress {
+            current: 0,
+            items_received: 0,
+            bytes_received: 0,
+            elapsed: Duration::ZERO,
+            network_time: Duration::ZERO,
+            validation_time: Duration::ZERO,
+            ..progress()
+        };
+        assert_eq!(progress.items_per_second(), None);
+        assert_eq!(progress.bytes_per_second(), None);
+        assert_eq!(progress.eta(), None);
+        let done = SyncProgress {
+            current: 5_000,
+            ..progress
+        };
+        assert_eq!(done.eta(), Some(Duration::ZERO));
+    }
+
+    #[test]
+    fn it_tracks_received_items() {
+        let mut tracker = SyncProgressTracker::new(SyncStage::Blocks, NodeId::default(), 10, 20);
+        tracker.record_received(Duration::from_millis(100), 1_000);
+        tracker.record_validated(Duration::from_millis(50), 11);
+        tracker.record_received(Duration::from_millis(300), 3_000);
+        tracker.record_validated(Duration::from_millis(150), 12);
+        let progress = tracker.progress();
+        assert_eq!(progress.start, 10);
+        assert_eq!(progress.current, 12);
+        assert_eq!(progress.total, 20);
+        assert_eq!(progress.items_received, 2);
+        assert_eq!(progress.bytes_received, 4_000);
+        assert_eq!(progress.network_time, Duration::from_millis(400));
+        assert_eq!(progress.validation_time, Duration::from_millis(200));
+        assert_eq!(progress.bytes_per_second(), Some(10_000.0));
+    }
+
+    #[test]
+    fn it_formats_durations() {
+        assert_eq!(format_duration(Duration::from_secs(5)), "5s");
+        assert_eq!(format_duration(Duration::from_secs(65)), "1m 5s");
+        assert_eq!(format_duration(Duration::from_secs(3_725)), "1h 2m 5s");
+    }
+}
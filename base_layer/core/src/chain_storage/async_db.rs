@@ -63,6 +63,7 @@ use crate::{
         TargetDifficulties,
     },
     common::rolling_vec::RollingVec,
+    consensus::DeploymentState,
     proof_of_work::{PowAlgorithm, TargetDifficultyWindow},
     transactions::transaction_components::{OutputType, TransactionInput, TransactionKernel, TransactionOutput},
     OutputSmt,
@@ -274,6 +275,10 @@ impl<B: BlockchainBackend + 'static> AsyncBlockchainDb<B> {
 
     make_async_fn!(fetch_target_difficulties_for_next_block(current_block_hash: HashOutput) -> TargetDifficulties, "fetch_target_difficulties_for_next_block");
 
+    make_async_fn!(fetch_deployment_state(name: String, prev_hash: HashOutput) -> DeploymentState, "fetch_deployment_state");
+
+    make_async_fn!(is_deployment_active(name: String, prev_hash: HashOutput) -> bool, "is_deployment_active");
+
     make_async_fn!(fetch_block_hashes_from_header_tip(n: usize, offset: usize) -> Vec<HashOutput>, "fetch_block_hashes_from_header_tip");
 
     make_async_fn!(get_stats() -> DbBasicStats, "get_stats");
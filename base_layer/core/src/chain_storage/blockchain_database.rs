@@ -75,6 +75,7 @@ use crate::{
         BlockchainBackend,
         DbBasicStats,
         DbTotalSizeStats,
+        DeploymentStateCache,
         HorizonData,
         InputMinedInfo,
         MmrTree,
@@ -86,8 +87,10 @@ use crate::{
     common::{rolling_vec::RollingVec, BanPeriod},
     consensus::{
         chain_strength_comparer::ChainStrengthComparer,
+        deployments::signalling_bits_of,
         ConsensusConstants,
         ConsensusManager,
+        DeploymentState,
         DomainSeparatedConsensusHasher,
     },
     input_mr_hash_from_pruned_mmr,
@@ -214,6 +217,7 @@ pub struct BlockchainDatabase<B> {
     difficulty_calculator: Arc<DifficultyCalculator>,
     disable_add_block_flag: Arc<AtomicBool>,
     smt: Arc<RwLock<OutputSmt>>,
+    deployment_states: DeploymentStateCache,
 }
 
 #[allow(clippy::ptr_arg)]
@@ -239,6 +243,7 @@ where B: BlockchainBackend
             difficulty_calculator: Arc::new(difficulty_calculator),
             disable_add_block_flag: Arc::new(AtomicBool::new(false)),
             smt,
+            deployment_states: DeploymentStateCache::new(),
         };
         let genesis_block = Arc::new(blockchain_db.consensus_manager.get_genesis_block());
         if is_empty {
@@ -800,6 +805,32 @@ where B: BlockchainBackend
         fetch_target_difficulty_for_next_block(&*db, &self.consensus_manager, pow_algo, &current_block_hash)
     }
 
+    /// Returns the activation state of the named consensus deployment for a block that builds on the block with hash
+    /// `prev_hash`. The state only depends on the ancestors of the block, so the state of the next block can be queried
+    /// before it is added.
+    pub fn fetch_deployment_state(
+        &self,
+        name: String,
+        prev_hash: HashOutput,
+    ) -> Result<DeploymentState, ChainStorageError> {
+        let db = self.db_read_access()?;
+        let height = db.fetch_chain_header_in_all_chains(&prev_hash)?.height() + 1;
+        fetch_deployment_state(
+            &*db,
+            &self.consensus_manager,
+            &self.deployment_states,
+            &name,
+            &prev_hash,
+            height,
+        )
+    }
+
+    /// Returns true if the named consensus deployment is active for a block that builds on the block with hash
+    /// `prev_hash`
+    pub fn is_deployment_active(&self, name: String, prev_hash: HashOutput) -> Result<bool, ChainStorageError> {
+        Ok(self.fetch_deployment_state(name, prev_hash)?.is_active())
+    }
+
     pub fn fetch_target_difficulties_for_next_block(
         &self,
         current_block_hash: HashOutput,
@@ -809,18 +840,22 @@ where B: BlockchainBackend
         let mut targets = TargetDifficulties::new(&self.consensus_manager, current_header.height().saturating_add(1))
             .map_err(ChainStorageError::UnexpectedResult)?;
         // Add start header since we have it on hand
-        targets.add_front(
-            current_header.header(),
-            current_header.accumulated_data().target_difficulty,
-        );
+        targets
+            .add_front(
+                current_header.header(),
+                current_header.accumulated_data().target_difficulty,
+            )
+            .map_err(|e| ChainStorageError::UnexpectedResult(e.to_string()))?;
 
         while current_header.height() > 0 && !targets.is_full() {
             current_header = db.fetch_chain_header_in_all_chains(&current_header.header().prev_hash)?;
             if !targets.is_algo_full(current_header.header().pow_algo()) {
-                targets.add_front(
-                    current_header.header(),
-                    current_header.accumulated_data().target_difficulty,
-                );
+                targets
+                    .add_front(
+                        current_header.header(),
+                        current_header.accumulated_data().target_difficulty,
+                    )
+                    .map_err(|e| ChainStorageError::UnexpectedResult(e.to_string()))?;
             }
             if targets.is_full() {
                 break;
@@ -1506,6 +1541,98 @@ pub fn fetch_header<T: BlockchainBackend>(db: &T, block_num: u64) -> Result<Bloc
     fetch!(db, block_num, HeaderHeight)
 }
 
+/// Returns the activation state of the named consensus deployment for a block at `height` that builds on the block
+/// with hash `prev_hash`. Only the ancestors of the block are considered, so the state is correct for blocks on any
+/// chain. The state during each signalling window is cached by the hash of the last block before the window, so the
+/// signals in a window are only counted once.
+pub fn fetch_deployment_state<T: BlockchainBackend>(
+    db: &T,
+    rules: &ConsensusManager,
+    cache: &DeploymentStateCache,
+    name: &str,
+    prev_hash: &HashOutput,
+    height: u64,
+) -> Result<DeploymentState, ChainStorageError> {
+    let deployment = rules
+        .deployment(name)
+        .map_err(|e| ChainStorageError::InvalidQuery(e.to_string()))?;
+    let params = match deployment.signalling_params() {
+        Some(params) => params,
+        None => return deployment.state_at(height, |_, _| Ok(0)),
+    };
+    let first_window = params.window_start(params.start_height);
+    let mut window_start = params.window_start(height);
+    if window_start <= first_window {
+        // The state up to the first signalling window does not depend on any blocks
+        return deployment.state_at(height, |_, _| Ok(0));
+    }
+
+    let prev_header = db.fetch_chain_header_in_all_chains(prev_hash)?;
+    if prev_header.height() + 1 != height {
+        return Err(ChainStorageError::InvalidQuery(format!(
+            "Block {} is at height {}, not at height {}",
+            prev_hash,
+            prev_header.height(),
+            height.saturating_sub(1)
+        )));
+    }
+    let mut header = fetch_ancestor_header(db, prev_header, window_start - 1)?;
+
+    // Walk back one window at a time until a window with a known state, counting the signals in each window
+    let mut windows = Vec::new();
+    let mut state = loop {
+        if window_start == first_window {
+            break params.next_state(DeploymentState::Defined, first_window, 0);
+        }
+        let boundary_hash = *header.hash();
+        if let Some(state) = cache.get(deployment.name(), &boundary_hash)? {
+            break state;
+        }
+        let prev_window_start = window_start - params.window;
+        let mut num_signalled = 0;
+        loop {
+            if signalling_bits_of(header.header().version) & (1 << params.bit) != 0 {
+                num_signalled += 1;
+            }
+            if header.height() == prev_window_start {
+                break;
+            }
+            header = db.fetch_chain_header_in_all_chains(&header.header().prev_hash)?;
+        }
+        windows.push((window_start, boundary_hash, num_signalled));
+        window_start = prev_window_start;
+        if window_start > first_window {
+            header = db.fetch_chain_header_in_all_chains(&header.header().prev_hash)?;
+        }
+    };
+
+    for (window_start, boundary_hash, num_signalled) in windows.into_iter().rev() {
+        state = params.next_state(state, window_start, num_signalled);
+        cache.insert(deployment.name(), boundary_hash, state)?;
+    }
+    Ok(state)
+}
+
+/// Returns the ancestor of `header` at `height`. Ancestors of a header on the main chain are looked up by height,
+/// other chains are walked back through their previous block hashes.
+fn fetch_ancestor_header<T: BlockchainBackend>(
+    db: &T,
+    mut header: ChainHeader,
+    height: u64,
+) -> Result<ChainHeader, ChainStorageError> {
+    let is_main_chain = db
+        .fetch_chain_header_by_height(header.height())
+        .optional()?
+        .map_or(false, |main_chain_header| main_chain_header.hash() == header.hash());
+    if is_main_chain {
+        return db.fetch_chain_header_by_height(height);
+    }
+    while header.height() > height {
+        header = db.fetch_chain_header_in_all_chains(&header.header().prev_hash)?;
+    }
+    Ok(header)
+}
+
 pub fn fetch_headers<T: BlockchainBackend>(
     db: &T,
     mut start: u64,
@@ -1664,7 +1791,9 @@ pub fn fetch_target_difficulty_for_next_block<T: BlockchainBackend>(
         .new_target_difficulty(pow_algo, header.height() + 1)
         .map_err(ChainStorageError::UnexpectedResult)?;
     if header.header().pow.pow_algo == pow_algo {
-        target_difficulties.add_front(header.header().timestamp(), header.accumulated_data().target_difficulty);
+        target_difficulties
+            .add_front(header.header().timestamp(), header.accumulated_data().target_difficulty)
+            .map_err(|e| ChainStorageError::UnexpectedResult(e.to_string()))?;
     }
     while header.height() > 0 && !target_difficulties.is_full() {
         header = db.fetch_chain_header_in_all_chains(&header.header().prev_hash)?;
@@ -1672,7 +1801,9 @@ pub fn fetch_target_difficulty_for_next_block<T: BlockchainBackend>(
         // LWMA works with the "newest" value being at the back of the array, so we need to keep pushing to the front as
         // we keep adding "older" values
         if header.header().pow.pow_algo == pow_algo {
-            target_difficulties.add_front(header.header().timestamp(), header.accumulated_data().target_difficulty);
+            target_difficulties
+                .add_front(header.header().timestamp(), header.accumulated_data().target_difficulty)
+                .map_err(|e| ChainStorageError::UnexpectedResult(e.to_string()))?;
         }
     }
 
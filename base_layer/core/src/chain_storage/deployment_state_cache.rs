@@ -0,0 +1,59 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use tari_common_types::types::HashOutput;
+
+use crate::{chain_storage::ChainStorageError, consensus::DeploymentState};
+
+/// The maximum number of window states held by a [`DeploymentStateCache`]. There is one entry per deployment per
+/// signalling window on each chain that was validated, so the limit is only reached after a very long time.
+const MAX_CACHED_WINDOW_STATES: usize = 10_000;
+
+/// Caches the state of signalling deployments during a signalling window. The state during a window only depends on
+/// the blocks before it, so it is keyed by the hash of the last block before the window, which makes it correct for
+/// windows on any chain.
+#[derive(Debug, Clone, Default)]
+pub struct DeploymentStateCache {
+    states: Arc<RwLock<HashMap<(&'static str, HashOutput), DeploymentState>>>,
+}
+
+impl DeploymentStateCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The state of the named deployment during the window that follows the block with hash `boundary_hash`
+    pub fn get(
+        &self,
+        name: &'static str,
+        boundary_hash: &HashOutput,
+    ) -> Result<Option<DeploymentState>, ChainStorageError> {
+        let states = self
+            .states
+            .read()
+            .map_err(|_| ChainStorageError::AccessError("read lock on deployment state cache".into()))?;
+        Ok(states.get(&(name, *boundary_hash)).copied())
+    }
+
+    pub fn insert(
+        &self,
+        name: &'static str,
+        boundary_hash: HashOutput,
+        state: DeploymentState,
+    ) -> Result<(), ChainStorageError> {
+        let mut states = self
+            .states
+            .write()
+            .map_err(|_| ChainStorageError::AccessError("write lock on deployment state cache".into()))?;
+        if states.len() >= MAX_CACHED_WINDOW_STATES {
+            states.clear();
+        }
+        states.insert((name, boundary_hash), state);
+        Ok(())
+    }
+}
@@ -41,6 +41,7 @@ mod blockchain_database;
 pub use blockchain_database::{
     calculate_mmr_roots,
     calculate_validator_node_mr,
+    fetch_deployment_state,
     fetch_header,
     fetch_headers,
     fetch_target_difficulty_for_next_block,
@@ -53,6 +54,9 @@ pub use blockchain_database::{
 mod blockchain_backend;
 pub use blockchain_backend::BlockchainBackend;
 
+mod deployment_state_cache;
+pub use deployment_state_cache::DeploymentStateCache;
+
 mod consts;
 
 mod db_transaction;
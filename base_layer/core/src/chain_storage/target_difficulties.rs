@@ -20,16 +20,25 @@
 //  WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 //  USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use tari_utilities::epoch_time::EpochTime;
+
 use crate::{
     blocks::BlockHeader,
     consensus::ConsensusManager,
-    proof_of_work::{Difficulty, PowAlgorithm, TargetDifficultyWindow},
+    proof_of_work::{
+        lwma_diff::LinearWeightedMovingAverage,
+        Difficulty,
+        DifficultyAdjustment,
+        DifficultyAdjustmentError,
+        PowAlgorithm,
+        TargetDifficultyWindow,
+    },
 };
 
 #[derive(Debug, Clone)]
-pub struct TargetDifficulties {
-    randomx: TargetDifficultyWindow,
-    sha3x: TargetDifficultyWindow,
+pub struct TargetDifficulties<T = LinearWeightedMovingAverage> {
+    randomx: TargetDifficultyWindow<T>,
+    sha3x: TargetDifficultyWindow<T>,
 }
 
 impl TargetDifficulties {
@@ -39,15 +48,40 @@ impl TargetDifficulties {
             sha3x: consensus_rules.new_target_difficulty(PowAlgorithm::Sha3x, height)?,
         })
     }
+}
 
-    pub fn add_back(&mut self, header: &BlockHeader, target_difficulty: Difficulty) {
-        self.get_mut(header.pow_algo())
-            .add_back(header.timestamp(), target_difficulty);
+impl<T: DifficultyAdjustment> TargetDifficulties<T> {
+    /// Creates target difficulties from windows using any difficulty adjustment algorithm. This is intended for
+    /// analysing alternative algorithms, consensus always uses [TargetDifficulties::new].
+    pub fn from_windows(randomx: TargetDifficultyWindow<T>, sha3x: TargetDifficultyWindow<T>) -> Self {
+        Self { randomx, sha3x }
     }
 
-    pub fn add_front(&mut self, header: &BlockHeader, target_difficulty: Difficulty) {
+    pub fn add_back(
+        &mut self,
+        header: &BlockHeader,
+        target_difficulty: Difficulty,
+    ) -> Result<(), DifficultyAdjustmentError> {
+        self.add_back_for_algo(header.pow_algo(), header.timestamp(), target_difficulty)
+    }
+
+    pub fn add_front(
+        &mut self,
+        header: &BlockHeader,
+        target_difficulty: Difficulty,
+    ) -> Result<(), DifficultyAdjustmentError> {
         self.get_mut(header.pow_algo())
-            .add_front(header.timestamp(), target_difficulty);
+            .add_front(header.timestamp(), target_difficulty)
+    }
+
+    /// Appends the timestamp and target difficulty of a block mined with `algo`, without requiring the full header
+    pub fn add_back_for_algo(
+        &mut self,
+        algo: PowAlgorithm,
+        timestamp: EpochTime,
+        target_difficulty: Difficulty,
+    ) -> Result<(), DifficultyAdjustmentError> {
+        self.get_mut(algo).add_back(timestamp, target_difficulty)
     }
 
     pub fn is_algo_full(&self, algo: PowAlgorithm) -> bool {
@@ -58,7 +92,7 @@ impl TargetDifficulties {
         self.sha3x.is_full() && self.randomx.is_full()
     }
 
-    pub fn get(&self, algo: PowAlgorithm) -> &TargetDifficultyWindow {
+    pub fn get(&self, algo: PowAlgorithm) -> &TargetDifficultyWindow<T> {
         use PowAlgorithm::{RandomX, Sha3x};
         match algo {
             RandomX => &self.randomx,
@@ -66,7 +100,7 @@ impl TargetDifficulties {
         }
     }
 
-    fn get_mut(&mut self, algo: PowAlgorithm) -> &mut TargetDifficultyWindow {
+    fn get_mut(&mut self, algo: PowAlgorithm) -> &mut TargetDifficultyWindow<T> {
         use PowAlgorithm::{RandomX, Sha3x};
         match algo {
             RandomX => &mut self.randomx,
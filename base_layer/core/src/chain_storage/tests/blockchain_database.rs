@@ -653,3 +653,104 @@ mod validator_node_merkle_root {
         assert_eq!(tip.header().validator_node_mr, merkle_root);
     }
 }
+
+mod fetch_deployment_state {
+    use tari_common::configuration::Network;
+
+    use super::*;
+    use crate::{
+        consensus::{
+            deployments::{header_version_with_signals, SignallingParams},
+            ConsensusConstantsBuilder,
+            ConsensusManager,
+            Deployment,
+            DeploymentState,
+        },
+        test_helpers::blockchain::create_custom_blockchain,
+    };
+
+    const DEPLOYMENT: &str = "test";
+
+    fn setup_with_deployment() -> BlockchainDatabase<TempDatabase> {
+        let rules = ConsensusManager::builder(Network::LocalNet)
+            .add_consensus_constants(ConsensusConstantsBuilder::new(Network::LocalNet).build())
+            .add_deployment(Deployment::signalling(DEPLOYMENT, SignallingParams {
+                bit: 0,
+                start_height: 10,
+                timeout_height: 100,
+                window: 10,
+                threshold: 8,
+                min_activation_height: 0,
+                lock_in_on_timeout: false,
+            }))
+            .build()
+            .unwrap();
+        create_custom_blockchain(rules)
+    }
+
+    /// Adds headers on top of the tip, the header at each height signals if `signals(height)` is true
+    fn add_headers(db: &BlockchainDatabase<TempDatabase>, num_headers: u64, signals: impl Fn(u64) -> bool) {
+        let mut prev_header = db.fetch_tip_header().unwrap();
+        let blockchain_version = db.rules().consensus_constants(0).blockchain_version();
+        let headers = (0..num_headers)
+            .map(|_| {
+                let mut header = BlockHeader::from_previous(prev_header.header());
+                header.version = header_version_with_signals(blockchain_version, u8::from(signals(header.height)));
+                let accum = BlockHeaderAccumulatedData::builder(prev_header.accumulated_data())
+                    .with_hash(header.hash())
+                    .with_achieved_target_difficulty(
+                        AchievedTargetDifficulty::try_construct(
+                            PowAlgorithm::Sha3x,
+                            Difficulty::min(),
+                            Difficulty::min(),
+                        )
+                        .unwrap(),
+                    )
+                    .with_total_kernel_offset(Default::default())
+                    .build()
+                    .unwrap();
+                prev_header = ChainHeader::try_construct(header, accum).unwrap();
+                prev_header.clone()
+            })
+            .collect();
+        db.insert_valid_headers(headers).unwrap();
+    }
+
+    fn state_after(db: &BlockchainDatabase<TempDatabase>, height: u64) -> DeploymentState {
+        let hash = *db.fetch_chain_header(height).unwrap().hash();
+        db.fetch_deployment_state(DEPLOYMENT.to_string(), hash).unwrap()
+    }
+
+    #[test]
+    fn it_activates_a_deployment_from_the_signals_of_the_ancestors() {
+        let db = setup_with_deployment();
+        add_headers(&db, 40, |height| (10..18).contains(&height));
+
+        assert_eq!(state_after(&db, 8), DeploymentState::Defined);
+        assert_eq!(state_after(&db, 9), DeploymentState::Started);
+        assert_eq!(state_after(&db, 18), DeploymentState::Started);
+        assert_eq!(state_after(&db, 19), DeploymentState::LockedIn);
+        assert_eq!(state_after(&db, 29), DeploymentState::Active);
+        // The states of earlier windows are cached, the result does not depend on the order of the queries
+        assert_eq!(state_after(&db, 39), DeploymentState::Active);
+        assert_eq!(state_after(&db, 19), DeploymentState::LockedIn);
+    }
+
+    #[test]
+    fn it_does_not_activate_without_enough_signals() {
+        let db = setup_with_deployment();
+        add_headers(&db, 40, |height| (10..17).contains(&height));
+
+        assert_eq!(state_after(&db, 39), DeploymentState::Started);
+        let hash = *db.fetch_chain_header(40).unwrap().hash();
+        assert!(!db.is_deployment_active(DEPLOYMENT.to_string(), hash).unwrap());
+    }
+
+    #[test]
+    fn it_errors_for_unknown_deployments() {
+        let db = setup_with_deployment();
+        let hash = *db.fetch_chain_header(0).unwrap().hash();
+        let err = db.fetch_deployment_state("unknown".to_string(), hash).unwrap_err();
+        assert!(matches!(err, ChainStorageError::InvalidQuery(_)));
+    }
+}
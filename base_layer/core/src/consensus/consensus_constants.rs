@@ -143,7 +143,8 @@ fn version_zero() -> (
     let output_version_range = OutputVersionRange {
         outputs: TransactionOutputVersion::V0..=TransactionOutputVersion::V0,
         features: OutputFeaturesVersion::V0..=OutputFeaturesVersion::V0,
-        opcode: OpcodeVersion::V0..=OpcodeVersion::V0,
+        // V1 relative height locks and `ExecuteMast` are only permitted in blocks once `OPCODE_V1_DEPLOYMENT` is active
+        opcode: OpcodeVersion::V0..=OpcodeVersion::V1,
     };
 
     (input_version_range, output_version_range, kernel_version_range)
@@ -207,6 +208,11 @@ impl ConsensusConstants {
             .into()
     }
 
+    /// The Future Time Limit (FTL) in seconds, i.e. how far ahead of the current time a block timestamp may be
+    pub fn future_time_limit(&self) -> u64 {
+        self.future_time_limit
+    }
+
     /// This returns the FTL(Future Time Limit) for blocks
     /// Any block with a timestamp greater than this is rejected.
     /// This function returns the FTL as a UTC datetime
@@ -858,6 +864,11 @@ impl ConsensusConstantsBuilder {
         self
     }
 
+    pub fn with_difficulty_block_window(mut self, block_window: u64) -> Self {
+        self.consensus.difficulty_block_window = block_window;
+        self
+    }
+
     pub fn with_max_block_transaction_weight(mut self, weight: u64) -> Self {
         self.consensus.max_block_transaction_weight = weight;
         self
@@ -34,8 +34,11 @@ use crate::{
 };
 use crate::{
     consensus::{
+        deployments::{blockchain_version_of, header_version_with_signals, signalling_bits_of},
         emission::{Emission, EmissionSchedule},
         ConsensusConstants,
+        Deployment,
+        DeploymentError,
         NetworkConsensus,
     },
     proof_of_work::DifficultyAdjustmentError,
@@ -161,6 +164,53 @@ impl ConsensusManager {
     pub fn network(&self) -> NetworkConsensus {
         self.inner.network
     }
+
+    /// The consensus rule changes deployed on this network
+    pub fn deployments(&self) -> &[Deployment] {
+        &self.inner.deployments
+    }
+
+    /// Get the deployment with the given name
+    pub fn deployment(&self, name: &str) -> Result<&Deployment, DeploymentError> {
+        self.inner
+            .deployments
+            .iter()
+            .find(|d| d.name() == name)
+            .ok_or_else(|| DeploymentError::UnknownDeployment(name.to_string()))
+    }
+
+    /// The block header version for a new block at the given height. This is the blockchain version with the bits of
+    /// all deployments that are signalling at that height set.
+    pub fn header_version_for_new_block(&self, height: u64) -> u16 {
+        header_version_with_signals(
+            self.consensus_constants(height).blockchain_version(),
+            self.signalling_bits_at(height),
+        )
+    }
+
+    /// Returns true if a block header at the given height may have the given version. The blockchain version must be
+    /// in the valid range and all signalling bits that are set must belong to a deployment that is signalling at that
+    /// height.
+    pub fn is_valid_header_version(&self, height: u64, version: u16) -> bool {
+        let constants = self.consensus_constants(height);
+        if !constants
+            .valid_blockchain_version_range()
+            .contains(&blockchain_version_of(version))
+        {
+            return false;
+        }
+        signalling_bits_of(version) & !self.signalling_bits_at(height) == 0
+    }
+
+    /// The signalling bits of all deployments that are signalling at the given height
+    fn signalling_bits_at(&self, height: u64) -> u8 {
+        self.inner
+            .deployments
+            .iter()
+            .filter(|d| d.is_signalling_height(height))
+            .filter_map(|d| d.signalling_params())
+            .fold(0u8, |bits, params| bits | (1 << params.bit))
+    }
 }
 
 /// This is the used to control all consensus values.
@@ -172,6 +222,8 @@ struct ConsensusManagerInner {
     pub network: NetworkConsensus,
     /// The configuration for the emission schedule for integer only.
     pub emission: EmissionSchedule,
+    /// The consensus rule changes deployed on the network
+    pub deployments: Vec<Deployment>,
     /// This allows the user to set a custom Genesis block
     #[cfg(feature = "base_node")]
     pub gen_block: Option<ChainBlock>,
@@ -184,6 +236,7 @@ struct ConsensusManagerInner {
 pub struct ConsensusManagerBuilder {
     consensus_constants: Vec<ConsensusConstants>,
     network: NetworkConsensus,
+    deployments: Option<Vec<Deployment>>,
     /// This is can only used be used if the network is localnet
     #[cfg(feature = "base_node")]
     gen_block: Option<ChainBlock>,
@@ -197,6 +250,7 @@ impl ConsensusManagerBuilder {
         ConsensusManagerBuilder {
             consensus_constants: vec![],
             network: network.into(),
+            deployments: None,
             #[cfg(feature = "base_node")]
             gen_block: None,
             #[cfg(feature = "base_node")]
@@ -210,6 +264,13 @@ impl ConsensusManagerBuilder {
         self
     }
 
+    /// Adds a consensus rule change deployment. If any deployments are added, the network's default deployments are
+    /// not used.
+    pub fn add_deployment(mut self, deployment: Deployment) -> Self {
+        self.deployments.get_or_insert_with(Vec::new).push(deployment);
+        self
+    }
+
     /// Adds in a custom block to be used. This will be overwritten if the network is anything else than localnet
     #[cfg(feature = "base_node")]
     pub fn with_block(mut self, block: ChainBlock) -> Self {
@@ -236,6 +297,19 @@ impl ConsensusManagerBuilder {
             self.consensus_constants = self.network.create_consensus_constants();
         }
 
+        let deployments = self.deployments.unwrap_or_else(|| self.network.create_deployments());
+        for (i, deployment) in deployments.iter().enumerate() {
+            deployment.validate()?;
+            for other in &deployments[..i] {
+                if other.name() == deployment.name() {
+                    return Err(DeploymentError::DuplicateName(deployment.name()).into());
+                }
+                if other.overlaps(deployment) {
+                    return Err(DeploymentError::OverlappingSignallingBit(other.name(), deployment.name()).into());
+                }
+            }
+        }
+
         let emission = EmissionSchedule::new(
             self.consensus_constants[0].emission_initial,
             self.consensus_constants[0].emission_decay,
@@ -248,6 +322,7 @@ impl ConsensusManagerBuilder {
             consensus_constants: self.consensus_constants,
             network: self.network,
             emission,
+            deployments,
             #[cfg(feature = "base_node")]
             gen_block: self.gen_block,
             #[cfg(feature = "base_node")]
@@ -271,4 +346,6 @@ impl ConsensusManagerBuilder {
 pub enum ConsensusBuilderError {
     #[error("Cannot set a genesis block with a network other than LocalNet")]
     CannotSetGenesisBlock,
+    #[error("Invalid deployment: {0}")]
+    InvalidDeployment(#[from] DeploymentError),
 }
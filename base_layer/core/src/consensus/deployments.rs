@@ -0,0 +1,478 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Activation of consensus rule changes (deployments).
+//!
+//! A deployment either activates at a fixed height, or is activated by miners signalling readiness. Signalling
+//! deployments follow a state machine that is evaluated once per signalling window:
+//!
+//! ```text
+//! Defined -> Started -> LockedIn -> Active
+//!               \
+//!                -> Failed
+//! ```
+//!
+//! Miners signal readiness by setting the deployment's bit in the upper byte of the block header version. The lower
+//! byte of the header version holds the blockchain version. Once the number of signalling blocks in a window reaches
+//! the threshold, the deployment locks in and becomes active from the start of the next window (but never before its
+//! minimum activation height). A deployment that has not locked in by its timeout height fails, unless it is configured
+//! to lock in on timeout.
+
+use std::fmt;
+
+use thiserror::Error;
+
+/// The deployment of the `min_value_promise`, `script_matches` and `relative_height` covenant filters
+pub const COVENANT_FILTERS_DEPLOYMENT: &str = "covenant_filters";
+/// The deployment of the script opcodes and input data items of `OpcodeVersion::V1`: the relative height lock
+/// `CheckRelativeHeightVerify`, and `ExecuteMast` with the script it is revealed
+pub const OPCODE_V1_DEPLOYMENT: &str = "opcode_v1";
+
+/// The number of bits of the block header version that are available for signalling
+pub const NUM_SIGNALLING_BITS: u8 = 8;
+/// The header version bits that hold the blockchain version
+pub const BLOCKCHAIN_VERSION_MASK: u16 = 0x00ff;
+const SIGNALLING_BITS_SHIFT: u16 = 8;
+
+/// Returns the blockchain version part of a block header version
+pub fn blockchain_version_of(header_version: u16) -> u16 {
+    header_version & BLOCKCHAIN_VERSION_MASK
+}
+
+/// Returns the signalling bits of a block header version
+pub fn signalling_bits_of(header_version: u16) -> u8 {
+    #[allow(clippy::cast_possible_truncation)]
+    let bits = (header_version >> SIGNALLING_BITS_SHIFT) as u8;
+    bits
+}
+
+/// Returns a block header version with the given blockchain version and signalling bits
+pub fn header_version_with_signals(blockchain_version: u16, signalling_bits: u8) -> u16 {
+    blockchain_version_of(blockchain_version) | (u16::from(signalling_bits) << SIGNALLING_BITS_SHIFT)
+}
+
+/// How a deployment becomes active
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeploymentActivation {
+    /// The deployment is active from the given height onwards
+    Height(u64),
+    /// The deployment is activated by miners signalling readiness
+    Signalling(SignallingParams),
+}
+
+/// The parameters of a deployment that is activated by miner signalling
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SignallingParams {
+    /// The bit of the signalling byte of the header version used to signal readiness, in `0..NUM_SIGNALLING_BITS`
+    pub bit: u8,
+    /// Signalling starts in the first window that starts at or after this height
+    pub start_height: u64,
+    /// The deployment fails (or locks in, see `lock_in_on_timeout`) if it has not locked in by the first window that
+    /// starts at or after this height
+    pub timeout_height: u64,
+    /// The length of a signalling window in blocks
+    pub window: u64,
+    /// The number of blocks in a window that must signal for the deployment to lock in
+    pub threshold: u64,
+    /// A locked in deployment does not activate before this height
+    pub min_activation_height: u64,
+    /// If true, the deployment locks in at the timeout height instead of failing
+    pub lock_in_on_timeout: bool,
+}
+
+impl SignallingParams {
+    /// Returns true if blocks at the given height may signal for this deployment
+    pub fn is_signalling_height(&self, height: u64) -> bool {
+        (self.start_height..self.timeout_height).contains(&height)
+    }
+
+    /// The height of the first block of the window containing `height`
+    pub fn window_start(&self, height: u64) -> u64 {
+        height - height % self.window
+    }
+
+    /// The state of the deployment during the window starting at `window_start`, given the state during the previous
+    /// window and the number of blocks that signalled in it.
+    pub fn next_state(&self, previous: DeploymentState, window_start: u64, num_signalled: u64) -> DeploymentState {
+        use DeploymentState::{Active, Defined, Failed, LockedIn, Started};
+        match previous {
+            Defined if window_start >= self.timeout_height && !self.lock_in_on_timeout => Failed,
+            Defined if window_start >= self.start_height => Started,
+            Defined => Defined,
+            Started if num_signalled >= self.threshold => LockedIn,
+            Started if window_start >= self.timeout_height => {
+                if self.lock_in_on_timeout {
+                    LockedIn
+                } else {
+                    Failed
+                }
+            },
+            Started => Started,
+            LockedIn if window_start >= self.min_activation_height => Active,
+            LockedIn => LockedIn,
+            Active => Active,
+            Failed => Failed,
+        }
+    }
+}
+
+/// A named consensus rule change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deployment {
+    name: &'static str,
+    activation: DeploymentActivation,
+}
+
+impl Deployment {
+    /// A deployment that is active from `height` onwards
+    pub fn at_height(name: &'static str, height: u64) -> Self {
+        Self {
+            name,
+            activation: DeploymentActivation::Height(height),
+        }
+    }
+
+    /// A deployment that is activated by miner signalling
+    pub fn signalling(name: &'static str, params: SignallingParams) -> Self {
+        Self {
+            name,
+            activation: DeploymentActivation::Signalling(params),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    pub fn activation(&self) -> &DeploymentActivation {
+        &self.activation
+    }
+
+    /// The signalling parameters, if this deployment is activated by signalling
+    pub fn signalling_params(&self) -> Option<&SignallingParams> {
+        match &self.activation {
+            DeploymentActivation::Height(_) => None,
+            DeploymentActivation::Signalling(params) => Some(params),
+        }
+    }
+
+    /// Returns true if blocks at `height` may signal for this deployment
+    pub fn is_signalling_height(&self, height: u64) -> bool {
+        self.signalling_params()
+            .map_or(false, |params| params.is_signalling_height(height))
+    }
+
+    /// Checks that the deployment parameters are consistent
+    pub fn validate(&self) -> Result<(), DeploymentError> {
+        let params = match self.signalling_params() {
+            Some(params) => params,
+            None => return Ok(()),
+        };
+        let invalid = |reason: &str| DeploymentError::InvalidParameters {
+            name: self.name,
+            reason: reason.to_string(),
+        };
+        if params.bit >= NUM_SIGNALLING_BITS {
+            return Err(invalid("signalling bit out of range"));
+        }
+        if params.window == 0 {
+            return Err(invalid("signalling window must not be empty"));
+        }
+        if params.threshold == 0 || params.threshold > params.window {
+            return Err(invalid("threshold must be between 1 and the window length"));
+        }
+        if params.timeout_height <= params.start_height {
+            return Err(invalid("timeout height must be greater than the start height"));
+        }
+        Ok(())
+    }
+
+    /// Returns true if this deployment can signal at some height at which `other` can also signal, using the same bit
+    pub fn overlaps(&self, other: &Deployment) -> bool {
+        match (self.signalling_params(), other.signalling_params()) {
+            (Some(a), Some(b)) => {
+                a.bit == b.bit && a.start_height < b.timeout_height && b.start_height < a.timeout_height
+            },
+            _ => false,
+        }
+    }
+
+    /// Computes the state of the deployment at `height`. `count_signals(start, end_inclusive)` must return the number
+    /// of blocks in the given (inclusive) range of heights that signal for the deployment; it is called once per
+    /// completed signalling window from the start height up to `height`.
+    pub fn state_at<F, E>(&self, height: u64, mut count_signals: F) -> Result<DeploymentState, E>
+    where F: FnMut(u64, u64) -> Result<u64, E> {
+        let params = match &self.activation {
+            DeploymentActivation::Height(activation_height) => {
+                return Ok(if height >= *activation_height {
+                    DeploymentState::Active
+                } else {
+                    DeploymentState::Defined
+                });
+            },
+            DeploymentActivation::Signalling(params) => params,
+        };
+
+        let target_window = params.window_start(height);
+        // Windows before the start height are always in the defined state
+        let mut window_start = params.window_start(params.start_height);
+        let mut state = params.next_state(DeploymentState::Defined, window_start, 0);
+        if window_start > target_window {
+            return Ok(DeploymentState::Defined);
+        }
+        while window_start < target_window {
+            let next_window_start = window_start + params.window;
+            let num_signalled = if state == DeploymentState::Started {
+                count_signals(window_start, next_window_start - 1)?
+            } else {
+                0
+            };
+            state = params.next_state(state, next_window_start, num_signalled);
+            window_start = next_window_start;
+            if state.is_final() {
+                break;
+            }
+        }
+        Ok(state)
+    }
+}
+
+impl fmt::Display for Deployment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.activation {
+            DeploymentActivation::Height(height) => write!(f, "{} (active from height {})", self.name, height),
+            DeploymentActivation::Signalling(params) => write!(
+                f,
+                "{} (bit {}, heights {}..{}, {}/{} blocks)",
+                self.name, params.bit, params.start_height, params.timeout_height, params.threshold, params.window
+            ),
+        }
+    }
+}
+
+/// The activation state of a deployment at a given height
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeploymentState {
+    /// Signalling has not started yet
+    Defined,
+    /// Miners are signalling for the deployment
+    Started,
+    /// The threshold was reached, the deployment will become active
+    LockedIn,
+    /// The new consensus rules are in effect
+    Active,
+    /// The deployment timed out without reaching the threshold
+    Failed,
+}
+
+impl DeploymentState {
+    pub fn is_active(self) -> bool {
+        self == DeploymentState::Active
+    }
+
+    /// Returns true if the state can no longer change
+    pub fn is_final(self) -> bool {
+        matches!(self, DeploymentState::Active | DeploymentState::Failed)
+    }
+}
+
+impl fmt::Display for DeploymentState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DeploymentState::Defined => write!(f, "Defined"),
+            DeploymentState::Started => write!(f, "Started"),
+            DeploymentState::LockedIn => write!(f, "LockedIn"),
+            DeploymentState::Active => write!(f, "Active"),
+            DeploymentState::Failed => write!(f, "Failed"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum DeploymentError {
+    #[error("Deployment `{name}` has invalid parameters: {reason}")]
+    InvalidParameters { name: &'static str, reason: String },
+    #[error("Deployments `{0}` and `{1}` use the same signalling bit at the same time")]
+    OverlappingSignallingBit(&'static str, &'static str),
+    #[error("Deployment `{0}` is defined more than once")]
+    DuplicateName(&'static str),
+    #[error("Deployment `{0}` is not defined")]
+    UnknownDeployment(String),
+}
+
+#[cfg(test)]
+mod test {
+    use std::convert::Infallible;
+
+    use tari_common::configuration::Network;
+
+    use super::*;
+    use crate::consensus::{ConsensusBuilderError, ConsensusManager};
+
+    fn params() -> SignallingParams {
+        SignallingParams {
+            bit: 1,
+            start_height: 100,
+            timeout_height: 500,
+            window: 100,
+            threshold: 75,
+            min_activation_height: 0,
+            lock_in_on_timeout: false,
+        }
+    }
+
+    /// Computes the state at `height`, with `signals(window_start)` returning the number of signalling blocks
+    fn state(deployment: &Deployment, height: u64, signals: impl Fn(u64) -> u64) -> DeploymentState {
+        deployment
+            .state_at(height, |start, _| Ok::<_, Infallible>(signals(start)))
+            .unwrap()
+    }
+
+    #[test]
+    fn it_splits_header_versions() {
+        let version = header_version_with_signals(1, 0b0000_0101);
+        assert_eq!(version, 0x0501);
+        assert_eq!(blockchain_version_of(version), 1);
+        assert_eq!(signalling_bits_of(version), 0b0000_0101);
+        assert_eq!(signalling_bits_of(1), 0);
+    }
+
+    #[test]
+    fn it_activates_at_height() {
+        let deployment = Deployment::at_height("test", 10);
+        assert_eq!(state(&deployment, 9, |_| 0), DeploymentState::Defined);
+        assert_eq!(state(&deployment, 10, |_| 0), DeploymentState::Active);
+    }
+
+    #[test]
+    fn it_activates_after_the_threshold_is_reached() {
+        let deployment = Deployment::signalling("test", params());
+        assert_eq!(state(&deployment, 99, |_| 100), DeploymentState::Defined);
+        assert_eq!(state(&deployment, 100, |_| 100), DeploymentState::Started);
+        // Signalling in the window starting at 200 locks in during the next window and activates in the one after
+        let signals = |start| if start == 200 { 75 } else { 10 };
+        assert_eq!(state(&deployment, 299, signals), DeploymentState::Started);
+        assert_eq!(state(&deployment, 300, signals), DeploymentState::LockedIn);
+        assert_eq!(state(&deployment, 399, signals), DeploymentState::LockedIn);
+        assert_eq!(state(&deployment, 400, signals), DeploymentState::Active);
+        assert_eq!(state(&deployment, 10_000, signals), DeploymentState::Active);
+    }
+
+    #[test]
+    fn it_waits_for_the_minimum_activation_height() {
+        let deployment = Deployment::signalling("test", SignallingParams {
+            min_activation_height: 650,
+            ..params()
+        });
+        assert_eq!(state(&deployment, 250, |_| 100), DeploymentState::LockedIn);
+        assert_eq!(state(&deployment, 650, |_| 100), DeploymentState::LockedIn);
+        assert_eq!(state(&deployment, 700, |_| 100), DeploymentState::Active);
+    }
+
+    #[test]
+    fn it_fails_or_locks_in_on_timeout() {
+        let deployment = Deployment::signalling("test", params());
+        assert_eq!(state(&deployment, 499, |_| 74), DeploymentState::Started);
+        assert_eq!(state(&deployment, 500, |_| 74), DeploymentState::Failed);
+        assert_eq!(state(&deployment, 5_000, |_| 74), DeploymentState::Failed);
+
+        let deployment = Deployment::signalling("test", SignallingParams {
+            lock_in_on_timeout: true,
+            ..params()
+        });
+        assert_eq!(state(&deployment, 500, |_| 0), DeploymentState::LockedIn);
+        assert_eq!(state(&deployment, 600, |_| 0), DeploymentState::Active);
+    }
+
+    #[test]
+    fn it_only_counts_signals_while_started() {
+        let deployment = Deployment::signalling("test", params());
+        let mut windows = Vec::new();
+        deployment
+            .state_at(1_000, |start, end| {
+                windows.push((start, end));
+                Ok::<_, Infallible>(100)
+            })
+            .unwrap();
+        assert_eq!(windows, vec![(100, 199)]);
+    }
+
+    #[test]
+    fn it_validates_parameters() {
+        assert!(Deployment::signalling("test", params()).validate().is_ok());
+        let invalid = [
+            SignallingParams { bit: 8, ..params() },
+            SignallingParams { window: 0, ..params() },
+            SignallingParams {
+                threshold: 101,
+                ..params()
+            },
+            SignallingParams {
+                timeout_height: 100,
+                ..params()
+            },
+        ];
+        for params in invalid {
+            assert!(Deployment::signalling("test", params).validate().is_err());
+        }
+    }
+
+    #[test]
+    fn it_detects_overlapping_bits() {
+        let a = Deployment::signalling("a", params());
+        let b = Deployment::signalling("b", SignallingParams {
+            start_height: 400,
+            timeout_height: 800,
+            ..params()
+        });
+        let c = Deployment::signalling("c", SignallingParams {
+            start_height: 500,
+            timeout_height: 800,
+            ..params()
+        });
+        assert!(a.overlaps(&b));
+        assert!(!a.overlaps(&c));
+        assert!(!a.overlaps(&Deployment::at_height("d", 0)));
+    }
+
+    #[test]
+    fn it_sets_and_validates_signalling_bits_in_header_versions() {
+        let rules = ConsensusManager::builder(Network::LocalNet)
+            .add_deployment(Deployment::signalling("test", params()))
+            .build()
+            .unwrap();
+        let version = rules.consensus_constants(0).blockchain_version();
+        assert_eq!(rules.header_version_for_new_block(99), version);
+        assert_eq!(
+            rules.header_version_for_new_block(100),
+            header_version_with_signals(version, 0b10)
+        );
+        assert!(rules.is_valid_header_version(100, header_version_with_signals(version, 0b10)));
+        assert!(rules.is_valid_header_version(100, version));
+        assert!(!rules.is_valid_header_version(99, header_version_with_signals(version, 0b10)));
+        assert!(!rules.is_valid_header_version(100, header_version_with_signals(version, 0b01)));
+        assert!(!rules.is_valid_header_version(500, header_version_with_signals(version, 0b10)));
+    }
+
+    #[test]
+    fn it_rejects_conflicting_deployments() {
+        let err = ConsensusManager::builder(Network::LocalNet)
+            .add_deployment(Deployment::signalling("a", params()))
+            .add_deployment(Deployment::signalling("b", params()))
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConsensusBuilderError::InvalidDeployment(DeploymentError::OverlappingSignallingBit("a", "b"))
+        ));
+        let err = ConsensusManager::builder(Network::LocalNet)
+            .add_deployment(Deployment::at_height("a", 1))
+            .add_deployment(Deployment::at_height("a", 2))
+            .build()
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            ConsensusBuilderError::InvalidDeployment(DeploymentError::DuplicateName("a"))
+        ));
+    }
+}
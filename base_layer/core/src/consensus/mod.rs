@@ -26,6 +26,15 @@ pub(crate) mod chain_strength_comparer;
 pub mod consensus_constants;
 pub use consensus_constants::{ConsensusConstants, ConsensusConstantsBuilder};
 
+pub mod deployments;
+pub use deployments::{
+    Deployment,
+    DeploymentError,
+    DeploymentState,
+    COVENANT_FILTERS_DEPLOYMENT,
+    OPCODE_V1_DEPLOYMENT,
+};
+
 mod consensus_manager;
 pub use consensus_manager::{ConsensusBuilderError, ConsensusManager, ConsensusManagerBuilder, ConsensusManagerError};
 
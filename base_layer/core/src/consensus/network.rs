@@ -22,7 +22,10 @@
 
 use tari_common::configuration::Network;
 
-use super::consensus_constants::ConsensusConstants;
+use super::{
+    consensus_constants::ConsensusConstants,
+    deployments::{Deployment, COVENANT_FILTERS_DEPLOYMENT, OPCODE_V1_DEPLOYMENT},
+};
 
 /// Represents the consensus used for a given network
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
@@ -41,6 +44,18 @@ impl NetworkConsensus {
         }
     }
 
+    /// The consensus rule changes deployed on the network
+    pub fn create_deployments(&self) -> Vec<Deployment> {
+        match self.as_network() {
+            Network::LocalNet => vec![
+                Deployment::at_height(COVENANT_FILTERS_DEPLOYMENT, 0),
+                Deployment::at_height(OPCODE_V1_DEPLOYMENT, 0),
+            ],
+            // There are currently no deployments scheduled on the other networks
+            _ => Vec::new(),
+        }
+    }
+
     #[inline]
     pub fn as_network(self) -> Network {
         self.0
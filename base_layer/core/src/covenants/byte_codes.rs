@@ -67,7 +67,7 @@ pub(super) fn is_valid_filter_code(code: u8) -> bool {
 }
 
 /// Array with all possible covenant filter bytecodes.
-pub(super) const ALL_FILTERS: [u8; 10] = [
+pub(super) const ALL_FILTERS: [u8; 13] = [
     FILTER_IDENTITY,
     FILTER_AND,
     FILTER_OR,
@@ -78,6 +78,9 @@ pub(super) const ALL_FILTERS: [u8; 10] = [
     FILTER_FIELDS_HASHED_EQ,
     FILTER_FIELD_EQ,
     FILTER_ABSOLUTE_HEIGHT,
+    FILTER_MIN_VALUE_PROMISE,
+    FILTER_SCRIPT_MATCHES,
+    FILTER_RELATIVE_HEIGHT,
 ];
 
 /// Identity filter.
@@ -101,6 +104,12 @@ pub const FILTER_FIELDS_HASHED_EQ: u8 = 0x32;
 pub const FILTER_FIELD_EQ: u8 = 0x33;
 /// Absolute height filter.
 pub const FILTER_ABSOLUTE_HEIGHT: u8 = 0x34;
+/// Covenant filter minimum value promise byte code.
+pub const FILTER_MIN_VALUE_PROMISE: u8 = 0x35;
+/// Covenant filter script matches byte code.
+pub const FILTER_SCRIPT_MATCHES: u8 = 0x36;
+/// Covenant filter relative height byte code.
+pub const FILTER_RELATIVE_HEIGHT: u8 = 0x37;
 
 //---------------------------------- FIELD byte codes --------------------------------------------//
 /// Field commitment.
@@ -0,0 +1,868 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! A textual language for covenants.
+//!
+//! The syntax is that of the `covenant!` macro. A covenant is a filter, written as its name followed by a
+//! parenthesised, comma separated list of its arguments, each of which is a filter or a value:
+//!
+//! ```text
+//! # Before height 42, the output may only be spent into outputs with the same script
+//! or(absolute_height(@uint(42)), fields_preserved(@fields(@field::script)))
+//! ```
+//!
+//! Values are one of:
+//! * `@hash(<hex>)`, `@public_key(<hex>)`, `@commitment(<hex>)` or `@bytes(<hex>)`;
+//! * `@uint(<integer>)`;
+//! * `@output_type(<name>)`, e.g. `@output_type(Standard)`;
+//! * `@script(<script>)`, with the script in the TariScript assembly syntax;
+//! * `@covenant(<covenant>)`;
+//! * `@field::<name>` or `@fields(@field::<name>, ...)`, e.g. `@field::features_maturity`.
+//!
+//! Everything from a `#` to the end of the line is a comment, and an empty text is the empty covenant. Each filter is
+//! checked to be given the number and types of arguments it takes, including that the value compared by `field_eq`
+//! has the type of the field. [Covenant::decompile] formats a covenant in this language.
+
+use std::{convert::TryFrom, str::FromStr};
+
+use tari_common_types::types::{Commitment, FixedHash, PublicKey};
+use tari_script::TariScript;
+use tari_utilities::hex::{from_hex, to_hex, Hex};
+
+use crate::{
+    covenants::{
+        arguments::{BytesArg, CovenantArg},
+        covenant::{MAX_COVENANT_BYTES, MAX_COVENANT_TOKENS},
+        error::{CovenantError, CovenantParseError, CovenantParseErrorKind},
+        fields::{OutputField, OutputFields},
+        filters::CovenantFilter,
+        token::CovenantToken,
+        Covenant,
+    },
+    transactions::transaction_components::OutputType,
+};
+
+/// The names of the output fields, as used by the `covenant!` macro
+const FIELD_NAMES: [(OutputField, &str); 10] = [
+    (OutputField::Commitment, "commitment"),
+    (OutputField::Script, "script"),
+    (OutputField::SenderOffsetPublicKey, "sender_offset_public_key"),
+    (OutputField::Covenant, "covenant"),
+    (OutputField::Features, "features"),
+    (OutputField::FeaturesOutputType, "features_output_type"),
+    (OutputField::FeaturesMaturity, "features_maturity"),
+    (OutputField::FeaturesSideChainFeatures, "features_sidechain_feature"),
+    (OutputField::FeaturesRangeProofType, "features_range_proof_type"),
+    (OutputField::MinimumValuePromise, "minimum_value_promise"),
+];
+
+/// The maximum depth of filters and covenant values nested in one another. A covenant can hold no more filters than
+/// tokens, so this only rejects text that could not compile and bounds the recursion of the parser.
+const MAX_NESTING_DEPTH: usize = MAX_COVENANT_TOKENS;
+
+fn field_name(field: OutputField) -> &'static str {
+    FIELD_NAMES
+        .iter()
+        .find(|(f, _)| *f == field)
+        .map(|(_, name)| *name)
+        .expect("all fields are named")
+}
+
+/// The type of a covenant argument
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArgKind {
+    Hash,
+    PublicKey,
+    Commitment,
+    Script,
+    Covenant,
+    OutputType,
+    Uint,
+    Field,
+    Fields,
+    Bytes,
+}
+
+impl ArgKind {
+    fn of(arg: &CovenantArg) -> Self {
+        match arg {
+            CovenantArg::Hash(_) => ArgKind::Hash,
+            CovenantArg::PublicKey(_) => ArgKind::PublicKey,
+            CovenantArg::Commitment(_) => ArgKind::Commitment,
+            CovenantArg::TariScript(_) => ArgKind::Script,
+            CovenantArg::Covenant(_) => ArgKind::Covenant,
+            CovenantArg::OutputType(_) => ArgKind::OutputType,
+            CovenantArg::Uint(_) => ArgKind::Uint,
+            CovenantArg::OutputField(_) => ArgKind::Field,
+            CovenantArg::OutputFields(_) => ArgKind::Fields,
+            CovenantArg::Bytes(_) => ArgKind::Bytes,
+        }
+    }
+
+    fn describe(self) -> &'static str {
+        match self {
+            ArgKind::Hash => "@hash",
+            ArgKind::PublicKey => "@public_key",
+            ArgKind::Commitment => "@commitment",
+            ArgKind::Script => "@script",
+            ArgKind::Covenant => "@covenant",
+            ArgKind::OutputType => "@output_type",
+            ArgKind::Uint => "@uint",
+            ArgKind::Field => "@field",
+            ArgKind::Fields => "@fields",
+            ArgKind::Bytes => "@bytes",
+        }
+    }
+
+    /// The type of the values that `field_eq` can compare the field with, if any
+    fn of_field_value(field: OutputField) -> Option<Self> {
+        match field {
+            OutputField::Commitment => Some(ArgKind::Commitment),
+            OutputField::Script => Some(ArgKind::Script),
+            OutputField::SenderOffsetPublicKey => Some(ArgKind::PublicKey),
+            OutputField::Covenant => Some(ArgKind::Covenant),
+            OutputField::FeaturesOutputType => Some(ArgKind::OutputType),
+            OutputField::FeaturesMaturity => Some(ArgKind::Uint),
+            OutputField::Features |
+            OutputField::FeaturesSideChainFeatures |
+            OutputField::FeaturesRangeProofType |
+            OutputField::MinimumValuePromise => None,
+        }
+    }
+}
+
+/// An argument of a filter
+#[derive(Debug, Clone, Copy)]
+enum Param {
+    Filter,
+    Arg(ArgKind),
+    /// The value compared by `field_eq`, of the type of the preceding field
+    FieldValue,
+}
+
+/// The name of a filter in the covenant language
+pub(super) fn filter_name(filter: &CovenantFilter) -> &'static str {
+    signature(filter).0
+}
+
+/// The name of a filter and the arguments it takes
+fn signature(filter: &CovenantFilter) -> (&'static str, &'static [Param]) {
+    const TWO_FILTERS: &[Param] = &[Param::Filter, Param::Filter];
+    match filter {
+        CovenantFilter::Identity(_) => ("identity", &[]),
+        CovenantFilter::And(_) => ("and", TWO_FILTERS),
+        CovenantFilter::Or(_) => ("or", TWO_FILTERS),
+        CovenantFilter::Xor(_) => ("xor", TWO_FILTERS),
+        CovenantFilter::Not(_) => ("not", &[Param::Filter]),
+        CovenantFilter::OutputHashEq(_) => ("output_hash_eq", &[Param::Arg(ArgKind::Hash)]),
+        CovenantFilter::FieldsPreserved(_) => ("fields_preserved", &[Param::Arg(ArgKind::Fields)]),
+        CovenantFilter::FieldEq(_) => ("field_eq", &[Param::Arg(ArgKind::Field), Param::FieldValue]),
+        CovenantFilter::FieldsHashedEq(_) => ("fields_hashed_eq", &[
+            Param::Arg(ArgKind::Fields),
+            Param::Arg(ArgKind::Hash),
+        ]),
+        CovenantFilter::AbsoluteHeight(_) => ("absolute_height", &[Param::Arg(ArgKind::Uint)]),
+        CovenantFilter::MinValuePromise(_) => ("min_value_promise", &[Param::Arg(ArgKind::Uint)]),
+        CovenantFilter::ScriptMatches(_) => ("script_matches", &[Param::Arg(ArgKind::Script)]),
+        CovenantFilter::RelativeHeight(_) => ("relative_height", &[Param::Arg(ArgKind::Uint)]),
+    }
+}
+
+fn filter_by_name(name: &str) -> Option<CovenantFilter> {
+    let filter = match name {
+        "identity" => CovenantFilter::identity(),
+        "and" => CovenantFilter::and(),
+        "or" => CovenantFilter::or(),
+        "xor" => CovenantFilter::xor(),
+        "not" => CovenantFilter::not(),
+        "output_hash_eq" => CovenantFilter::output_hash_eq(),
+        "fields_preserved" => CovenantFilter::fields_preserved(),
+        "field_eq" => CovenantFilter::field_eq(),
+        "fields_hashed_eq" => CovenantFilter::fields_hashed_eq(),
+        "absolute_height" => CovenantFilter::absolute_height(),
+        "min_value_promise" => CovenantFilter::min_value_promise(),
+        "script_matches" => CovenantFilter::script_matches(),
+        "relative_height" => CovenantFilter::relative_height(),
+        _ => return None,
+    };
+    Some(filter)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    fn error(self, kind: CovenantParseErrorKind) -> CovenantParseError {
+        CovenantParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+}
+
+/// A filter or value, with the tokens it compiles to
+struct Item {
+    position: Position,
+    /// The argument type of a value, or `None` for a filter
+    kind: Option<ArgKind>,
+    tokens: Vec<(CovenantToken, Position)>,
+}
+
+impl Item {
+    fn describe(&self) -> &'static str {
+        self.kind.map_or("a filter", ArgKind::describe)
+    }
+}
+
+struct Parser {
+    chars: Vec<char>,
+    index: usize,
+    position: Position,
+    /// The number of filters and covenant values enclosing the item being parsed
+    depth: usize,
+    /// The number of tokens parsed so far in the covenant being parsed
+    num_tokens: usize,
+}
+
+impl Parser {
+    fn new(source: &str) -> Self {
+        Self {
+            chars: source.chars().collect(),
+            index: 0,
+            position: Position { line: 1, column: 1 },
+            depth: 0,
+            num_tokens: 0,
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.index).copied()
+    }
+
+    fn advance(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.index += 1;
+        if c == '\n' {
+            self.position.line += 1;
+            self.position.column = 1;
+        } else {
+            self.position.column += 1;
+        }
+        Some(c)
+    }
+
+    fn skip_comment(&mut self) {
+        while self.peek().map_or(false, |c| c != '\n') {
+            self.advance();
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while let Some(c) = self.peek() {
+            match c {
+                '#' => self.skip_comment(),
+                c if c.is_whitespace() => {
+                    self.advance();
+                },
+                _ => break,
+            }
+        }
+    }
+
+    fn unexpected(&self, expected: &'static str) -> CovenantParseError {
+        let found = match self.peek() {
+            Some(c) => format!("`{}`", c),
+            None => "the end of the input".to_string(),
+        };
+        self.position
+            .error(CovenantParseErrorKind::UnexpectedToken { expected, found })
+    }
+
+    fn expect(&mut self, c: char, expected: &'static str) -> Result<(), CovenantParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some(c) {
+            self.advance();
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    /// Reads a filter name, argument type, field name or value
+    fn word(&mut self, expected: &'static str) -> Result<(String, Position), CovenantParseError> {
+        self.skip_whitespace();
+        let position = self.position;
+        let mut word = String::new();
+        while let Some(c) = self.peek().filter(|c| c.is_ascii_alphanumeric() || *c == '_') {
+            word.push(c);
+            self.advance();
+        }
+        if word.is_empty() {
+            return Err(self.unexpected(expected));
+        }
+        Ok((word, position))
+    }
+
+    /// Reads the text up to the parenthesis that closes the one just read, which is consumed
+    fn enclosed_text(&mut self) -> Result<String, CovenantParseError> {
+        let mut text = String::new();
+        let mut depth = 0usize;
+        loop {
+            match self.peek() {
+                Some('#') => self.skip_comment(),
+                Some(')') if depth == 0 => {
+                    self.advance();
+                    return Ok(text);
+                },
+                Some(c) => {
+                    match c {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {},
+                    }
+                    text.push(c);
+                    self.advance();
+                },
+                None => return Err(self.unexpected("`)`")),
+            }
+        }
+    }
+
+    /// Counts a token of the covenant being parsed, failing as soon as there are too many for it to compile
+    fn push_token(&mut self, position: Position) -> Result<(), CovenantParseError> {
+        self.num_tokens += 1;
+        if self.num_tokens > MAX_COVENANT_TOKENS {
+            return Err(position.error(CovenantParseErrorKind::TooManyTokens(MAX_COVENANT_TOKENS)));
+        }
+        Ok(())
+    }
+
+    fn enter(&mut self, position: Position) -> Result<(), CovenantParseError> {
+        self.depth += 1;
+        if self.depth > MAX_NESTING_DEPTH {
+            return Err(position.error(CovenantParseErrorKind::TooDeeplyNested(MAX_NESTING_DEPTH)));
+        }
+        Ok(())
+    }
+
+    fn leave(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn parse(mut self) -> Result<Covenant, CovenantParseError> {
+        self.skip_whitespace();
+        if self.peek().is_none() {
+            return Ok(Covenant::new());
+        }
+        let item = self.parse_filter()?;
+        self.skip_whitespace();
+        if self.peek().is_some() {
+            return Err(self.unexpected("the end of the covenant"));
+        }
+        build_covenant(item.tokens)
+    }
+
+    fn parse_item(&mut self) -> Result<Item, CovenantParseError> {
+        self.skip_whitespace();
+        if self.peek() == Some('@') {
+            self.parse_value()
+        } else {
+            self.parse_filter()
+        }
+    }
+
+    fn parse_filter(&mut self) -> Result<Item, CovenantParseError> {
+        let (name, position) = self.word("a filter")?;
+        let filter =
+            filter_by_name(&name).ok_or_else(|| position.error(CovenantParseErrorKind::UnknownFilter(name)))?;
+        let (name, params) = signature(&filter);
+        self.push_token(position)?;
+        self.enter(position)?;
+
+        self.expect('(', "`(`")?;
+        let mut arguments = Vec::new();
+        self.skip_whitespace();
+        if self.peek() == Some(')') {
+            self.advance();
+        } else {
+            loop {
+                arguments.push(self.parse_item()?);
+                self.skip_whitespace();
+                match self.peek() {
+                    Some(',') => {
+                        self.advance();
+                    },
+                    Some(')') => {
+                        self.advance();
+                        break;
+                    },
+                    _ => return Err(self.unexpected("`,` or `)`")),
+                }
+            }
+        }
+
+        if arguments.len() != params.len() {
+            let position = arguments.get(params.len()).map_or(position, |item| item.position);
+            return Err(position.error(CovenantParseErrorKind::WrongArgumentCount {
+                filter: name,
+                expected: params.len(),
+                actual: arguments.len(),
+            }));
+        }
+        let mut tokens = vec![(CovenantToken::from(filter), position)];
+        let mut field = None;
+        for (index, (param, item)) in params.iter().zip(arguments).enumerate() {
+            let expected = match param {
+                Param::Filter => None,
+                Param::Arg(kind) => Some(*kind),
+                Param::FieldValue => {
+                    let field: OutputField = field.expect("field_eq takes a field before the value");
+                    let kind = ArgKind::of_field_value(field).ok_or_else(|| {
+                        item.position
+                            .error(CovenantParseErrorKind::IncomparableField(field_name(field)))
+                    })?;
+                    Some(kind)
+                },
+            };
+            if item.kind != expected {
+                return Err(item.position.error(CovenantParseErrorKind::ArgumentTypeMismatch {
+                    filter: name,
+                    index: index + 1,
+                    expected: expected.map_or("a filter", ArgKind::describe),
+                    found: item.describe(),
+                }));
+            }
+            if let Some((CovenantToken::Arg(arg), _)) = item.tokens.first() {
+                if let CovenantArg::OutputField(f) = **arg {
+                    field = Some(f);
+                }
+            }
+            tokens.extend(item.tokens);
+        }
+        self.leave();
+        Ok(Item {
+            position,
+            kind: None,
+            tokens,
+        })
+    }
+
+    fn parse_value(&mut self) -> Result<Item, CovenantParseError> {
+        let position = self.position;
+        self.expect('@', "`@`")?;
+        let (kind, kind_position) = self.word("an argument type")?;
+        self.push_token(position)?;
+        let arg = match kind.as_str() {
+            "field" => {
+                self.expect(':', "`::`")?;
+                self.expect(':', "`::`")?;
+                CovenantArg::OutputField(self.parse_field_name()?)
+            },
+            "fields" => {
+                self.expect('(', "`(`")?;
+                let mut fields = OutputFields::new();
+                loop {
+                    self.expect('@', "`@field::<name>`")?;
+                    let (kind, position) = self.word("`field`")?;
+                    if kind != "field" {
+                        return Err(position.error(CovenantParseErrorKind::UnexpectedToken {
+                            expected: "`@field::<name>`",
+                            found: format!("`@{}`", kind),
+                        }));
+                    }
+                    self.expect(':', "`::`")?;
+                    self.expect(':', "`::`")?;
+                    fields.push(self.parse_field_name()?);
+                    self.skip_whitespace();
+                    match self.peek() {
+                        Some(',') => {
+                            self.advance();
+                        },
+                        Some(')') => {
+                            self.advance();
+                            break;
+                        },
+                        _ => return Err(self.unexpected("`,` or `)`")),
+                    }
+                }
+                CovenantArg::OutputFields(fields)
+            },
+            "script" => {
+                self.expect('(', "`(`")?;
+                self.skip_whitespace();
+                let script_position = self.position;
+                let source = self.enclosed_text()?;
+                let script = TariScript::from_str(&source)
+                    .map_err(|e| script_position.error(CovenantParseErrorKind::InvalidScript(e.to_string())))?;
+                CovenantArg::TariScript(script)
+            },
+            "covenant" => {
+                self.expect('(', "`(`")?;
+                self.enter(position)?;
+                self.skip_whitespace();
+                // The tokens of the nested covenant are counted separately, as it is compiled on its own
+                let num_tokens = std::mem::replace(&mut self.num_tokens, 0);
+                let covenant = if self.peek() == Some(')') {
+                    Covenant::new()
+                } else {
+                    build_covenant(self.parse_filter()?.tokens)?
+                };
+                self.num_tokens = num_tokens;
+                self.leave();
+                self.expect(')', "`)`")?;
+                CovenantArg::Covenant(covenant)
+            },
+            "uint" => {
+                let (value, position) = self.parse_enclosed_word("an integer")?;
+                let value = value
+                    .parse()
+                    .map_err(|_| position.error(CovenantParseErrorKind::InvalidInteger(value)))?;
+                CovenantArg::Uint(value)
+            },
+            "output_type" => {
+                let (value, position) = self.parse_enclosed_word("an output type")?;
+                let output_type = OutputType::all()
+                    .iter()
+                    .find(|output_type| output_type.to_string() == value)
+                    .ok_or_else(|| position.error(CovenantParseErrorKind::UnknownOutputType(value)))?;
+                CovenantArg::OutputType(*output_type)
+            },
+            "hash" => {
+                let (value, position) = self.parse_enclosed_word("a hash")?;
+                let hash =
+                    FixedHash::from_hex(&value).map_err(|_| invalid_value(position, "32-byte hex hash", value))?;
+                CovenantArg::Hash(hash)
+            },
+            "public_key" => {
+                let (value, position) = self.parse_enclosed_word("a public key")?;
+                let public_key =
+                    PublicKey::from_hex(&value).map_err(|_| invalid_value(position, "public key", value))?;
+                CovenantArg::PublicKey(public_key)
+            },
+            "commitment" => {
+                let (value, position) = self.parse_enclosed_word("a commitment")?;
+                let commitment =
+                    Commitment::from_hex(&value).map_err(|_| invalid_value(position, "commitment", value))?;
+                CovenantArg::Commitment(commitment)
+            },
+            "bytes" => {
+                let (value, position) = self.parse_enclosed_word("hex encoded bytes")?;
+                let bytes = from_hex(&value)
+                    .ok()
+                    .and_then(|bytes| BytesArg::try_from(bytes).ok())
+                    .ok_or_else(|| invalid_value(position, "hex encoded bytes", value))?;
+                CovenantArg::Bytes(bytes)
+            },
+            _ => return Err(kind_position.error(CovenantParseErrorKind::UnknownArgument(kind))),
+        };
+        Ok(Item {
+            position,
+            kind: Some(ArgKind::of(&arg)),
+            tokens: vec![(arg.into(), position)],
+        })
+    }
+
+    fn parse_field_name(&mut self) -> Result<OutputField, CovenantParseError> {
+        let (name, position) = self.word("a field name")?;
+        FIELD_NAMES
+            .iter()
+            .find(|(_, n)| *n == name)
+            .map(|(field, _)| *field)
+            .ok_or_else(|| position.error(CovenantParseErrorKind::UnknownField(name)))
+    }
+
+    fn parse_enclosed_word(&mut self, expected: &'static str) -> Result<(String, Position), CovenantParseError> {
+        self.expect('(', "`(`")?;
+        let word = self.word(expected)?;
+        self.expect(')', "`)`")?;
+        Ok(word)
+    }
+}
+
+fn invalid_value(position: Position, expected: &'static str, value: String) -> CovenantParseError {
+    position.error(CovenantParseErrorKind::InvalidValue { expected, value })
+}
+
+fn build_covenant(tokens: Vec<(CovenantToken, Position)>) -> Result<Covenant, CovenantParseError> {
+    let mut covenant = Covenant::new();
+    for (token, position) in tokens {
+        covenant
+            .push_token(token)
+            .map_err(|_| position.error(CovenantParseErrorKind::TooManyTokens(MAX_COVENANT_TOKENS)))?;
+    }
+    if covenant.get_byte_length() > MAX_COVENANT_BYTES {
+        let start = Position { line: 1, column: 1 };
+        return Err(start.error(CovenantParseErrorKind::TooManyBytes(MAX_COVENANT_BYTES)));
+    }
+    Ok(covenant)
+}
+
+impl FromStr for Covenant {
+    type Err = CovenantParseError;
+
+    /// Compiles a covenant from its textual representation, see the `compiler` module.
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        Parser::new(source).parse()
+    }
+}
+
+impl Covenant {
+    /// Formats the covenant in its textual representation, which compiles back into the same covenant. Fails if the
+    /// tokens of the covenant do not form a valid filter tree, e.g. if a filter is missing an argument.
+    pub fn decompile(&self) -> Result<String, CovenantError> {
+        let mut text = String::new();
+        let mut tokens = self.tokens().iter();
+        if self.is_empty() {
+            return Ok(text);
+        }
+        decompile_filter(&mut tokens, &mut text)?;
+        if tokens.next().is_some() {
+            return Err(CovenantError::RemainingTokens);
+        }
+        Ok(text)
+    }
+}
+
+pub(super) fn decompile_filter<'a, I: Iterator<Item = &'a CovenantToken>>(
+    tokens: &mut I,
+    text: &mut String,
+) -> Result<(), CovenantError> {
+    let filter = match tokens.next().ok_or(CovenantError::UnexpectedEndOfTokens)? {
+        CovenantToken::Filter(filter) => filter,
+        CovenantToken::Arg(_) => return Err(CovenantError::ExpectedFilterButGotArg),
+    };
+    let (name, params) = signature(filter);
+    text.push_str(name);
+    text.push('(');
+    for (index, param) in params.iter().enumerate() {
+        if index > 0 {
+            text.push_str(", ");
+        }
+        if let Param::Filter = param {
+            decompile_filter(tokens, text)?;
+            continue;
+        }
+        let arg = match tokens.next().ok_or(CovenantError::UnexpectedEndOfTokens)? {
+            CovenantToken::Arg(arg) => arg,
+            CovenantToken::Filter(_) => return Err(CovenantError::ExpectedArgButGotFilter),
+        };
+        if let Param::Arg(kind) = param {
+            if ArgKind::of(arg) != *kind {
+                return Err(CovenantError::UnexpectedArgument {
+                    expected: kind.describe(),
+                    got: arg.to_string(),
+                });
+            }
+        }
+        text.push_str(&decompile_arg(arg)?);
+    }
+    text.push(')');
+    Ok(())
+}
+
+fn decompile_arg(arg: &CovenantArg) -> Result<String, CovenantError> {
+    let text = match arg {
+        CovenantArg::Hash(hash) => format!("@hash({})", hash.to_hex()),
+        CovenantArg::PublicKey(public_key) => format!("@public_key({})", public_key.to_hex()),
+        CovenantArg::Commitment(commitment) => format!("@commitment({})", commitment.to_hex()),
+        CovenantArg::TariScript(script) => format!("@script({})", script),
+        CovenantArg::Covenant(covenant) => format!("@covenant({})", covenant.decompile()?),
+        CovenantArg::OutputType(output_type) => format!("@output_type({})", output_type),
+        CovenantArg::Uint(value) => format!("@uint({})", value),
+        CovenantArg::OutputField(field) => format!("@field::{}", field_name(*field)),
+        CovenantArg::OutputFields(fields) => format!(
+            "@fields({})",
+            fields
+                .iter()
+                .map(|field| format!("@field::{}", field_name(*field)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+        CovenantArg::Bytes(bytes) => format!("@bytes({})", to_hex(bytes.as_ref())),
+    };
+    Ok(text)
+}
+
+#[cfg(test)]
+mod test {
+    use tari_script::script;
+
+    use super::*;
+    use crate::covenant;
+
+    const KEY: &str = "b0c1f788f137ba0cdc0b61e89ee43b80ebf5cca4136d3229561bf11eba347849";
+    const HASH: &str = "53563b674ba8e5166adb57afa8355bcf2ee759941eef8f8959b802367c2558bd";
+
+    fn parse_err(source: &str) -> CovenantParseError {
+        Covenant::from_str(source).unwrap_err()
+    }
+
+    #[test]
+    fn it_compiles_the_covenant_macro_syntax() {
+        let covenant = Covenant::from_str(
+            "# Spendable into the same script, or anywhere after height 42\nor(absolute_height(@uint(42)), \
+             fields_preserved(@fields(@field::script, @field::features_maturity)))",
+        )
+        .unwrap();
+        assert_eq!(
+            covenant,
+            covenant!(or(
+                absolute_height(@uint(42)),
+                fields_preserved(@fields(@field::script, @field::features_maturity))
+            ))
+            .unwrap()
+        );
+        assert_eq!(Covenant::from_str("  \n# nothing\n").unwrap(), Covenant::new());
+    }
+
+    #[test]
+    fn it_round_trips_all_filters_and_arguments() {
+        let hash = FixedHash::from_hex(HASH).unwrap();
+        let public_key = PublicKey::from_hex(KEY).unwrap();
+        let script = script!(CheckHeightVerify(100) PushPubKey(Box::new(public_key.clone()))).unwrap();
+        let inner = covenant!(identity()).unwrap();
+        let covenant = covenant!(and(
+            xor(
+                not(output_hash_eq(@hash(hash))),
+                fields_hashed_eq(@fields(@field::commitment, @field::covenant), @hash(hash))
+            ),
+            or(
+                and(
+                    field_eq(@field::script, @script(script)),
+                    field_eq(@field::sender_offset_public_key, @public_key(public_key))
+                ),
+                and(
+                    field_eq(@field::covenant, @covenant(inner)),
+                    field_eq(@field::features_output_type, @output_type(Burn))
+                )
+            )
+        ))
+        .unwrap();
+        let text = covenant.decompile().unwrap();
+        assert_eq!(Covenant::from_str(&text).unwrap(), covenant);
+
+        let covenant = covenant!(field_eq(@field::commitment, @commitment(Commitment::default()))).unwrap();
+        assert_eq!(Covenant::from_str(&covenant.decompile().unwrap()).unwrap(), covenant);
+        assert_eq!(Covenant::new().decompile().unwrap(), "");
+
+        let source =
+            "and(min_value_promise(@uint(1000)), or(script_matches(@script(PushOne)), relative_height(@uint(10))))";
+        assert_eq!(Covenant::from_str(source).unwrap().decompile().unwrap(), source);
+    }
+
+    #[test]
+    fn it_reports_type_mismatches_with_positions() {
+        let err = parse_err("and(identity(), @uint(1))");
+        assert_eq!((err.line, err.column), (1, 17));
+        assert_eq!(err.kind, CovenantParseErrorKind::ArgumentTypeMismatch {
+            filter: "and",
+            index: 2,
+            expected: "a filter",
+            found: "@uint",
+        });
+
+        let err = parse_err(&format!(
+            "not(\n  field_eq(@field::features_maturity, @hash({})))",
+            HASH
+        ));
+        assert_eq!((err.line, err.column), (2, 39));
+        assert!(matches!(err.kind, CovenantParseErrorKind::ArgumentTypeMismatch {
+            expected: "@uint",
+            ..
+        }));
+
+        let err = parse_err("field_eq(@field::features, @uint(1))");
+        assert_eq!(err.kind, CovenantParseErrorKind::IncomparableField("features"));
+
+        let err = parse_err("absolute_height()");
+        assert_eq!(err.kind, CovenantParseErrorKind::WrongArgumentCount {
+            filter: "absolute_height",
+            expected: 1,
+            actual: 0,
+        });
+    }
+
+    #[test]
+    fn it_rejects_malformed_text() {
+        assert_eq!(
+            parse_err("identity() identity()").kind,
+            CovenantParseErrorKind::UnexpectedToken {
+                expected: "the end of the covenant",
+                found: "`i`".to_string()
+            }
+        );
+        assert_eq!(
+            parse_err("maybe()").kind,
+            CovenantParseErrorKind::UnknownFilter("maybe".to_string())
+        );
+        assert_eq!(
+            parse_err("fields_preserved(@fields(@field::colour))").kind,
+            CovenantParseErrorKind::UnknownField("colour".to_string())
+        );
+        assert_eq!(
+            parse_err("absolute_height(@uint(-1))").kind,
+            CovenantParseErrorKind::UnexpectedToken {
+                expected: "an integer",
+                found: "`-`".to_string()
+            }
+        );
+        assert!(matches!(
+            parse_err("field_eq(@field::script, @script(PushInt(1) Pop))").kind,
+            CovenantParseErrorKind::InvalidScript(_)
+        ));
+        assert!(matches!(
+            parse_err("output_hash_eq(@hash(abcd))").kind,
+            CovenantParseErrorKind::InvalidValue { .. }
+        ));
+        assert!(matches!(
+            parse_err("or(identity(), identity()").kind,
+            CovenantParseErrorKind::UnexpectedToken { .. }
+        ));
+    }
+
+    #[test]
+    fn it_limits_the_tokens_and_nesting_while_parsing() {
+        let nested_nots = |n: usize| format!("{}identity(){}", "not(".repeat(n), ")".repeat(n));
+        assert_eq!(
+            Covenant::from_str(&nested_nots(MAX_COVENANT_TOKENS - 1))
+                .unwrap()
+                .tokens()
+                .len(),
+            128
+        );
+        let err = parse_err(&nested_nots(100_000));
+        assert_eq!(err.kind, CovenantParseErrorKind::TooManyTokens(MAX_COVENANT_TOKENS));
+        assert_eq!((err.line, err.column), (1, 4 * MAX_COVENANT_TOKENS + 1));
+
+        let nested_covenants = |n: usize| {
+            format!(
+                "{}identity(){}",
+                "field_eq(@field::covenant, @covenant(".repeat(n),
+                "))".repeat(n)
+            )
+        };
+        assert!(Covenant::from_str(&nested_covenants(3)).is_ok());
+        let err = parse_err(&nested_covenants(100_000));
+        assert_eq!(err.kind, CovenantParseErrorKind::TooDeeplyNested(MAX_NESTING_DEPTH));
+    }
+
+    #[test]
+    fn it_fails_to_decompile_incomplete_covenants() {
+        let mut covenant = Covenant::new();
+        covenant.push_token(CovenantToken::and()).unwrap();
+        covenant.push_token(CovenantToken::identity()).unwrap();
+        assert!(matches!(
+            covenant.decompile().unwrap_err(),
+            CovenantError::UnexpectedEndOfTokens
+        ));
+        covenant.push_token(CovenantToken::uint(1)).unwrap();
+        assert!(matches!(
+            covenant.decompile().unwrap_err(),
+            CovenantError::ExpectedFilterButGotArg
+        ));
+    }
+}
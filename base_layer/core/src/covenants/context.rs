@@ -23,8 +23,11 @@
 use crate::{
     covenants::{
         arguments::CovenantArg,
+        compiler::{decompile_filter, filter_name},
+        dry_run::FilterTrace,
         error::CovenantError,
         filters::CovenantFilter,
+        output_set::OutputSet,
         token::{CovenantToken, CovenantTokenCollection},
     },
     transactions::transaction_components::TransactionInput,
@@ -37,6 +40,8 @@ pub struct CovenantContext<'a> {
     input: &'a TransactionInput,
     tokens: CovenantTokenCollection,
     block_height: u64,
+    input_mined_height: Option<u64>,
+    trace: Option<FilterTrace>,
 }
 
 impl<'a> CovenantContext<'a> {
@@ -45,9 +50,23 @@ impl<'a> CovenantContext<'a> {
             input,
             tokens,
             block_height,
+            input_mined_height: None,
+            trace: None,
         }
     }
 
+    /// Sets the height of the block the output spent by the input was mined in, used by the relative height filter
+    pub fn with_input_mined_height(mut self, input_mined_height: u64) -> Self {
+        self.input_mined_height = Some(input_mined_height);
+        self
+    }
+
+    /// Records the outputs each filter is applied to and matches, see [Covenant::dry_run](super::Covenant::dry_run)
+    pub(super) fn with_trace(mut self) -> Self {
+        self.trace = Some(FilterTrace::default());
+        self
+    }
+
     /// Returns true if there are more tokens to consume, otherwise false
     pub fn has_more_tokens(&self) -> bool {
         !self.tokens.is_empty()
@@ -84,8 +103,42 @@ impl<'a> CovenantContext<'a> {
         self.block_height
     }
 
+    /// The height of the block the output spent by the input was mined in, if known
+    pub fn input_mined_height(&self) -> Option<u64> {
+        self.input_mined_height
+    }
+
     /// Transaction input
     pub fn input(&self) -> &TransactionInput {
         self.input
     }
+
+    /// Records that `filter`, whose token was just consumed, is applied to `output_set`. Does nothing unless tracing.
+    pub(super) fn begin_filter(&mut self, filter: &CovenantFilter, output_set: &OutputSet<'_>) {
+        if let Some(trace) = self.trace.as_mut() {
+            // The arguments and sub-filters of the filter are the next tokens
+            let filter_token = CovenantToken::Filter(filter.clone());
+            let mut source = String::new();
+            if decompile_filter(
+                &mut std::iter::once(&filter_token).chain(self.tokens.iter()),
+                &mut source,
+            )
+            .is_err()
+            {
+                source = format!("{}(..)", filter_name(filter));
+            }
+            trace.begin(filter_name(filter), source, output_set);
+        }
+    }
+
+    /// Records the outputs matched by the filter last passed to `begin_filter`, or `None` if it failed
+    pub(super) fn end_filter(&mut self, matched: Option<&OutputSet<'_>>) {
+        if let Some(trace) = self.trace.as_mut() {
+            trace.end(matched);
+        }
+    }
+
+    pub(super) fn take_trace(&mut self) -> Option<FilterTrace> {
+        self.trace.take()
+    }
 }
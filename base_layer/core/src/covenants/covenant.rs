@@ -33,20 +33,22 @@ use super::decoder::CovenantDecodeError;
 use crate::{
     common::byte_counter::ByteCounter,
     covenants::{
+        compiler::filter_name,
         context::CovenantContext,
         decoder::CovenantTokenDecoder,
         encoder::CovenantTokenEncoder,
         error::CovenantError,
-        filters::Filter,
+        filters::{CovenantFilter, Filter},
         output_set::OutputSet,
         token::{CovenantToken, CovenantTokenCollection},
     },
     transactions::transaction_components::{TransactionInput, TransactionOutput},
 };
 
-const MAX_COVENANT_BYTES: usize = 4096;
+pub(super) const MAX_COVENANT_BYTES: usize = 4096;
+pub(super) const MAX_COVENANT_TOKENS: usize = 128;
 
-pub(crate) type CovenantTokens = MaxSizeVec<CovenantToken, 128>;
+pub(crate) type CovenantTokens = MaxSizeVec<CovenantToken, MAX_COVENANT_TOKENS>;
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 /// A covenant allows a UTXO to specify some restrictions on how it is spent in a future transaction.
@@ -126,7 +128,8 @@ impl Covenant {
     }
 
     /// It executes the covenant on the transaction input being spent, it filters the transaction outputs which should
-    /// generate at least one match. An empty covenant is an identity and matches all outputs.
+    /// generate at least one match. An empty covenant is an identity and matches all outputs. Relative height filters
+    /// match all outputs, see [Covenant::execute_with_input_mined_height].
     pub fn execute(
         &self,
         block_height: u64,
@@ -140,9 +143,36 @@ impl Covenant {
 
         let tokens = CovenantTokenCollection::from_iter(self.tokens.clone());
         let mut cx = CovenantContext::new(tokens, input, block_height);
-        let root = cx.require_next_filter()?;
         let mut output_set = OutputSet::new(outputs);
-        root.filter(&mut cx, &mut output_set)?;
+        Self::filter_outputs(&mut cx, &mut output_set)
+    }
+
+    /// Executes the covenant like [Covenant::execute], given the height of the block the output spent by the input was
+    /// mined in, which relative height filters are checked against.
+    pub fn execute_with_input_mined_height(
+        &self,
+        block_height: u64,
+        input_mined_height: u64,
+        input: &TransactionInput,
+        outputs: &[TransactionOutput],
+    ) -> Result<usize, CovenantError> {
+        if self.tokens.is_empty() {
+            return Ok(outputs.len());
+        }
+
+        let tokens = CovenantTokenCollection::from_iter(self.tokens.clone());
+        let mut cx = CovenantContext::new(tokens, input, block_height).with_input_mined_height(input_mined_height);
+        let mut output_set = OutputSet::new(outputs);
+        Self::filter_outputs(&mut cx, &mut output_set)
+    }
+
+    /// Applies the root filter of the covenant in `cx` to `output_set`, returning the number of matching outputs.
+    pub(super) fn filter_outputs(
+        cx: &mut CovenantContext<'_>,
+        output_set: &mut OutputSet<'_>,
+    ) -> Result<usize, CovenantError> {
+        let root = cx.require_next_filter()?;
+        root.filter(cx, output_set)?;
         if cx.has_more_tokens() {
             return Err(CovenantError::RemainingTokens);
         }
@@ -158,7 +188,6 @@ impl Covenant {
         Ok(self.tokens.push(token)?)
     }
 
-    #[cfg(test)]
     /// Outputs a slice of the instance existing `CovenantToken`'s.
     pub(super) fn tokens(&self) -> &[CovenantToken] {
         &self.tokens
@@ -173,6 +202,25 @@ impl Covenant {
     pub fn is_empty(&self) -> bool {
         self.tokens.is_empty()
     }
+
+    /// The filters of the covenant that are activated by a consensus deployment, as pairs of the filter name and the
+    /// deployment name. Covenants passed as arguments are not included, since they are not executed.
+    pub fn required_deployments(&self) -> Vec<(&'static str, &'static str)> {
+        self.filters()
+            .filter_map(|filter| filter.deployment().map(|deployment| (filter_name(filter), deployment)))
+            .collect()
+    }
+
+    /// True if the covenant contains a relative height filter, which depends on the height the spent output was mined
+    /// at
+    pub fn has_relative_height_filter(&self) -> bool {
+        self.filters()
+            .any(|filter| matches!(filter, CovenantFilter::RelativeHeight(_)))
+    }
+
+    fn filters(&self) -> impl Iterator<Item = &CovenantFilter> {
+        self.tokens.iter().filter_map(CovenantToken::as_filter)
+    }
 }
 
 impl FromIterator<CovenantToken> for Covenant {
@@ -189,10 +237,12 @@ mod test {
     use borsh::{BorshDeserialize, BorshSerialize};
 
     use crate::{
+        consensus::COVENANT_FILTERS_DEPLOYMENT,
         covenant,
         covenants::{
             test::{create_input, create_outputs},
             Covenant,
+            CovenantError,
         },
         transactions::{key_manager::create_memory_db_key_manager, test_helpers::UtxoTestParams},
     };
@@ -225,6 +275,41 @@ mod test {
         assert_eq!(num_matching_outputs, 3);
     }
 
+    #[tokio::test]
+    async fn it_checks_relative_height_filters_against_the_input_mined_height() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let outputs = create_outputs(2, UtxoTestParams::default(), &key_manager).await;
+        let input = create_input(&key_manager).await;
+        let covenant = covenant!(relative_height(@uint(10))).unwrap();
+        assert!(covenant.has_relative_height_filter());
+
+        assert_eq!(covenant.execute(105, &input, &outputs).unwrap(), 2);
+        assert!(matches!(
+            covenant.execute_with_input_mined_height(105, 100, &input, &outputs),
+            Err(CovenantError::NoMatchingOutputs)
+        ));
+        assert_eq!(
+            covenant
+                .execute_with_input_mined_height(110, 100, &input, &outputs)
+                .unwrap(),
+            2
+        );
+    }
+
+    #[test]
+    fn it_lists_the_deployments_its_filters_require() {
+        let covenant = covenant!(or(absolute_height(@uint(42)), min_value_promise(@uint(1000)))).unwrap();
+        assert_eq!(covenant.required_deployments(), vec![(
+            "min_value_promise",
+            COVENANT_FILTERS_DEPLOYMENT
+        )]);
+        assert!(!covenant.has_relative_height_filter());
+        assert!(covenant!(absolute_height(@uint(42)))
+            .unwrap()
+            .required_deployments()
+            .is_empty());
+    }
+
     #[tokio::test]
     async fn test_borsh_de_serialization() {
         let key_manager = create_memory_db_key_manager().unwrap();
@@ -132,6 +132,7 @@ impl<R: io::Read> CovenantReadExt for R {
 #[cfg(test)]
 mod test {
     use tari_common_types::types::FixedHash;
+    use tari_script::script;
     use tari_test_utils::unpack_enum;
     use tari_utilities::hex::{to_hex, Hex};
 
@@ -140,9 +141,17 @@ mod test {
         covenant,
         covenants::{
             arguments::CovenantArg,
-            byte_codes::ARG_OUTPUT_FIELD,
+            byte_codes::{
+                ARG_OUTPUT_FIELD,
+                ARG_UINT,
+                FILTER_AND,
+                FILTER_MIN_VALUE_PROMISE,
+                FILTER_RELATIVE_HEIGHT,
+                FILTER_SCRIPT_MATCHES,
+            },
             fields::OutputField,
             filters::CovenantFilter,
+            Covenant,
         },
     };
 
@@ -206,6 +215,21 @@ mod test {
         assert!(decoder.next().is_none());
     }
 
+    #[test]
+    fn it_decodes_the_value_and_script_filters() {
+        let template = script!(CheckHeightVerify(0) PushPubKey(Box::default())).unwrap();
+        let covenant = covenant!(and(
+            min_value_promise(@uint(1000)),
+            and(script_matches(@script(template)), relative_height(@uint(10)))
+        ))
+        .unwrap();
+        let bytes = covenant.to_bytes();
+        assert_eq!(bytes[..4], [FILTER_AND, FILTER_MIN_VALUE_PROMISE, ARG_UINT, 0xe8]);
+        assert!(bytes.contains(&FILTER_SCRIPT_MATCHES));
+        assert!(bytes.ends_with(&[FILTER_RELATIVE_HEIGHT, ARG_UINT, 10, 0, 0, 0, 0, 0, 0, 0]));
+        assert_eq!(Covenant::from_bytes(&mut bytes.as_slice()).unwrap(), covenant);
+    }
+
     mod covenant_read_ext {
         use super::*;
 
@@ -0,0 +1,283 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::iter::FromIterator;
+
+use crate::{
+    covenants::{
+        context::CovenantContext,
+        error::CovenantError,
+        output_set::OutputSet,
+        token::CovenantTokenCollection,
+        Covenant,
+    },
+    transactions::transaction_components::{TransactionInput, TransactionOutput},
+};
+
+/// A filter applied while evaluating a covenant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CovenantFilterStep {
+    /// The number of filters this filter is nested in, 0 for the root filter
+    pub depth: usize,
+    /// The name of the filter, e.g. `field_eq`
+    pub filter: &'static str,
+    /// The filter with its arguments and sub-filters in the covenant language
+    pub source: String,
+    /// The indexes of the outputs the filter was applied to
+    pub applied_to: Vec<usize>,
+    /// The indexes of the outputs that matched the filter, or `None` if the filter failed
+    pub matched: Option<Vec<usize>>,
+}
+
+impl CovenantFilterStep {
+    /// True if the filter was applied to some outputs and matched none of them
+    pub fn emptied_output_set(&self) -> bool {
+        !self.applied_to.is_empty() && self.matched.as_ref().map_or(false, |matched| matched.is_empty())
+    }
+}
+
+/// The result of evaluating a covenant against a candidate transaction, see [Covenant::dry_run]
+#[derive(Debug)]
+pub struct CovenantDryRun {
+    /// The filters of the covenant in the order they were applied. The sub-filters of a filter follow it.
+    pub steps: Vec<CovenantFilterStep>,
+    /// The indexes of the outputs that satisfy the covenant
+    pub matching_outputs: Vec<usize>,
+    /// The result of [Covenant::execute]
+    pub result: Result<usize, CovenantError>,
+}
+
+impl CovenantDryRun {
+    pub fn is_satisfied(&self) -> bool {
+        self.result.is_ok()
+    }
+
+    /// The innermost filters that left no outputs when the covenant failed with `NoMatchingOutputs`. For instance, if
+    /// `and(a, b)` matches nothing because `b` matched none of the outputs matched by `a`, this is `b`.
+    pub fn emptied_by(&self) -> Vec<&CovenantFilterStep> {
+        match self.result {
+            Err(CovenantError::NoMatchingOutputs) if !self.steps.is_empty() => {
+                let mut steps = Vec::new();
+                self.collect_emptied_by(0, &mut steps);
+                steps
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    fn collect_emptied_by<'a>(&'a self, index: usize, steps: &mut Vec<&'a CovenantFilterStep>) {
+        let step = &self.steps[index];
+        if !step.emptied_output_set() {
+            return;
+        }
+        let emptied_sub_filters = self
+            .sub_filters(index)
+            .filter(|i| self.steps[*i].emptied_output_set())
+            .collect::<Vec<_>>();
+        if emptied_sub_filters.is_empty() {
+            steps.push(step);
+        }
+        for sub_filter in emptied_sub_filters {
+            self.collect_emptied_by(sub_filter, steps);
+        }
+    }
+
+    /// The indexes of the steps of the direct sub-filters of the filter at `index`
+    fn sub_filters(&self, index: usize) -> impl Iterator<Item = usize> + '_ {
+        let depth = self.steps[index].depth;
+        self.steps
+            .iter()
+            .enumerate()
+            .skip(index + 1)
+            .take_while(move |(_, step)| step.depth > depth)
+            .filter(move |(_, step)| step.depth == depth + 1)
+            .map(|(i, _)| i)
+    }
+
+    /// Describes why the covenant is not satisfied, or `None` if it is
+    pub fn failure_reason(&self) -> Option<String> {
+        match &self.result {
+            Ok(_) => None,
+            Err(CovenantError::NoMatchingOutputs) => {
+                let emptied_by = self.emptied_by();
+                if emptied_by.is_empty() {
+                    return Some("The transaction has no outputs".to_string());
+                }
+                let reasons = emptied_by
+                    .iter()
+                    .map(|step| format!("`{}` matched none of outputs {:?}", step.source, step.applied_to))
+                    .collect::<Vec<_>>();
+                Some(format!("No outputs match the covenant: {}", reasons.join(", ")))
+            },
+            Err(err) => Some(err.to_string()),
+        }
+    }
+}
+
+impl Covenant {
+    /// Executes the covenant like [Covenant::execute], recording the outputs each filter is applied to and matches.
+    /// This allows a transaction that satisfies the covenant to be built, or the reason it does not to be found.
+    /// Relative height filters are only checked if the height the output spent by the input was mined at is given.
+    pub fn dry_run(
+        &self,
+        block_height: u64,
+        input_mined_height: Option<u64>,
+        input: &TransactionInput,
+        outputs: &[TransactionOutput],
+    ) -> CovenantDryRun {
+        if self.is_empty() {
+            return CovenantDryRun {
+                steps: Vec::new(),
+                matching_outputs: (0..outputs.len()).collect(),
+                result: Ok(outputs.len()),
+            };
+        }
+
+        let tokens = CovenantTokenCollection::from_iter(self.tokens().iter().cloned());
+        let mut cx = CovenantContext::new(tokens, input, block_height).with_trace();
+        if let Some(input_mined_height) = input_mined_height {
+            cx = cx.with_input_mined_height(input_mined_height);
+        }
+        let mut output_set = OutputSet::new(outputs);
+        let result = Self::filter_outputs(&mut cx, &mut output_set);
+        CovenantDryRun {
+            steps: cx.take_trace().map(FilterTrace::into_steps).unwrap_or_default(),
+            matching_outputs: match result {
+                Ok(_) => output_set.get_selected_indexes(),
+                Err(_) => Vec::new(),
+            },
+            result,
+        }
+    }
+}
+
+/// The filters applied so far by a covenant context that is tracing
+#[derive(Debug, Default)]
+pub(super) struct FilterTrace {
+    steps: Vec<CovenantFilterStep>,
+    /// The indexes of the steps of the filters that are being applied, innermost last
+    open: Vec<usize>,
+}
+
+impl FilterTrace {
+    pub fn begin(&mut self, filter: &'static str, source: String, output_set: &OutputSet<'_>) {
+        self.open.push(self.steps.len());
+        self.steps.push(CovenantFilterStep {
+            depth: self.open.len() - 1,
+            filter,
+            source,
+            applied_to: output_set.get_selected_indexes(),
+            matched: None,
+        });
+    }
+
+    pub fn end(&mut self, matched: Option<&OutputSet<'_>>) {
+        if let Some(index) = self.open.pop() {
+            self.steps[index].matched = matched.map(OutputSet::get_selected_indexes);
+        }
+    }
+
+    pub fn into_steps(self) -> Vec<CovenantFilterStep> {
+        self.steps
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+    use crate::{
+        covenant,
+        covenants::test::{create_input, create_outputs},
+        transactions::{key_manager::create_memory_db_key_manager, test_helpers::UtxoTestParams},
+    };
+
+    #[tokio::test]
+    async fn it_records_the_outputs_matched_by_each_filter() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let mut outputs = create_outputs(6, UtxoTestParams::default(), &key_manager).await;
+        outputs[1].features.maturity = 42;
+        outputs[3].features.maturity = 42;
+        outputs[4].features.maturity = 43;
+        let input = create_input(&key_manager).await;
+        let covenant = Covenant::from_str(
+            "or(field_eq(@field::features_maturity, @uint(42)), not(field_eq(@field::features_maturity, @uint(0))))",
+        )
+        .unwrap();
+
+        let dry_run = covenant.dry_run(0, None, &input, &outputs);
+        assert!(dry_run.is_satisfied());
+        assert_eq!(dry_run.result.as_ref().unwrap(), &3);
+        assert_eq!(dry_run.matching_outputs, vec![1, 3, 4]);
+        assert_eq!(dry_run.failure_reason(), None);
+
+        let steps = dry_run
+            .steps
+            .iter()
+            .map(|step| (step.depth, step.filter, step.matched.clone().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(steps, vec![
+            (0, "or", vec![1, 3, 4]),
+            (1, "field_eq", vec![1, 3]),
+            (1, "not", vec![1, 3, 4]),
+            (2, "field_eq", vec![0, 2, 5]),
+        ]);
+        assert_eq!(
+            dry_run.steps[1].source,
+            "field_eq(@field::features_maturity, @uint(42))"
+        );
+        assert_eq!(dry_run.steps[1].applied_to, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[tokio::test]
+    async fn it_finds_the_filter_that_left_no_outputs() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let mut outputs = create_outputs(4, UtxoTestParams::default(), &key_manager).await;
+        outputs[2].features.maturity = 42;
+        let input = create_input(&key_manager).await;
+        let covenant = Covenant::from_str(
+            "and(field_eq(@field::features_maturity, @uint(42)), field_eq(@field::features_output_type, \
+             @output_type(Burn)))",
+        )
+        .unwrap();
+
+        let dry_run = covenant.dry_run(0, None, &input, &outputs);
+        assert!(matches!(dry_run.result, Err(CovenantError::NoMatchingOutputs)));
+        assert!(dry_run.matching_outputs.is_empty());
+        let emptied_by = dry_run.emptied_by();
+        assert_eq!(emptied_by.len(), 1);
+        assert_eq!(
+            emptied_by[0].source,
+            "field_eq(@field::features_output_type, @output_type(Burn))"
+        );
+        assert_eq!(emptied_by[0].applied_to, vec![2]);
+        assert_eq!(
+            dry_run.failure_reason().unwrap(),
+            "No outputs match the covenant: `field_eq(@field::features_output_type, @output_type(Burn))` matched none \
+             of outputs [2]"
+        );
+
+        let dry_run = covenant.dry_run(0, None, &input, &[]);
+        assert_eq!(dry_run.failure_reason().unwrap(), "The transaction has no outputs");
+    }
+
+    #[tokio::test]
+    async fn it_records_failing_filters() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let outputs = create_outputs(2, UtxoTestParams::default(), &key_manager).await;
+        let input = create_input(&key_manager).await;
+        // The compiler rejects comparing a script to an integer, the macro does not
+        let covenant = covenant!(not(field_eq(@field::script, @uint(1)))).unwrap();
+
+        let dry_run = covenant.dry_run(0, None, &input, &outputs);
+        assert!(matches!(dry_run.result, Err(CovenantError::InvalidArgument { .. })));
+        assert_eq!(dry_run.steps.len(), 2);
+        assert!(dry_run.steps.iter().all(|step| step.matched.is_none()));
+        assert!(dry_run.emptied_by().is_empty());
+        assert_eq!(
+            covenant.execute(0, &input, &outputs).unwrap_err().to_string(),
+            dry_run.failure_reason().unwrap()
+        );
+    }
+}
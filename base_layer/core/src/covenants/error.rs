@@ -41,3 +41,54 @@ pub enum CovenantError {
     #[error("Max sized vector error: {0}")]
     MaxSizeVecError(#[from] MaxSizeVecError),
 }
+
+/// An error encountered while compiling a covenant from its textual representation. The line and column (both starting
+/// at 1) locate the start of the offending token.
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+#[error("{kind} at line {line}, column {column}")]
+pub struct CovenantParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: CovenantParseErrorKind,
+}
+
+#[derive(Debug, Clone, thiserror::Error, PartialEq, Eq)]
+pub enum CovenantParseErrorKind {
+    #[error("Unknown filter `{0}`")]
+    UnknownFilter(String),
+    #[error("Unknown argument type `@{0}`")]
+    UnknownArgument(String),
+    #[error("Unknown output field `{0}`")]
+    UnknownField(String),
+    #[error("Unknown output type `{0}`")]
+    UnknownOutputType(String),
+    #[error("Expected {expected}, found {found}")]
+    UnexpectedToken { expected: &'static str, found: String },
+    #[error("`{filter}` takes {expected} argument(s) but {actual} were given")]
+    WrongArgumentCount {
+        filter: &'static str,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Argument {index} of `{filter}` must be {expected}, found {found}")]
+    ArgumentTypeMismatch {
+        filter: &'static str,
+        index: usize,
+        expected: &'static str,
+        found: &'static str,
+    },
+    #[error("`{0}` cannot be compared by `field_eq`")]
+    IncomparableField(&'static str),
+    #[error("Invalid integer `{0}`")]
+    InvalidInteger(String),
+    #[error("Invalid {expected} `{value}`")]
+    InvalidValue { expected: &'static str, value: String },
+    #[error("Invalid script: {0}")]
+    InvalidScript(String),
+    #[error("The covenant exceeds the maximum of {0} tokens")]
+    TooManyTokens(usize),
+    #[error("The covenant exceeds the maximum of {0} bytes")]
+    TooManyBytes(usize),
+    #[error("The covenant exceeds the maximum nesting depth of {0}")]
+    TooDeeplyNested(usize),
+}
@@ -29,18 +29,24 @@ use super::{
     fields_hashed_eq::FieldsHashedEqFilter,
     fields_preserved::FieldsPreservedFilter,
     identity::IdentityFilter,
+    min_value_promise::MinValuePromiseFilter,
     not::NotFilter,
     or::OrFilter,
     output_hash_eq::OutputHashEqFilter,
+    relative_height::RelativeHeightFilter,
+    script_matches::ScriptMatchesFilter,
     xor::XorFilter,
 };
-use crate::covenants::{
-    byte_codes,
-    context::CovenantContext,
-    decoder::CovenantDecodeError,
-    encoder::CovenentWriteExt,
-    error::CovenantError,
-    output_set::OutputSet,
+use crate::{
+    consensus::COVENANT_FILTERS_DEPLOYMENT,
+    covenants::{
+        byte_codes,
+        context::CovenantContext,
+        decoder::CovenantDecodeError,
+        encoder::CovenentWriteExt,
+        error::CovenantError,
+        output_set::OutputSet,
+    },
 };
 
 /// The filter trait is implemented by all covenant filters.
@@ -61,6 +67,9 @@ pub enum CovenantFilter {
     FieldEq(FieldEqFilter),
     FieldsHashedEq(FieldsHashedEqFilter),
     AbsoluteHeight(AbsoluteHeightFilter),
+    MinValuePromise(MinValuePromiseFilter),
+    ScriptMatches(ScriptMatchesFilter),
+    RelativeHeight(RelativeHeightFilter),
 }
 
 impl CovenantFilter {
@@ -91,6 +100,9 @@ impl CovenantFilter {
             FieldEq(_) => FILTER_FIELD_EQ,
             FieldsHashedEq(_) => FILTER_FIELDS_HASHED_EQ,
             AbsoluteHeight(_) => FILTER_ABSOLUTE_HEIGHT,
+            MinValuePromise(_) => FILTER_MIN_VALUE_PROMISE,
+            ScriptMatches(_) => FILTER_SCRIPT_MATCHES,
+            RelativeHeight(_) => FILTER_RELATIVE_HEIGHT,
         }
     }
 
@@ -108,6 +120,9 @@ impl CovenantFilter {
             FILTER_FIELD_EQ => Ok(Self::field_eq()),
             FILTER_FIELDS_HASHED_EQ => Ok(Self::fields_hashed_eq()),
             FILTER_ABSOLUTE_HEIGHT => Ok(Self::absolute_height()),
+            FILTER_MIN_VALUE_PROMISE => Ok(Self::min_value_promise()),
+            FILTER_SCRIPT_MATCHES => Ok(Self::script_matches()),
+            FILTER_RELATIVE_HEIGHT => Ok(Self::relative_height()),
             _ => Err(CovenantDecodeError::UnknownFilterByteCode { code }),
         }
     }
@@ -161,6 +176,32 @@ impl CovenantFilter {
     pub fn absolute_height() -> Self {
         CovenantFilter::AbsoluteHeight(AbsoluteHeightFilter)
     }
+
+    /// Return the "minimum value promise" covenant filter.
+    pub fn min_value_promise() -> Self {
+        CovenantFilter::MinValuePromise(MinValuePromiseFilter)
+    }
+
+    /// Return the "script matches" covenant filter.
+    pub fn script_matches() -> Self {
+        CovenantFilter::ScriptMatches(ScriptMatchesFilter)
+    }
+
+    /// Return the "relative height" covenant filter.
+    pub fn relative_height() -> Self {
+        CovenantFilter::RelativeHeight(RelativeHeightFilter)
+    }
+
+    /// The consensus deployment that activates this filter, if it was added after launch. Outputs may not carry a
+    /// covenant with the filter before the deployment is active.
+    pub fn deployment(&self) -> Option<&'static str> {
+        match self {
+            CovenantFilter::MinValuePromise(_) |
+            CovenantFilter::ScriptMatches(_) |
+            CovenantFilter::RelativeHeight(_) => Some(COVENANT_FILTERS_DEPLOYMENT),
+            _ => None,
+        }
+    }
 }
 
 impl Filter for CovenantFilter {
@@ -168,7 +209,8 @@ impl Filter for CovenantFilter {
     fn filter(&self, context: &mut CovenantContext<'_>, output_set: &mut OutputSet<'_>) -> Result<(), CovenantError> {
         #[allow(clippy::enum_glob_use)]
         use CovenantFilter::*;
-        match self {
+        context.begin_filter(self, output_set);
+        let result = match self {
             Identity(identity) => identity.filter(context, output_set),
             And(and) => and.filter(context, output_set),
             Or(or) => or.filter(context, output_set),
@@ -179,6 +221,11 @@ impl Filter for CovenantFilter {
             FieldEq(fields_eq) => fields_eq.filter(context, output_set),
             FieldsHashedEq(fields_hashed_eq) => fields_hashed_eq.filter(context, output_set),
             AbsoluteHeight(abs_height) => abs_height.filter(context, output_set),
-        }
+            MinValuePromise(min_value_promise) => min_value_promise.filter(context, output_set),
+            ScriptMatches(script_matches) => script_matches.filter(context, output_set),
+            RelativeHeight(relative_height) => relative_height.filter(context, output_set),
+        };
+        context.end_filter(result.as_ref().ok().map(|_| &*output_set));
+        result
     }
 }
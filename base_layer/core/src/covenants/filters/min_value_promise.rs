@@ -0,0 +1,52 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use crate::covenants::{context::CovenantContext, error::CovenantError, filters::Filter, output_set::OutputSet};
+
+/// Holding struct for the "minimum value promise" filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MinValuePromiseFilter;
+
+impl Filter for MinValuePromiseFilter {
+    // Filters out all outputs that do not promise a minimum value of at least the value given in the covenant context.
+    // The range proof of an output proves that its value is at least its minimum value promise.
+    fn filter(&self, context: &mut CovenantContext<'_>, output_set: &mut OutputSet<'_>) -> Result<(), CovenantError> {
+        let min_value = context.next_arg()?.require_uint()?;
+        output_set.retain(|output| Ok(output.minimum_value_promise.as_u64() >= min_value))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        covenant,
+        covenants::{filters::test::setup_filter_test, test::create_input},
+        transactions::{key_manager::create_memory_db_key_manager, tari_amount::MicroMinotari},
+    };
+
+    #[tokio::test]
+    async fn it_filters_outputs_promising_less_than_the_value() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let covenant = covenant!(min_value_promise(@uint(1000))).unwrap();
+        let input = create_input(&key_manager).await;
+        let (mut context, outputs) = setup_filter_test(
+            &covenant,
+            &input,
+            0,
+            |outputs| {
+                outputs[3].minimum_value_promise = MicroMinotari(999);
+                outputs[5].minimum_value_promise = MicroMinotari(1000);
+                outputs[7].minimum_value_promise = MicroMinotari(5000);
+            },
+            &key_manager,
+        )
+        .await;
+        let mut output_set = OutputSet::new(&outputs);
+
+        MinValuePromiseFilter.filter(&mut context, &mut output_set).unwrap();
+
+        assert_eq!(output_set.get_selected_indexes(), vec![5, 7]);
+    }
+}
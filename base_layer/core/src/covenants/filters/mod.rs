@@ -26,9 +26,12 @@ mod field_eq;
 mod fields_hashed_eq;
 mod fields_preserved;
 mod identity;
+mod min_value_promise;
 mod not;
 mod or;
 mod output_hash_eq;
+mod relative_height;
+mod script_matches;
 mod xor;
 
 pub use absolute_height::AbsoluteHeightFilter;
@@ -37,9 +40,12 @@ pub use field_eq::FieldEqFilter;
 pub use fields_hashed_eq::FieldsHashedEqFilter;
 pub use fields_preserved::FieldsPreservedFilter;
 pub use identity::IdentityFilter;
+pub use min_value_promise::MinValuePromiseFilter;
 pub use not::NotFilter;
 pub use or::OrFilter;
 pub use output_hash_eq::OutputHashEqFilter;
+pub use relative_height::RelativeHeightFilter;
+pub use script_matches::ScriptMatchesFilter;
 pub use xor::XorFilter;
 
 mod filter;
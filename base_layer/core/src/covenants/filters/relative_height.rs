@@ -0,0 +1,61 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use crate::covenants::{context::CovenantContext, error::CovenantError, filters::Filter, output_set::OutputSet};
+
+/// Holding struct for the "relative height" filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelativeHeightFilter;
+
+impl Filter for RelativeHeightFilter {
+    // The relative height filter removes all outputs in the mutable output set if fewer than the number of blocks given
+    // in the covenant context have been mined since the output spent by the input. Like the `CheckRelativeHeightVerify`
+    // opcode, nothing is removed if the height that output was mined at is not known to the context; the chain linked
+    // validator executes these covenants again with that height.
+    fn filter(&self, context: &mut CovenantContext<'_>, output_set: &mut OutputSet<'_>) -> Result<(), CovenantError> {
+        let min_blocks = context.next_arg()?.require_uint()?;
+        if let Some(mined_height) = context.input_mined_height() {
+            if context.block_height().saturating_sub(mined_height) < min_blocks {
+                output_set.clear();
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        covenant,
+        covenants::{filters::test::setup_filter_test, test::create_input},
+        transactions::key_manager::create_memory_db_key_manager,
+    };
+
+    #[tokio::test]
+    async fn it_filters_all_out_until_enough_blocks_are_mined() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let covenant = covenant!(relative_height(@uint(10))).unwrap();
+        let input = create_input(&key_manager).await;
+        for (block_height, expected_len) in [(109, 0), (110, 10), (200, 10)] {
+            let (context, outputs) = setup_filter_test(&covenant, &input, block_height, |_| {}, &key_manager).await;
+            let mut context = context.with_input_mined_height(100);
+            let mut output_set = OutputSet::new(&outputs);
+            RelativeHeightFilter.filter(&mut context, &mut output_set).unwrap();
+            assert_eq!(output_set.len(), expected_len);
+        }
+    }
+
+    #[tokio::test]
+    async fn it_filters_nothing_if_the_mined_height_is_unknown() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let covenant = covenant!(relative_height(@uint(10))).unwrap();
+        let input = create_input(&key_manager).await;
+        let (mut context, outputs) = setup_filter_test(&covenant, &input, 0, |_| {}, &key_manager).await;
+
+        let mut output_set = OutputSet::new(&outputs);
+        RelativeHeightFilter.filter(&mut context, &mut output_set).unwrap();
+
+        assert_eq!(output_set.len(), 10);
+    }
+}
@@ -0,0 +1,57 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use crate::covenants::{context::CovenantContext, error::CovenantError, filters::Filter, output_set::OutputSet};
+
+/// Holding struct for the "script matches" filter
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptMatchesFilter;
+
+impl Filter for ScriptMatchesFilter {
+    // Filters out all outputs whose script does not match the template script given in the covenant context. A script
+    // matches the template if it has the same opcodes, in the same order, with any values, see
+    // `TariScript::pattern_match`.
+    fn filter(&self, context: &mut CovenantContext<'_>, output_set: &mut OutputSet<'_>) -> Result<(), CovenantError> {
+        let template = context.next_arg()?.require_tariscript()?;
+        output_set.retain(|output| Ok(template.pattern_match(&output.script)))?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use tari_script::script;
+
+    use super::*;
+    use crate::{
+        covenant,
+        covenants::{filters::test::setup_filter_test, test::create_input},
+        transactions::key_manager::create_memory_db_key_manager,
+    };
+
+    #[tokio::test]
+    async fn it_filters_outputs_with_scripts_that_do_not_match_the_template() {
+        let key_manager = create_memory_db_key_manager().unwrap();
+        let template = script!(CheckHeightVerify(0) PushPubKey(Box::default())).unwrap();
+        let covenant = covenant!(script_matches(@script(template))).unwrap();
+        let input = create_input(&key_manager).await;
+        let (mut context, outputs) = setup_filter_test(
+            &covenant,
+            &input,
+            0,
+            |outputs| {
+                outputs[2].script = script!(CheckHeightVerify(100) PushPubKey(Box::default())).unwrap();
+                outputs[4].script = script!(CheckHeightVerify(0) PushPubKey(Box::default())).unwrap();
+                outputs[6].script = script!(CheckHeight(100) PushPubKey(Box::default())).unwrap();
+                outputs[8].script = script!(CheckHeightVerify(100) PushPubKey(Box::default()) Drop).unwrap();
+            },
+            &key_manager,
+        )
+        .await;
+        let mut output_set = OutputSet::new(&outputs);
+
+        ScriptMatchesFilter.filter(&mut context, &mut output_set).unwrap();
+
+        assert_eq!(output_set.get_selected_indexes(), vec![2, 4]);
+    }
+}
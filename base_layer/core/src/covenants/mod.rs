@@ -25,12 +25,18 @@
 //! Allows rules to be specified that restrict _future_ spending of subsequent transactions.
 //!
 //! <https://rfc.tari.com/RFC-0250_Covenants.html>
+//!
+//! Covenants can be written in the syntax of the `covenant!` macro and compiled with `Covenant::from_str`, and
+//! formatted back into that syntax with `Covenant::decompile`. `Covenant::dry_run` evaluates a covenant against a
+//! candidate transaction and reports the outputs matched by each filter.
 
 mod arguments;
 mod byte_codes;
+mod compiler;
 mod context;
 mod covenant;
 mod decoder;
+mod dry_run;
 mod encoder;
 mod error;
 mod fields;
@@ -40,7 +46,8 @@ mod serde;
 mod token;
 
 pub use covenant::Covenant;
-pub use error::CovenantError;
+pub use dry_run::{CovenantDryRun, CovenantFilterStep};
+pub use error::{CovenantError, CovenantParseError, CovenantParseErrorKind};
 // Used in macro
 #[allow(unused_imports)]
 pub(crate) use fields::OutputField;
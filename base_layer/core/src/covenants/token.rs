@@ -38,9 +38,12 @@ use crate::{
             FieldsHashedEqFilter,
             FieldsPreservedFilter,
             IdentityFilter,
+            MinValuePromiseFilter,
             NotFilter,
             OrFilter,
             OutputHashEqFilter,
+            RelativeHeightFilter,
+            ScriptMatchesFilter,
             XorFilter,
         },
         Covenant,
@@ -162,6 +165,24 @@ impl CovenantToken {
         CovenantFilter::AbsoluteHeight(AbsoluteHeightFilter).into()
     }
 
+    #[allow(dead_code)]
+    /// Helper for creating a new instance wrapping a `MinValuePromiseFilter`.
+    pub fn min_value_promise() -> Self {
+        CovenantFilter::MinValuePromise(MinValuePromiseFilter).into()
+    }
+
+    #[allow(dead_code)]
+    /// Helper for creating a new instance wrapping a `ScriptMatchesFilter`.
+    pub fn script_matches() -> Self {
+        CovenantFilter::ScriptMatches(ScriptMatchesFilter).into()
+    }
+
+    #[allow(dead_code)]
+    /// Helper for creating a new instance wrapping a `RelativeHeightFilter`.
+    pub fn relative_height() -> Self {
+        CovenantFilter::RelativeHeight(RelativeHeightFilter).into()
+    }
+
     #[allow(dead_code)]
     /// Helper for creating a new instance wrapping an `HashFilter`.
     pub fn hash(hash: FixedHash) -> Self {
@@ -251,6 +272,11 @@ impl CovenantTokenCollection {
     pub fn next(&mut self) -> Option<CovenantToken> {
         self.tokens.pop_front()
     }
+
+    /// Iterates over the remaining tokens without consuming them.
+    pub(super) fn iter(&self) -> impl Iterator<Item = &CovenantToken> {
+        self.tokens.iter()
+    }
 }
 
 impl FromIterator<CovenantToken> for CovenantTokenCollection {
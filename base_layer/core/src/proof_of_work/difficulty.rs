@@ -129,16 +129,26 @@ impl fmt::Display for Difficulty {
 /// General difficulty adjustment algorithm trait. The key method is `get_difficulty`, which returns the target
 /// difficulty given a set of historical achieved difficulties; supplied through the `add` method.
 pub trait DifficultyAdjustment {
-    /// Adds the latest block timestamp (in seconds) and total accumulated difficulty. If the new data point violates
+    /// Adds the latest block timestamp (in seconds) and target difficulty. If the new data point violates
     /// some difficulty criteria, then `add` returns an error with the type of failure indicated
-    fn add(
+    fn add(&mut self, timestamp: EpochTime, target_difficulty: Difficulty) -> Result<(), DifficultyAdjustmentError>;
+
+    /// Adds an older block timestamp (in seconds) and target difficulty, i.e. one that precedes all data points
+    /// currently held. This is used when the window is populated by walking the chain backwards from the tip.
+    fn add_front(
         &mut self,
         timestamp: EpochTime,
-        accumulated_difficulty: Difficulty,
+        target_difficulty: Difficulty,
     ) -> Result<(), DifficultyAdjustmentError>;
 
     /// Return the calculated target difficulty for the next block.
     fn get_difficulty(&self) -> Option<Difficulty>;
+
+    /// Returns true if the algorithm holds enough data points to calculate a difficulty over its full window
+    fn is_full(&self) -> bool;
+
+    /// Returns the number of data points currently held
+    fn num_samples(&self) -> usize;
 }
 
 #[cfg(test)]
@@ -187,9 +187,26 @@ impl DifficultyAdjustment for LinearWeightedMovingAverage {
         Ok(())
     }
 
+    fn add_front(
+        &mut self,
+        timestamp: EpochTime,
+        target_difficulty: Difficulty,
+    ) -> Result<(), DifficultyAdjustmentError> {
+        LinearWeightedMovingAverage::add_front(self, timestamp, target_difficulty);
+        Ok(())
+    }
+
     fn get_difficulty(&self) -> Option<Difficulty> {
         self.calculate()
     }
+
+    fn is_full(&self) -> bool {
+        LinearWeightedMovingAverage::is_full(self)
+    }
+
+    fn num_samples(&self) -> usize {
+        LinearWeightedMovingAverage::num_samples(self)
+    }
 }
 
 #[cfg(test)]
@@ -704,6 +704,8 @@ mod test {
             coinbase_tx_extra: extra,
             aux_chain_merkle_proof,
         };
+        assert_eq!(monero_data.aux_chain_count(), 1);
+        assert!(!monero_data.has_monero_transactions());
         let mut serialized = Vec::new();
         monero_data.serialize(&mut serialized).unwrap();
         let pow = ProofOfWork {
@@ -715,6 +717,35 @@ mod test {
         verify_header(&block_header, &hash, &rules).unwrap();
     }
 
+    #[test]
+    fn test_merge_mined_with_monero_transactions_and_aux_chains() {
+        let blocktemplate_blob = "0c0c8cd6a0fa057fe21d764e7abf004e975396a2160773b93712bf6118c3b4959ddd8ee0f76aad0000000002e1ea2701ffa5ea2701d5a299e2abb002028eb3066ced1b2cc82ea046f3716a48e9ae37144057d5fb48a97f941225a1957b2b0106225b7ec0a6544d8da39abe68d8bd82619b4a7c5bdae89c3783b256a8fa47820208f63aa86d2e857f070000".to_string();
+        let seed_hash = "9f02e032f9b15d2aded991e0f68cc3c3427270b568b782e55fbd269ead0bad97".to_string();
+        let bytes = hex::decode(blocktemplate_blob).unwrap();
+        let mut block = deserialize::<monero::Block>(&bytes[..]).unwrap();
+        let hash = Hash::null();
+        insert_aux_chain_mr_and_info_into_block(&mut block, hash, 3, 0).unwrap();
+        // A Monero node template includes transactions besides the coinbase
+        let hashes = vec![block.miner_tx.hash(), Hash::new([1u8; 32]), Hash::new([2u8; 32])];
+        let root = tree_hash(&hashes).unwrap();
+        let coinbase_merkle_proof = create_merkle_proof(&hashes, &hashes[0]).unwrap();
+        let aux_hashes = vec![hash];
+        let aux_chain_merkle_proof = create_merkle_proof(&aux_hashes, &aux_hashes[0]).unwrap();
+
+        let monero_data = MoneroPowData {
+            header: block.header,
+            randomx_key: FixedByteArray::from_canonical_bytes(&from_hex(&seed_hash).unwrap()).unwrap(),
+            transaction_count: 3,
+            merkle_root: root,
+            coinbase_merkle_proof,
+            coinbase_tx_hasher: Keccak::v256(),
+            coinbase_tx_extra: block.miner_tx.prefix.extra.clone(),
+            aux_chain_merkle_proof,
+        };
+        assert_eq!(monero_data.aux_chain_count(), 3);
+        assert!(monero_data.has_monero_transactions());
+    }
+
     #[test]
     fn test_append_mm_tag_no_tag() {
         let rules = ConsensusManager::builder(Network::LocalNet).build().unwrap();
@@ -29,7 +29,7 @@ use std::{
 
 use borsh::{BorshDeserialize, BorshSerialize};
 use monero::{
-    blockdata::transaction::RawExtraField,
+    blockdata::transaction::{ExtraField, RawExtraField, SubField},
     consensus::{Decodable, Encodable},
     cryptonote::hash::Hashable,
     util::ringct::{RctSigBase, RctType},
@@ -40,7 +40,12 @@ use tari_utilities::{
 };
 use tiny_keccak::{Hasher, Keccak};
 
-use super::{error::MergeMineError, fixed_array::FixedByteArray, merkle_tree::MerkleProof};
+use super::{
+    error::MergeMineError,
+    fixed_array::FixedByteArray,
+    merkle_tree::MerkleProof,
+    merkle_tree_parameters::MerkleTreeParameters,
+};
 use crate::{
     blocks::BlockHeader,
     consensus::ConsensusManager,
@@ -189,6 +194,26 @@ impl MoneroPowData {
     pub fn randomx_key(&self) -> &[u8] {
         self.randomx_key.as_slice()
     }
+
+    /// Returns the number of chains merge mined in the Monero block, including Tari, as given by the merge mining tag
+    /// in the coinbase extra field
+    pub fn aux_chain_count(&self) -> u8 {
+        let extra_field = ExtraField::try_parse(&self.coinbase_tx_extra).unwrap_or_else(|ex_field| ex_field);
+        extra_field
+            .0
+            .into_iter()
+            .find_map(|item| match item {
+                SubField::MergeMining(depth, _) => Some(MerkleTreeParameters::from_varint(depth).number_of_chains()),
+                _ => None,
+            })
+            .unwrap_or(1)
+    }
+
+    /// Returns true if the coinbase merkle proof shows that the Monero block contains transactions besides the
+    /// coinbase
+    pub fn has_monero_transactions(&self) -> bool {
+        !self.coinbase_merkle_proof.branch().is_empty()
+    }
 }
 
 impl Display for MoneroPowData {
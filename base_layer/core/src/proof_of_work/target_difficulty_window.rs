@@ -24,56 +24,76 @@ use std::cmp;
 
 use tari_utilities::epoch_time::EpochTime;
 
-use crate::proof_of_work::{difficulty::DifficultyAdjustment, lwma_diff::LinearWeightedMovingAverage, Difficulty};
+use crate::proof_of_work::{
+    difficulty::DifficultyAdjustment,
+    lwma_diff::LinearWeightedMovingAverage,
+    Difficulty,
+    DifficultyAdjustmentError,
+};
 
-/// A window of target difficulties
+/// A window of target difficulties, backed by a difficulty adjustment algorithm. Consensus uses the
+/// [LinearWeightedMovingAverage](crate::proof_of_work::lwma_diff::LinearWeightedMovingAverage) algorithm, other
+/// implementations of [DifficultyAdjustment] can be plugged in for analysis using
+/// [TargetDifficultyWindow::from_algorithm].
 #[derive(Debug, Clone)]
-pub struct TargetDifficultyWindow {
-    lwma: LinearWeightedMovingAverage,
+pub struct TargetDifficultyWindow<T = LinearWeightedMovingAverage> {
+    algorithm: T,
 }
 
 impl TargetDifficultyWindow {
-    /// Initialize a new `TargetDifficultyWindow`
+    /// Initialize a new `TargetDifficultyWindow` using the LWMA difficulty adjustment algorithm
     pub(crate) fn new(block_window: usize, target_time: u64) -> Result<Self, String> {
         Ok(Self {
-            lwma: LinearWeightedMovingAverage::new(block_window, target_time)?,
+            algorithm: LinearWeightedMovingAverage::new(block_window, target_time)?,
         })
     }
+}
+
+impl<T: DifficultyAdjustment> TargetDifficultyWindow<T> {
+    /// Initialize a new `TargetDifficultyWindow` using the given difficulty adjustment algorithm
+    pub fn from_algorithm(algorithm: T) -> Self {
+        Self { algorithm }
+    }
 
     /// Appends a target difficulty. If the number of stored difficulties exceeds the block window, the stored
     /// difficulty at the front is removed keeping the size of the stored difficulties equal to the block window.
     #[inline]
-    pub fn add_back(&mut self, time: EpochTime, difficulty: Difficulty) {
-        self.lwma.add_back(time, difficulty);
+    pub fn add_back(&mut self, time: EpochTime, difficulty: Difficulty) -> Result<(), DifficultyAdjustmentError> {
+        self.algorithm.add(time, difficulty)
     }
 
     /// Prepends a target difficulty. If the number of stored difficulties exceeds the block window, the stored
     /// difficulty at the back is removed keeping the size of the stored difficulties equal to the block window.
     #[inline]
-    pub fn add_front(&mut self, time: EpochTime, difficulty: Difficulty) {
-        self.lwma.add_front(time, difficulty);
+    pub fn add_front(&mut self, time: EpochTime, difficulty: Difficulty) -> Result<(), DifficultyAdjustmentError> {
+        self.algorithm.add_front(time, difficulty)
     }
 
     /// Returns true of the TargetDifficulty has `block_window` data points, otherwise false
     #[inline]
     pub fn is_full(&self) -> bool {
-        self.lwma.is_full()
+        self.algorithm.is_full()
     }
 
     /// Returns the number of target difficulties in the window
     pub fn len(&self) -> usize {
-        self.lwma.num_samples()
+        self.algorithm.num_samples()
     }
 
     /// Returns true if the window is empty
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.lwma.num_samples() == 0
+        self.algorithm.num_samples() == 0
+    }
+
+    /// Returns a reference to the underlying difficulty adjustment algorithm
+    pub fn algorithm(&self) -> &T {
+        &self.algorithm
     }
 
     /// Calculates the target difficulty for the current set of target difficulties.
     pub fn calculate(&self, min: Difficulty, max: Difficulty) -> Difficulty {
-        let difficulty = self.lwma.get_difficulty().unwrap_or(min);
+        let difficulty = self.algorithm.get_difficulty().unwrap_or(min);
         cmp::max(min, cmp::min(max, difficulty))
     }
 }
@@ -86,23 +106,74 @@ mod test {
     fn it_calculates_the_target_difficulty() {
         let mut target_difficulties = TargetDifficultyWindow::new(5, 60).unwrap();
         let mut time = Difficulty::from_u64(60).unwrap().as_u64().into();
-        target_difficulties.add_back(time, Difficulty::from_u64(100).unwrap());
+        target_difficulties
+            .add_back(time, Difficulty::from_u64(100).unwrap())
+            .unwrap();
         time = time
             .checked_add(EpochTime::from(Difficulty::from_u64(60).unwrap().as_u64()))
             .unwrap();
-        target_difficulties.add_back(time, Difficulty::from_u64(100).unwrap());
+        target_difficulties
+            .add_back(time, Difficulty::from_u64(100).unwrap())
+            .unwrap();
         time = time
             .checked_add(EpochTime::from(Difficulty::from_u64(60).unwrap().as_u64()))
             .unwrap();
-        target_difficulties.add_back(time, Difficulty::from_u64(100).unwrap());
+        target_difficulties
+            .add_back(time, Difficulty::from_u64(100).unwrap())
+            .unwrap();
         time = time
             .checked_add(EpochTime::from(Difficulty::from_u64(60).unwrap().as_u64()))
             .unwrap();
-        target_difficulties.add_back(time, Difficulty::from_u64(100).unwrap());
+        target_difficulties
+            .add_back(time, Difficulty::from_u64(100).unwrap())
+            .unwrap();
 
         assert_eq!(
             target_difficulties.calculate(Difficulty::from_u64(1).unwrap(), Difficulty::from_u64(400).unwrap()),
             Difficulty::from_u64(100).unwrap()
         );
     }
+
+    #[derive(Debug, Clone, Default)]
+    struct FixedDifficulty {
+        samples: usize,
+    }
+
+    impl DifficultyAdjustment for FixedDifficulty {
+        fn add(&mut self, _: EpochTime, _: Difficulty) -> Result<(), DifficultyAdjustmentError> {
+            self.samples += 1;
+            Ok(())
+        }
+
+        fn add_front(&mut self, _: EpochTime, _: Difficulty) -> Result<(), DifficultyAdjustmentError> {
+            self.samples += 1;
+            Ok(())
+        }
+
+        fn get_difficulty(&self) -> Option<Difficulty> {
+            Some(Difficulty::from_u64(1_000).unwrap())
+        }
+
+        fn is_full(&self) -> bool {
+            self.samples >= 2
+        }
+
+        fn num_samples(&self) -> usize {
+            self.samples
+        }
+    }
+
+    #[test]
+    fn it_uses_the_given_algorithm() {
+        let mut target_difficulties = TargetDifficultyWindow::from_algorithm(FixedDifficulty::default());
+        assert!(target_difficulties.is_empty());
+        target_difficulties.add_back(60.into(), Difficulty::min()).unwrap();
+        target_difficulties.add_front(0.into(), Difficulty::min()).unwrap();
+        assert!(target_difficulties.is_full());
+        assert_eq!(target_difficulties.len(), 2);
+        assert_eq!(
+            target_difficulties.calculate(Difficulty::min(), Difficulty::from_u64(400).unwrap()),
+            Difficulty::from_u64(400).unwrap()
+        );
+    }
 }
@@ -1652,4 +1652,26 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
         let public_key = spend_key + &public_key;
         Ok(public_key)
     }
+
+    pub async fn import_stealth_address_script_key(
+        &self,
+        commitment_mask_key_id: &TariKeyId,
+        spend_key_id: &TariKeyId,
+    ) -> Result<TariKeyId, KeyManagerServiceError> {
+        // Stealth keys for the wallet's own spend key need not be stored, they can be derived on demand
+        if spend_key_id == &self.get_spend_key().await?.key_id {
+            return Ok(KeyId::Derived {
+                key: commitment_mask_key_id.into(),
+            });
+        }
+        let commitment_mask = self.get_private_key(commitment_mask_key_id).await?;
+        let hasher = DomainSeparatedHasher::<Blake2b<U64>, KeyManagerTransactionsHashDomain>::new_with_label(
+            HASHER_LABEL_STEALTH_KEY,
+        );
+        let hasher = hasher.chain(commitment_mask.as_bytes()).finalize();
+        let private_key = PrivateKey::from_uniform_bytes(hasher.as_ref())
+            .map_err(|_| KeyManagerServiceError::UnknownError("Invalid commitment mask private key".to_string()))?;
+        let private_key = private_key + self.get_private_key(spend_key_id).await?;
+        self.import_key(private_key).await
+    }
 }
@@ -280,6 +280,14 @@ pub trait TransactionKeyManagerInterface: KeyManagerInterface<PublicKey> {
         commitment_mask_key_id: &TariKeyId,
         spend_key: &PublicKey,
     ) -> Result<PublicKey, TransactionError>;
+
+    /// Returns the script key of a stealth address output paid to the spend key `spend_key_id`. Keys for spend keys
+    /// other than the wallet's own are imported.
+    async fn import_stealth_address_script_key(
+        &self,
+        commitment_mask_key_id: &TariKeyId,
+        spend_key_id: &TariKeyId,
+    ) -> Result<TariKeyId, KeyManagerServiceError>;
 }
 
 #[async_trait::async_trait]
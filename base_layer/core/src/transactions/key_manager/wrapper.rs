@@ -590,6 +590,18 @@ where TBackend: KeyManagerBackend<PublicKey> + 'static
             .stealth_address_script_spending_key(commitment_mask_key_id, spend_key)
             .await
     }
+
+    async fn import_stealth_address_script_key(
+        &self,
+        commitment_mask_key_id: &TariKeyId,
+        spend_key_id: &TariKeyId,
+    ) -> Result<TariKeyId, KeyManagerServiceError> {
+        self.transaction_key_manager_inner
+            .read()
+            .await
+            .import_stealth_address_script_key(commitment_mask_key_id, spend_key_id)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -25,7 +25,7 @@ use tari_common_types::{
     tari_address::TariAddress,
     types::{ComAndPubSignature, PublicKey},
 };
-use tari_script::{ExecutionStack, TariScript};
+use tari_script::{ExecutionStack, MastTree, TariScript};
 
 use crate::{
     covenants::Covenant,
@@ -115,6 +115,19 @@ impl WalletOutputBuilder {
         self
     }
 
+    /// Locks the output with the given MAST and sets the input data that spends it with the script at `leaf_index`,
+    /// given the input data of that script.
+    pub fn with_mast_spend_path(
+        mut self,
+        tree: &MastTree,
+        leaf_index: usize,
+        leaf_input_data: &ExecutionStack,
+    ) -> Result<Self, TransactionError> {
+        self.input_data = Some(tree.spend_inputs(leaf_index, leaf_input_data)?);
+        self.script = Some(tree.locking_script());
+        Ok(self)
+    }
+
     pub fn with_covenant(mut self, covenant: Covenant) -> Self {
         self.covenant = covenant;
         self
@@ -0,0 +1,279 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Adaptor signatures for scriptless-script atomic swaps.
+//!
+//! An adaptor signature (or pre-signature) is a Schnorr signature that has been "encrypted" to an adaptor point
+//! `T = t·G`. Anyone can check that it is a valid pre-signature for `T`, but only someone who knows `t` can complete
+//! it into a valid signature. Once the completed signature is published, `t` can be extracted from it by anyone
+//! holding the pre-signature.
+//!
+//! In a cross-chain swap, the party locking Tari hands the counterparty a pre-signature for a `CheckSig` in the lock
+//! script (see [AdaptorSwapScript]), encrypted to the counterparty's key share on the other chain. Claiming the Tari
+//! output publishes the completed signature, which reveals that key share. Unlike a SHA hashlock, nothing on either
+//! chain links the two transactions.
+
+use blake2::Blake2b;
+use digest::consts::{U32, U64};
+use serde::{Deserialize, Serialize};
+use tari_common_types::types::{PrivateKey, PublicKey};
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher, keys::PublicKey as PublicKeyT};
+use tari_script::{script, CheckSigSchnorrSignature, Message, Opcode, ScriptError, TariScript};
+use tari_utilities::ByteArray;
+
+use crate::transactions::transaction_protocol::TransactionProtocolError;
+
+hash_domain!(
+    AdaptorSwapHashDomain,
+    "com.tari.base_layer.core.transactions.adaptor_swap",
+    0
+);
+
+/// A Schnorr pre-signature that can be completed into a [CheckSigSchnorrSignature] with the discrete log of its
+/// adaptor point
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdaptorSignature {
+    /// `R = r·G`. The public nonce of the completed signature is `R + T`.
+    public_nonce: PublicKey,
+    /// `s' = r + e·a`, where `e` is the challenge of the completed signature
+    pre_signature: PrivateKey,
+}
+
+impl AdaptorSignature {
+    pub fn new(public_nonce: PublicKey, pre_signature: PrivateKey) -> Self {
+        Self {
+            public_nonce,
+            pre_signature,
+        }
+    }
+
+    /// Creates a pre-signature of `message` with `secret_key`, encrypted to `adaptor_point`
+    pub fn sign(
+        secret_key: &PrivateKey,
+        secret_nonce: PrivateKey,
+        adaptor_point: &PublicKey,
+        message: &[u8],
+    ) -> Result<Self, TransactionProtocolError> {
+        let public_key = PublicKey::from_secret_key(secret_key);
+        let public_nonce = PublicKey::from_secret_key(&secret_nonce);
+        let challenge = Self::challenge(&public_nonce, adaptor_point, &public_key, message);
+        let signature = CheckSigSchnorrSignature::sign_raw_uniform(secret_key, secret_nonce, challenge.as_ref())
+            .map_err(|e| TransactionProtocolError::SigningError(e.to_string()))?;
+        Ok(Self {
+            public_nonce,
+            pre_signature: signature.get_signature().clone(),
+        })
+    }
+
+    /// Checks that completing this pre-signature with the discrete log of `adaptor_point` yields a valid signature of
+    /// `message` for `public_key`
+    pub fn verify(&self, public_key: &PublicKey, adaptor_point: &PublicKey, message: &[u8]) -> bool {
+        let challenge = Self::challenge(&self.public_nonce, adaptor_point, public_key, message);
+        CheckSigSchnorrSignature::new(self.public_nonce.clone(), self.pre_signature.clone())
+            .verify_raw_uniform(public_key, challenge.as_ref())
+    }
+
+    /// Completes the pre-signature with `adaptor_secret`, the discrete log of the adaptor point
+    pub fn complete(&self, adaptor_secret: &PrivateKey) -> CheckSigSchnorrSignature {
+        let adaptor_point = PublicKey::from_secret_key(adaptor_secret);
+        CheckSigSchnorrSignature::new(
+            &self.public_nonce + &adaptor_point,
+            &self.pre_signature + adaptor_secret,
+        )
+    }
+
+    /// Extracts the discrete log of `adaptor_point` from a signature completed from this pre-signature, or returns
+    /// `None` if `signature` was not completed from it
+    pub fn extract_secret(
+        &self,
+        adaptor_point: &PublicKey,
+        signature: &CheckSigSchnorrSignature,
+    ) -> Option<PrivateKey> {
+        if signature.get_public_nonce() != &(&self.public_nonce + adaptor_point) {
+            return None;
+        }
+        let secret = signature.get_signature() - &self.pre_signature;
+        if &PublicKey::from_secret_key(&secret) == adaptor_point {
+            Some(secret)
+        } else {
+            None
+        }
+    }
+
+    pub fn public_nonce(&self) -> &PublicKey {
+        &self.public_nonce
+    }
+
+    pub fn pre_signature(&self) -> &PrivateKey {
+        &self.pre_signature
+    }
+
+    /// The challenge of the completed signature, `e = H(R + T || A || m)`, as computed by `CheckSig`
+    fn challenge(
+        public_nonce: &PublicKey,
+        adaptor_point: &PublicKey,
+        public_key: &PublicKey,
+        message: &[u8],
+    ) -> impl AsRef<[u8]> {
+        CheckSigSchnorrSignature::construct_domain_separated_challenge::<_, Blake2b<U64>>(
+            &(public_nonce + adaptor_point),
+            public_key,
+            message,
+        )
+    }
+}
+
+/// The script of an output locked in an adaptor signature swap:
+///
+/// ```text
+/// PushPubKey(<swap key>) CheckSig(<message>) IfThen
+///     PushPubKey(<claim key>)
+/// Else
+///     CheckHeightVerify(<refund height>) PushPubKey(<refund key>)
+/// EndIf
+/// ```
+///
+/// The output is claimed with the input stack `[<signature of the message by the swap key>]`, which requires the
+/// completed adaptor signature. After the refund height, it can be refunded with any other signature on the stack.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AdaptorSwapScript {
+    pub swap_public_key: PublicKey,
+    pub adaptor_point: PublicKey,
+    pub claim_public_key: PublicKey,
+    pub refund_public_key: PublicKey,
+    pub refund_height: u64,
+}
+
+impl AdaptorSwapScript {
+    /// The message signed by the swap key. It commits to the adaptor point, so that a pre-signature cannot be reused
+    /// with a different one.
+    pub fn message(&self) -> Message {
+        Self::compute_message(&self.swap_public_key, &self.adaptor_point)
+    }
+
+    fn compute_message(swap_public_key: &PublicKey, adaptor_point: &PublicKey) -> Message {
+        let hash = DomainSeparatedHasher::<Blake2b<U32>, AdaptorSwapHashDomain>::new_with_label("message")
+            .chain(swap_public_key.as_bytes())
+            .chain(adaptor_point.as_bytes())
+            .finalize();
+        let mut message = Message::default();
+        message.copy_from_slice(hash.as_ref());
+        message
+    }
+
+    pub fn to_script(&self) -> Result<TariScript, ScriptError> {
+        script!(
+            PushPubKey(Box::new(self.swap_public_key.clone())) CheckSig(Box::new(self.message())) IfThen
+                PushPubKey(Box::new(self.claim_public_key.clone()))
+            Else
+                CheckHeightVerify(self.refund_height) PushPubKey(Box::new(self.refund_public_key.clone()))
+            EndIf
+        )
+    }
+
+    /// Parses the script of a swap output. The message of the `CheckSig` must commit to `adaptor_point`.
+    pub fn from_script(script: &TariScript, adaptor_point: &PublicKey) -> Option<Self> {
+        #[allow(clippy::enum_glob_use)]
+        use Opcode::*;
+        let (swap_public_key, message, claim_public_key, refund_height, refund_public_key) = match script.as_slice() {
+            [PushPubKey(a), CheckSig(m), IfThen, PushPubKey(c), Else, CheckHeightVerify(h), PushPubKey(r), EndIf] => {
+                (a, m, c, h, r)
+            },
+            _ => return None,
+        };
+        if **message != Self::compute_message(swap_public_key, adaptor_point) {
+            return None;
+        }
+        Some(Self {
+            swap_public_key: (**swap_public_key).clone(),
+            adaptor_point: adaptor_point.clone(),
+            claim_public_key: (**claim_public_key).clone(),
+            refund_public_key: (**refund_public_key).clone(),
+            refund_height: *refund_height,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use rand::rngs::OsRng;
+    use tari_crypto::keys::SecretKey;
+    use tari_script::{inputs, ExecutionStack, ScriptContext, StackItem};
+
+    use super::*;
+
+    fn random_keypair() -> (PrivateKey, PublicKey) {
+        let k = PrivateKey::random(&mut OsRng);
+        let p = PublicKey::from_secret_key(&k);
+        (k, p)
+    }
+
+    #[test]
+    fn it_completes_and_extracts_the_adaptor_secret() {
+        let (a, public_key) = random_keypair();
+        let (t, adaptor_point) = random_keypair();
+        let message = b"swap";
+
+        let adaptor_signature =
+            AdaptorSignature::sign(&a, PrivateKey::random(&mut OsRng), &adaptor_point, message).unwrap();
+        assert!(adaptor_signature.verify(&public_key, &adaptor_point, message));
+        assert!(!adaptor_signature.verify(&public_key, &random_keypair().1, message));
+        assert!(!adaptor_signature.verify(&public_key, &adaptor_point, b"other"));
+
+        // The pre-signature on its own is not a valid signature
+        let pre_signature = CheckSigSchnorrSignature::new(
+            adaptor_signature.public_nonce().clone(),
+            adaptor_signature.pre_signature().clone(),
+        );
+        assert!(!pre_signature.verify(&public_key, message));
+
+        let signature = adaptor_signature.complete(&t);
+        assert!(signature.verify(&public_key, message));
+        assert_eq!(adaptor_signature.extract_secret(&adaptor_point, &signature), Some(t));
+
+        let other_signature = CheckSigSchnorrSignature::sign(&a, message, &mut OsRng).unwrap();
+        assert_eq!(adaptor_signature.extract_secret(&adaptor_point, &other_signature), None);
+    }
+
+    #[test]
+    fn it_claims_or_refunds_the_swap_script() {
+        let (a, swap_public_key) = random_keypair();
+        let (t, adaptor_point) = random_keypair();
+        let swap = AdaptorSwapScript {
+            swap_public_key,
+            adaptor_point: adaptor_point.clone(),
+            claim_public_key: random_keypair().1,
+            refund_public_key: random_keypair().1,
+            refund_height: 100,
+        };
+        let script = swap.to_script().unwrap();
+        assert_eq!(
+            AdaptorSwapScript::from_script(&script, &adaptor_point),
+            Some(swap.clone())
+        );
+        assert_eq!(AdaptorSwapScript::from_script(&script, &random_keypair().1), None);
+
+        let adaptor_signature =
+            AdaptorSignature::sign(&a, PrivateKey::random(&mut OsRng), &adaptor_point, &swap.message()).unwrap();
+        let signature = adaptor_signature.complete(&t);
+        let context = ScriptContext::new(10, &[0; 32], &Default::default());
+        assert_eq!(
+            script.execute_with_context(&inputs!(signature), &context).unwrap(),
+            StackItem::PublicKey(swap.claim_public_key.clone())
+        );
+
+        let refund_inputs = inputs!(CheckSigSchnorrSignature::default());
+        assert_eq!(
+            script.execute_with_context(&refund_inputs, &context).unwrap_err(),
+            ScriptError::VerifyFailed
+        );
+        let context = ScriptContext::new(100, &[0; 32], &Default::default());
+        assert_eq!(
+            script.execute_with_context(&refund_inputs, &context).unwrap(),
+            StackItem::PublicKey(swap.refund_public_key.clone())
+        );
+        assert!(script
+            .execute_with_context(&ExecutionStack::default(), &context)
+            .is_err());
+    }
+}
@@ -93,6 +93,7 @@ use thiserror::Error;
 
 use crate::transactions::{tari_amount::*, transaction_components::TransactionError};
 
+pub mod adaptor_signature;
 pub mod proto;
 pub mod recipient;
 pub mod sender;
@@ -23,12 +23,13 @@
 use std::collections::HashSet;
 
 use log::warn;
-use tari_common_types::types::FixedHash;
+use tari_common_types::types::{FixedHash, HashOutput};
+use tari_script::{Opcode, OpcodeVersion, ScriptContext, StackItem};
 use tari_utilities::hex::Hex;
 
 use crate::{
-    chain_storage::BlockchainBackend,
-    consensus::{ConsensusConstants, ConsensusManager},
+    chain_storage::{fetch_deployment_state, BlockchainBackend, DeploymentStateCache},
+    consensus::{ConsensusConstants, ConsensusManager, OPCODE_V1_DEPLOYMENT},
     transactions::{
         aggregated_body::AggregateBody,
         transaction_components::{TransactionError, TransactionInput, TransactionOutput},
@@ -50,27 +51,60 @@ pub const LOG_TARGET: &str = "c::val::aggregate_body_chain_linked_validator";
 #[derive(Clone)]
 pub struct AggregateBodyChainLinkedValidator {
     consensus_manager: ConsensusManager,
+    deployment_states: DeploymentStateCache,
 }
 
 impl AggregateBodyChainLinkedValidator {
     pub fn new(consensus_manager: ConsensusManager) -> Self {
-        Self { consensus_manager }
+        Self {
+            consensus_manager,
+            deployment_states: DeploymentStateCache::new(),
+        }
     }
 
+    /// Validates the body against the chain, as the body of a block at `height` that builds on the block with hash
+    /// `prev_hash`
     pub fn validate<B: BlockchainBackend>(
         &self,
         body: &AggregateBody,
         height: u64,
+        prev_hash: &HashOutput,
         db: &B,
     ) -> Result<AggregateBody, ValidationError> {
         let constants = self.consensus_manager.consensus_constants(height);
+        let active_deployments = self.fetch_active_deployments(db, height, prev_hash)?;
 
         self.validate_consensus(body, db, constants)?;
-        let body = self.validate_input_and_maturity(body, db, constants, height)?;
+        let body = self.validate_input_and_maturity(body, db, constants, height, prev_hash, &active_deployments)?;
 
         Ok(body)
     }
 
+    /// Resolves, once for the whole body, which deployments are active in the block at `height` that builds on the
+    /// block with hash `prev_hash`. The state of a deployment only depends on the ancestry of that block.
+    fn fetch_active_deployments<B: BlockchainBackend>(
+        &self,
+        db: &B,
+        height: u64,
+        prev_hash: &HashOutput,
+    ) -> Result<HashSet<&'static str>, ValidationError> {
+        let mut active_deployments = HashSet::new();
+        for deployment in self.consensus_manager.deployments() {
+            let state = fetch_deployment_state(
+                db,
+                &self.consensus_manager,
+                &self.deployment_states,
+                deployment.name(),
+                prev_hash,
+                height,
+            )?;
+            if state.is_active() {
+                active_deployments.insert(deployment.name());
+            }
+        }
+        Ok(active_deployments)
+    }
+
     fn validate_consensus<B: BlockchainBackend>(
         &self,
         body: &AggregateBody,
@@ -92,6 +126,8 @@ impl AggregateBodyChainLinkedValidator {
         db: &B,
         constants: &ConsensusConstants,
         height: u64,
+        prev_hash: &HashOutput,
+        active_deployments: &HashSet<&'static str>,
     ) -> Result<AggregateBody, ValidationError> {
         // inputs may be "slim", only containing references to outputs
         // so we need to resolve those references, creating a new body in the process
@@ -101,6 +137,10 @@ impl AggregateBodyChainLinkedValidator {
 
         validate_input_maturity(&body, height)?;
         check_inputs_are_utxos(db, &body)?;
+        check_relative_height_locks(db, &body, height, prev_hash)?;
+        check_covenant_relative_heights(db, &body, height)?;
+        check_covenant_deployments(&body, active_deployments)?;
+        check_script_deployments(&body, active_deployments)?;
         check_outputs(db, constants, &body)?;
         verify_no_duplicated_inputs_outputs(&body)?;
         check_total_burned(&body)?;
@@ -252,6 +292,140 @@ fn check_inputs_are_utxos<B: BlockchainBackend>(db: &B, body: &AggregateBody) ->
     Ok(())
 }
 
+/// This function executes the scripts of inputs that contain relative height locks again, now with the height at which
+/// the spent output was mined. That height is not known to the internal consistency validator, which skips these
+/// checks.
+fn check_relative_height_locks<B: BlockchainBackend>(
+    db: &B,
+    body: &AggregateBody,
+    height: u64,
+    prev_hash: &HashOutput,
+) -> Result<(), ValidationError> {
+    for input in body.inputs() {
+        let is_relative_lock = |opcode: &Opcode| matches!(opcode, Opcode::CheckRelativeHeightVerify(_));
+        // The lock can also be in a script revealed to `ExecuteMast`
+        let has_relative_lock = input.script()?.as_slice().iter().any(is_relative_lock) ||
+            input.input_data.as_slice().iter().any(|item| match item {
+                StackItem::Script(script) => script.as_slice().iter().any(is_relative_lock),
+                _ => false,
+            });
+        if !has_relative_lock {
+            continue;
+        }
+        let mined_height = fetch_input_mined_height(db, input, height)?;
+        let context = ScriptContext::new(height, prev_hash, input.commitment()?).with_mined_height(mined_height);
+        if let Err(e) = input.run_script(Some(context)) {
+            warn!(
+                target: LOG_TARGET,
+                "Input {} failed its relative height lock at height {}: {}",
+                input.commitment()?.to_hex(),
+                height,
+                e
+            );
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+/// The height of the block the output spent by `input` was mined in
+fn fetch_input_mined_height<B: BlockchainBackend>(
+    db: &B,
+    input: &TransactionInput,
+    height: u64,
+) -> Result<u64, ValidationError> {
+    match db.fetch_output(&input.output_hash())? {
+        Some(output_mined_info) => Ok(output_mined_info.mined_height),
+        // The output is created in this body, which check_inputs_are_utxos has established
+        None => Ok(height),
+    }
+}
+
+/// The internal consistency validator executes covenants without the height the spent outputs were mined at, so
+/// covenants with relative height filters are executed again here with it.
+fn check_covenant_relative_heights<B: BlockchainBackend>(
+    db: &B,
+    body: &AggregateBody,
+    height: u64,
+) -> Result<(), ValidationError> {
+    for input in body.inputs() {
+        let covenant = input.covenant()?;
+        if !covenant.has_relative_height_filter() {
+            continue;
+        }
+        let mined_height = fetch_input_mined_height(db, input, height)?;
+        if let Err(e) = covenant.execute_with_input_mined_height(height, mined_height, input, body.outputs()) {
+            warn!(
+                target: LOG_TARGET,
+                "Input {} failed the relative height filter of its covenant at height {}: {}",
+                input.commitment()?.to_hex(),
+                height,
+                e
+            );
+            return Err(e.into());
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the covenants of the outputs only use filters whose consensus deployment is active. Deployments that
+/// are not scheduled on this network are never active.
+fn check_covenant_deployments(
+    body: &AggregateBody,
+    active_deployments: &HashSet<&'static str>,
+) -> Result<(), ValidationError> {
+    for output in body.outputs() {
+        for (filter, deployment) in output.covenant.required_deployments() {
+            if !active_deployments.contains(deployment) {
+                return Err(ValidationError::CovenantFilterNotActive { filter, deployment });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The consensus deployment that activates the opcodes and input data items of `version`, if they were added after
+/// launch
+fn script_version_deployment(version: OpcodeVersion) -> Option<&'static str> {
+    match version {
+        OpcodeVersion::V0 => None,
+        OpcodeVersion::V1 => Some(OPCODE_V1_DEPLOYMENT),
+    }
+}
+
+/// Checks that the scripts of the outputs and the input data of the inputs only use opcodes and items whose consensus
+/// deployment is active. A script revealed to `ExecuteMast` is an input data item of the latest version, so the
+/// opcodes within it need no further check.
+fn check_script_deployments(
+    body: &AggregateBody,
+    active_deployments: &HashSet<&'static str>,
+) -> Result<(), ValidationError> {
+    let inactive_deployment = |version: OpcodeVersion| {
+        script_version_deployment(version).filter(|deployment| !active_deployments.contains(deployment))
+    };
+    for output in body.outputs() {
+        for opcode in output.script.as_slice() {
+            if let Some(deployment) = inactive_deployment(opcode.get_version()) {
+                return Err(ValidationError::ScriptVersionNotActive {
+                    item: opcode.to_string(),
+                    deployment,
+                });
+            }
+        }
+    }
+    for input in body.inputs() {
+        for item in input.input_data.as_slice() {
+            if let Some(deployment) = inactive_deployment(item.get_version()) {
+                return Err(ValidationError::ScriptVersionNotActive {
+                    item: item.to_string(),
+                    deployment,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
 /// This function checks:
 /// 1. that the output type is permitted
 /// 2. the byte size of TariScript does not exceed the maximum
@@ -330,3 +504,31 @@ fn verify_timelocks(body: &AggregateBody, current_height: u64) -> Result<(), Val
     }
     Ok(())
 }
+
+#[cfg(test)]
+mod test {
+    use tari_script::{script, ExecutionStack};
+
+    use super::*;
+    use crate::consensus::COVENANT_FILTERS_DEPLOYMENT;
+
+    #[test]
+    fn it_only_permits_v1_script_items_once_deployed() {
+        let mut output = TransactionOutput::default();
+        output.script = script!(CheckRelativeHeightVerify(10)).unwrap();
+        let body = AggregateBody::new(vec![], vec![output], vec![]);
+        let err = check_script_deployments(&body, &HashSet::from([COVENANT_FILTERS_DEPLOYMENT])).unwrap_err();
+        assert!(matches!(err, ValidationError::ScriptVersionNotActive {
+            deployment: OPCODE_V1_DEPLOYMENT,
+            ..
+        }));
+        check_script_deployments(&body, &HashSet::from([OPCODE_V1_DEPLOYMENT])).unwrap();
+
+        // A script revealed to `ExecuteMast` is itself a V1 item
+        let mut input = TransactionInput::default();
+        input.input_data = ExecutionStack::new(vec![StackItem::Script(script!(PushOne).unwrap())]);
+        let body = AggregateBody::new(vec![input], vec![TransactionOutput::default()], vec![]);
+        assert!(check_script_deployments(&body, &HashSet::new()).is_err());
+        check_script_deployments(&body, &HashSet::from([OPCODE_V1_DEPLOYMENT])).unwrap();
+    }
+}
@@ -79,7 +79,9 @@ impl BlockBodyFullValidator {
         let height = block.header.height;
         // the inputs may be only references to outputs, that's why the validator returns a new body and we need a new
         // block
-        let body = self.aggregate_body_chain_validator.validate(body, height, backend)?;
+        let body = self
+            .aggregate_body_chain_validator
+            .validate(body, height, &block.header.prev_hash, backend)?;
         let block = Block::new(block.header.clone(), body);
 
         // validate the internal consistency of the block body
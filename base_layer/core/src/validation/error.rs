@@ -134,6 +134,15 @@ pub enum ValidationError {
     DifficultyError(#[from] DifficultyError),
     #[error("Covenant too large. Max size: {max_size}, Actual size: {actual_size}")]
     CovenantTooLarge { max_size: usize, actual_size: usize },
+    #[error("Covenant filter '{filter}' is not permitted before deployment '{deployment}' is active")]
+    CovenantFilterNotActive {
+        filter: &'static str,
+        deployment: &'static str,
+    },
+    #[error("Script item '{item}' is not permitted before deployment '{deployment}' is active")]
+    ScriptVersionNotActive { item: String, deployment: &'static str },
+    #[error("The script of output {0} can never be spent")]
+    UnspendableScript(String),
 }
 
 // ChainStorageError has a ValidationError variant, so to prevent a cyclic dependency we use a string representation in
@@ -184,12 +193,17 @@ impl ValidationError {
             err @ ValidationError::InvalidValidatorNodeSignature |
             err @ ValidationError::DifficultyError(_) |
             err @ ValidationError::CoinbaseExceedsMaxLimit |
-            err @ ValidationError::CovenantTooLarge { .. } => Some(BanReason {
+            err @ ValidationError::CovenantTooLarge { .. } |
+            err @ ValidationError::CovenantFilterNotActive { .. } |
+            err @ ValidationError::ScriptVersionNotActive { .. } => Some(BanReason {
                 reason: err.to_string(),
                 ban_duration: BanPeriod::Long,
             }),
             ValidationError::MergeMineError(e) => e.get_ban_reason(),
-            ValidationError::FatalStorageError(_) | ValidationError::IncorrectNumberOfTimestampsProvided { .. } => None,
+            // Unspendable scripts are valid in blocks, they are only rejected by the mempool
+            ValidationError::FatalStorageError(_) |
+            ValidationError::IncorrectNumberOfTimestampsProvided { .. } |
+            ValidationError::UnspendableScript(_) => None,
         }
     }
 }
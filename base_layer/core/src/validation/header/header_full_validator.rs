@@ -71,7 +71,7 @@ impl<B: BlockchainBackend> HeaderChainLinkedValidator<B> for HeaderFullValidator
         let constants = self.rules.consensus_constants(header.height);
 
         check_not_bad_block(db, header.hash())?;
-        check_blockchain_version(constants, header.version)?;
+        check_blockchain_version(&self.rules, header)?;
         check_height(header, prev_header)?;
         check_prev_hash(header, prev_header)?;
 
@@ -149,11 +149,13 @@ fn check_prev_hash(header: &BlockHeader, prev_header: &BlockHeader) -> Result<()
     Ok(())
 }
 
-fn check_blockchain_version(constants: &ConsensusConstants, version: u16) -> Result<(), ValidationError> {
-    if constants.valid_blockchain_version_range().contains(&version) {
+fn check_blockchain_version(rules: &ConsensusManager, header: &BlockHeader) -> Result<(), ValidationError> {
+    if rules.is_valid_header_version(header.height, header.version) {
         Ok(())
     } else {
-        Err(ValidationError::InvalidBlockchainVersion { version })
+        Err(ValidationError::InvalidBlockchainVersion {
+            version: header.version,
+        })
     }
 }
 
@@ -25,7 +25,7 @@ use std::convert::TryFrom;
 use log::*;
 use tari_common_types::types::FixedHash;
 use tari_crypto::tari_utilities::{epoch_time::EpochTime, hex::Hex};
-use tari_script::TariScript;
+use tari_script::{StackItem, TariScript};
 
 use crate::{
     blocks::{BlockHeader, BlockHeaderValidationError, BlockValidationError},
@@ -439,6 +439,29 @@ pub fn validate_input_version(
         return Err(ValidationError::ConsensusError(msg));
     }
 
+    let opcode_version_range = &consensus_constants.output_version_range().opcode;
+    for item in input.input_data.as_slice() {
+        if !opcode_version_range.contains(&item.get_version()) {
+            let msg = format!(
+                "Transaction input data contains an item not allowed by consensus ({:?})",
+                item.get_version()
+            );
+            return Err(ValidationError::ConsensusError(msg));
+        }
+        // Scripts revealed to `ExecuteMast` are held to the same opcode versions as output scripts
+        if let StackItem::Script(script) = item {
+            for opcode in script.as_slice() {
+                if !opcode_version_range.contains(&opcode.get_version()) {
+                    let msg = format!(
+                        "Transaction input data script opcode is not allowed by consensus ({})",
+                        opcode
+                    );
+                    return Err(ValidationError::ConsensusError(msg));
+                }
+            }
+        }
+    }
+
     Ok(())
 }
 
@@ -574,6 +597,32 @@ mod test {
         }
     }
 
+    mod validate_input_version {
+        use tari_script::{script, ExecutionStack};
+
+        use super::*;
+        use crate::transactions::transaction_components::TransactionInput;
+
+        #[test]
+        fn it_only_allows_revealed_scripts_where_execute_mast_is_allowed() {
+            let input = TransactionInput {
+                input_data: ExecutionStack::new(vec![StackItem::Script(script!(PushOne).unwrap())]),
+                ..Default::default()
+            };
+            let localnet = &ConsensusConstants::localnet()[0];
+            assert!(validate_input_version(localnet, &input).is_ok());
+            let esmeralda = &ConsensusConstants::esmeralda()[0];
+            let err = validate_input_version(esmeralda, &input).unwrap_err();
+            assert!(matches!(err, ValidationError::ConsensusError(_)));
+
+            let input = TransactionInput {
+                input_data: ExecutionStack::new(vec![StackItem::Number(1)]),
+                ..Default::default()
+            };
+            assert!(validate_input_version(esmeralda, &input).is_ok());
+        }
+    }
+
     mod check_coinbase_maturity {
         use futures::executor::block_on;
 
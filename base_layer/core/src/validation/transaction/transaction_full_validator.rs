@@ -20,6 +20,8 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+use tari_utilities::hex::Hex;
+
 use super::{TransactionChainLinkedValidator, TransactionInternalConsistencyValidator};
 use crate::{
     chain_storage::{BlockchainBackend, BlockchainDatabase},
@@ -63,7 +65,19 @@ impl<B: BlockchainBackend> TransactionValidator for TransactionFullValidator<B>
         }?;
         self.internal_validator.validate_with_current_tip(tx, tip)?;
         self.chain_validator.validate(tx)?;
+        check_output_scripts_are_spendable(tx)?;
 
         Ok(())
     }
 }
+
+/// Outputs with scripts that can never be spent are valid in a block, but the funds in them are lost, so the mempool
+/// does not accept them
+fn check_output_scripts_are_spendable(tx: &Transaction) -> Result<(), ValidationError> {
+    for output in tx.body.outputs() {
+        if !output.script.analyze().can_succeed() {
+            return Err(ValidationError::UnspendableScript(output.commitment.to_hex()));
+        }
+    }
+    Ok(())
+}
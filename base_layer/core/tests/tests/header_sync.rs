@@ -64,6 +64,7 @@ async fn test_header_sync_happy_path() {
     match event.clone() {
         StateEvent::HeadersSynchronized(_val, sync_result) => {
             assert_eq!(sync_result.headers_returned, 1);
+            assert_eq!(sync_result.headers_synced, 1);
             assert_eq!(sync_result.peer_fork_hash_index, 0);
             if let HeaderSyncStatus::Lagging(val) = sync_result.header_sync_status {
                 assert_eq!(val.best_block_header.height(), 0);
@@ -82,6 +83,7 @@ async fn test_header_sync_happy_path() {
     match event.clone() {
         StateEvent::HeadersSynchronized(_val, sync_result) => {
             assert_eq!(sync_result.headers_returned, 0);
+            assert_eq!(sync_result.headers_synced, 0);
             assert_eq!(sync_result.peer_fork_hash_index, 0);
             if let HeaderSyncStatus::InSyncOrAhead = sync_result.header_sync_status {
                 // Good, headers were in sync
@@ -113,6 +115,7 @@ async fn test_header_sync_happy_path() {
     match event {
         StateEvent::HeadersSynchronized(_val, sync_result) => {
             assert_eq!(sync_result.headers_returned, 1);
+            assert_eq!(sync_result.headers_synced, 1);
             assert_eq!(sync_result.peer_fork_hash_index, 0);
             if let HeaderSyncStatus::Lagging(val) = sync_result.header_sync_status {
                 assert_eq!(val.best_block_header.height(), 0);
@@ -48,6 +48,16 @@ pub struct BalancedBinaryMerkleProof<D> {
 impl<D> BalancedBinaryMerkleProof<D>
 where D: Digest
 {
+    /// Creates a proof from the sibling hashes on the path from the leaf to the root and the index of the leaf node in
+    /// the tree (not the leaf index), as returned by `path` and `node_index`
+    pub fn from_path(path: Vec<Hash>, node_index: u32) -> Self {
+        Self {
+            path,
+            node_index,
+            _phantom: PhantomData,
+        }
+    }
+
     #[must_use = "Must use the result of the proof verification"]
     pub fn verify(&self, root: &Hash, leaf_hash: Hash) -> bool {
         let mut computed_root = leaf_hash;
@@ -0,0 +1 @@
+DROP TABLE adaptor_swaps;
@@ -0,0 +1,18 @@
+CREATE TABLE adaptor_swaps
+(
+    output_hash       BLOB PRIMARY KEY NOT NULL,
+    role              INTEGER          NOT NULL,
+    status            INTEGER          NOT NULL,
+    amount            BIGINT           NOT NULL,
+    swap_public_key   BLOB             NOT NULL,
+    adaptor_point     BLOB             NOT NULL,
+    public_nonce      BLOB             NOT NULL,
+    pre_signature     BLOB             NOT NULL,
+    refund_height     BIGINT           NOT NULL,
+    lock_tx_id        BIGINT           NULL,
+    spend_tx_id       BIGINT           NULL,
+    signature_nonce   BLOB             NULL,
+    signature         BLOB             NULL,
+    adaptor_secret    BLOB             NULL,
+    timestamp         DATETIME         NOT NULL
+);
@@ -0,0 +1,2 @@
+DROP TABLE multisig_outputs;
+DROP TABLE multisig_accounts;
@@ -0,0 +1,23 @@
+CREATE TABLE multisig_accounts
+(
+    name            TEXT PRIMARY KEY NOT NULL,
+    threshold       INTEGER          NOT NULL,
+    public_keys     BLOB             NOT NULL,
+    script_key_id   TEXT             NOT NULL,
+    view_key_id     TEXT             NOT NULL,
+    view_public_key BLOB             NOT NULL,
+    created_at      DATETIME         NOT NULL
+);
+
+CREATE TABLE multisig_outputs
+(
+    hash           BLOB PRIMARY KEY NOT NULL,
+    account_name   TEXT             NOT NULL,
+    commitment     BLOB             NOT NULL,
+    value          BIGINT           NOT NULL,
+    mined_height   BIGINT           NOT NULL,
+    spent_in_tx_id BIGINT           NULL,
+    spent          INTEGER          NOT NULL DEFAULT 0
+);
+
+CREATE INDEX idx_multisig_outputs_account_name ON multisig_outputs (account_name);
@@ -0,0 +1,2 @@
+ALTER TABLE completed_transactions
+    DROP COLUMN replaced_by_tx_id;
@@ -0,0 +1,2 @@
+ALTER TABLE completed_transactions
+    ADD replaced_by_tx_id BIGINT NULL;
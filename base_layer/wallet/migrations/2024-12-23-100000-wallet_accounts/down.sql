@@ -0,0 +1,6 @@
+DROP INDEX idx_outputs_account_name;
+
+ALTER TABLE outputs
+    DROP COLUMN account_name;
+
+DROP TABLE wallet_accounts;
@@ -0,0 +1,11 @@
+CREATE TABLE wallet_accounts
+(
+    name          TEXT PRIMARY KEY NOT NULL,
+    account_index BIGINT UNIQUE    NOT NULL,
+    created_at    DATETIME         NOT NULL
+);
+
+ALTER TABLE outputs
+    ADD account_name TEXT NULL;
+
+CREATE INDEX idx_outputs_account_name ON outputs (account_name);
@@ -0,0 +1,9 @@
+DROP TABLE transaction_labels;
+
+DROP INDEX idx_outputs_label;
+
+ALTER TABLE outputs
+    DROP COLUMN frozen;
+
+ALTER TABLE outputs
+    DROP COLUMN label;
@@ -0,0 +1,13 @@
+ALTER TABLE outputs
+    ADD label TEXT NULL;
+
+ALTER TABLE outputs
+    ADD frozen INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX idx_outputs_label ON outputs (label);
+
+CREATE TABLE transaction_labels
+(
+    tx_id BIGINT PRIMARY KEY NOT NULL,
+    label TEXT               NOT NULL
+);
@@ -0,0 +1,20 @@
+CREATE TABLE payment_schedules
+(
+    id                  INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+    destination         BLOB      NOT NULL,
+    amount              BIGINT    NOT NULL,
+    payment_id          BLOB      NOT NULL,
+    fee_per_gram        BIGINT    NOT NULL,
+    max_fee             BIGINT    NULL,
+    interval_secs       BIGINT    NOT NULL,
+    next_payment        TIMESTAMP NOT NULL,
+    end_time            TIMESTAMP NULL,
+    max_missed_payments INTEGER   NOT NULL DEFAULT 0,
+    status              INTEGER   NOT NULL DEFAULT 0,
+    payments_made       BIGINT    NOT NULL DEFAULT 0,
+    last_tx_id          BIGINT    NULL,
+    last_error          TEXT      NULL,
+    failed_attempts     INTEGER   NOT NULL DEFAULT 0,
+    retry_at            TIMESTAMP NULL,
+    created_at          TIMESTAMP NOT NULL
+);
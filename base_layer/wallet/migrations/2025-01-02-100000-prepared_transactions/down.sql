@@ -0,0 +1 @@
+DROP TABLE prepared_transactions;
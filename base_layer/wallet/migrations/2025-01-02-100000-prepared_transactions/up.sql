@@ -0,0 +1,10 @@
+CREATE TABLE prepared_transactions
+(
+    tx_id       BIGINT PRIMARY KEY NOT NULL,
+    destination BLOB      NOT NULL,
+    amount      BIGINT    NOT NULL,
+    fee         BIGINT    NOT NULL,
+    change      BIGINT    NOT NULL,
+    payment_id  BLOB      NOT NULL,
+    timestamp   TIMESTAMP NOT NULL
+);
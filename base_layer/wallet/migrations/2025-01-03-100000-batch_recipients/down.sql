@@ -0,0 +1 @@
+DROP TABLE batch_recipients;
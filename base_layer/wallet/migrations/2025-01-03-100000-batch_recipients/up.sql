@@ -0,0 +1,9 @@
+CREATE TABLE batch_recipients
+(
+    tx_id      BIGINT  NOT NULL,
+    position   INTEGER NOT NULL,
+    address    BLOB    NOT NULL,
+    amount     BIGINT  NOT NULL,
+    payment_id BLOB    NOT NULL,
+    PRIMARY KEY (tx_id, position)
+);
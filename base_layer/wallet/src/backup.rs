@@ -0,0 +1,202 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Encrypted, portable wallet backups.
+//!
+//! Recovering a wallet from its seed words finds the wallet's funds, but not the transaction history, contacts,
+//! labels, known one-sided payment scripts, named and multi-signature accounts, swaps, payment schedules or imported
+//! keys, and it requires the whole chain to be scanned. A backup holds that data together with the wallet's unspent
+//! outputs and the last block the wallet scanned, so that a wallet restored from a backup only has to scan the chain
+//! from that block onwards.
+//!
+//! Outputs encumbered by a pending transaction are not backed up, since the pending transaction is not. If the
+//! transaction is mined, its outputs are found by scanning from the backed up block.
+
+use argon2::password_hash::{rand_core::OsRng, SaltString};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tari_common::configuration::Network;
+use tari_common_types::{
+    encryption::{decrypt_bytes_integral_nonce, encrypt_bytes_integral_nonce},
+    tari_address::TariAddress,
+    transaction::TxId,
+    types::{FixedHash, PrivateKey},
+};
+use tari_core::transactions::transaction_components::WalletOutput;
+use tari_utilities::{
+    hex::{from_hex, Hex},
+    Hidden,
+    SafePassword,
+};
+
+use crate::{
+    error::WalletError,
+    output_manager_service::storage::{
+        models::{KnownOneSidedPaymentScript, MultisigAccount, MultisigOutput, SpendingPriority, WalletAccount},
+        OutputSource,
+    },
+    storage::sqlite_db::wallet::derive_passphrase_cipher,
+    transaction_service::storage::models::TransactionServiceBackup,
+};
+
+/// The version of the backup file format
+pub const WALLET_BACKUP_VERSION: u8 = 1;
+
+// Additional authenticated data for the backup encryption, followed by the backup version
+const BACKUP_AAD_PREFIX: &str = "wallet_backup_v";
+
+/// The wallet data that cannot be derived from the seed words
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletBackup {
+    pub network: Network,
+    pub created_at: DateTime<Utc>,
+    /// The last block scanned by the wallet, from which a restored wallet continues scanning
+    pub scanned_block: Option<BackupScannedBlock>,
+    pub transactions: TransactionServiceBackup,
+    pub contacts: Vec<BackupContact>,
+    /// The private keys imported into the wallet's key manager that the backed up outputs, scripts and accounts use.
+    /// They cannot be derived from the seed words.
+    pub imported_keys: Vec<PrivateKey>,
+    pub known_one_sided_payment_scripts: Vec<KnownOneSidedPaymentScript>,
+    pub wallet_accounts: Vec<WalletAccount>,
+    pub multisig_accounts: Vec<MultisigAccount>,
+    /// The unspent outputs of the multi-signature accounts
+    pub multisig_outputs: Vec<MultisigOutput>,
+    pub outputs: Vec<BackupOutput>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupScannedBlock {
+    pub height: u64,
+    pub header_hash: FixedHash,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupContact {
+    pub alias: String,
+    pub address: TariAddress,
+    pub favourite: bool,
+}
+
+/// An unspent output, along with the coin control settings the user gave it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupOutput {
+    pub output: WalletOutput,
+    pub source: OutputSource,
+    pub spending_priority: SpendingPriority,
+    pub received_in_tx_id: Option<TxId>,
+    /// The named wallet account the output belongs to
+    pub account: Option<String>,
+    pub label: Option<String>,
+    pub frozen: bool,
+}
+
+/// A summary of the data added to a wallet from a backup
+#[derive(Debug, Clone, Default)]
+pub struct RestoredWalletBackup {
+    pub transactions: usize,
+    pub contacts: usize,
+    pub outputs: usize,
+    /// The height from which the wallet will continue scanning the chain
+    pub scanned_height: Option<u64>,
+}
+
+/// The backup file: the serialized backup, encrypted with a key derived from the user's passphrase
+#[derive(Serialize, Deserialize)]
+struct EncryptedWalletBackup {
+    version: u8,
+    kdf_version: u8,
+    salt: String,
+    data: String,
+}
+
+impl WalletBackup {
+    /// Serializes and encrypts the backup with the passphrase
+    pub fn encrypt(&self, passphrase: &SafePassword) -> Result<Vec<u8>, WalletError> {
+        let plaintext = serde_json::to_vec(self).map_err(|e| WalletError::BackupError(e.to_string()))?;
+        let salt = SaltString::generate(&mut OsRng).to_string();
+        let (kdf_version, cipher) = derive_passphrase_cipher(passphrase, None, &salt)?;
+        let data = encrypt_bytes_integral_nonce(&cipher, backup_aad(WALLET_BACKUP_VERSION), Hidden::hide(plaintext))
+            .map_err(WalletError::BackupError)?;
+
+        serde_json::to_vec(&EncryptedWalletBackup {
+            version: WALLET_BACKUP_VERSION,
+            kdf_version,
+            salt,
+            data: data.to_hex(),
+        })
+        .map_err(|e| WalletError::BackupError(e.to_string()))
+    }
+
+    /// Decrypts and deserializes a backup created with [`WalletBackup::encrypt`]
+    pub fn decrypt(backup: &[u8], passphrase: &SafePassword) -> Result<Self, WalletError> {
+        let backup: EncryptedWalletBackup = serde_json::from_slice(backup)
+            .map_err(|e| WalletError::BackupError(format!("Not a wallet backup file: {}", e)))?;
+        if backup.version != WALLET_BACKUP_VERSION {
+            return Err(WalletError::BackupError(format!(
+                "Unsupported backup version {}",
+                backup.version
+            )));
+        }
+        let data = from_hex(&backup.data).map_err(|e| WalletError::BackupError(e.to_string()))?;
+        let (_, cipher) = derive_passphrase_cipher(passphrase, Some(backup.kdf_version), &backup.salt)?;
+        let plaintext = decrypt_bytes_integral_nonce(&cipher, backup_aad(backup.version), &data)
+            .map_err(|_| WalletError::BackupError("Invalid passphrase or corrupt backup".to_string()))?;
+
+        serde_json::from_slice(&plaintext).map_err(|e| WalletError::BackupError(e.to_string()))
+    }
+}
+
+fn backup_aad(version: u8) -> Vec<u8> {
+    let mut aad = BACKUP_AAD_PREFIX.as_bytes().to_owned();
+    aad.push(version);
+    aad
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::Utc;
+    use tari_common::configuration::Network;
+    use tari_common_types::types::{FixedHash, PrivateKey};
+    use tari_utilities::SafePassword;
+
+    use super::{BackupScannedBlock, WalletBackup};
+    use crate::{error::WalletError, transaction_service::storage::models::TransactionServiceBackup};
+
+    #[test]
+    fn it_encrypts_and_decrypts_a_backup() {
+        let backup = WalletBackup {
+            network: Network::LocalNet,
+            created_at: Utc::now(),
+            scanned_block: Some(BackupScannedBlock {
+                height: 1234,
+                header_hash: FixedHash::zero(),
+            }),
+            transactions: TransactionServiceBackup {
+                transaction_labels: vec![(1u64.into(), "rent".to_string())],
+                ..Default::default()
+            },
+            contacts: vec![],
+            imported_keys: vec![PrivateKey::from(7u64)],
+            known_one_sided_payment_scripts: vec![],
+            wallet_accounts: vec![],
+            multisig_accounts: vec![],
+            multisig_outputs: vec![],
+            outputs: vec![],
+        };
+        let passphrase = SafePassword::from("backup passphrase");
+        let encrypted = backup.encrypt(&passphrase).unwrap();
+
+        let decrypted = WalletBackup::decrypt(&encrypted, &passphrase).unwrap();
+        assert_eq!(decrypted.network, Network::LocalNet);
+        assert_eq!(decrypted.scanned_block.unwrap().height, 1234);
+        assert_eq!(
+            decrypted.transactions.transaction_labels,
+            backup.transactions.transaction_labels
+        );
+        assert_eq!(decrypted.imported_keys, backup.imported_keys);
+
+        let result = WalletBackup::decrypt(&encrypted, &SafePassword::from("wrong passphrase"));
+        assert!(matches!(result, Err(WalletError::BackupError(_))));
+    }
+}
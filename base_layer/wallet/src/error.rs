@@ -106,6 +106,8 @@ pub enum WalletError {
     PublicAddressNotSet,
     #[error("Wallet connectivity error: `{0}`")]
     WalletConnectivityError(#[from] WalletConnectivityError),
+    #[error("Wallet backup error: {0}")]
+    BackupError(String),
 }
 
 pub const LOG_TARGET: &str = "minotari::application";
@@ -9,6 +9,7 @@
 
 #[macro_use]
 mod macros;
+pub mod backup;
 pub mod base_node_service;
 pub mod connectivity_service;
 pub mod error;
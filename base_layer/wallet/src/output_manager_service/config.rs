@@ -45,6 +45,15 @@ pub struct OutputManagerServiceConfig {
     pub autoignore_onesided_utxos: bool,
     /// The number of seconds that have to pass for the wallet to run revalidation of invalid UTXOs on startup.
     pub num_of_seconds_to_revalidate_invalid_utxos: u64,
+    /// Outputs with a value at or below this value, in micro MinoTari, are candidates for consolidation
+    pub auto_consolidation_max_output_value: u64,
+    /// The minimum number of candidate outputs required before a consolidation transaction is created
+    pub auto_consolidation_min_outputs: usize,
+    /// The maximum number of outputs merged by a single consolidation transaction
+    pub auto_consolidation_max_inputs: usize,
+    /// Outputs are only consolidated while the fee per gram required to be included in the next block, in micro
+    /// MinoTari, is at or below this value
+    pub auto_consolidation_max_fee_per_gram: u64,
 }
 
 impl Default for OutputManagerServiceConfig {
@@ -57,6 +66,10 @@ impl Default for OutputManagerServiceConfig {
             tx_validator_batch_size: 100,
             autoignore_onesided_utxos: false,
             num_of_seconds_to_revalidate_invalid_utxos: 60 * 60 * 24 * 3,
+            auto_consolidation_max_output_value: 1_000_000,
+            auto_consolidation_min_outputs: 20,
+            auto_consolidation_max_inputs: 100,
+            auto_consolidation_max_fee_per_gram: 5,
         }
     }
 }
@@ -73,6 +73,12 @@ pub enum OutputManagerError {
     InconsistentBaseNodeDataError(&'static str),
     #[error("Not enough funds to fulfil transaction")]
     NotEnoughFunds,
+    #[error("This is a watch-only wallet that cannot spend funds")]
+    WatchOnlyWallet,
+    #[error("Wallet account `{0}` not found")]
+    WalletAccountNotFound(String),
+    #[error("Wallet accounts are only supported by wallets that derive their keys from a seed")]
+    WalletAccountsNotSupported,
     #[error("Funds are still pending. Unable to fulfil transaction right now.")]
     FundsPending,
     #[error("Output already exists")]
@@ -113,6 +119,10 @@ pub enum OutputManagerError {
     NodeIdError(#[from] NodeIdError),
     #[error("Script hash does not match expected script")]
     InvalidScriptHash,
+    #[error("The script can never be spent: {0}")]
+    UnspendableScript(String),
+    #[error("Invalid adaptor swap: `{0}`")]
+    InvalidAdaptorSwap(String),
     #[error("Unsupported Covenant")]
     InvalidCovenant,
     #[error("Unsupported Output Features")]
@@ -210,6 +220,10 @@ pub enum OutputManagerStorageError {
     AeadError(String),
     #[error("Tried to insert a script that already exists in the database")]
     DuplicateScript,
+    #[error("A multi-signature account named `{0}` already exists")]
+    DuplicateMultisigAccount(String),
+    #[error("A wallet account named `{0}` already exists")]
+    DuplicateWalletAccount(String),
     #[error("Tari script error: {0}")]
     ScriptError(#[from] ScriptError),
     #[error("Binary not stored as valid hex:{0}")]
@@ -25,11 +25,12 @@ use std::{collections::HashMap, fmt, fmt::Formatter, sync::Arc};
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::TxId,
-    types::{Commitment, FixedHash, HashOutput, PublicKey},
+    types::{Commitment, FixedHash, HashOutput, PrivateKey, PublicKey},
 };
 use tari_core::{
     covenants::Covenant,
     transactions::{
+        key_manager::TariKeyId,
         tari_amount::MicroMinotari,
         transaction_components::{
             encrypted_data::PaymentId,
@@ -39,7 +40,11 @@ use tari_core::{
             WalletOutput,
             WalletOutputBuilder,
         },
-        transaction_protocol::{sender::TransactionSenderMessage, TransactionMetadata},
+        transaction_protocol::{
+            adaptor_signature::{AdaptorSignature, AdaptorSwapScript},
+            sender::TransactionSenderMessage,
+            TransactionMetadata,
+        },
         ReceiverTransactionProtocol,
         SenderTransactionProtocol,
     },
@@ -51,11 +56,21 @@ use tari_utilities::hex::Hex;
 use tokio::sync::broadcast;
 use tower::Service;
 
-use crate::output_manager_service::{
-    error::OutputManagerError,
-    service::{Balance, OutputInfoByTxId, UseOutput},
-    storage::models::{DbWalletOutput, KnownOneSidedPaymentScript, SpendingPriority},
-    UtxoSelectionCriteria,
+use crate::{
+    output_manager_service::{
+        error::OutputManagerError,
+        service::{Balance, OutputInfoByTxId, UseOutput},
+        storage::models::{
+            DbWalletOutput,
+            KnownOneSidedPaymentScript,
+            MultisigAccount,
+            MultisigOutput,
+            SpendingPriority,
+            WalletAccount,
+        },
+        UtxoSelectionCriteria,
+    },
+    transaction_service::offline_signing::{OfflineWalletOutput, UnsignedTransaction},
 };
 
 /// API Request enum
@@ -116,6 +131,12 @@ pub enum OutputManagerRequest {
         selection_criteria: UtxoSelectionCriteria,
         payment_id: PaymentId,
     },
+    CreateBatchTransaction {
+        outputs: Vec<(WalletOutput, TariKeyId)>,
+        fee_per_gram: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        payment_id: PaymentId,
+    },
     CancelTransaction(TxId),
     GetSpentOutputs,
     GetUnspentOutputs,
@@ -135,6 +156,7 @@ pub enum OutputManagerRequest {
         commitments: Vec<Commitment>,
         fee_per_gram: MicroMinotari,
     },
+    CreateConsolidationTransaction,
     FeeEstimate {
         amount: MicroMinotari,
         selection_criteria: UtxoSelectionCriteria,
@@ -154,7 +176,86 @@ pub enum OutputManagerRequest {
     ReinstateCancelledInboundTx(TxId),
     CreateClaimShaAtomicSwapTransaction(HashOutput, PublicKey, MicroMinotari),
     CreateHtlcRefundTransaction(HashOutput, MicroMinotari),
+    CreateClaimAdaptorSwapTransaction {
+        output_hash: HashOutput,
+        adaptor_signature: Box<AdaptorSignature>,
+        adaptor_secret: PrivateKey,
+        fee_per_gram: MicroMinotari,
+    },
     GetOutputInfoByTxId(TxId),
+    GetMultisigAccountKey(String),
+    CreateMultisigAccount {
+        name: String,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        view_key_share: Option<PrivateKey>,
+    },
+    GetMultisigAccounts,
+    GetMultisigOutputs(String),
+    ValidateMultisigOutputs(String),
+    ScanOutputsForMultisigAccounts(Vec<TransactionOutput>, u64),
+    ReleaseTransactionInputs {
+        tx_id: TxId,
+        amount: MicroMinotari,
+        fee_per_gram: MicroMinotari,
+    },
+    RestoreReleasedTransactionInputs {
+        tx_id: TxId,
+        input_commitments: Vec<Commitment>,
+    },
+    PrepareUnsignedTransaction {
+        tx_id: TxId,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+    },
+    PrepareToSignUnsignedTransaction(Box<UnsignedTransaction>),
+    ImportSignedTransactionOutputs {
+        tx_id: TxId,
+        input_commitments: Vec<Commitment>,
+        output_commitments: Vec<Commitment>,
+        change_output: Option<Box<OfflineWalletOutput>>,
+    },
+    CreateWalletAccount(String),
+    GetWalletAccounts,
+    GetWalletAccountBalance(String),
+    GetWalletAccountOutputs(String),
+    GetWalletAccountTxIds(String),
+    SetOutputsLabel {
+        commitments: Vec<Commitment>,
+        label: Option<String>,
+    },
+    SetOutputsFrozen {
+        commitments: Vec<Commitment>,
+        frozen: bool,
+    },
+}
+
+impl OutputManagerRequest {
+    /// Whether the request spends outputs of this wallet, which requires the private spend key
+    pub fn spends_funds(&self) -> bool {
+        #[allow(clippy::enum_glob_use)]
+        use OutputManagerRequest::*;
+        matches!(
+            self,
+            EncumberAggregateUtxo { .. } |
+                SpendBackupPreMineUtxo { .. } |
+                PrepareToSendTransaction { .. } |
+                CreatePayToSelfTransaction { .. } |
+                CreatePayToSelfWithOutputs { .. } |
+                CreateBatchTransaction { .. } |
+                CreateCoinSplit(_) |
+                CreateCoinSplitEven(_) |
+                ScrapeWallet { .. } |
+                CreateCoinJoin { .. } |
+                CreateConsolidationTransaction |
+                CreateClaimShaAtomicSwapTransaction(..) |
+                CreateHtlcRefundTransaction(..) |
+                CreateClaimAdaptorSwapTransaction { .. } |
+                ReleaseTransactionInputs { .. } |
+                PrepareToSignUnsignedTransaction(_)
+        )
+    }
 }
 
 impl fmt::Display for OutputManagerRequest {
@@ -251,6 +352,7 @@ impl fmt::Display for OutputManagerRequest {
                 "CreateCoinJoin: commitments={:#?}, fee_per_gram={}",
                 commitments, fee_per_gram,
             ),
+            CreateConsolidationTransaction => write!(f, "CreateConsolidationTransaction"),
             FeeEstimate {
                 amount,
                 selection_criteria,
@@ -269,6 +371,7 @@ impl fmt::Display for OutputManagerRequest {
                 write!(f, "CreateOutputWithFeatures({}, {})", value, features,)
             },
             CreatePayToSelfWithOutputs { .. } => write!(f, "CreatePayToSelfWithOutputs"),
+            CreateBatchTransaction { outputs, .. } => write!(f, "CreateBatchTransaction({} outputs)", outputs.len()),
             ReinstateCancelledInboundTx(_) => write!(f, "ReinstateCancelledInboundTx"),
             CreateClaimShaAtomicSwapTransaction(output, pre_image, fee_per_gram) => write!(
                 f,
@@ -280,8 +383,98 @@ impl fmt::Display for OutputManagerRequest {
                 "CreateHtlcRefundTransaction(output hash: {}, , fee_per_gram: {} )",
                 output, fee_per_gram,
             ),
+            CreateClaimAdaptorSwapTransaction {
+                output_hash,
+                fee_per_gram,
+                ..
+            } => write!(
+                f,
+                "CreateClaimAdaptorSwapTransaction(output hash: {}, fee_per_gram: {} )",
+                output_hash, fee_per_gram,
+            ),
 
             GetOutputInfoByTxId(t) => write!(f, "GetOutputInfoByTxId: {}", t),
+            GetMultisigAccountKey(name) => write!(f, "GetMultisigAccountKey({})", name),
+            CreateMultisigAccount {
+                name,
+                threshold,
+                public_keys,
+                ..
+            } => write!(
+                f,
+                "CreateMultisigAccount(name: {}, threshold: {}, parties: {})",
+                name,
+                threshold,
+                public_keys.len()
+            ),
+            GetMultisigAccounts => write!(f, "GetMultisigAccounts"),
+            GetMultisigOutputs(name) => write!(f, "GetMultisigOutputs({})", name),
+            ValidateMultisigOutputs(name) => write!(f, "ValidateMultisigOutputs({})", name),
+            ScanOutputsForMultisigAccounts(outputs, height) => write!(
+                f,
+                "ScanOutputsForMultisigAccounts({} outputs at height {})",
+                outputs.len(),
+                height
+            ),
+            ReleaseTransactionInputs {
+                tx_id,
+                amount,
+                fee_per_gram,
+            } => write!(
+                f,
+                "ReleaseTransactionInputs(tx_id: {}, amount: {}, fee_per_gram: {})",
+                tx_id, amount, fee_per_gram
+            ),
+            RestoreReleasedTransactionInputs {
+                tx_id,
+                input_commitments,
+            } => write!(
+                f,
+                "RestoreReleasedTransactionInputs(tx_id: {}, inputs: {})",
+                tx_id,
+                input_commitments.len()
+            ),
+            PrepareUnsignedTransaction {
+                tx_id,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+            } => write!(
+                f,
+                "PrepareUnsignedTransaction(tx_id: {}, amount: {}, selection: {}, fee_per_gram: {})",
+                tx_id, amount, selection_criteria, fee_per_gram
+            ),
+            PrepareToSignUnsignedTransaction(unsigned_transaction) => write!(
+                f,
+                "PrepareToSignUnsignedTransaction(tx_id: {})",
+                unsigned_transaction.tx_id
+            ),
+            ImportSignedTransactionOutputs {
+                tx_id,
+                input_commitments,
+                change_output,
+                ..
+            } => write!(
+                f,
+                "ImportSignedTransactionOutputs(tx_id: {}, {} inputs, change: {})",
+                tx_id,
+                input_commitments.len(),
+                change_output.is_some()
+            ),
+            CreateWalletAccount(name) => write!(f, "CreateWalletAccount({})", name),
+            GetWalletAccounts => write!(f, "GetWalletAccounts"),
+            GetWalletAccountBalance(name) => write!(f, "GetWalletAccountBalance({})", name),
+            GetWalletAccountOutputs(name) => write!(f, "GetWalletAccountOutputs({})", name),
+            GetWalletAccountTxIds(name) => write!(f, "GetWalletAccountTxIds({})", name),
+            SetOutputsLabel { commitments, label } => write!(
+                f,
+                "SetOutputsLabel({} outputs, label: {})",
+                commitments.len(),
+                label.as_deref().unwrap_or("<none>")
+            ),
+            SetOutputsFrozen { commitments, frozen } => {
+                write!(f, "SetOutputsFrozen({} outputs, frozen: {})", commitments.len(), frozen)
+            },
         }
     }
 }
@@ -318,6 +511,7 @@ pub enum OutputManagerResponse {
     BaseNodePublicKeySet,
     TxoValidationStarted(u64),
     Transaction((TxId, Transaction, MicroMinotari)),
+    ConsolidationTransaction(Option<(TxId, Transaction, MicroMinotari)>),
     PublicRewindKeys(Box<PublicRewindKeys>),
     RecoveryByte(u8),
     FeeEstimate(MicroMinotari),
@@ -333,8 +527,34 @@ pub enum OutputManagerResponse {
     },
     ReinstatedCancelledInboundTx,
     ClaimHtlcTransaction((TxId, MicroMinotari, MicroMinotari, Transaction)),
+    ClaimAdaptorSwapTransaction(Box<AdaptorSwapClaim>),
     OutputInfoByTxId(OutputInfoByTxId),
     CoinPreview((Vec<MicroMinotari>, MicroMinotari)),
+    MultisigAccountKey(PublicKey),
+    MultisigAccountCreated(MultisigAccount, Vec<(PublicKey, PrivateKey)>),
+    MultisigAccounts(Vec<MultisigAccount>),
+    MultisigOutputs(Vec<MultisigOutput>),
+    TransactionInputsReleased((Vec<Commitment>, Option<String>)),
+    ReleasedTransactionInputsRestored,
+    UnsignedTransactionPrepared((Vec<OfflineWalletOutput>, MicroMinotari, MicroMinotari)),
+    UnsignedTransactionReadyToSign((Box<SenderTransactionProtocol>, Option<Box<OfflineWalletOutput>>)),
+    SignedTransactionOutputsImported,
+    WalletAccountCreated((WalletAccount, TariAddress)),
+    WalletAccounts(Vec<(WalletAccount, TariAddress)>),
+    WalletAccountTxIds(Vec<TxId>),
+    OutputsUpdated,
+}
+
+/// A transaction claiming an adaptor signature swap output
+#[derive(Debug, Clone)]
+pub struct AdaptorSwapClaim {
+    pub tx_id: TxId,
+    pub fee: MicroMinotari,
+    pub amount: MicroMinotari,
+    pub transaction: Transaction,
+    pub swap_script: AdaptorSwapScript,
+    /// The completed adaptor signature, which reveals the adaptor secret once the transaction is published
+    pub signature: CheckSigSchnorrSignature,
 }
 
 pub type OutputManagerEventSender = broadcast::Sender<Arc<OutputManagerEvent>>;
@@ -347,6 +567,15 @@ pub enum OutputManagerEvent {
     TxoValidationInternalFailure(u64),
     TxoValidationCommunicationFailure(u64),
     TxoValidationAlreadyBusy(u64),
+    /// Small outputs were merged into a single output by the automatic consolidation policy, in the transaction that
+    /// the transaction service submits to the network
+    OutputsConsolidated {
+        tx_id: TxId,
+        num_inputs: usize,
+        amount: MicroMinotari,
+        fee: MicroMinotari,
+    },
+    OutputConsolidationFailed(String),
 }
 
 impl fmt::Display for OutputManagerEvent {
@@ -364,6 +593,22 @@ impl fmt::Display for OutputManagerEvent {
             OutputManagerEvent::TxoValidationAlreadyBusy(tx) => {
                 write!(f, "Txo is already running, stopping {}", tx)
             },
+            OutputManagerEvent::OutputsConsolidated {
+                tx_id,
+                num_inputs,
+                amount,
+                fee,
+                ..
+            } => {
+                write!(
+                    f,
+                    "OutputsConsolidated: {} outputs totalling {} with fee {} (tx_id={})",
+                    num_inputs, amount, fee, tx_id
+                )
+            },
+            OutputManagerEvent::OutputConsolidationFailed(reason) => {
+                write!(f, "OutputConsolidationFailed: {}", reason)
+            },
         }
     }
 }
@@ -746,6 +991,22 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Creates a transaction that merges small outputs into a single output, if the automatic consolidation policy
+    /// finds enough of them and the network fee rate is low enough. The inputs stay encumbered until the transaction
+    /// is cancelled.
+    pub async fn create_consolidation_transaction(
+        &mut self,
+    ) -> Result<Option<(TxId, Transaction, MicroMinotari)>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreateConsolidationTransaction)
+            .await??
+        {
+            OutputManagerResponse::ConsolidationTransaction(result) => Ok(result),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn create_htlc_refund_transaction(
         &mut self,
         output: HashOutput,
@@ -761,6 +1022,30 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Creates a transaction that claims an adaptor signature swap output sent to this wallet, by completing the
+    /// adaptor signature with the adaptor secret
+    pub async fn create_claim_adaptor_swap_transaction(
+        &mut self,
+        output_hash: HashOutput,
+        adaptor_signature: AdaptorSignature,
+        adaptor_secret: PrivateKey,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<AdaptorSwapClaim, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreateClaimAdaptorSwapTransaction {
+                output_hash,
+                adaptor_signature: Box::new(adaptor_signature),
+                adaptor_secret,
+                fee_per_gram,
+            })
+            .await??
+        {
+            OutputManagerResponse::ClaimAdaptorSwapTransaction(claim) => Ok(*claim),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn create_claim_sha_atomic_swap_transaction(
         &mut self,
         output: HashOutput,
@@ -838,6 +1123,30 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Creates a transaction paying the provided outputs, which must already be signed by their sender offset keys,
+    /// from this wallet's outputs plus change. Returns the transaction id, the transaction and the fee.
+    pub async fn create_batch_transaction(
+        &mut self,
+        outputs: Vec<(WalletOutput, TariKeyId)>,
+        fee_per_gram: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        payment_id: PaymentId,
+    ) -> Result<(TxId, Transaction, MicroMinotari), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreateBatchTransaction {
+                outputs,
+                fee_per_gram,
+                selection_criteria,
+                payment_id,
+            })
+            .await??
+        {
+            OutputManagerResponse::Transaction(result) => Ok(result),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     #[allow(clippy::mutable_key_type)]
     pub async fn encumber_aggregate_utxo(
         &mut self,
@@ -968,6 +1277,305 @@ impl OutputManagerHandle {
         }
     }
 
+    /// Returns the public key of this wallet's share of the named multi-signature account
+    pub async fn get_multisig_account_key(&mut self, name: String) -> Result<PublicKey, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetMultisigAccountKey(name))
+            .await??
+        {
+            OutputManagerResponse::MultisigAccountKey(key) => Ok(key),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Creates an N-of-M multi-signature account. One of `public_keys` must be the key returned by
+    /// `get_multisig_account_key` for the account name. The coordinator, who holds the first key, creates the account
+    /// first and receives the view key shares of the other parties, keyed by their public keys. The other parties
+    /// create the account with their share.
+    pub async fn create_multisig_account(
+        &mut self,
+        name: String,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        view_key_share: Option<PrivateKey>,
+    ) -> Result<(MultisigAccount, Vec<(PublicKey, PrivateKey)>), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreateMultisigAccount {
+                name,
+                threshold,
+                public_keys,
+                view_key_share,
+            })
+            .await??
+        {
+            OutputManagerResponse::MultisigAccountCreated(account, view_key_shares) => Ok((account, view_key_shares)),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_multisig_accounts(&mut self) -> Result<Vec<MultisigAccount>, OutputManagerError> {
+        match self.handle.call(OutputManagerRequest::GetMultisigAccounts).await?? {
+            OutputManagerResponse::MultisigAccounts(accounts) => Ok(accounts),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_multisig_outputs(
+        &mut self,
+        account_name: String,
+    ) -> Result<Vec<MultisigOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetMultisigOutputs(account_name))
+            .await??
+        {
+            OutputManagerResponse::MultisigOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Checks the outputs of a multi-signature account against the base node and marks those that have been spent.
+    /// Returns the account's outputs.
+    pub async fn validate_multisig_outputs(
+        &mut self,
+        account_name: String,
+    ) -> Result<Vec<MultisigOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::ValidateMultisigOutputs(account_name))
+            .await??
+        {
+            OutputManagerResponse::MultisigOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Scans the outputs mined at `mined_height` for payments to this wallet's multi-signature accounts. Returns the
+    /// outputs found.
+    pub async fn scan_outputs_for_multisig_accounts(
+        &mut self,
+        outputs: Vec<TransactionOutput>,
+        mined_height: u64,
+    ) -> Result<Vec<MultisigOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::ScanOutputsForMultisigAccounts(
+                outputs,
+                mined_height,
+            ))
+            .await??
+        {
+            OutputManagerResponse::MultisigOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Releases the inputs spent by the unmined transaction `tx_id` so that they can be spent again by a replacement
+    /// transaction paying `amount` at the higher `fee_per_gram`. Returns the commitments of the released inputs and the
+    /// named wallet account they belong to, if any.
+    pub async fn release_transaction_inputs(
+        &mut self,
+        tx_id: TxId,
+        amount: MicroMinotari,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<(Vec<Commitment>, Option<String>), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::ReleaseTransactionInputs {
+                tx_id,
+                amount,
+                fee_per_gram,
+            })
+            .await??
+        {
+            OutputManagerResponse::TransactionInputsReleased(released) => Ok(released),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Encumbers the inputs released by `release_transaction_inputs` to the pending transaction again, for when its
+    /// replacement could not be created
+    pub async fn restore_released_transaction_inputs(
+        &mut self,
+        tx_id: TxId,
+        input_commitments: Vec<Commitment>,
+    ) -> Result<(), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::RestoreReleasedTransactionInputs {
+                tx_id,
+                input_commitments,
+            })
+            .await??
+        {
+            OutputManagerResponse::ReleasedTransactionInputsRestored => Ok(()),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Selects and encumbers the inputs for a one-sided transaction of `amount` that will be signed by an offline
+    /// wallet. Returns the inputs with their commitment masks, the fee and the change value.
+    pub async fn prepare_unsigned_transaction(
+        &mut self,
+        tx_id: TxId,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<(Vec<OfflineWalletOutput>, MicroMinotari, MicroMinotari), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::PrepareUnsignedTransaction {
+                tx_id,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+            })
+            .await??
+        {
+            OutputManagerResponse::UnsignedTransactionPrepared(prepared) => Ok(prepared),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Builds the sender transaction protocol for an unsigned transaction exported by a view-only wallet. Returns the
+    /// protocol and the change output with its commitment mask.
+    pub async fn prepare_to_sign_unsigned_transaction(
+        &mut self,
+        unsigned_transaction: UnsignedTransaction,
+    ) -> Result<(SenderTransactionProtocol, Option<OfflineWalletOutput>), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::PrepareToSignUnsignedTransaction(Box::new(
+                unsigned_transaction,
+            )))
+            .await??
+        {
+            OutputManagerResponse::UnsignedTransactionReadyToSign((stp, change_output)) => {
+                Ok((*stp, change_output.map(|o| *o)))
+            },
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Checks the inputs of a signed transaction against the ones encumbered for `tx_id` and adds its change output,
+    /// which must be one of `output_commitments`
+    pub async fn import_signed_transaction_outputs(
+        &mut self,
+        tx_id: TxId,
+        input_commitments: Vec<Commitment>,
+        output_commitments: Vec<Commitment>,
+        change_output: Option<OfflineWalletOutput>,
+    ) -> Result<(), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::ImportSignedTransactionOutputs {
+                tx_id,
+                input_commitments,
+                output_commitments,
+                change_output: change_output.map(Box::new),
+            })
+            .await??
+        {
+            OutputManagerResponse::SignedTransactionOutputsImported => Ok(()),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Creates a named account, with its own keys derived from the wallet seed. Returns the account and its one-sided
+    /// address.
+    pub async fn create_wallet_account(
+        &mut self,
+        name: String,
+    ) -> Result<(WalletAccount, TariAddress), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::CreateWalletAccount(name))
+            .await??
+        {
+            OutputManagerResponse::WalletAccountCreated(account) => Ok(account),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the named accounts of this wallet and their one-sided addresses
+    pub async fn get_wallet_accounts(&mut self) -> Result<Vec<(WalletAccount, TariAddress)>, OutputManagerError> {
+        match self.handle.call(OutputManagerRequest::GetWalletAccounts).await?? {
+            OutputManagerResponse::WalletAccounts(accounts) => Ok(accounts),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_wallet_account_balance(&mut self, name: String) -> Result<Balance, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetWalletAccountBalance(name))
+            .await??
+        {
+            OutputManagerResponse::Balance(b) => Ok(b),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_wallet_account_outputs(
+        &mut self,
+        name: String,
+    ) -> Result<Vec<DbWalletOutput>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetWalletAccountOutputs(name))
+            .await??
+        {
+            OutputManagerResponse::UnspentOutputs(outputs) => Ok(outputs),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the ids of the transactions that paid to or spent from a named account
+    pub async fn get_wallet_account_tx_ids(&mut self, name: String) -> Result<Vec<TxId>, OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::GetWalletAccountTxIds(name))
+            .await??
+        {
+            OutputManagerResponse::WalletAccountTxIds(tx_ids) => Ok(tx_ids),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Sets the user label of outputs, or clears it if `label` is `None`
+    pub async fn set_outputs_label(
+        &mut self,
+        commitments: Vec<Commitment>,
+        label: Option<String>,
+    ) -> Result<(), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::SetOutputsLabel { commitments, label })
+            .await??
+        {
+            OutputManagerResponse::OutputsUpdated => Ok(()),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Freezes or unfreezes outputs. Frozen outputs are never selected as transaction inputs.
+    pub async fn set_outputs_frozen(
+        &mut self,
+        commitments: Vec<Commitment>,
+        frozen: bool,
+    ) -> Result<(), OutputManagerError> {
+        match self
+            .handle
+            .call(OutputManagerRequest::SetOutputsFrozen { commitments, frozen })
+            .await??
+        {
+            OutputManagerResponse::OutputsUpdated => Ok(()),
+            _ => Err(OutputManagerError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn get_output_info_for_tx_id(&mut self, tx_id: TxId) -> Result<OutputInfoByTxId, OutputManagerError> {
         match self
             .handle
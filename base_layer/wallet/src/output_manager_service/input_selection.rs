@@ -42,6 +42,11 @@ pub struct UtxoSelectionCriteria {
     pub excluding: Vec<Commitment>,
     pub min_dust: u64,
     pub excluding_onesided: bool,
+    /// The named wallet account to spend from. Outputs that do not belong to any named account are selected if this is
+    /// `None`.
+    pub account: Option<String>,
+    /// Only select outputs carrying this user label
+    pub label: Option<String>,
 }
 
 impl UtxoSelectionCriteria {
@@ -71,11 +76,28 @@ impl UtxoSelectionCriteria {
             ..Default::default()
         }
     }
+
+    pub fn with_account(mut self, account: Option<String>) -> Self {
+        self.account = account;
+        self
+    }
+
+    pub fn with_label(mut self, label: Option<String>) -> Self {
+        self.label = label;
+        self
+    }
 }
 
 impl Display for UtxoSelectionCriteria {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        write!(f, "filter: {}, ordering: {}", self.filter, self.ordering)
+        write!(f, "filter: {}, ordering: {}", self.filter, self.ordering)?;
+        if let Some(account) = &self.account {
+            write!(f, ", account: {}", account)?;
+        }
+        if let Some(label) = &self.label {
+            write!(f, ", label: {}", label)?;
+        }
+        Ok(())
     }
 }
 
@@ -33,10 +33,11 @@ pub mod service;
 pub mod storage;
 mod tasks;
 
-use std::marker::PhantomData;
+use std::{marker::PhantomData, sync::Arc};
 
 use futures::future;
 use log::*;
+use tari_common_types::wallet_types::WalletType;
 use tari_core::{
     consensus::NetworkConsensus,
     transactions::{
@@ -76,6 +77,7 @@ where T: OutputManagerBackend
     backend: Option<T>,
     factories: CryptoFactories,
     network: NetworkConsensus,
+    wallet_type: Arc<WalletType>,
     phantom: PhantomData<TKeyManagerInterface>,
 }
 
@@ -87,12 +89,14 @@ where T: OutputManagerBackend + 'static
         backend: T,
         factories: CryptoFactories,
         network: NetworkConsensus,
+        wallet_type: Arc<WalletType>,
     ) -> Self {
         Self {
             config,
             backend: Some(backend),
             factories,
             network,
+            wallet_type,
             phantom: PhantomData,
         }
     }
@@ -120,6 +124,7 @@ where
         let config = self.config.clone();
         let constants = self.network.create_consensus_constants().pop().unwrap();
         let network = self.network.as_network();
+        let wallet_type = self.wallet_type.clone();
         context.spawn_when_ready(move |handles| async move {
             let base_node_service_handle = handles.expect_handle::<BaseNodeServiceHandle>();
             let connectivity = handles.expect_handle::<WalletConnectivityHandle>();
@@ -137,6 +142,7 @@ where
                 network,
                 connectivity,
                 key_manager,
+                wallet_type,
             )
             .await
             .expect("Could not initialize Output Manager Service")
@@ -20,7 +20,9 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use tari_common_types::tari_address::TariAddress;
+use std::sync::Arc;
+
+use tari_common_types::{tari_address::TariAddress, wallet_types::WalletType};
 use tari_core::{consensus::ConsensusConstants, transactions::CryptoFactories};
 use tari_shutdown::ShutdownSignal;
 
@@ -43,4 +45,5 @@ pub(crate) struct OutputManagerResources<TBackend, TWalletConnectivity, TKeyMana
     pub shutdown_signal: ShutdownSignal,
     pub interactive_tari_address: TariAddress,
     pub one_sided_tari_address: TariAddress,
+    pub wallet_type: Arc<WalletType>,
 }
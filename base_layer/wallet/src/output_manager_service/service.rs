@@ -19,8 +19,9 @@
 // SERVICES; LOSS OF USE, DATA, OR PROFITS; OR BUSINESS INTERRUPTION) HOWEVER CAUSED AND ON ANY THEORY OF LIABILITY,
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
-use std::{collections::HashMap, convert::TryInto, fmt, sync::Arc};
+use std::{collections::HashMap, convert::TryInto, fmt, sync::Arc, time::Duration};
 
+use chrono::Utc;
 use diesel::result::{DatabaseErrorKind, Error as DieselError};
 use futures::{pin_mut, StreamExt};
 use log::*;
@@ -31,6 +32,7 @@ use tari_common_types::{
     tari_address::{TariAddress, TariAddressFeatures},
     transaction::TxId,
     types::{BlockHash, Commitment, HashOutput, PrivateKey, PublicKey},
+    wallet_types::WalletType,
 };
 use tari_comms::types::CommsDHKE;
 use tari_core::{
@@ -42,10 +44,10 @@ use tari_core::{
         shared_secret_to_output_encryption_key,
         shared_secret_to_output_spending_key,
     },
-    proto::base_node::FetchMatchingUtxos,
+    proto::base_node::{FetchMatchingUtxos, GetMempoolFeePerGramStatsRequest},
     transactions::{
         fee::Fee,
-        key_manager::{TariKeyId, TransactionKeyManagerInterface},
+        key_manager::{SecretTransactionKeyManagerInterface, TariKeyId, TransactionKeyManagerInterface},
         tari_amount::MicroMinotari,
         transaction_components::{
             encrypted_data::PaymentId,
@@ -60,13 +62,21 @@ use tari_core::{
             WalletOutput,
             WalletOutputBuilder,
         },
-        transaction_protocol::{sender::TransactionSenderMessage, TransactionMetadata},
+        transaction_protocol::{
+            adaptor_signature::{AdaptorSignature, AdaptorSwapScript},
+            sender::TransactionSenderMessage,
+            TransactionMetadata,
+        },
         CryptoFactories,
         ReceiverTransactionProtocol,
         SenderTransactionProtocol,
     },
 };
-use tari_crypto::{commitment::HomomorphicCommitmentFactory, ristretto::pedersen::PedersenCommitment};
+use tari_crypto::{
+    commitment::HomomorphicCommitmentFactory,
+    keys::{PublicKey as PublicKeyTrait, SecretKey},
+    ristretto::pedersen::PedersenCommitment,
+};
 use tari_key_manager::key_manager_service::{KeyAndId, KeyId, SerializedKeyString};
 use tari_script::{
     inputs,
@@ -77,6 +87,7 @@ use tari_script::{
     Opcode,
     StackItem,
     TariScript,
+    MAX_MULTISIG_LIMIT,
 };
 use tari_service_framework::reply_channel;
 use tari_shutdown::ShutdownSignal;
@@ -90,6 +101,7 @@ use crate::{
         config::OutputManagerServiceConfig,
         error::{OutputManagerError, OutputManagerProtocolError, OutputManagerStorageError},
         handle::{
+            AdaptorSwapClaim,
             OutputManagerEvent,
             OutputManagerEventSender,
             OutputManagerRequest,
@@ -101,16 +113,26 @@ use crate::{
         resources::OutputManagerResources,
         storage::{
             database::{OutputBackendQuery, OutputManagerBackend, OutputManagerDatabase},
-            models::{DbWalletOutput, KnownOneSidedPaymentScript, SpendingPriority},
+            models::{
+                DbWalletOutput,
+                KnownOneSidedPaymentScript,
+                MultisigAccount,
+                MultisigOutput,
+                SpendingPriority,
+                WalletAccount,
+            },
             OutputSource,
             OutputStatus,
         },
         tasks::TxoValidationTask,
         TRANSACTION_INPUTS_LIMIT,
     },
+    transaction_service::offline_signing::{OfflineWalletOutput, UnsignedTransaction},
 };
 
 const LOG_TARGET: &str = "wallet::output_manager_service";
+/// How long the automatic consolidation policy waits for a base node connection to query the fee rate
+const CONSOLIDATION_BASE_NODE_TIMEOUT: Duration = Duration::from_secs(30);
 
 /// This service will manage a wallet's available outputs and the key manager that produces the keys for these outputs.
 /// The service will assemble transactions to be sent from the wallets available outputs and provide keys to receive
@@ -130,7 +152,7 @@ impl<TBackend, TWalletConnectivity, TKeyManagerInterface>
 where
     TBackend: OutputManagerBackend + 'static,
     TWalletConnectivity: WalletConnectivityInterface,
-    TKeyManagerInterface: TransactionKeyManagerInterface,
+    TKeyManagerInterface: TransactionKeyManagerInterface + SecretTransactionKeyManagerInterface,
 {
     pub async fn new(
         config: OutputManagerServiceConfig,
@@ -147,6 +169,7 @@ where
         network: Network,
         connectivity: TWalletConnectivity,
         key_manager: TKeyManagerInterface,
+        wallet_type: Arc<WalletType>,
     ) -> Result<Self, OutputManagerError> {
         let view_key = key_manager.get_view_key().await?;
         let spend_key = key_manager.get_spend_key().await?;
@@ -175,6 +198,7 @@ where
             shutdown_signal,
             one_sided_tari_address,
             interactive_tari_address,
+            wallet_type,
         };
 
         Ok(Self {
@@ -237,6 +261,9 @@ where
         request: OutputManagerRequest,
     ) -> Result<OutputManagerResponse, OutputManagerError> {
         trace!(target: LOG_TARGET, "Handling Service Request: {}", request);
+        if request.spends_funds() && self.resources.wallet_type.is_watch_only() {
+            return Err(OutputManagerError::WatchOnlyWallet);
+        }
         match request {
             OutputManagerRequest::AddOutput((uo, spend_priority)) => self
                 .add_output(None, *uo, spend_priority)
@@ -452,6 +479,10 @@ where
                 .create_coin_join(commitments, fee_per_gram)
                 .await
                 .map(OutputManagerResponse::Transaction),
+            OutputManagerRequest::CreateConsolidationTransaction => self
+                .consolidate_outputs()
+                .await
+                .map(OutputManagerResponse::ConsolidationTransaction),
 
             OutputManagerRequest::ScanForRecoverableOutputs(outputs) => {
                 StandardUtxoRecoverer::new(self.resources.key_manager.clone(), self.resources.db.clone())
@@ -489,10 +520,28 @@ where
                     tx_id,
                 })
             },
+            OutputManagerRequest::CreateBatchTransaction {
+                outputs,
+                fee_per_gram,
+                selection_criteria,
+                payment_id,
+            } => self
+                .create_batch_transaction(outputs, selection_criteria, fee_per_gram, payment_id)
+                .await
+                .map(OutputManagerResponse::Transaction),
             OutputManagerRequest::CreateClaimShaAtomicSwapTransaction(output_hash, pre_image, fee_per_gram) => {
                 self.claim_sha_atomic_swap_with_hash(output_hash, pre_image, fee_per_gram)
                     .await
             },
+            OutputManagerRequest::CreateClaimAdaptorSwapTransaction {
+                output_hash,
+                adaptor_signature,
+                adaptor_secret,
+                fee_per_gram,
+            } => {
+                self.claim_adaptor_swap_with_hash(output_hash, *adaptor_signature, adaptor_secret, fee_per_gram)
+                    .await
+            },
             OutputManagerRequest::CreateHtlcRefundTransaction(output, fee_per_gram) => self
                 .create_htlc_refund_transaction(output, fee_per_gram)
                 .await
@@ -501,6 +550,197 @@ where
                 let output_statuses_by_tx_id = self.get_output_info_by_tx_id(tx_id)?;
                 Ok(OutputManagerResponse::OutputInfoByTxId(output_statuses_by_tx_id))
             },
+            OutputManagerRequest::GetMultisigAccountKey(name) => Ok(OutputManagerResponse::MultisigAccountKey(
+                self.resources
+                    .key_manager
+                    .get_public_key_at_key_id(&multisig_account_key_id(&name))
+                    .await?,
+            )),
+            OutputManagerRequest::CreateMultisigAccount {
+                name,
+                threshold,
+                public_keys,
+                view_key_share,
+            } => self
+                .create_multisig_account(name, threshold, public_keys, view_key_share)
+                .await
+                .map(|(account, view_key_shares)| {
+                    OutputManagerResponse::MultisigAccountCreated(account, view_key_shares)
+                }),
+            OutputManagerRequest::GetMultisigAccounts => Ok(OutputManagerResponse::MultisigAccounts(
+                self.resources.db.get_multisig_accounts()?,
+            )),
+            OutputManagerRequest::GetMultisigOutputs(account_name) => Ok(OutputManagerResponse::MultisigOutputs(
+                self.resources.db.get_multisig_outputs(&account_name)?,
+            )),
+            OutputManagerRequest::ValidateMultisigOutputs(account_name) => self
+                .validate_multisig_outputs(account_name)
+                .await
+                .map(OutputManagerResponse::MultisigOutputs),
+            OutputManagerRequest::ScanOutputsForMultisigAccounts(outputs, mined_height) => self
+                .scan_outputs_for_multisig_accounts(outputs, mined_height)
+                .await
+                .map(OutputManagerResponse::MultisigOutputs),
+            OutputManagerRequest::ReleaseTransactionInputs {
+                tx_id,
+                amount,
+                fee_per_gram,
+            } => self
+                .release_transaction_inputs(tx_id, amount, fee_per_gram)
+                .map(OutputManagerResponse::TransactionInputsReleased),
+            OutputManagerRequest::RestoreReleasedTransactionInputs {
+                tx_id,
+                input_commitments,
+            } => self
+                .restore_released_transaction_inputs(tx_id, input_commitments)
+                .map(|_| OutputManagerResponse::ReleasedTransactionInputsRestored),
+            OutputManagerRequest::PrepareUnsignedTransaction {
+                tx_id,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+            } => self
+                .prepare_unsigned_transaction(tx_id, amount, selection_criteria, fee_per_gram)
+                .await
+                .map(OutputManagerResponse::UnsignedTransactionPrepared),
+            OutputManagerRequest::PrepareToSignUnsignedTransaction(unsigned_transaction) => self
+                .prepare_to_sign_unsigned_transaction(*unsigned_transaction)
+                .await
+                .map(|(stp, change_output)| {
+                    OutputManagerResponse::UnsignedTransactionReadyToSign((Box::new(stp), change_output.map(Box::new)))
+                }),
+            OutputManagerRequest::ImportSignedTransactionOutputs {
+                tx_id,
+                input_commitments,
+                output_commitments,
+                change_output,
+            } => self
+                .import_signed_transaction_outputs(
+                    tx_id,
+                    input_commitments,
+                    output_commitments,
+                    change_output.map(|o| *o),
+                )
+                .await
+                .map(|_| OutputManagerResponse::SignedTransactionOutputsImported),
+            OutputManagerRequest::CreateWalletAccount(name) => self
+                .create_wallet_account(name)
+                .await
+                .map(OutputManagerResponse::WalletAccountCreated),
+            OutputManagerRequest::GetWalletAccounts => {
+                let mut accounts = Vec::new();
+                for account in self.resources.db.get_wallet_accounts()? {
+                    let address = self.get_wallet_account_address(&account).await?;
+                    accounts.push((account, address));
+                }
+                Ok(OutputManagerResponse::WalletAccounts(accounts))
+            },
+            OutputManagerRequest::GetWalletAccountBalance(name) => {
+                let account = self.get_wallet_account(&name)?;
+                let current_tip_for_time_lock_calculation = match self.base_node_service.get_chain_metadata().await {
+                    Ok(metadata) => metadata.map(|m| m.best_block_height()),
+                    Err(_) => None,
+                };
+                Ok(OutputManagerResponse::Balance(self.resources.db.get_account_balance(
+                    &account.name,
+                    current_tip_for_time_lock_calculation,
+                )?))
+            },
+            OutputManagerRequest::GetWalletAccountOutputs(name) => {
+                let account = self.get_wallet_account(&name)?;
+                Ok(OutputManagerResponse::UnspentOutputs(
+                    self.resources.db.fetch_unspent_account_outputs(&account.name)?,
+                ))
+            },
+            OutputManagerRequest::GetWalletAccountTxIds(name) => {
+                let account = self.get_wallet_account(&name)?;
+                Ok(OutputManagerResponse::WalletAccountTxIds(
+                    self.resources.db.fetch_account_tx_ids(&account.name)?,
+                ))
+            },
+            OutputManagerRequest::SetOutputsLabel { commitments, label } => {
+                let label = label.filter(|l| !l.is_empty());
+                self.resources.db.set_outputs_label(commitments, label.as_deref())?;
+                Ok(OutputManagerResponse::OutputsUpdated)
+            },
+            OutputManagerRequest::SetOutputsFrozen { commitments, frozen } => {
+                self.resources.db.set_outputs_frozen(commitments, frozen)?;
+                Ok(OutputManagerResponse::OutputsUpdated)
+            },
+        }
+    }
+
+    /// Creates a named account with the next unused keys on the account key branches
+    async fn create_wallet_account(
+        &mut self,
+        name: String,
+    ) -> Result<(WalletAccount, TariAddress), OutputManagerError> {
+        if !matches!(*self.resources.wallet_type, WalletType::DerivedKeys) {
+            return Err(OutputManagerError::WalletAccountsNotSupported);
+        }
+        let account_index = self
+            .resources
+            .db
+            .get_wallet_accounts()?
+            .iter()
+            .map(|account| account.account_index + 1)
+            .max()
+            .unwrap_or_default();
+        let account = WalletAccount {
+            name,
+            account_index,
+            created_at: Utc::now(),
+        };
+        self.resources.db.add_wallet_account(account.clone())?;
+        let address = self.get_wallet_account_address(&account).await?;
+        info!(
+            target: LOG_TARGET,
+            "Created wallet account '{}' with address {}", account.name, address
+        );
+        Ok((account, address))
+    }
+
+    fn get_wallet_account(&self, name: &str) -> Result<WalletAccount, OutputManagerError> {
+        match self.resources.db.get_wallet_account(name) {
+            Ok(account) => Ok(account),
+            Err(OutputManagerStorageError::ValueNotFound) => {
+                Err(OutputManagerError::WalletAccountNotFound(name.to_string()))
+            },
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The one-sided address of a named account
+    async fn get_wallet_account_address(&self, account: &WalletAccount) -> Result<TariAddress, OutputManagerError> {
+        let view_key = self
+            .resources
+            .key_manager
+            .get_public_key_at_key_id(&account.view_key_id())
+            .await?;
+        let spend_key = self
+            .resources
+            .key_manager
+            .get_public_key_at_key_id(&account.spend_key_id())
+            .await?;
+        Ok(TariAddress::new_dual_address(
+            view_key,
+            spend_key,
+            self.resources.one_sided_tari_address.network(),
+            TariAddressFeatures::create_one_sided_only(),
+        ))
+    }
+
+    /// Assigns outputs created by a transaction that spends from a named account to that account
+    fn assign_outputs_to_account(
+        &self,
+        account_name: Option<&str>,
+        commitments: Vec<Commitment>,
+    ) -> Result<(), OutputManagerError> {
+        match account_name {
+            Some(account_name) if !commitments.is_empty() => {
+                Ok(self.resources.db.set_outputs_account(commitments, account_name)?)
+            },
+            _ => Ok(()),
         }
     }
 
@@ -982,6 +1222,11 @@ where
             selection_criteria,
             fee_per_gram,
         );
+        // Funds sent to a script that can never succeed are lost
+        let analysis = recipient_script.analyze();
+        if !analysis.can_succeed() {
+            return Err(OutputManagerError::UnspendableScript(analysis.to_string()));
+        }
         let features_and_scripts_byte_size = self
             .resources
             .consensus_constants
@@ -998,6 +1243,7 @@ where
                         .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
             );
 
+        let account_name = selection_criteria.account.clone();
         let input_selection = self
             .select_utxos(
                 amount,
@@ -1080,11 +1326,14 @@ where
             );
         }
 
+        let account_outputs = change_output.iter().map(|o| o.commitment.clone()).collect();
+
         // The Transaction Protocol built successfully so we will pull the unspent outputs out of the unspent list and
         // store them until the transaction times out OR is confirmed
         self.resources
             .db
             .encumber_outputs(tx_id, input_selection.into_selected(), change_output)?;
+        self.assign_outputs_to_account(account_name.as_deref(), account_outputs)?;
 
         debug!(target: LOG_TARGET, "Prepared transaction (TxId: {}) to send", tx_id);
 
@@ -1123,6 +1372,7 @@ where
             features_and_scripts_byte_size += weighting.round_up_features_and_scripts_size(features + covenant + script)
         }
 
+        let account_name = selection_criteria.account.clone();
         let input_selection = self
             .select_utxos(
                 total_value,
@@ -1213,14 +1463,135 @@ where
             );
         }
 
+        let account_outputs = db_outputs.iter().map(|o| o.commitment.clone()).collect();
         self.resources
             .db
             .encumber_outputs(tx_id, input_selection.into_selected(), db_outputs)?;
+        self.assign_outputs_to_account(account_name.as_deref(), account_outputs)?;
         stp.finalize(&self.resources.key_manager).await?;
 
         Ok((tx_id, stp.into_transaction()?))
     }
 
+    /// Creates a transaction paying the provided outputs, each with the sender offset key it was signed with, from
+    /// the wallet's outputs plus change
+    async fn create_batch_transaction(
+        &mut self,
+        outputs: Vec<(WalletOutput, TariKeyId)>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    ) -> Result<(TxId, Transaction, MicroMinotari), OutputManagerError> {
+        if outputs.is_empty() {
+            return Err(OutputManagerError::InvalidArgument(
+                "A batch transaction needs at least one output".to_string(),
+            ));
+        }
+        let total_value = outputs
+            .iter()
+            .try_fold(MicroMinotari::zero(), |total, (o, _)| total.checked_add(o.value))
+            .ok_or_else(|| {
+                OutputManagerError::InvalidArgument("The total value of the batch outputs overflows".to_string())
+            })?;
+        let weighting = self.resources.consensus_constants.transaction_weight_params();
+        let mut features_and_scripts_byte_size = 0;
+        for (output, _) in &outputs {
+            let size = output
+                .features
+                .get_serialized_size()
+                .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                output
+                    .covenant
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                output
+                    .script
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?;
+            features_and_scripts_byte_size += weighting.round_up_features_and_scripts_size(size);
+        }
+
+        let account_name = selection_criteria.account.clone();
+        let input_selection = self
+            .select_utxos(
+                total_value,
+                selection_criteria,
+                fee_per_gram,
+                outputs.len(),
+                features_and_scripts_byte_size,
+            )
+            .await?;
+
+        let mut builder = SenderTransactionProtocol::builder(
+            self.resources.consensus_constants.clone(),
+            self.resources.key_manager.clone(),
+        );
+        builder
+            .with_lock_height(0)
+            .with_fee_per_gram(fee_per_gram)
+            .with_kernel_features(KernelFeatures::empty())
+            .with_payment_id(payment_id);
+
+        for uo in input_selection.iter() {
+            builder.with_input(uo.wallet_output.clone()).await?;
+        }
+
+        if input_selection.requires_change_output() {
+            let (change_commitment_mask_key, change_script_key) = self
+                .resources
+                .key_manager
+                .get_next_commitment_mask_and_script_key()
+                .await?;
+            builder.with_change_data(
+                script!(PushPubKey(Box::new(change_script_key.pub_key)))?,
+                ExecutionStack::default(),
+                change_script_key.key_id,
+                change_commitment_mask_key.key_id,
+                Covenant::default(),
+                self.resources.interactive_tari_address.clone(),
+            );
+        }
+
+        for (output, sender_offset_key_id) in outputs {
+            builder
+                .with_output(output, sender_offset_key_id)
+                .await
+                .map_err(|e| OutputManagerError::BuildError(e.to_string()))?;
+        }
+
+        let mut stp = builder
+            .build()
+            .await
+            .map_err(|e| OutputManagerError::BuildError(e.message))?;
+        let tx_id = stp.get_tx_id()?;
+        let fee = stp.get_fee_amount()?;
+
+        let mut change_outputs = Vec::new();
+        if let Some(wallet_output) = stp.get_change_output()? {
+            change_outputs.push(
+                DbWalletOutput::from_wallet_output(
+                    wallet_output,
+                    &self.resources.key_manager,
+                    None,
+                    OutputSource::default(),
+                    Some(tx_id),
+                    None,
+                )
+                .await?,
+            );
+        }
+
+        let account_outputs = change_outputs.iter().map(|o| o.commitment.clone()).collect();
+        self.resources
+            .db
+            .encumber_outputs(tx_id, input_selection.into_selected(), change_outputs)?;
+        self.assign_outputs_to_account(account_name.as_deref(), account_outputs)?;
+        self.confirm_encumberance(tx_id)?;
+        stp.finalize(&self.resources.key_manager).await?;
+
+        Ok((tx_id, stp.into_transaction()?, fee))
+    }
+
     async fn pre_mine_script_key_from_payment_id(
         &self,
         payment_id: PaymentId,
@@ -1300,24 +1671,50 @@ where
             )));
         }
         trace!(target: LOG_TARGET, "encumber_aggregate_utxo: fetched outputs");
+        let output_hash = output.hash();
+        let multisig_account = self
+            .resources
+            .db
+            .get_multisig_accounts()?
+            .into_iter()
+            .find(|account| account.is_output_script(&output.script, &output.commitment));
         // Retrieve the list of n public keys from the script
         let (multi_sig_public_keys, threshold) = get_multi_sig_script_components(&output.script, tx_id)?;
         trace!(target: LOG_TARGET, "encumber_aggregate_utxo: retrieved public keys from script");
-        // Create a deterministic encryption key from the sum of the public keys
-        let sum_public_keys = multi_sig_public_keys
-            .iter()
-            .fold(tari_common_types::types::PublicKey::default(), |acc, x| acc + x);
-        let encryption_private_key = public_key_to_output_encryption_key(&sum_public_keys)?;
+        let encryption_private_key = match multisig_account {
+            Some(ref account) => {
+                self.multisig_output_encryption_key(account, &output.sender_offset_public_key)
+                    .await?
+            },
+            None => {
+                // Create a deterministic encryption key from the sum of the public keys
+                let sum_public_keys = multi_sig_public_keys
+                    .iter()
+                    .fold(tari_common_types::types::PublicKey::default(), |acc, x| acc + x);
+                public_key_to_output_encryption_key(&sum_public_keys)?
+            },
+        };
         let mut aggregated_script_public_key_shares = PublicKey::default();
-        trace!(target: LOG_TARGET, "encumber_aggregate_utxo: created deterministic encryption key");
+        trace!(target: LOG_TARGET, "encumber_aggregate_utxo: created encryption key");
         // Decrypt the output secrets and create a new input as WalletOutput (unblinded)
         let (input, payment_id) = if let Ok((amount, commitment_mask, payment_id)) =
             EncryptedData::decrypt_data(&encryption_private_key, &output.commitment, &output.encrypted_data)
         {
             if output.verify_mask(&self.resources.factories.range_proof, &commitment_mask, amount.as_u64())? {
-                let script_key = self
-                    .pre_mine_script_key_from_payment_id(payment_id.clone(), tx_id)
-                    .await?;
+                let script_key = match multisig_account {
+                    Some(ref account) => KeyAndId::<PublicKey> {
+                        pub_key: self
+                            .resources
+                            .key_manager
+                            .get_public_key_at_key_id(&account.script_key_id)
+                            .await?,
+                        key_id: account.script_key_id.clone(),
+                    },
+                    None => {
+                        self.pre_mine_script_key_from_payment_id(payment_id.clone(), tx_id)
+                            .await?
+                    },
+                };
                 let mut script_signatures = Vec::new();
                 // lets add our own signature to the list
                 let self_signature = self
@@ -1453,6 +1850,9 @@ where
         let _single_round_sender_data = stp.build_single_round_message(&self.resources.key_manager).await?;
 
         self.confirm_encumberance(tx_id)?;
+        if multisig_account.is_some() {
+            self.resources.db.set_multisig_output_spent_in_tx(output_hash, tx_id)?;
+        }
 
         // Prepare receiver part of the transaction
 
@@ -1926,6 +2326,7 @@ where
                         .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
             );
 
+        let account_name = selection_criteria.account.clone();
         let input_selection = self
             .select_utxos(
                 amount,
@@ -2005,9 +2406,11 @@ where
             "Encumber send to self transaction ({}) outputs.",
             tx_id
         );
+        let account_outputs = outputs.iter().map(|o| o.commitment.clone()).collect();
         self.resources
             .db
             .encumber_outputs(tx_id, input_selection.into_selected(), outputs)?;
+        self.assign_outputs_to_account(account_name.as_deref(), account_outputs)?;
         self.confirm_encumberance(tx_id)?;
         let fee = stp.get_fee_amount()?;
         trace!(target: LOG_TARGET, "Finalize send-to-self transaction ({}).", tx_id);
@@ -2034,46 +2437,360 @@ where
         Ok(self.resources.db.cancel_pending_transaction_outputs(tx_id)?)
     }
 
-    /// Restore the pending transaction encumberance and output for an inbound transaction that was previously
-    /// cancelled.
-    fn reinstate_cancelled_inbound_transaction_outputs(&mut self, tx_id: TxId) -> Result<(), OutputManagerError> {
-        self.resources.db.reinstate_cancelled_inbound_output(tx_id)?;
-
-        Ok(())
-    }
-
-    /// Select which unspent transaction outputs to use to send a transaction of the specified amount. Use the specified
-    /// selection strategy to choose the outputs. It also determines if a change output is required.
-    #[allow(clippy::too_many_lines)]
-    async fn select_utxos(
+    /// Release the inputs of an unmined outbound transaction so that a replacement transaction paying the same `amount`
+    /// at `fee_per_gram` can spend them. The inputs must be able to cover the amount and the new fee, else nothing is
+    /// released.
+    fn release_transaction_inputs(
         &mut self,
+        tx_id: TxId,
         amount: MicroMinotari,
-        mut selection_criteria: UtxoSelectionCriteria,
         fee_per_gram: MicroMinotari,
-        num_outputs: usize,
-        total_output_features_and_scripts_byte_size: usize,
-    ) -> Result<UtxoSelection, OutputManagerError> {
-        let start = Instant::now();
+    ) -> Result<(Vec<Commitment>, Option<String>), OutputManagerError> {
+        let inputs = self
+            .resources
+            .db
+            .fetch_outputs_by_tx_id(tx_id)?
+            .into_iter()
+            .filter(|o| o.spent_in_tx_id == Some(tx_id) && o.status == OutputStatus::EncumberedToBeSpent)
+            .collect::<Vec<_>>();
+        if inputs.is_empty() {
+            return Err(OutputManagerError::ServiceError(format!(
+                "Transaction {} has no pending inputs to release",
+                tx_id
+            )));
+        }
+
+        let fee_calc = self.get_fee_calc();
+        let features_and_scripts_size = fee_calc.weighting().round_up_features_and_scripts_size(
+            OutputFeatures::default()
+                .get_serialized_size()
+                .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                TariScript::default()
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                Covenant::new()
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
+        );
+        let fee = fee_calc.calculate(fee_per_gram, 1, inputs.len(), 2, features_and_scripts_size * 2);
+        let total = inputs.iter().map(|o| o.wallet_output.value).sum::<MicroMinotari>();
+        if total < amount + fee {
+            return Err(OutputManagerError::NotEnoughFunds);
+        }
+
         debug!(
             target: LOG_TARGET,
-            "select_utxos amount: {}, fee_per_gram: {}, num_outputs: {}, output_features_and_scripts_byte_size: {}, \
-             selection_criteria: {:?}",
-            amount,
-            fee_per_gram,
-            num_outputs,
-            total_output_features_and_scripts_byte_size,
-            selection_criteria
+            "Releasing {} inputs of TxId: {} for a replacement transaction",
+            inputs.len(),
+            tx_id
         );
-        let mut utxos = Vec::new();
+        // The inputs of a transaction are all selected from the same account
+        let account_name = self.resources.db.fetch_output_account(&inputs[0].commitment)?;
+        self.resources.db.cancel_pending_transaction_outputs(tx_id)?;
 
-        let fee_calc = self.get_fee_calc();
+        Ok((inputs.into_iter().map(|o| o.commitment).collect(), account_name))
+    }
+
+    /// Undoes `release_transaction_inputs` when the replacement transaction could not be created. The inputs are
+    /// encumbered to the original transaction again, and its change outputs are expected again.
+    fn restore_released_transaction_inputs(
+        &mut self,
+        tx_id: TxId,
+        input_commitments: Vec<Commitment>,
+    ) -> Result<(), OutputManagerError> {
+        let mut inputs = Vec::with_capacity(input_commitments.len());
+        for commitment in input_commitments {
+            let mut input = self.resources.db.fetch_by_commitment(commitment)?;
+            // The failed replacement may have encumbered the inputs before it failed
+            if let Some(replacement_tx_id) = input.spent_in_tx_id.filter(|id| *id != tx_id) {
+                debug!(
+                    target: LOG_TARGET,
+                    "Cancelling failed replacement TxId: {} of TxId: {}", replacement_tx_id, tx_id
+                );
+                self.resources
+                    .db
+                    .cancel_pending_transaction_outputs(replacement_tx_id)?;
+                input = self.resources.db.fetch_by_commitment(input.commitment)?;
+            }
+            inputs.push(input);
+        }
+
+        self.resources.db.encumber_outputs(tx_id, inputs, Vec::new())?;
+        self.resources.db.confirm_encumbered_outputs(tx_id)?;
+        self.resources.db.reinstate_cancelled_inbound_output(tx_id)?;
+        Ok(())
+    }
+
+    /// Select the inputs for a one-sided transaction that will be signed by an offline wallet holding the spend key.
+    /// The selected inputs are encumbered to `tx_id` until the signed transaction is imported again, and are returned
+    /// with their commitment masks together with the fee and change value the offline wallet must use.
+    async fn prepare_unsigned_transaction(
+        &mut self,
+        tx_id: TxId,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<(Vec<OfflineWalletOutput>, MicroMinotari, MicroMinotari), OutputManagerError> {
+        // The offline wallet sends to a stealth address, so a default public key gives the correct script size
+        let features_and_scripts_byte_size = self
+            .resources
+            .consensus_constants
+            .transaction_weight_params()
+            .round_up_features_and_scripts_size(
+                OutputFeatures::default()
+                    .get_serialized_size()
+                    .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    push_pubkey_script(&PublicKey::default())
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))? +
+                    Covenant::default()
+                        .get_serialized_size()
+                        .map_err(|e| OutputManagerError::ConversionError(e.to_string()))?,
+            );
+        let input_selection = self
+            .select_utxos(
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                1,
+                features_and_scripts_byte_size,
+            )
+            .await?;
+
+        // Without a change output the builder pays whatever is left over as fee
+        let change = if input_selection.requires_change_output() {
+            input_selection.total_value() - amount - input_selection.as_final_fee()
+        } else {
+            MicroMinotari::zero()
+        };
+        let fee = input_selection.total_value() - amount - change;
+
+        let mut inputs = Vec::with_capacity(input_selection.num_selected());
+        for uo in input_selection.iter() {
+            let commitment_mask = self
+                .resources
+                .key_manager
+                .get_private_key(&uo.wallet_output.spending_key_id)
+                .await?;
+            inputs.push(OfflineWalletOutput {
+                output: uo.wallet_output.clone(),
+                commitment_mask,
+            });
+        }
+
+        // Signing happens on another device and can take a while, so the encumberance must survive a restart
+        self.resources
+            .db
+            .encumber_outputs(tx_id, input_selection.into_selected(), Vec::new())?;
+        self.resources.db.confirm_encumbered_outputs(tx_id)?;
+        debug!(
+            target: LOG_TARGET,
+            "Prepared unsigned transaction (TxId: {}) with {} inputs", tx_id, inputs.len()
+        );
+
+        Ok((inputs, fee, change))
+    }
+
+    /// Prepare a Sender Transaction Protocol from an unsigned transaction that was exported by a view-only wallet. The
+    /// inputs are spent with this wallet's spend key, so nothing is stored or encumbered here. The change output, if
+    /// any, is returned with its commitment mask so that the view-only wallet can import it.
+    async fn prepare_to_sign_unsigned_transaction(
+        &mut self,
+        unsigned_transaction: UnsignedTransaction,
+    ) -> Result<(SenderTransactionProtocol, Option<OfflineWalletOutput>), OutputManagerError> {
+        let tx_id = unsigned_transaction.tx_id;
+        let mut builder = SenderTransactionProtocol::builder(
+            self.resources.consensus_constants.clone(),
+            self.resources.key_manager.clone(),
+        );
+        builder
+            .with_fee_per_gram(unsigned_transaction.fee_per_gram)
+            .with_recipient_data(
+                push_pubkey_script(&PublicKey::default()),
+                OutputFeatures::default(),
+                Covenant::default(),
+                MicroMinotari::zero(),
+                unsigned_transaction.amount,
+                unsigned_transaction.destination.clone(),
+            )
+            .await?
+            .with_sender_address(self.resources.interactive_tari_address.clone())
+            .with_payment_id(unsigned_transaction.payment_id.clone())
+            .with_prevent_fee_gt_amount(self.resources.config.prevent_fee_gt_amount)
+            .with_kernel_features(KernelFeatures::empty())
+            .with_tx_id(tx_id);
+
+        for input in unsigned_transaction.inputs {
+            let wallet_output = self.import_offline_wallet_output(input).await?;
+            builder.with_input(wallet_output).await?;
+        }
+
+        let (change_commitment_mask_key, change_script_key) = self
+            .resources
+            .key_manager
+            .get_next_commitment_mask_and_script_key()
+            .await?;
+        builder.with_change_data(
+            script!(PushPubKey(Box::new(change_script_key.pub_key.clone())))?,
+            ExecutionStack::default(),
+            change_script_key.key_id,
+            change_commitment_mask_key.key_id.clone(),
+            Covenant::default(),
+            self.resources.interactive_tari_address.clone(),
+        );
+
+        let stp = builder
+            .build()
+            .await
+            .map_err(|e| OutputManagerError::BuildError(e.message))?;
+
+        let fee = stp.get_fee_amount()?;
+        if fee != unsigned_transaction.fee {
+            return Err(OutputManagerError::BuildError(format!(
+                "The transaction fee ({}) does not match the fee of the unsigned transaction ({})",
+                fee, unsigned_transaction.fee
+            )));
+        }
+        let change_output = match stp.get_change_output()? {
+            Some(output) => Some(OfflineWalletOutput {
+                output,
+                commitment_mask: self
+                    .resources
+                    .key_manager
+                    .get_private_key(&change_commitment_mask_key.key_id)
+                    .await?,
+            }),
+            None => None,
+        };
+        if change_output.as_ref().map(|o| o.output.value).unwrap_or_default() != unsigned_transaction.change {
+            return Err(OutputManagerError::BuildError(format!(
+                "The transaction change does not match the change of the unsigned transaction ({})",
+                unsigned_transaction.change
+            )));
+        }
+
+        debug!(target: LOG_TARGET, "Prepared unsigned transaction (TxId: {}) to sign", tx_id);
+        Ok((stp, change_output))
+    }
+
+    /// Check that a transaction signed by an offline wallet spends exactly the inputs that were encumbered for it, and
+    /// add its change output, which must be one of the transaction's outputs, to the outputs to be received.
+    async fn import_signed_transaction_outputs(
+        &mut self,
+        tx_id: TxId,
+        input_commitments: Vec<Commitment>,
+        output_commitments: Vec<Commitment>,
+        change_output: Option<OfflineWalletOutput>,
+    ) -> Result<(), OutputManagerError> {
+        let mut encumbered = self
+            .resources
+            .db
+            .fetch_outputs_by_tx_id(tx_id)?
+            .into_iter()
+            .filter(|o| o.spent_in_tx_id == Some(tx_id) && o.status == OutputStatus::EncumberedToBeSpent)
+            .map(|o| o.commitment)
+            .collect::<Vec<_>>();
+        let mut input_commitments = input_commitments;
+        encumbered.sort();
+        input_commitments.sort();
+        if encumbered.is_empty() || encumbered != input_commitments {
+            return Err(OutputManagerError::ServiceError(format!(
+                "The signed transaction does not spend the inputs prepared for transaction {}",
+                tx_id
+            )));
+        }
+
+        if let Some(change_output) = change_output {
+            let wallet_output = self.import_offline_wallet_output(change_output).await?;
+            let output = DbWalletOutput::from_wallet_output(
+                wallet_output,
+                &self.resources.key_manager,
+                None,
+                OutputSource::default(),
+                Some(tx_id),
+                None,
+            )
+            .await?;
+            if !output_commitments.contains(&output.commitment) {
+                return Err(OutputManagerError::ServiceError(format!(
+                    "The change output is not an output of the signed transaction {}",
+                    tx_id
+                )));
+            }
+            self.resources.db.add_output_to_be_received(tx_id, output)?;
+        }
+
+        Ok(())
+    }
+
+    /// Import the commitment mask of an output created by another wallet and rekey the output to use it
+    async fn import_offline_wallet_output(
+        &self,
+        offline_output: OfflineWalletOutput,
+    ) -> Result<WalletOutput, OutputManagerError> {
+        let OfflineWalletOutput {
+            output: mut wallet_output,
+            commitment_mask,
+        } = offline_output;
+        let commitment_mask_key_id = self.resources.key_manager.import_key(commitment_mask).await?;
+        if wallet_output.script_key_id ==
+            (TariKeyId::Derived {
+                key: SerializedKeyString::from(wallet_output.spending_key_id.to_string()),
+            })
+        {
+            wallet_output.script_key_id = TariKeyId::Derived {
+                key: SerializedKeyString::from(commitment_mask_key_id.to_string()),
+            };
+        }
+        wallet_output.spending_key_id = commitment_mask_key_id;
+        Ok(wallet_output)
+    }
+
+    /// Restore the pending transaction encumberance and output for an inbound transaction that was previously
+    /// cancelled.
+    fn reinstate_cancelled_inbound_transaction_outputs(&mut self, tx_id: TxId) -> Result<(), OutputManagerError> {
+        self.resources.db.reinstate_cancelled_inbound_output(tx_id)?;
+
+        Ok(())
+    }
+
+    /// Select which unspent transaction outputs to use to send a transaction of the specified amount. Use the specified
+    /// selection strategy to choose the outputs. It also determines if a change output is required.
+    #[allow(clippy::too_many_lines)]
+    async fn select_utxos(
+        &mut self,
+        amount: MicroMinotari,
+        mut selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        num_outputs: usize,
+        total_output_features_and_scripts_byte_size: usize,
+    ) -> Result<UtxoSelection, OutputManagerError> {
+        let start = Instant::now();
+        debug!(
+            target: LOG_TARGET,
+            "select_utxos amount: {}, fee_per_gram: {}, num_outputs: {}, output_features_and_scripts_byte_size: {}, \
+             selection_criteria: {:?}",
+            amount,
+            fee_per_gram,
+            num_outputs,
+            total_output_features_and_scripts_byte_size,
+            selection_criteria
+        );
+        let mut utxos = Vec::new();
+
+        let fee_calc = self.get_fee_calc();
 
         // Attempt to get the chain tip height
         let chain_metadata = self.base_node_service.get_chain_metadata().await?;
 
-        // Respecting the setting to not choose outputs that reveal the address
-        if self.resources.config.autoignore_onesided_utxos {
+        // Named wallet accounts only receive one-sided payments, so the setting to not choose outputs that reveal the
+        // address does not apply to them
+        if let Some(account_name) = &selection_criteria.account {
+            self.get_wallet_account(account_name)?;
+        } else if self.resources.config.autoignore_onesided_utxos {
             selection_criteria.excluding_onesided = self.resources.config.autoignore_onesided_utxos;
+        } else {
+            // One-sided outputs may be chosen
         }
 
         debug!(
@@ -2864,6 +3581,106 @@ where
         Ok((tx_id, stp.into_transaction()?, accumulated_amount + fee))
     }
 
+    /// Merges small outputs into a single output when the network fee rate is low, as configured by the automatic
+    /// consolidation policy. The outcome is published on the event stream, and the consolidation transaction is
+    /// returned to the transaction service, which submits it to the network.
+    async fn consolidate_outputs(&mut self) -> Result<Option<(TxId, Transaction, MicroMinotari)>, OutputManagerError> {
+        let result = self.create_consolidation_transaction().await;
+        let event = match &result {
+            Ok(Some((tx_id, transaction, amount))) => OutputManagerEvent::OutputsConsolidated {
+                tx_id: *tx_id,
+                num_inputs: transaction.body.inputs().len(),
+                amount: *amount,
+                fee: transaction.body.get_total_fee().unwrap_or_default(),
+            },
+            Ok(None) => return result,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Automatic output consolidation failed: {}", e);
+                OutputManagerEvent::OutputConsolidationFailed(e.to_string())
+            },
+        };
+        info!(target: LOG_TARGET, "{}", event);
+        if let Err(e) = self.resources.event_publisher.send(Arc::new(event)) {
+            debug!(
+                target: LOG_TARGET,
+                "Error sending event because there are no subscribers: {:?}", e
+            );
+        }
+        result
+    }
+
+    /// Creates a coin join of the smallest spendable outputs if there are enough of them and the fee rate is below the
+    /// configured limit. Frozen outputs, labelled outputs and outputs belonging to named accounts are left alone.
+    async fn create_consolidation_transaction(
+        &mut self,
+    ) -> Result<Option<(TxId, Transaction, MicroMinotari)>, OutputManagerError> {
+        let config = self.resources.config.clone();
+        let tip_height = match self.base_node_service.get_chain_metadata().await? {
+            Some(metadata) => metadata.best_block_height(),
+            None => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Skipping output consolidation, the chain tip is not known yet"
+                );
+                return Ok(None);
+            },
+        };
+
+        let mut selection_criteria = UtxoSelectionCriteria::smallest_first(config.dust_ignore_value);
+        selection_criteria.excluding_onesided = config.autoignore_onesided_utxos;
+        let commitments = self
+            .resources
+            .db
+            .fetch_unspent_outputs_for_spending(&selection_criteria, MicroMinotari::zero(), Some(tip_height))?
+            .into_iter()
+            .filter(|o| {
+                o.label.is_none() && o.wallet_output.value.as_u64() <= config.auto_consolidation_max_output_value
+            })
+            .take(config.auto_consolidation_max_inputs)
+            .map(|o| o.commitment)
+            .collect::<Vec<_>>();
+        if commitments.len() < config.auto_consolidation_min_outputs.max(2) {
+            debug!(
+                target: LOG_TARGET,
+                "Skipping output consolidation, only {} outputs qualify",
+                commitments.len()
+            );
+            return Ok(None);
+        }
+
+        let mut client = match self
+            .resources
+            .connectivity
+            .obtain_base_node_wallet_rpc_client_timeout(CONSOLIDATION_BASE_NODE_TIMEOUT)
+            .await
+        {
+            Some(client) => client,
+            None => {
+                debug!(
+                    target: LOG_TARGET,
+                    "Skipping output consolidation, no base node connection"
+                );
+                return Ok(None);
+            },
+        };
+        let stats = client
+            .get_mempool_fee_per_gram_stats(GetMempoolFeePerGramStatsRequest { count: 1 })
+            .await?;
+        // With an empty mempool the minimum fee is enough to be included in the next block
+        let fee_per_gram = stats.stats.first().map_or(1, |s| s.min_fee_per_gram).max(1);
+        if fee_per_gram > config.auto_consolidation_max_fee_per_gram {
+            debug!(
+                target: LOG_TARGET,
+                "Skipping output consolidation, the fee rate of {} uT/g is above the limit of {} uT/g",
+                fee_per_gram,
+                config.auto_consolidation_max_fee_per_gram
+            );
+            return Ok(None);
+        }
+
+        self.create_coin_join(commitments, fee_per_gram.into()).await.map(Some)
+    }
+
     pub async fn scrape_wallet(
         &mut self,
         tx_id: TxId,
@@ -2967,12 +3784,90 @@ where
         Ok(results)
     }
 
-    #[allow(clippy::too_many_lines)]
     pub async fn create_claim_sha_atomic_swap_transaction(
         &mut self,
         output: TransactionOutput,
         pre_image: PublicKey,
         fee_per_gram: MicroMinotari,
+    ) -> Result<(TxId, MicroMinotari, MicroMinotari, Transaction), OutputManagerError> {
+        self.create_claim_atomic_swap_transaction(
+            output,
+            inputs!(pre_image),
+            PaymentId::open_from_str("SHA-XTR atomic swap"),
+            fee_per_gram,
+        )
+        .await
+    }
+
+    async fn claim_adaptor_swap_with_hash(
+        &mut self,
+        output_hash: HashOutput,
+        adaptor_signature: AdaptorSignature,
+        adaptor_secret: PrivateKey,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<OutputManagerResponse, OutputManagerError> {
+        let output = self
+            .fetch_unspent_outputs_from_node(vec![output_hash])
+            .await?
+            .pop()
+            .ok_or_else(|| OutputManagerError::ServiceError("Output not found".to_string()))?;
+
+        self.create_claim_adaptor_swap_transaction(output, adaptor_signature, adaptor_secret, fee_per_gram)
+            .await
+            .map(|claim| OutputManagerResponse::ClaimAdaptorSwapTransaction(Box::new(claim)))
+    }
+
+    /// Claims an adaptor signature swap output sent to this wallet, by completing the adaptor signature with the
+    /// adaptor secret. Fails if the output is not locked with an [AdaptorSwapScript] that this wallet can claim, or if
+    /// the adaptor signature is not a valid pre-signature for the adaptor secret.
+    pub async fn create_claim_adaptor_swap_transaction(
+        &mut self,
+        output: TransactionOutput,
+        adaptor_signature: AdaptorSignature,
+        adaptor_secret: PrivateKey,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<AdaptorSwapClaim, OutputManagerError> {
+        let adaptor_point = PublicKey::from_secret_key(&adaptor_secret);
+        let swap_script = AdaptorSwapScript::from_script(&output.script, &adaptor_point).ok_or_else(|| {
+            OutputManagerError::InvalidAdaptorSwap("The output is not locked to this adaptor point".to_string())
+        })?;
+        if swap_script.claim_public_key != self.resources.key_manager.get_spend_key().await?.pub_key {
+            return Err(OutputManagerError::InvalidAdaptorSwap(
+                "The output cannot be claimed by this wallet".to_string(),
+            ));
+        }
+        if !adaptor_signature.verify(&swap_script.swap_public_key, &adaptor_point, &swap_script.message()) {
+            return Err(OutputManagerError::InvalidAdaptorSwap(
+                "The adaptor signature is not valid for this output and adaptor point".to_string(),
+            ));
+        }
+        let signature = adaptor_signature.complete(&adaptor_secret);
+        let (tx_id, fee, amount, transaction) = self
+            .create_claim_atomic_swap_transaction(
+                output,
+                inputs!(signature.clone()),
+                PaymentId::open_from_str("Adaptor signature atomic swap"),
+                fee_per_gram,
+            )
+            .await?;
+        Ok(AdaptorSwapClaim {
+            tx_id,
+            fee,
+            amount,
+            transaction,
+            swap_script,
+            signature,
+        })
+    }
+
+    /// Spends a one-sided atomic swap output sent to this wallet with the given input stack
+    #[allow(clippy::too_many_lines)]
+    async fn create_claim_atomic_swap_transaction(
+        &mut self,
+        output: TransactionOutput,
+        input_data: ExecutionStack,
+        payment_id: PaymentId,
+        fee_per_gram: MicroMinotari,
     ) -> Result<(TxId, MicroMinotari, MicroMinotari, Transaction), OutputManagerError> {
         let shared_secret = self
             .resources
@@ -2994,7 +3889,7 @@ where
                     spending_key_id,
                     output.features,
                     output.script,
-                    inputs!(pre_image),
+                    input_data,
                     self.resources.key_manager.get_spend_key().await?.key_id,
                     output.sender_offset_public_key,
                     output.metadata_signature,
@@ -3016,7 +3911,7 @@ where
                 builder
                     .with_lock_height(0)
                     .with_fee_per_gram(fee_per_gram)
-                    .with_payment_id(PaymentId::open_from_str("SHA-XTR atomic swap"))
+                    .with_payment_id(payment_id)
                     .with_kernel_features(KernelFeatures::empty())
                     .with_prevent_fee_gt_amount(self.resources.config.prevent_fee_gt_amount)
                     .with_input(rewound_output)
@@ -3177,6 +4072,246 @@ where
         Ok(())
     }
 
+    async fn create_multisig_account(
+        &mut self,
+        name: String,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        view_key_share: Option<PrivateKey>,
+    ) -> Result<(MultisigAccount, Vec<(PublicKey, PrivateKey)>), OutputManagerError> {
+        if name.trim().is_empty() {
+            return Err(OutputManagerError::InvalidArgument(
+                "The account name cannot be empty".to_string(),
+            ));
+        }
+        if public_keys.is_empty() || public_keys.len() > usize::from(MAX_MULTISIG_LIMIT) {
+            return Err(OutputManagerError::InvalidArgument(format!(
+                "An account needs between 1 and {} public keys, received {}",
+                MAX_MULTISIG_LIMIT,
+                public_keys.len()
+            )));
+        }
+        if threshold == 0 || usize::from(threshold) > public_keys.len() {
+            return Err(OutputManagerError::InvalidArgument(format!(
+                "The threshold must be between 1 and {}, received {}",
+                public_keys.len(),
+                threshold
+            )));
+        }
+        if public_keys
+            .iter()
+            .enumerate()
+            .any(|(i, key)| public_keys[..i].contains(key))
+        {
+            return Err(OutputManagerError::InvalidArgument(
+                "The public keys must be unique".to_string(),
+            ));
+        }
+        if let Some(account) = self
+            .resources
+            .db
+            .get_multisig_accounts()?
+            .into_iter()
+            .find(|account| account.threshold == threshold && account.public_keys == public_keys)
+        {
+            return Err(OutputManagerError::InvalidArgument(format!(
+                "Account '{}' has the same threshold and public keys",
+                account.name
+            )));
+        }
+
+        // One of the public keys must be this wallet's share of the account
+        let script_key_id = multisig_account_key_id(&name);
+        let script_public_key = self
+            .resources
+            .key_manager
+            .get_public_key_at_key_id(&script_key_id)
+            .await?;
+        if !public_keys.contains(&script_public_key) {
+            return Err(OutputManagerError::InvalidArgument(format!(
+                "None of the public keys is this wallet's key for account '{}'",
+                name
+            )));
+        }
+
+        // The coordinator picks the view key and hands out shares of it, the other parties unmask theirs
+        let coordinator_public_key = &public_keys[0];
+        let mut view_key_shares = Vec::new();
+        let view_key = if *coordinator_public_key == script_public_key {
+            if view_key_share.is_some() {
+                return Err(OutputManagerError::InvalidArgument(
+                    "The coordinator of an account does not need a view key share".to_string(),
+                ));
+            }
+            let view_key = PrivateKey::random(&mut OsRng);
+            for public_key in &public_keys[1..] {
+                let shared_secret = self
+                    .resources
+                    .key_manager
+                    .get_diffie_hellman_shared_secret(&script_key_id, public_key)
+                    .await?;
+                view_key_shares.push((
+                    public_key.clone(),
+                    MultisigAccount::view_key_share(&view_key, &shared_secret)?,
+                ));
+            }
+            view_key
+        } else {
+            let view_key_share = view_key_share.ok_or_else(|| {
+                OutputManagerError::InvalidArgument(
+                    "A view key share from the coordinator of the account is required".to_string(),
+                )
+            })?;
+            let shared_secret = self
+                .resources
+                .key_manager
+                .get_diffie_hellman_shared_secret(&script_key_id, coordinator_public_key)
+                .await?;
+            MultisigAccount::view_key_from_share(&view_key_share, &shared_secret)?
+        };
+        let view_public_key = PublicKey::from_secret_key(&view_key);
+        let view_key_id = self.resources.key_manager.import_key(view_key).await?;
+
+        let account = MultisigAccount {
+            name,
+            threshold,
+            public_keys,
+            script_key_id,
+            view_key_id,
+            view_public_key,
+            created_at: Utc::now(),
+        };
+        self.resources.db.add_multisig_account(account.clone())?;
+        info!(
+            target: LOG_TARGET,
+            "Created {}-of-{} multi-signature account '{}'",
+            account.threshold,
+            account.public_keys.len(),
+            account.name
+        );
+        Ok((account, view_key_shares))
+    }
+
+    /// Marks the outputs of a multi-signature account that are no longer in the UTXO set as spent
+    async fn validate_multisig_outputs(
+        &mut self,
+        account_name: String,
+    ) -> Result<Vec<MultisigOutput>, OutputManagerError> {
+        let outputs = self.resources.db.get_multisig_outputs(&account_name)?;
+        let unspent_hashes = outputs
+            .iter()
+            .filter(|output| !output.spent)
+            .map(|output| output.hash)
+            .collect::<Vec<_>>();
+        if unspent_hashes.is_empty() {
+            return Ok(outputs);
+        }
+        let found_hashes = self
+            .fetch_unspent_outputs_from_node(unspent_hashes)
+            .await?
+            .iter()
+            .map(|output| output.hash())
+            .collect::<Vec<_>>();
+        for output in outputs.iter().filter(|o| !o.spent && !found_hashes.contains(&o.hash)) {
+            debug!(
+                target: LOG_TARGET,
+                "Multi-signature output {} of account '{}' has been spent", output.hash, account_name
+            );
+            self.resources
+                .db
+                .mark_multisig_output_as_spent(output.hash, output.spent_in_tx_id)?;
+        }
+        Ok(self.resources.db.get_multisig_outputs(&account_name)?)
+    }
+
+    /// The key an output of a multi-signature account is encrypted with
+    async fn multisig_output_encryption_key(
+        &self,
+        account: &MultisigAccount,
+        sender_offset_public_key: &PublicKey,
+    ) -> Result<PrivateKey, OutputManagerError> {
+        let shared_secret = self
+            .resources
+            .key_manager
+            .get_diffie_hellman_shared_secret(&account.view_key_id, sender_offset_public_key)
+            .await?;
+        Ok(MultisigAccount::output_encryption_key(&shared_secret)?)
+    }
+
+    /// Scanning outputs addressed to this wallet's multi-signature accounts
+    async fn scan_outputs_for_multisig_accounts(
+        &mut self,
+        outputs: Vec<TransactionOutput>,
+        mined_height: u64,
+    ) -> Result<Vec<MultisigOutput>, OutputManagerError> {
+        let accounts = self.resources.db.get_multisig_accounts()?;
+        let mut found_outputs = Vec::new();
+        if accounts.is_empty() {
+            return Ok(found_outputs);
+        }
+        for output in outputs {
+            let account = match accounts
+                .iter()
+                .find(|account| account.is_output_script(&output.script, &output.commitment))
+            {
+                Some(account) => account,
+                None => continue,
+            };
+            let encryption_key = self
+                .multisig_output_encryption_key(account, &output.sender_offset_public_key)
+                .await?;
+            let (value, commitment_mask, _payment_id) =
+                match EncryptedData::decrypt_data(&encryption_key, &output.commitment, &output.encrypted_data) {
+                    Ok(data) => data,
+                    Err(e) => {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Could not decrypt output {} of multi-signature account '{}': {}",
+                            output.hash(),
+                            account.name,
+                            e
+                        );
+                        continue;
+                    },
+                };
+            if !output.verify_mask(&self.resources.factories.range_proof, &commitment_mask, value.as_u64())? {
+                warn!(
+                    target: LOG_TARGET,
+                    "Could not verify the mask of output {} of multi-signature account '{}'",
+                    output.hash(),
+                    account.name
+                );
+                continue;
+            }
+            let multisig_output = MultisigOutput {
+                hash: output.hash(),
+                account_name: account.name.clone(),
+                commitment: output.commitment.clone(),
+                value,
+                mined_height,
+                spent_in_tx_id: None,
+                spent: false,
+            };
+            match self.resources.db.add_multisig_output(multisig_output.clone()) {
+                Ok(()) => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Found output {} ({}) of multi-signature account '{}'",
+                        multisig_output.hash,
+                        multisig_output.value,
+                        multisig_output.account_name
+                    );
+                    found_outputs.push(multisig_output);
+                },
+                Err(OutputManagerStorageError::DuplicateOutput) => {
+                    trace!(target: LOG_TARGET, "Duplicate multi-signature output not added");
+                },
+                Err(e) => return Err(e.into()),
+            }
+        }
+        Ok(found_outputs)
+    }
+
     // Scanning outputs addressed to this wallet
     #[allow(clippy::too_many_lines)]
     async fn scan_outputs_for_one_sided_payments(
@@ -3196,8 +4331,26 @@ where
         }
 
         let view_key = self.resources.key_manager.get_view_key().await?;
+        let spend_key = self.resources.key_manager.get_spend_key().await?;
+        // Stealth payments can be made to the wallet's own address or to the address of any of its named accounts
+        let mut stealth_keys = vec![(None, view_key.key_id.clone(), spend_key.key_id, spend_key.pub_key)];
+        for account in self.resources.db.get_wallet_accounts()? {
+            let spend_key_id = account.spend_key_id();
+            let spend_public_key = self
+                .resources
+                .key_manager
+                .get_public_key_at_key_id(&spend_key_id)
+                .await?;
+            stealth_keys.push((
+                Some(account.name.clone()),
+                account.view_key_id(),
+                spend_key_id,
+                spend_public_key,
+            ));
+        }
 
         let mut scanned_outputs = vec![];
+        let mut account_outputs = vec![];
 
         for (output, tx_id) in outputs {
             if let [Opcode::PushPubKey(scanned_pk)] = output.script.as_slice() {
@@ -3242,76 +4395,84 @@ where
                         }
                     }
                 }
-                // it is not some known key, so lets try and see if this is a stealth tx for us
+                // it is not some known key, so lets try and see if this is a stealth tx for us or one of our accounts
                 else {
-                    let shared_secret = self
-                        .resources
-                        .key_manager
-                        .get_diffie_hellman_shared_secret(&view_key.key_id, &output.sender_offset_public_key)
-                        .await?;
-
-                    let encryption_key = shared_secret_to_output_encryption_key(&shared_secret)?;
-                    if let Ok((committed_value, commitment_mask_private_key, payment_id)) =
-                        EncryptedData::decrypt_data(&encryption_key, &output.commitment, &output.encrypted_data)
-                    {
-                        let commitment_mask_key_id = &self
+                    for (account_name, view_key_id, spend_key_id, spend_public_key) in &stealth_keys {
+                        let shared_secret = self
                             .resources
                             .key_manager
-                            .import_key(commitment_mask_private_key.clone())
+                            .get_diffie_hellman_shared_secret(view_key_id, &output.sender_offset_public_key)
                             .await?;
 
-                        if output.verify_mask(
+                        let encryption_key = shared_secret_to_output_encryption_key(&shared_secret)?;
+                        let (committed_value, commitment_mask_private_key, payment_id) =
+                            match EncryptedData::decrypt_data(
+                                &encryption_key,
+                                &output.commitment,
+                                &output.encrypted_data,
+                            ) {
+                                Ok(data) => data,
+                                Err(_) => continue,
+                            };
+                        if !output.verify_mask(
                             &self.resources.factories.range_proof,
                             &commitment_mask_private_key,
                             committed_value.into(),
                         )? {
-                            let script_spending_key = self
-                                .resources
-                                .key_manager
-                                .stealth_address_script_spending_key(
-                                    commitment_mask_key_id,
-                                    &self.resources.key_manager.get_spend_key().await?.pub_key,
-                                )
-                                .await?;
-
-                            if script_spending_key != **scanned_pk {
-                                continue;
-                            }
-                            let commitment_mask = self
-                                .resources
-                                .key_manager
-                                .import_key(commitment_mask_private_key)
-                                .await?;
-                            let script_key = TariKeyId::Derived {
-                                key: SerializedKeyString::from(commitment_mask.to_string()),
-                            };
+                            continue;
+                        }
+                        let commitment_mask = self
+                            .resources
+                            .key_manager
+                            .import_key(commitment_mask_private_key)
+                            .await?;
+                        let script_spending_key = self
+                            .resources
+                            .key_manager
+                            .stealth_address_script_spending_key(&commitment_mask, spend_public_key)
+                            .await?;
+                        if script_spending_key != **scanned_pk {
+                            continue;
+                        }
+                        let script_key = self
+                            .resources
+                            .key_manager
+                            .import_stealth_address_script_key(&commitment_mask, spend_key_id)
+                            .await?;
 
-                            let rewound_output = WalletOutput::new_with_rangeproof(
-                                output.version,
-                                committed_value,
-                                commitment_mask,
-                                output.features,
-                                output.script,
-                                ExecutionStack::new(vec![]),
-                                script_key,
-                                output.sender_offset_public_key,
-                                output.metadata_signature,
-                                0,
-                                output.covenant,
-                                output.encrypted_data,
-                                output.minimum_value_promise,
-                                output.proof,
-                                payment_id,
-                            );
+                        let rewound_output = WalletOutput::new_with_rangeproof(
+                            output.version,
+                            committed_value,
+                            commitment_mask,
+                            output.features.clone(),
+                            output.script.clone(),
+                            ExecutionStack::new(vec![]),
+                            script_key,
+                            output.sender_offset_public_key.clone(),
+                            output.metadata_signature.clone(),
+                            0,
+                            output.covenant.clone(),
+                            output.encrypted_data.clone(),
+                            output.minimum_value_promise,
+                            output.proof.clone(),
+                            payment_id,
+                        );
 
-                            scanned_outputs.push((rewound_output, OutputSource::StealthOneSided, tx_id));
+                        if let Some(account_name) = account_name {
+                            account_outputs.push((account_name.clone(), output.commitment.clone()));
                         }
+                        scanned_outputs.push((rewound_output, OutputSource::StealthOneSided, tx_id));
+                        break;
                     }
                 }
             }
         }
 
-        self.import_onesided_outputs(scanned_outputs).await
+        let recovered_outputs = self.import_onesided_outputs(scanned_outputs).await?;
+        for (account_name, commitment) in account_outputs {
+            self.resources.db.set_outputs_account(vec![commitment], &account_name)?;
+        }
+        Ok(recovered_outputs)
     }
 
     // Import scanned outputs into the wallet
@@ -3380,11 +4541,25 @@ pub enum UseOutput {
     AsProvided(TransactionOutput),
 }
 
+/// This wallet's key for the named multi-signature account
+fn multisig_account_key_id(name: &str) -> TariKeyId {
+    KeyId::Managed {
+        branch: TransactionKeyManagerBranch::MultiSig.get_branch_key(),
+        index: MultisigAccount::key_index(name),
+    }
+}
+
 fn get_multi_sig_script_components(
     script: &TariScript,
     tx_id: TxId,
 ) -> Result<(Vec<PublicKey>, u8), OutputManagerError> {
-    if let Some(Opcode::CheckMultiSigVerifyAggregatePubKey(m, _n, keys, _msg)) = script.as_slice().get(3) {
+    let opcode = match script.as_slice() {
+        // A multi-signature account output, `CheckMultiSigVerifyAggregatePubKey`
+        [opcode] => Some(opcode),
+        // A pre-mine output, `CheckHeight LeZero IfThen CheckMultiSigVerifyAggregatePubKey Else PushPubKey EndIf`
+        opcodes => opcodes.get(3),
+    };
+    if let Some(Opcode::CheckMultiSigVerifyAggregatePubKey(m, _n, keys, _msg)) = opcode {
         Ok((keys.clone(), *m))
     } else {
         Err(OutputManagerError::ServiceError(format!(
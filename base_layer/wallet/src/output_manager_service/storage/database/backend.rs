@@ -13,7 +13,7 @@ use crate::output_manager_service::{
     service::Balance,
     storage::{
         database::{DbKey, DbValue, OutputBackendQuery, WriteOperation},
-        models::DbWalletOutput,
+        models::{DbWalletOutput, MultisigAccount, MultisigOutput, OutputManagerBackup, WalletAccount},
         sqlite_db::{ReceivedOutputInfoForBatch, SpentOutputInfoForBatch},
     },
 };
@@ -82,8 +82,9 @@ pub trait OutputManagerBackend: Send + Sync + Clone {
     fn get_last_spent_output(&self) -> Result<Option<DbWalletOutput>, OutputManagerStorageError>;
     /// Reinstate a cancelled inbound output
     fn reinstate_cancelled_inbound_output(&self, tx_id: TxId) -> Result<(), OutputManagerStorageError>;
-    /// Return the available, time locked, pending incoming and pending outgoing balance
-    fn get_balance(&self, tip: Option<u64>) -> Result<Balance, OutputManagerStorageError>;
+    /// Return the available, time locked, pending incoming and pending outgoing balance of a named wallet account, or
+    /// of the outputs that do not belong to any named account if `account_name` is `None`
+    fn get_balance(&self, tip: Option<u64>, account_name: Option<&str>) -> Result<Balance, OutputManagerStorageError>;
     /// Import unvalidated output
     fn add_unvalidated_output(&self, output: DbWalletOutput, tx_id: TxId) -> Result<(), OutputManagerStorageError>;
     fn fetch_unspent_outputs_for_spending(
@@ -94,4 +95,49 @@ pub trait OutputManagerBackend: Send + Sync + Clone {
     ) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError>;
     fn fetch_outputs_by_tx_id(&self, tx_id: TxId) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError>;
     fn fetch_outputs_by_query(&self, q: OutputBackendQuery) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError>;
+    /// Retrieve all multi-signature accounts, oldest first
+    fn fetch_multisig_accounts(&self) -> Result<Vec<MultisigAccount>, OutputManagerStorageError>;
+    /// Add a multi-signature account. Account names are unique.
+    fn insert_multisig_account(&self, account: MultisigAccount) -> Result<(), OutputManagerStorageError>;
+    /// Retrieve the outputs of a multi-signature account, in the order they were mined
+    fn fetch_multisig_outputs(&self, account_name: &str) -> Result<Vec<MultisigOutput>, OutputManagerStorageError>;
+    /// Add an output found for a multi-signature account
+    fn add_multisig_output(&self, output: MultisigOutput) -> Result<(), OutputManagerStorageError>;
+    /// Update the spent status of a multi-signature account output
+    fn update_multisig_output_spent_status(
+        &self,
+        hash: FixedHash,
+        spent_in_tx_id: Option<TxId>,
+        spent: bool,
+    ) -> Result<(), OutputManagerStorageError>;
+    /// Retrieve all named wallet accounts, oldest first
+    fn fetch_wallet_accounts(&self) -> Result<Vec<WalletAccount>, OutputManagerStorageError>;
+    /// Add a named wallet account. Account names are unique.
+    fn insert_wallet_account(&self, account: WalletAccount) -> Result<(), OutputManagerStorageError>;
+    /// Assign outputs to a named wallet account
+    fn set_outputs_account(
+        &self,
+        commitments: Vec<Commitment>,
+        account_name: &str,
+    ) -> Result<(), OutputManagerStorageError>;
+    /// Retrieve the unspent outputs of a named wallet account
+    fn fetch_unspent_account_outputs(
+        &self,
+        account_name: &str,
+    ) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError>;
+    /// Retrieve the name of the wallet account an output belongs to, or `None` if it does not belong to a named account
+    fn fetch_output_account(&self, commitment: &Commitment) -> Result<Option<String>, OutputManagerStorageError>;
+    /// Retrieve the ids of the transactions that paid to or spent from the outputs of a named wallet account
+    fn fetch_account_tx_ids(&self, account_name: &str) -> Result<Vec<TxId>, OutputManagerStorageError>;
+    /// Set or clear the user label of outputs
+    fn set_outputs_label(
+        &self,
+        commitments: Vec<Commitment>,
+        label: Option<&str>,
+    ) -> Result<(), OutputManagerStorageError>;
+    /// Freeze or unfreeze outputs. Frozen outputs are never selected as transaction inputs.
+    fn set_outputs_frozen(&self, commitments: Vec<Commitment>, frozen: bool) -> Result<(), OutputManagerStorageError>;
+    /// Insert the data from a wallet backup in a single database transaction, skipping data that already exists, and
+    /// return the number of outputs inserted
+    fn import_backup(&self, backup: OutputManagerBackup) -> Result<usize, OutputManagerStorageError>;
 }
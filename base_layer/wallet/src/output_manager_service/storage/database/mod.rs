@@ -43,7 +43,14 @@ use crate::output_manager_service::{
     input_selection::UtxoSelectionCriteria,
     service::Balance,
     storage::{
-        models::{DbWalletOutput, KnownOneSidedPaymentScript},
+        models::{
+            DbWalletOutput,
+            KnownOneSidedPaymentScript,
+            MultisigAccount,
+            MultisigOutput,
+            OutputManagerBackup,
+            WalletAccount,
+        },
         sqlite_db::{ReceivedOutputInfoForBatch, SpentOutputInfoForBatch},
         OutputStatus,
     },
@@ -181,7 +188,16 @@ where T: OutputManagerBackend + 'static
         &self,
         current_tip_for_time_lock_calculation: Option<u64>,
     ) -> Result<Balance, OutputManagerStorageError> {
-        self.db.get_balance(current_tip_for_time_lock_calculation)
+        self.db.get_balance(current_tip_for_time_lock_calculation, None)
+    }
+
+    pub fn get_account_balance(
+        &self,
+        account_name: &str,
+        current_tip_for_time_lock_calculation: Option<u64>,
+    ) -> Result<Balance, OutputManagerStorageError> {
+        self.db
+            .get_balance(current_tip_for_time_lock_calculation, Some(account_name))
     }
 
     /// This method is called when a transaction is built to be sent. It will encumber unspent outputs against a pending
@@ -437,6 +453,105 @@ where T: OutputManagerBackend + 'static
     ) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
         self.db.fetch_outputs_by_query(q)
     }
+
+    pub fn get_multisig_accounts(&self) -> Result<Vec<MultisigAccount>, OutputManagerStorageError> {
+        self.db.fetch_multisig_accounts()
+    }
+
+    pub fn get_multisig_account(&self, name: &str) -> Result<MultisigAccount, OutputManagerStorageError> {
+        self.db
+            .fetch_multisig_accounts()?
+            .into_iter()
+            .find(|account| account.name == name)
+            .ok_or(OutputManagerStorageError::ValueNotFound)
+    }
+
+    pub fn add_multisig_account(&self, account: MultisigAccount) -> Result<(), OutputManagerStorageError> {
+        self.db.insert_multisig_account(account)
+    }
+
+    pub fn get_multisig_outputs(&self, account_name: &str) -> Result<Vec<MultisigOutput>, OutputManagerStorageError> {
+        self.db.fetch_multisig_outputs(account_name)
+    }
+
+    pub fn add_multisig_output(&self, output: MultisigOutput) -> Result<(), OutputManagerStorageError> {
+        self.db.add_multisig_output(output)
+    }
+
+    pub fn set_multisig_output_spent_in_tx(
+        &self,
+        hash: FixedHash,
+        tx_id: TxId,
+    ) -> Result<(), OutputManagerStorageError> {
+        self.db.update_multisig_output_spent_status(hash, Some(tx_id), false)
+    }
+
+    pub fn mark_multisig_output_as_spent(
+        &self,
+        hash: FixedHash,
+        spent_in_tx_id: Option<TxId>,
+    ) -> Result<(), OutputManagerStorageError> {
+        self.db.update_multisig_output_spent_status(hash, spent_in_tx_id, true)
+    }
+
+    pub fn get_wallet_accounts(&self) -> Result<Vec<WalletAccount>, OutputManagerStorageError> {
+        self.db.fetch_wallet_accounts()
+    }
+
+    pub fn get_wallet_account(&self, name: &str) -> Result<WalletAccount, OutputManagerStorageError> {
+        self.db
+            .fetch_wallet_accounts()?
+            .into_iter()
+            .find(|account| account.name == name)
+            .ok_or(OutputManagerStorageError::ValueNotFound)
+    }
+
+    pub fn add_wallet_account(&self, account: WalletAccount) -> Result<(), OutputManagerStorageError> {
+        self.db.insert_wallet_account(account)
+    }
+
+    pub fn set_outputs_account(
+        &self,
+        commitments: Vec<Commitment>,
+        account_name: &str,
+    ) -> Result<(), OutputManagerStorageError> {
+        self.db.set_outputs_account(commitments, account_name)
+    }
+
+    pub fn fetch_unspent_account_outputs(
+        &self,
+        account_name: &str,
+    ) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        self.db.fetch_unspent_account_outputs(account_name)
+    }
+
+    pub fn fetch_output_account(&self, commitment: &Commitment) -> Result<Option<String>, OutputManagerStorageError> {
+        self.db.fetch_output_account(commitment)
+    }
+
+    pub fn fetch_account_tx_ids(&self, account_name: &str) -> Result<Vec<TxId>, OutputManagerStorageError> {
+        self.db.fetch_account_tx_ids(account_name)
+    }
+
+    pub fn set_outputs_label(
+        &self,
+        commitments: Vec<Commitment>,
+        label: Option<&str>,
+    ) -> Result<(), OutputManagerStorageError> {
+        self.db.set_outputs_label(commitments, label)
+    }
+
+    pub fn set_outputs_frozen(
+        &self,
+        commitments: Vec<Commitment>,
+        frozen: bool,
+    ) -> Result<(), OutputManagerStorageError> {
+        self.db.set_outputs_frozen(commitments, frozen)
+    }
+
+    pub fn import_backup(&self, backup: OutputManagerBackup) -> Result<usize, OutputManagerStorageError> {
+        self.db.import_backup(backup)
+    }
 }
 
 fn unexpected_result<T>(req: DbKey, res: DbValue) -> Result<T, OutputManagerStorageError> {
@@ -24,15 +24,24 @@ use std::{cmp::Ordering, convert::TryFrom};
 
 use chrono::{DateTime, Utc};
 use derivative::Derivative;
+use serde::{Deserialize, Serialize};
 use tari_common_types::{
+    key_branches::TransactionKeyManagerBranch,
     transaction::TxId,
-    types::{BlockHash, Commitment, HashOutput},
+    types::{BlockHash, Commitment, HashOutput, PrivateKey, PublicKey, WalletHasher},
 };
-use tari_core::transactions::{
-    key_manager::{TariKeyId, TransactionKeyManagerInterface},
-    transaction_components::{encrypted_data::PaymentId, WalletOutput},
+use tari_comms::types::CommsDHKE;
+use tari_core::{
+    one_sided::shared_secret_to_output_encryption_key,
+    transactions::{
+        key_manager::{TariKeyId, TransactionKeyManagerInterface},
+        tari_amount::MicroMinotari,
+        transaction_components::{encrypted_data::PaymentId, WalletOutput},
+    },
 };
-use tari_script::{ExecutionStack, TariScript};
+use tari_crypto::keys::SecretKey;
+use tari_script::{ExecutionStack, Opcode, ScriptError, TariScript, MAX_MULTISIG_LIMIT};
+use tari_utilities::{ByteArray, ByteArrayError};
 
 use crate::output_manager_service::{
     error::OutputManagerStorageError,
@@ -57,6 +66,10 @@ pub struct DbWalletOutput {
     pub received_in_tx_id: Option<TxId>,
     pub spent_in_tx_id: Option<TxId>,
     pub payment_id: PaymentId,
+    /// A user label used to group outputs, e.g. to keep customer deposits apart from operating funds
+    pub label: Option<String>,
+    /// Frozen outputs are never selected as transaction inputs
+    pub frozen: bool,
 }
 
 impl DbWalletOutput {
@@ -85,6 +98,8 @@ impl DbWalletOutput {
             received_in_tx_id,
             spent_in_tx_id,
             payment_id,
+            label: None,
+            frozen: false,
         })
     }
 }
@@ -117,7 +132,7 @@ impl Eq for DbWalletOutput {}
 
 // ---------------------------------------------------------------------------
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Eq, PartialEq, Serialize, Deserialize)]
 pub enum SpendingPriority {
     Normal,
     HtlcSpendAsap,
@@ -144,7 +159,7 @@ impl From<SpendingPriority> for i32 {
     }
 }
 
-#[derive(Derivative, Clone)]
+#[derive(Derivative, Clone, Serialize, Deserialize)]
 #[derivative(Debug)]
 pub struct KnownOneSidedPaymentScript {
     pub script_hash: Vec<u8>,
@@ -159,3 +174,143 @@ impl PartialEq for KnownOneSidedPaymentScript {
         self.script_hash == other.script_hash
     }
 }
+
+/// An N-of-M multi-signature account. Each party holds one of the account's public keys, and any `threshold` of them
+/// can jointly spend the account's outputs.
+///
+/// The parties also share the account's view key, which the account's outputs are encrypted to. The coordinator, the
+/// party holding the first public key, picks the view key when it creates the account, and hands every other party a
+/// share of it that only that party can unmask, using the Diffie-Hellman secret of their two account keys.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultisigAccount {
+    pub name: String,
+    pub threshold: u8,
+    /// The public keys of all parties, in the order they appear in the account's output scripts
+    pub public_keys: Vec<PublicKey>,
+    /// This wallet's share of the account
+    pub script_key_id: TariKeyId,
+    pub view_key_id: TariKeyId,
+    /// The key that senders encrypt the account's outputs to
+    pub view_public_key: PublicKey,
+    pub created_at: DateTime<Utc>,
+}
+
+impl MultisigAccount {
+    /// The script of an output paid to an account, `CheckMultiSigVerifyAggregatePubKey(m, n, keys, commitment)`. The
+    /// parties sign the output's commitment, so that their signatures cannot be replayed against another output.
+    pub fn output_script(
+        threshold: u8,
+        public_keys: &[PublicKey],
+        commitment: &Commitment,
+    ) -> Result<TariScript, ScriptError> {
+        let n = u8::try_from(public_keys.len()).map_err(|_| ScriptError::ValueExceedsBounds)?;
+        if threshold == 0 || threshold > n || n > MAX_MULTISIG_LIMIT {
+            return Err(ScriptError::ValueExceedsBounds);
+        }
+        let mut message = [0u8; 32];
+        message.copy_from_slice(commitment.as_bytes());
+        TariScript::new(vec![Opcode::CheckMultiSigVerifyAggregatePubKey(
+            threshold,
+            n,
+            public_keys.to_vec(),
+            Box::new(message),
+        )])
+    }
+
+    /// The index of this wallet's key for the named account on the multi-signature key branch. The key is derived
+    /// from the account name, so that the wallet does not have to search the branch for it.
+    pub fn key_index(name: &str) -> u64 {
+        let hash = WalletHasher::new_with_label("multisig_account_key_index")
+            .chain(name.as_bytes())
+            .finalize();
+        let mut index = [0u8; 8];
+        index.copy_from_slice(&hash.as_ref()[..8]);
+        u64::from_le_bytes(index)
+    }
+
+    /// The share of the view key that the coordinator hands to a party. `shared_secret` is the Diffie-Hellman secret of
+    /// the coordinator's and the party's account keys.
+    pub fn view_key_share(view_key: &PrivateKey, shared_secret: &CommsDHKE) -> Result<PrivateKey, ByteArrayError> {
+        Ok(view_key + &Self::view_key_mask(shared_secret)?)
+    }
+
+    /// Unmasks the view key from a share created with [`MultisigAccount::view_key_share`]
+    pub fn view_key_from_share(share: &PrivateKey, shared_secret: &CommsDHKE) -> Result<PrivateKey, ByteArrayError> {
+        Ok(share - &Self::view_key_mask(shared_secret)?)
+    }
+
+    fn view_key_mask(shared_secret: &CommsDHKE) -> Result<PrivateKey, ByteArrayError> {
+        PrivateKey::from_uniform_bytes(
+            WalletHasher::new_with_label("multisig_view_key_share")
+                .chain(shared_secret.as_bytes())
+                .finalize()
+                .as_ref(),
+        )
+    }
+
+    /// The key an account output is encrypted with. `shared_secret` is the Diffie-Hellman secret of the output's
+    /// sender offset key and the account's view key, which the sender and every party can compute.
+    pub fn output_encryption_key(shared_secret: &CommsDHKE) -> Result<PrivateKey, ByteArrayError> {
+        shared_secret_to_output_encryption_key(shared_secret)
+    }
+
+    /// Returns true if `script` is the script of an output with `commitment` paid to this account
+    pub fn is_output_script(&self, script: &TariScript, commitment: &Commitment) -> bool {
+        matches!(
+            script.as_slice(),
+            [Opcode::CheckMultiSigVerifyAggregatePubKey(m, _n, keys, msg)]
+                if *m == self.threshold && keys == &self.public_keys && msg.as_slice() == commitment.as_bytes()
+        )
+    }
+}
+
+/// An unspent output of a [MultisigAccount], found by the UTXO scanner
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultisigOutput {
+    pub hash: HashOutput,
+    pub account_name: String,
+    pub commitment: Commitment,
+    pub value: MicroMinotari,
+    pub mined_height: u64,
+    /// The aggregate transaction spending this output, once this wallet has led a spend session for it
+    pub spent_in_tx_id: Option<TxId>,
+    /// Set once the output is no longer in the UTXO set
+    pub spent: bool,
+}
+
+/// A named account within the wallet. Each account has its own view and spend key, derived from the wallet seed on the
+/// account key branches, and so its own one-sided address, outputs and transaction history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WalletAccount {
+    pub name: String,
+    /// The index of the account's keys on the account key branches
+    pub account_index: u64,
+    pub created_at: DateTime<Utc>,
+}
+
+impl WalletAccount {
+    pub fn view_key_id(&self) -> TariKeyId {
+        TariKeyId::Managed {
+            branch: TransactionKeyManagerBranch::AccountView.get_branch_key(),
+            index: self.account_index,
+        }
+    }
+
+    pub fn spend_key_id(&self) -> TariKeyId {
+        TariKeyId::Managed {
+            branch: TransactionKeyManagerBranch::AccountSpend.get_branch_key(),
+            index: self.account_index,
+        }
+    }
+}
+
+/// The output manager data restored from a wallet backup
+#[derive(Debug, Clone, Default)]
+pub struct OutputManagerBackup {
+    /// Unspent outputs, with the named wallet account each belongs to
+    pub outputs: Vec<(DbWalletOutput, Option<String>)>,
+    pub known_one_sided_payment_scripts: Vec<KnownOneSidedPaymentScript>,
+    pub wallet_accounts: Vec<WalletAccount>,
+    pub multisig_accounts: Vec<MultisigAccount>,
+    pub multisig_outputs: Vec<MultisigOutput>,
+}
@@ -21,12 +21,13 @@
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 use core::convert::TryFrom;
 
+use serde::{Deserialize, Serialize};
 use strum_macros::Display;
 
 use crate::output_manager_service::error::OutputManagerStorageError;
 
 // The source of where the output came from
-#[derive(Copy, Clone, Debug, PartialEq, Display, Default)]
+#[derive(Copy, Clone, Debug, PartialEq, Display, Default, Serialize, Deserialize)]
 pub enum OutputSource {
     #[default]
     Standard,
@@ -36,10 +36,11 @@ pub use output_sql::OutputSql;
 use tari_common_sqlite::{sqlite_connection_pool::PooledDbConnection, util::diesel_ext::ExpectedRowsExtension};
 use tari_common_types::{
     transaction::TxId,
-    types::{Commitment, FixedHash},
+    types::{Commitment, FixedHash, PublicKey},
 };
 use tari_core::transactions::{
     key_manager::TariKeyId,
+    tari_amount::MicroMinotari,
     transaction_components::{OutputType, TransactionOutput},
 };
 use tari_crypto::tari_utilities::{hex::Hex, ByteArray};
@@ -52,12 +53,19 @@ use crate::{
         service::Balance,
         storage::{
             database::{DbKey, DbKeyValuePair, DbValue, OutputBackendQuery, OutputManagerBackend, WriteOperation},
-            models::{DbWalletOutput, KnownOneSidedPaymentScript},
+            models::{
+                DbWalletOutput,
+                KnownOneSidedPaymentScript,
+                MultisigAccount,
+                MultisigOutput,
+                OutputManagerBackup,
+                WalletAccount,
+            },
             OutputStatus,
         },
         UtxoSelectionCriteria,
     },
-    schema::{known_one_sided_payment_scripts, outputs},
+    schema::{known_one_sided_payment_scripts, multisig_accounts, multisig_outputs, outputs, wallet_accounts},
     storage::sqlite_utilities::wallet_db_connection::WalletDbConnection,
 };
 
@@ -945,12 +953,13 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
     fn get_balance(
         &self,
         current_tip_for_time_lock_calculation: Option<u64>,
+        account_name: Option<&str>,
     ) -> Result<Balance, OutputManagerStorageError> {
         let start = Instant::now();
         let mut conn = self.database_connection.get_pooled_connection()?;
         let acquire_lock = start.elapsed();
 
-        let result = OutputSql::get_balance(current_tip_for_time_lock_calculation, &mut conn);
+        let result = OutputSql::get_balance(current_tip_for_time_lock_calculation, account_name, &mut conn);
         if start.elapsed().as_millis() > 0 {
             trace!(
                 target: LOG_TARGET,
@@ -1201,6 +1210,199 @@ impl OutputManagerBackend for OutputManagerSqliteDatabase {
             })
             .collect())
     }
+
+    fn fetch_multisig_accounts(&self) -> Result<Vec<MultisigAccount>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        MultisigAccountSql::index(&mut conn)?
+            .into_iter()
+            .map(MultisigAccount::try_from)
+            .collect()
+    }
+
+    fn insert_multisig_account(&self, account: MultisigAccount) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        if MultisigAccountSql::find(&account.name, &mut conn)?.is_some() {
+            return Err(OutputManagerStorageError::DuplicateMultisigAccount(account.name));
+        }
+        MultisigAccountSql::from(account).commit(&mut conn)
+    }
+
+    fn fetch_multisig_outputs(&self, account_name: &str) -> Result<Vec<MultisigOutput>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        MultisigOutputSql::index_by_account(account_name, &mut conn)?
+            .into_iter()
+            .map(MultisigOutput::try_from)
+            .collect()
+    }
+
+    fn add_multisig_output(&self, output: MultisigOutput) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        if MultisigOutputSql::find(output.hash.as_slice(), &mut conn)?.is_some() {
+            return Err(OutputManagerStorageError::DuplicateOutput);
+        }
+        MultisigOutputSql::from(output).commit(&mut conn)
+    }
+
+    fn update_multisig_output_spent_status(
+        &self,
+        hash: FixedHash,
+        spent_in_tx_id: Option<TxId>,
+        spent: bool,
+    ) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        diesel::update(multisig_outputs::table.filter(multisig_outputs::hash.eq(hash.as_slice())))
+            .set((
+                multisig_outputs::spent_in_tx_id.eq(spent_in_tx_id.map(TxId::as_i64_wrapped)),
+                multisig_outputs::spent.eq(i32::from(spent)),
+            ))
+            .execute(&mut conn)
+            .num_rows_affected_or_not_found(1)?;
+        Ok(())
+    }
+
+    fn fetch_wallet_accounts(&self) -> Result<Vec<WalletAccount>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        WalletAccountSql::index(&mut conn)?
+            .into_iter()
+            .map(WalletAccount::try_from)
+            .collect()
+    }
+
+    fn insert_wallet_account(&self, account: WalletAccount) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        if WalletAccountSql::find(&account.name, &mut conn)?.is_some() {
+            return Err(OutputManagerStorageError::DuplicateWalletAccount(account.name));
+        }
+        WalletAccountSql::from(account).commit(&mut conn)
+    }
+
+    fn set_outputs_account(
+        &self,
+        commitments: Vec<Commitment>,
+        account_name: &str,
+    ) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let num_outputs = commitments.len();
+        diesel::update(
+            outputs::table
+                .filter(outputs::commitment.eq_any::<Vec<Vec<u8>>>(commitments.iter().map(|c| c.to_vec()).collect())),
+        )
+        .set(outputs::account_name.eq(account_name))
+        .execute(&mut conn)
+        .num_rows_affected_or_not_found(num_outputs)?;
+        Ok(())
+    }
+
+    fn fetch_unspent_account_outputs(
+        &self,
+        account_name: &str,
+    ) -> Result<Vec<DbWalletOutput>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        OutputSql::index_unspent_by_account(account_name, &mut conn)?
+            .into_iter()
+            .map(|o| o.to_db_wallet_output())
+            .collect::<Result<Vec<_>, _>>()
+    }
+
+    fn fetch_output_account(&self, commitment: &Commitment) -> Result<Option<String>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        Ok(OutputSql::find_by_commitment(&commitment.to_vec(), &mut conn)?.account_name)
+    }
+
+    fn fetch_account_tx_ids(&self, account_name: &str) -> Result<Vec<TxId>, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let tx_ids: Vec<(Option<i64>, Option<i64>)> = outputs::table
+            .filter(outputs::account_name.eq(account_name))
+            .select((outputs::received_in_tx_id, outputs::spent_in_tx_id))
+            .load(&mut conn)?;
+        let mut tx_ids = tx_ids
+            .into_iter()
+            .flat_map(|(received, spent)| received.into_iter().chain(spent))
+            .map(|id| TxId::from(id as u64))
+            .collect::<Vec<_>>();
+        tx_ids.sort();
+        tx_ids.dedup();
+        Ok(tx_ids)
+    }
+
+    fn set_outputs_label(
+        &self,
+        commitments: Vec<Commitment>,
+        label: Option<&str>,
+    ) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let num_outputs = commitments.len();
+        diesel::update(
+            outputs::table
+                .filter(outputs::commitment.eq_any::<Vec<Vec<u8>>>(commitments.iter().map(|c| c.to_vec()).collect())),
+        )
+        .set(outputs::label.eq(label))
+        .execute(&mut conn)
+        .num_rows_affected_or_not_found(num_outputs)?;
+        Ok(())
+    }
+
+    fn set_outputs_frozen(&self, commitments: Vec<Commitment>, frozen: bool) -> Result<(), OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let num_outputs = commitments.len();
+        diesel::update(
+            outputs::table
+                .filter(outputs::commitment.eq_any::<Vec<Vec<u8>>>(commitments.iter().map(|c| c.to_vec()).collect())),
+        )
+        .set(outputs::frozen.eq(i32::from(frozen)))
+        .execute(&mut conn)
+        .num_rows_affected_or_not_found(num_outputs)?;
+        Ok(())
+    }
+
+    fn import_backup(&self, backup: OutputManagerBackup) -> Result<usize, OutputManagerStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        conn.transaction::<_, OutputManagerStorageError, _>(|conn| {
+            for account in backup.wallet_accounts {
+                if WalletAccountSql::find(&account.name, conn)?.is_none() {
+                    WalletAccountSql::from(account).commit(conn)?;
+                }
+            }
+            for account in backup.multisig_accounts {
+                if MultisigAccountSql::find(&account.name, conn)?.is_none() {
+                    MultisigAccountSql::from(account).commit(conn)?;
+                }
+            }
+            for output in backup.multisig_outputs {
+                if MultisigOutputSql::find(output.hash.as_slice(), conn)?.is_none() {
+                    MultisigOutputSql::from(output).commit(conn)?;
+                }
+            }
+            for script in backup.known_one_sided_payment_scripts {
+                let script_sql = KnownOneSidedPaymentScriptSql::from_known_one_sided_payment_script(script)?;
+                if KnownOneSidedPaymentScriptSql::find(&script_sql.script_hash, conn).is_err() {
+                    script_sql.commit(conn)?;
+                }
+            }
+
+            let mut imported = 0;
+            for (output, account_name) in backup.outputs {
+                let commitment = output.commitment.to_vec();
+                if OutputSql::find_by_commitment(&commitment, conn).is_ok() {
+                    continue;
+                }
+                let label = output.label.clone();
+                let frozen = output.frozen;
+                let received_in_tx_id = output.received_in_tx_id;
+                NewOutputSql::new(output, Some(OutputStatus::UnspentMinedUnconfirmed), received_in_tx_id)?
+                    .commit(conn)?;
+                diesel::update(outputs::table.filter(outputs::commitment.eq(&commitment)))
+                    .set((
+                        outputs::account_name.eq(account_name),
+                        outputs::label.eq(label),
+                        outputs::frozen.eq(i32::from(frozen)),
+                    ))
+                    .execute(conn)?;
+                imported += 1;
+            }
+            Ok(imported)
+        })
+    }
 }
 
 /// These are the fields to be set for the received outputs batch mode update
@@ -1433,6 +1635,227 @@ impl KnownOneSidedPaymentScriptSql {
     }
 }
 
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = multisig_accounts)]
+pub struct MultisigAccountSql {
+    pub name: String,
+    pub threshold: i32,
+    pub public_keys: Vec<u8>,
+    pub script_key_id: String,
+    pub view_key_id: String,
+    pub view_public_key: Vec<u8>,
+    pub created_at: NaiveDateTime,
+}
+
+impl MultisigAccountSql {
+    /// Write this struct to the database
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), OutputManagerStorageError> {
+        diesel::insert_into(multisig_accounts::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Find a particular account, if it exists
+    pub fn find(
+        name: &str,
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<MultisigAccountSql>, OutputManagerStorageError> {
+        Ok(multisig_accounts::table
+            .filter(multisig_accounts::name.eq(name))
+            .first::<MultisigAccountSql>(conn)
+            .optional()?)
+    }
+
+    /// Return all accounts
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<MultisigAccountSql>, OutputManagerStorageError> {
+        Ok(multisig_accounts::table
+            .order_by(multisig_accounts::created_at.asc())
+            .load::<MultisigAccountSql>(conn)?)
+    }
+}
+
+impl From<MultisigAccount> for MultisigAccountSql {
+    fn from(account: MultisigAccount) -> Self {
+        Self {
+            name: account.name,
+            threshold: i32::from(account.threshold),
+            public_keys: account.public_keys.iter().flat_map(|k| k.to_vec()).collect(),
+            script_key_id: account.script_key_id.to_string(),
+            view_key_id: account.view_key_id.to_string(),
+            view_public_key: account.view_public_key.to_vec(),
+            created_at: account.created_at.naive_utc(),
+        }
+    }
+}
+
+impl TryFrom<MultisigAccountSql> for MultisigAccount {
+    type Error = OutputManagerStorageError;
+
+    fn try_from(account: MultisigAccountSql) -> Result<Self, Self::Error> {
+        let public_keys = account
+            .public_keys
+            .chunks(PublicKey::key_length())
+            .map(PublicKey::from_canonical_bytes)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?;
+        Ok(Self {
+            name: account.name,
+            threshold: u8::try_from(account.threshold)
+                .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?,
+            public_keys,
+            script_key_id: TariKeyId::from_str(&account.script_key_id).map_err(|_| {
+                OutputManagerStorageError::ConversionError {
+                    reason: "Could not convert script key to TariKeyId".to_string(),
+                }
+            })?,
+            view_key_id: TariKeyId::from_str(&account.view_key_id).map_err(|_| {
+                OutputManagerStorageError::ConversionError {
+                    reason: "Could not convert view key to TariKeyId".to_string(),
+                }
+            })?,
+            view_public_key: PublicKey::from_canonical_bytes(&account.view_public_key)
+                .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?,
+            created_at: account.created_at.and_utc(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = multisig_outputs)]
+pub struct MultisigOutputSql {
+    pub hash: Vec<u8>,
+    pub account_name: String,
+    pub commitment: Vec<u8>,
+    pub value: i64,
+    pub mined_height: i64,
+    pub spent_in_tx_id: Option<i64>,
+    pub spent: i32,
+}
+
+impl MultisigOutputSql {
+    /// Write this struct to the database
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), OutputManagerStorageError> {
+        diesel::insert_into(multisig_outputs::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Find a particular output, if it exists
+    pub fn find(
+        hash: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<MultisigOutputSql>, OutputManagerStorageError> {
+        Ok(multisig_outputs::table
+            .filter(multisig_outputs::hash.eq(hash))
+            .first::<MultisigOutputSql>(conn)
+            .optional()?)
+    }
+
+    /// Return all outputs of an account, oldest first
+    pub fn index_by_account(
+        account_name: &str,
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<MultisigOutputSql>, OutputManagerStorageError> {
+        Ok(multisig_outputs::table
+            .filter(multisig_outputs::account_name.eq(account_name))
+            .order_by(multisig_outputs::mined_height.asc())
+            .load::<MultisigOutputSql>(conn)?)
+    }
+}
+
+impl From<MultisigOutput> for MultisigOutputSql {
+    fn from(output: MultisigOutput) -> Self {
+        Self {
+            hash: output.hash.to_vec(),
+            account_name: output.account_name,
+            commitment: output.commitment.to_vec(),
+            value: output.value.as_u64() as i64,
+            mined_height: output.mined_height as i64,
+            spent_in_tx_id: output.spent_in_tx_id.map(TxId::as_i64_wrapped),
+            spent: i32::from(output.spent),
+        }
+    }
+}
+
+impl TryFrom<MultisigOutputSql> for MultisigOutput {
+    type Error = OutputManagerStorageError;
+
+    fn try_from(output: MultisigOutputSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            hash: FixedHash::try_from(output.hash.as_slice())
+                .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?,
+            account_name: output.account_name,
+            commitment: Commitment::from_canonical_bytes(&output.commitment)
+                .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?,
+            value: MicroMinotari::from(output.value as u64),
+            mined_height: output.mined_height as u64,
+            spent_in_tx_id: output.spent_in_tx_id.map(|id| TxId::from(id as u64)),
+            spent: output.spent != 0,
+        })
+    }
+}
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = wallet_accounts)]
+pub struct WalletAccountSql {
+    pub name: String,
+    pub account_index: i64,
+    pub created_at: NaiveDateTime,
+}
+
+impl WalletAccountSql {
+    /// Write this struct to the database
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), OutputManagerStorageError> {
+        diesel::insert_into(wallet_accounts::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    /// Find a particular account, if it exists
+    pub fn find(
+        name: &str,
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<WalletAccountSql>, OutputManagerStorageError> {
+        Ok(wallet_accounts::table
+            .filter(wallet_accounts::name.eq(name))
+            .first::<WalletAccountSql>(conn)
+            .optional()?)
+    }
+
+    /// Return all accounts
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<WalletAccountSql>, OutputManagerStorageError> {
+        Ok(wallet_accounts::table
+            .order_by(wallet_accounts::account_index.asc())
+            .load::<WalletAccountSql>(conn)?)
+    }
+}
+
+impl From<WalletAccount> for WalletAccountSql {
+    fn from(account: WalletAccount) -> Self {
+        Self {
+            name: account.name,
+            account_index: account.account_index as i64,
+            created_at: account.created_at.naive_utc(),
+        }
+    }
+}
+
+impl TryFrom<WalletAccountSql> for WalletAccount {
+    type Error = OutputManagerStorageError;
+
+    fn try_from(account: WalletAccountSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            name: account.name,
+            account_index: u64::try_from(account.account_index)
+                .map_err(|e| OutputManagerStorageError::ConversionError { reason: e.to_string() })?,
+            created_at: account.created_at.and_utc(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
 
@@ -110,6 +110,9 @@ pub struct OutputSql {
     pub source: i32,
     pub last_validation_timestamp: Option<NaiveDateTime>,
     pub payment_id: Option<Vec<u8>>,
+    pub account_name: Option<String>,
+    pub label: Option<String>,
+    pub frozen: i32,
 }
 
 impl OutputSql {
@@ -210,6 +213,7 @@ impl OutputSql {
         let mut query = outputs::table
             .into_boxed()
             .filter(outputs::status.eq(OutputStatus::Unspent as i32))
+            .filter(outputs::frozen.eq(0))
             .filter(outputs::value.gt(i64_value))
             .order_by(outputs::spending_priority.desc());
 
@@ -244,6 +248,15 @@ impl OutputSql {
             },
         }
 
+        query = match &selection_criteria.account {
+            Some(account_name) => query.filter(outputs::account_name.eq(account_name.clone())),
+            None => query.filter(outputs::account_name.is_null()),
+        };
+
+        if let Some(label) = &selection_criteria.label {
+            query = query.filter(outputs::label.eq(label.clone()));
+        }
+
         for exclude in &selection_criteria.excluding {
             query = query.filter(outputs::commitment.ne(exclude.as_bytes()));
         }
@@ -317,6 +330,17 @@ impl OutputSql {
             .load(conn)?)
     }
 
+    pub fn index_unspent_by_account(
+        account_name: &str,
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<OutputSql>, OutputManagerStorageError> {
+        Ok(outputs::table
+            .filter(outputs::status.eq(OutputStatus::Unspent as i32))
+            .filter(outputs::account_name.eq(account_name))
+            .order(outputs::id.asc())
+            .load(conn)?)
+    }
+
     pub fn index_marked_deleted_in_block_is_null(
         conn: &mut SqliteConnection,
     ) -> Result<Vec<OutputSql>, OutputManagerStorageError> {
@@ -414,9 +438,12 @@ impl OutputSql {
     }
 
     /// Return the available, time locked, pending incoming and pending outgoing balance
+    /// Returns the balance of the outputs belonging to the named wallet account, or of the outputs that do not belong
+    /// to any named account if `account_name` is `None`
     #[allow(clippy::cast_possible_wrap)]
     pub fn get_balance(
         current_tip_for_time_lock_calculation: Option<u64>,
+        account_name: Option<&str>,
         conn: &mut SqliteConnection,
     ) -> Result<Balance, OutputManagerStorageError> {
         #[derive(QueryableByName, Clone)]
@@ -426,34 +453,39 @@ impl OutputSql {
             #[diesel(sql_type = diesel::sql_types::Text)]
             category: String,
         }
+        type AccountName = diesel::sql_types::Nullable<diesel::sql_types::Text>;
         let balance_query_result = if let Some(current_tip) = current_tip_for_time_lock_calculation {
             let balance_query = sql_query(
                 "SELECT coalesce(sum(value), 0) as amount, 'available_balance' as category \
-                 FROM outputs WHERE status = ? AND maturity <= ? AND script_lock_height <= ? \
+                 FROM outputs WHERE account_name IS ? AND (status = ? AND maturity <= ? AND script_lock_height <= ?) \
                  UNION ALL \
                  SELECT coalesce(sum(value), 0) as amount, 'time_locked_balance' as category \
-                 FROM outputs WHERE status = ? AND maturity > ? OR script_lock_height > ? \
+                 FROM outputs WHERE account_name IS ? AND (status = ? AND maturity > ? OR script_lock_height > ?) \
                  UNION ALL \
                  SELECT coalesce(sum(value), 0) as amount, 'pending_incoming_balance' as category \
-                 FROM outputs WHERE source != ? AND status = ? OR status = ? OR status = ? \
+                 FROM outputs WHERE account_name IS ? AND (source != ? AND status = ? OR status = ? OR status = ?) \
                  UNION ALL \
                  SELECT coalesce(sum(value), 0) as amount, 'pending_outgoing_balance' as category \
-                 FROM outputs WHERE status = ? OR status = ? OR status = ?",
+                 FROM outputs WHERE account_name IS ? AND (status = ? OR status = ? OR status = ?)",
             )
                 // available_balance
+                .bind::<AccountName, _>(account_name)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::Unspent as i32)
                 .bind::<diesel::sql_types::BigInt, _>(current_tip as i64)
                 .bind::<diesel::sql_types::BigInt, _>(current_tip as i64)
                 // time_locked_balance
+                .bind::<AccountName, _>(account_name)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::Unspent as i32)
                 .bind::<diesel::sql_types::BigInt, _>(current_tip as i64)
                 .bind::<diesel::sql_types::BigInt, _>(current_tip as i64)
                 // pending_incoming_balance
+                .bind::<AccountName, _>(account_name)
                 .bind::<diesel::sql_types::Integer, _>(OutputSource::Coinbase as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::EncumberedToBeReceived as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::ShortTermEncumberedToBeReceived as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::UnspentMinedUnconfirmed as i32)
                 // pending_outgoing_balance
+                .bind::<AccountName, _>(account_name)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::EncumberedToBeSpent as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::ShortTermEncumberedToBeSpent as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::SpentMinedUnconfirmed as i32);
@@ -461,22 +493,25 @@ impl OutputSql {
         } else {
             let balance_query = sql_query(
                 "SELECT coalesce(sum(value), 0) as amount, 'available_balance' as category \
-                 FROM outputs WHERE status = ? \
+                 FROM outputs WHERE account_name IS ? AND (status = ?) \
                  UNION ALL \
                  SELECT coalesce(sum(value), 0) as amount, 'pending_incoming_balance' as category \
-                 FROM outputs WHERE source != ? AND status = ? OR status = ? OR status = ? \
+                 FROM outputs WHERE account_name IS ? AND (source != ? AND status = ? OR status = ? OR status = ?) \
                  UNION ALL \
                  SELECT coalesce(sum(value), 0) as amount, 'pending_outgoing_balance' as category \
-                 FROM outputs WHERE status = ? OR status = ? OR status = ?",
+                 FROM outputs WHERE account_name IS ? AND (status = ? OR status = ? OR status = ?)",
             )
                 // available_balance
+                .bind::<AccountName, _>(account_name)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::Unspent as i32)
                 // pending_incoming_balance
+                .bind::<AccountName, _>(account_name)
                 .bind::<diesel::sql_types::Integer, _>(OutputSource::Coinbase as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::EncumberedToBeReceived as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::ShortTermEncumberedToBeReceived as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::UnspentMinedUnconfirmed as i32)
                 // pending_outgoing_balance
+                .bind::<AccountName, _>(account_name)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::EncumberedToBeSpent as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::ShortTermEncumberedToBeSpent as i32)
                 .bind::<diesel::sql_types::Integer, _>(OutputStatus::SpentMinedUnconfirmed as i32);
@@ -815,6 +850,8 @@ impl OutputSql {
             received_in_tx_id: self.received_in_tx_id.map(|d| (d as u64).into()),
             spent_in_tx_id: self.spent_in_tx_id.map(|d| (d as u64).into()),
             payment_id,
+            label: self.label,
+            frozen: self.frozen != 0,
         })
     }
 }
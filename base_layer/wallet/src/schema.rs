@@ -1,5 +1,35 @@
 // @generated automatically by Diesel CLI.
 
+diesel::table! {
+    adaptor_swaps (output_hash) {
+        output_hash -> Binary,
+        role -> Integer,
+        status -> Integer,
+        amount -> BigInt,
+        swap_public_key -> Binary,
+        adaptor_point -> Binary,
+        public_nonce -> Binary,
+        pre_signature -> Binary,
+        refund_height -> BigInt,
+        lock_tx_id -> Nullable<BigInt>,
+        spend_tx_id -> Nullable<BigInt>,
+        signature_nonce -> Nullable<Binary>,
+        signature -> Nullable<Binary>,
+        adaptor_secret -> Nullable<Binary>,
+        timestamp -> Timestamp,
+    }
+}
+
+diesel::table! {
+    batch_recipients (tx_id, position) {
+        tx_id -> BigInt,
+        position -> Integer,
+        address -> Binary,
+        amount -> BigInt,
+        payment_id -> Binary,
+    }
+}
+
 diesel::table! {
     burnt_proofs (id) {
         id -> Integer,
@@ -37,6 +67,7 @@ diesel::table! {
         transaction_signature_nonce -> Binary,
         transaction_signature_key -> Binary,
         payment_id -> Nullable<Binary>,
+        replaced_by_tx_id -> Nullable<BigInt>,
     }
 }
 
@@ -65,6 +96,30 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    multisig_accounts (name) {
+        name -> Text,
+        threshold -> Integer,
+        public_keys -> Binary,
+        script_key_id -> Text,
+        view_key_id -> Text,
+        view_public_key -> Binary,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    multisig_outputs (hash) {
+        hash -> Binary,
+        account_name -> Text,
+        commitment -> Binary,
+        value -> BigInt,
+        mined_height -> BigInt,
+        spent_in_tx_id -> Nullable<BigInt>,
+        spent -> Integer,
+    }
+}
+
 diesel::table! {
     outbound_transactions (tx_id) {
         tx_id -> BigInt,
@@ -118,6 +173,43 @@ diesel::table! {
         source -> Integer,
         last_validation_timestamp -> Nullable<Timestamp>,
         payment_id -> Nullable<Binary>,
+        account_name -> Nullable<Text>,
+        label -> Nullable<Text>,
+        frozen -> Integer,
+    }
+}
+
+diesel::table! {
+    payment_schedules (id) {
+        id -> Integer,
+        destination -> Binary,
+        amount -> BigInt,
+        payment_id -> Binary,
+        fee_per_gram -> BigInt,
+        max_fee -> Nullable<BigInt>,
+        interval_secs -> BigInt,
+        next_payment -> Timestamp,
+        end_time -> Nullable<Timestamp>,
+        max_missed_payments -> Integer,
+        status -> Integer,
+        payments_made -> BigInt,
+        last_tx_id -> Nullable<BigInt>,
+        last_error -> Nullable<Text>,
+        failed_attempts -> Integer,
+        retry_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    prepared_transactions (tx_id) {
+        tx_id -> BigInt,
+        destination -> Binary,
+        amount -> BigInt,
+        fee -> BigInt,
+        change -> BigInt,
+        payment_id -> Binary,
+        timestamp -> Timestamp,
     }
 }
 
@@ -138,14 +230,37 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    transaction_labels (tx_id) {
+        tx_id -> BigInt,
+        label -> Text,
+    }
+}
+
+diesel::table! {
+    wallet_accounts (name) {
+        name -> Text,
+        account_index -> BigInt,
+        created_at -> Timestamp,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
+    adaptor_swaps,
+    batch_recipients,
     burnt_proofs,
     client_key_values,
     completed_transactions,
     inbound_transactions,
     known_one_sided_payment_scripts,
+    multisig_accounts,
+    multisig_outputs,
     outbound_transactions,
     outputs,
+    payment_schedules,
+    prepared_transactions,
     scanned_blocks,
+    transaction_labels,
+    wallet_accounts,
     wallet_settings,
 );
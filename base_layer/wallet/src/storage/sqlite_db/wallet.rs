@@ -760,6 +760,18 @@ fn derive_secondary_key(
     Ok((secondary_key, secondary_key_hash))
 }
 
+/// Derive a cipher from a passphrase and salt, for data that is kept outside of the database such as wallet backups.
+/// Returns the version of the `Argon2` parameters that were used along with the cipher.
+pub(crate) fn derive_passphrase_cipher(
+    passphrase: &SafePassword,
+    version: Option<u8>,
+    salt: &String,
+) -> Result<(u8, XChaCha20Poly1305), WalletStorageError> {
+    let argon2_params = Argon2Parameters::from_version(version)?;
+    let (key, _) = derive_secondary_key(passphrase, argon2_params.clone(), salt)?;
+    Ok((argon2_params.id, XChaCha20Poly1305::new(Key::from_slice(key.reveal()))))
+}
+
 /// Encrypt the main database key using the secondary key
 fn encrypt_main_key(
     secondary_key: &WalletSecondaryEncryptionKey,
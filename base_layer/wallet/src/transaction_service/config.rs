@@ -69,6 +69,15 @@ pub struct TransactionServiceConfig {
     /// This is the timeout period that will be used to re-submit transactions not found in the mempool
     #[serde(with = "serializers::seconds")]
     pub transaction_mempool_resubmission_window: Duration,
+    /// This is the interval at which payment schedules are checked for payments that are due
+    #[serde(with = "serializers::seconds")]
+    pub payment_schedule_check_interval: Duration,
+    /// If set to `true`, the wallet will periodically merge its small outputs into a single output while the network
+    /// fee rate is low. Which outputs are merged is configured by the output manager.
+    pub auto_consolidation_enabled: bool,
+    /// The interval at which the wallet checks whether its outputs should be consolidated
+    #[serde(with = "serializers::seconds")]
+    pub auto_consolidation_check_interval: Duration,
 }
 
 impl Default for TransactionServiceConfig {
@@ -87,6 +96,9 @@ impl Default for TransactionServiceConfig {
             transaction_routing_mechanism: TransactionRoutingMechanism::default(),
             transaction_event_channel_size: 1000,
             transaction_mempool_resubmission_window: Duration::from_secs(600),
+            payment_schedule_check_interval: Duration::from_secs(60),
+            auto_consolidation_enabled: false,
+            auto_consolidation_check_interval: Duration::from_secs(60 * 60),
         }
     }
 }
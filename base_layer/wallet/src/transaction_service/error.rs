@@ -27,7 +27,7 @@ use tari_common_sqlite::error::SqliteStorageError;
 use tari_common_types::{
     tari_address::TariAddressError,
     transaction::{TransactionConversionError, TransactionDirectionError, TxId},
-    types::FixedHashSizeError,
+    types::{FixedHashSizeError, HashOutput},
 };
 use tari_comms::{connectivity::ConnectivityError, peer_manager::node_id::NodeIdError, protocol::rpc::RpcError};
 use tari_comms_dht::outbound::DhtOutboundError;
@@ -48,7 +48,11 @@ use crate::{
     error::WalletStorageError,
     output_manager_service::error::OutputManagerError,
     transaction_service::{
-        storage::{database::DbKey, sqlite_db::CompletedTransactionConversionError},
+        storage::{
+            database::DbKey,
+            models::{AdaptorSwapRole, AdaptorSwapStatus},
+            sqlite_db::CompletedTransactionConversionError,
+        },
         utc::NegativeDurationError,
     },
 };
@@ -61,6 +65,20 @@ pub enum TransactionServiceError {
     InvalidNetwork,
     #[error("One-sided transaction error: `{0}`")]
     OneSidedTransactionError(String),
+    #[error("Adaptor swap error: `{0}`")]
+    AdaptorSwapError(String),
+    #[error("Fee bump error: `{0}`")]
+    FeeBumpError(String),
+    #[error("Batch payment error: `{0}`")]
+    BatchPaymentError(String),
+    #[error("Payment schedule error: `{0}`")]
+    PaymentScheduleError(String),
+    #[error("Offline signing error: `{0}`")]
+    OfflineSigningError(String),
+    #[error("This is a watch-only wallet that cannot spend funds, prepare an unsigned transaction instead")]
+    WatchOnlyWallet,
+    #[error("Cannot transfer funds from a wallet account to itself")]
+    TransferToSameWalletAccount,
     #[error("Transaction Protocol Error: `{0}`")]
     TransactionProtocolError(#[from] TransactionProtocolError),
     #[error("The message being processed is not recognized by the Transaction Manager")]
@@ -284,6 +302,18 @@ pub enum TransactionStorageError {
     SqliteStorageError(#[from] SqliteStorageError),
     #[error("Coinbase transactions are not supported in the wallet")]
     CoinbaseNotSupported,
+    #[error("Adaptor swap of output `{0}` not found")]
+    AdaptorSwapNotFound(HashOutput),
+    #[error("An adaptor swap cannot go from `{from}` to `{to}` for the {role}")]
+    InvalidAdaptorSwapTransition {
+        role: AdaptorSwapRole,
+        from: AdaptorSwapStatus,
+        to: AdaptorSwapStatus,
+    },
+    #[error("Payment schedule `{0}` not found")]
+    PaymentScheduleNotFound(u32),
+    #[error("No transaction `{0}` was prepared for offline signing")]
+    PreparedTransactionNotFound(TxId),
 }
 
 impl From<ByteArrayError> for TransactionStorageError {
@@ -49,11 +49,12 @@ use tari_core::{
             Transaction,
             TransactionOutput,
         },
+        transaction_protocol::adaptor_signature::AdaptorSignature,
     },
 };
 use tari_crypto::ristretto::pedersen::PedersenCommitment;
 use tari_max_size::{MaxSizeBytes, MaxSizeString};
-use tari_script::CheckSigSchnorrSignature;
+use tari_script::{CheckSigSchnorrSignature, ExecutionStack};
 use tari_service_framework::reply_channel::SenderService;
 use tari_utilities::hex::Hex;
 use tokio::sync::broadcast;
@@ -63,10 +64,15 @@ use crate::{
     output_manager_service::{service::UseOutput, UtxoSelectionCriteria},
     transaction_service::{
         error::TransactionServiceError,
+        offline_signing::{SignedTransaction, UnsignedTransaction},
         storage::models::{
+            AdaptorSwap,
+            BatchRecipient,
             CompletedTransaction,
             InboundTransaction,
             OutboundTransaction,
+            PaymentSchedule,
+            TransactionServiceBackup,
             TxCancellationReason,
             WalletTransaction,
         },
@@ -102,6 +108,15 @@ pub enum TransactionServiceRequest {
         payment_id: PaymentId,
         claim_public_key: Option<PublicKey>,
     },
+    SendToMultisigAccount {
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        view_public_key: PublicKey,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    },
     EncumberAggregateUtxo {
         fee_per_gram: MicroMinotari,
         expected_commitment: PedersenCommitment,
@@ -158,6 +173,12 @@ pub enum TransactionServiceRequest {
         fee_per_gram: MicroMinotari,
         payment_id: PaymentId,
     },
+    SendBatch {
+        recipients: Vec<BatchRecipient>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    },
     SendOneSidedToStealthAddressTransaction {
         destination: TariAddress,
         amount: MicroMinotari,
@@ -177,7 +198,64 @@ pub enum TransactionServiceRequest {
         MicroMinotari,
         PaymentId,
     ),
+    SendAdaptorSwapTransaction {
+        destination: TariAddress,
+        amount: MicroMinotari,
+        adaptor_point: PublicKey,
+        refund_delay: u64,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    },
+    ClaimAdaptorSwap {
+        output_hash: HashOutput,
+        adaptor_signature: Box<AdaptorSignature>,
+        adaptor_secret: PrivateKey,
+        fee_per_gram: MicroMinotari,
+    },
+    RevealAdaptorSwapSecret {
+        output_hash: HashOutput,
+        input_data: ExecutionStack,
+    },
+    RefundAdaptorSwap {
+        output_hash: HashOutput,
+        fee_per_gram: MicroMinotari,
+    },
+    GetAdaptorSwaps,
     CancelTransaction(TxId),
+    BumpFee {
+        tx_id: TxId,
+        fee_per_gram: MicroMinotari,
+    },
+    PrepareUnsignedTransaction {
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    },
+    SignUnsignedTransaction(Box<UnsignedTransaction>),
+    FinaliseSignedTransaction(Box<SignedTransaction>),
+    AbandonUnsignedTransaction(TxId),
+    TransferBetweenWalletAccounts {
+        from_account: Option<String>,
+        to_account: Option<String>,
+        amount: MicroMinotari,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    },
+    GetWalletAccountTransactions(Option<String>),
+    SetTransactionLabel {
+        tx_id: TxId,
+        label: Option<String>,
+    },
+    GetTransactionLabels,
+    GetBatchRecipients(TxId),
+    GetBackupData,
+    ImportBackupData(Box<TransactionServiceBackup>),
+    AddPaymentSchedule(PaymentSchedule),
+    GetPaymentSchedules,
+    CancelPaymentSchedule(u32),
     ImportUtxoWithStatus {
         amount: MicroMinotari,
         source_address: TariAddress,
@@ -203,6 +281,34 @@ pub enum TransactionServiceRequest {
     },
 }
 
+impl TransactionServiceRequest {
+    /// Whether the request spends outputs of this wallet, which requires the private spend key
+    pub fn spends_funds(&self) -> bool {
+        matches!(
+            self,
+            Self::SendTransaction { .. } |
+                Self::BurnTari { .. } |
+                Self::SendToMultisigAccount { .. } |
+                Self::EncumberAggregateUtxo { .. } |
+                Self::SpendBackupPreMineUtxo { .. } |
+                Self::RegisterValidatorNode { .. } |
+                Self::RegisterCodeTemplate { .. } |
+                Self::SendOneSidedTransaction { .. } |
+                Self::SendOneSidedToStealthAddressTransaction { .. } |
+                Self::SendBatch { .. } |
+                Self::AddPaymentSchedule(_) |
+                Self::ScrapeWallet { .. } |
+                Self::SendShaAtomicSwapTransaction(..) |
+                Self::SendAdaptorSwapTransaction { .. } |
+                Self::ClaimAdaptorSwap { .. } |
+                Self::RefundAdaptorSwap { .. } |
+                Self::BumpFee { .. } |
+                Self::SignUnsignedTransaction(_) |
+                Self::TransferBetweenWalletAccounts { .. }
+        )
+    }
+}
+
 impl fmt::Display for TransactionServiceRequest {
     #[allow(clippy::too_many_lines)]
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -236,6 +342,20 @@ impl fmt::Display for TransactionServiceRequest {
                 amount, destination, payment_id
             ),
             Self::BurnTari { amount, payment_id, .. } => write!(f, "Burning Tari ({}, {})", amount, payment_id),
+            Self::SendToMultisigAccount {
+                threshold,
+                public_keys,
+                amount,
+                payment_id,
+                ..
+            } => write!(
+                f,
+                "SendToMultisigAccount ({}-of-{}, {}, {})",
+                threshold,
+                public_keys.len(),
+                amount,
+                payment_id
+            ),
             Self::SpendBackupPreMineUtxo {
                 fee_per_gram,
                 output_hash,
@@ -344,6 +464,7 @@ impl fmt::Display for TransactionServiceRequest {
                 "SendOneSidedTransaction (to {}, {}, {})",
                 destination, amount, payment_id
             ),
+            Self::SendBatch { recipients, .. } => write!(f, "SendBatch ({} recipients)", recipients.len()),
             Self::SendOneSidedToStealthAddressTransaction {
                 destination,
                 amount,
@@ -357,7 +478,72 @@ impl fmt::Display for TransactionServiceRequest {
             Self::SendShaAtomicSwapTransaction(k, _, v, _, id) => {
                 write!(f, "SendShaAtomicSwapTransaction (to {}, {}, {})", k, v, id)
             },
+            Self::SendAdaptorSwapTransaction {
+                destination,
+                amount,
+                refund_delay,
+                ..
+            } => write!(
+                f,
+                "SendAdaptorSwapTransaction (to {}, {}, refund delay: {})",
+                destination, amount, refund_delay
+            ),
+            Self::ClaimAdaptorSwap { output_hash, .. } => write!(f, "ClaimAdaptorSwap ({})", output_hash),
+            Self::RevealAdaptorSwapSecret { output_hash, .. } => {
+                write!(f, "RevealAdaptorSwapSecret ({})", output_hash)
+            },
+            Self::RefundAdaptorSwap { output_hash, .. } => write!(f, "RefundAdaptorSwap ({})", output_hash),
+            Self::GetAdaptorSwaps => write!(f, "GetAdaptorSwaps"),
             Self::CancelTransaction(t) => write!(f, "CancelTransaction ({})", t),
+            Self::BumpFee { tx_id, fee_per_gram } => {
+                write!(f, "BumpFee (tx_id: {}, fee_per_gram: {})", tx_id, fee_per_gram)
+            },
+            Self::PrepareUnsignedTransaction {
+                destination, amount, ..
+            } => write!(f, "PrepareUnsignedTransaction (to {}, {})", destination, amount),
+            Self::SignUnsignedTransaction(t) => write!(f, "SignUnsignedTransaction ({})", t.tx_id),
+            Self::FinaliseSignedTransaction(t) => write!(f, "FinaliseSignedTransaction ({})", t.tx_id),
+            Self::AbandonUnsignedTransaction(t) => write!(f, "AbandonUnsignedTransaction ({})", t),
+            Self::TransferBetweenWalletAccounts {
+                from_account,
+                to_account,
+                amount,
+                ..
+            } => write!(
+                f,
+                "TransferBetweenWalletAccounts (from {} to {}, {})",
+                from_account.as_deref().unwrap_or("default"),
+                to_account.as_deref().unwrap_or("default"),
+                amount
+            ),
+            Self::GetWalletAccountTransactions(account) => write!(
+                f,
+                "GetWalletAccountTransactions ({})",
+                account.as_deref().unwrap_or("default")
+            ),
+            Self::SetTransactionLabel { tx_id, label } => write!(
+                f,
+                "SetTransactionLabel ({}, {})",
+                tx_id,
+                label.as_deref().unwrap_or("<none>")
+            ),
+            Self::GetTransactionLabels => write!(f, "GetTransactionLabels"),
+            Self::GetBatchRecipients(tx_id) => write!(f, "GetBatchRecipients ({})", tx_id),
+            Self::GetBackupData => write!(f, "GetBackupData"),
+            Self::ImportBackupData(backup) => write!(
+                f,
+                "ImportBackupData ({} transactions)",
+                backup.completed_transactions.len()
+            ),
+            Self::AddPaymentSchedule(schedule) => write!(
+                f,
+                "AddPaymentSchedule (to {}, {}, every {}s)",
+                schedule.destination,
+                schedule.amount,
+                schedule.interval.as_secs()
+            ),
+            Self::GetPaymentSchedules => write!(f, "GetPaymentSchedules"),
+            Self::CancelPaymentSchedule(id) => write!(f, "CancelPaymentSchedule ({})", id),
             Self::ImportUtxoWithStatus {
                 amount,
                 source_address,
@@ -433,7 +619,21 @@ pub enum TransactionServiceResponse {
     ValidationStarted(OperationId),
     CompletedTransactionValidityChanged,
     ShaAtomicSwapTransactionSent(Box<(TxId, PublicKey, TransactionOutput)>),
+    AdaptorSwapTransactionSent(Box<(TxId, AdaptorSignature, TransactionOutput)>),
+    AdaptorSwapSecret(PrivateKey),
+    AdaptorSwaps(Vec<AdaptorSwap>),
     FeePerGramStatsPerBlock(FeePerGramStatsResponse),
+    UnsignedTransactionPrepared(Box<UnsignedTransaction>),
+    TransactionSigned(Box<SignedTransaction>),
+    UnsignedTransactionAbandoned,
+    TransactionLabelSet,
+    TransactionLabels(HashMap<TxId, String>),
+    BatchRecipients(Vec<BatchRecipient>),
+    BackupData(Box<TransactionServiceBackup>),
+    BackupDataImported(usize),
+    PaymentScheduleAdded(u32),
+    PaymentSchedules(Vec<PaymentSchedule>),
+    PaymentScheduleCancelled,
 }
 
 #[derive(Clone, Debug, Hash, PartialEq, Eq, Default)]
@@ -488,6 +688,14 @@ pub enum TransactionEvent {
     TransactionValidationStateChanged(OperationId),
     TransactionValidationCompleted(OperationId),
     TransactionValidationFailed(OperationId, u64),
+    ScheduledPaymentSent {
+        schedule_id: u32,
+        tx_id: TxId,
+    },
+    ScheduledPaymentFailed {
+        schedule_id: u32,
+        reason: String,
+    },
     Error(String),
 }
 
@@ -567,6 +775,12 @@ impl fmt::Display for TransactionEvent {
             TransactionEvent::TransactionValidationFailed(operation_id, reason) => {
                 write!(f, "Transaction validation(#{operation_id}) failed: {reason}")
             },
+            TransactionEvent::ScheduledPaymentSent { schedule_id, tx_id } => {
+                write!(f, "ScheduledPaymentSent for schedule #{schedule_id}: {tx_id}")
+            },
+            TransactionEvent::ScheduledPaymentFailed { schedule_id, reason } => {
+                write!(f, "ScheduledPaymentFailed for schedule #{schedule_id}: {reason}")
+            },
         }
     }
 }
@@ -738,6 +952,31 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Pays all recipients with one-sided stealth address outputs in a single transaction, with one change output. The
+    /// transaction is recorded with the first recipient as its destination and the total amount paid, and all
+    /// recipients can be retrieved with [`Self::get_batch_recipients`].
+    pub async fn send_batch(
+        &mut self,
+        recipients: Vec<BatchRecipient>,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SendBatch {
+                recipients,
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     /// Burns the given amount of Tari from the wallet
     pub async fn burn_tari(
         &mut self,
@@ -763,6 +1002,36 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Sends `amount` to the N-of-M multi-signature account with `public_keys` and `view_public_key`, returning the
+    /// transaction ID and the hash of the account's output
+    pub async fn send_to_multisig_account(
+        &mut self,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        view_public_key: PublicKey,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    ) -> Result<(TxId, FixedHash), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SendToMultisigAccount {
+                threshold,
+                public_keys,
+                view_public_key,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSentWithOutputHash(tx_id, output_hash) => Ok((tx_id, output_hash)),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     #[allow(clippy::mutable_key_type)]
     pub async fn encumber_aggregate_utxo(
         &mut self,
@@ -911,6 +1180,240 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Replaces an unmined one-sided or self-spend transaction with one that spends the same inputs at a higher
+    /// `fee_per_gram`. Returns the TxId of the replacement transaction.
+    pub async fn bump_fee(
+        &mut self,
+        tx_id: TxId,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::BumpFee { tx_id, fee_per_gram })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Prepares a one-sided transaction to `destination` that is signed by an offline wallet holding the spend key.
+    /// The selected inputs stay encumbered until the signed transaction is finalised or the prepared transaction is
+    /// abandoned.
+    pub async fn prepare_unsigned_transaction(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    ) -> Result<UnsignedTransaction, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::PrepareUnsignedTransaction {
+                destination,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+            })
+            .await??
+        {
+            TransactionServiceResponse::UnsignedTransactionPrepared(unsigned_transaction) => Ok(*unsigned_transaction),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Signs an unsigned transaction exported by a view-only wallet. The signed transaction is returned to be
+    /// finalised by that wallet and is not stored or broadcast by this one.
+    pub async fn sign_unsigned_transaction(
+        &mut self,
+        unsigned_transaction: UnsignedTransaction,
+    ) -> Result<SignedTransaction, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SignUnsignedTransaction(Box::new(
+                unsigned_transaction,
+            )))
+            .await??
+        {
+            TransactionServiceResponse::TransactionSigned(signed_transaction) => Ok(*signed_transaction),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Finalises and broadcasts a transaction signed by an offline wallet
+    pub async fn finalise_signed_transaction(
+        &mut self,
+        signed_transaction: SignedTransaction,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::FinaliseSignedTransaction(Box::new(
+                signed_transaction,
+            )))
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Abandons a transaction prepared for offline signing that will not be finalised, releasing its inputs
+    pub async fn abandon_unsigned_transaction(&mut self, tx_id: TxId) -> Result<(), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::AbandonUnsignedTransaction(tx_id))
+            .await??
+        {
+            TransactionServiceResponse::UnsignedTransactionAbandoned => Ok(()),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Sends funds between two of this wallet's accounts as a one-sided payment to the address of `to_account`. `None`
+    /// refers to the wallet's default account.
+    pub async fn transfer_between_wallet_accounts(
+        &mut self,
+        from_account: Option<String>,
+        to_account: Option<String>,
+        amount: MicroMinotari,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::TransferBetweenWalletAccounts {
+                from_account,
+                to_account,
+                amount,
+                fee_per_gram,
+                payment_id,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the completed transactions that paid to or spent from the named account. For `None`, returns the
+    /// completed transactions that did not involve any named account.
+    pub async fn get_wallet_account_transactions(
+        &mut self,
+        account: Option<String>,
+    ) -> Result<HashMap<TxId, CompletedTransaction>, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GetWalletAccountTransactions(account))
+            .await??
+        {
+            TransactionServiceResponse::CompletedTransactions(c) => Ok(c),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Sets the user label of a transaction, or clears it if `label` is `None`
+    pub async fn set_transaction_label(
+        &mut self,
+        tx_id: TxId,
+        label: Option<String>,
+    ) -> Result<(), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SetTransactionLabel { tx_id, label })
+            .await??
+        {
+            TransactionServiceResponse::TransactionLabelSet => Ok(()),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the user labels of all labelled transactions
+    pub async fn get_transaction_labels(&mut self) -> Result<HashMap<TxId, String>, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GetTransactionLabels)
+            .await??
+        {
+            TransactionServiceResponse::TransactionLabels(labels) => Ok(labels),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns all recipients of a batch payment, in the order they were paid, or none if the transaction is not a
+    /// batch payment
+    pub async fn get_batch_recipients(&mut self, tx_id: TxId) -> Result<Vec<BatchRecipient>, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GetBatchRecipients(tx_id))
+            .await??
+        {
+            TransactionServiceResponse::BatchRecipients(recipients) => Ok(recipients),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns the transaction service data to include in a wallet backup
+    pub async fn get_backup_data(&mut self) -> Result<TransactionServiceBackup, TransactionServiceError> {
+        match self.handle.call(TransactionServiceRequest::GetBackupData).await?? {
+            TransactionServiceResponse::BackupData(backup) => Ok(*backup),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Adds the transaction service data from a wallet backup and returns the number of transactions added. Data
+    /// that the wallet already has is skipped.
+    pub async fn import_backup_data(
+        &mut self,
+        backup: TransactionServiceBackup,
+    ) -> Result<usize, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::ImportBackupData(Box::new(backup)))
+            .await??
+        {
+            TransactionServiceResponse::BackupDataImported(count) => Ok(count),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Adds a payment schedule, which the wallet pays while it is running, and returns its id
+    pub async fn add_payment_schedule(&mut self, schedule: PaymentSchedule) -> Result<u32, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::AddPaymentSchedule(schedule))
+            .await??
+        {
+            TransactionServiceResponse::PaymentScheduleAdded(id) => Ok(id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Returns all payment schedules, including completed and cancelled ones
+    pub async fn get_payment_schedules(&mut self) -> Result<Vec<PaymentSchedule>, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::GetPaymentSchedules)
+            .await??
+        {
+            TransactionServiceResponse::PaymentSchedules(schedules) => Ok(schedules),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Cancels an active payment schedule, so that no further payments are made
+    pub async fn cancel_payment_schedule(&mut self, id: u32) -> Result<(), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::CancelPaymentSchedule(id))
+            .await??
+        {
+            TransactionServiceResponse::PaymentScheduleCancelled => Ok(()),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     pub async fn get_pending_inbound_transactions(
         &mut self,
     ) -> Result<HashMap<TxId, InboundTransaction>, TransactionServiceError> {
@@ -1189,6 +1692,104 @@ impl TransactionServiceHandle {
         }
     }
 
+    /// Locks `amount` in an adaptor signature atomic swap with `destination`, returning the adaptor signature to hand
+    /// to the destination along with the swap output
+    pub async fn send_adaptor_swap_transaction(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        adaptor_point: PublicKey,
+        refund_delay: u64,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    ) -> Result<(TxId, AdaptorSignature, TransactionOutput), TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::SendAdaptorSwapTransaction {
+                destination,
+                amount,
+                adaptor_point,
+                refund_delay,
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+            })
+            .await??
+        {
+            TransactionServiceResponse::AdaptorSwapTransactionSent(boxed) => Ok(*boxed),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Claims an adaptor signature swap output sent to this wallet
+    pub async fn claim_adaptor_swap(
+        &mut self,
+        output_hash: HashOutput,
+        adaptor_signature: AdaptorSignature,
+        adaptor_secret: PrivateKey,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::ClaimAdaptorSwap {
+                output_hash,
+                adaptor_signature: Box::new(adaptor_signature),
+                adaptor_secret,
+                fee_per_gram,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Extracts the adaptor secret of a swap this wallet initiated from the input data of the input that claimed it
+    pub async fn reveal_adaptor_swap_secret(
+        &mut self,
+        output_hash: HashOutput,
+        input_data: ExecutionStack,
+    ) -> Result<PrivateKey, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::RevealAdaptorSwapSecret {
+                output_hash,
+                input_data,
+            })
+            .await??
+        {
+            TransactionServiceResponse::AdaptorSwapSecret(adaptor_secret) => Ok(adaptor_secret),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    /// Refunds the output of a swap this wallet initiated, once the refund height has been reached
+    pub async fn refund_adaptor_swap(
+        &mut self,
+        output_hash: HashOutput,
+        fee_per_gram: MicroMinotari,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self
+            .handle
+            .call(TransactionServiceRequest::RefundAdaptorSwap {
+                output_hash,
+                fee_per_gram,
+            })
+            .await??
+        {
+            TransactionServiceResponse::TransactionSent(tx_id) => Ok(tx_id),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
+    pub async fn get_adaptor_swaps(&mut self) -> Result<Vec<AdaptorSwap>, TransactionServiceError> {
+        match self.handle.call(TransactionServiceRequest::GetAdaptorSwaps).await?? {
+            TransactionServiceResponse::AdaptorSwaps(swaps) => Ok(swaps),
+            _ => Err(TransactionServiceError::UnexpectedApiResponse),
+        }
+    }
+
     /// Query the base node for the fee per gram stats of the next {count} blocks.
     pub async fn get_fee_per_gram_stats_per_block(
         &mut self,
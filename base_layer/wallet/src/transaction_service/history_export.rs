@@ -0,0 +1,360 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Exports the transaction history of a wallet for bookkeeping. All amounts are in µT, so that they can be valued in
+//! any currency by the user's own accounting tools.
+
+use std::{collections::HashMap, convert::TryFrom, fmt, fmt::Display, str::FromStr};
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use tari_common_types::{
+    tari_address::TariAddress,
+    transaction::{TransactionDirection, TransactionStatus, TxId},
+};
+use tari_core::transactions::tari_amount::MicroMinotari;
+
+use crate::transaction_service::{error::TransactionServiceError, storage::models::CompletedTransaction};
+
+/// The columns of a CSV transaction history export, in the order of the fields of [`TransactionHistoryEntry`]
+const CSV_HEADER: &str = "tx_id,timestamp,mined_timestamp,mined_height,confirmations,direction,status,cancelled,\
+                          amount,fee,counterparty,payment_id,label,running_balance";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransactionHistoryFormat {
+    Csv,
+    Json,
+}
+
+impl FromStr for TransactionHistoryFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "csv" => Ok(Self::Csv),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "Unknown transaction history format '{}', expected 'csv' or 'json'",
+                s
+            )),
+        }
+    }
+}
+
+impl Display for TransactionHistoryFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Csv => write!(f, "csv"),
+            Self::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// A completed or cancelled transaction, as it appears in an export of the transaction history
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionHistoryEntry {
+    pub tx_id: TxId,
+    pub timestamp: DateTime<Utc>,
+    pub mined_timestamp: Option<DateTime<Utc>>,
+    pub mined_height: Option<u64>,
+    pub confirmations: Option<u64>,
+    pub direction: String,
+    pub status: String,
+    pub cancelled: bool,
+    pub amount: u64,
+    pub fee: u64,
+    /// The address of the sender of an inbound transaction, or the recipient of an outbound transaction
+    pub counterparty: String,
+    pub payment_id: String,
+    pub label: Option<String>,
+    /// The sum of the effects of this and all earlier transactions on the wallet balance. Outbound transactions
+    /// reduce the balance by their amount and fee, and payments to self only by their fee. Cancelled, rejected and
+    /// replaced transactions, and transactions of unknown direction, do not change it.
+    pub running_balance: i64,
+}
+
+/// Builds the transaction history from the completed and cancelled transactions of the wallet, oldest first.
+/// Confirmations are counted from `tip_height` if it is known. Payments to self are recorded from and to the wallet's
+/// `interactive_address`.
+pub fn transaction_history(
+    mut transactions: Vec<CompletedTransaction>,
+    labels: &HashMap<TxId, String>,
+    tip_height: Option<u64>,
+    interactive_address: &TariAddress,
+) -> Vec<TransactionHistoryEntry> {
+    transactions.sort_by_key(|tx| (tx.timestamp, tx.tx_id.as_u64()));
+
+    let mut running_balance = 0i64;
+    transactions
+        .into_iter()
+        .map(|tx| {
+            running_balance = running_balance.saturating_add(balance_change(&tx, interactive_address));
+            let counterparty = match tx.direction {
+                TransactionDirection::Inbound => &tx.source_address,
+                _ => &tx.destination_address,
+            };
+            let confirmations = match (tx.mined_height, tip_height) {
+                (Some(mined_height), Some(tip_height)) => Some(tip_height.saturating_sub(mined_height) + 1),
+                _ => tx.confirmations,
+            };
+            TransactionHistoryEntry {
+                tx_id: tx.tx_id,
+                timestamp: tx.timestamp,
+                mined_timestamp: tx.mined_timestamp,
+                mined_height: tx.mined_height,
+                confirmations,
+                direction: tx.direction.to_string(),
+                status: tx.status.to_string(),
+                cancelled: tx.cancelled.is_some(),
+                amount: tx.amount.as_u64(),
+                fee: tx.fee.as_u64(),
+                counterparty: counterparty.to_base58(),
+                payment_id: tx.payment_id.user_data_as_string(),
+                label: labels.get(&tx.tx_id).cloned(),
+                running_balance,
+            }
+        })
+        .collect()
+}
+
+/// Formats the transaction history as CSV, with a header row, or as a JSON array
+pub fn format_transaction_history(
+    history: &[TransactionHistoryEntry],
+    format: TransactionHistoryFormat,
+) -> Result<String, TransactionServiceError> {
+    match format {
+        TransactionHistoryFormat::Json => Ok(serde_json::to_string_pretty(history)?),
+        TransactionHistoryFormat::Csv => {
+            let mut csv = String::from(CSV_HEADER);
+            csv.push('\n');
+            for entry in history {
+                let row = [
+                    entry.tx_id.to_string(),
+                    entry.timestamp.to_rfc3339(),
+                    entry.mined_timestamp.map(|t| t.to_rfc3339()).unwrap_or_default(),
+                    entry.mined_height.map(|h| h.to_string()).unwrap_or_default(),
+                    entry.confirmations.map(|c| c.to_string()).unwrap_or_default(),
+                    entry.direction.clone(),
+                    entry.status.clone(),
+                    entry.cancelled.to_string(),
+                    entry.amount.to_string(),
+                    entry.fee.to_string(),
+                    entry.counterparty.clone(),
+                    entry.payment_id.clone(),
+                    entry.label.clone().unwrap_or_default(),
+                    entry.running_balance.to_string(),
+                ];
+                csv.push_str(
+                    &row.iter()
+                        .map(String::as_str)
+                        .map(csv_field)
+                        .collect::<Vec<_>>()
+                        .join(","),
+                );
+                csv.push('\n');
+            }
+            Ok(csv)
+        },
+    }
+}
+
+fn balance_change(tx: &CompletedTransaction, interactive_address: &TariAddress) -> i64 {
+    if tx.cancelled.is_some() ||
+        tx.replaced_by_tx_id.is_some() ||
+        matches!(
+            tx.status,
+            TransactionStatus::Rejected | TransactionStatus::CoinbaseNotInBlockChain
+        )
+    {
+        return 0;
+    }
+    let amount = signed(tx.amount);
+    let fee = signed(tx.fee);
+    let is_payment_to_self =
+        tx.source_address == *interactive_address && tx.destination_address == *interactive_address;
+    match tx.direction {
+        // A payment to self is recorded as an inbound transaction, and only costs its fee
+        TransactionDirection::Inbound if is_payment_to_self => -fee,
+        TransactionDirection::Inbound => amount,
+        // A one-sided payment to the wallet's own address, e.g. a transfer between wallet accounts, is counted in full,
+        // as the output it pays is imported as a separate inbound transaction once it is scanned
+        TransactionDirection::Outbound => -amount.saturating_add(fee),
+        TransactionDirection::Unknown => 0,
+    }
+}
+
+// No amount exceeds the total supply, which is well below `i64::MAX` µT
+fn signed(value: MicroMinotari) -> i64 {
+    i64::try_from(value.as_u64()).unwrap_or(i64::MAX)
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains(|c| matches!(c, ',' | '"' | '\n' | '\r')) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use chrono::{Duration, Utc};
+    use rand::rngs::OsRng;
+    use tari_common::configuration::Network;
+    use tari_common_types::{
+        tari_address::TariAddress,
+        transaction::{TransactionDirection, TransactionStatus},
+        types::{PrivateKey, PublicKey, Signature},
+    };
+    use tari_core::transactions::{
+        tari_amount::MicroMinotari,
+        transaction_components::{encrypted_data::PaymentId, Transaction},
+    };
+    use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey as SecretKeyTrait};
+
+    use super::{format_transaction_history, transaction_history, TransactionHistoryFormat, CSV_HEADER};
+    use crate::transaction_service::storage::models::{CompletedTransaction, TxCancellationReason};
+
+    fn random_address() -> TariAddress {
+        TariAddress::new_dual_address_with_default_features(
+            PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+            PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+            Network::LocalNet,
+        )
+    }
+
+    fn completed_tx(
+        tx_id: u64,
+        source_address: TariAddress,
+        destination_address: TariAddress,
+        direction: TransactionDirection,
+        amount: u64,
+        fee: u64,
+    ) -> CompletedTransaction {
+        CompletedTransaction {
+            tx_id: tx_id.into(),
+            source_address,
+            destination_address,
+            amount: MicroMinotari::from(amount),
+            fee: MicroMinotari::from(fee),
+            transaction: Transaction::new(vec![], vec![], vec![], PrivateKey::default(), PrivateKey::default()),
+            status: TransactionStatus::MinedConfirmed,
+            timestamp: Utc::now() - Duration::hours(10) + Duration::hours(i64::try_from(tx_id).unwrap()),
+            cancelled: None,
+            direction,
+            send_count: 0,
+            last_send_timestamp: None,
+            transaction_signature: Signature::default(),
+            confirmations: None,
+            mined_height: Some(100 + tx_id),
+            mined_in_block: None,
+            mined_timestamp: None,
+            payment_id: PaymentId::Empty,
+            replaced_by_tx_id: None,
+        }
+    }
+
+    #[test]
+    fn it_exports_the_history_with_a_running_balance() {
+        let own_address = random_address();
+        let one_sided_address = random_address();
+        let other_address = random_address();
+        let mut cancelled = completed_tx(
+            3,
+            own_address.clone(),
+            other_address.clone(),
+            TransactionDirection::Outbound,
+            100,
+            10,
+        );
+        cancelled.cancelled = Some(TxCancellationReason::UserCancelled);
+        let mut replaced = completed_tx(
+            5,
+            own_address.clone(),
+            other_address.clone(),
+            TransactionDirection::Outbound,
+            200,
+            10,
+        );
+        replaced.replaced_by_tx_id = Some(6u64.into());
+        let transactions = vec![
+            // A payment to self
+            completed_tx(
+                4,
+                own_address.clone(),
+                own_address.clone(),
+                TransactionDirection::Inbound,
+                500,
+                5,
+            ),
+            // A transfer between wallet accounts, and the output it paid once scanned
+            completed_tx(
+                7,
+                one_sided_address.clone(),
+                one_sided_address.clone(),
+                TransactionDirection::Outbound,
+                200,
+                5,
+            ),
+            completed_tx(
+                8,
+                own_address.clone(),
+                one_sided_address.clone(),
+                TransactionDirection::Inbound,
+                200,
+                0,
+            ),
+            completed_tx(
+                2,
+                own_address.clone(),
+                other_address.clone(),
+                TransactionDirection::Outbound,
+                300,
+                20,
+            ),
+            cancelled,
+            replaced,
+            completed_tx(
+                6,
+                own_address.clone(),
+                own_address.clone(),
+                TransactionDirection::Unknown,
+                100,
+                10,
+            ),
+            completed_tx(
+                1,
+                other_address.clone(),
+                own_address.clone(),
+                TransactionDirection::Inbound,
+                1000,
+                10,
+            ),
+        ];
+        let labels = HashMap::from([(1u64.into(), "Salary, \"March\"".to_string())]);
+
+        let history = transaction_history(transactions, &labels, Some(110), &own_address);
+        let balances = history.iter().map(|e| e.running_balance).collect::<Vec<_>>();
+        assert_eq!(balances, vec![1000, 680, 680, 675, 675, 675, 470, 670]);
+        assert_eq!(history[0].counterparty, other_address.to_base58());
+        assert_eq!(history[1].counterparty, other_address.to_base58());
+        assert_eq!(history[0].confirmations, Some(10));
+        assert!(history[2].cancelled);
+        assert_eq!(history[4].tx_id.as_u64(), 5);
+        assert_eq!(history[5].direction, TransactionDirection::Unknown.to_string());
+
+        let csv = format_transaction_history(&history, TransactionHistoryFormat::Csv).unwrap();
+        let mut lines = csv.lines();
+        assert_eq!(lines.next(), Some(CSV_HEADER));
+        assert!(lines.next().unwrap().contains(",\"Salary, \"\"March\"\"\",1000"));
+        assert_eq!(lines.count(), 7);
+
+        let json = format_transaction_history(&history, TransactionHistoryFormat::Json).unwrap();
+        let entries: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(entries.len(), 8);
+        assert_eq!(entries[7]["running_balance"], 670);
+    }
+}
@@ -68,6 +68,8 @@ use crate::{
 pub mod config;
 pub mod error;
 pub mod handle;
+pub mod history_export;
+pub mod offline_signing;
 pub mod protocols;
 pub mod service;
 pub mod storage;
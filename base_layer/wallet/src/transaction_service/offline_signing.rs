@@ -0,0 +1,74 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Unsigned and signed transactions that are passed between an online view-only wallet and an offline wallet that
+//! holds the spend key. The online wallet selects the inputs and works out the fee and change, the offline wallet
+//! builds and signs the one-sided transaction, and the online wallet then finalises and broadcasts it.
+
+use serde::{Deserialize, Serialize};
+use tari_common_types::{tari_address::TariAddress, transaction::TxId, types::PrivateKey};
+use tari_core::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::{encrypted_data::PaymentId, Transaction, WalletOutput},
+};
+
+use crate::transaction_service::error::TransactionServiceError;
+
+/// A wallet output together with the commitment mask that opens it. The commitment mask is not a spend secret, but
+/// the key id of the output is only meaningful to the wallet that created it, so the mask is carried along for the
+/// other wallet to import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfflineWalletOutput {
+    pub output: WalletOutput,
+    pub commitment_mask: PrivateKey,
+}
+
+/// A one-sided transaction prepared by a view-only wallet, waiting to be signed by the wallet holding the spend key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsignedTransaction {
+    pub tx_id: TxId,
+    pub destination: TariAddress,
+    pub amount: MicroMinotari,
+    pub fee_per_gram: MicroMinotari,
+    pub fee: MicroMinotari,
+    pub change: MicroMinotari,
+    pub payment_id: PaymentId,
+    pub inputs: Vec<OfflineWalletOutput>,
+}
+
+impl UnsignedTransaction {
+    pub fn input_value(&self) -> MicroMinotari {
+        self.inputs.iter().map(|i| i.output.value).sum()
+    }
+
+    /// Checks that the inputs pay exactly for the amount, fee and change
+    pub fn validate(&self) -> Result<(), TransactionServiceError> {
+        if self.inputs.is_empty() {
+            return Err(TransactionServiceError::OfflineSigningError(
+                "The unsigned transaction has no inputs".to_string(),
+            ));
+        }
+        if self.input_value() != self.amount + self.fee + self.change {
+            return Err(TransactionServiceError::OfflineSigningError(format!(
+                "The inputs ({}) do not add up to the amount ({}), fee ({}) and change ({})",
+                self.input_value(),
+                self.amount,
+                self.fee,
+                self.change
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// A transaction signed by the wallet holding the spend key, to be finalised and broadcast by the view-only wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub tx_id: TxId,
+    pub destination: TariAddress,
+    pub amount: MicroMinotari,
+    pub fee: MicroMinotari,
+    pub payment_id: PaymentId,
+    pub transaction: Transaction,
+    pub change_output: Option<OfflineWalletOutput>,
+}
@@ -39,7 +39,7 @@ use tari_common_types::{
     key_branches::TransactionKeyManagerBranch,
     tari_address::{TariAddress, TariAddressFeatures},
     transaction::{ImportStatus, TransactionDirection, TransactionStatus, TxId},
-    types::{CommitmentFactory, HashOutput, PrivateKey, PublicKey, Signature},
+    types::{Commitment, CommitmentFactory, FixedHash, HashOutput, PrivateKey, PublicKey, Signature},
     wallet_types::WalletType,
 };
 use tari_comms::{types::CommsPublicKey, NodeIdentity};
@@ -51,18 +51,21 @@ use tari_core::{
     one_sided::{shared_secret_to_output_encryption_key, shared_secret_to_output_spending_key},
     proto::{base_node as base_node_proto, base_node::FetchMatchingUtxos},
     transactions::{
-        key_manager::TransactionKeyManagerInterface,
+        key_manager::{TariKeyId, TransactionKeyManagerInterface},
         tari_amount::MicroMinotari,
         transaction_components::{
             encrypted_data::PaymentId,
             CodeTemplateRegistration,
             KernelFeatures,
             OutputFeatures,
+            OutputType,
             Transaction,
             TransactionOutput,
+            WalletOutput,
             WalletOutputBuilder,
         },
         transaction_protocol::{
+            adaptor_signature::{AdaptorSignature, AdaptorSwapScript},
             proto::protocol as proto,
             recipient::RecipientSignedMessage,
             sender::TransactionSenderMessage,
@@ -79,27 +82,38 @@ use tari_crypto::{
 };
 use tari_key_manager::key_manager_service::KeyId;
 use tari_p2p::domain_message::DomainMessage;
-use tari_script::{push_pubkey_script, script, CheckSigSchnorrSignature, ExecutionStack, ScriptContext, TariScript};
+use tari_script::{
+    inputs,
+    push_pubkey_script,
+    script,
+    CheckSigSchnorrSignature,
+    ExecutionStack,
+    ScriptContext,
+    StackItem,
+    TariScript,
+};
 use tari_service_framework::{reply_channel, reply_channel::Receiver};
 use tari_shutdown::ShutdownSignal;
 use tokio::{
     sync::{mpsc, mpsc::Sender, oneshot, Mutex},
     task::JoinHandle,
+    time::{self, MissedTickBehavior},
 };
 
 use crate::{
     base_node_service::handle::{BaseNodeEvent, BaseNodeServiceHandle},
     connectivity_service::WalletConnectivityInterface,
     output_manager_service::{
+        error::OutputManagerError,
         handle::{OutputManagerEvent, OutputManagerHandle},
         service::UseOutput,
-        storage::models::SpendingPriority,
+        storage::models::{MultisigAccount, SpendingPriority},
         UtxoSelectionCriteria,
     },
     storage::database::{WalletBackend, WalletDatabase},
     transaction_service::{
         config::TransactionServiceConfig,
-        error::{TransactionServiceError, TransactionServiceProtocolError},
+        error::{TransactionServiceError, TransactionServiceProtocolError, TransactionStorageError},
         handle::{
             FeePerGramStatsResponse,
             TransactionEvent,
@@ -107,6 +121,7 @@ use crate::{
             TransactionServiceRequest,
             TransactionServiceResponse,
         },
+        offline_signing::{SignedTransaction, UnsignedTransaction},
         protocols::{
             check_transaction_size,
             transaction_broadcast_protocol::TransactionBroadcastProtocol,
@@ -117,7 +132,15 @@ use crate::{
         storage::{
             database::{TransactionBackend, TransactionDatabase},
             models::{
+                AdaptorSwap,
+                AdaptorSwapRole,
+                AdaptorSwapStatus,
+                BatchRecipient,
                 CompletedTransaction,
+                PaymentSchedule,
+                PaymentScheduleStatus,
+                PreparedTransaction,
+                TransactionServiceBackup,
                 TxCancellationReason,
                 WalletTransaction::{Completed, PendingInbound, PendingOutbound},
             },
@@ -375,6 +398,23 @@ where
         let mut base_node_service_event_stream = self.base_node_service.get_event_stream();
         let mut output_manager_event_stream = self.resources.output_manager_service.get_event_stream();
 
+        // The first check waits a full interval, so that the wallet's outputs have been validated before paying
+        let check_interval = self
+            .resources
+            .config
+            .payment_schedule_check_interval
+            .max(Duration::from_secs(1));
+        let mut payment_schedule_check = time::interval_at(time::Instant::now() + check_interval, check_interval);
+        payment_schedule_check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let consolidation_interval = self
+            .resources
+            .config
+            .auto_consolidation_check_interval
+            .max(Duration::from_secs(1));
+        let mut consolidation_check =
+            time::interval_at(time::Instant::now() + consolidation_interval, consolidation_interval);
+        consolidation_check.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
         debug!(target: LOG_TARGET, "Transaction Service started");
         loop {
             tokio::select! {
@@ -570,9 +610,18 @@ where
                         Ok(join_result_inner) => self.complete_transaction_validation_protocol(
                             join_result_inner,
                             &mut transaction_broadcast_protocol_handles,
-                        ),
+                        ).await,
                         Err(e) => error!(target: LOG_TARGET, "Error resolving Transaction Validation protocol: {:?}", e),
                     };
+                }
+                _ = payment_schedule_check.tick() => {
+                    let result = self.make_due_scheduled_payments(&mut transaction_broadcast_protocol_handles).await;
+                    if let Err(e) = result {
+                        warn!(target: LOG_TARGET, "Error making scheduled payments: {:?}", e);
+                    }
+                }
+                _ = consolidation_check.tick(), if self.resources.config.auto_consolidation_enabled => {
+                    self.consolidate_outputs(&mut transaction_broadcast_protocol_handles).await;
                 }
                  _ = shutdown.wait() => {
                     info!(target: LOG_TARGET, "Transaction service shutting down because it received the shutdown signal");
@@ -603,9 +652,17 @@ where
         >,
         reply_channel: oneshot::Sender<Result<TransactionServiceResponse, TransactionServiceError>>,
     ) -> Result<(), TransactionServiceError> {
+        trace!(target: LOG_TARGET, "Handling Service Request: {}", request);
+        if request.spends_funds() && self.resources.wallet_type.is_watch_only() {
+            let _result = reply_channel
+                .send(Err(TransactionServiceError::WatchOnlyWallet))
+                .inspect_err(|_| {
+                    warn!(target: LOG_TARGET, "Failed to send reply");
+                });
+            return Ok(());
+        }
         let mut reply_channel = Some(reply_channel);
 
-        trace!(target: LOG_TARGET, "Handling Service Request: {}", request);
         let response = match request {
             TransactionServiceRequest::SendTransaction {
                 destination,
@@ -651,6 +708,21 @@ where
                 .await
                 .map(TransactionServiceResponse::TransactionSent),
 
+            TransactionServiceRequest::SendBatch {
+                recipients,
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+            } => self
+                .send_batch(
+                    recipients,
+                    selection_criteria,
+                    fee_per_gram,
+                    payment_id,
+                    transaction_broadcast_join_handles,
+                )
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
             TransactionServiceRequest::ScrapeWallet {
                 destination,
                 fee_per_gram,
@@ -697,6 +769,29 @@ where
                     tx_id,
                     proof: Box::new(proof),
                 }),
+            TransactionServiceRequest::SendToMultisigAccount {
+                threshold,
+                public_keys,
+                view_public_key,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+            } => self
+                .send_to_multisig_account(
+                    threshold,
+                    public_keys,
+                    view_public_key,
+                    amount,
+                    selection_criteria,
+                    fee_per_gram,
+                    payment_id,
+                    transaction_broadcast_join_handles,
+                )
+                .await
+                .map(|(tx_id, output_hash)| {
+                    TransactionServiceResponse::TransactionSentWithOutputHash(tx_id, output_hash)
+                }),
             TransactionServiceRequest::EncumberAggregateUtxo {
                 fee_per_gram,
                 expected_commitment,
@@ -851,10 +946,133 @@ where
                 )
                 .await?,
             )),
+            TransactionServiceRequest::SendAdaptorSwapTransaction {
+                destination,
+                amount,
+                adaptor_point,
+                refund_delay,
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+            } => Ok(TransactionServiceResponse::AdaptorSwapTransactionSent(
+                self.send_adaptor_swap_transaction(
+                    destination,
+                    amount,
+                    adaptor_point,
+                    refund_delay,
+                    selection_criteria,
+                    fee_per_gram,
+                    payment_id,
+                    transaction_broadcast_join_handles,
+                )
+                .await?,
+            )),
+            TransactionServiceRequest::ClaimAdaptorSwap {
+                output_hash,
+                adaptor_signature,
+                adaptor_secret,
+                fee_per_gram,
+            } => self
+                .claim_adaptor_swap(
+                    output_hash,
+                    *adaptor_signature,
+                    adaptor_secret,
+                    fee_per_gram,
+                    transaction_broadcast_join_handles,
+                )
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
+            TransactionServiceRequest::RevealAdaptorSwapSecret {
+                output_hash,
+                input_data,
+            } => self
+                .reveal_adaptor_swap_secret(output_hash, &input_data)
+                .map(TransactionServiceResponse::AdaptorSwapSecret),
+            TransactionServiceRequest::RefundAdaptorSwap {
+                output_hash,
+                fee_per_gram,
+            } => self
+                .refund_adaptor_swap(output_hash, fee_per_gram, transaction_broadcast_join_handles)
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
+            TransactionServiceRequest::GetAdaptorSwaps => {
+                Ok(TransactionServiceResponse::AdaptorSwaps(self.db.get_adaptor_swaps()?))
+            },
             TransactionServiceRequest::CancelTransaction(tx_id) => self
                 .cancel_pending_transaction(tx_id)
                 .await
                 .map(|_| TransactionServiceResponse::TransactionCancelled),
+            TransactionServiceRequest::BumpFee { tx_id, fee_per_gram } => self
+                .bump_fee(tx_id, fee_per_gram, transaction_broadcast_join_handles)
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
+            TransactionServiceRequest::PrepareUnsignedTransaction {
+                destination,
+                amount,
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+            } => self
+                .prepare_unsigned_transaction(destination, amount, selection_criteria, fee_per_gram, payment_id)
+                .await
+                .map(|t| TransactionServiceResponse::UnsignedTransactionPrepared(Box::new(t))),
+            TransactionServiceRequest::SignUnsignedTransaction(unsigned_transaction) => self
+                .sign_unsigned_transaction(*unsigned_transaction)
+                .await
+                .map(|t| TransactionServiceResponse::TransactionSigned(Box::new(t))),
+            TransactionServiceRequest::FinaliseSignedTransaction(signed_transaction) => self
+                .finalise_signed_transaction(*signed_transaction, transaction_broadcast_join_handles)
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
+            TransactionServiceRequest::AbandonUnsignedTransaction(tx_id) => self
+                .abandon_unsigned_transaction(tx_id)
+                .await
+                .map(|_| TransactionServiceResponse::UnsignedTransactionAbandoned),
+            TransactionServiceRequest::TransferBetweenWalletAccounts {
+                from_account,
+                to_account,
+                amount,
+                fee_per_gram,
+                payment_id,
+            } => self
+                .transfer_between_wallet_accounts(
+                    from_account,
+                    to_account,
+                    amount,
+                    fee_per_gram,
+                    payment_id,
+                    transaction_broadcast_join_handles,
+                )
+                .await
+                .map(TransactionServiceResponse::TransactionSent),
+            TransactionServiceRequest::GetWalletAccountTransactions(account) => self
+                .get_wallet_account_transactions(account)
+                .await
+                .map(TransactionServiceResponse::CompletedTransactions),
+            TransactionServiceRequest::SetTransactionLabel { tx_id, label } => self
+                .set_transaction_label(tx_id, label)
+                .map(|_| TransactionServiceResponse::TransactionLabelSet),
+            TransactionServiceRequest::AddPaymentSchedule(schedule) => self
+                .add_payment_schedule(schedule)
+                .map(TransactionServiceResponse::PaymentScheduleAdded),
+            TransactionServiceRequest::GetPaymentSchedules => Ok(TransactionServiceResponse::PaymentSchedules(
+                self.db.get_payment_schedules()?,
+            )),
+            TransactionServiceRequest::CancelPaymentSchedule(id) => self
+                .cancel_payment_schedule(id)
+                .map(|_| TransactionServiceResponse::PaymentScheduleCancelled),
+            TransactionServiceRequest::GetTransactionLabels => Ok(TransactionServiceResponse::TransactionLabels(
+                self.db.get_transaction_labels()?,
+            )),
+            TransactionServiceRequest::GetBatchRecipients(tx_id) => Ok(TransactionServiceResponse::BatchRecipients(
+                self.db.get_batch_recipients(tx_id)?,
+            )),
+            TransactionServiceRequest::GetBackupData => self
+                .get_backup_data()
+                .map(|backup| TransactionServiceResponse::BackupData(Box::new(backup))),
+            TransactionServiceRequest::ImportBackupData(backup) => Ok(TransactionServiceResponse::BackupDataImported(
+                self.db.import_backup(*backup)?,
+            )),
             TransactionServiceRequest::GetPendingInboundTransactions => Ok(
                 TransactionServiceResponse::PendingInboundTransactions(self.db.get_pending_inbound_transactions()?),
             ),
@@ -1425,7 +1643,6 @@ where
     /// 'dest_pubkey': The Comms pubkey of the recipient node
     /// 'amount': The amount of Tari to send to the recipient
     /// 'fee_per_gram': The amount of fee per transaction gram to be included in transaction
-    #[allow(clippy::too_many_lines)]
     pub async fn send_sha_atomic_swap_transaction(
         &mut self,
         destination: TariAddress,
@@ -1456,6 +1673,262 @@ where
             EndIf
         )?;
 
+        let tx_output = self
+            .send_htlc_transaction(
+                tx_id,
+                destination,
+                amount,
+                script,
+                ExecutionStack::default(),
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+                transaction_broadcast_join_handles,
+            )
+            .await?;
+
+        Ok(Box::new((tx_id, pre_image, tx_output)))
+    }
+
+    /// Broadcasts a transaction locking `amount` in an adaptor signature atomic swap with `destination`. The
+    /// destination can claim the output by completing the returned adaptor signature with the discrete log of
+    /// `adaptor_point`, which reveals it to this wallet. From `refund_delay` blocks after the current tip, this wallet
+    /// can refund the output instead.
+    pub async fn send_adaptor_swap_transaction(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        adaptor_point: PublicKey,
+        refund_delay: u64,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<Box<(TxId, AdaptorSignature, TransactionOutput)>, TransactionServiceError> {
+        let tx_id = TxId::new_random();
+        self.verify_send(&destination, TariAddressFeatures::create_one_sided_only())?;
+        if refund_delay == 0 {
+            return Err(TransactionServiceError::AdaptorSwapError(
+                "The refund delay must be at least one block".to_string(),
+            ));
+        }
+
+        // The swap key is only used to create the adaptor signature, so it does not need to be kept
+        let swap_private_key = PrivateKey::random(&mut OsRng);
+        let swap_script = AdaptorSwapScript {
+            swap_public_key: PublicKey::from_secret_key(&swap_private_key),
+            adaptor_point,
+            claim_public_key: destination.public_spend_key().clone(),
+            refund_public_key: self.resources.one_sided_tari_address.public_spend_key().clone(),
+            refund_height: self.last_seen_tip_height.unwrap_or(0) + refund_delay,
+        };
+        let adaptor_signature = AdaptorSignature::sign(
+            &swap_private_key,
+            PrivateKey::random(&mut OsRng),
+            &swap_script.adaptor_point,
+            &swap_script.message(),
+        )?;
+
+        // Any signature other than the completed adaptor signature takes the refund branch of the script
+        let tx_output = self
+            .send_htlc_transaction(
+                tx_id,
+                destination,
+                amount,
+                swap_script.to_script()?,
+                inputs!(CheckSigSchnorrSignature::default()),
+                selection_criteria,
+                fee_per_gram,
+                payment_id,
+                transaction_broadcast_join_handles,
+            )
+            .await?;
+
+        self.db.insert_adaptor_swap(AdaptorSwap {
+            output_hash: tx_output.hash(),
+            role: AdaptorSwapRole::Initiator,
+            status: AdaptorSwapStatus::Locked,
+            amount,
+            swap_public_key: swap_script.swap_public_key,
+            adaptor_point: swap_script.adaptor_point,
+            adaptor_signature: adaptor_signature.clone(),
+            refund_height: swap_script.refund_height,
+            lock_tx_id: Some(tx_id),
+            spend_tx_id: None,
+            signature: None,
+            adaptor_secret: None,
+            timestamp: Utc::now(),
+        })?;
+
+        Ok(Box::new((tx_id, adaptor_signature, tx_output)))
+    }
+
+    /// Claims an adaptor signature swap output sent to this wallet by completing `adaptor_signature` with
+    /// `adaptor_secret`. Publishing the claim reveals the adaptor secret to the sender of the output.
+    pub async fn claim_adaptor_swap(
+        &mut self,
+        output_hash: HashOutput,
+        adaptor_signature: AdaptorSignature,
+        adaptor_secret: PrivateKey,
+        fee_per_gram: MicroMinotari,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        match self.db.get_adaptor_swap(&output_hash) {
+            Ok(swap) => {
+                return Err(TransactionServiceError::AdaptorSwapError(format!(
+                    "The swap of output {} is already {}",
+                    output_hash, swap.status
+                )))
+            },
+            Err(TransactionStorageError::AdaptorSwapNotFound(_)) => {},
+            Err(e) => return Err(e.into()),
+        }
+        let adaptor_point = PublicKey::from_secret_key(&adaptor_secret);
+        let claim = self
+            .resources
+            .output_manager_service
+            .create_claim_adaptor_swap_transaction(output_hash, adaptor_signature.clone(), adaptor_secret, fee_per_gram)
+            .await?;
+        let tx_id = claim.tx_id;
+        self.submit_transaction_to_self(
+            transaction_broadcast_join_handles,
+            tx_id,
+            claim.transaction,
+            claim.fee,
+            claim.amount,
+            PaymentId::open_from_str("Claiming adaptor signature atomic swap"),
+        )
+        .await?;
+
+        self.db.insert_adaptor_swap(AdaptorSwap {
+            output_hash,
+            role: AdaptorSwapRole::Participant,
+            status: AdaptorSwapStatus::Claimed,
+            amount: claim.amount + claim.fee,
+            swap_public_key: claim.swap_script.swap_public_key,
+            adaptor_point,
+            adaptor_signature,
+            refund_height: claim.swap_script.refund_height,
+            lock_tx_id: None,
+            spend_tx_id: Some(tx_id),
+            signature: Some(claim.signature),
+            adaptor_secret: None,
+            timestamp: Utc::now(),
+        })?;
+        Ok(tx_id)
+    }
+
+    /// Extracts the adaptor secret of a swap this wallet initiated from `input_data`, the input stack of the input
+    /// that claimed the swap output
+    pub fn reveal_adaptor_swap_secret(
+        &mut self,
+        output_hash: HashOutput,
+        input_data: &ExecutionStack,
+    ) -> Result<PrivateKey, TransactionServiceError> {
+        let mut swap = self.db.get_adaptor_swap(&output_hash)?;
+        if swap.role != AdaptorSwapRole::Initiator {
+            return Err(TransactionServiceError::AdaptorSwapError(
+                "Only the initiator of a swap learns the adaptor secret".to_string(),
+            ));
+        }
+        if let Some(adaptor_secret) = swap.adaptor_secret {
+            return Ok(adaptor_secret);
+        }
+        let (signature, adaptor_secret) = input_data
+            .as_slice()
+            .iter()
+            .filter_map(|item| match item {
+                StackItem::Signature(signature) => Some(signature),
+                _ => None,
+            })
+            .find_map(|signature| {
+                swap.adaptor_signature
+                    .extract_secret(&swap.adaptor_point, signature)
+                    .map(|secret| (signature.clone(), secret))
+            })
+            .ok_or_else(|| {
+                TransactionServiceError::AdaptorSwapError(
+                    "The input data does not contain the completed adaptor signature".to_string(),
+                )
+            })?;
+
+        swap.status = AdaptorSwapStatus::SecretRevealed;
+        swap.signature = Some(signature);
+        swap.adaptor_secret = Some(adaptor_secret.clone());
+        self.db.update_adaptor_swap(swap)?;
+        Ok(adaptor_secret)
+    }
+
+    /// Spends the output of a swap this wallet initiated back to itself. This is only possible from the refund height
+    /// of the swap.
+    pub async fn refund_adaptor_swap(
+        &mut self,
+        output_hash: HashOutput,
+        fee_per_gram: MicroMinotari,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        let mut swap = self.db.get_adaptor_swap(&output_hash)?;
+        if !swap.status.can_transition_to(swap.role, AdaptorSwapStatus::Refunded) {
+            return Err(TransactionServiceError::AdaptorSwapError(format!(
+                "The swap of output {} cannot be refunded by the {} once {}",
+                output_hash, swap.role, swap.status
+            )));
+        }
+        // The refund can be mined in the block after the tip at the earliest
+        let next_height = self.last_seen_tip_height.unwrap_or(0) + 1;
+        if next_height < swap.refund_height {
+            return Err(TransactionServiceError::AdaptorSwapError(format!(
+                "The swap of output {} cannot be refunded before height {}",
+                output_hash, swap.refund_height
+            )));
+        }
+
+        let (tx_id, fee, amount, tx) = self
+            .resources
+            .output_manager_service
+            .create_htlc_refund_transaction(output_hash, fee_per_gram)
+            .await?;
+        self.submit_transaction_to_self(
+            transaction_broadcast_join_handles,
+            tx_id,
+            tx,
+            fee,
+            amount,
+            PaymentId::open_from_str("Refunding adaptor signature atomic swap"),
+        )
+        .await?;
+
+        swap.status = AdaptorSwapStatus::Refunded;
+        swap.spend_tx_id = Some(tx_id);
+        self.db.update_adaptor_swap(swap)?;
+        Ok(tx_id)
+    }
+
+    /// Broadcasts a one-sided transaction to `destination` locked with a hashed or adaptor signature time lock script,
+    /// and keeps the output so that this wallet can refund it. `input_data` is the input stack of the refund.
+    #[allow(clippy::too_many_lines)]
+    async fn send_htlc_transaction(
+        &mut self,
+        tx_id: TxId,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        script: TariScript,
+        input_data: ExecutionStack,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TransactionOutput, TransactionServiceError> {
+        let tip_height = self.last_seen_tip_height.unwrap_or(0);
+
         // Empty covenant
         let covenant = Covenant::default();
 
@@ -1564,7 +2037,7 @@ where
                 PaymentId::Address(self.resources.interactive_tari_address.clone()),
             )
             .await?
-            .with_input_data(ExecutionStack::default())
+            .with_input_data(input_data)
             .with_covenant(covenant)
             .with_sender_offset_public_key(sender_offset_public_key)
             .with_script_key(
@@ -1655,7 +2128,7 @@ where
             .to_transaction_output(&self.resources.transaction_key_manager_service)
             .await?;
 
-        Ok(Box::new((tx_id, pre_image, tx_output)))
+        Ok(tx_output)
     }
 
     #[allow(clippy::too_many_lines)]
@@ -1905,39 +2378,204 @@ where
         Ok(tx_id)
     }
 
-    #[allow(clippy::too_many_lines)]
-    async fn scrape_wallet(
+    /// Pays all recipients with one-sided stealth address outputs in a single transaction
+    async fn send_batch(
         &mut self,
-        dest_address: TariAddress,
+        recipients: Vec<BatchRecipient>,
+        selection_criteria: UtxoSelectionCriteria,
         fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
         transaction_broadcast_join_handles: &mut FuturesUnordered<
             JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
         >,
     ) -> Result<TxId, TransactionServiceError> {
-        let tx_id = TxId::new_random();
-        let payment_id = PaymentId::Address(self.resources.interactive_tari_address.clone());
-        self.verify_send(&dest_address, TariAddressFeatures::create_one_sided_only())?;
+        let destination = recipients
+            .first()
+            .map(|r| r.address.clone())
+            .ok_or_else(|| TransactionServiceError::BatchPaymentError("No recipients provided".to_string()))?;
+        let amount = BatchRecipient::total_amount(&recipients).ok_or_else(|| {
+            TransactionServiceError::BatchPaymentError("The total amount of the recipients overflows".to_string())
+        })?;
+        let mut outputs = Vec::with_capacity(recipients.len());
+        for recipient in &recipients {
+            self.verify_send(&recipient.address, TariAddressFeatures::create_one_sided_only())?;
+            let payment_id = match recipient.payment_id.clone() {
+                PaymentId::Open(v) => PaymentId::AddressAndData {
+                    sender_address: self.resources.interactive_tari_address.clone(),
+                    user_data: v,
+                },
+                PaymentId::Empty => PaymentId::AddressAndData {
+                    sender_address: self.resources.interactive_tari_address.clone(),
+                    user_data: vec![],
+                },
+                payment_id => payment_id,
+            };
+            outputs.push(
+                self.create_stealth_address_output(&recipient.address, recipient.amount, payment_id)
+                    .await?,
+            );
+        }
 
-        // Prepare sender part of the transaction
-        let mut stp = self
+        let (tx_id, tx, fee) = self
             .resources
             .output_manager_service
-            .scrape_wallet(tx_id, fee_per_gram, dest_address.clone())
+            .create_batch_transaction(outputs, fee_per_gram, selection_criteria, payment_id.clone())
             .await?;
+        // The transaction is recorded with the first recipient as its destination, so all recipients are kept alongside
+        if let Err(e) = self.db.insert_batch_recipients(tx_id, recipients.clone()) {
+            self.resources.output_manager_service.cancel_transaction(tx_id).await?;
+            return Err(e.into());
+        }
+        info!(
+            target: LOG_TARGET,
+            "Created batch transaction TxId: {} paying {} recipients",
+            tx_id,
+            recipients.len()
+        );
 
-        // This call is needed to advance the state from `SingleRoundMessageReady` to `SingleRoundMessageReady`,
-        // but the returned value is not used. We have to wait until the sender transaction protocol creates a
-        // sender_offset_private_key for us, so we can use it to create the shared secret
-        let key = self
-            .resources
-            .transaction_key_manager_service
-            .get_next_key(TransactionKeyManagerBranch::OneSidedSenderOffset.get_branch_key())
-            .await?;
+        let _result = self
+            .event_publisher
+            .send(Arc::new(TransactionEvent::TransactionCompletedImmediately(tx_id)));
 
-        stp.change_recipient_sender_offset_private_key(key.key_id)?;
-        let _single_round_sender_data = stp
-            .build_single_round_message(&self.resources.transaction_key_manager_service)
-            .await
+        self.submit_transaction(
+            transaction_broadcast_join_handles,
+            CompletedTransaction::new(
+                tx_id,
+                self.resources.one_sided_tari_address.clone(),
+                destination,
+                amount,
+                fee,
+                tx.clone(),
+                TransactionStatus::Completed,
+                Utc::now(),
+                TransactionDirection::Outbound,
+                None,
+                None,
+                payment_id,
+            )?,
+        )
+        .await?;
+
+        for recipient in recipients {
+            tokio::spawn(send_finalized_transaction_message(
+                tx_id,
+                tx.clone(),
+                recipient.address.comms_public_key().clone(),
+                self.resources.outbound_message_service.clone(),
+                self.resources.config.direct_send_timeout,
+                self.resources.config.transaction_routing_mechanism,
+            ));
+        }
+
+        Ok(tx_id)
+    }
+
+    /// Creates a one-sided stealth address output paying `amount` to `destination`, signed by a new sender offset key.
+    /// Returns the output and the sender offset key id.
+    async fn create_stealth_address_output(
+        &mut self,
+        destination: &TariAddress,
+        amount: MicroMinotari,
+        payment_id: PaymentId,
+    ) -> Result<(WalletOutput, TariKeyId), TransactionServiceError> {
+        let sender_offset_key_id = self
+            .resources
+            .transaction_key_manager_service
+            .get_next_key(TransactionKeyManagerBranch::OneSidedSenderOffset.get_branch_key())
+            .await?
+            .key_id;
+
+        // Diffie-Hellman shared secret `k_Ob * K_Sb = K_Ob * k_Sb` results in a public key, which is fed into
+        // KDFs to produce the spending, rewind, and encryption keys
+        let shared_secret = self
+            .resources
+            .transaction_key_manager_service
+            .get_diffie_hellman_shared_secret(
+                &sender_offset_key_id,
+                destination.public_view_key().ok_or_else(|| {
+                    TransactionServiceError::OneSidedTransactionError("Missing public view key".to_string())
+                })?,
+            )
+            .await?;
+        let commitment_mask_key_id = self
+            .resources
+            .transaction_key_manager_service
+            .import_key(shared_secret_to_output_spending_key(&shared_secret)?)
+            .await?;
+        let encryption_key = self
+            .resources
+            .transaction_key_manager_service
+            .import_key(shared_secret_to_output_encryption_key(&shared_secret)?)
+            .await?;
+        let script_spending_key = self
+            .resources
+            .transaction_key_manager_service
+            .stealth_address_script_spending_key(&commitment_mask_key_id, destination.public_spend_key())
+            .await?;
+        let sender_offset_public_key = self
+            .resources
+            .transaction_key_manager_service
+            .get_public_key_at_key_id(&sender_offset_key_id)
+            .await?;
+
+        let output = WalletOutputBuilder::new(amount, commitment_mask_key_id)
+            .with_features(OutputFeatures::default())
+            .with_script(push_pubkey_script(&script_spending_key))
+            .encrypt_data_for_recovery(
+                &self.resources.transaction_key_manager_service,
+                Some(&encryption_key),
+                payment_id,
+            )
+            .await?
+            .with_input_data(Default::default())
+            .with_sender_offset_public_key(sender_offset_public_key)
+            .with_script_key(KeyId::Zero)
+            .with_minimum_value_promise(MicroMinotari::zero())
+            .sign_as_sender_and_receiver_verified(
+                &self.resources.transaction_key_manager_service,
+                &sender_offset_key_id,
+                destination,
+            )
+            .await?
+            .try_build(&self.resources.transaction_key_manager_service)
+            .await?;
+
+        Ok((output, sender_offset_key_id))
+    }
+
+    #[allow(clippy::too_many_lines)]
+    async fn scrape_wallet(
+        &mut self,
+        dest_address: TariAddress,
+        fee_per_gram: MicroMinotari,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        let tx_id = TxId::new_random();
+        let payment_id = PaymentId::Address(self.resources.interactive_tari_address.clone());
+        self.verify_send(&dest_address, TariAddressFeatures::create_one_sided_only())?;
+
+        // Prepare sender part of the transaction
+        let mut stp = self
+            .resources
+            .output_manager_service
+            .scrape_wallet(tx_id, fee_per_gram, dest_address.clone())
+            .await?;
+
+        // This call is needed to advance the state from `SingleRoundMessageReady` to `SingleRoundMessageReady`,
+        // but the returned value is not used. We have to wait until the sender transaction protocol creates a
+        // sender_offset_private_key for us, so we can use it to create the shared secret
+        let key = self
+            .resources
+            .transaction_key_manager_service
+            .get_next_key(TransactionKeyManagerBranch::OneSidedSenderOffset.get_branch_key())
+            .await?;
+
+        stp.change_recipient_sender_offset_private_key(key.key_id)?;
+        let _single_round_sender_data = stp
+            .build_single_round_message(&self.resources.transaction_key_manager_service)
+            .await
             .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
 
         // Prepare receiver part of the transaction
@@ -2373,136 +3011,314 @@ where
         }))
     }
 
-    pub async fn register_validator_node(
-        &mut self,
-        amount: MicroMinotari,
-        validator_node_public_key: CommsPublicKey,
-        validator_node_signature: Signature,
-        selection_criteria: UtxoSelectionCriteria,
-        fee_per_gram: MicroMinotari,
-        payment_id: PaymentId,
-        join_handles: &mut FuturesUnordered<
-            JoinHandle<Result<TransactionSendResult, TransactionServiceProtocolError<TxId>>>,
-        >,
-        transaction_broadcast_join_handles: &mut FuturesUnordered<
-            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
-        >,
-        reply_channel: oneshot::Sender<Result<TransactionServiceResponse, TransactionServiceError>>,
-    ) -> Result<(), TransactionServiceError> {
-        let output_features =
-            OutputFeatures::for_validator_node_registration(validator_node_public_key, validator_node_signature);
-        self.send_transaction(
-            self.resources.interactive_tari_address.clone(),
-            amount,
-            selection_criteria,
-            output_features,
-            fee_per_gram,
-            payment_id,
-            TransactionMetadata::default(),
-            join_handles,
-            transaction_broadcast_join_handles,
-            reply_channel,
-        )
-        .await
-    }
-
-    pub async fn register_code_template(
-        &mut self,
-        fee_per_gram: MicroMinotari,
-        template_registration: CodeTemplateRegistration,
-        selection_criteria: UtxoSelectionCriteria,
-        payment_id: PaymentId,
-        join_handles: &mut FuturesUnordered<
-            JoinHandle<Result<TransactionSendResult, TransactionServiceProtocolError<TxId>>>,
-        >,
-        transaction_broadcast_join_handles: &mut FuturesUnordered<
-            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
-        >,
-        reply_channel: oneshot::Sender<Result<TransactionServiceResponse, TransactionServiceError>>,
-    ) -> Result<(), TransactionServiceError> {
-        self.send_transaction(
-            self.resources.interactive_tari_address.clone(),
-            0.into(),
-            selection_criteria,
-            OutputFeatures::for_template_registration(template_registration),
-            fee_per_gram,
-            payment_id,
-            TransactionMetadata::default(),
-            join_handles,
-            transaction_broadcast_join_handles,
-            reply_channel,
-        )
-        .await
-    }
-
-    /// Sends a one side payment transaction to a recipient
-    /// # Arguments
-    /// 'dest_pubkey': The Comms pubkey of the recipient node
-    /// 'amount': The amount of Tari to send to the recipient
-    /// 'fee_per_gram': The amount of fee per transaction gram to be included in transaction
-    pub async fn send_one_sided_to_stealth_address_transaction(
+    /// Sends `amount` to an N-of-M multi-signature account. The output is encrypted to the account's view key, so
+    /// that the UTXO scanners of its parties find it.
+    #[allow(clippy::too_many_lines)]
+    pub async fn send_to_multisig_account(
         &mut self,
-        destination: TariAddress,
+        threshold: u8,
+        public_keys: Vec<PublicKey>,
+        view_public_key: PublicKey,
         amount: MicroMinotari,
         selection_criteria: UtxoSelectionCriteria,
-        output_features: OutputFeatures,
         fee_per_gram: MicroMinotari,
         payment_id: PaymentId,
         transaction_broadcast_join_handles: &mut FuturesUnordered<
             JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
         >,
-    ) -> Result<TxId, TransactionServiceError> {
-        self.send_one_sided_or_stealth(
-            destination,
-            amount,
-            selection_criteria,
-            output_features,
-            fee_per_gram,
-            transaction_broadcast_join_handles,
-            None, // The stealth address for the script will be calculated in the next step
-            payment_id,
-        )
-        .await
-    }
-
-    /// Accept the public reply from a recipient and apply the reply to the relevant transaction protocol
-    /// # Arguments
-    /// 'recipient_reply' - The public response from a recipient with data required to complete the transaction
-    #[allow(clippy::too_many_lines)]
-    pub async fn accept_recipient_reply(
-        &mut self,
-        source_pubkey: CommsPublicKey,
-        recipient_reply: Result<proto::RecipientSignedMessage, prost::DecodeError>,
-    ) -> Result<(), TransactionServiceError> {
-        // Check if a wallet recovery is in progress, if it is we will ignore this request
-        self.check_recovery_status()?;
-
-        if let Err(e) = recipient_reply {
-            // We should ban but there is no banning in the wallet...
-            return Err(TransactionServiceError::InvalidMessageError(format!(
-                "Could not decode RecipientSignedMessage: {:?}",
-                e
-            )));
-        }
+    ) -> Result<(TxId, FixedHash), TransactionServiceError> {
+        let tx_id = TxId::new_random();
+        trace!(target: LOG_TARGET, "Multi-signature account transaction start - TxId: {}", tx_id);
 
-        let recipient_reply: RecipientSignedMessage = recipient_reply
-            .unwrap()
-            .try_into()
-            .map_err(TransactionServiceError::InvalidMessageError)?;
+        // The script commits to the output's commitment, which is not known yet. A script with an empty commitment
+        // has the same size, so it is used to calculate the fee.
+        let placeholder_script = MultisigAccount::output_script(threshold, &public_keys, &Commitment::default())?;
+        let mut stp = self
+            .resources
+            .output_manager_service
+            .prepare_transaction_to_send(
+                tx_id,
+                amount,
+                selection_criteria,
+                OutputFeatures::default(),
+                fee_per_gram,
+                TransactionMetadata::default(),
+                placeholder_script,
+                Covenant::default(),
+                MicroMinotari::zero(),
+                self.resources.interactive_tari_address.clone(),
+                payment_id.clone(),
+            )
+            .await?;
 
-        let tx_id = recipient_reply.tx_id;
+        // This call is needed to advance the state from `SingleRoundMessageReady` to `SingleRoundMessageReady`,
+        // but the returned value is not used
+        let _single_round_sender_data = stp
+            .build_single_round_message(&self.resources.transaction_key_manager_service)
+            .await
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
 
-        // First we check if this Reply is for a cancelled Pending Outbound Tx or a Completed Tx
-        let cancelled_outbound_tx = self.db.get_cancelled_pending_outbound_transaction(tx_id);
-        let completed_tx = self.db.get_completed_transaction_cancelled_or_not(tx_id);
-        // This closure will check if the timestamps are beyond the cooldown period
-        let check_cooldown = |timestamp: Option<DateTime<Utc>>| {
-            if let Some(t) = timestamp {
-                // Check if the last reply is beyond the resend cooldown
-                if let Ok(elapsed_time) = Utc::now().signed_duration_since(t).to_std() {
-                    if elapsed_time < self.resources.config.resend_response_cooldown {
-                        trace!(
-                            target: LOG_TARGET,
+        let sender_message = TransactionSenderMessage::new_single_round_message(
+            stp.get_single_round_message(&self.resources.transaction_key_manager_service)
+                .await?,
+        );
+        let commitment_mask_key = self.resources.transaction_key_manager_service.get_random_key().await?;
+        let commitment = self
+            .resources
+            .transaction_key_manager_service
+            .get_commitment(&commitment_mask_key.key_id, &amount.into())
+            .await?;
+        let script = MultisigAccount::output_script(threshold, &public_keys, &commitment)?;
+        let sender_offset_private_key = stp
+            .get_recipient_sender_offset_private_key()
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?
+            .ok_or(TransactionServiceProtocolError::new(
+                tx_id,
+                TransactionServiceError::InvalidKeyId("Missing sender offset keyid".to_string()),
+            ))?;
+        let shared_secret = self
+            .resources
+            .transaction_key_manager_service
+            .get_diffie_hellman_shared_secret(&sender_offset_private_key, &view_public_key)
+            .await?;
+        let encryption_key = self
+            .resources
+            .transaction_key_manager_service
+            .import_key(MultisigAccount::output_encryption_key(&shared_secret)?)
+            .await?;
+        let single_round_message = sender_message.single().ok_or(TransactionServiceProtocolError::new(
+            tx_id,
+            TransactionServiceError::InvalidMessageError("Sent invalid message type".to_string()),
+        ))?;
+        let output = WalletOutputBuilder::new(amount, commitment_mask_key.key_id)
+            .with_features(single_round_message.features.clone())
+            .with_script(script)
+            .encrypt_data_for_recovery(
+                &self.resources.transaction_key_manager_service,
+                Some(&encryption_key),
+                payment_id.clone(),
+            )
+            .await?
+            .with_input_data(Default::default())
+            .with_sender_offset_public_key(single_round_message.sender_offset_public_key.clone())
+            .with_script_key(KeyId::Zero)
+            .with_minimum_value_promise(single_round_message.minimum_value_promise)
+            .sign_as_sender_and_receiver(
+                &self.resources.transaction_key_manager_service,
+                &sender_offset_private_key,
+            )
+            .await?
+            .try_build(&self.resources.transaction_key_manager_service)
+            .await?;
+        let output_hash = output
+            .to_transaction_output(&self.resources.transaction_key_manager_service)
+            .await?
+            .hash();
+
+        let tip_height = self.last_seen_tip_height.unwrap_or(0);
+        let consensus_constants = self.consensus_manager.consensus_constants(tip_height);
+        let rtp = ReceiverTransactionProtocol::new(
+            sender_message,
+            output,
+            &self.resources.transaction_key_manager_service,
+            consensus_constants,
+        )
+        .await;
+        let recipient_reply = rtp.get_signed_data()?.clone();
+
+        // Start finalizing
+        stp.add_presigned_recipient_info(recipient_reply)
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
+
+        // Finalize
+        stp.finalize(&self.resources.transaction_key_manager_service)
+            .await
+            .map_err(|e| {
+                error!(
+                    target: LOG_TARGET,
+                    "Transaction (TxId: {}) could not be finalized. Failure error: {:?}", tx_id, e,
+                );
+                TransactionServiceProtocolError::new(tx_id, e.into())
+            })?;
+        info!(
+            target: LOG_TARGET,
+            "Finalized multi-signature account transaction - TxId: {}", tx_id
+        );
+
+        // This event being sent is important, but not critical to the protocol being successful. Send only fails if
+        // there are no subscribers.
+        let _result = self
+            .event_publisher
+            .send(Arc::new(TransactionEvent::TransactionCompletedImmediately(tx_id)));
+
+        let tx = stp
+            .get_transaction()
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
+        let fee = stp
+            .get_fee_amount()
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
+
+        self.resources
+            .output_manager_service
+            .confirm_pending_transaction(tx_id)
+            .await
+            .map_err(|e| TransactionServiceProtocolError::new(tx_id, e.into()))?;
+        self.submit_transaction(
+            transaction_broadcast_join_handles,
+            CompletedTransaction::new(
+                tx_id,
+                self.resources.interactive_tari_address.clone(),
+                TariAddress::default(),
+                amount,
+                fee,
+                tx.clone(),
+                TransactionStatus::Completed,
+                Utc::now(),
+                TransactionDirection::Outbound,
+                None,
+                None,
+                payment_id,
+            )?,
+        )
+        .await?;
+        info!(
+            target: LOG_TARGET,
+            "Submitted multi-signature account transaction - TxId: {}", tx_id
+        );
+
+        Ok((tx_id, output_hash))
+    }
+
+    pub async fn register_validator_node(
+        &mut self,
+        amount: MicroMinotari,
+        validator_node_public_key: CommsPublicKey,
+        validator_node_signature: Signature,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+        join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TransactionSendResult, TransactionServiceProtocolError<TxId>>>,
+        >,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+        reply_channel: oneshot::Sender<Result<TransactionServiceResponse, TransactionServiceError>>,
+    ) -> Result<(), TransactionServiceError> {
+        let output_features =
+            OutputFeatures::for_validator_node_registration(validator_node_public_key, validator_node_signature);
+        self.send_transaction(
+            self.resources.interactive_tari_address.clone(),
+            amount,
+            selection_criteria,
+            output_features,
+            fee_per_gram,
+            payment_id,
+            TransactionMetadata::default(),
+            join_handles,
+            transaction_broadcast_join_handles,
+            reply_channel,
+        )
+        .await
+    }
+
+    pub async fn register_code_template(
+        &mut self,
+        fee_per_gram: MicroMinotari,
+        template_registration: CodeTemplateRegistration,
+        selection_criteria: UtxoSelectionCriteria,
+        payment_id: PaymentId,
+        join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TransactionSendResult, TransactionServiceProtocolError<TxId>>>,
+        >,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+        reply_channel: oneshot::Sender<Result<TransactionServiceResponse, TransactionServiceError>>,
+    ) -> Result<(), TransactionServiceError> {
+        self.send_transaction(
+            self.resources.interactive_tari_address.clone(),
+            0.into(),
+            selection_criteria,
+            OutputFeatures::for_template_registration(template_registration),
+            fee_per_gram,
+            payment_id,
+            TransactionMetadata::default(),
+            join_handles,
+            transaction_broadcast_join_handles,
+            reply_channel,
+        )
+        .await
+    }
+
+    /// Sends a one side payment transaction to a recipient
+    /// # Arguments
+    /// 'dest_pubkey': The Comms pubkey of the recipient node
+    /// 'amount': The amount of Tari to send to the recipient
+    /// 'fee_per_gram': The amount of fee per transaction gram to be included in transaction
+    pub async fn send_one_sided_to_stealth_address_transaction(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        output_features: OutputFeatures,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        self.send_one_sided_or_stealth(
+            destination,
+            amount,
+            selection_criteria,
+            output_features,
+            fee_per_gram,
+            transaction_broadcast_join_handles,
+            None, // The stealth address for the script will be calculated in the next step
+            payment_id,
+        )
+        .await
+    }
+
+    /// Accept the public reply from a recipient and apply the reply to the relevant transaction protocol
+    /// # Arguments
+    /// 'recipient_reply' - The public response from a recipient with data required to complete the transaction
+    #[allow(clippy::too_many_lines)]
+    pub async fn accept_recipient_reply(
+        &mut self,
+        source_pubkey: CommsPublicKey,
+        recipient_reply: Result<proto::RecipientSignedMessage, prost::DecodeError>,
+    ) -> Result<(), TransactionServiceError> {
+        // Check if a wallet recovery is in progress, if it is we will ignore this request
+        self.check_recovery_status()?;
+
+        if let Err(e) = recipient_reply {
+            // We should ban but there is no banning in the wallet...
+            return Err(TransactionServiceError::InvalidMessageError(format!(
+                "Could not decode RecipientSignedMessage: {:?}",
+                e
+            )));
+        }
+
+        let recipient_reply: RecipientSignedMessage = recipient_reply
+            .unwrap()
+            .try_into()
+            .map_err(TransactionServiceError::InvalidMessageError)?;
+
+        let tx_id = recipient_reply.tx_id;
+
+        // First we check if this Reply is for a cancelled Pending Outbound Tx or a Completed Tx
+        let cancelled_outbound_tx = self.db.get_cancelled_pending_outbound_transaction(tx_id);
+        let completed_tx = self.db.get_completed_transaction_cancelled_or_not(tx_id);
+        // This closure will check if the timestamps are beyond the cooldown period
+        let check_cooldown = |timestamp: Option<DateTime<Utc>>| {
+            if let Some(t) = timestamp {
+                // Check if the last reply is beyond the resend cooldown
+                if let Ok(elapsed_time) = Utc::now().signed_duration_since(t).to_std() {
+                    if elapsed_time < self.resources.config.resend_response_cooldown {
+                        trace!(
+                            target: LOG_TARGET,
                             "A repeated Transaction Reply (TxId: {}) has been received before the resend cooldown has \
                              expired. Ignoring.",
                             tx_id
@@ -2523,176 +3339,1062 @@ where
                 return Ok(());
             }
 
-            if ctx.cancelled.is_some() {
-                // Send a cancellation message
-                debug!(
-                    target: LOG_TARGET,
-                    "A repeated Transaction Reply (TxId: {}) has been received for cancelled completed transaction. \
-                     Transaction Cancelled response is being sent.",
-                    tx_id
-                );
-                tokio::spawn(send_transaction_cancelled_message(
-                    tx_id,
-                    source_pubkey,
-                    self.resources.outbound_message_service.clone(),
-                ));
-            } else {
-                // Resend the reply
-                debug!(
-                    target: LOG_TARGET,
-                    "A repeated Transaction Reply (TxId: {}) has been received. Reply is being resent.", tx_id
-                );
-                tokio::spawn(send_finalized_transaction_message(
-                    tx_id,
-                    ctx.transaction,
-                    source_pubkey,
-                    self.resources.outbound_message_service.clone(),
-                    self.resources.config.direct_send_timeout,
-                    self.resources.config.transaction_routing_mechanism,
-                ));
-            }
+            if ctx.cancelled.is_some() {
+                // Send a cancellation message
+                debug!(
+                    target: LOG_TARGET,
+                    "A repeated Transaction Reply (TxId: {}) has been received for cancelled completed transaction. \
+                     Transaction Cancelled response is being sent.",
+                    tx_id
+                );
+                tokio::spawn(send_transaction_cancelled_message(
+                    tx_id,
+                    source_pubkey,
+                    self.resources.outbound_message_service.clone(),
+                ));
+            } else {
+                // Resend the reply
+                debug!(
+                    target: LOG_TARGET,
+                    "A repeated Transaction Reply (TxId: {}) has been received. Reply is being resent.", tx_id
+                );
+                tokio::spawn(send_finalized_transaction_message(
+                    tx_id,
+                    ctx.transaction,
+                    source_pubkey,
+                    self.resources.outbound_message_service.clone(),
+                    self.resources.config.direct_send_timeout,
+                    self.resources.config.transaction_routing_mechanism,
+                ));
+            }
+
+            if let Err(e) = self.resources.db.increment_send_count(tx_id) {
+                warn!(
+                    target: LOG_TARGET,
+                    "Could not increment send count for completed transaction TxId {}: {:?}", tx_id, e
+                );
+            }
+            return Ok(());
+        }
+
+        if let Ok(otx) = cancelled_outbound_tx {
+            // Check that it is from the same person
+            if otx.destination_address.comms_public_key() != &source_pubkey {
+                return Err(TransactionServiceError::InvalidSourcePublicKey);
+            }
+            if !check_cooldown(otx.last_send_timestamp) {
+                return Ok(());
+            }
+
+            // Send a cancellation message
+            debug!(
+                target: LOG_TARGET,
+                "A repeated Transaction Reply (TxId: {}) has been received for cancelled pending outbound \
+                 transaction. Transaction Cancelled response is being sent.",
+                tx_id
+            );
+            tokio::spawn(send_transaction_cancelled_message(
+                tx_id,
+                source_pubkey,
+                self.resources.outbound_message_service.clone(),
+            ));
+
+            if let Err(e) = self.resources.db.increment_send_count(tx_id) {
+                warn!(
+                    target: LOG_TARGET,
+                    "Could not increment send count for completed transaction TxId {}: {:?}", tx_id, e
+                );
+            }
+            return Ok(());
+        }
+
+        // Is this a new Transaction Reply for an existing pending transaction?
+        let sender = match self.pending_transaction_reply_senders.get_mut(&tx_id) {
+            None => return Err(TransactionServiceError::TransactionDoesNotExistError),
+            Some(s) => s,
+        };
+        sender
+            .send((source_pubkey, recipient_reply))
+            .await
+            .map_err(|_| TransactionServiceError::ProtocolChannelError)?;
+
+        Ok(())
+    }
+
+    /// Handle the final clean up after a Send Transaction protocol completes
+    fn complete_send_transaction_protocol(
+        &mut self,
+        join_result: Result<TransactionSendResult, TransactionServiceProtocolError<TxId>>,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) {
+        match join_result {
+            Ok(val) => {
+                if val.transaction_status != TransactionStatus::Queued {
+                    let _sender = self.pending_transaction_reply_senders.remove(&val.tx_id);
+                    let _sender = self.send_transaction_cancellation_senders.remove(&val.tx_id);
+                    let completed_tx = match self.db.get_completed_transaction(val.tx_id) {
+                        Ok(v) => v,
+                        Err(e) => {
+                            error!(
+                                target: LOG_TARGET,
+                                "Error starting Broadcast Protocol after completed Send Transaction Protocol: {:?}", e
+                            );
+                            return;
+                        },
+                    };
+                    let _result = self
+                        .broadcast_completed_transaction(completed_tx, transaction_broadcast_join_handles)
+                        .map_err(|resp| {
+                            error!(
+                                target: LOG_TARGET,
+                                "Error starting Broadcast Protocol after completed Send Transaction Protocol: {:?}",
+                                resp
+                            );
+                            resp
+                        });
+                } else if val.transaction_status == TransactionStatus::Queued {
+                    trace!(
+                        target: LOG_TARGET,
+                        "Send Transaction Protocol for TxId: {} not completed successfully, transaction Queued",
+                        val.tx_id
+                    );
+                } else {
+                    // dont care
+                }
+            },
+            Err(TransactionServiceProtocolError { id, error }) => {
+                let _public_key = self.pending_transaction_reply_senders.remove(&id);
+                let _result = self.send_transaction_cancellation_senders.remove(&id);
+                if let TransactionServiceError::Shutdown = error {
+                    return;
+                }
+                warn!(
+                    target: LOG_TARGET,
+                    "Error completing Send Transaction Protocol (Id: {}): {:?}", id, error
+                );
+                let _size = self
+                    .event_publisher
+                    .send(Arc::new(TransactionEvent::Error(format!("{:?}", error))));
+            },
+        }
+    }
+
+    /// Cancel a pending transaction
+    async fn cancel_pending_transaction(&mut self, tx_id: TxId) -> Result<(), TransactionServiceError> {
+        self.db.cancel_pending_transaction(tx_id).map_err(|e| {
+            warn!(
+                target: LOG_TARGET,
+                "Pending Transaction does not exist and could not be cancelled: {:?}", e
+            );
+            e
+        })?;
+
+        self.resources.output_manager_service.cancel_transaction(tx_id).await?;
+
+        if let Some(cancellation_sender) = self.send_transaction_cancellation_senders.remove(&tx_id) {
+            let _result = cancellation_sender.send(());
+        }
+        let _public_key = self.pending_transaction_reply_senders.remove(&tx_id);
+
+        if let Some(cancellation_sender) = self.receiver_transaction_cancellation_senders.remove(&tx_id) {
+            let _result = cancellation_sender.send(());
+        }
+        let _public_key = self.finalized_transaction_senders.remove(&tx_id);
+
+        let _size = self
+            .event_publisher
+            .send(Arc::new(TransactionEvent::TransactionCancelled(
+                tx_id,
+                TxCancellationReason::UserCancelled,
+            )))
+            .map_err(|e| {
+                trace!(
+                    target: LOG_TARGET,
+                    "Error sending event because there are no subscribers: {:?}",
+                    e
+                );
+                e
+            });
+
+        info!(target: LOG_TARGET, "Pending Transaction (TxId: {}) cancelled", tx_id);
+
+        Ok(())
+    }
+
+    /// Replace an unmined one-sided or self-spend transaction with a new transaction that spends the same inputs to the
+    /// same destination at a higher fee per gram. The original transaction is kept until one of the two is mined.
+    async fn bump_fee(
+        &mut self,
+        tx_id: TxId,
+        fee_per_gram: MicroMinotari,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        let original = self.db.get_completed_transaction(tx_id)?;
+        if original.mined_height.is_some() ||
+            !matches!(
+                original.status,
+                TransactionStatus::Completed | TransactionStatus::Broadcast
+            )
+        {
+            return Err(TransactionServiceError::FeeBumpError(format!(
+                "Transaction {} is not waiting to be mined",
+                tx_id
+            )));
+        }
+        if let Some(replaced_by) = original.replaced_by_tx_id {
+            return Err(TransactionServiceError::FeeBumpError(format!(
+                "Transaction {} has already been replaced by transaction {}",
+                tx_id, replaced_by
+            )));
+        }
+        if original
+            .transaction
+            .body
+            .outputs()
+            .iter()
+            .any(|o| o.features.output_type != OutputType::Standard)
+        {
+            return Err(TransactionServiceError::FeeBumpError(
+                "Only transactions with standard outputs can be fee bumped".to_string(),
+            ));
+        }
+        // A transfer between wallet accounts into the default account is also paid from and to the one-sided address,
+        // but is a one-sided transaction rather than a spend to self
+        let is_self_spend = original.source_address == original.destination_address &&
+            original.destination_address == self.resources.interactive_tari_address;
+        let is_one_sided = original.source_address == self.resources.one_sided_tari_address &&
+            original.direction == TransactionDirection::Outbound;
+        if !is_self_spend && !is_one_sided {
+            return Err(TransactionServiceError::FeeBumpError(
+                "Only one-sided and self-spend transactions can be fee bumped".to_string(),
+            ));
+        }
+
+        let tip_height = self.last_seen_tip_height.unwrap_or(0);
+        let weight = original.transaction.calculate_weight(
+            self.resources
+                .consensus_manager
+                .consensus_constants(tip_height)
+                .transaction_weight_params(),
+        )?;
+        let original_fee_per_gram = MicroMinotari::from(original.fee.as_u64() / weight.max(1));
+        if fee_per_gram <= original_fee_per_gram {
+            return Err(TransactionServiceError::FeeBumpError(format!(
+                "The new fee per gram must be higher than the current {}",
+                original_fee_per_gram
+            )));
+        }
+
+        let (commitments, account_name) = self
+            .resources
+            .output_manager_service
+            .release_transaction_inputs(tx_id, original.amount, fee_per_gram)
+            .await?;
+        // The change of the replacement goes back to the account the inputs were spent from
+        let selection_criteria = UtxoSelectionCriteria::specific(commitments.clone()).with_account(account_name);
+        // A batch payment is recorded with only its first recipient as the destination, so it is rebuilt from all of
+        // its recipients
+        let batch_recipients = self.db.get_batch_recipients(tx_id)?;
+
+        let result = if !batch_recipients.is_empty() {
+            self.send_batch(
+                batch_recipients,
+                selection_criteria,
+                fee_per_gram,
+                original.payment_id.clone(),
+                transaction_broadcast_join_handles,
+            )
+            .await
+        } else if is_self_spend {
+            let new_tx_id = TxId::new_random();
+            match self
+                .resources
+                .output_manager_service
+                .create_pay_to_self_transaction(
+                    new_tx_id,
+                    original.amount,
+                    selection_criteria,
+                    OutputFeatures::default(),
+                    fee_per_gram,
+                    None,
+                )
+                .await
+            {
+                Ok((fee, transaction)) => self
+                    .submit_transaction_to_self(
+                        transaction_broadcast_join_handles,
+                        new_tx_id,
+                        transaction,
+                        fee,
+                        original.amount,
+                        original.payment_id.clone(),
+                    )
+                    .await
+                    .map(|_| new_tx_id),
+                Err(e) => Err(e.into()),
+            }
+        } else {
+            self.send_one_sided_or_stealth(
+                original.destination_address.clone(),
+                original.amount,
+                selection_criteria,
+                OutputFeatures::default(),
+                fee_per_gram,
+                transaction_broadcast_join_handles,
+                None,
+                original.payment_id.clone(),
+            )
+            .await
+        };
+
+        let new_tx_id = match result {
+            Ok(v) => v,
+            Err(e) => {
+                warn!(
+                    target: LOG_TARGET,
+                    "Could not create the fee bumped replacement for TxId: {}: {}", tx_id, e
+                );
+                // The original transaction is still pending, so its inputs are spent and its change is expected again
+                self.resources
+                    .output_manager_service
+                    .restore_released_transaction_inputs(tx_id, commitments)
+                    .await?;
+                return Err(e);
+            },
+        };
+        self.db.set_completed_transaction_replaced_by(tx_id, new_tx_id)?;
+        info!(
+            target: LOG_TARGET,
+            "Transaction (TxId: {}) replaced by fee bumped transaction (TxId: {}) at {} per gram",
+            tx_id,
+            new_tx_id,
+            fee_per_gram
+        );
+
+        Ok(new_tx_id)
+    }
+
+    /// Prepares a one-sided transaction that is signed by an offline wallet holding the spend key. The inputs are
+    /// selected and encumbered by this wallet, so it only needs to be able to see its outputs.
+    async fn prepare_unsigned_transaction(
+        &mut self,
+        destination: TariAddress,
+        amount: MicroMinotari,
+        selection_criteria: UtxoSelectionCriteria,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+    ) -> Result<UnsignedTransaction, TransactionServiceError> {
+        self.verify_send(&destination, TariAddressFeatures::create_one_sided_only())?;
+        let payment_id = match payment_id {
+            PaymentId::Open(v) => PaymentId::AddressAndData {
+                sender_address: self.resources.interactive_tari_address.clone(),
+                user_data: v,
+            },
+            PaymentId::Empty => PaymentId::AddressAndData {
+                sender_address: self.resources.interactive_tari_address.clone(),
+                user_data: vec![],
+            },
+            _ => payment_id,
+        };
+        let tx_id = TxId::new_random();
+        let (inputs, fee, change) = self
+            .resources
+            .output_manager_service
+            .prepare_unsigned_transaction(tx_id, amount, selection_criteria, fee_per_gram)
+            .await?;
+        let prepared_transaction = PreparedTransaction {
+            tx_id,
+            destination: destination.clone(),
+            amount,
+            fee,
+            change,
+            payment_id: payment_id.clone(),
+            timestamp: Utc::now(),
+        };
+        if let Err(e) = self.db.insert_prepared_transaction(prepared_transaction) {
+            self.resources.output_manager_service.cancel_transaction(tx_id).await?;
+            return Err(e.into());
+        }
+        info!(
+            target: LOG_TARGET,
+            "Prepared unsigned transaction (TxId: {}) of {} with {} inputs for offline signing",
+            tx_id,
+            amount,
+            inputs.len()
+        );
+
+        Ok(UnsignedTransaction {
+            tx_id,
+            destination,
+            amount,
+            fee_per_gram,
+            fee,
+            change,
+            payment_id,
+            inputs,
+        })
+    }
+
+    /// Signs an unsigned transaction exported by a view-only wallet as a stealth one-sided transaction. Nothing is
+    /// stored or broadcast, the signed transaction is handed back to the view-only wallet to finalise.
+    async fn sign_unsigned_transaction(
+        &mut self,
+        unsigned_transaction: UnsignedTransaction,
+    ) -> Result<SignedTransaction, TransactionServiceError> {
+        unsigned_transaction.validate()?;
+        self.verify_send(
+            &unsigned_transaction.destination,
+            TariAddressFeatures::create_one_sided_only(),
+        )?;
+        let tx_id = unsigned_transaction.tx_id;
+        let dest_address = unsigned_transaction.destination.clone();
+        let amount = unsigned_transaction.amount;
+        let payment_id = unsigned_transaction.payment_id.clone();
+
+        let (mut stp, change_output) = self
+            .resources
+            .output_manager_service
+            .prepare_to_sign_unsigned_transaction(unsigned_transaction)
+            .await?;
+
+        let key = self
+            .resources
+            .transaction_key_manager_service
+            .get_next_key(TransactionKeyManagerBranch::OneSidedSenderOffset.get_branch_key())
+            .await?;
+        stp.change_recipient_sender_offset_private_key(key.key_id)?;
+        let _single_round_sender_data = stp
+            .build_single_round_message(&self.resources.transaction_key_manager_service)
+            .await?;
+
+        let sender_offset_private_key =
+            stp.get_recipient_sender_offset_private_key()?
+                .ok_or(TransactionServiceError::InvalidKeyId(
+                    "Missing sender offset keyid".to_string(),
+                ))?;
+        let shared_secret = self
+            .resources
+            .transaction_key_manager_service
+            .get_diffie_hellman_shared_secret(
+                &sender_offset_private_key,
+                dest_address
+                    .public_view_key()
+                    .ok_or(TransactionServiceError::OneSidedTransactionError(
+                        "Missing public view key".to_string(),
+                    ))?,
+            )
+            .await?;
+        let commitment_mask_private_key = shared_secret_to_output_spending_key(&shared_secret)?;
+        let spending_key_id = self
+            .resources
+            .transaction_key_manager_service
+            .import_key(commitment_mask_private_key)
+            .await?;
+        let script_spending_key = self
+            .resources
+            .transaction_key_manager_service
+            .stealth_address_script_spending_key(&spending_key_id, dest_address.public_spend_key())
+            .await?;
+
+        let sender_message = TransactionSenderMessage::new_single_round_message(
+            stp.get_single_round_message(&self.resources.transaction_key_manager_service)
+                .await?,
+        );
+        let encryption_private_key = shared_secret_to_output_encryption_key(&shared_secret)?;
+        let encryption_key = self
+            .resources
+            .transaction_key_manager_service
+            .import_key(encryption_private_key)
+            .await?;
+        let sender_offset_public_key = self
+            .resources
+            .transaction_key_manager_service
+            .get_public_key_at_key_id(&sender_offset_private_key)
+            .await?;
+
+        let output = WalletOutputBuilder::new(amount, spending_key_id)
+            .with_features(
+                sender_message
+                    .single()
+                    .ok_or(TransactionServiceError::InvalidMessageError(
+                        "Sent invalid message type".to_string(),
+                    ))?
+                    .features
+                    .clone(),
+            )
+            .with_script(push_pubkey_script(&script_spending_key))
+            .encrypt_data_for_recovery(
+                &self.resources.transaction_key_manager_service,
+                Some(&encryption_key),
+                payment_id.clone(),
+            )
+            .await?
+            .with_input_data(Default::default())
+            .with_sender_offset_public_key(sender_offset_public_key)
+            .with_script_key(KeyId::Zero)
+            .with_minimum_value_promise(MicroMinotari::zero())
+            .sign_as_sender_and_receiver_verified(
+                &self.resources.transaction_key_manager_service,
+                &sender_offset_private_key,
+                &dest_address,
+            )
+            .await?
+            .try_build(&self.resources.transaction_key_manager_service)
+            .await?;
+
+        let tip_height = self.last_seen_tip_height.unwrap_or(0);
+        let consensus_constants = self.consensus_manager.consensus_constants(tip_height);
+        let rtp = ReceiverTransactionProtocol::new(
+            sender_message,
+            output,
+            &self.resources.transaction_key_manager_service,
+            consensus_constants,
+        )
+        .await;
+        let recipient_reply = rtp.get_signed_data()?.clone();
+        stp.add_presigned_recipient_info(recipient_reply)?;
+        stp.finalize(&self.resources.transaction_key_manager_service)
+            .await
+            .map_err(|e| {
+                error!(
+                    target: LOG_TARGET,
+                    "Offline transaction (TxId: {}) could not be finalized. Failure error: {:?}", tx_id, e,
+                );
+                TransactionServiceError::from(e)
+            })?;
+        info!(target: LOG_TARGET, "Signed offline transaction TxId: {}", tx_id);
+
+        Ok(SignedTransaction {
+            tx_id,
+            destination: dest_address,
+            amount,
+            fee: stp.get_fee_amount()?,
+            payment_id,
+            transaction: stp.get_transaction()?.clone(),
+            change_output,
+        })
+    }
+
+    /// Finalises a transaction signed by an offline wallet for an unsigned transaction prepared by this wallet, and
+    /// broadcasts it.
+    async fn finalise_signed_transaction(
+        &mut self,
+        signed_transaction: SignedTransaction,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        let SignedTransaction {
+            tx_id,
+            destination,
+            amount,
+            fee,
+            payment_id,
+            transaction,
+            change_output,
+        } = signed_transaction;
+        if self.db.transaction_exists(tx_id)? {
+            return Err(TransactionServiceError::OfflineSigningError(format!(
+                "Transaction {} has already been finalised",
+                tx_id
+            )));
+        }
+        let prepared = self.db.get_prepared_transaction(tx_id)?;
+        if destination != prepared.destination || amount != prepared.amount || payment_id != prepared.payment_id {
+            return Err(TransactionServiceError::OfflineSigningError(format!(
+                "The signed transaction does not pay {} to {} as prepared for transaction {}",
+                prepared.amount, prepared.destination, tx_id
+            )));
+        }
+        let kernel_fee = transaction.body.get_total_fee()?;
+        if kernel_fee != prepared.fee || fee != prepared.fee {
+            return Err(TransactionServiceError::OfflineSigningError(format!(
+                "The kernel fee ({}) does not match the prepared fee ({})",
+                kernel_fee, prepared.fee
+            )));
+        }
+        let change = change_output.as_ref().map(|o| o.output.value).unwrap_or_default();
+        if change != prepared.change {
+            return Err(TransactionServiceError::OfflineSigningError(format!(
+                "The change ({}) does not match the prepared change ({})",
+                change, prepared.change
+            )));
+        }
+        // The payment to the destination, and the change output if there is one
+        let expected_outputs = if change_output.is_some() { 2 } else { 1 };
+        if transaction.body.outputs().len() != expected_outputs {
+            return Err(TransactionServiceError::OfflineSigningError(format!(
+                "The signed transaction has {} outputs, expected {}",
+                transaction.body.outputs().len(),
+                expected_outputs
+            )));
+        }
+        let input_commitments = transaction
+            .body
+            .inputs()
+            .iter()
+            .map(|i| i.commitment().cloned())
+            .collect::<Result<Vec<_>, _>>()?;
+        let output_commitments = transaction
+            .body
+            .outputs()
+            .iter()
+            .map(|o| o.commitment.clone())
+            .collect::<Vec<_>>();
+        self.resources
+            .output_manager_service
+            .import_signed_transaction_outputs(tx_id, input_commitments, output_commitments, change_output)
+            .await?;
+
+        self.submit_transaction(
+            transaction_broadcast_join_handles,
+            CompletedTransaction::new(
+                tx_id,
+                self.resources.one_sided_tari_address.clone(),
+                destination.clone(),
+                amount,
+                fee,
+                transaction.clone(),
+                TransactionStatus::Completed,
+                Utc::now(),
+                TransactionDirection::Outbound,
+                None,
+                None,
+                payment_id,
+            )?,
+        )
+        .await?;
+        let _result = self
+            .event_publisher
+            .send(Arc::new(TransactionEvent::TransactionCompletedImmediately(tx_id)));
+
+        tokio::spawn(send_finalized_transaction_message(
+            tx_id,
+            transaction,
+            destination.comms_public_key().clone(),
+            self.resources.outbound_message_service.clone(),
+            self.resources.config.direct_send_timeout,
+            self.resources.config.transaction_routing_mechanism,
+        ));
+        if let Err(e) = self.db.remove_prepared_transaction(tx_id) {
+            warn!(
+                target: LOG_TARGET,
+                "Could not remove prepared transaction TxId: {} after finalising it: {}", tx_id, e
+            );
+        }
+        info!(target: LOG_TARGET, "Finalised offline signed transaction TxId: {}", tx_id);
+
+        Ok(tx_id)
+    }
+
+    /// Abandons a transaction prepared for offline signing that will not be finalised, and releases the inputs that
+    /// were encumbered for it
+    async fn abandon_unsigned_transaction(&mut self, tx_id: TxId) -> Result<(), TransactionServiceError> {
+        self.db.get_prepared_transaction(tx_id)?;
+        self.resources.output_manager_service.cancel_transaction(tx_id).await?;
+        self.db.remove_prepared_transaction(tx_id)?;
+        info!(target: LOG_TARGET, "Abandoned unsigned transaction TxId: {}", tx_id);
+
+        Ok(())
+    }
+
+    /// Pays `amount` from one wallet account to the one-sided address of another. The UTXO scanner assigns the
+    /// received output to the destination account once it is mined.
+    async fn transfer_between_wallet_accounts(
+        &mut self,
+        from_account: Option<String>,
+        to_account: Option<String>,
+        amount: MicroMinotari,
+        fee_per_gram: MicroMinotari,
+        payment_id: PaymentId,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        if from_account == to_account {
+            return Err(TransactionServiceError::TransferToSameWalletAccount);
+        }
+        let destination = match &to_account {
+            Some(name) => self
+                .resources
+                .output_manager_service
+                .get_wallet_accounts()
+                .await?
+                .into_iter()
+                .find(|(account, _)| &account.name == name)
+                .map(|(_, address)| address)
+                .ok_or_else(|| OutputManagerError::WalletAccountNotFound(name.clone()))?,
+            None => self.resources.one_sided_tari_address.clone(),
+        };
+        self.send_one_sided_or_stealth(
+            destination,
+            amount,
+            UtxoSelectionCriteria::default().with_account(from_account),
+            OutputFeatures::default(),
+            fee_per_gram,
+            transaction_broadcast_join_handles,
+            None,
+            payment_id,
+        )
+        .await
+    }
+
+    async fn get_wallet_account_transactions(
+        &mut self,
+        account: Option<String>,
+    ) -> Result<HashMap<TxId, CompletedTransaction>, TransactionServiceError> {
+        let mut transactions = self.db.get_completed_transactions()?;
+        match account {
+            Some(name) => {
+                let tx_ids = self
+                    .resources
+                    .output_manager_service
+                    .get_wallet_account_tx_ids(name)
+                    .await?;
+                transactions.retain(|tx_id, _| tx_ids.contains(tx_id));
+            },
+            None => {
+                let mut account_tx_ids = HashSet::new();
+                for (account, _) in self.resources.output_manager_service.get_wallet_accounts().await? {
+                    account_tx_ids.extend(
+                        self.resources
+                            .output_manager_service
+                            .get_wallet_account_tx_ids(account.name)
+                            .await?,
+                    );
+                }
+                transactions.retain(|tx_id, _| !account_tx_ids.contains(tx_id));
+            },
+        }
+        Ok(transactions)
+    }
+
+    /// Sets or clears the user label of a transaction, which must exist
+    fn set_transaction_label(&mut self, tx_id: TxId, label: Option<String>) -> Result<(), TransactionServiceError> {
+        if self.db.get_any_transaction(tx_id)?.is_none() && self.db.get_any_cancelled_transaction(tx_id)?.is_none() {
+            return Err(TransactionServiceError::TransactionDoesNotExistError);
+        }
+        let label = label.filter(|l| !l.is_empty());
+        self.db.set_transaction_label(tx_id, label.as_deref())?;
+        Ok(())
+    }
 
-            if let Err(e) = self.resources.db.increment_send_count(tx_id) {
-                warn!(
-                    target: LOG_TARGET,
-                    "Could not increment send count for completed transaction TxId {}: {:?}", tx_id, e
-                );
+    /// Collects the transaction service data for a wallet backup. Transactions that are still being negotiated or
+    /// broadcast are left out, as are transactions prepared for offline signing, whose inputs are not backed up.
+    fn get_backup_data(&mut self) -> Result<TransactionServiceBackup, TransactionServiceError> {
+        let mut completed_transactions = self
+            .db
+            .get_completed_transactions()?
+            .into_values()
+            .filter(|tx| {
+                !matches!(
+                    tx.status,
+                    TransactionStatus::Completed |
+                        TransactionStatus::Broadcast |
+                        TransactionStatus::Pending |
+                        TransactionStatus::Queued
+                )
+            })
+            .collect::<Vec<_>>();
+        completed_transactions.extend(self.db.get_cancelled_completed_transactions()?.into_values());
+        completed_transactions.sort_by_key(|tx| tx.timestamp);
+
+        let tx_ids = completed_transactions.iter().map(|tx| tx.tx_id).collect::<HashSet<_>>();
+        let transaction_labels = self
+            .db
+            .get_transaction_labels()?
+            .into_iter()
+            .filter(|(tx_id, _)| tx_ids.contains(tx_id))
+            .collect();
+        let mut batch_recipients = Vec::new();
+        for tx in &completed_transactions {
+            if tx.direction == TransactionDirection::Outbound {
+                let recipients = self.db.get_batch_recipients(tx.tx_id)?;
+                if !recipients.is_empty() {
+                    batch_recipients.push((tx.tx_id, recipients));
+                }
             }
-            return Ok(());
         }
 
-        if let Ok(otx) = cancelled_outbound_tx {
-            // Check that it is from the same person
-            if otx.destination_address.comms_public_key() != &source_pubkey {
-                return Err(TransactionServiceError::InvalidSourcePublicKey);
-            }
-            if !check_cooldown(otx.last_send_timestamp) {
-                return Ok(());
-            }
+        Ok(TransactionServiceBackup {
+            completed_transactions,
+            transaction_labels,
+            batch_recipients,
+            adaptor_swaps: self.db.get_adaptor_swaps()?,
+            payment_schedules: self.db.get_payment_schedules()?,
+        })
+    }
 
-            // Send a cancellation message
-            debug!(
-                target: LOG_TARGET,
-                "A repeated Transaction Reply (TxId: {}) has been received for cancelled pending outbound \
-                 transaction. Transaction Cancelled response is being sent.",
-                tx_id
-            );
-            tokio::spawn(send_transaction_cancelled_message(
-                tx_id,
-                source_pubkey,
-                self.resources.outbound_message_service.clone(),
+    fn add_payment_schedule(&mut self, mut schedule: PaymentSchedule) -> Result<u32, TransactionServiceError> {
+        self.verify_send(&schedule.destination, TariAddressFeatures::create_one_sided_only())?;
+        if schedule.amount == MicroMinotari::zero() {
+            return Err(TransactionServiceError::PaymentScheduleError(
+                "The payment amount must be more than zero".to_string(),
+            ));
+        }
+        if schedule
+            .end_time
+            .map_or(false, |end_time| end_time < schedule.next_payment)
+        {
+            return Err(TransactionServiceError::PaymentScheduleError(
+                "The end time is before the first payment".to_string(),
             ));
+        }
+        schedule.status = PaymentScheduleStatus::Active;
+        schedule.payments_made = 0;
+        schedule.last_tx_id = None;
+        schedule.last_error = None;
+        schedule.failed_attempts = 0;
+        schedule.retry_at = None;
+        let id = self.db.insert_payment_schedule(schedule)?;
+        info!(target: LOG_TARGET, "Added payment schedule #{}", id);
+        Ok(id)
+    }
 
-            if let Err(e) = self.resources.db.increment_send_count(tx_id) {
-                warn!(
+    fn cancel_payment_schedule(&mut self, id: u32) -> Result<(), TransactionServiceError> {
+        let mut schedule = self.db.get_payment_schedule(id)?;
+        if schedule.status != PaymentScheduleStatus::Active {
+            return Err(TransactionServiceError::PaymentScheduleError(format!(
+                "Payment schedule #{} is {}",
+                id, schedule.status
+            )));
+        }
+        schedule.status = PaymentScheduleStatus::Cancelled;
+        self.db.update_payment_schedule(schedule)?;
+        info!(target: LOG_TARGET, "Cancelled payment schedule #{}", id);
+        Ok(())
+    }
+
+    /// Makes the payments of all payment schedules that are due, skipping the missed payments that a schedule does not
+    /// make up. A failed payment is retried with a backoff, until too many attempts in a row fail and the schedule
+    /// fails.
+    async fn make_due_scheduled_payments(
+        &mut self,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<(), TransactionServiceError> {
+        if self.resources.wallet_type.is_watch_only() {
+            return Ok(());
+        }
+        let now = Utc::now();
+        let due_schedules = self
+            .db
+            .get_payment_schedules()?
+            .into_iter()
+            .filter(|schedule| schedule.is_due(now))
+            .collect::<Vec<_>>();
+        for mut schedule in due_schedules {
+            let skipped = schedule.skip_missed_payments(now);
+            if skipped > 0 {
+                info!(
                     target: LOG_TARGET,
-                    "Could not increment send count for completed transaction TxId {}: {:?}", tx_id, e
+                    "Skipped {} missed payments of payment schedule #{}", skipped, schedule.id
                 );
             }
-            return Ok(());
+            let event = match self
+                .make_scheduled_payment(&schedule, transaction_broadcast_join_handles)
+                .await
+            {
+                Ok(tx_id) => {
+                    info!(
+                        target: LOG_TARGET,
+                        "Made payment {} of payment schedule #{} with TxId: {}",
+                        schedule.payments_made + 1,
+                        schedule.id,
+                        tx_id
+                    );
+                    schedule.record_payment(tx_id);
+                    TransactionEvent::ScheduledPaymentSent {
+                        schedule_id: schedule.id,
+                        tx_id,
+                    }
+                },
+                Err(e) => {
+                    warn!(
+                        target: LOG_TARGET,
+                        "Payment of payment schedule #{} failed: {}", schedule.id, e
+                    );
+                    schedule.record_failure(e.to_string(), now);
+                    if schedule.status == PaymentScheduleStatus::Failed {
+                        warn!(
+                            target: LOG_TARGET,
+                            "Payment schedule #{} failed after {} attempts in a row",
+                            schedule.id,
+                            schedule.failed_attempts
+                        );
+                    }
+                    TransactionEvent::ScheduledPaymentFailed {
+                        schedule_id: schedule.id,
+                        reason: e.to_string(),
+                    }
+                },
+            };
+            self.db.update_payment_schedule(schedule)?;
+            let _size = self.event_publisher.send(Arc::new(event));
         }
-
-        // Is this a new Transaction Reply for an existing pending transaction?
-        let sender = match self.pending_transaction_reply_senders.get_mut(&tx_id) {
-            None => return Err(TransactionServiceError::TransactionDoesNotExistError),
-            Some(s) => s,
-        };
-        sender
-            .send((source_pubkey, recipient_reply))
-            .await
-            .map_err(|_| TransactionServiceError::ProtocolChannelError)?;
-
         Ok(())
     }
 
-    /// Handle the final clean up after a Send Transaction protocol completes
-    fn complete_send_transaction_protocol(
+    async fn make_scheduled_payment(
+        &mut self,
+        schedule: &PaymentSchedule,
+        transaction_broadcast_join_handles: &mut FuturesUnordered<
+            JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
+        >,
+    ) -> Result<TxId, TransactionServiceError> {
+        if let Some(max_fee) = schedule.max_fee {
+            let fee = self
+                .resources
+                .output_manager_service
+                .fee_estimate(
+                    schedule.amount,
+                    UtxoSelectionCriteria::default(),
+                    schedule.fee_per_gram,
+                    1,
+                    1,
+                )
+                .await?;
+            if fee > max_fee {
+                return Err(TransactionServiceError::PaymentScheduleError(format!(
+                    "The estimated fee {} is more than the maximum fee {}",
+                    fee, max_fee
+                )));
+            }
+        }
+        self.send_one_sided_to_stealth_address_transaction(
+            schedule.destination.clone(),
+            schedule.amount,
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            schedule.fee_per_gram,
+            schedule.payment_id.clone(),
+            transaction_broadcast_join_handles,
+        )
+        .await
+    }
+
+    /// Asks the output manager to merge small outputs as configured by its automatic consolidation policy, and submits
+    /// the consolidation transaction. The outputs are released again if the transaction cannot be submitted.
+    async fn consolidate_outputs(
         &mut self,
-        join_result: Result<TransactionSendResult, TransactionServiceProtocolError<TxId>>,
         transaction_broadcast_join_handles: &mut FuturesUnordered<
             JoinHandle<Result<TxId, TransactionServiceProtocolError<TxId>>>,
         >,
     ) {
-        match join_result {
-            Ok(val) => {
-                if val.transaction_status != TransactionStatus::Queued {
-                    let _sender = self.pending_transaction_reply_senders.remove(&val.tx_id);
-                    let _sender = self.send_transaction_cancellation_senders.remove(&val.tx_id);
-                    let completed_tx = match self.db.get_completed_transaction(val.tx_id) {
-                        Ok(v) => v,
-                        Err(e) => {
-                            error!(
-                                target: LOG_TARGET,
-                                "Error starting Broadcast Protocol after completed Send Transaction Protocol: {:?}", e
-                            );
-                            return;
-                        },
-                    };
-                    let _result = self
-                        .broadcast_completed_transaction(completed_tx, transaction_broadcast_join_handles)
-                        .map_err(|resp| {
-                            error!(
-                                target: LOG_TARGET,
-                                "Error starting Broadcast Protocol after completed Send Transaction Protocol: {:?}",
-                                resp
-                            );
-                            resp
-                        });
-                } else if val.transaction_status == TransactionStatus::Queued {
-                    trace!(
-                        target: LOG_TARGET,
-                        "Send Transaction Protocol for TxId: {} not completed successfully, transaction Queued",
-                        val.tx_id
-                    );
-                } else {
-                    // dont care
-                }
+        if self.resources.wallet_type.is_watch_only() {
+            return;
+        }
+        let (tx_id, transaction, amount) = match self
+            .resources
+            .output_manager_service
+            .create_consolidation_transaction()
+            .await
+        {
+            Ok(Some(consolidation)) => consolidation,
+            Ok(None) => return,
+            Err(e) => {
+                warn!(target: LOG_TARGET, "Automatic output consolidation failed: {}", e);
+                return;
             },
-            Err(TransactionServiceProtocolError { id, error }) => {
-                let _public_key = self.pending_transaction_reply_senders.remove(&id);
-                let _result = self.send_transaction_cancellation_senders.remove(&id);
-                if let TransactionServiceError::Shutdown = error {
-                    return;
-                }
+        };
+        let num_inputs = transaction.body.inputs().len();
+        let payment_id = PaymentId::open_from_str(&format!("Automatic consolidation of {} outputs", num_inputs));
+        let result = match transaction.body.get_total_fee() {
+            Ok(fee) => {
+                self.submit_transaction_to_self(
+                    transaction_broadcast_join_handles,
+                    tx_id,
+                    transaction,
+                    fee,
+                    amount,
+                    payment_id,
+                )
+                .await
+            },
+            Err(e) => Err(e.into()),
+        };
+        if let Err(e) = result {
+            warn!(
+                target: LOG_TARGET,
+                "Failed to submit consolidation transaction (TxId: {}): {}", tx_id, e
+            );
+            if let Err(e) = self.resources.output_manager_service.cancel_transaction(tx_id).await {
                 warn!(
                     target: LOG_TARGET,
-                    "Error completing Send Transaction Protocol (Id: {}): {:?}", id, error
+                    "Failed to release outputs of consolidation transaction (TxId: {}): {}", tx_id, e
                 );
-                let _size = self
-                    .event_publisher
-                    .send(Arc::new(TransactionEvent::Error(format!("{:?}", error))));
-            },
+            }
         }
     }
 
-    /// Cancel a pending transaction
-    async fn cancel_pending_transaction(&mut self, tx_id: TxId) -> Result<(), TransactionServiceError> {
-        self.db.cancel_pending_transaction(tx_id).map_err(|e| {
-            warn!(
-                target: LOG_TARGET,
-                "Pending Transaction does not exist and could not be cancelled: {:?}", e
-            );
-            e
-        })?;
-
-        self.resources.output_manager_service.cancel_transaction(tx_id).await?;
+    /// Once any version of a fee bumped transaction is mined, the other versions spend inputs that are no longer
+    /// available and are cancelled.
+    async fn resolve_replaced_transactions(&mut self) -> Result<(), TransactionServiceError> {
+        let replaced_by = self
+            .db
+            .get_replaced_transactions()?
+            .into_iter()
+            .filter_map(|tx| tx.replaced_by_tx_id.map(|r| (tx.tx_id, r)))
+            .collect::<HashMap<_, _>>();
+        let replacements = replaced_by.values().copied().collect::<HashSet<_>>();
+
+        for first in replaced_by.keys().filter(|tx_id| !replacements.contains(tx_id)) {
+            let mut chain = vec![self.db.get_completed_transaction_cancelled_or_not(*first)?];
+            let mut current = *first;
+            while let Some(next) = replaced_by.get(&current) {
+                if chain.iter().any(|tx| tx.tx_id == *next) {
+                    break;
+                }
+                chain.push(self.db.get_completed_transaction_cancelled_or_not(*next)?);
+                current = *next;
+            }
 
-        if let Some(cancellation_sender) = self.send_transaction_cancellation_senders.remove(&tx_id) {
-            let _result = cancellation_sender.send(());
+            let mined_tx_id = match chain.iter().find(|tx| tx.mined_height.is_some()) {
+                Some(tx) => tx.tx_id,
+                None => continue,
+            };
+            let mut is_replacement = false;
+            for tx in &chain {
+                if tx.tx_id == mined_tx_id {
+                    is_replacement = true;
+                    continue;
+                }
+                if tx.mined_height.is_some() || tx.cancelled.is_some() {
+                    continue;
+                }
+                if is_replacement {
+                    // The replacement still holds the inputs and change outputs of the mined transaction
+                    self.cancel_transaction(tx.tx_id, TxCancellationReason::DoubleSpend)
+                        .await;
+                    self.resources
+                        .output_manager_service
+                        .reinstate_cancelled_inbound_transaction_outputs(mined_tx_id)
+                        .await?;
+                    self.publish_transaction_cancelled(tx.tx_id, TxCancellationReason::DoubleSpend);
+                } else {
+                    self.db
+                        .reject_completed_transaction(tx.tx_id, TxCancellationReason::Replaced)?;
+                    self.publish_transaction_cancelled(tx.tx_id, TxCancellationReason::Replaced);
+                }
+                info!(
+                    target: LOG_TARGET,
+                    "Fee bumped Transaction (TxId: {}) cancelled, TxId: {} was mined", tx.tx_id, mined_tx_id
+                );
+            }
         }
-        let _public_key = self.pending_transaction_reply_senders.remove(&tx_id);
 
-        if let Some(cancellation_sender) = self.receiver_transaction_cancellation_senders.remove(&tx_id) {
-            let _result = cancellation_sender.send(());
-        }
-        let _public_key = self.finalized_transaction_senders.remove(&tx_id);
+        Ok(())
+    }
 
+    fn publish_transaction_cancelled(&self, tx_id: TxId, reason: TxCancellationReason) {
         let _size = self
             .event_publisher
-            .send(Arc::new(TransactionEvent::TransactionCancelled(
-                tx_id,
-                TxCancellationReason::UserCancelled,
-            )))
+            .send(Arc::new(TransactionEvent::TransactionCancelled(tx_id, reason)))
             .map_err(|e| {
                 trace!(
                     target: LOG_TARGET,
@@ -2701,10 +4403,6 @@ where
                 );
                 e
             });
-
-        info!(target: LOG_TARGET, "Pending Transaction (TxId: {}) cancelled", tx_id);
-
-        Ok(())
     }
 
     /// Handle a Transaction Cancelled message received from the Comms layer
@@ -3321,7 +5019,7 @@ where
     }
 
     /// Handle the final clean up after a Transaction Validation protocol completes
-    fn complete_transaction_validation_protocol(
+    async fn complete_transaction_validation_protocol(
         &mut self,
         join_result: Result<OperationId, TransactionServiceProtocolError<OperationId>>,
         transaction_broadcast_join_handles: &mut FuturesUnordered<
@@ -3334,6 +5032,10 @@ where
                     target: LOG_TARGET,
                     "Transaction Validation Protocol (Id: {}) completed successfully", id
                 );
+                // Cancel the versions of fee bumped transactions that can no longer be mined.
+                if let Err(e) = self.resolve_replaced_transactions().await {
+                    warn!(target: LOG_TARGET, "Error resolving replaced transactions: {}", e);
+                }
                 // Restart broadcast protocols for any transactions that were found to be no longer mined.
                 let _ = self
                     .restart_broadcast_protocols(transaction_broadcast_join_handles)
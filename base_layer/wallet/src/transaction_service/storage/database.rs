@@ -32,7 +32,7 @@ use log::*;
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::{TransactionDirection, TransactionStatus, TxId},
-    types::{BlockHash, PrivateKey},
+    types::{BlockHash, HashOutput, PrivateKey},
 };
 use tari_core::transactions::{
     tari_amount::MicroMinotari,
@@ -43,9 +43,14 @@ use crate::transaction_service::{
     error::TransactionStorageError,
     storage::{
         models::{
+            AdaptorSwap,
+            BatchRecipient,
             CompletedTransaction,
             InboundTransaction,
             OutboundTransaction,
+            PaymentSchedule,
+            PreparedTransaction,
+            TransactionServiceBackup,
             TxCancellationReason,
             WalletTransaction,
         },
@@ -156,6 +161,47 @@ pub trait TransactionBackend: Send + Sync + Clone {
         &self,
         height: u64,
     ) -> Result<Vec<CompletedTransaction>, TransactionStorageError>;
+    /// Record that a completed transaction has been replaced by a fee bumped transaction spending the same inputs
+    fn set_completed_transaction_replaced_by(
+        &self,
+        tx_id: TxId,
+        replaced_by_tx_id: TxId,
+    ) -> Result<(), TransactionStorageError>;
+    /// Retrieve all completed transactions, cancelled or not, that have been replaced by a fee bumped transaction
+    fn fetch_replaced_transactions(&self) -> Result<Vec<CompletedTransaction>, TransactionStorageError>;
+    /// Retrieve the adaptor signature swap of the output with the provided hash
+    fn fetch_adaptor_swap(&self, output_hash: &HashOutput) -> Result<Option<AdaptorSwap>, TransactionStorageError>;
+    /// Retrieve all adaptor signature swaps, newest first
+    fn fetch_adaptor_swaps(&self) -> Result<Vec<AdaptorSwap>, TransactionStorageError>;
+    /// Insert an adaptor signature swap, replacing the swap of the same output if there is one
+    fn save_adaptor_swap(&self, swap: AdaptorSwap) -> Result<(), TransactionStorageError>;
+    /// Retrieve the user labels of all labelled transactions
+    fn fetch_transaction_labels(&self) -> Result<HashMap<TxId, String>, TransactionStorageError>;
+    /// Set or clear the user label of a transaction
+    fn set_transaction_label(&self, tx_id: TxId, label: Option<&str>) -> Result<(), TransactionStorageError>;
+    /// Retrieve all payment schedules, oldest first
+    fn fetch_payment_schedules(&self) -> Result<Vec<PaymentSchedule>, TransactionStorageError>;
+    /// Insert a new payment schedule and return the id assigned to it
+    fn insert_payment_schedule(&self, schedule: PaymentSchedule) -> Result<u32, TransactionStorageError>;
+    /// Store the new state of an existing payment schedule
+    fn update_payment_schedule(&self, schedule: PaymentSchedule) -> Result<(), TransactionStorageError>;
+    /// Retrieve the recipients of the batch payment with the provided tx_id, in the order they were paid
+    fn fetch_batch_recipients(&self, tx_id: TxId) -> Result<Vec<BatchRecipient>, TransactionStorageError>;
+    /// Insert the recipients of a batch payment
+    fn insert_batch_recipients(
+        &self,
+        tx_id: TxId,
+        recipients: Vec<BatchRecipient>,
+    ) -> Result<(), TransactionStorageError>;
+    /// Retrieve the transaction with the provided tx_id that was prepared for offline signing
+    fn fetch_prepared_transaction(&self, tx_id: TxId) -> Result<Option<PreparedTransaction>, TransactionStorageError>;
+    /// Insert a transaction prepared for offline signing
+    fn insert_prepared_transaction(&self, transaction: PreparedTransaction) -> Result<(), TransactionStorageError>;
+    /// Remove a transaction prepared for offline signing once it is finalised or abandoned
+    fn remove_prepared_transaction(&self, tx_id: TxId) -> Result<(), TransactionStorageError>;
+    /// Insert the data from a wallet backup in a single database transaction, skipping data that already exists, and
+    /// return the number of transactions inserted
+    fn import_backup(&self, backup: TransactionServiceBackup) -> Result<usize, TransactionStorageError>;
 }
 
 #[derive(Clone, PartialEq)]
@@ -767,6 +813,106 @@ where T: TransactionBackend + 'static
         }?;
         Ok(t)
     }
+
+    pub fn set_completed_transaction_replaced_by(
+        &self,
+        tx_id: TxId,
+        replaced_by_tx_id: TxId,
+    ) -> Result<(), TransactionStorageError> {
+        self.db.set_completed_transaction_replaced_by(tx_id, replaced_by_tx_id)
+    }
+
+    pub fn get_replaced_transactions(&self) -> Result<Vec<CompletedTransaction>, TransactionStorageError> {
+        self.db.fetch_replaced_transactions()
+    }
+
+    pub fn get_adaptor_swap(&self, output_hash: &HashOutput) -> Result<AdaptorSwap, TransactionStorageError> {
+        self.db
+            .fetch_adaptor_swap(output_hash)?
+            .ok_or(TransactionStorageError::AdaptorSwapNotFound(*output_hash))
+    }
+
+    pub fn get_adaptor_swaps(&self) -> Result<Vec<AdaptorSwap>, TransactionStorageError> {
+        self.db.fetch_adaptor_swaps()
+    }
+
+    pub fn insert_adaptor_swap(&self, swap: AdaptorSwap) -> Result<(), TransactionStorageError> {
+        if self.db.fetch_adaptor_swap(&swap.output_hash)?.is_some() {
+            return Err(TransactionStorageError::DuplicateOutput);
+        }
+        self.db.save_adaptor_swap(swap)
+    }
+
+    /// Stores the new state of an adaptor signature swap, which must be a valid transition from its stored state
+    pub fn update_adaptor_swap(&self, swap: AdaptorSwap) -> Result<(), TransactionStorageError> {
+        let stored = self.get_adaptor_swap(&swap.output_hash)?;
+        if stored.role != swap.role || !stored.status.can_transition_to(stored.role, swap.status) {
+            return Err(TransactionStorageError::InvalidAdaptorSwapTransition {
+                role: stored.role,
+                from: stored.status,
+                to: swap.status,
+            });
+        }
+        self.db.save_adaptor_swap(swap)
+    }
+
+    pub fn get_transaction_labels(&self) -> Result<HashMap<TxId, String>, TransactionStorageError> {
+        self.db.fetch_transaction_labels()
+    }
+
+    pub fn set_transaction_label(&self, tx_id: TxId, label: Option<&str>) -> Result<(), TransactionStorageError> {
+        self.db.set_transaction_label(tx_id, label)
+    }
+
+    pub fn get_payment_schedules(&self) -> Result<Vec<PaymentSchedule>, TransactionStorageError> {
+        self.db.fetch_payment_schedules()
+    }
+
+    pub fn get_payment_schedule(&self, id: u32) -> Result<PaymentSchedule, TransactionStorageError> {
+        self.db
+            .fetch_payment_schedules()?
+            .into_iter()
+            .find(|schedule| schedule.id == id)
+            .ok_or(TransactionStorageError::PaymentScheduleNotFound(id))
+    }
+
+    pub fn insert_payment_schedule(&self, schedule: PaymentSchedule) -> Result<u32, TransactionStorageError> {
+        self.db.insert_payment_schedule(schedule)
+    }
+
+    pub fn update_payment_schedule(&self, schedule: PaymentSchedule) -> Result<(), TransactionStorageError> {
+        self.db.update_payment_schedule(schedule)
+    }
+
+    pub fn get_batch_recipients(&self, tx_id: TxId) -> Result<Vec<BatchRecipient>, TransactionStorageError> {
+        self.db.fetch_batch_recipients(tx_id)
+    }
+
+    pub fn insert_batch_recipients(
+        &self,
+        tx_id: TxId,
+        recipients: Vec<BatchRecipient>,
+    ) -> Result<(), TransactionStorageError> {
+        self.db.insert_batch_recipients(tx_id, recipients)
+    }
+
+    pub fn get_prepared_transaction(&self, tx_id: TxId) -> Result<PreparedTransaction, TransactionStorageError> {
+        self.db
+            .fetch_prepared_transaction(tx_id)?
+            .ok_or(TransactionStorageError::PreparedTransactionNotFound(tx_id))
+    }
+
+    pub fn insert_prepared_transaction(&self, transaction: PreparedTransaction) -> Result<(), TransactionStorageError> {
+        self.db.insert_prepared_transaction(transaction)
+    }
+
+    pub fn remove_prepared_transaction(&self, tx_id: TxId) -> Result<(), TransactionStorageError> {
+        self.db.remove_prepared_transaction(tx_id)
+    }
+
+    pub fn import_backup(&self, backup: TransactionServiceBackup) -> Result<usize, TransactionStorageError> {
+        self.db.import_backup(backup)
+    }
 }
 
 impl Display for DbKey {
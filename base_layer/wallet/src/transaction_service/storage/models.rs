@@ -23,6 +23,7 @@
 use std::{
     convert::TryFrom,
     fmt::{Display, Error, Formatter},
+    time::Duration,
 };
 
 use chrono::{DateTime, Utc};
@@ -30,14 +31,16 @@ use serde::{Deserialize, Serialize};
 use tari_common_types::{
     tari_address::TariAddress,
     transaction::{TransactionConversionError, TransactionDirection, TransactionStatus, TxId},
-    types::{BlockHash, PrivateKey, Signature},
+    types::{BlockHash, HashOutput, PrivateKey, PublicKey, Signature},
 };
 use tari_core::transactions::{
     tari_amount::MicroMinotari,
     transaction_components::{encrypted_data::PaymentId, Transaction},
+    transaction_protocol::adaptor_signature::AdaptorSignature,
     ReceiverTransactionProtocol,
     SenderTransactionProtocol,
 };
+use tari_script::CheckSigSchnorrSignature;
 
 use crate::transaction_service::error::TransactionStorageError;
 
@@ -147,6 +150,9 @@ pub struct CompletedTransaction {
     pub mined_in_block: Option<BlockHash>,
     pub mined_timestamp: Option<DateTime<Utc>>,
     pub payment_id: PaymentId,
+    /// The fee bumped transaction that spends the same inputs as this one, if there is one
+    #[serde(default)]
+    pub replaced_by_tx_id: Option<TxId>,
 }
 
 impl CompletedTransaction {
@@ -191,6 +197,7 @@ impl CompletedTransaction {
             mined_in_block: None,
             mined_timestamp,
             payment_id,
+            replaced_by_tx_id: None,
         })
     }
 }
@@ -270,6 +277,7 @@ impl From<OutboundTransaction> for CompletedTransaction {
             mined_in_block: None,
             mined_timestamp: None,
             payment_id: tx.payment_id,
+            replaced_by_tx_id: None,
         }
     }
 }
@@ -299,6 +307,7 @@ impl From<InboundTransaction> for CompletedTransaction {
             mined_in_block: None,
             mined_timestamp: None,
             payment_id: tx.payment_id,
+            replaced_by_tx_id: None,
         }
     }
 }
@@ -331,6 +340,7 @@ pub enum TxCancellationReason {
     TimeLocked,         // 5
     InvalidTransaction, // 6
     Oversized,          // 7
+    Replaced,           // 8
 }
 
 impl TryFrom<u32> for TxCancellationReason {
@@ -346,6 +356,7 @@ impl TryFrom<u32> for TxCancellationReason {
             5 => Ok(TxCancellationReason::TimeLocked),
             6 => Ok(TxCancellationReason::InvalidTransaction),
             7 => Ok(TxCancellationReason::Oversized),
+            8 => Ok(TxCancellationReason::Replaced),
             code => Err(TransactionConversionError { code: code as i32 }),
         }
     }
@@ -364,7 +375,366 @@ impl Display for TxCancellationReason {
             TimeLocked => "TimeLocked",
             InvalidTransaction => "Invalid Transaction",
             Oversized => "Oversized",
+            Replaced => "Replaced",
         };
         fmt.write_str(response)
     }
 }
+
+/// The side of an adaptor signature atomic swap this wallet is on
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AdaptorSwapRole {
+    /// Locked Tari in the swap output and learns the adaptor secret when the counterparty claims it
+    Initiator, // 0
+    /// Claims the swap output by completing the adaptor signature with the adaptor secret
+    Participant, // 1
+}
+
+impl TryFrom<i32> for AdaptorSwapRole {
+    type Error = TransactionConversionError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(AdaptorSwapRole::Initiator),
+            1 => Ok(AdaptorSwapRole::Participant),
+            code => Err(TransactionConversionError { code }),
+        }
+    }
+}
+
+impl Display for AdaptorSwapRole {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        match self {
+            AdaptorSwapRole::Initiator => fmt.write_str("Initiator"),
+            AdaptorSwapRole::Participant => fmt.write_str("Participant"),
+        }
+    }
+}
+
+/// The state of an adaptor signature atomic swap
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AdaptorSwapStatus {
+    /// The swap output has been sent, or received and not yet claimed
+    Locked, // 0
+    /// The participant claimed the swap output, revealing the completed signature
+    Claimed, // 1
+    /// The initiator extracted the adaptor secret from the completed signature
+    SecretRevealed, // 2
+    /// The initiator spent the swap output back to itself after the refund height
+    Refunded, // 3
+}
+
+impl AdaptorSwapStatus {
+    /// The swap state machine: a locked swap is claimed by the participant, and either reveals the secret to the
+    /// initiator or is refunded to it. All other states are final.
+    pub fn can_transition_to(self, role: AdaptorSwapRole, next: AdaptorSwapStatus) -> bool {
+        matches!(
+            (role, self, next),
+            (
+                AdaptorSwapRole::Participant,
+                AdaptorSwapStatus::Locked,
+                AdaptorSwapStatus::Claimed
+            ) | (
+                AdaptorSwapRole::Initiator,
+                AdaptorSwapStatus::Locked,
+                AdaptorSwapStatus::SecretRevealed
+            ) | (
+                AdaptorSwapRole::Initiator,
+                AdaptorSwapStatus::Locked,
+                AdaptorSwapStatus::Refunded
+            )
+        )
+    }
+}
+
+impl TryFrom<i32> for AdaptorSwapStatus {
+    type Error = TransactionConversionError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(AdaptorSwapStatus::Locked),
+            1 => Ok(AdaptorSwapStatus::Claimed),
+            2 => Ok(AdaptorSwapStatus::SecretRevealed),
+            3 => Ok(AdaptorSwapStatus::Refunded),
+            code => Err(TransactionConversionError { code }),
+        }
+    }
+}
+
+impl Display for AdaptorSwapStatus {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        let status = match self {
+            AdaptorSwapStatus::Locked => "Locked",
+            AdaptorSwapStatus::Claimed => "Claimed",
+            AdaptorSwapStatus::SecretRevealed => "Secret Revealed",
+            AdaptorSwapStatus::Refunded => "Refunded",
+        };
+        fmt.write_str(status)
+    }
+}
+
+/// An adaptor signature atomic swap, identified by the hash of the output locked in it
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AdaptorSwap {
+    pub output_hash: HashOutput,
+    pub role: AdaptorSwapRole,
+    pub status: AdaptorSwapStatus,
+    pub amount: MicroMinotari,
+    /// The key the `CheckSig` of the swap script verifies against
+    pub swap_public_key: PublicKey,
+    /// The adaptor point the pre-signature is encrypted to. Its discrete log is the participant's secret.
+    pub adaptor_point: PublicKey,
+    pub adaptor_signature: AdaptorSignature,
+    /// The height from which the initiator can refund the swap output
+    pub refund_height: u64,
+    /// The transaction that locked the swap output, if this wallet sent it
+    pub lock_tx_id: Option<TxId>,
+    /// The transaction that claimed or refunded the swap output, if this wallet sent it
+    pub spend_tx_id: Option<TxId>,
+    /// The completed signature, once the swap output has been claimed
+    pub signature: Option<CheckSigSchnorrSignature>,
+    /// The discrete log of the adaptor point, once the initiator has extracted it
+    pub adaptor_secret: Option<PrivateKey>,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// The state of a payment schedule
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum PaymentScheduleStatus {
+    /// Payments are made when they fall due
+    Active, // 0
+    /// All payments have been made
+    Completed, // 1
+    /// The schedule was cancelled before all payments were made
+    Cancelled, // 2
+    /// Too many attempts in a row to make a payment failed, so no further payments are made
+    Failed, // 3
+}
+
+impl TryFrom<i32> for PaymentScheduleStatus {
+    type Error = TransactionConversionError;
+
+    fn try_from(value: i32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(PaymentScheduleStatus::Active),
+            1 => Ok(PaymentScheduleStatus::Completed),
+            2 => Ok(PaymentScheduleStatus::Cancelled),
+            3 => Ok(PaymentScheduleStatus::Failed),
+            code => Err(TransactionConversionError { code }),
+        }
+    }
+}
+
+impl Display for PaymentScheduleStatus {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> Result<(), Error> {
+        let status = match self {
+            PaymentScheduleStatus::Active => "Active",
+            PaymentScheduleStatus::Completed => "Completed",
+            PaymentScheduleStatus::Cancelled => "Cancelled",
+            PaymentScheduleStatus::Failed => "Failed",
+        };
+        fmt.write_str(status)
+    }
+}
+
+/// The number of attempts in a row to make a payment of a schedule that may fail before the schedule fails
+pub const MAX_PAYMENT_SCHEDULE_FAILURES: u32 = 10;
+/// The delay before a failed payment of a schedule is retried, which doubles with every further failure
+const PAYMENT_RETRY_BASE_DELAY: Duration = Duration::from_secs(60);
+const PAYMENT_RETRY_MAX_DELAY: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// A one-sided payment the wallet makes automatically, once or at a fixed interval, while it is running
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PaymentSchedule {
+    /// Assigned by the database when the schedule is added
+    pub id: u32,
+    pub destination: TariAddress,
+    pub amount: MicroMinotari,
+    pub payment_id: PaymentId,
+    pub fee_per_gram: MicroMinotari,
+    /// A payment is not made while its estimated fee is higher than this
+    pub max_fee: Option<MicroMinotari>,
+    /// The time between payments, zero for a single payment
+    pub interval: Duration,
+    pub next_payment: DateTime<Utc>,
+    /// No payments are made after this time
+    pub end_time: Option<DateTime<Utc>>,
+    /// The number of payments that fell due before the latest one, e.g. while the wallet was not running, that are
+    /// still made. Older missed payments are skipped.
+    pub max_missed_payments: u32,
+    pub status: PaymentScheduleStatus,
+    pub payments_made: u64,
+    pub last_tx_id: Option<TxId>,
+    /// Why the last attempt to make a payment failed, cleared once a payment is made
+    pub last_error: Option<String>,
+    /// The number of attempts in a row to make a payment that failed
+    pub failed_attempts: u32,
+    /// A failed payment is not retried before this time
+    pub retry_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl PaymentSchedule {
+    pub fn new(
+        destination: TariAddress,
+        amount: MicroMinotari,
+        fee_per_gram: MicroMinotari,
+        interval: Duration,
+        start_time: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: 0,
+            destination,
+            amount,
+            payment_id: PaymentId::Empty,
+            fee_per_gram,
+            max_fee: None,
+            interval,
+            next_payment: start_time,
+            end_time: None,
+            max_missed_payments: 0,
+            status: PaymentScheduleStatus::Active,
+            payments_made: 0,
+            last_tx_id: None,
+            last_error: None,
+            failed_attempts: 0,
+            retry_at: None,
+            created_at: Utc::now(),
+        }
+    }
+
+    pub fn with_payment_id(mut self, payment_id: PaymentId) -> Self {
+        self.payment_id = payment_id;
+        self
+    }
+
+    pub fn with_max_fee(mut self, max_fee: Option<MicroMinotari>) -> Self {
+        self.max_fee = max_fee;
+        self
+    }
+
+    pub fn with_end_time(mut self, end_time: Option<DateTime<Utc>>) -> Self {
+        self.end_time = end_time;
+        self
+    }
+
+    pub fn with_max_missed_payments(mut self, max_missed_payments: u32) -> Self {
+        self.max_missed_payments = max_missed_payments;
+        self
+    }
+
+    /// Whether a payment of this schedule is due at `now`
+    pub fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.status == PaymentScheduleStatus::Active &&
+            self.next_payment <= now &&
+            self.retry_at.map_or(true, |retry_at| retry_at <= now)
+    }
+
+    /// Skips the payments that were missed before a payment is made at `now`. Of the payments that fell due before
+    /// the latest one, only the last `max_missed_payments` are kept, and they are made up one at a time. Returns the
+    /// number of payments skipped.
+    pub fn skip_missed_payments(&mut self, now: DateTime<Utc>) -> u64 {
+        let interval_ms = i64::try_from(self.interval.as_millis()).unwrap_or(i64::MAX);
+        if interval_ms == 0 {
+            return 0;
+        }
+        let last_due = self.end_time.map_or(now, |end_time| end_time.min(now));
+        let overdue_ms = last_due.signed_duration_since(self.next_payment).num_milliseconds();
+        let missed = u64::try_from(overdue_ms / interval_ms).unwrap_or(0);
+        let skipped = missed.saturating_sub(u64::from(self.max_missed_payments));
+        let next_payment = i64::try_from(skipped)
+            .ok()
+            .and_then(|skipped| interval_ms.checked_mul(skipped))
+            .and_then(chrono::Duration::try_milliseconds)
+            .and_then(|skip| self.next_payment.checked_add_signed(skip));
+        match next_payment {
+            Some(next_payment) if skipped > 0 => {
+                self.next_payment = next_payment;
+                skipped
+            },
+            _ => 0,
+        }
+    }
+
+    /// Records the payment made by `tx_id` and moves the schedule on to its next payment, completing the schedule
+    /// when there is none.
+    pub fn record_payment(&mut self, tx_id: TxId) {
+        self.payments_made += 1;
+        self.last_tx_id = Some(tx_id);
+        self.last_error = None;
+        self.failed_attempts = 0;
+        self.retry_at = None;
+        let next_payment = Some(self.interval)
+            .filter(|interval| !interval.is_zero())
+            .and_then(|interval| chrono::Duration::from_std(interval).ok())
+            .and_then(|interval| self.next_payment.checked_add_signed(interval));
+        match next_payment {
+            Some(next_payment) if self.end_time.map_or(true, |end_time| next_payment <= end_time) => {
+                self.next_payment = next_payment;
+            },
+            _ => self.status = PaymentScheduleStatus::Completed,
+        }
+    }
+
+    /// Records an attempt to make a payment that failed at `now`. The payment is retried after a delay that doubles
+    /// with every failure, until [`MAX_PAYMENT_SCHEDULE_FAILURES`] attempts in a row have failed and the schedule
+    /// fails.
+    pub fn record_failure(&mut self, error: String, now: DateTime<Utc>) {
+        self.failed_attempts = self.failed_attempts.saturating_add(1);
+        self.last_error = Some(error);
+        if self.failed_attempts >= MAX_PAYMENT_SCHEDULE_FAILURES {
+            self.status = PaymentScheduleStatus::Failed;
+            self.retry_at = None;
+            return;
+        }
+        let delay = PAYMENT_RETRY_BASE_DELAY
+            .saturating_mul(2u32.saturating_pow(self.failed_attempts - 1))
+            .min(PAYMENT_RETRY_MAX_DELAY);
+        self.retry_at = chrono::Duration::from_std(delay)
+            .ok()
+            .and_then(|delay| now.checked_add_signed(delay));
+    }
+}
+
+/// A recipient of a batch payment, paid with a one-sided stealth address output
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BatchRecipient {
+    pub address: TariAddress,
+    pub amount: MicroMinotari,
+    pub payment_id: PaymentId,
+}
+
+impl BatchRecipient {
+    /// The total amount paid to the recipients, or `None` if it overflows
+    pub fn total_amount(recipients: &[BatchRecipient]) -> Option<MicroMinotari> {
+        recipients
+            .iter()
+            .try_fold(MicroMinotari::zero(), |total, r| total.checked_add(r.amount))
+    }
+}
+
+/// The transaction service data held in a wallet backup. Transactions that are still being negotiated or broadcast,
+/// and the transactions prepared for offline signing whose inputs they encumber, are not included.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TransactionServiceBackup {
+    pub completed_transactions: Vec<CompletedTransaction>,
+    pub transaction_labels: Vec<(TxId, String)>,
+    /// The recipients of the backed up batch payments
+    pub batch_recipients: Vec<(TxId, Vec<BatchRecipient>)>,
+    pub adaptor_swaps: Vec<AdaptorSwap>,
+    pub payment_schedules: Vec<PaymentSchedule>,
+}
+
+/// A transaction prepared by a view-only wallet for offline signing. Its inputs stay encumbered until the signed
+/// transaction is finalised or the prepared transaction is abandoned, and the signed transaction must pay exactly what
+/// was prepared.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreparedTransaction {
+    pub tx_id: TxId,
+    pub destination: TariAddress,
+    pub amount: MicroMinotari,
+    pub fee: MicroMinotari,
+    pub change: MicroMinotari,
+    pub payment_id: PaymentId,
+    pub timestamp: DateTime<Utc>,
+}
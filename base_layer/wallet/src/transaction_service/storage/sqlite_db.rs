@@ -24,6 +24,7 @@ use std::{
     collections::HashMap,
     convert::{TryFrom, TryInto},
     sync::{Arc, RwLock},
+    time::Duration,
 };
 
 use chacha20poly1305::XChaCha20Poly1305;
@@ -41,25 +42,47 @@ use tari_common_types::{
         TransactionStatus,
         TxId,
     },
-    types::{BlockHash, PrivateKey, PublicKey, Signature},
+    types::{BlockHash, HashOutput, PrivateKey, PublicKey, Signature},
 };
-use tari_core::transactions::{tari_amount::MicroMinotari, transaction_components::encrypted_data::PaymentId};
+use tari_core::transactions::{
+    tari_amount::MicroMinotari,
+    transaction_components::encrypted_data::PaymentId,
+    transaction_protocol::adaptor_signature::AdaptorSignature,
+};
+use tari_script::CheckSigSchnorrSignature;
 use tari_utilities::{hex::Hex, ByteArray, Hidden};
 use thiserror::Error;
 use tokio::time::Instant;
 use zeroize::Zeroize;
 
 use crate::{
-    schema::{completed_transactions, inbound_transactions, outbound_transactions},
+    schema::{
+        adaptor_swaps,
+        batch_recipients,
+        completed_transactions,
+        inbound_transactions,
+        outbound_transactions,
+        payment_schedules,
+        prepared_transactions,
+        transaction_labels,
+    },
     storage::sqlite_utilities::wallet_db_connection::WalletDbConnection,
     transaction_service::{
         error::{TransactionKeyError, TransactionStorageError},
         storage::{
             database::{DbKey, DbKeyValuePair, DbValue, TransactionBackend, WriteOperation},
             models::{
+                AdaptorSwap,
+                AdaptorSwapRole,
+                AdaptorSwapStatus,
+                BatchRecipient,
                 CompletedTransaction,
                 InboundTransaction,
                 OutboundTransaction,
+                PaymentSchedule,
+                PaymentScheduleStatus,
+                PreparedTransaction,
+                TransactionServiceBackup,
                 TxCancellationReason,
                 WalletTransaction,
             },
@@ -1088,6 +1111,244 @@ impl TransactionBackend for TransactionServiceSqliteDatabase {
         Ok(coinbases)
     }
 
+    fn set_completed_transaction_replaced_by(
+        &self,
+        tx_id: TxId,
+        replaced_by_tx_id: TxId,
+    ) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+
+        match CompletedTransactionSql::set_replaced_by(tx_id, replaced_by_tx_id, &mut conn) {
+            Err(TransactionStorageError::DieselError(DieselError::NotFound)) => Err(
+                TransactionStorageError::ValueNotFound(DbKey::CompletedTransaction(tx_id)),
+            ),
+            result => result,
+        }
+    }
+
+    fn fetch_replaced_transactions(&self) -> Result<Vec<CompletedTransaction>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let cipher = acquire_read_lock!(self.cipher);
+
+        CompletedTransactionSql::index_replaced(&mut conn)?
+            .into_iter()
+            .map(|ct: CompletedTransactionSql| {
+                CompletedTransaction::try_from(ct, &cipher).map_err(TransactionStorageError::from)
+            })
+            .collect::<Result<Vec<CompletedTransaction>, TransactionStorageError>>()
+    }
+
+    fn fetch_adaptor_swap(&self, output_hash: &HashOutput) -> Result<Option<AdaptorSwap>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let cipher = acquire_read_lock!(self.cipher);
+
+        AdaptorSwapSql::find(output_hash.as_slice(), &mut conn)?
+            .map(|swap| AdaptorSwap::try_from(swap, &cipher))
+            .transpose()
+    }
+
+    fn fetch_adaptor_swaps(&self) -> Result<Vec<AdaptorSwap>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let cipher = acquire_read_lock!(self.cipher);
+
+        AdaptorSwapSql::index(&mut conn)?
+            .into_iter()
+            .map(|swap| AdaptorSwap::try_from(swap, &cipher))
+            .collect()
+    }
+
+    fn save_adaptor_swap(&self, swap: AdaptorSwap) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let cipher = acquire_read_lock!(self.cipher);
+
+        AdaptorSwapSql::try_from(swap, &cipher)?.commit(&mut conn)
+    }
+
+    fn fetch_transaction_labels(&self) -> Result<HashMap<TxId, String>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        Ok(transaction_labels::table
+            .load::<TransactionLabelSql>(&mut conn)?
+            .into_iter()
+            .map(|l| (TxId::from(l.tx_id as u64), l.label))
+            .collect())
+    }
+
+    fn set_transaction_label(&self, tx_id: TxId, label: Option<&str>) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        match label {
+            Some(label) => {
+                diesel::replace_into(transaction_labels::table)
+                    .values(TransactionLabelSql {
+                        tx_id: tx_id.as_i64_wrapped(),
+                        label: label.to_string(),
+                    })
+                    .execute(&mut conn)?;
+            },
+            None => {
+                diesel::delete(transaction_labels::table.filter(transaction_labels::tx_id.eq(tx_id.as_i64_wrapped())))
+                    .execute(&mut conn)?;
+            },
+        }
+        Ok(())
+    }
+
+    fn fetch_payment_schedules(&self) -> Result<Vec<PaymentSchedule>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        payment_schedules::table
+            .order_by(payment_schedules::id.asc())
+            .load::<PaymentScheduleSql>(&mut conn)?
+            .into_iter()
+            .map(PaymentSchedule::try_from)
+            .collect()
+    }
+
+    fn insert_payment_schedule(&self, schedule: PaymentSchedule) -> Result<u32, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let schedule_sql = NewPaymentScheduleSql::from(schedule);
+        conn.transaction::<_, TransactionStorageError, _>(|conn| {
+            diesel::insert_into(payment_schedules::table)
+                .values(schedule_sql)
+                .execute(conn)?;
+            let id = payment_schedules::table
+                .select(diesel::dsl::max(payment_schedules::id))
+                .first::<Option<i32>>(conn)?
+                .ok_or_else(|| {
+                    TransactionStorageError::UnexpectedResult("Payment schedule not inserted".to_string())
+                })?;
+            Ok(id as u32)
+        })
+    }
+
+    fn update_payment_schedule(&self, schedule: PaymentSchedule) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let num_updated = diesel::update(payment_schedules::table.filter(payment_schedules::id.eq(schedule.id as i32)))
+            .set((
+                payment_schedules::next_payment.eq(schedule.next_payment.naive_utc()),
+                payment_schedules::status.eq(schedule.status as i32),
+                payment_schedules::payments_made.eq(schedule.payments_made as i64),
+                payment_schedules::last_tx_id.eq(schedule.last_tx_id.map(|tx_id| tx_id.as_i64_wrapped())),
+                payment_schedules::last_error.eq(schedule.last_error),
+                payment_schedules::failed_attempts.eq(schedule.failed_attempts as i32),
+                payment_schedules::retry_at.eq(schedule.retry_at.map(|retry_at| retry_at.naive_utc())),
+            ))
+            .execute(&mut conn)?;
+        if num_updated == 0 {
+            return Err(TransactionStorageError::PaymentScheduleNotFound(schedule.id));
+        }
+        Ok(())
+    }
+
+    fn fetch_batch_recipients(&self, tx_id: TxId) -> Result<Vec<BatchRecipient>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        batch_recipients::table
+            .filter(batch_recipients::tx_id.eq(tx_id.as_i64_wrapped()))
+            .order_by(batch_recipients::position.asc())
+            .load::<BatchRecipientSql>(&mut conn)?
+            .into_iter()
+            .map(BatchRecipient::try_from)
+            .collect()
+    }
+
+    fn insert_batch_recipients(
+        &self,
+        tx_id: TxId,
+        recipients: Vec<BatchRecipient>,
+    ) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        diesel::insert_into(batch_recipients::table)
+            .values(BatchRecipientSql::from_recipients(tx_id, recipients))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    fn fetch_prepared_transaction(&self, tx_id: TxId) -> Result<Option<PreparedTransaction>, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        prepared_transactions::table
+            .filter(prepared_transactions::tx_id.eq(tx_id.as_i64_wrapped()))
+            .first::<PreparedTransactionSql>(&mut conn)
+            .optional()?
+            .map(PreparedTransaction::try_from)
+            .transpose()
+    }
+
+    fn insert_prepared_transaction(&self, transaction: PreparedTransaction) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        diesel::insert_into(prepared_transactions::table)
+            .values(PreparedTransactionSql::from(transaction))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    fn remove_prepared_transaction(&self, tx_id: TxId) -> Result<(), TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        diesel::delete(prepared_transactions::table.filter(prepared_transactions::tx_id.eq(tx_id.as_i64_wrapped())))
+            .execute(&mut conn)?;
+        Ok(())
+    }
+
+    fn import_backup(&self, backup: TransactionServiceBackup) -> Result<usize, TransactionStorageError> {
+        let mut conn = self.database_connection.get_pooled_connection()?;
+        let cipher = acquire_read_lock!(self.cipher);
+
+        conn.transaction::<_, TransactionStorageError, _>(|conn| {
+            let mut imported = 0;
+            for transaction in backup.completed_transactions {
+                let tx_id = transaction.tx_id;
+                if CompletedTransactionSql::find(tx_id, conn).is_ok() ||
+                    InboundTransactionSql::find(tx_id, conn).is_ok() ||
+                    OutboundTransactionSql::find(tx_id, conn).is_ok()
+                {
+                    continue;
+                }
+                CompletedTransactionSql::try_from(transaction, &cipher)?.commit(conn)?;
+                imported += 1;
+            }
+
+            for (tx_id, label) in backup.transaction_labels {
+                diesel::insert_or_ignore_into(transaction_labels::table)
+                    .values(TransactionLabelSql {
+                        tx_id: tx_id.as_i64_wrapped(),
+                        label,
+                    })
+                    .execute(conn)?;
+            }
+
+            for (tx_id, recipients) in backup.batch_recipients {
+                let existing = batch_recipients::table
+                    .filter(batch_recipients::tx_id.eq(tx_id.as_i64_wrapped()))
+                    .count()
+                    .get_result::<i64>(conn)?;
+                if existing == 0 {
+                    diesel::insert_into(batch_recipients::table)
+                        .values(BatchRecipientSql::from_recipients(tx_id, recipients))
+                        .execute(conn)?;
+                }
+            }
+
+            for swap in backup.adaptor_swaps {
+                if AdaptorSwapSql::find(swap.output_hash.as_slice(), conn)?.is_none() {
+                    AdaptorSwapSql::try_from(swap, &cipher)?.commit(conn)?;
+                }
+            }
+
+            // Schedules are assigned new ids, so a schedule is identified by when it was created and who it pays
+            for schedule in backup.payment_schedules {
+                let existing = payment_schedules::table
+                    .filter(payment_schedules::created_at.eq(schedule.created_at.naive_utc()))
+                    .filter(payment_schedules::destination.eq(schedule.destination.to_vec()))
+                    .count()
+                    .get_result::<i64>(conn)?;
+                if existing == 0 {
+                    diesel::insert_into(payment_schedules::table)
+                        .values(NewPaymentScheduleSql::from(schedule))
+                        .execute(conn)?;
+                }
+            }
+
+            Ok(imported)
+        })
+    }
+
     fn fetch_confirmed_detected_transactions_from_height(
         &self,
         height: u64,
@@ -1699,6 +1960,7 @@ pub struct CompletedTransactionSql {
     transaction_signature_nonce: Vec<u8>,
     transaction_signature_key: Vec<u8>,
     payment_id: Option<Vec<u8>>,
+    replaced_by_tx_id: Option<i64>,
 }
 
 impl CompletedTransactionSql {
@@ -1764,6 +2026,15 @@ impl CompletedTransactionSql {
             .load::<CompletedTransactionSql>(conn)?)
     }
 
+    pub fn index_replaced(
+        conn: &mut SqliteConnection,
+    ) -> Result<Vec<CompletedTransactionSql>, TransactionStorageError> {
+        Ok(completed_transactions::table
+            .filter(completed_transactions::replaced_by_tx_id.is_not_null())
+            .order_by(completed_transactions::tx_id)
+            .load::<CompletedTransactionSql>(conn)?)
+    }
+
     pub fn find(tx_id: TxId, conn: &mut SqliteConnection) -> Result<CompletedTransactionSql, TransactionStorageError> {
         Ok(completed_transactions::table
             .filter(completed_transactions::tx_id.eq(tx_id.as_u64() as i64))
@@ -1809,6 +2080,22 @@ impl CompletedTransactionSql {
         Ok(())
     }
 
+    pub fn set_replaced_by(
+        tx_id: TxId,
+        replaced_by_tx_id: TxId,
+        conn: &mut SqliteConnection,
+    ) -> Result<(), TransactionStorageError> {
+        diesel::update(completed_transactions::table.filter(completed_transactions::tx_id.eq(tx_id.as_u64() as i64)))
+            .set(UpdateCompletedTransactionSql {
+                replaced_by_tx_id: Some(Some(replaced_by_tx_id.as_u64() as i64)),
+                ..Default::default()
+            })
+            .execute(conn)
+            .num_rows_affected_or_not_found(1)?;
+
+        Ok(())
+    }
+
     pub fn increment_send_count(tx_id: TxId, conn: &mut SqliteConnection) -> Result<(), TransactionStorageError> {
         // This query uses a sub-query to retrieve an existing value in the table
         diesel::update(completed_transactions::table.filter(completed_transactions::tx_id.eq(tx_id.as_u64() as i64)))
@@ -1983,6 +2270,7 @@ impl CompletedTransactionSql {
             transaction_signature_nonce: c.transaction_signature.get_public_nonce().to_vec(),
             transaction_signature_key: c.transaction_signature.get_signature().to_vec(),
             payment_id: Some(c.payment_id.to_bytes()),
+            replaced_by_tx_id: c.replaced_by_tx_id.map(|v| v.as_u64() as i64),
         };
 
         output.encrypt(cipher).map_err(TransactionStorageError::AeadError)
@@ -2080,6 +2368,7 @@ impl CompletedTransaction {
             mined_in_block,
             mined_timestamp: c.mined_timestamp.map(|t| t.and_utc()),
             payment_id: PaymentId::from_bytes(&c.payment_id.unwrap_or_default()),
+            replaced_by_tx_id: c.replaced_by_tx_id.map(|v| (v as u64).into()),
         };
 
         // zeroize sensitive data
@@ -2105,6 +2394,7 @@ pub struct UpdateCompletedTransactionSql {
     mined_timestamp: Option<NaiveDateTime>,
     transaction_signature_nonce: Option<Vec<u8>>,
     transaction_signature_key: Option<Vec<u8>>,
+    replaced_by_tx_id: Option<Option<i64>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -2179,6 +2469,332 @@ impl UnconfirmedTransactionInfoSql {
     }
 }
 
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = adaptor_swaps)]
+struct AdaptorSwapSql {
+    output_hash: Vec<u8>,
+    role: i32,
+    status: i32,
+    amount: i64,
+    swap_public_key: Vec<u8>,
+    adaptor_point: Vec<u8>,
+    public_nonce: Vec<u8>,
+    pre_signature: Vec<u8>,
+    refund_height: i64,
+    lock_tx_id: Option<i64>,
+    spend_tx_id: Option<i64>,
+    signature_nonce: Option<Vec<u8>>,
+    signature: Option<Vec<u8>>,
+    adaptor_secret: Option<Vec<u8>>,
+    timestamp: NaiveDateTime,
+}
+
+impl AdaptorSwapSql {
+    /// Inserts the swap, or replaces the swap with the same output hash
+    pub fn commit(&self, conn: &mut SqliteConnection) -> Result<(), TransactionStorageError> {
+        diesel::replace_into(adaptor_swaps::table)
+            .values(self.clone())
+            .execute(conn)?;
+        Ok(())
+    }
+
+    pub fn index(conn: &mut SqliteConnection) -> Result<Vec<AdaptorSwapSql>, TransactionStorageError> {
+        Ok(adaptor_swaps::table
+            .order_by(adaptor_swaps::timestamp.desc())
+            .load::<AdaptorSwapSql>(conn)?)
+    }
+
+    pub fn find(
+        output_hash: &[u8],
+        conn: &mut SqliteConnection,
+    ) -> Result<Option<AdaptorSwapSql>, TransactionStorageError> {
+        Ok(adaptor_swaps::table
+            .filter(adaptor_swaps::output_hash.eq(output_hash))
+            .first::<AdaptorSwapSql>(conn)
+            .optional()?)
+    }
+
+    fn try_from(swap: AdaptorSwap, cipher: &XChaCha20Poly1305) -> Result<Self, TransactionStorageError> {
+        let (signature_nonce, signature) = match swap.signature {
+            Some(signature) => (
+                Some(signature.get_public_nonce().to_vec()),
+                Some(signature.get_signature().to_vec()),
+            ),
+            None => (None, None),
+        };
+        let swap_sql = Self {
+            output_hash: swap.output_hash.to_vec(),
+            role: swap.role as i32,
+            status: swap.status as i32,
+            amount: swap.amount.as_u64() as i64,
+            swap_public_key: swap.swap_public_key.to_vec(),
+            adaptor_point: swap.adaptor_point.to_vec(),
+            public_nonce: swap.adaptor_signature.public_nonce().to_vec(),
+            pre_signature: swap.adaptor_signature.pre_signature().to_vec(),
+            refund_height: swap.refund_height as i64,
+            lock_tx_id: swap.lock_tx_id.map(|tx_id| tx_id.as_i64_wrapped()),
+            spend_tx_id: swap.spend_tx_id.map(|tx_id| tx_id.as_i64_wrapped()),
+            signature_nonce,
+            signature,
+            adaptor_secret: swap.adaptor_secret.map(|secret| secret.to_vec()),
+            timestamp: swap.timestamp.naive_utc(),
+        };
+        swap_sql.encrypt(cipher).map_err(TransactionStorageError::AeadError)
+    }
+}
+
+impl Encryptable<XChaCha20Poly1305> for AdaptorSwapSql {
+    fn domain(&self, field_name: &'static str) -> Vec<u8> {
+        [Self::ADAPTOR_SWAP, self.output_hash.as_slice(), field_name.as_bytes()]
+            .concat()
+            .to_vec()
+    }
+
+    fn encrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        if let Some(adaptor_secret) = self.adaptor_secret.take() {
+            self.adaptor_secret = Some(encrypt_bytes_integral_nonce(
+                cipher,
+                self.domain("adaptor_secret"),
+                Hidden::hide(adaptor_secret),
+            )?);
+        }
+        Ok(self)
+    }
+
+    fn decrypt(mut self, cipher: &XChaCha20Poly1305) -> Result<Self, String> {
+        if let Some(adaptor_secret) = self.adaptor_secret.take() {
+            self.adaptor_secret = Some(decrypt_bytes_integral_nonce(
+                cipher,
+                self.domain("adaptor_secret"),
+                &adaptor_secret,
+            )?);
+        }
+        Ok(self)
+    }
+}
+
+impl AdaptorSwap {
+    fn try_from(swap: AdaptorSwapSql, cipher: &XChaCha20Poly1305) -> Result<Self, TransactionStorageError> {
+        let mut swap = swap.decrypt(cipher).map_err(TransactionStorageError::AeadError)?;
+        let signature = match (&swap.signature_nonce, &swap.signature) {
+            (Some(nonce), Some(signature)) => Some(CheckSigSchnorrSignature::new(
+                PublicKey::from_vec(nonce)?,
+                PrivateKey::from_vec(signature)?,
+            )),
+            _ => None,
+        };
+        let adaptor_secret = swap
+            .adaptor_secret
+            .as_ref()
+            .map(|secret| PrivateKey::from_vec(secret))
+            .transpose()?;
+        let output = Self {
+            output_hash: HashOutput::try_from(swap.output_hash.clone())
+                .map_err(|e| TransactionStorageError::ByteArrayError(e.to_string()))?,
+            role: AdaptorSwapRole::try_from(swap.role)?,
+            status: AdaptorSwapStatus::try_from(swap.status)?,
+            amount: MicroMinotari::from(swap.amount as u64),
+            swap_public_key: PublicKey::from_vec(&swap.swap_public_key)?,
+            adaptor_point: PublicKey::from_vec(&swap.adaptor_point)?,
+            adaptor_signature: AdaptorSignature::new(
+                PublicKey::from_vec(&swap.public_nonce)?,
+                PrivateKey::from_vec(&swap.pre_signature)?,
+            ),
+            refund_height: swap.refund_height as u64,
+            lock_tx_id: swap.lock_tx_id.map(|tx_id| (tx_id as u64).into()),
+            spend_tx_id: swap.spend_tx_id.map(|tx_id| (tx_id as u64).into()),
+            signature,
+            adaptor_secret,
+            timestamp: swap.timestamp.and_utc(),
+        };
+
+        // zeroize sensitive data
+        swap.adaptor_secret.zeroize();
+
+        Ok(output)
+    }
+}
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = transaction_labels)]
+struct TransactionLabelSql {
+    tx_id: i64,
+    label: String,
+}
+
+#[derive(Clone, Debug, Queryable, PartialEq)]
+#[diesel(table_name = payment_schedules)]
+struct PaymentScheduleSql {
+    id: i32,
+    destination: Vec<u8>,
+    amount: i64,
+    payment_id: Vec<u8>,
+    fee_per_gram: i64,
+    max_fee: Option<i64>,
+    interval_secs: i64,
+    next_payment: NaiveDateTime,
+    end_time: Option<NaiveDateTime>,
+    max_missed_payments: i32,
+    status: i32,
+    payments_made: i64,
+    last_tx_id: Option<i64>,
+    last_error: Option<String>,
+    failed_attempts: i32,
+    retry_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+}
+
+/// A payment schedule that has not been assigned an id yet
+#[derive(Clone, Debug, Insertable, PartialEq)]
+#[diesel(table_name = payment_schedules)]
+struct NewPaymentScheduleSql {
+    destination: Vec<u8>,
+    amount: i64,
+    payment_id: Vec<u8>,
+    fee_per_gram: i64,
+    max_fee: Option<i64>,
+    interval_secs: i64,
+    next_payment: NaiveDateTime,
+    end_time: Option<NaiveDateTime>,
+    max_missed_payments: i32,
+    status: i32,
+    payments_made: i64,
+    last_tx_id: Option<i64>,
+    last_error: Option<String>,
+    failed_attempts: i32,
+    retry_at: Option<NaiveDateTime>,
+    created_at: NaiveDateTime,
+}
+
+impl From<PaymentSchedule> for NewPaymentScheduleSql {
+    fn from(schedule: PaymentSchedule) -> Self {
+        Self {
+            destination: schedule.destination.to_vec(),
+            amount: schedule.amount.as_u64() as i64,
+            payment_id: schedule.payment_id.to_bytes(),
+            fee_per_gram: schedule.fee_per_gram.as_u64() as i64,
+            max_fee: schedule.max_fee.map(|fee| fee.as_u64() as i64),
+            interval_secs: schedule.interval.as_secs() as i64,
+            next_payment: schedule.next_payment.naive_utc(),
+            end_time: schedule.end_time.map(|end_time| end_time.naive_utc()),
+            max_missed_payments: schedule.max_missed_payments as i32,
+            status: schedule.status as i32,
+            payments_made: schedule.payments_made as i64,
+            last_tx_id: schedule.last_tx_id.map(|tx_id| tx_id.as_i64_wrapped()),
+            last_error: schedule.last_error,
+            failed_attempts: schedule.failed_attempts as i32,
+            retry_at: schedule.retry_at.map(|retry_at| retry_at.naive_utc()),
+            created_at: schedule.created_at.naive_utc(),
+        }
+    }
+}
+
+impl TryFrom<PaymentScheduleSql> for PaymentSchedule {
+    type Error = TransactionStorageError;
+
+    fn try_from(schedule: PaymentScheduleSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: schedule.id as u32,
+            destination: TariAddress::from_bytes(&schedule.destination)?,
+            amount: MicroMinotari::from(schedule.amount as u64),
+            payment_id: PaymentId::from_bytes(&schedule.payment_id),
+            fee_per_gram: MicroMinotari::from(schedule.fee_per_gram as u64),
+            max_fee: schedule.max_fee.map(|fee| MicroMinotari::from(fee as u64)),
+            interval: Duration::from_secs(schedule.interval_secs as u64),
+            next_payment: schedule.next_payment.and_utc(),
+            end_time: schedule.end_time.map(|end_time| end_time.and_utc()),
+            max_missed_payments: schedule.max_missed_payments as u32,
+            status: PaymentScheduleStatus::try_from(schedule.status)?,
+            payments_made: schedule.payments_made as u64,
+            last_tx_id: schedule.last_tx_id.map(|tx_id| (tx_id as u64).into()),
+            last_error: schedule.last_error,
+            failed_attempts: schedule.failed_attempts as u32,
+            retry_at: schedule.retry_at.map(|retry_at| retry_at.and_utc()),
+            created_at: schedule.created_at.and_utc(),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = batch_recipients)]
+struct BatchRecipientSql {
+    tx_id: i64,
+    position: i32,
+    address: Vec<u8>,
+    amount: i64,
+    payment_id: Vec<u8>,
+}
+
+impl BatchRecipientSql {
+    fn from_recipients(tx_id: TxId, recipients: Vec<BatchRecipient>) -> Vec<Self> {
+        recipients
+            .into_iter()
+            .enumerate()
+            .map(|(position, recipient)| Self {
+                tx_id: tx_id.as_i64_wrapped(),
+                position: position as i32,
+                address: recipient.address.to_vec(),
+                amount: recipient.amount.as_u64() as i64,
+                payment_id: recipient.payment_id.to_bytes(),
+            })
+            .collect()
+    }
+}
+
+impl TryFrom<BatchRecipientSql> for BatchRecipient {
+    type Error = TransactionStorageError;
+
+    fn try_from(recipient: BatchRecipientSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            address: TariAddress::from_bytes(&recipient.address)?,
+            amount: MicroMinotari::from(recipient.amount as u64),
+            payment_id: PaymentId::from_bytes(&recipient.payment_id),
+        })
+    }
+}
+
+#[derive(Clone, Debug, Queryable, Insertable, PartialEq)]
+#[diesel(table_name = prepared_transactions)]
+struct PreparedTransactionSql {
+    tx_id: i64,
+    destination: Vec<u8>,
+    amount: i64,
+    fee: i64,
+    change: i64,
+    payment_id: Vec<u8>,
+    timestamp: NaiveDateTime,
+}
+
+impl From<PreparedTransaction> for PreparedTransactionSql {
+    fn from(transaction: PreparedTransaction) -> Self {
+        Self {
+            tx_id: transaction.tx_id.as_i64_wrapped(),
+            destination: transaction.destination.to_vec(),
+            amount: transaction.amount.as_u64() as i64,
+            fee: transaction.fee.as_u64() as i64,
+            change: transaction.change.as_u64() as i64,
+            payment_id: transaction.payment_id.to_bytes(),
+            timestamp: transaction.timestamp.naive_utc(),
+        }
+    }
+}
+
+impl TryFrom<PreparedTransactionSql> for PreparedTransaction {
+    type Error = TransactionStorageError;
+
+    fn try_from(transaction: PreparedTransactionSql) -> Result<Self, Self::Error> {
+        Ok(Self {
+            tx_id: (transaction.tx_id as u64).into(),
+            destination: TariAddress::from_bytes(&transaction.destination)?,
+            amount: MicroMinotari::from(transaction.amount as u64),
+            fee: MicroMinotari::from(transaction.fee as u64),
+            change: MicroMinotari::from(transaction.change as u64),
+            payment_id: PaymentId::from_bytes(&transaction.payment_id),
+            timestamp: transaction.timestamp.and_utc(),
+        })
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::{mem::size_of, time::Duration};
@@ -2490,6 +3106,7 @@ mod test {
             mined_in_block: None,
             mined_timestamp: None,
             payment_id: PaymentId::open_from_str("Yo!"),
+            replaced_by_tx_id: None,
         };
         let source_address = TariAddress::new_dual_address_with_default_features(
             PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
@@ -2520,6 +3137,7 @@ mod test {
             mined_in_block: None,
             mined_timestamp: None,
             payment_id: PaymentId::open_from_str("Yo!"),
+            replaced_by_tx_id: None,
         };
 
         CompletedTransactionSql::try_from(completed_tx1.clone(), &cipher)
@@ -2759,6 +3377,7 @@ mod test {
             mined_in_block: None,
             mined_timestamp: None,
             payment_id: PaymentId::open_from_str("Yo!"),
+            replaced_by_tx_id: None,
         };
 
         let completed_tx_sql = CompletedTransactionSql::try_from(completed_tx.clone(), &cipher).unwrap();
@@ -2891,6 +3510,7 @@ mod test {
                 mined_in_block: None,
                 mined_timestamp: None,
                 payment_id: PaymentId::open_from_str("Yo!"),
+                replaced_by_tx_id: None,
             };
             let completed_tx_sql = CompletedTransactionSql::try_from(completed_tx, &cipher).unwrap();
 
@@ -3033,6 +3653,7 @@ mod test {
                 mined_in_block: None,
                 mined_timestamp: None,
                 payment_id: PaymentId::open_from_str("Yo!"),
+                replaced_by_tx_id: None,
             };
             let completed_tx_sql = CompletedTransactionSql::try_from(completed_tx.clone(), &cipher).unwrap();
 
@@ -503,6 +503,18 @@ where
             total_scanned += outputs.len();
 
             let start = Instant::now();
+            // A failure to find multi-signature account outputs must not stop the wallet from finding its own
+            if let Err(e) = self
+                .resources
+                .output_manager_service
+                .scan_outputs_for_multisig_accounts(outputs.clone(), current_height)
+                .await
+            {
+                warn!(
+                    target: LOG_TARGET,
+                    "Failed to scan block {} for multi-signature account outputs: {}", current_height, e
+                );
+            }
             let found_outputs = self.scan_for_outputs(outputs).await?;
             scan_for_outputs_profiling.push(start.elapsed());
 
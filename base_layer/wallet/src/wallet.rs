@@ -20,9 +20,17 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::{cmp, marker::PhantomData, sync::Arc, thread};
+use std::{
+    cmp,
+    collections::{HashMap, HashSet},
+    marker::PhantomData,
+    str::FromStr,
+    sync::Arc,
+    thread,
+};
 
 use blake2::Blake2b;
+use chrono::Utc;
 use digest::consts::U32;
 use futures::executor::block_on;
 use log::*;
@@ -48,6 +56,7 @@ use tari_comms_dht::{store_forward::StoreAndForwardRequester, Dht};
 use tari_contacts::contacts_service::{
     handle::ContactsServiceHandle,
     storage::database::ContactsBackend,
+    types::Contact,
     ContactsServiceInitializer,
 };
 use tari_core::{
@@ -80,9 +89,10 @@ use tari_p2p::{
 use tari_script::{push_pubkey_script, ExecutionStack, TariScript};
 use tari_service_framework::StackBuilder;
 use tari_shutdown::ShutdownSignal;
-use tari_utilities::{hex::Hex, ByteArray};
+use tari_utilities::{hex::Hex, ByteArray, SafePassword};
 
 use crate::{
+    backup::{BackupContact, BackupOutput, BackupScannedBlock, RestoredWalletBackup, WalletBackup},
     base_node_service::{handle::BaseNodeServiceHandle, BaseNodeServiceInitializer},
     config::WalletConfig,
     connectivity_service::{
@@ -97,19 +107,26 @@ use crate::{
         error::OutputManagerError,
         handle::OutputManagerHandle,
         storage::{
-            database::{OutputManagerBackend, OutputManagerDatabase},
-            models::KnownOneSidedPaymentScript,
+            database::{OutputBackendQuery, OutputManagerBackend, OutputManagerDatabase},
+            models::{DbWalletOutput, KnownOneSidedPaymentScript, OutputManagerBackup},
+            OutputStatus,
         },
         OutputManagerServiceInitializer,
     },
     storage::database::{WalletBackend, WalletDatabase},
     transaction_service::{
         handle::TransactionServiceHandle,
+        history_export::{format_transaction_history, transaction_history, TransactionHistoryFormat},
         storage::database::TransactionBackend,
         TransactionServiceInitializer,
     },
     util::wallet_identity::WalletIdentity,
-    utxo_scanner_service::{handle::UtxoScannerHandle, initializer::UtxoScannerServiceInitializer, RECOVERY_KEY},
+    utxo_scanner_service::{
+        handle::UtxoScannerHandle,
+        initializer::UtxoScannerServiceInitializer,
+        service::ScannedBlock,
+        RECOVERY_KEY,
+    },
 };
 
 const LOG_TARGET: &str = "wallet";
@@ -200,6 +217,7 @@ where
                 output_manager_backend.clone(),
                 factories.clone(),
                 config.network.into(),
+                wallet_type.clone(),
             ))
             .add_initializer(TransactionKeyManagerInitializer::new(
                 key_manager_backend,
@@ -787,6 +805,32 @@ where
         Ok(self.db.get_client_key_value(RECOVERY_KEY.to_string())?.is_some())
     }
 
+    /// Export the completed and cancelled transactions of the wallet, oldest first, for bookkeeping
+    pub async fn export_transaction_history(
+        &mut self,
+        format: TransactionHistoryFormat,
+    ) -> Result<String, WalletError> {
+        let mut transactions = self
+            .transaction_service
+            .get_completed_transactions()
+            .await?
+            .into_values()
+            .collect::<Vec<_>>();
+        transactions.extend(
+            self.transaction_service
+                .get_cancelled_completed_transactions()
+                .await?
+                .into_values(),
+        );
+        let labels = self.transaction_service.get_transaction_labels().await?;
+        let tip_height = self.db.get_chain_metadata()?.map(|m| m.best_block_height());
+
+        let interactive_address = self.get_wallet_interactive_address().await?;
+
+        let history = transaction_history(transactions, &labels, tip_height, &interactive_address);
+        Ok(format_transaction_history(&history, format)?)
+    }
+
     pub fn get_seed_words(&self, language: &MnemonicLanguage) -> Result<SeedWords, WalletError> {
         let master_seed = self.db.get_master_seed()?.ok_or_else(|| {
             WalletError::WalletStorageError(WalletStorageError::RecoverySeedError(
@@ -797,6 +841,270 @@ where
         let seed_words = master_seed.to_mnemonic(*language, None)?;
         Ok(seed_words)
     }
+
+    /// Create an encrypted backup of the wallet data that cannot be recovered from the seed words. Transactions that
+    /// are still being negotiated or broadcast are not included, and neither are the outputs they encumber. Those
+    /// outputs are found again by scanning from the backed up block once the transactions are mined.
+    #[allow(clippy::too_many_lines)]
+    pub async fn create_backup(&mut self, passphrase: &SafePassword) -> Result<Vec<u8>, WalletError> {
+        let scanned_block = self
+            .db
+            .get_scanned_blocks()?
+            .into_iter()
+            .max_by_key(|b| b.height)
+            .map(|b| BackupScannedBlock {
+                height: b.height,
+                header_hash: b.header_hash,
+            });
+
+        let contacts = self
+            .contacts_service
+            .get_contacts()
+            .await?
+            .into_iter()
+            .map(|c| BackupContact {
+                alias: c.alias,
+                address: c.address,
+                favourite: c.favourite,
+            })
+            .collect();
+
+        let wallet_accounts = self.output_db.get_wallet_accounts().map_err(OutputManagerError::from)?;
+        let mut output_accounts = HashMap::new();
+        for account in &wallet_accounts {
+            for output in self
+                .output_db
+                .fetch_unspent_account_outputs(&account.name)
+                .map_err(OutputManagerError::from)?
+            {
+                output_accounts.insert(output.commitment.to_vec(), account.name.clone());
+            }
+        }
+        let outputs = self
+            .output_db
+            .fetch_outputs_by_query(OutputBackendQuery {
+                status: vec![OutputStatus::Unspent, OutputStatus::UnspentMinedUnconfirmed],
+                ..Default::default()
+            })
+            .map_err(OutputManagerError::from)?
+            .into_iter()
+            .map(|o| BackupOutput {
+                account: output_accounts.remove(&o.commitment.to_vec()),
+                output: o.wallet_output,
+                source: o.source,
+                spending_priority: o.spending_priority,
+                received_in_tx_id: o.received_in_tx_id,
+                label: o.label,
+                frozen: o.frozen,
+            })
+            .collect::<Vec<_>>();
+
+        let known_one_sided_payment_scripts = self
+            .output_db
+            .get_all_known_one_sided_payment_scripts()
+            .map_err(OutputManagerError::from)?;
+        let multisig_accounts = self
+            .output_db
+            .get_multisig_accounts()
+            .map_err(OutputManagerError::from)?;
+        let mut multisig_outputs = Vec::new();
+        for account in &multisig_accounts {
+            for mut output in self
+                .output_db
+                .get_multisig_outputs(&account.name)
+                .map_err(OutputManagerError::from)?
+            {
+                if output.spent {
+                    continue;
+                }
+                // Spend sessions are not backed up, so a restored output is free to be spent again
+                output.spent_in_tx_id = None;
+                multisig_outputs.push(output);
+            }
+        }
+
+        let key_ids = outputs
+            .iter()
+            .flat_map(|o| [&o.output.spending_key_id, &o.output.script_key_id])
+            .chain(known_one_sided_payment_scripts.iter().map(|s| &s.script_key_id))
+            .chain(
+                multisig_accounts
+                    .iter()
+                    .flat_map(|a| [&a.script_key_id, &a.view_key_id]),
+            )
+            .filter_map(imported_key_id)
+            .collect::<Vec<_>>();
+        let mut imported_keys = Vec::new();
+        for (i, key_id) in key_ids.iter().enumerate() {
+            if !key_ids[..i].contains(key_id) {
+                imported_keys.push(self.key_manager_service.get_private_key(key_id).await?);
+            }
+        }
+
+        let backup = WalletBackup {
+            network: self.network.as_network(),
+            created_at: Utc::now(),
+            scanned_block,
+            transactions: self.transaction_service.get_backup_data().await?,
+            contacts,
+            imported_keys,
+            known_one_sided_payment_scripts,
+            wallet_accounts,
+            multisig_accounts,
+            multisig_outputs,
+            outputs,
+        };
+        backup.encrypt(passphrase)
+    }
+
+    /// Import a backup created with [`Wallet::create_backup`]. Data already in the wallet is kept. This should be
+    /// called before the UTXO scanner is started, which will then continue scanning from the block the backup was
+    /// made at.
+    ///
+    /// The whole backup is checked before anything is written, apart from the imported keys that are needed to
+    /// check the outputs. The output manager and transaction service data are each written in a single database
+    /// transaction, and since data the wallet already has is skipped, a restore that failed part way can be rerun.
+    #[allow(clippy::too_many_lines)]
+    pub async fn restore_backup(
+        &mut self,
+        backup: &[u8],
+        passphrase: &SafePassword,
+    ) -> Result<RestoredWalletBackup, WalletError> {
+        let backup = WalletBackup::decrypt(backup, passphrase)?;
+        if backup.network != self.network.as_network() {
+            return Err(WalletError::BackupError(format!(
+                "Backup is for network {} but this wallet is on {}",
+                backup.network,
+                self.network.as_network()
+            )));
+        }
+
+        let account_names = self
+            .output_db
+            .get_wallet_accounts()
+            .map_err(OutputManagerError::from)?
+            .into_iter()
+            .map(|a| a.name)
+            .chain(backup.wallet_accounts.iter().map(|a| a.name.clone()))
+            .collect::<HashSet<_>>();
+        if let Some(account) = backup
+            .outputs
+            .iter()
+            .filter_map(|o| o.account.as_ref())
+            .find(|account| !account_names.contains(*account))
+        {
+            return Err(WalletError::BackupError(format!(
+                "Backup output belongs to unknown account '{}'",
+                account
+            )));
+        }
+        let multisig_account_names = self
+            .output_db
+            .get_multisig_accounts()
+            .map_err(OutputManagerError::from)?
+            .into_iter()
+            .map(|a| a.name)
+            .chain(backup.multisig_accounts.iter().map(|a| a.name.clone()))
+            .collect::<HashSet<_>>();
+        if let Some(output) = backup
+            .multisig_outputs
+            .iter()
+            .find(|o| !multisig_account_names.contains(&o.account_name))
+        {
+            return Err(WalletError::BackupError(format!(
+                "Backup multisig output belongs to unknown account '{}'",
+                output.account_name
+            )));
+        }
+
+        // Importing a key the wallet already has is a no-op
+        for key in backup.imported_keys {
+            self.key_manager_service.import_key(key).await?;
+        }
+
+        let mut outputs = Vec::with_capacity(backup.outputs.len());
+        for output in backup.outputs {
+            let mut db_output = DbWalletOutput::from_wallet_output(
+                output.output,
+                &self.key_manager_service,
+                Some(output.spending_priority),
+                output.source,
+                output.received_in_tx_id,
+                None,
+            )
+            .await
+            .map_err(OutputManagerError::from)?;
+            db_output.label = output.label;
+            db_output.frozen = output.frozen;
+            outputs.push((db_output, output.account));
+        }
+
+        let mut restored = RestoredWalletBackup {
+            outputs: self
+                .output_db
+                .import_backup(OutputManagerBackup {
+                    outputs,
+                    known_one_sided_payment_scripts: backup.known_one_sided_payment_scripts,
+                    wallet_accounts: backup.wallet_accounts,
+                    multisig_accounts: backup.multisig_accounts,
+                    multisig_outputs: backup.multisig_outputs,
+                })
+                .map_err(OutputManagerError::from)?,
+            transactions: self.transaction_service.import_backup_data(backup.transactions).await?,
+            ..Default::default()
+        };
+
+        for contact in backup.contacts {
+            self.contacts_service
+                .upsert_contact(Contact::new(
+                    contact.alias,
+                    contact.address,
+                    None,
+                    None,
+                    contact.favourite,
+                ))
+                .await?;
+            restored.contacts += 1;
+        }
+
+        if let Some(scanned_block) = backup.scanned_block {
+            let last_scanned_height = self.db.get_scanned_blocks()?.iter().map(|b| b.height).max();
+            if last_scanned_height.map_or(true, |h| h < scanned_block.height) {
+                self.db.save_scanned_block(ScannedBlock {
+                    header_hash: scanned_block.header_hash,
+                    height: scanned_block.height,
+                    num_outputs: None,
+                    amount: None,
+                    timestamp: Utc::now().naive_utc(),
+                })?;
+            }
+            restored.scanned_height = Some(scanned_block.height);
+        }
+
+        // The restored outputs may have been spent since the backup was made
+        self.output_manager_service.validate_txos().await?;
+        info!(
+            target: LOG_TARGET,
+            "Restored {} transactions, {} contacts and {} outputs from a wallet backup made at {}",
+            restored.transactions,
+            restored.contacts,
+            restored.outputs,
+            backup.created_at
+        );
+
+        Ok(restored)
+    }
+}
+
+/// The imported key that a key id is, or is derived from, if any
+fn imported_key_id(key_id: &TariKeyId) -> Option<TariKeyId> {
+    match key_id {
+        TariKeyId::Imported { .. } => Some(key_id.clone()),
+        TariKeyId::Derived { key } => TariKeyId::from_str(&key.to_string())
+            .ok()
+            .and_then(|key_id| imported_key_id(&key_id)),
+        _ => None,
+    }
 }
 
 pub fn read_or_create_master_seed<T: WalletBackend + 'static>(
@@ -22,14 +22,17 @@
 
 use std::{mem::size_of, panic, path::Path, sync::Arc, time::Duration};
 
+use blake2::Blake2b;
 use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
+use chrono::{Duration as ChronoDuration, Utc};
+use digest::consts::U32;
 use rand::{rngs::OsRng, RngCore};
 use support::utils::make_non_recoverable_input;
 use tari_common::configuration::{MultiaddrList, StringList};
 use tari_common_types::{
     chain_metadata::ChainMetadata,
     tari_address::TariAddress,
-    transaction::TransactionStatus,
+    transaction::{ImportStatus, TransactionStatus},
     types::{FixedHash, PrivateKey, PublicKey},
 };
 use tari_comms::{
@@ -50,16 +53,17 @@ use tari_core::{
     consensus::ConsensusManager,
     covenants::Covenant,
     transactions::{
+        key_manager::{create_memory_db_key_manager, SecretTransactionKeyManagerInterface},
         tari_amount::{uT, MicroMinotari},
         test_helpers::{create_wallet_output_with_data, TestParams},
-        transaction_components::OutputFeatures,
+        transaction_components::{encrypted_data::PaymentId, OutputFeatures},
         CryptoFactories,
     },
 };
 use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
 use tari_key_manager::{
     cipher_seed::CipherSeed,
-    key_manager_service::storage::sqlite_db::KeyManagerSqliteDatabase,
+    key_manager_service::{storage::sqlite_db::KeyManagerSqliteDatabase, KeyManagerInterface},
     mnemonic::Mnemonic,
     SeedWords,
 };
@@ -74,14 +78,18 @@ use tari_p2p::{
     TcpTransportConfig,
     TransportConfig,
 };
-use tari_script::{inputs, script};
+use tari_script::{inputs, script, ExecutionStack};
 use tari_shutdown::{Shutdown, ShutdownSignal};
 use tari_test_utils::{collect_recv, comms_and_services::get_next_memory_address, random};
 use tari_utilities::{Hidden, SafePassword};
 use minotari_wallet::{
     error::{WalletError, WalletStorageError},
     output_manager_service::{
-        storage::{database::OutputManagerDatabase, sqlite_db::OutputManagerSqliteDatabase},
+        storage::{
+            database::OutputManagerDatabase,
+            models::{KnownOneSidedPaymentScript, WalletAccount},
+            sqlite_db::OutputManagerSqliteDatabase,
+        },
         UtxoSelectionCriteria,
     },
     storage::{
@@ -93,7 +101,7 @@ use minotari_wallet::{
     transaction_service::{
         config::TransactionServiceConfig,
         handle::TransactionEvent,
-        storage::sqlite_db::TransactionServiceSqliteDatabase,
+        storage::{models::PaymentSchedule, sqlite_db::TransactionServiceSqliteDatabase},
     },
     wallet::read_or_create_master_seed,
     Wallet,
@@ -837,6 +845,200 @@ async fn test_recovery_birthday() {
     assert_eq!(birthday, db_birthday);
 }
 
+#[tokio::test]
+#[allow(clippy::too_many_lines)]
+async fn test_restore_backup_into_a_fresh_wallet() {
+    let alice_db_tempdir = tempdir().unwrap();
+    let restored_db_tempdir = tempdir().unwrap();
+    let consensus_manager = ConsensusManager::builder(Network::LocalNet).build();
+    let factories = CryptoFactories::default();
+    let shutdown = Shutdown::new();
+    let seed = CipherSeed::new();
+
+    let mut alice_wallet = create_wallet(
+        alice_db_tempdir.path(),
+        "alice_db",
+        consensus_manager.clone(),
+        factories.clone(),
+        shutdown.to_signal(),
+        "alice passphrase".to_string().into(),
+        Some(seed.clone()),
+    )
+    .await
+    .unwrap();
+
+    let sender_address = TariAddress::new_dual_address_with_default_features(
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        Network::LocalNet,
+    );
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let input = make_input(
+        &mut OsRng,
+        MicroMinotari::from(5000),
+        &OutputFeatures::default(),
+        &key_manager,
+    )
+    .await;
+    let tx_id = alice_wallet
+        .transaction_service
+        .import_utxo_with_status(
+            input.value,
+            sender_address.clone(),
+            ImportStatus::Imported,
+            None,
+            None,
+            None,
+            input.to_transaction_output(&key_manager).await.unwrap(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    alice_wallet
+        .transaction_service
+        .set_transaction_label(tx_id, Some("salary".to_string()))
+        .await
+        .unwrap();
+    alice_wallet
+        .contacts_service
+        .upsert_contact(Contact::new(
+            "sender".to_string(),
+            sender_address.clone(),
+            None,
+            None,
+            true,
+        ))
+        .await
+        .unwrap();
+    alice_wallet
+        .transaction_service
+        .add_payment_schedule(PaymentSchedule::new(
+            sender_address.clone(),
+            MicroMinotari::from(1000),
+            MicroMinotari::from(5),
+            Duration::from_secs(24 * 60 * 60),
+            Utc::now() + ChronoDuration::days(1),
+        ))
+        .await
+        .unwrap();
+    alice_wallet
+        .output_db
+        .add_wallet_account(WalletAccount {
+            name: "savings".to_string(),
+            account_index: 1,
+            created_at: Utc::now(),
+        })
+        .unwrap();
+
+    // A one-sided payment script with a key that cannot be derived from the seed words
+    let script_key = PrivateKey::random(&mut OsRng);
+    let script_key_id = alice_wallet
+        .key_manager_service
+        .import_key(script_key.clone())
+        .await
+        .unwrap();
+    let script = script!(Nop).unwrap();
+    alice_wallet
+        .output_db
+        .add_known_script(KnownOneSidedPaymentScript {
+            script_hash: script.as_hash::<Blake2b<U32>>().unwrap().to_vec(),
+            script_key_id: script_key_id.clone(),
+            script,
+            input: ExecutionStack::default(),
+            script_lock_height: 0,
+        })
+        .unwrap();
+
+    let backup_passphrase = SafePassword::from("backup passphrase");
+    let backup = alice_wallet.create_backup(&backup_passphrase).await.unwrap();
+
+    let mut restored_wallet = create_wallet(
+        restored_db_tempdir.path(),
+        "restored_db",
+        consensus_manager,
+        factories,
+        shutdown.to_signal(),
+        "restored passphrase".to_string().into(),
+        Some(seed),
+    )
+    .await
+    .unwrap();
+    let result = restored_wallet
+        .restore_backup(&backup, &SafePassword::from("wrong passphrase"))
+        .await;
+    assert!(matches!(result, Err(WalletError::BackupError(_))));
+
+    let restored = restored_wallet
+        .restore_backup(&backup, &backup_passphrase)
+        .await
+        .unwrap();
+    assert_eq!(restored.transactions, 1);
+    assert_eq!(restored.contacts, 1);
+
+    let completed_tx = restored_wallet
+        .transaction_service
+        .get_completed_transactions()
+        .await
+        .unwrap()
+        .remove(&tx_id)
+        .expect("Tx should be restored");
+    assert_eq!(completed_tx.status, TransactionStatus::Imported);
+    assert_eq!(
+        restored_wallet
+            .transaction_service
+            .get_transaction_labels()
+            .await
+            .unwrap()
+            .get(&tx_id),
+        Some(&"salary".to_string())
+    );
+    let contacts = restored_wallet.contacts_service.get_contacts().await.unwrap();
+    assert_eq!(contacts.len(), 1);
+    assert_eq!(contacts[0].alias, "sender");
+    assert!(contacts[0].favourite);
+    let schedules = restored_wallet
+        .transaction_service
+        .get_payment_schedules()
+        .await
+        .unwrap();
+    assert_eq!(schedules.len(), 1);
+    assert_eq!(schedules[0].destination, sender_address);
+    let accounts = restored_wallet.output_db.get_wallet_accounts().unwrap();
+    assert_eq!(accounts.len(), 1);
+    assert_eq!(accounts[0].name, "savings");
+    let scripts = restored_wallet
+        .output_db
+        .get_all_known_one_sided_payment_scripts()
+        .unwrap();
+    assert_eq!(scripts.len(), 1);
+    assert_eq!(scripts[0].script_key_id, script_key_id);
+    assert_eq!(
+        restored_wallet
+            .key_manager_service
+            .get_private_key(&script_key_id)
+            .await
+            .unwrap(),
+        script_key
+    );
+
+    // Restoring the same backup again adds nothing
+    let restored = restored_wallet
+        .restore_backup(&backup, &backup_passphrase)
+        .await
+        .unwrap();
+    assert_eq!(restored.transactions, 0);
+    assert_eq!(restored.outputs, 0);
+    assert_eq!(
+        restored_wallet
+            .transaction_service
+            .get_payment_schedules()
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+}
+
 #[tokio::test]
 #[allow(clippy::too_many_lines)]
 async fn test_contacts_service_liveness() {
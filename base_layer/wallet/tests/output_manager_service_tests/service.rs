@@ -32,7 +32,7 @@ use minotari_wallet::{
         service::OutputManagerService,
         storage::{
             database::{OutputManagerBackend, OutputManagerDatabase},
-            models::SpendingPriority,
+            models::{MultisigAccount, SpendingPriority},
             sqlite_db::OutputManagerSqliteDatabase,
             OutputStatus,
         },
@@ -47,7 +47,7 @@ use tari_common_types::{
     key_branches::TransactionKeyManagerBranch,
     tari_address::TariAddress,
     transaction::TxId,
-    types::{ComAndPubSignature, FixedHash, PublicKey},
+    types::{ComAndPubSignature, FixedHash, PrivateKey, PublicKey},
 };
 use tari_comms::{
     peer_manager::{NodeIdentity, PeerFeatures},
@@ -65,13 +65,20 @@ use tari_core::{
         key_manager::{create_memory_db_key_manager, MemoryDbKeyManager, TransactionKeyManagerInterface},
         tari_amount::{uT, MicroMinotari, T},
         test_helpers::{create_wallet_output_with_data, TestParams},
-        transaction_components::{encrypted_data::PaymentId, OutputFeatures, TransactionOutput, WalletOutput},
+        transaction_components::{
+            encrypted_data::PaymentId,
+            OutputFeatures,
+            TransactionOutput,
+            WalletOutput,
+            WalletOutputBuilder,
+        },
         transaction_protocol::{sender::TransactionSenderMessage, TransactionMetadata},
         weight::TransactionWeight,
         CryptoFactories,
         SenderTransactionProtocol,
     },
 };
+use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
 use tari_key_manager::key_manager_service::{KeyId, KeyManagerInterface};
 use tari_script::{inputs, script, TariScript};
 use tari_service_framework::reply_channel;
@@ -112,6 +119,14 @@ struct TestOmsService {
 async fn setup_output_manager_service<T: OutputManagerBackend + 'static>(
     backend: T,
     with_connection: bool,
+) -> TestOmsService {
+    setup_output_manager_service_with_config(backend, with_connection, OutputManagerServiceConfig::default()).await
+}
+
+async fn setup_output_manager_service_with_config<T: OutputManagerBackend + 'static>(
+    backend: T,
+    with_connection: bool,
+    config: OutputManagerServiceConfig,
 ) -> TestOmsService {
     let shutdown = Shutdown::new();
     let factories = CryptoFactories::default();
@@ -159,7 +174,7 @@ async fn setup_output_manager_service<T: OutputManagerBackend + 'static>(
     let key_manager = create_memory_db_key_manager().unwrap();
 
     let output_manager_service = OutputManagerService::new(
-        OutputManagerServiceConfig { ..Default::default() },
+        config,
         oms_request_receiver,
         OutputManagerDatabase::new(backend),
         oms_event_publisher.clone(),
@@ -170,6 +185,7 @@ async fn setup_output_manager_service<T: OutputManagerBackend + 'static>(
         Network::LocalNet,
         wallet_connectivity_mock.clone(),
         key_manager.clone(),
+        key_manager.get_wallet_type().await,
     )
     .await
     .unwrap();
@@ -234,6 +250,7 @@ pub async fn setup_oms_with_bn_state<T: OutputManagerBackend + 'static>(
         Network::LocalNet,
         connectivity,
         key_manager.clone(),
+        key_manager.get_wallet_type().await,
     )
     .await
     .unwrap();
@@ -801,6 +818,44 @@ async fn send_not_enough_funds() {
     }
 }
 
+#[tokio::test]
+async fn send_to_unspendable_script() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let mut oms = setup_output_manager_service(backend.clone(), true).await;
+
+    let uo = make_input(
+        &mut OsRng.clone(),
+        MicroMinotari::from(10_000),
+        &OutputFeatures::default(),
+        &oms.key_manager_handle,
+    )
+    .await;
+    oms.output_manager_handle.add_output(uo.clone(), None).await.unwrap();
+    backend
+        .mark_outputs_as_unspent(vec![(uo.hash(&oms.key_manager_handle).await.unwrap(), true)])
+        .unwrap();
+
+    // Every path of the script ends in `Return`, so the funds could never be spent
+    let result = oms
+        .output_manager_handle
+        .prepare_transaction_to_send(
+            TxId::new_random(),
+            MicroMinotari::from(1_000),
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            MicroMinotari::from(4),
+            TransactionMetadata::default(),
+            script!(PushOne IfThen Return Else Return EndIf).unwrap(),
+            Covenant::default(),
+            MicroMinotari::zero(),
+            TariAddress::default(),
+            PaymentId::Empty,
+        )
+        .await;
+    assert!(matches!(result, Err(OutputManagerError::UnspendableScript(_))));
+}
+
 #[tokio::test]
 async fn send_no_change() {
     let (connection, _tempdir) = get_temp_sqlite_database_connection();
@@ -1326,6 +1381,100 @@ async fn it_handles_large_coin_splits() {
     assert_eq!(coin_split_tx.body.outputs().len(), split_count + 1);
 }
 
+#[tokio::test]
+async fn it_consolidates_small_outputs_when_fees_are_low() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection.clone());
+    let config = OutputManagerServiceConfig {
+        auto_consolidation_max_output_value: 100_000,
+        auto_consolidation_min_outputs: 3,
+        ..Default::default()
+    };
+    let mut oms = setup_output_manager_service_with_config(backend.clone(), true, config).await;
+    let mut event_stream = oms.output_manager_handle.get_event_stream();
+
+    let mut commitments = Vec::new();
+    for value in [10_000 * uT, 20_000 * uT, 30_000 * uT, 40_000 * uT, 50_000 * uT, 2 * T] {
+        let uo = make_input(&mut OsRng, value, &OutputFeatures::default(), &oms.key_manager_handle).await;
+        oms.output_manager_handle.add_output(uo.clone(), None).await.unwrap();
+        backend
+            .mark_outputs_as_unspent(vec![(uo.hash(&oms.key_manager_handle).await.unwrap(), true)])
+            .unwrap();
+        commitments.push(uo.commitment(&oms.key_manager_handle).await.unwrap());
+    }
+    // Frozen and labelled outputs are never consolidated, and the last output is above the value threshold
+    oms.output_manager_handle
+        .set_outputs_frozen(vec![commitments[3].clone()], true)
+        .await
+        .unwrap();
+    oms.output_manager_handle
+        .set_outputs_label(vec![commitments[4].clone()], Some("deposits".to_string()))
+        .await
+        .unwrap();
+
+    let (tx_id, transaction, amount) = oms
+        .output_manager_handle
+        .create_consolidation_transaction()
+        .await
+        .unwrap()
+        .expect("No consolidation transaction created");
+    assert_eq!(transaction.body.inputs().len(), 3);
+    assert_eq!(transaction.body.outputs().len(), 1);
+    assert_eq!(amount, 60_000 * uT);
+    let spent_commitments = transaction
+        .body
+        .inputs()
+        .iter()
+        .map(|input| input.commitment().unwrap().clone())
+        .collect::<Vec<_>>();
+    for commitment in &commitments[..3] {
+        assert!(spent_commitments.contains(commitment));
+    }
+
+    // The event only describes the consolidation, the transaction is returned to the caller
+    let event = tokio::time::timeout(Duration::from_secs(30), async {
+        loop {
+            let event = event_stream.recv().await.unwrap();
+            if let OutputManagerEvent::OutputsConsolidated { .. } = &*event {
+                return event;
+            }
+        }
+    })
+    .await
+    .expect("No consolidation event received");
+    match &*event {
+        OutputManagerEvent::OutputsConsolidated {
+            tx_id: event_tx_id,
+            num_inputs,
+            amount: event_amount,
+            fee,
+        } => {
+            assert_eq!(*event_tx_id, tx_id);
+            assert_eq!(*num_inputs, 3);
+            assert_eq!(*event_amount, amount);
+            assert_eq!(transaction.body.get_total_fee().unwrap(), *fee);
+        },
+        _ => unreachable!(),
+    }
+
+    // The consolidated outputs stay encumbered, so too few small outputs are left for another consolidation
+    assert!(oms
+        .output_manager_handle
+        .create_consolidation_transaction()
+        .await
+        .unwrap()
+        .is_none());
+
+    // Cancelling the transaction releases the outputs for the next consolidation
+    oms.output_manager_handle.cancel_transaction(tx_id).await.unwrap();
+    assert!(oms
+        .output_manager_handle
+        .create_consolidation_transaction()
+        .await
+        .unwrap()
+        .is_some());
+}
+
 #[tokio::test]
 #[allow(clippy::too_many_lines)]
 async fn test_txo_validation() {
@@ -2321,3 +2470,154 @@ async fn recovered_output_key_not_in_keychain() {
         "It should not reach an error condition or return an output"
     );
 }
+
+#[tokio::test]
+async fn multisig_account_parties_share_the_view_key() {
+    let mut parties = Vec::new();
+    let mut public_keys = Vec::new();
+    let mut tempdirs = Vec::new();
+    for _ in 0..3 {
+        let (connection, tempdir) = get_temp_sqlite_database_connection();
+        let mut oms = setup_output_manager_service(OutputManagerSqliteDatabase::new(connection), true).await;
+        let key = oms
+            .output_manager_handle
+            .get_multisig_account_key("treasury".to_string())
+            .await
+            .unwrap();
+        // The key only depends on the account name
+        assert_eq!(
+            oms.output_manager_handle
+                .get_multisig_account_key("treasury".to_string())
+                .await
+                .unwrap(),
+            key
+        );
+        public_keys.push(key);
+        parties.push(oms);
+        tempdirs.push(tempdir);
+    }
+
+    // The other parties need a view key share from the coordinator
+    let err = parties[1]
+        .output_manager_handle
+        .create_multisig_account("treasury".to_string(), 2, public_keys.clone(), None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutputManagerError::InvalidArgument(_)));
+
+    let (coordinator_account, view_key_shares) = parties[0]
+        .output_manager_handle
+        .create_multisig_account("treasury".to_string(), 2, public_keys.clone(), None)
+        .await
+        .unwrap();
+    assert_eq!(view_key_shares.len(), 2);
+    assert_ne!(coordinator_account.view_public_key, public_keys[0]);
+
+    for (party, (public_key, share)) in parties[1..].iter_mut().zip(view_key_shares.iter()) {
+        assert!(public_keys.contains(public_key));
+        let (account, shares) = party
+            .output_manager_handle
+            .create_multisig_account("treasury".to_string(), 2, public_keys.clone(), Some(share.clone()))
+            .await
+            .unwrap();
+        assert!(shares.is_empty());
+        assert_eq!(account.view_public_key, coordinator_account.view_public_key);
+    }
+
+    // A share only unmasks the view key for the party it was made for
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let mut other = setup_output_manager_service(OutputManagerSqliteDatabase::new(connection), true).await;
+    let mut other_keys = public_keys.clone();
+    other_keys[2] = other
+        .output_manager_handle
+        .get_multisig_account_key("treasury".to_string())
+        .await
+        .unwrap();
+    let (account, _) = other
+        .output_manager_handle
+        .create_multisig_account(
+            "treasury".to_string(),
+            2,
+            other_keys,
+            Some(view_key_shares[1].1.clone()),
+        )
+        .await
+        .unwrap();
+    assert_ne!(account.view_public_key, coordinator_account.view_public_key);
+
+    // A wallet cannot create an account that none of its keys belong to
+    let err = other
+        .output_manager_handle
+        .create_multisig_account("other".to_string(), 2, public_keys, None)
+        .await
+        .unwrap_err();
+    assert!(matches!(err, OutputManagerError::InvalidArgument(_)));
+}
+
+#[tokio::test]
+async fn multisig_account_outputs_are_found_with_the_view_key() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let mut oms = setup_output_manager_service(OutputManagerSqliteDatabase::new(connection), true).await;
+    let public_keys = vec![
+        oms.output_manager_handle
+            .get_multisig_account_key("treasury".to_string())
+            .await
+            .unwrap(),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+    ];
+    let (account, _) = oms
+        .output_manager_handle
+        .create_multisig_account("treasury".to_string(), 2, public_keys.clone(), None)
+        .await
+        .unwrap();
+
+    // The sender encrypts the output to the account's view key with its sender offset key
+    let sender_key_manager = create_memory_db_key_manager().unwrap();
+    let value = MicroMinotari::from(5_000);
+    let mut outputs = Vec::new();
+    for view_public_key in [
+        account.view_public_key.clone(),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+    ] {
+        let commitment_mask_key = sender_key_manager.get_random_key().await.unwrap();
+        let commitment = sender_key_manager
+            .get_commitment(&commitment_mask_key.key_id, &value.into())
+            .await
+            .unwrap();
+        let sender_offset_key = sender_key_manager.get_random_key().await.unwrap();
+        let shared_secret = sender_key_manager
+            .get_diffie_hellman_shared_secret(&sender_offset_key.key_id, &view_public_key)
+            .await
+            .unwrap();
+        let encryption_key = sender_key_manager
+            .import_key(MultisigAccount::output_encryption_key(&shared_secret).unwrap())
+            .await
+            .unwrap();
+        let output = WalletOutputBuilder::new(value, commitment_mask_key.key_id)
+            .with_script(MultisigAccount::output_script(2, &public_keys, &commitment).unwrap())
+            .encrypt_data_for_recovery(&sender_key_manager, Some(&encryption_key), PaymentId::Empty)
+            .await
+            .unwrap()
+            .with_input_data(Default::default())
+            .with_sender_offset_public_key(sender_offset_key.pub_key)
+            .with_script_key(KeyId::Zero)
+            .sign_as_sender_and_receiver(&sender_key_manager, &sender_offset_key.key_id)
+            .await
+            .unwrap()
+            .try_build(&sender_key_manager)
+            .await
+            .unwrap();
+        outputs.push(output.to_transaction_output(&sender_key_manager).await.unwrap());
+    }
+
+    // The output encrypted to another view key is skipped
+    let found = oms
+        .output_manager_handle
+        .scan_outputs_for_multisig_accounts(outputs.clone(), 10)
+        .await
+        .unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].hash, outputs[0].hash());
+    assert_eq!(found[0].value, value);
+    assert_eq!(found[0].account_name, "treasury");
+}
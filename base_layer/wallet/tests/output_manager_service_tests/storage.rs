@@ -22,28 +22,31 @@
 
 use std::convert::TryFrom;
 
+use chrono::Utc;
 use minotari_wallet::output_manager_service::{
     error::OutputManagerStorageError,
     service::Balance,
     storage::{
         database::{OutputManagerBackend, OutputManagerDatabase},
-        models::DbWalletOutput,
+        models::{DbWalletOutput, MultisigAccount, MultisigOutput, WalletAccount},
         sqlite_db::{OutputManagerSqliteDatabase, ReceivedOutputInfoForBatch, SpentOutputInfoForBatch},
         OutputSource,
         OutputStatus,
     },
+    UtxoSelectionCriteria,
 };
 use rand::{rngs::OsRng, RngCore};
 use tari_common_types::{
     transaction::TxId,
-    types::{FixedHash, HashOutput, PrivateKey},
+    types::{Commitment, FixedHash, HashOutput, PrivateKey, PublicKey},
 };
 use tari_core::transactions::{
-    key_manager::create_memory_db_key_manager,
+    key_manager::{create_memory_db_key_manager, TransactionKeyManagerBranch},
     tari_amount::MicroMinotari,
     transaction_components::OutputFeatures,
 };
-use tari_crypto::keys::SecretKey;
+use tari_crypto::keys::{PublicKey as PublicKeyTrait, SecretKey};
+use tari_key_manager::key_manager_service::KeyId;
 use tari_utilities::{hex::Hex, ByteArray};
 
 use crate::support::{data::get_temp_sqlite_database_connection, utils::make_input};
@@ -762,3 +765,219 @@ pub async fn test_mark_as_unmined() {
     }
     assert_eq!(batch_invalid_count, batch_count);
 }
+
+#[test]
+pub fn test_multisig_accounts_and_outputs() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let db = OutputManagerDatabase::new(backend);
+
+    let public_keys = (0..3)
+        .map(|_| PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)))
+        .collect::<Vec<_>>();
+    let view_public_key = PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng));
+    let account = MultisigAccount {
+        name: "treasury".to_string(),
+        threshold: 2,
+        public_keys: public_keys.clone(),
+        script_key_id: KeyId::Managed {
+            branch: TransactionKeyManagerBranch::MultiSig.get_branch_key(),
+            index: 1,
+        },
+        view_key_id: KeyId::Imported {
+            key: view_public_key.clone(),
+        },
+        view_public_key,
+        created_at: Utc::now(),
+    };
+    db.add_multisig_account(account.clone()).unwrap();
+    let err = db.add_multisig_account(account.clone()).unwrap_err();
+    assert!(matches!(err, OutputManagerStorageError::DuplicateMultisigAccount(_)));
+    let fetched = db.get_multisig_account("treasury").unwrap();
+    assert_eq!(fetched.threshold, 2);
+    assert_eq!(fetched.public_keys, public_keys);
+    assert_eq!(fetched.script_key_id, account.script_key_id);
+    assert_eq!(fetched.view_key_id, account.view_key_id);
+    assert_eq!(fetched.view_public_key, account.view_public_key);
+    assert!(matches!(
+        db.get_multisig_account("other").unwrap_err(),
+        OutputManagerStorageError::ValueNotFound
+    ));
+
+    // Only scripts for this account's keys and the output's own commitment are recognised
+    let commitment = Commitment::from_public_key(&PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)));
+    let script = MultisigAccount::output_script(2, &public_keys, &commitment).unwrap();
+    assert!(account.is_output_script(&script, &commitment));
+    assert!(!account.is_output_script(&script, &Commitment::default()));
+    let script = MultisigAccount::output_script(3, &public_keys, &commitment).unwrap();
+    assert!(!account.is_output_script(&script, &commitment));
+    assert!(MultisigAccount::output_script(4, &public_keys, &commitment).is_err());
+    assert!(MultisigAccount::output_script(0, &public_keys, &commitment).is_err());
+
+    let output = MultisigOutput {
+        hash: FixedHash::from([1u8; 32]),
+        account_name: "treasury".to_string(),
+        commitment,
+        value: MicroMinotari::from(1000),
+        mined_height: 10,
+        spent_in_tx_id: None,
+        spent: false,
+    };
+    db.add_multisig_output(output.clone()).unwrap();
+    assert!(matches!(
+        db.add_multisig_output(output.clone()).unwrap_err(),
+        OutputManagerStorageError::DuplicateOutput
+    ));
+    assert!(db.get_multisig_outputs("other").unwrap().is_empty());
+
+    db.set_multisig_output_spent_in_tx(output.hash, 5u64.into()).unwrap();
+    let fetched = db.get_multisig_outputs("treasury").unwrap().pop().unwrap();
+    assert_eq!(fetched.spent_in_tx_id, Some(5u64.into()));
+    assert!(!fetched.spent);
+
+    db.mark_multisig_output_as_spent(output.hash, Some(5u64.into()))
+        .unwrap();
+    let fetched = db.get_multisig_outputs("treasury").unwrap().pop().unwrap();
+    assert!(fetched.spent);
+}
+
+#[tokio::test]
+pub async fn test_wallet_accounts() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let db = OutputManagerDatabase::new(backend);
+
+    let account = WalletAccount {
+        name: "savings".to_string(),
+        account_index: 0,
+        created_at: Utc::now(),
+    };
+    db.add_wallet_account(account.clone()).unwrap();
+    assert!(matches!(
+        db.add_wallet_account(account.clone()).unwrap_err(),
+        OutputManagerStorageError::DuplicateWalletAccount(_)
+    ));
+    assert_eq!(db.get_wallet_account("savings").unwrap().account_index, 0);
+    assert_ne!(account.view_key_id(), account.spend_key_id());
+
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let mut outputs = Vec::new();
+    for i in 0..2u64 {
+        let uo = make_input(
+            &mut OsRng,
+            MicroMinotari::from(1000 * (i + 1)),
+            &OutputFeatures::default(),
+            &key_manager,
+        )
+        .await;
+        let output = DbWalletOutput::from_wallet_output(uo, &key_manager, None, OutputSource::Standard, None, None)
+            .await
+            .unwrap();
+        db.add_unspent_output_with_tx_id(TxId::from(i + 1), output.clone())
+            .unwrap();
+        outputs.push(output);
+    }
+    db.mark_outputs_as_unspent(outputs.iter().map(|o| (o.hash, true)).collect())
+        .unwrap();
+    db.set_outputs_account(vec![outputs[1].commitment.clone()], "savings")
+        .unwrap();
+
+    // Outputs of named accounts are neither counted in nor spent from the default account
+    assert_eq!(
+        db.get_balance(None).unwrap().available_balance,
+        MicroMinotari::from(1000)
+    );
+    assert_eq!(
+        db.get_account_balance("savings", None).unwrap().available_balance,
+        MicroMinotari::from(2000)
+    );
+    let selected = db
+        .fetch_unspent_outputs_for_spending(&UtxoSelectionCriteria::default(), MicroMinotari::from(500), None)
+        .unwrap();
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].commitment, outputs[0].commitment);
+    let selected = db
+        .fetch_unspent_outputs_for_spending(
+            &UtxoSelectionCriteria::default().with_account(Some("savings".to_string())),
+            MicroMinotari::from(500),
+            None,
+        )
+        .unwrap();
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].commitment, outputs[1].commitment);
+
+    let account_outputs = db.fetch_unspent_account_outputs("savings").unwrap();
+    assert_eq!(account_outputs.len(), 1);
+    assert_eq!(db.fetch_account_tx_ids("savings").unwrap(), vec![TxId::from(2u64)]);
+}
+
+#[tokio::test]
+pub async fn test_output_labels_and_freezing() {
+    let (connection, _tempdir) = get_temp_sqlite_database_connection();
+    let backend = OutputManagerSqliteDatabase::new(connection);
+    let db = OutputManagerDatabase::new(backend);
+
+    let key_manager = create_memory_db_key_manager().unwrap();
+    let mut outputs = Vec::new();
+    for i in 0..3u64 {
+        let uo = make_input(
+            &mut OsRng,
+            MicroMinotari::from(1000 * (i + 1)),
+            &OutputFeatures::default(),
+            &key_manager,
+        )
+        .await;
+        let output = DbWalletOutput::from_wallet_output(uo, &key_manager, None, OutputSource::Standard, None, None)
+            .await
+            .unwrap();
+        db.add_unspent_output(output.clone()).unwrap();
+        outputs.push(output);
+    }
+    db.mark_outputs_as_unspent(outputs.iter().map(|o| (o.hash, true)).collect())
+        .unwrap();
+
+    db.set_outputs_label(
+        vec![outputs[0].commitment.clone(), outputs[1].commitment.clone()],
+        Some("deposits"),
+    )
+    .unwrap();
+    db.set_outputs_frozen(vec![outputs[1].commitment.clone()], true)
+        .unwrap();
+
+    let unspent = db.fetch_sorted_unspent_outputs().unwrap();
+    let labelled = unspent
+        .iter()
+        .filter(|o| o.label.as_deref() == Some("deposits"))
+        .collect::<Vec<_>>();
+    assert_eq!(labelled.len(), 2);
+    assert_eq!(unspent.iter().filter(|o| o.frozen).count(), 1);
+
+    // Frozen outputs are never selected
+    let selected = db
+        .fetch_unspent_outputs_for_spending(&UtxoSelectionCriteria::default(), MicroMinotari::from(500), None)
+        .unwrap();
+    assert_eq!(selected.len(), 2);
+    assert!(selected.iter().all(|o| o.commitment != outputs[1].commitment));
+    let selected = db
+        .fetch_unspent_outputs_for_spending(
+            &UtxoSelectionCriteria::default().with_label(Some("deposits".to_string())),
+            MicroMinotari::from(500),
+            None,
+        )
+        .unwrap();
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].commitment, outputs[0].commitment);
+
+    db.set_outputs_frozen(vec![outputs[1].commitment.clone()], false)
+        .unwrap();
+    db.set_outputs_label(vec![outputs[0].commitment.clone()], None).unwrap();
+    let selected = db
+        .fetch_unspent_outputs_for_spending(
+            &UtxoSelectionCriteria::default().with_label(Some("deposits".to_string())),
+            MicroMinotari::from(500),
+            None,
+        )
+        .unwrap();
+    assert_eq!(selected.len(), 1);
+    assert_eq!(selected[0].commitment, outputs[1].commitment);
+}
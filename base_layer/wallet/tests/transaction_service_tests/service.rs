@@ -49,12 +49,14 @@ use minotari_wallet::{
     },
     output_manager_service::{
         config::OutputManagerServiceConfig,
+        error::OutputManagerError,
         handle::{OutputManagerEvent, OutputManagerHandle},
         service::OutputManagerService,
         storage::{
             database::{OutputManagerBackend, OutputManagerDatabase},
             models::KnownOneSidedPaymentScript,
             sqlite_db::{OutputManagerSqliteDatabase, ReceivedOutputInfoForBatch},
+            OutputStatus,
         },
         OutputManagerServiceInitializer,
         UtxoSelectionCriteria,
@@ -72,11 +74,18 @@ use minotari_wallet::{
     },
     transaction_service::{
         config::TransactionServiceConfig,
+        error::TransactionServiceError,
         handle::{TransactionEvent, TransactionSendStatus, TransactionServiceHandle},
         service::TransactionService,
         storage::{
             database::{DbKeyValuePair, TransactionBackend, TransactionDatabase, WriteOperation},
-            models::{CompletedTransaction, InboundTransaction, OutboundTransaction, WalletTransaction},
+            models::{
+                BatchRecipient,
+                CompletedTransaction,
+                InboundTransaction,
+                OutboundTransaction,
+                WalletTransaction,
+            },
             sqlite_db::TransactionServiceSqliteDatabase,
         },
         TransactionServiceInitializer,
@@ -87,7 +96,7 @@ use rand::{rngs::OsRng, RngCore};
 use tari_common_sqlite::connection::{DbConnection, DbConnectionUrl};
 use tari_common_types::{
     chain_metadata::ChainMetadata,
-    tari_address::TariAddress,
+    tari_address::{TariAddress, TariAddressFeatures},
     transaction::{ImportStatus, TransactionDirection, TransactionStatus, TxId},
     types::{FixedHash, PrivateKey, PublicKey, Signature},
     wallet_types::{ProvidedKeysWallet, WalletType},
@@ -245,6 +254,7 @@ async fn setup_transaction_service<P: AsRef<Path>>(
             oms_backend.clone(),
             factories.clone(),
             Network::LocalNet.into(),
+            wallet_type.clone(),
         ))
         .add_initializer(TransactionKeyManagerInitializer::<KeyManagerSqliteDatabase<_>>::new(
             kms_backend,
@@ -319,11 +329,22 @@ pub struct TransactionServiceNoCommsInterface {
 
 /// This utility function creates a Transaction service without using the Service Framework Stack and exposes all the
 /// streams for testing purposes.
-#[allow(clippy::type_complexity)]
 async fn setup_transaction_service_no_comms(
     factories: CryptoFactories,
     db_connection: WalletDbConnection,
     config: Option<TransactionServiceConfig>,
+) -> TransactionServiceNoCommsInterface {
+    setup_transaction_service_no_comms_with_wallet_type(factories, db_connection, config, None).await
+}
+
+/// As [`setup_transaction_service_no_comms`], with the services running as `wallet_type` instead of the wallet type of
+/// the key manager
+#[allow(clippy::type_complexity)]
+async fn setup_transaction_service_no_comms_with_wallet_type(
+    factories: CryptoFactories,
+    db_connection: WalletDbConnection,
+    config: Option<TransactionServiceConfig>,
+    wallet_type: Option<Arc<WalletType>>,
 ) -> TransactionServiceNoCommsInterface {
     let (oms_request_sender, oms_request_receiver) = reply_channel::unbounded();
 
@@ -387,6 +408,10 @@ async fn setup_transaction_service_no_comms(
     let ts_service_db = TransactionServiceSqliteDatabase::new(db_connection.clone(), cipher.clone());
     let ts_db = TransactionDatabase::new(ts_service_db.clone());
     let key_manager = create_memory_db_key_manager().unwrap();
+    let wallet_type = match wallet_type {
+        Some(wallet_type) => wallet_type,
+        None => key_manager.get_wallet_type().await,
+    };
     let oms_db = OutputManagerDatabase::new(OutputManagerSqliteDatabase::new(db_connection));
     let output_manager_service = OutputManagerService::new(
         OutputManagerServiceConfig::default(),
@@ -400,6 +425,7 @@ async fn setup_transaction_service_no_comms(
         Network::LocalNet,
         wallet_connectivity_service_mock.clone(),
         key_manager.clone(),
+        wallet_type.clone(),
     )
     .await
     .unwrap();
@@ -441,7 +467,7 @@ async fn setup_transaction_service_no_comms(
         factories,
         shutdown.to_signal(),
         base_node_service_handle,
-        key_manager.get_wallet_type().await,
+        wallet_type.clone(),
     )
     .await
     .unwrap();
@@ -3015,6 +3041,7 @@ async fn test_power_mode_updates() {
         mined_in_block: None,
         mined_timestamp: None,
         payment_id: PaymentId::open_from_str("Yo!"),
+        replaced_by_tx_id: None,
     };
 
     let source_address = TariAddress::new_dual_address_with_default_features(
@@ -3046,6 +3073,7 @@ async fn test_power_mode_updates() {
         mined_in_block: None,
         mined_timestamp: None,
         payment_id: PaymentId::open_from_str("Yo!"),
+        replaced_by_tx_id: None,
     };
 
     tx_backend
@@ -5740,6 +5768,7 @@ async fn broadcast_all_completed_transactions_on_startup() {
         mined_in_block: None,
         mined_timestamp: None,
         payment_id: PaymentId::open_from_str("Yo!"),
+        replaced_by_tx_id: None,
     };
 
     let completed_tx2 = CompletedTransaction {
@@ -6202,3 +6231,469 @@ async fn test_get_fee_per_gram_per_block_basic() {
     assert_eq!(estimates.stats, stats.into_iter().map(Into::into).collect::<Vec<_>>());
     assert_eq!(estimates.stats.len(), 1)
 }
+
+#[tokio::test]
+async fn test_bump_fee_of_transaction_to_self() {
+    let factories = CryptoFactories::default();
+    let connection = make_wallet_database_memory_connection();
+    let mut alice_ts_interface = setup_transaction_service_no_comms(factories, connection, None).await;
+    let key_manager = alice_ts_interface.key_manager_handle.clone();
+
+    let input = make_input(&mut OsRng, 25_000.into(), &OutputFeatures::default(), &key_manager).await;
+    alice_ts_interface
+        .output_manager_service_handle
+        .add_output(input.clone(), None)
+        .await
+        .unwrap();
+    alice_ts_interface
+        .oms_db
+        .mark_outputs_as_unspent(vec![(input.hash(&key_manager).await.unwrap(), true)])
+        .unwrap();
+    let own_address = TariAddress::new_dual_address(
+        key_manager.get_view_key().await.unwrap().pub_key,
+        key_manager.get_comms_key().await.unwrap().pub_key,
+        Network::LocalNet,
+        TariAddressFeatures::create_interactive_only(),
+    );
+    let tx_id = alice_ts_interface
+        .transaction_service_handle
+        .send_transaction(
+            own_address,
+            10_000.into(),
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20.into(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let original = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(tx_id)
+        .await
+        .unwrap();
+    let input_commitment = input.commitment(&key_manager).await.unwrap();
+
+    // A frozen input cannot be selected, so the replacement fails after the inputs were released
+    alice_ts_interface
+        .output_manager_service_handle
+        .set_outputs_frozen(vec![input_commitment.clone()], true)
+        .await
+        .unwrap();
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .bump_fee(tx_id, 40.into())
+        .await
+        .is_err());
+    let outputs = alice_ts_interface.oms_db.fetch_outputs_by_tx_id(tx_id).unwrap();
+    let spent = outputs
+        .iter()
+        .find(|o| o.commitment == input_commitment)
+        .expect("The input should still belong to the transaction");
+    assert_eq!(spent.status, OutputStatus::EncumberedToBeSpent);
+    assert_eq!(spent.spent_in_tx_id, Some(tx_id));
+    assert!(outputs
+        .iter()
+        .any(|o| o.received_in_tx_id == Some(tx_id) && o.status == OutputStatus::EncumberedToBeReceived));
+    let completed = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(tx_id)
+        .await
+        .unwrap();
+    assert!(completed.replaced_by_tx_id.is_none());
+
+    alice_ts_interface
+        .output_manager_service_handle
+        .set_outputs_frozen(vec![input_commitment.clone()], false)
+        .await
+        .unwrap();
+    let new_tx_id = alice_ts_interface
+        .transaction_service_handle
+        .bump_fee(tx_id, 40.into())
+        .await
+        .unwrap();
+    let completed = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(tx_id)
+        .await
+        .unwrap();
+    assert_eq!(completed.replaced_by_tx_id, Some(new_tx_id));
+    let replacement = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(new_tx_id)
+        .await
+        .unwrap();
+    assert_eq!(replacement.amount, original.amount);
+    assert!(replacement.fee > original.fee);
+    let spent = alice_ts_interface.oms_db.fetch_by_commitment(input_commitment).unwrap();
+    assert_eq!(spent.spent_in_tx_id, Some(new_tx_id));
+
+    // A transaction can only be replaced once
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .bump_fee(tx_id, 80.into())
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_bump_fee_of_transfer_from_wallet_account() {
+    let factories = CryptoFactories::default();
+    let connection = make_wallet_database_memory_connection();
+    let mut alice_ts_interface = setup_transaction_service_no_comms(factories, connection, None).await;
+    let key_manager = alice_ts_interface.key_manager_handle.clone();
+
+    alice_ts_interface
+        .output_manager_service_handle
+        .create_wallet_account("savings".to_string())
+        .await
+        .unwrap();
+    let input = make_input(&mut OsRng, 25_000.into(), &OutputFeatures::default(), &key_manager).await;
+    alice_ts_interface
+        .output_manager_service_handle
+        .add_output(input.clone(), None)
+        .await
+        .unwrap();
+    alice_ts_interface
+        .oms_db
+        .mark_outputs_as_unspent(vec![(input.hash(&key_manager).await.unwrap(), true)])
+        .unwrap();
+    let input_commitment = input.commitment(&key_manager).await.unwrap();
+    alice_ts_interface
+        .oms_db
+        .set_outputs_account(vec![input_commitment.clone()], "savings")
+        .unwrap();
+
+    // A transfer into the default account is paid to the wallet's own one-sided address
+    let tx_id = alice_ts_interface
+        .transaction_service_handle
+        .transfer_between_wallet_accounts(
+            Some("savings".to_string()),
+            None,
+            10_000.into(),
+            20.into(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let original = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(tx_id)
+        .await
+        .unwrap();
+    assert_eq!(original.source_address, original.destination_address);
+
+    let new_tx_id = alice_ts_interface
+        .transaction_service_handle
+        .bump_fee(tx_id, 40.into())
+        .await
+        .unwrap();
+    let replacement = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(new_tx_id)
+        .await
+        .unwrap();
+    assert_eq!(replacement.direction, TransactionDirection::Outbound);
+    assert_eq!(replacement.amount, original.amount);
+    assert!(replacement.fee > original.fee);
+    let spent = alice_ts_interface
+        .oms_db
+        .fetch_by_commitment(input_commitment.clone())
+        .unwrap();
+    assert_eq!(spent.spent_in_tx_id, Some(new_tx_id));
+
+    // The change of the replacement stays in the account the transfer was paid from
+    let change = alice_ts_interface
+        .oms_db
+        .fetch_outputs_by_tx_id(new_tx_id)
+        .unwrap()
+        .into_iter()
+        .find(|o| o.received_in_tx_id == Some(new_tx_id))
+        .expect("The replacement should have change");
+    assert_eq!(
+        alice_ts_interface
+            .oms_db
+            .fetch_output_account(&change.commitment)
+            .unwrap(),
+        Some("savings".to_string())
+    );
+}
+
+#[tokio::test]
+async fn test_prepare_sign_and_finalise_unsigned_transaction() {
+    let factories = CryptoFactories::default();
+    let connection = make_wallet_database_memory_connection();
+    let mut alice_ts_interface = setup_transaction_service_no_comms(factories, connection, None).await;
+    let key_manager = alice_ts_interface.key_manager_handle.clone();
+
+    let input = make_input(&mut OsRng, 25_000.into(), &OutputFeatures::default(), &key_manager).await;
+    alice_ts_interface
+        .output_manager_service_handle
+        .add_output(input.clone(), None)
+        .await
+        .unwrap();
+    alice_ts_interface
+        .oms_db
+        .mark_outputs_as_unspent(vec![(input.hash(&key_manager).await.unwrap(), true)])
+        .unwrap();
+    let input_commitment = input.commitment(&key_manager).await.unwrap();
+    let destination = TariAddress::new_dual_address_with_default_features(
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        Network::LocalNet,
+    );
+
+    let unsigned_transaction = alice_ts_interface
+        .transaction_service_handle
+        .prepare_unsigned_transaction(
+            destination.clone(),
+            10_000.into(),
+            UtxoSelectionCriteria::default(),
+            20.into(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let tx_id = unsigned_transaction.tx_id;
+    assert_eq!(unsigned_transaction.inputs.len(), 1);
+    let spent = alice_ts_interface
+        .oms_db
+        .fetch_by_commitment(input_commitment.clone())
+        .unwrap();
+    assert_eq!(spent.status, OutputStatus::EncumberedToBeSpent);
+    assert_eq!(spent.spent_in_tx_id, Some(tx_id));
+
+    // Abandoning the prepared transaction releases its inputs
+    alice_ts_interface
+        .transaction_service_handle
+        .abandon_unsigned_transaction(tx_id)
+        .await
+        .unwrap();
+    let released = alice_ts_interface
+        .oms_db
+        .fetch_by_commitment(input_commitment.clone())
+        .unwrap();
+    assert_eq!(released.status, OutputStatus::Unspent);
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .abandon_unsigned_transaction(tx_id)
+        .await
+        .is_err());
+
+    let unsigned_transaction = alice_ts_interface
+        .transaction_service_handle
+        .prepare_unsigned_transaction(
+            destination.clone(),
+            10_000.into(),
+            UtxoSelectionCriteria::default(),
+            20.into(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let signed_transaction = alice_ts_interface
+        .transaction_service_handle
+        .sign_unsigned_transaction(unsigned_transaction.clone())
+        .await
+        .unwrap();
+    assert_eq!(signed_transaction.tx_id, unsigned_transaction.tx_id);
+
+    // The signed transaction must pay what was prepared
+    let mut tampered = signed_transaction.clone();
+    tampered.amount = 9_000.into();
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .finalise_signed_transaction(tampered)
+        .await
+        .is_err());
+    let mut tampered = signed_transaction.clone();
+    tampered.change_output = None;
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .finalise_signed_transaction(tampered)
+        .await
+        .is_err());
+
+    let tx_id = alice_ts_interface
+        .transaction_service_handle
+        .finalise_signed_transaction(signed_transaction.clone())
+        .await
+        .unwrap();
+    let completed = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(tx_id)
+        .await
+        .unwrap();
+    assert_eq!(completed.amount, unsigned_transaction.amount);
+    assert_eq!(completed.fee, unsigned_transaction.fee);
+    assert_eq!(completed.destination_address, destination);
+    let outputs = alice_ts_interface.oms_db.fetch_outputs_by_tx_id(tx_id).unwrap();
+    assert!(outputs.iter().any(|o| o.received_in_tx_id == Some(tx_id) &&
+        o.status == OutputStatus::EncumberedToBeReceived &&
+        o.wallet_output.value == unsigned_transaction.change));
+
+    assert!(alice_ts_interface
+        .transaction_service_handle
+        .finalise_signed_transaction(signed_transaction)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_watch_only_wallet_rejects_spending() {
+    let factories = CryptoFactories::default();
+    let connection = make_wallet_database_memory_connection();
+    let wallet_type = Arc::new(WalletType::ProvidedKeys(ProvidedKeysWallet::watch_only(
+        PrivateKey::random(&mut OsRng),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+    )));
+    let mut alice_ts_interface =
+        setup_transaction_service_no_comms_with_wallet_type(factories, connection, None, Some(wallet_type)).await;
+    let key_manager = alice_ts_interface.key_manager_handle.clone();
+
+    let input = make_input(&mut OsRng, 25_000.into(), &OutputFeatures::default(), &key_manager).await;
+    alice_ts_interface
+        .output_manager_service_handle
+        .add_output(input.clone(), None)
+        .await
+        .unwrap();
+    alice_ts_interface
+        .oms_db
+        .mark_outputs_as_unspent(vec![(input.hash(&key_manager).await.unwrap(), true)])
+        .unwrap();
+    let input_commitment = input.commitment(&key_manager).await.unwrap();
+    let destination = TariAddress::new_dual_address_with_default_features(
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+        Network::LocalNet,
+    );
+
+    let result = alice_ts_interface
+        .transaction_service_handle
+        .send_one_sided_to_stealth_address_transaction(
+            destination.clone(),
+            10_000.into(),
+            UtxoSelectionCriteria::default(),
+            OutputFeatures::default(),
+            20.into(),
+            PaymentId::Empty,
+        )
+        .await;
+    assert!(matches!(result, Err(TransactionServiceError::WatchOnlyWallet)));
+    let result = alice_ts_interface
+        .output_manager_service_handle
+        .create_coin_split(vec![input_commitment.clone()], 5_000.into(), 2, 20.into())
+        .await;
+    assert!(matches!(result, Err(OutputManagerError::WatchOnlyWallet)));
+    let unspent = alice_ts_interface.oms_db.fetch_by_commitment(input_commitment).unwrap();
+    assert_eq!(unspent.status, OutputStatus::Unspent);
+
+    // A watch-only wallet can still prepare a transaction to be signed offline, but it cannot sign one
+    let unsigned_transaction = alice_ts_interface
+        .transaction_service_handle
+        .prepare_unsigned_transaction(
+            destination,
+            10_000.into(),
+            UtxoSelectionCriteria::default(),
+            20.into(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let result = alice_ts_interface
+        .transaction_service_handle
+        .sign_unsigned_transaction(unsigned_transaction)
+        .await;
+    assert!(matches!(result, Err(TransactionServiceError::WatchOnlyWallet)));
+}
+
+#[tokio::test]
+async fn test_send_batch() {
+    let factories = CryptoFactories::default();
+    let connection = make_wallet_database_memory_connection();
+    let mut alice_ts_interface = setup_transaction_service_no_comms(factories, connection, None).await;
+    let key_manager = alice_ts_interface.key_manager_handle.clone();
+
+    let input = make_input(&mut OsRng, 25_000.into(), &OutputFeatures::default(), &key_manager).await;
+    alice_ts_interface
+        .output_manager_service_handle
+        .add_output(input.clone(), None)
+        .await
+        .unwrap();
+    alice_ts_interface
+        .oms_db
+        .mark_outputs_as_unspent(vec![(input.hash(&key_manager).await.unwrap(), true)])
+        .unwrap();
+    let recipients = (0..2u64)
+        .map(|i| BatchRecipient {
+            address: TariAddress::new_dual_address_with_default_features(
+                PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+                PublicKey::from_secret_key(&PrivateKey::random(&mut OsRng)),
+                Network::LocalNet,
+            ),
+            amount: (3_000 + i * 1_000).into(),
+            payment_id: PaymentId::Empty,
+        })
+        .collect::<Vec<_>>();
+
+    // The total amount must not overflow
+    let mut overflowing = recipients.clone();
+    overflowing[0].amount = u64::MAX.into();
+    let result = alice_ts_interface
+        .transaction_service_handle
+        .send_batch(
+            overflowing,
+            UtxoSelectionCriteria::default(),
+            20.into(),
+            PaymentId::Empty,
+        )
+        .await;
+    assert!(matches!(result, Err(TransactionServiceError::BatchPaymentError(_))));
+
+    let tx_id = alice_ts_interface
+        .transaction_service_handle
+        .send_batch(
+            recipients.clone(),
+            UtxoSelectionCriteria::default(),
+            20.into(),
+            PaymentId::Empty,
+        )
+        .await
+        .unwrap();
+    let completed = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(tx_id)
+        .await
+        .unwrap();
+    assert_eq!(completed.amount, MicroMinotari::from(7_000));
+    assert_eq!(completed.destination_address, recipients[0].address);
+    // One output per recipient and the change
+    assert_eq!(completed.transaction.body.outputs().len(), 3);
+    let batch_recipients = alice_ts_interface
+        .transaction_service_handle
+        .get_batch_recipients(tx_id)
+        .await
+        .unwrap();
+    assert_eq!(batch_recipients, recipients);
+
+    // A fee bumped batch still pays every recipient
+    let new_tx_id = alice_ts_interface
+        .transaction_service_handle
+        .bump_fee(tx_id, 40.into())
+        .await
+        .unwrap();
+    let replacement = alice_ts_interface
+        .transaction_service_handle
+        .get_completed_transaction(new_tx_id)
+        .await
+        .unwrap();
+    assert_eq!(replacement.amount, completed.amount);
+    assert!(replacement.fee > completed.fee);
+    assert_eq!(replacement.transaction.body.outputs().len(), 3);
+    let batch_recipients = alice_ts_interface
+        .transaction_service_handle
+        .get_batch_recipients(new_tx_id)
+        .await
+        .unwrap();
+    assert_eq!(batch_recipients, recipients);
+}
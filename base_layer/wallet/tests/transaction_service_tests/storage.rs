@@ -20,7 +20,7 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::mem::size_of;
+use std::{mem::size_of, time::Duration};
 
 use chacha20poly1305::{Key, KeyInit, XChaCha20Poly1305};
 use chrono::{DateTime, Utc};
@@ -30,11 +30,17 @@ use minotari_wallet::{
     transaction_service::storage::{
         database::{DbKeyValuePair, TransactionBackend, TransactionDatabase, WriteOperation},
         models::{
+            AdaptorSwap,
+            AdaptorSwapRole,
+            AdaptorSwapStatus,
             CompletedTransaction,
             InboundTransaction,
             OutboundTransaction,
+            PaymentSchedule,
+            PaymentScheduleStatus,
             TxCancellationReason,
             WalletTransaction,
+            MAX_PAYMENT_SCHEDULE_FAILURES,
         },
         sqlite_db::TransactionServiceSqliteDatabase,
     },
@@ -62,7 +68,7 @@ use tari_core::{
             TransactionOutputVersion,
             WalletOutput,
         },
-        transaction_protocol::sender::TransactionSenderMessage,
+        transaction_protocol::{adaptor_signature::AdaptorSignature, sender::TransactionSenderMessage},
         ReceiverTransactionProtocol,
         SenderTransactionProtocol,
     },
@@ -346,6 +352,7 @@ pub async fn test_db_backend<T: TransactionBackend + 'static>(backend: T) {
             mined_in_block: None,
             mined_timestamp: None,
             payment_id: PaymentId::open_from_str(messages[i]),
+            replaced_by_tx_id: None,
         });
         db.complete_outbound_transaction(outbound_txs[i].tx_id, completed_txs[i].clone())
             .unwrap();
@@ -380,6 +387,17 @@ pub async fn test_db_backend<T: TransactionBackend + 'static>(backend: T) {
     assert!(retrieved_completed_tx.last_send_timestamp.is_some());
     assert!(retrieved_completed_tx.confirmations.is_none());
 
+    assert!(db.get_replaced_transactions().unwrap().is_empty());
+    db.set_completed_transaction_replaced_by(completed_txs[0].tx_id, completed_txs[1].tx_id)
+        .unwrap();
+    let replaced_txs = db.get_replaced_transactions().unwrap();
+    assert_eq!(replaced_txs.len(), 1);
+    assert_eq!(replaced_txs[0].tx_id, completed_txs[0].tx_id);
+    assert_eq!(replaced_txs[0].replaced_by_tx_id, Some(completed_txs[1].tx_id));
+    assert!(db
+        .set_completed_transaction_replaced_by(100u64.into(), completed_txs[1].tx_id)
+        .is_err());
+
     assert!(db.fetch_last_mined_transaction().unwrap().is_none());
 
     db.set_transaction_mined_height(
@@ -681,3 +699,241 @@ async fn import_tx_and_read_it_from_db() {
     assert_eq!(db_tx.first().unwrap().tx_id, TxId::from(3u64));
     assert_eq!(db_tx.first().unwrap().mined_height, Some(7));
 }
+
+#[tokio::test]
+async fn adaptor_swap_state_is_persisted() {
+    let db_name = format!("{}.sqlite3", random::string(8));
+    let db_tempdir = tempdir().unwrap();
+    let db_folder = db_tempdir.path().to_str().unwrap().to_string();
+    let db_path = format!("{}/{}", db_folder, db_name);
+    let connection = run_migration_and_create_sqlite_connection(db_path, 16).unwrap();
+
+    let mut key = [0u8; size_of::<Key>()];
+    OsRng.fill_bytes(&mut key);
+    let key_ga = Key::from_slice(&key);
+    let cipher = XChaCha20Poly1305::new(key_ga);
+    let db = TransactionDatabase::new(TransactionServiceSqliteDatabase::new(connection, cipher));
+
+    let swap_private_key = PrivateKey::random(&mut OsRng);
+    let adaptor_secret = PrivateKey::random(&mut OsRng);
+    let adaptor_point = PublicKey::from_secret_key(&adaptor_secret);
+    let adaptor_signature = AdaptorSignature::sign(
+        &swap_private_key,
+        PrivateKey::random(&mut OsRng),
+        &adaptor_point,
+        b"swap",
+    )
+    .unwrap();
+    let swap = AdaptorSwap {
+        output_hash: FixedHash::from([7u8; 32]),
+        role: AdaptorSwapRole::Initiator,
+        status: AdaptorSwapStatus::Locked,
+        amount: MicroMinotari::from(10_000),
+        swap_public_key: PublicKey::from_secret_key(&swap_private_key),
+        adaptor_point: adaptor_point.clone(),
+        adaptor_signature: adaptor_signature.clone(),
+        refund_height: 100,
+        lock_tx_id: Some(TxId::from(1u64)),
+        spend_tx_id: None,
+        signature: None,
+        adaptor_secret: None,
+        timestamp: DateTime::from_timestamp(0, 0).unwrap(),
+    };
+    db.insert_adaptor_swap(swap.clone()).unwrap();
+    assert!(db.insert_adaptor_swap(swap.clone()).is_err());
+    assert_eq!(db.get_adaptor_swap(&swap.output_hash).unwrap(), swap);
+    assert!(db.get_adaptor_swap(&FixedHash::zero()).is_err());
+
+    // A participant transition is rejected for the initiator
+    let mut claimed = swap.clone();
+    claimed.status = AdaptorSwapStatus::Claimed;
+    assert!(db.update_adaptor_swap(claimed).is_err());
+
+    let mut revealed = swap.clone();
+    revealed.status = AdaptorSwapStatus::SecretRevealed;
+    revealed.signature = Some(adaptor_signature.complete(&adaptor_secret));
+    revealed.adaptor_secret = Some(adaptor_secret);
+    db.update_adaptor_swap(revealed.clone()).unwrap();
+    assert_eq!(db.get_adaptor_swap(&swap.output_hash).unwrap(), revealed);
+
+    // The secret is revealed, so the swap can no longer be refunded
+    let mut refunded = revealed.clone();
+    refunded.status = AdaptorSwapStatus::Refunded;
+    assert!(db.update_adaptor_swap(refunded).is_err());
+    assert_eq!(db.get_adaptor_swaps().unwrap(), vec![revealed]);
+}
+
+#[tokio::test]
+async fn transaction_labels_are_persisted() {
+    let db_name = format!("{}.sqlite3", random::string(8));
+    let db_tempdir = tempdir().unwrap();
+    let db_folder = db_tempdir.path().to_str().unwrap().to_string();
+    let db_path = format!("{}/{}", db_folder, db_name);
+    let connection = run_migration_and_create_sqlite_connection(db_path, 16).unwrap();
+
+    let mut key = [0u8; size_of::<Key>()];
+    OsRng.fill_bytes(&mut key);
+    let key_ga = Key::from_slice(&key);
+    let cipher = XChaCha20Poly1305::new(key_ga);
+    let db = TransactionDatabase::new(TransactionServiceSqliteDatabase::new(connection, cipher));
+
+    db.set_transaction_label(TxId::from(1u64), Some("customer deposit"))
+        .unwrap();
+    db.set_transaction_label(TxId::from(2u64), Some("operating")).unwrap();
+    db.set_transaction_label(TxId::from(2u64), Some("payroll")).unwrap();
+    let labels = db.get_transaction_labels().unwrap();
+    assert_eq!(labels.len(), 2);
+    assert_eq!(labels.get(&TxId::from(2u64)).unwrap(), "payroll");
+
+    db.set_transaction_label(TxId::from(1u64), None).unwrap();
+    let labels = db.get_transaction_labels().unwrap();
+    assert_eq!(labels.len(), 1);
+    assert!(!labels.contains_key(&TxId::from(1u64)));
+}
+
+#[tokio::test]
+async fn payment_schedules_are_persisted() {
+    let db_name = format!("{}.sqlite3", random::string(8));
+    let db_tempdir = tempdir().unwrap();
+    let db_folder = db_tempdir.path().to_str().unwrap().to_string();
+    let db_path = format!("{}/{}", db_folder, db_name);
+    let connection = run_migration_and_create_sqlite_connection(db_path, 16).unwrap();
+
+    let mut key = [0u8; size_of::<Key>()];
+    OsRng.fill_bytes(&mut key);
+    let key_ga = Key::from_slice(&key);
+    let cipher = XChaCha20Poly1305::new(key_ga);
+    let db = TransactionDatabase::new(TransactionServiceSqliteDatabase::new(connection, cipher));
+
+    let start_time = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+    let interval = Duration::from_secs(24 * 60 * 60);
+    let daily = PaymentSchedule::new(
+        TariAddress::default(),
+        MicroMinotari::from(10_000),
+        MicroMinotari::from(5),
+        interval,
+        start_time,
+    )
+    .with_payment_id(PaymentId::open_from_str("payroll"))
+    .with_max_fee(Some(MicroMinotari::from(1_000)))
+    .with_end_time(Some(start_time + chrono::Duration::days(1)));
+    let once = PaymentSchedule::new(
+        TariAddress::default(),
+        MicroMinotari::from(500),
+        MicroMinotari::from(5),
+        Duration::ZERO,
+        start_time,
+    );
+    let daily_id = db.insert_payment_schedule(daily).unwrap();
+    let once_id = db.insert_payment_schedule(once).unwrap();
+    assert_ne!(daily_id, once_id);
+
+    let mut schedule = db.get_payment_schedule(daily_id).unwrap();
+    assert!(schedule.is_due(start_time));
+    assert_eq!(schedule.payment_id, PaymentId::open_from_str("payroll"));
+    assert_eq!(schedule.max_fee, Some(MicroMinotari::from(1_000)));
+
+    schedule.record_failure("Not enough funds".to_string(), start_time);
+    db.update_payment_schedule(schedule.clone()).unwrap();
+    assert_eq!(db.get_payment_schedule(daily_id).unwrap(), schedule);
+
+    // The second payment is the last one before the end time
+    schedule.record_payment(TxId::from(1u64));
+    assert_eq!(schedule.status, PaymentScheduleStatus::Active);
+    assert!(!schedule.is_due(start_time));
+    db.update_payment_schedule(schedule.clone()).unwrap();
+    assert_eq!(db.get_payment_schedule(daily_id).unwrap(), schedule);
+    schedule.record_payment(TxId::from(2u64));
+    assert_eq!(schedule.status, PaymentScheduleStatus::Completed);
+    assert_eq!(schedule.payments_made, 2);
+    db.update_payment_schedule(schedule).unwrap();
+
+    let mut schedule = db.get_payment_schedule(once_id).unwrap();
+    schedule.record_payment(TxId::from(3u64));
+    assert_eq!(schedule.status, PaymentScheduleStatus::Completed);
+
+    schedule.id = 1000;
+    assert!(db.update_payment_schedule(schedule).is_err());
+    assert!(db.get_payment_schedule(1000).is_err());
+    assert_eq!(db.get_payment_schedules().unwrap().len(), 2);
+}
+
+#[test]
+fn payment_schedules_skip_missed_payments() {
+    let start_time = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+    let hourly = PaymentSchedule::new(
+        TariAddress::default(),
+        MicroMinotari::from(10_000),
+        MicroMinotari::from(5),
+        Duration::from_secs(60 * 60),
+        start_time,
+    );
+
+    // Nothing was missed while the payment is on time
+    let mut schedule = hourly.clone();
+    assert_eq!(schedule.skip_missed_payments(start_time), 0);
+    assert_eq!(schedule.next_payment, start_time);
+
+    // By default only the latest of the payments that fell due is made
+    let now = start_time + chrono::Duration::minutes(5 * 60 + 30);
+    assert_eq!(schedule.skip_missed_payments(now), 5);
+    assert_eq!(schedule.next_payment, start_time + chrono::Duration::hours(5));
+    schedule.record_payment(TxId::from(1u64));
+    assert!(!schedule.is_due(now));
+
+    // Up to the maximum number of missed payments are made up, one at a time
+    let mut schedule = hourly.clone().with_max_missed_payments(2);
+    assert_eq!(schedule.skip_missed_payments(now), 3);
+    assert_eq!(schedule.next_payment, start_time + chrono::Duration::hours(3));
+    for tx_id in 1..=3u64 {
+        assert!(schedule.is_due(now));
+        assert_eq!(schedule.skip_missed_payments(now), 0);
+        schedule.record_payment(TxId::from(tx_id));
+    }
+    assert!(!schedule.is_due(now));
+    assert_eq!(schedule.payments_made, 3);
+
+    // The last payment before the end time is still made
+    let mut schedule = hourly.with_end_time(Some(start_time + chrono::Duration::hours(2)));
+    assert_eq!(schedule.skip_missed_payments(now), 2);
+    assert_eq!(schedule.next_payment, start_time + chrono::Duration::hours(2));
+    schedule.record_payment(TxId::from(1u64));
+    assert_eq!(schedule.status, PaymentScheduleStatus::Completed);
+}
+
+#[test]
+fn payment_schedules_back_off_and_fail_after_repeated_failures() {
+    let start_time = DateTime::from_timestamp(Utc::now().timestamp(), 0).unwrap();
+    let mut schedule = PaymentSchedule::new(
+        TariAddress::default(),
+        MicroMinotari::from(10_000),
+        MicroMinotari::from(5),
+        Duration::from_secs(24 * 60 * 60),
+        start_time,
+    );
+
+    // The retry delay doubles with every failure
+    schedule.record_failure("Not enough funds".to_string(), start_time);
+    assert_eq!(schedule.failed_attempts, 1);
+    assert_eq!(schedule.retry_at, Some(start_time + chrono::Duration::minutes(1)));
+    assert!(!schedule.is_due(start_time));
+    assert!(schedule.is_due(start_time + chrono::Duration::minutes(1)));
+    schedule.record_failure("Not enough funds".to_string(), start_time);
+    assert_eq!(schedule.retry_at, Some(start_time + chrono::Duration::minutes(2)));
+
+    // A payment resets the failures
+    schedule.record_payment(TxId::from(1u64));
+    assert_eq!(schedule.failed_attempts, 0);
+    assert_eq!(schedule.retry_at, None);
+    assert_eq!(schedule.last_error, None);
+
+    for _ in 0..MAX_PAYMENT_SCHEDULE_FAILURES - 1 {
+        schedule.record_failure("Not enough funds".to_string(), start_time);
+        assert_eq!(schedule.status, PaymentScheduleStatus::Active);
+    }
+    assert!(schedule.retry_at.unwrap() <= start_time + chrono::Duration::hours(6));
+    schedule.record_failure("Not enough funds".to_string(), start_time);
+    assert_eq!(schedule.status, PaymentScheduleStatus::Failed);
+    assert_eq!(schedule.failed_attempts, MAX_PAYMENT_SCHEDULE_FAILURES);
+    assert!(!schedule.is_due(start_time + chrono::Duration::days(2)));
+}
@@ -356,6 +356,10 @@ where TBackend: TransactionBackend + 'static
                                 OutputManagerEvent::TxoValidationCommunicationFailure(request_key) => {
                                     self.output_validation_complete_event(request_key,  3);
                                 },
+                                OutputManagerEvent::OutputsConsolidated { .. } => {
+                                    self.trigger_balance_refresh().await;
+                                },
+                                OutputManagerEvent::OutputConsolidationFailed(_) => {},
                             }
                         },
                         Err(_e) => error!(target: LOG_TARGET, "Error reading from Output Manager Service event broadcast channel"),
@@ -198,6 +198,10 @@ impl From<WalletError> for LibWalletError {
                 code: 113,
                 message: format!("{:?}", w),
             },
+            WalletError::OutputManagerError(OutputManagerError::WatchOnlyWallet) => Self {
+                code: 116,
+                message: format!("{:?}", w),
+            },
             WalletError::OutputManagerError(_) => Self {
                 code: 114,
                 message: format!("{:?}", w),
@@ -219,6 +223,13 @@ impl From<WalletError> for LibWalletError {
                 code: 204,
                 message: format!("{:?}", w),
             },
+            WalletError::TransactionServiceError(TransactionServiceError::WatchOnlyWallet) |
+            WalletError::TransactionServiceError(TransactionServiceError::OutputManagerError(
+                OutputManagerError::WatchOnlyWallet,
+            )) => Self {
+                code: 213,
+                message: format!("{:?}", w),
+            },
             WalletError::TransactionServiceError(TransactionServiceError::OutputManagerError(
                 OutputManagerError::BuildError(ref s),
             )) if s == &"Fee is greater than amount".to_string() => Self {
@@ -332,6 +343,10 @@ impl From<WalletError> for LibWalletError {
                 code: 434,
                 message: format!("{:?}", w),
             },
+            WalletError::BackupError(_) => Self {
+                code: 435,
+                message: format!("{:?}", w),
+            },
             // these are general catch errors to try and reduce 999 when we get it with zero additional logging
             WalletError::SetLoggerError(_) => Self {
                 code: 994,
@@ -52,6 +52,7 @@ use std::{
     convert::{TryFrom, TryInto},
     ffi::{CStr, CString},
     fmt::{Display, Formatter},
+    fs,
     mem::ManuallyDrop,
     num::NonZeroU16,
     path::PathBuf,
@@ -100,6 +101,7 @@ use minotari_wallet::{
     transaction_service::{
         config::TransactionServiceConfig,
         error::TransactionServiceError,
+        history_export::TransactionHistoryFormat,
         storage::{
             database::TransactionDatabase,
             models::{CompletedTransaction, InboundTransaction, OutboundTransaction},
@@ -4294,6 +4296,7 @@ pub unsafe extern "C" fn completed_transaction_get_confirmations(
 /// |   5 | TimeLocked          |
 /// |   6 | InvalidTransaction  |
 /// |   7 | AbandonedCoinbase   |
+/// |   8 | Replaced            |
 /// # Safety
 /// None
 #[no_mangle]
@@ -7897,6 +7900,67 @@ pub unsafe extern "C" fn wallet_get_cancelled_transactions(
     Box::into_raw(Box::new(TariCompletedTransactions(completed)))
 }
 
+/// Export the completed and cancelled transactions of a TariWallet, oldest first, for bookkeeping. Each transaction
+/// has its timestamps, mined height, confirmations, direction, status, amount and fee in µT, counterparty address,
+/// payment ID, label and the running balance of the wallet after it.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer
+/// `format` - The format of the export, 0 for CSV with a header row and 1 for a JSON array
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `*mut c_char` - Returns the exported history, note that it returns ptr::null_mut() if wallet is null, the format
+/// is invalid or an error is encountered
+///
+/// # Safety
+/// The ```string_destroy``` method must be called when finished with a string coming from rust to prevent a memory leak
+#[no_mangle]
+pub unsafe extern "C" fn wallet_export_transaction_history(
+    wallet: *mut TariWallet,
+    format: c_uint,
+    error_out: *mut c_int,
+) -> *mut c_char {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return ptr::null_mut();
+    }
+    let format = match format {
+        0 => TransactionHistoryFormat::Csv,
+        1 => TransactionHistoryFormat::Json,
+        _ => {
+            error = LibWalletError::from(InterfaceError::InvalidArgument("format".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+
+    let history = match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.export_transaction_history(format))
+    {
+        Ok(history) => history,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return ptr::null_mut();
+        },
+    };
+    match CString::new(history) {
+        Ok(history) => history.into_raw(),
+        Err(e) => {
+            error = LibWalletError::from(InterfaceError::InternalError(e.to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            ptr::null_mut()
+        },
+    }
+}
+
 /// Get the TariCompletedTransaction from a TariWallet by its' TransactionId
 ///
 /// ## Arguments
@@ -8818,6 +8882,168 @@ pub unsafe extern "C" fn wallet_is_recovery_in_progress(wallet: *mut TariWallet,
     }
 }
 
+/// Writes an encrypted backup of the wallet data that cannot be recovered from the seed words, i.e. the transaction
+/// history, contacts, labels, known one-sided payment scripts, accounts and unspent outputs, to a file.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `backup_file_path` - The path of the backup file to write
+/// `passphrase` - The passphrase to encrypt the backup with
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns if the backup was written successfully
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_create_backup(
+    wallet: *mut TariWallet,
+    backup_file_path: *const c_char,
+    passphrase: *const c_char,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    if backup_file_path.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("backup_file_path".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    if passphrase.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("passphrase".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    let backup_file_path = match CStr::from_ptr(backup_file_path).to_str() {
+        Ok(v) => PathBuf::from(v),
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("backup_file_path".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+    let passphrase = match CStr::from_ptr(passphrase).to_str() {
+        Ok(v) => SafePassword::from(v.to_owned()),
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("passphrase".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+
+    let backup = match (*wallet).runtime.block_on((*wallet).wallet.create_backup(&passphrase)) {
+        Ok(backup) => backup,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+    match fs::write(backup_file_path, backup) {
+        Ok(()) => true,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::WalletStorageError(WalletStorageError::FileError(
+                e.to_string(),
+            )))
+            .code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
+/// Imports a backup written by `wallet_create_backup` into the wallet. This should be called on a newly created or
+/// recovered wallet before `wallet_start_recovery`, which will then only scan the chain from the height the backup was
+/// made at.
+///
+/// ## Arguments
+/// `wallet` - The TariWallet pointer.
+/// `backup_file_path` - The path of the backup file to read
+/// `passphrase` - The passphrase the backup was encrypted with
+/// `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+/// as an out parameter.
+///
+/// ## Returns
+/// `bool` - Returns if the backup was restored successfully
+///
+/// # Safety
+/// None
+#[no_mangle]
+pub unsafe extern "C" fn wallet_restore_backup(
+    wallet: *mut TariWallet,
+    backup_file_path: *const c_char,
+    passphrase: *const c_char,
+    error_out: *mut c_int,
+) -> bool {
+    let mut error = 0;
+    ptr::swap(error_out, &mut error as *mut c_int);
+
+    if wallet.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("wallet".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    if backup_file_path.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("backup_file_path".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+    if passphrase.is_null() {
+        error = LibWalletError::from(InterfaceError::NullError("passphrase".to_string())).code;
+        ptr::swap(error_out, &mut error as *mut c_int);
+        return false;
+    }
+
+    let backup_file_path = match CStr::from_ptr(backup_file_path).to_str() {
+        Ok(v) => PathBuf::from(v),
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("backup_file_path".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+    let passphrase = match CStr::from_ptr(passphrase).to_str() {
+        Ok(v) => SafePassword::from(v.to_owned()),
+        _ => {
+            error = LibWalletError::from(InterfaceError::PointerError("passphrase".to_string())).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+
+    let backup = match fs::read(backup_file_path) {
+        Ok(backup) => backup,
+        Err(e) => {
+            error = LibWalletError::from(WalletError::WalletStorageError(WalletStorageError::FileError(
+                e.to_string(),
+            )))
+            .code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            return false;
+        },
+    };
+    match (*wallet)
+        .runtime
+        .block_on((*wallet).wallet.restore_backup(&backup, &passphrase))
+    {
+        Ok(_) => true,
+        Err(e) => {
+            error = LibWalletError::from(e).code;
+            ptr::swap(error_out, &mut error as *mut c_int);
+            false
+        },
+    }
+}
+
 /// Starts the Wallet recovery process.
 ///
 /// ## Arguments
@@ -2269,6 +2269,7 @@ unsigned long long completed_transaction_get_confirmations(TariCompletedTransact
  * |   5 | TimeLocked          |
  * |   6 | InvalidTransaction  |
  * |   7 | AbandonedCoinbase   |
+ * |   8 | Replaced            |
  * # Safety
  * None
  */
@@ -3624,6 +3625,28 @@ struct TariPendingOutboundTransactions *wallet_get_pending_outbound_transactions
 struct TariCompletedTransactions *wallet_get_cancelled_transactions(struct TariWallet *wallet,
                                                                     int *error_out);
 
+/**
+ * Export the completed and cancelled transactions of a TariWallet, oldest first, for bookkeeping. Each transaction
+ * has its timestamps, mined height, confirmations, direction, status, amount and fee in µT, counterparty address,
+ * payment ID, label and the running balance of the wallet after it.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer
+ * `format` - The format of the export, 0 for CSV with a header row and 1 for a JSON array
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `*mut c_char` - Returns the exported history, note that it returns ptr::null_mut() if wallet is null, the format
+ * is invalid or an error is encountered
+ *
+ * # Safety
+ * The ```string_destroy``` method must be called when finished with a string coming from rust to prevent a memory leak
+ */
+char *wallet_export_transaction_history(struct TariWallet *wallet,
+                                        unsigned int format,
+                                        int *error_out);
+
 /**
  * Get the TariCompletedTransaction from a TariWallet by its' TransactionId
  *
@@ -3944,6 +3967,51 @@ bool wallet_clear_value(struct TariWallet *wallet,
 bool wallet_is_recovery_in_progress(struct TariWallet *wallet,
                                     int *error_out);
 
+/**
+ * Writes an encrypted backup of the wallet data that cannot be recovered from the seed words, i.e. the transaction
+ * history, contacts, labels, known one-sided payment scripts, accounts and unspent outputs, to a file.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `backup_file_path` - The path of the backup file to write
+ * `passphrase` - The passphrase to encrypt the backup with
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if the backup was written successfully
+ *
+ * # Safety
+ * None
+ */
+bool wallet_create_backup(struct TariWallet *wallet,
+                          const char *backup_file_path,
+                          const char *passphrase,
+                          int *error_out);
+
+/**
+ * Imports a backup written by `wallet_create_backup` into the wallet. This should be called on a newly created or
+ * recovered wallet before `wallet_start_recovery`, which will then only scan the chain from the height the backup was
+ * made at.
+ *
+ * ## Arguments
+ * `wallet` - The TariWallet pointer.
+ * `backup_file_path` - The path of the backup file to read
+ * `passphrase` - The passphrase the backup was encrypted with
+ * `error_out` - Pointer to an int which will be modified to an error code should one occur, may not be null. Functions
+ * as an out parameter.
+ *
+ * ## Returns
+ * `bool` - Returns if the backup was restored successfully
+ *
+ * # Safety
+ * None
+ */
+bool wallet_restore_backup(struct TariWallet *wallet,
+                           const char *backup_file_path,
+                           const char *passphrase,
+                           int *error_out);
+
 /**
  * Starts the Wallet recovery process.
  *
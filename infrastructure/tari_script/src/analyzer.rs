@@ -0,0 +1,768 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Static analysis of TariScript.
+//!
+//! The analyzer walks every execution path of a script, forking at each `IfThen` whose predicate is not a constant,
+//! and tracks an abstract stack of the types of items (and the values of constant integers) rather than the items
+//! themselves. Items that are popped before the script pushed them must come from the input stack, so every path
+//! yields the shape of the input stack it needs. A path fails if it can never succeed whatever the input stack and
+//! script context are, e.g. because it reaches `Return`, applies an opcode to an item of the wrong type or leaves
+//! more than one item on the stack.
+//!
+//! Opcodes whose outcome depends on the script context or on signatures (`CheckHeightVerify`, `CheckSigVerify`, ...)
+//! are assumed to succeed, so a path that does not fail might still never succeed in practice.
+//!
+//! The script revealed to `ExecuteMast` is only known when the output is spent, so the inputs of a path through
+//! `ExecuteMast` stop at the revealed script and its merkle proof, and its cost excludes the revealed script.
+
+use std::fmt;
+
+use crate::{
+    op_codes::Opcode,
+    script::{ExecutionState, MAX_MULTISIG_LIMIT},
+    stack::MAX_STACK_SIZE,
+    ScriptError,
+    StackItem,
+    TariScript,
+};
+
+/// The maximum number of execution paths analyzed. Each `IfThen` whose predicate is not constant doubles the number
+/// of paths, so the analysis of a script with many sequential statements stops at this limit.
+pub const MAX_ANALYZED_PATHS: usize = 1024;
+
+const OPCODE_COST: u64 = 1;
+const HASH_COST: u64 = 4;
+const SCALAR_MUL_COST: u64 = 20;
+const SIGNATURE_COST: u64 = 40;
+/// The depth of a balanced binary tree of `MAX_MAST_LEAVES` leaves
+const MAX_MAST_DEPTH: u64 = 8;
+
+/// A set of stack item types
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct StackItemTypes(u8);
+
+impl StackItemTypes {
+    pub const ANY: Self = Self(0b111_1111);
+    pub const COMMITMENT: Self = Self(1 << 3);
+    /// The types compared by `Equal`
+    const EQUATABLE: Self = Self(Self::ANY.0 & !Self::SCALAR.0 & !Self::SCRIPT.0);
+    pub const HASH: Self = Self(1 << 1);
+    const NAMES: [(Self, &'static str); 7] = [
+        (Self::NUMBER, "number"),
+        (Self::HASH, "hash"),
+        (Self::SCALAR, "scalar"),
+        (Self::COMMITMENT, "commitment"),
+        (Self::PUBLIC_KEY, "public key"),
+        (Self::SIGNATURE, "signature"),
+        (Self::SCRIPT, "script"),
+    ];
+    pub const NUMBER: Self = Self(1);
+    pub const PUBLIC_KEY: Self = Self(1 << 4);
+    pub const SCALAR: Self = Self(1 << 2);
+    pub const SCRIPT: Self = Self(1 << 6);
+    pub const SIGNATURE: Self = Self(1 << 5);
+
+    /// The type of the given stack item
+    pub fn of(item: &StackItem) -> Self {
+        match item {
+            StackItem::Number(_) => Self::NUMBER,
+            StackItem::Hash(_) => Self::HASH,
+            StackItem::Scalar(_) => Self::SCALAR,
+            StackItem::Commitment(_) => Self::COMMITMENT,
+            StackItem::PublicKey(_) => Self::PUBLIC_KEY,
+            StackItem::Signature(_) => Self::SIGNATURE,
+            StackItem::Script(_) => Self::SCRIPT,
+        }
+    }
+
+    #[must_use]
+    pub const fn union(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    #[must_use]
+    pub const fn intersection(self, other: Self) -> Self {
+        Self(self.0 & other.0)
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Whether every type in `other` is in this set
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl fmt::Display for StackItemTypes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if *self == Self::ANY {
+            return f.write_str("any");
+        }
+        let names = Self::NAMES
+            .iter()
+            .filter(|(types, _)| self.contains(*types))
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>();
+        if names.is_empty() {
+            f.write_str("none")
+        } else {
+            f.write_str(&names.join(" or "))
+        }
+    }
+}
+
+/// The opcode at which an execution path fails
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathFailure {
+    /// The position of the opcode in the script, or `None` if the path fails once all opcodes are executed
+    pub index: Option<usize>,
+    pub error: ScriptError,
+}
+
+/// A single path through the script, determined by the clause taken at each `IfThen` it executes
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionPath {
+    /// The position of each `IfThen` executed on this path and whether its `IfThen` (true) or `Else` (false) clause
+    /// was taken
+    pub branches: Vec<(usize, bool)>,
+    /// The types of the items the input stack must hold, from the bottom to the top of the stack. For a path that
+    /// fails, these are the items popped from the input stack before the failure.
+    pub inputs: Vec<StackItemTypes>,
+    /// The maximum number of items on the stack during execution, including the input stack
+    pub max_stack_depth: usize,
+    /// The weighted cost of the opcodes executed on this path
+    pub cost: u64,
+    /// The reason the path can never succeed, or `None` if it can
+    pub failure: Option<PathFailure>,
+}
+
+impl ExecutionPath {
+    pub fn can_succeed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// A clause of an `IfThen` statement that is never executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnreachableBranch {
+    /// The position of the `IfThen` opcode in the script
+    pub index: usize,
+    /// True if the `IfThen` clause is never executed, false if the `Else` clause is never executed
+    pub if_clause: bool,
+}
+
+/// The result of [TariScript::analyze]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptAnalysis {
+    pub paths: Vec<ExecutionPath>,
+    /// Clauses of `IfThen` statements that are reached but never executed, because their predicate is constant
+    pub unreachable_branches: Vec<UnreachableBranch>,
+    /// The positions of the opcodes that are not executed on any path
+    pub unreachable_opcodes: Vec<usize>,
+    /// False if the script has more than [MAX_ANALYZED_PATHS] paths and only some of them were analyzed. Unreachable
+    /// branches and opcodes are not reported for an incomplete analysis.
+    pub complete: bool,
+}
+
+impl ScriptAnalysis {
+    /// The paths that can succeed
+    pub fn spend_paths(&self) -> impl Iterator<Item = &ExecutionPath> {
+        self.paths.iter().filter(|path| path.can_succeed())
+    }
+
+    /// Whether the script can be spent. A script for which this is false locks its output forever.
+    pub fn can_succeed(&self) -> bool {
+        !self.complete || self.spend_paths().next().is_some()
+    }
+
+    /// The maximum stack depth of the paths that can succeed
+    pub fn max_stack_depth(&self) -> usize {
+        self.spend_paths().map(|path| path.max_stack_depth).max().unwrap_or(0)
+    }
+
+    /// The maximum cost of executing the script, including paths that fail
+    pub fn max_cost(&self) -> u64 {
+        self.paths.iter().map(|path| path.cost).max().unwrap_or(0)
+    }
+}
+
+impl fmt::Display for ScriptAnalysis {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (i, path) in self.paths.iter().enumerate() {
+            write!(f, "Path {}:", i + 1)?;
+            if path.branches.is_empty() {
+                write!(f, " no branches")?;
+            }
+            for (index, if_clause) in &path.branches {
+                write!(f, " #{} {}", index, if if *if_clause { "IfThen" } else { "Else" })?;
+            }
+            let inputs = path.inputs.iter().map(|t| t.to_string()).collect::<Vec<_>>();
+            write!(f, "\n  inputs: [{}]", inputs.join(", "))?;
+            write!(f, "\n  max stack depth: {}, cost: {}", path.max_stack_depth, path.cost)?;
+            match &path.failure {
+                Some(PathFailure {
+                    index: Some(index),
+                    error,
+                }) => writeln!(f, "\n  never succeeds: #{}: {}", index, error)?,
+                Some(PathFailure { index: None, error }) => writeln!(f, "\n  never succeeds: {}", error)?,
+                None => writeln!(f)?,
+            }
+        }
+        if !self.complete {
+            writeln!(f, "Only the first {} paths were analyzed", MAX_ANALYZED_PATHS)?;
+        }
+        for branch in &self.unreachable_branches {
+            let clause = if branch.if_clause { "IfThen" } else { "Else" };
+            writeln!(f, "Unreachable {} clause of #{}", clause, branch.index)?;
+        }
+        if !self.unreachable_opcodes.is_empty() {
+            let indices = self
+                .unreachable_opcodes
+                .iter()
+                .map(|i| format!("#{}", i))
+                .collect::<Vec<_>>();
+            writeln!(f, "Unreachable opcodes: {}", indices.join(", "))?;
+        }
+        if self.can_succeed() {
+            write!(
+                f,
+                "Max stack depth: {}, max cost: {}",
+                self.max_stack_depth(),
+                self.max_cost()
+            )
+        } else {
+            write!(f, "The script can never succeed")
+        }
+    }
+}
+
+impl TariScript {
+    /// Analyzes every execution path of the script without executing it. Opcodes that depend on the script context
+    /// or on signatures are assumed to succeed.
+    pub fn analyze(&self) -> ScriptAnalysis {
+        let opcodes = self.as_slice();
+        let mut reached = vec![false; opcodes.len()];
+        let mut clauses_taken = vec![(false, false); opcodes.len()];
+        let mut pending = vec![PathState::default()];
+        let mut num_paths = 1;
+        let mut complete = true;
+        let mut paths = Vec::new();
+
+        while let Some(mut path) = pending.pop() {
+            let failure = loop {
+                let index = path.pc;
+                let opcode = match opcodes.get(index) {
+                    Some(opcode) => opcode,
+                    None => break path.finish().err().map(|error| PathFailure { index: None, error }),
+                };
+                path.pc += 1;
+                if path.state.is_executing_clause(opcode) {
+                    reached[index] = true;
+                    path.cost += opcode_cost(opcode);
+                }
+                match path.step(opcode) {
+                    Ok(Branching::None) => {},
+                    Ok(Branching::Constant(if_clause)) => {
+                        path.take(index, if_clause, &mut clauses_taken);
+                    },
+                    Ok(Branching::Both) => {
+                        if num_paths < MAX_ANALYZED_PATHS {
+                            let mut other = path.clone();
+                            other.state.open_if_then(Some(false));
+                            other.take(index, false, &mut clauses_taken);
+                            pending.push(other);
+                            num_paths += 1;
+                        } else {
+                            complete = false;
+                        }
+                        path.state.open_if_then(Some(true));
+                        path.take(index, true, &mut clauses_taken);
+                    },
+                    Err(error) => {
+                        break Some(PathFailure {
+                            index: Some(index),
+                            error,
+                        })
+                    },
+                }
+            };
+            paths.push(path.into_execution_path(failure));
+        }
+
+        let mut unreachable_branches = Vec::new();
+        let mut unreachable_opcodes = Vec::new();
+        if complete {
+            for (index, opcode) in opcodes.iter().enumerate() {
+                if !reached[index] {
+                    unreachable_opcodes.push(index);
+                } else if *opcode == Opcode::IfThen {
+                    let (if_taken, else_taken) = clauses_taken[index];
+                    if !if_taken {
+                        unreachable_branches.push(UnreachableBranch { index, if_clause: true });
+                    }
+                    if !else_taken {
+                        unreachable_branches.push(UnreachableBranch {
+                            index,
+                            if_clause: false,
+                        });
+                    }
+                } else {
+                    // Only conditionals have branches
+                }
+            }
+        }
+
+        ScriptAnalysis {
+            paths,
+            unreachable_branches,
+            unreachable_opcodes,
+            complete,
+        }
+    }
+}
+
+/// The cost of executing an opcode, relative to that of a simple stack operation
+fn opcode_cost(opcode: &Opcode) -> u64 {
+    #[allow(clippy::enum_glob_use)]
+    use Opcode::*;
+    match opcode {
+        HashBlake256 | HashSha256 | HashSha3 => HASH_COST,
+        ToRistrettoPoint => SCALAR_MUL_COST,
+        CheckSig(_) | CheckSigVerify(_) => SIGNATURE_COST,
+        // Each public key is checked against at most one signature
+        CheckMultiSig(_, n, _, _) |
+        CheckMultiSigVerify(_, n, _, _) |
+        CheckMultiSigVerifyAggregatePubKey(_, n, _, _) => SIGNATURE_COST * u64::from(*n),
+        // The revealed script is hashed, and so is every level of the tree
+        ExecuteMast(_) => HASH_COST * (MAX_MAST_DEPTH + 1),
+        _ => OPCODE_COST,
+    }
+}
+
+/// What an opcode did to the execution path
+enum Branching {
+    /// The opcode is not an executed `IfThen`
+    None,
+    /// An `IfThen` with a constant predicate, taking the `IfThen` clause if true
+    Constant(bool),
+    /// An `IfThen` whose predicate is unknown, so either clause can be taken. The statement is not opened yet.
+    Both,
+}
+
+/// An item on the abstract stack
+#[derive(Debug, Clone, Copy)]
+struct Value {
+    types: StackItemTypes,
+    /// The value of a constant integer
+    number: Option<i64>,
+    /// The position of the item in the input stack, counted from the top, if it is an input
+    input: Option<usize>,
+}
+
+impl Value {
+    fn of_type(types: StackItemTypes) -> Self {
+        Self {
+            types,
+            number: None,
+            input: None,
+        }
+    }
+
+    fn number(number: i64) -> Self {
+        Self {
+            types: StackItemTypes::NUMBER,
+            number: Some(number),
+            input: None,
+        }
+    }
+
+    fn boolean(value: Option<bool>) -> Self {
+        match value {
+            Some(value) => Self::number(i64::from(value)),
+            None => Self::of_type(StackItemTypes::NUMBER),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+struct PathState {
+    pc: usize,
+    stack: Vec<Value>,
+    /// The types required of the input items popped so far, from the top of the input stack
+    inputs: Vec<StackItemTypes>,
+    state: ExecutionState,
+    branches: Vec<(usize, bool)>,
+    /// The maximum of the number of items on the abstract stack less the number of inputs popped so far
+    max_offset: isize,
+    cost: u64,
+}
+
+impl PathState {
+    fn push(&mut self, value: Value) {
+        self.stack.push(value);
+        // Both lengths are bounded by the number of opcodes
+        #[allow(clippy::cast_possible_wrap)]
+        let offset = self.stack.len() as isize - self.inputs.len() as isize;
+        self.max_offset = self.max_offset.max(offset);
+    }
+
+    fn pop(&mut self) -> Value {
+        self.stack.pop().unwrap_or_else(|| {
+            self.inputs.push(StackItemTypes::ANY);
+            Value {
+                types: StackItemTypes::ANY,
+                number: None,
+                input: Some(self.inputs.len() - 1),
+            }
+        })
+    }
+
+    fn pop_n(&mut self, n: usize) -> Vec<Value> {
+        (0..n).map(|_| self.pop()).collect()
+    }
+
+    fn types(&self, value: &Value) -> StackItemTypes {
+        value.input.map_or(value.types, |i| self.inputs[i])
+    }
+
+    /// Requires all of the values to be of the same type, which is one of `allowed`, and returns the possible types
+    fn constrain(
+        &mut self,
+        values: &[Value],
+        allowed: StackItemTypes,
+        error: ScriptError,
+    ) -> Result<StackItemTypes, ScriptError> {
+        let types = values
+            .iter()
+            .fold(allowed, |types, value| types.intersection(self.types(value)));
+        if types.is_empty() {
+            return Err(error);
+        }
+        for value in values {
+            if let Some(i) = value.input {
+                self.inputs[i] = types;
+            }
+        }
+        Ok(types)
+    }
+
+    fn pop_number(&mut self) -> Result<Option<i64>, ScriptError> {
+        let value = self.pop();
+        self.constrain(&[value], StackItemTypes::NUMBER, ScriptError::InvalidInput)?;
+        Ok(value.number)
+    }
+
+    fn pop_typed(&mut self, allowed: StackItemTypes) -> Result<(), ScriptError> {
+        let value = self.pop();
+        self.constrain(&[value], allowed, ScriptError::IncompatibleTypes)?;
+        Ok(())
+    }
+
+    fn take(&mut self, index: usize, if_clause: bool, clauses_taken: &mut [(bool, bool)]) {
+        self.branches.push((index, if_clause));
+        if if_clause {
+            clauses_taken[index].0 = true;
+        } else {
+            clauses_taken[index].1 = true;
+        }
+    }
+
+    fn step(&mut self, opcode: &Opcode) -> Result<Branching, ScriptError> {
+        #[allow(clippy::enum_glob_use)]
+        use Opcode::*;
+        use StackItemTypes as T;
+
+        match opcode {
+            IfThen if self.state.executing => {
+                return match self.pop_number()? {
+                    Some(1) => {
+                        self.state.open_if_then(Some(true));
+                        Ok(Branching::Constant(true))
+                    },
+                    Some(0) => {
+                        self.state.open_if_then(Some(false));
+                        Ok(Branching::Constant(false))
+                    },
+                    Some(_) => Err(ScriptError::InvalidInput),
+                    None => Ok(Branching::Both),
+                };
+            },
+            IfThen => self.state.open_if_then(None),
+            Else => TariScript::handle_else(&mut self.state)?,
+            EndIf => TariScript::handle_end_if(&mut self.state)?,
+            _ if !self.state.executing => {},
+            CheckHeightVerify(_) | CheckRelativeHeightVerify(_) | Nop => {},
+            CheckHeight(_) => self.push(Value::of_type(T::NUMBER)),
+            CompareHeightVerify => {
+                self.pop_number()?;
+            },
+            CompareHeight => {
+                self.pop_number()?;
+                self.push(Value::of_type(T::NUMBER));
+            },
+            PushZero => self.push(Value::number(0)),
+            PushOne => self.push(Value::number(1)),
+            PushInt(n) => self.push(Value::number(*n)),
+            PushHash(_) => self.push(Value::of_type(T::HASH)),
+            PushPubKey(_) => self.push(Value::of_type(T::PUBLIC_KEY)),
+            Drop => {
+                self.pop();
+            },
+            Dup => {
+                let value = self.pop();
+                self.push(value);
+                self.push(value);
+            },
+            RevRot => {
+                let c = self.pop();
+                let b = self.pop();
+                let a = self.pop();
+                self.push(c);
+                self.push(a);
+                self.push(b);
+            },
+            GeZero | GtZero | LeZero | LtZero => {
+                let result = self.pop_number()?.map(|n| match opcode {
+                    GeZero => n >= 0,
+                    GtZero => n > 0,
+                    LeZero => n <= 0,
+                    _ => n < 0,
+                });
+                self.push(Value::boolean(result));
+            },
+            Add | Sub => {
+                let top = self.pop();
+                let two = self.pop();
+                let allowed = if *opcode == Add {
+                    T::NUMBER.union(T::COMMITMENT).union(T::PUBLIC_KEY)
+                } else {
+                    T::NUMBER.union(T::COMMITMENT)
+                };
+                let types = self.constrain(&[top, two], allowed, ScriptError::IncompatibleTypes)?;
+                let number = match (top.number, two.number) {
+                    (Some(v1), Some(v2)) if *opcode == Add => {
+                        Some(v1.checked_add(v2).ok_or(ScriptError::ValueExceedsBounds)?)
+                    },
+                    (Some(v1), Some(v2)) => Some(v2.checked_sub(v1).ok_or(ScriptError::ValueExceedsBounds)?),
+                    _ => None,
+                };
+                self.push(Value {
+                    types,
+                    number,
+                    input: None,
+                });
+            },
+            Equal | EqualVerify => {
+                let top = self.pop();
+                let two = self.pop();
+                self.constrain(&[top, two], T::EQUATABLE, ScriptError::IncompatibleTypes)?;
+                let equal = match (top.number, two.number) {
+                    (Some(v1), Some(v2)) => Some(v1 == v2),
+                    _ => None,
+                };
+                if *opcode == Equal {
+                    self.push(Value::boolean(equal));
+                } else if equal == Some(false) {
+                    return Err(ScriptError::VerifyFailed);
+                } else {
+                    // The values may be equal, so the verification may pass
+                }
+            },
+            Or(n) | OrVerify(n) => {
+                let values = self.pop_n(usize::from(*n) + 1);
+                self.constrain(&values, T::ANY, ScriptError::InvalidInput)?;
+                if matches!(opcode, Or(_)) {
+                    self.push(Value::of_type(T::NUMBER));
+                }
+            },
+            HashBlake256 | HashSha256 | HashSha3 => {
+                self.pop_typed(T::HASH.union(T::PUBLIC_KEY).union(T::COMMITMENT))?;
+                self.push(Value::of_type(T::HASH));
+            },
+            CheckSig(_) | CheckSigVerify(_) => {
+                self.pop_typed(T::PUBLIC_KEY)?;
+                self.pop_typed(T::SIGNATURE)?;
+                if matches!(opcode, CheckSig(_)) {
+                    self.push(Value::of_type(T::NUMBER));
+                }
+            },
+            CheckMultiSig(m, n, public_keys, _) |
+            CheckMultiSigVerify(m, n, public_keys, _) |
+            CheckMultiSigVerifyAggregatePubKey(m, n, public_keys, _) => {
+                if *m == 0 || *n == 0 || m > n || *n > MAX_MULTISIG_LIMIT || public_keys.len() != usize::from(*n) {
+                    return Err(ScriptError::ValueExceedsBounds);
+                }
+                for _ in 0..*m {
+                    self.pop_typed(T::SIGNATURE)?;
+                }
+                match opcode {
+                    CheckMultiSig(..) => self.push(Value::of_type(T::NUMBER)),
+                    CheckMultiSigVerifyAggregatePubKey(..) => self.push(Value::of_type(T::PUBLIC_KEY)),
+                    _ => {},
+                }
+            },
+            ToRistrettoPoint => {
+                self.pop_typed(T::HASH.union(T::SCALAR))?;
+                self.push(Value::of_type(T::PUBLIC_KEY));
+            },
+            Return => return Err(ScriptError::Return),
+            ExecuteMast(_) => {
+                self.pop_typed(T::SCRIPT)?;
+                self.pop_number()?;
+                self.pop_number()?;
+                // The revealed script consumes an unknown part of the stack and leaves its result
+                self.stack.clear();
+                self.push(Value::of_type(T::ANY));
+            },
+        }
+        Ok(Branching::None)
+    }
+
+    /// Checks the state once all opcodes are executed, as [TariScript::execute_with_context] does
+    fn finish(&mut self) -> Result<(), ScriptError> {
+        if !self.state.if_stack.is_empty() {
+            return Err(ScriptError::MissingOpcode);
+        }
+        match self.stack.len() {
+            // The input item below those popped by the script is the result
+            0 => self.inputs.push(StackItemTypes::ANY),
+            1 => {},
+            _ => return Err(ScriptError::NonUnitLengthStack),
+        }
+        if self.max_stack_depth() > MAX_STACK_SIZE {
+            return Err(ScriptError::StackOverflow);
+        }
+        Ok(())
+    }
+
+    fn max_stack_depth(&self) -> usize {
+        #[allow(clippy::cast_possible_wrap)]
+        let depth = self.inputs.len() as isize + self.max_offset;
+        usize::try_from(depth).unwrap_or(0)
+    }
+
+    fn into_execution_path(self, failure: Option<PathFailure>) -> ExecutionPath {
+        ExecutionPath {
+            max_stack_depth: self.max_stack_depth(),
+            inputs: self.inputs.into_iter().rev().collect(),
+            branches: self.branches,
+            cost: self.cost,
+            failure,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use super::*;
+
+    fn analyze(source: &str) -> ScriptAnalysis {
+        TariScript::from_str(source).unwrap().analyze()
+    }
+
+    #[test]
+    fn it_analyzes_a_single_path() {
+        let analysis = analyze(
+            "Dup HashBlake256 PushHash(0000000000000000000000000000000000000000000000000000000000000000) EqualVerify",
+        );
+        assert!(analysis.complete);
+        assert_eq!(analysis.paths.len(), 1);
+        let path = &analysis.paths[0];
+        assert!(path.can_succeed());
+        assert!(path.branches.is_empty());
+        assert_eq!(path.inputs, vec![StackItemTypes::HASH
+            .union(StackItemTypes::PUBLIC_KEY)
+            .union(StackItemTypes::COMMITMENT)]);
+        assert_eq!(path.max_stack_depth, 3);
+        assert_eq!(path.cost, 3 * OPCODE_COST + HASH_COST);
+        assert!(analysis.unreachable_opcodes.is_empty());
+    }
+
+    #[test]
+    fn it_requires_an_extra_input_when_the_script_leaves_the_stack_empty() {
+        let analysis = analyze("CheckSigVerify(0000000000000000000000000000000000000000000000000000000000000000)");
+        let path = &analysis.paths[0];
+        assert!(path.can_succeed());
+        assert_eq!(path.inputs, vec![
+            StackItemTypes::ANY,
+            StackItemTypes::SIGNATURE,
+            StackItemTypes::PUBLIC_KEY
+        ]);
+        assert_eq!(analysis.max_cost(), SIGNATURE_COST);
+    }
+
+    #[test]
+    fn it_forks_at_unknown_predicates() {
+        let analysis = analyze("IfThen PushOne Else Return EndIf");
+        assert_eq!(analysis.paths.len(), 2);
+        assert_eq!(analysis.spend_paths().count(), 1);
+        let spend_path = analysis.spend_paths().next().unwrap();
+        assert_eq!(spend_path.branches, vec![(0, true)]);
+        assert_eq!(spend_path.inputs, vec![StackItemTypes::NUMBER]);
+        let failing = analysis.paths.iter().find(|p| !p.can_succeed()).unwrap();
+        assert_eq!(
+            failing.failure,
+            Some(PathFailure {
+                index: Some(3),
+                error: ScriptError::Return
+            })
+        );
+        assert!(analysis.unreachable_branches.is_empty());
+        assert!(analysis.can_succeed());
+    }
+
+    #[test]
+    fn it_detects_unreachable_branches() {
+        let analysis = analyze("PushInt(5) GtZero IfThen PushOne Else PushZero Drop EndIf");
+        assert_eq!(analysis.paths.len(), 1);
+        assert_eq!(analysis.unreachable_branches, vec![UnreachableBranch {
+            index: 2,
+            if_clause: false
+        }]);
+        assert_eq!(analysis.unreachable_opcodes, vec![5, 6]);
+    }
+
+    #[test]
+    fn it_detects_scripts_that_never_succeed() {
+        assert!(!analyze("Return").can_succeed());
+        assert!(!analyze("PushOne PushOne").can_succeed());
+        assert!(!analyze("PushOne PushZero EqualVerify").can_succeed());
+        assert!(
+            !analyze("PushHash(0000000000000000000000000000000000000000000000000000000000000000) GeZero").can_succeed()
+        );
+        assert!(!analyze("IfThen Return Else Return EndIf").can_succeed());
+        // The same input cannot be both a number and a hash or scalar
+        assert!(!analyze("Dup ToRistrettoPoint Drop CompareHeightVerify").can_succeed());
+        assert!(analyze("PushOne").can_succeed());
+    }
+
+    #[test]
+    fn it_stops_at_the_revealed_script_of_a_mast() {
+        let analysis = analyze("ExecuteMast(0000000000000000000000000000000000000000000000000000000000000000)");
+        let path = &analysis.paths[0];
+        assert!(path.can_succeed());
+        assert_eq!(path.inputs, vec![
+            StackItemTypes::NUMBER,
+            StackItemTypes::NUMBER,
+            StackItemTypes::SCRIPT
+        ]);
+        assert!(
+            !analyze("PushOne ExecuteMast(0000000000000000000000000000000000000000000000000000000000000000)")
+                .can_succeed()
+        );
+    }
+
+    #[test]
+    fn it_stops_at_the_path_limit() {
+        let statement = "IfThen Else EndIf ";
+        let analysis = analyze(&statement.repeat(11));
+        assert!(!analysis.complete);
+        assert_eq!(analysis.paths.len(), MAX_ANALYZED_PATHS);
+        assert!(analysis.can_succeed());
+    }
+}
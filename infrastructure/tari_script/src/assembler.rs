@@ -0,0 +1,596 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! A textual assembly syntax for TariScript.
+//!
+//! A script is a sequence of opcodes separated by whitespace. Opcodes are written exactly as they are displayed, i.e.
+//! using their case-sensitive name followed by a parenthesised, comma separated list of arguments for opcodes that
+//! take any:
+//!
+//! ```text
+//! # Spendable by the key holder after block 100
+//! CheckHeightVerify(100)
+//! PushPubKey(6c9cb4d3e57351462122310fa22c90b1e6dfb528d64615363d1261a75da3e401)
+//! ```
+//!
+//! Arguments are one of:
+//! * decimal integers, for heights (`CheckHeight`, `CheckHeightVerify`, `CheckRelativeHeightVerify`), `PushInt` values
+//!   and the counts of `Or`, `OrVerify` and the multisig opcodes;
+//! * 32-byte hex values, for hashes (`PushHash`, `ExecuteMast`), public keys (`PushPubKey`) and messages (`CheckSig`,
+//!   `CheckSigVerify` and the multisig opcodes);
+//! * a bracketed, comma separated list of hex encoded public keys, for the multisig opcodes:
+//!
+//! ```text
+//! CheckMultiSig(1, 2, [<public key>, <public key>], <message>)
+//! ```
+//!
+//! Everything from a `#` to the end of the line is a comment. The output of [TariScript]'s `Display` implementation
+//! and of [TariScript::to_pretty_string] both parse back to the same script.
+
+use std::{convert::TryFrom, str::FromStr};
+
+use tari_crypto::ristretto::RistrettoPublicKey;
+use tari_utilities::hex::{from_hex, Hex};
+
+use crate::{
+    error::{ScriptParseError, ScriptParseErrorKind},
+    op_codes::{slice_to_boxed_hash, slice_to_boxed_message},
+    script::{MAX_MULTISIG_LIMIT, MAX_SCRIPT_BYTES, MAX_SCRIPT_OPCODES},
+    Opcode,
+    TariScript,
+};
+
+const HASH_LENGTH: usize = 32;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum TokenKind {
+    Word(String),
+    OpenParen,
+    CloseParen,
+    OpenBracket,
+    CloseBracket,
+    Comma,
+}
+
+impl TokenKind {
+    fn describe(&self) -> String {
+        match self {
+            TokenKind::Word(word) => word.clone(),
+            TokenKind::OpenParen => "(".to_string(),
+            TokenKind::CloseParen => ")".to_string(),
+            TokenKind::OpenBracket => "[".to_string(),
+            TokenKind::CloseBracket => "]".to_string(),
+            TokenKind::Comma => ",".to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Position {
+    line: usize,
+    column: usize,
+}
+
+impl Position {
+    fn error(self, kind: ScriptParseErrorKind) -> ScriptParseError {
+        ScriptParseError {
+            line: self.line,
+            column: self.column,
+            kind,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Token {
+    kind: TokenKind,
+    position: Position,
+}
+
+fn tokenize(source: &str) -> Result<Vec<Token>, ScriptParseError> {
+    let mut tokens = Vec::new();
+    for (line_index, line) in source.lines().enumerate() {
+        let mut chars = line.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            let position = Position {
+                line: line_index + 1,
+                column: line[..index].chars().count() + 1,
+            };
+            let kind = match c {
+                '#' => break,
+                c if c.is_whitespace() => continue,
+                '(' => TokenKind::OpenParen,
+                ')' => TokenKind::CloseParen,
+                '[' => TokenKind::OpenBracket,
+                ']' => TokenKind::CloseBracket,
+                ',' => TokenKind::Comma,
+                c if is_word_char(c) => {
+                    let mut word = c.to_string();
+                    while let Some((_, c)) = chars.next_if(|(_, c)| is_word_char(*c)) {
+                        word.push(c);
+                    }
+                    TokenKind::Word(word)
+                },
+                c => return Err(position.error(ScriptParseErrorKind::UnexpectedCharacter(c))),
+            };
+            tokens.push(Token { kind, position });
+        }
+    }
+    Ok(tokens)
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+#[derive(Debug, Clone)]
+enum Argument {
+    Value(String, Position),
+    List(Vec<(String, Position)>, Position),
+}
+
+impl Argument {
+    fn position(&self) -> Position {
+        match self {
+            Argument::Value(_, position) | Argument::List(_, position) => *position,
+        }
+    }
+
+    fn into_value(self) -> Result<(String, Position), ScriptParseError> {
+        match self {
+            Argument::Value(value, position) => Ok((value, position)),
+            Argument::List(_, position) => Err(position.error(ScriptParseErrorKind::UnexpectedToken {
+                expected: "a value",
+                found: "[".to_string(),
+            })),
+        }
+    }
+
+    fn into_list(self) -> Result<Vec<(String, Position)>, ScriptParseError> {
+        match self {
+            Argument::List(values, _) => Ok(values),
+            Argument::Value(value, position) => Err(position.error(ScriptParseErrorKind::UnexpectedToken {
+                expected: "a list of public keys",
+                found: value,
+            })),
+        }
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    index: usize,
+    end: Position,
+}
+
+impl Parser {
+    fn new(source: &str) -> Result<Self, ScriptParseError> {
+        let tokens = tokenize(source)?;
+        let num_lines = source.lines().count().max(1);
+        let end = Position {
+            line: num_lines,
+            column: source.lines().last().map(|l| l.chars().count()).unwrap_or_default() + 1,
+        };
+        Ok(Self { tokens, index: 0, end })
+    }
+
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.index)
+    }
+
+    fn next(&mut self, expected: &'static str) -> Result<Token, ScriptParseError> {
+        let token = self
+            .tokens
+            .get(self.index)
+            .cloned()
+            .ok_or_else(|| self.end.error(ScriptParseErrorKind::UnexpectedEnd(expected)))?;
+        self.index += 1;
+        Ok(token)
+    }
+
+    fn expect(&mut self, kind: &TokenKind, expected: &'static str) -> Result<Position, ScriptParseError> {
+        let token = self.next(expected)?;
+        if token.kind == *kind {
+            Ok(token.position)
+        } else {
+            Err(unexpected(&token, expected))
+        }
+    }
+
+    fn parse_opcodes(&mut self) -> Result<Vec<(Opcode, Position)>, ScriptParseError> {
+        let mut opcodes = Vec::new();
+        while self.peek().is_some() {
+            opcodes.push(self.parse_opcode()?);
+        }
+        Ok(opcodes)
+    }
+
+    fn parse_opcode(&mut self) -> Result<(Opcode, Position), ScriptParseError> {
+        let token = self.next("an opcode")?;
+        let name = match token.kind {
+            TokenKind::Word(ref name) => name.clone(),
+            _ => return Err(unexpected(&token, "an opcode")),
+        };
+        let arguments = if self.peek().map(|t| &t.kind) == Some(&TokenKind::OpenParen) {
+            self.parse_arguments()?
+        } else {
+            Vec::new()
+        };
+        let opcode = build_opcode(&name, token.position, arguments)?;
+        Ok((opcode, token.position))
+    }
+
+    fn parse_arguments(&mut self) -> Result<Vec<Argument>, ScriptParseError> {
+        self.expect(&TokenKind::OpenParen, "`(`")?;
+        let mut arguments = Vec::new();
+        loop {
+            let token = self.next("an argument")?;
+            let argument = match token.kind {
+                TokenKind::Word(value) => Argument::Value(value, token.position),
+                TokenKind::OpenBracket => Argument::List(self.parse_list()?, token.position),
+                TokenKind::CloseParen if arguments.is_empty() => return Ok(arguments),
+                _ => return Err(unexpected(&token, "an argument")),
+            };
+            arguments.push(argument);
+            let token = self.next("`,` or `)`")?;
+            match token.kind {
+                TokenKind::Comma => continue,
+                TokenKind::CloseParen => return Ok(arguments),
+                _ => return Err(unexpected(&token, "`,` or `)`")),
+            }
+        }
+    }
+
+    fn parse_list(&mut self) -> Result<Vec<(String, Position)>, ScriptParseError> {
+        let mut values = Vec::new();
+        loop {
+            let token = self.next("a list item")?;
+            match token.kind {
+                TokenKind::Word(value) => values.push((value, token.position)),
+                TokenKind::CloseBracket if values.is_empty() => return Ok(values),
+                _ => return Err(unexpected(&token, "a list item")),
+            }
+            let token = self.next("`,` or `]`")?;
+            match token.kind {
+                TokenKind::Comma => continue,
+                TokenKind::CloseBracket => return Ok(values),
+                _ => return Err(unexpected(&token, "`,` or `]`")),
+            }
+        }
+    }
+}
+
+fn unexpected(token: &Token, expected: &'static str) -> ScriptParseError {
+    token.position.error(ScriptParseErrorKind::UnexpectedToken {
+        expected,
+        found: token.kind.describe(),
+    })
+}
+
+fn parse_integer<T: FromStr>((value, position): (String, Position)) -> Result<T, ScriptParseError> {
+    value
+        .parse()
+        .map_err(|_| position.error(ScriptParseErrorKind::InvalidInteger(value)))
+}
+
+fn parse_hex_32((value, position): (String, Position)) -> Result<Vec<u8>, ScriptParseError> {
+    match from_hex(&value) {
+        Ok(bytes) if bytes.len() == HASH_LENGTH => Ok(bytes),
+        _ => Err(position.error(ScriptParseErrorKind::InvalidHex {
+            value,
+            expected_bytes: HASH_LENGTH,
+        })),
+    }
+}
+
+fn parse_public_key((value, position): (String, Position)) -> Result<RistrettoPublicKey, ScriptParseError> {
+    RistrettoPublicKey::from_hex(&value).map_err(|_| position.error(ScriptParseErrorKind::InvalidPublicKey(value)))
+}
+
+type MultiSigArgs = (u8, u8, Vec<RistrettoPublicKey>, Box<[u8; 32]>);
+
+/// The arguments of an opcode, which are checked against the number of arguments the opcode takes when they are taken
+struct OpcodeArguments<'a> {
+    name: &'a str,
+    position: Position,
+    values: Vec<Argument>,
+}
+
+impl OpcodeArguments<'_> {
+    fn take<const N: usize>(self) -> Result<[Argument; N], ScriptParseError> {
+        let (name, position) = (self.name, self.position);
+        <[Argument; N]>::try_from(self.values).map_err(|values| {
+            let position = values.get(N).map(Argument::position).unwrap_or(position);
+            position.error(ScriptParseErrorKind::WrongArgumentCount {
+                opcode: name.to_string(),
+                expected: N,
+                actual: values.len(),
+            })
+        })
+    }
+
+    /// Returns `opcode` if there are no arguments
+    fn none(self, opcode: Opcode) -> Result<Opcode, ScriptParseError> {
+        self.take::<0>()?;
+        Ok(opcode)
+    }
+
+    fn one(self) -> Result<(String, Position), ScriptParseError> {
+        let [value] = self.take()?;
+        value.into_value()
+    }
+
+    fn multisig(self) -> Result<MultiSigArgs, ScriptParseError> {
+        let [m, n, keys, msg] = self.take()?;
+        let (m, m_position) = m.into_value()?;
+        let m = parse_integer((m, m_position))?;
+        let (n, n_position) = n.into_value()?;
+        let n = parse_integer((n, n_position))?;
+        if m == 0 || m > n || n > MAX_MULTISIG_LIMIT {
+            return Err(m_position.error(ScriptParseErrorKind::InvalidMultisigThreshold {
+                m,
+                n,
+                max: MAX_MULTISIG_LIMIT,
+            }));
+        }
+        let keys = keys
+            .into_list()?
+            .into_iter()
+            .map(parse_public_key)
+            .collect::<Result<Vec<_>, _>>()?;
+        if keys.len() != usize::from(n) {
+            return Err(n_position.error(ScriptParseErrorKind::MultisigKeyCountMismatch {
+                declared: n,
+                listed: keys.len(),
+            }));
+        }
+        let msg = slice_to_boxed_message(&parse_hex_32(msg.into_value()?)?);
+        Ok((m, n, keys, msg))
+    }
+}
+
+fn build_opcode(name: &str, position: Position, arguments: Vec<Argument>) -> Result<Opcode, ScriptParseError> {
+    #[allow(clippy::enum_glob_use)]
+    use Opcode::*;
+    let args = OpcodeArguments {
+        name,
+        position,
+        values: arguments,
+    };
+    let opcode = match name {
+        "CheckHeightVerify" => CheckHeightVerify(parse_integer(args.one()?)?),
+        "CheckHeight" => CheckHeight(parse_integer(args.one()?)?),
+        "CheckRelativeHeightVerify" => CheckRelativeHeightVerify(parse_integer(args.one()?)?),
+        "CompareHeightVerify" => args.none(CompareHeightVerify)?,
+        "CompareHeight" => args.none(CompareHeight)?,
+        "Nop" => args.none(Nop)?,
+        "PushZero" => args.none(PushZero)?,
+        "PushOne" => args.none(PushOne)?,
+        "PushHash" => PushHash(slice_to_boxed_hash(&parse_hex_32(args.one()?)?)),
+        "PushInt" => PushInt(parse_integer(args.one()?)?),
+        "PushPubKey" => PushPubKey(Box::new(parse_public_key(args.one()?)?)),
+        "Drop" => args.none(Drop)?,
+        "Dup" => args.none(Dup)?,
+        "RevRot" => args.none(RevRot)?,
+        "GeZero" => args.none(GeZero)?,
+        "GtZero" => args.none(GtZero)?,
+        "LeZero" => args.none(LeZero)?,
+        "LtZero" => args.none(LtZero)?,
+        "Add" => args.none(Add)?,
+        "Sub" => args.none(Sub)?,
+        "Equal" => args.none(Equal)?,
+        "EqualVerify" => args.none(EqualVerify)?,
+        "Or" => Or(parse_integer(args.one()?)?),
+        "OrVerify" => OrVerify(parse_integer(args.one()?)?),
+        "HashBlake256" => args.none(HashBlake256)?,
+        "HashSha256" => args.none(HashSha256)?,
+        "HashSha3" => args.none(HashSha3)?,
+        "CheckSig" => CheckSig(slice_to_boxed_message(&parse_hex_32(args.one()?)?)),
+        "CheckSigVerify" => CheckSigVerify(slice_to_boxed_message(&parse_hex_32(args.one()?)?)),
+        "CheckMultiSig" => {
+            let (m, n, keys, msg) = args.multisig()?;
+            CheckMultiSig(m, n, keys, msg)
+        },
+        "CheckMultiSigVerify" => {
+            let (m, n, keys, msg) = args.multisig()?;
+            CheckMultiSigVerify(m, n, keys, msg)
+        },
+        "CheckMultiSigVerifyAggregatePubKey" => {
+            let (m, n, keys, msg) = args.multisig()?;
+            CheckMultiSigVerifyAggregatePubKey(m, n, keys, msg)
+        },
+        "ToRistrettoPoint" => args.none(ToRistrettoPoint)?,
+        "Return" => args.none(Return)?,
+        "IfThen" => args.none(IfThen)?,
+        "Else" => args.none(Else)?,
+        "EndIf" => args.none(EndIf)?,
+        "ExecuteMast" => ExecuteMast(slice_to_boxed_hash(&parse_hex_32(args.one()?)?)),
+        _ => return Err(position.error(ScriptParseErrorKind::UnknownOpcode(name.to_string()))),
+    };
+    Ok(opcode)
+}
+
+impl FromStr for Opcode {
+    type Err = ScriptParseError;
+
+    /// Parses a single opcode in the assembly syntax, e.g. `CheckHeight(100)`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s)?;
+        let (opcode, _) = parser.parse_opcode()?;
+        if let Some(token) = parser.peek() {
+            return Err(unexpected(token, "the end of the opcode"));
+        }
+        Ok(opcode)
+    }
+}
+
+impl FromStr for TariScript {
+    type Err = ScriptParseError;
+
+    /// Assembles a script from the assembly syntax, e.g. `CheckHeightVerify(100) PushPubKey(<key>)`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser::new(s)?;
+        let opcodes = parser.parse_opcodes()?;
+        if let Some((_, position)) = opcodes.get(MAX_SCRIPT_OPCODES) {
+            return Err(position.error(ScriptParseErrorKind::TooManyOpcodes(MAX_SCRIPT_OPCODES)));
+        }
+        let mut bytes = Vec::new();
+        for (opcode, position) in &opcodes {
+            opcode.to_bytes(&mut bytes);
+            if bytes.len() > MAX_SCRIPT_BYTES {
+                return Err(position.error(ScriptParseErrorKind::ScriptTooLarge(MAX_SCRIPT_BYTES)));
+            }
+        }
+        let script = TariScript::new(opcodes.into_iter().map(|(opcode, _)| opcode).collect())
+            .expect("the number of opcodes is checked above");
+        Ok(script)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::script;
+
+    const KEY: &str = "6c9cb4d3e57351462122310fa22c90b1e6dfb528d64615363d1261a75da3e401";
+    const KEY2: &str = "56c0fa32558d6edc0916baa26b48e745de834571534ca253ea82435f08ebbc7c";
+    const MSG: &str = "276657a418820f34036b20ea615302b373c70ac8feab8d30681a3e0f0960e708";
+
+    fn parse_err(source: &str) -> ScriptParseError {
+        TariScript::from_str(source).unwrap_err()
+    }
+
+    #[test]
+    fn it_assembles_a_script() {
+        let source = format!(
+            "# Time locked payment\nCheckHeightVerify(100)\n  PushPubKey({}) # the recipient\n",
+            KEY
+        );
+        let script = TariScript::from_str(&source).unwrap();
+        let key = RistrettoPublicKey::from_hex(KEY).unwrap();
+        assert_eq!(
+            script,
+            script!(CheckHeightVerify(100) PushPubKey(Box::new(key))).unwrap()
+        );
+    }
+
+    #[test]
+    fn it_round_trips_all_opcodes() {
+        let source = format!(
+            "CheckHeightVerify(1) CheckHeight(18446744073709551615) CheckRelativeHeightVerify(144) \
+             CompareHeightVerify CompareHeight Nop PushZero PushOne PushHash({msg}) PushInt(-257) PushPubKey({key}) \
+             Drop Dup RevRot GeZero GtZero LeZero LtZero Add Sub Equal EqualVerify Or(2) OrVerify(3) HashBlake256 \
+             HashSha256 HashSha3 CheckSig({msg}) CheckSigVerify({msg}) CheckMultiSig(1, 2, [{key}, {key2}], {msg}) \
+             CheckMultiSigVerify(1, 1, [{key}], {msg}) CheckMultiSigVerifyAggregatePubKey(2, 2, [{key}, {key2}], \
+             {msg}) ToRistrettoPoint IfThen Return Else PushOne EndIf ExecuteMast({msg})",
+            msg = MSG,
+            key = KEY,
+            key2 = KEY2
+        );
+        let script = TariScript::from_str(&source).unwrap();
+        assert_eq!(script.size(), 39);
+        assert_eq!(TariScript::from_str(&script.to_string()).unwrap(), script);
+        assert_eq!(TariScript::from_str(&script.to_pretty_string()).unwrap(), script);
+        for opcode in script.as_slice() {
+            assert_eq!(Opcode::from_str(&opcode.to_string()).unwrap(), *opcode);
+        }
+    }
+
+    #[test]
+    fn it_assembles_an_empty_script() {
+        let script = TariScript::from_str(" # nothing to see here\n").unwrap();
+        assert_eq!(script.size(), 0);
+    }
+
+    #[test]
+    fn it_reports_the_position_of_errors() {
+        let err = parse_err("Dup\n  HashBlake257");
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+        assert_eq!(
+            err.kind,
+            ScriptParseErrorKind::UnknownOpcode("HashBlake257".to_string())
+        );
+
+        let err = parse_err("Dup CheckHeight(abc)");
+        assert_eq!((err.line, err.column), (1, 17));
+        assert_eq!(err.kind, ScriptParseErrorKind::InvalidInteger("abc".to_string()));
+
+        let err = parse_err("PushInt(1, 2)");
+        assert_eq!((err.line, err.column), (1, 12));
+        assert!(matches!(err.kind, ScriptParseErrorKind::WrongArgumentCount {
+            expected: 1,
+            actual: 2,
+            ..
+        }));
+
+        let err = parse_err("Dup(1)");
+        assert!(matches!(err.kind, ScriptParseErrorKind::WrongArgumentCount {
+            expected: 0,
+            actual: 1,
+            ..
+        }));
+
+        let err = parse_err("PushHash(abcd)");
+        assert_eq!((err.line, err.column), (1, 10));
+        assert!(matches!(err.kind, ScriptParseErrorKind::InvalidHex {
+            expected_bytes: 32,
+            ..
+        }));
+
+        let err = parse_err("CheckHeight(100");
+        assert_eq!((err.line, err.column), (1, 16));
+        assert_eq!(err.kind, ScriptParseErrorKind::UnexpectedEnd("`,` or `)`"));
+
+        let err = parse_err("Dup; Drop");
+        assert_eq!((err.line, err.column), (1, 4));
+        assert_eq!(err.kind, ScriptParseErrorKind::UnexpectedCharacter(';'));
+
+        let err = parse_err(&format!("CheckMultiSig(1, 3, [{}, {}], {})", KEY, KEY2, MSG));
+        assert_eq!((err.line, err.column), (1, 18));
+        assert_eq!(err.kind, ScriptParseErrorKind::MultisigKeyCountMismatch {
+            declared: 3,
+            listed: 2
+        });
+
+        let err = parse_err(&format!("CheckMultiSig(1, 33, [], {})", MSG));
+        assert_eq!((err.line, err.column), (1, 15));
+        assert_eq!(err.kind, ScriptParseErrorKind::InvalidMultisigThreshold {
+            m: 1,
+            n: 33,
+            max: MAX_MULTISIG_LIMIT
+        });
+        let err = parse_err(&format!("CheckMultiSigVerify(0, 1, [{}], {})", KEY, MSG));
+        assert!(matches!(err.kind, ScriptParseErrorKind::InvalidMultisigThreshold {
+            m: 0,
+            ..
+        }));
+
+        let err = parse_err(&"Nop ".repeat(MAX_SCRIPT_OPCODES + 1));
+        assert_eq!((err.line, err.column), (1, MAX_SCRIPT_OPCODES * 4 + 1));
+        assert_eq!(err.kind, ScriptParseErrorKind::TooManyOpcodes(MAX_SCRIPT_OPCODES));
+
+        assert!(Opcode::from_str("Dup Drop").is_err());
+    }
+
+    #[test]
+    fn it_rejects_scripts_larger_than_the_maximum_size() {
+        // Each `PushPubKey` takes 33 bytes, 124 of them fit in a script
+        let push_key = format!("PushPubKey({}) ", KEY);
+        let script = TariScript::from_str(&push_key.repeat(124)).unwrap();
+        assert!(script.to_bytes().len() <= MAX_SCRIPT_BYTES);
+
+        let err = parse_err(&push_key.repeat(125));
+        assert_eq!((err.line, err.column), (1, 124 * push_key.len() + 1));
+        assert_eq!(err.kind, ScriptParseErrorKind::ScriptTooLarge(MAX_SCRIPT_BYTES));
+    }
+
+    #[test]
+    fn it_pretty_prints_branches() {
+        let script = TariScript::from_str("PushOne IfThen PushZero IfThen Nop EndIf Else PushOne EndIf").unwrap();
+        assert_eq!(
+            script.to_pretty_string(),
+            "PushOne\nIfThen\n    PushZero\n    IfThen\n        Nop\n    EndIf\nElse\n    PushOne\nEndIf\n"
+        );
+    }
+}
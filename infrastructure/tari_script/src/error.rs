@@ -73,3 +73,45 @@ impl From<ByteArrayError> for ScriptError {
         ScriptError::InvalidData
     }
 }
+
+/// An error encountered while assembling a script from its textual representation. The line and column (both starting
+/// at 1) locate the start of the offending token.
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+#[error("{kind} at line {line}, column {column}")]
+pub struct ScriptParseError {
+    pub line: usize,
+    pub column: usize,
+    pub kind: ScriptParseErrorKind,
+}
+
+#[derive(Debug, Clone, Error, PartialEq, Eq)]
+pub enum ScriptParseErrorKind {
+    #[error("Unknown opcode `{0}`")]
+    UnknownOpcode(String),
+    #[error("Unexpected character `{0}`")]
+    UnexpectedCharacter(char),
+    #[error("Unexpected end of input, expected {0}")]
+    UnexpectedEnd(&'static str),
+    #[error("Expected {expected}, found `{found}`")]
+    UnexpectedToken { expected: &'static str, found: String },
+    #[error("`{opcode}` takes {expected} argument(s) but {actual} were given")]
+    WrongArgumentCount {
+        opcode: String,
+        expected: usize,
+        actual: usize,
+    },
+    #[error("Invalid integer `{0}`")]
+    InvalidInteger(String),
+    #[error("Invalid hex value `{value}`, expected {expected_bytes} hex encoded bytes")]
+    InvalidHex { value: String, expected_bytes: usize },
+    #[error("Invalid public key `{0}`")]
+    InvalidPublicKey(String),
+    #[error("Multisig declares {declared} public key(s) but lists {listed}")]
+    MultisigKeyCountMismatch { declared: u8, listed: usize },
+    #[error("Multisig requires 0 < m <= n <= {max}, found m = {m} and n = {n}")]
+    InvalidMultisigThreshold { m: u8, n: u8, max: u8 },
+    #[error("The script exceeds the maximum of {0} opcodes")]
+    TooManyOpcodes(usize),
+    #[error("The script exceeds the maximum of {0} bytes")]
+    ScriptTooLarge(usize),
+}
@@ -15,14 +15,20 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
+mod analyzer;
+mod assembler;
 mod error;
+mod mast;
 mod op_codes;
 mod script;
 mod script_context;
 mod serde;
 mod stack;
+mod trace;
 
-pub use error::ScriptError;
+pub use analyzer::{ExecutionPath, PathFailure, ScriptAnalysis, StackItemTypes, UnreachableBranch, MAX_ANALYZED_PATHS};
+pub use error::{ScriptError, ScriptParseError, ScriptParseErrorKind};
+pub use mast::{mast_leaf_hash, MastHasher, MastTree, MAX_MAST_LEAVES};
 pub use op_codes::{
     slice_to_boxed_hash,
     slice_to_boxed_message,
@@ -33,7 +39,7 @@ pub use op_codes::{
     OpcodeVersion,
     ScalarValue,
 };
-pub use script::{ScriptOpcodes, TariScript};
+pub use script::{ScriptOpcodes, TariScript, MAX_MULTISIG_LIMIT};
 pub use script_context::ScriptContext;
 pub use stack::{ExecutionStack, StackItem};
 use tari_crypto::{
@@ -41,6 +47,7 @@ use tari_crypto::{
     ristretto::{RistrettoPublicKey, RistrettoSecretKey},
     signatures::SchnorrSignature,
 };
+pub use trace::{BranchState, BranchTrace, ExecutionTrace, TraceStep};
 
 hash_domain!(CheckSigHashDomain, "com.tari.script.check_sig", 1);
 
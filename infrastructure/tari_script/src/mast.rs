@@ -0,0 +1,213 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+//! Merkleized alternative scripts (MAST).
+//!
+//! Instead of a single script with an `IfThen` statement per spend path, an output can be locked with
+//! `ExecuteMast(<root>)`, where `root` is the root of a balanced binary merkle tree of alternative scripts. The spender
+//! reveals only the script they execute, along with a merkle proof that it is part of the tree:
+//!
+//! ```text
+//! <inputs of the revealed script> <proof hashes> <number of proof hashes> <node index> <revealed script>
+//! ```
+//!
+//! [MastTree::spend_inputs] builds this input stack.
+
+use blake2::Blake2b;
+use digest::consts::U32;
+use tari_crypto::{hash_domain, hashing::DomainSeparatedHasher};
+use tari_mmr::{BalancedBinaryMerkleProof, BalancedBinaryMerkleTree};
+
+use crate::{op_codes::slice_to_hash, ExecutionStack, HashValue, Opcode, ScriptError, StackItem, TariScript};
+
+hash_domain!(MastHashDomain, "com.tari.script.mast", 0);
+
+/// The hasher of the nodes of a MAST. Leaves are hashed with a different label, see [mast_leaf_hash].
+pub type MastHasher = DomainSeparatedHasher<Blake2b<U32>, MastHashDomain>;
+
+/// The maximum number of alternative scripts in a MAST
+pub const MAX_MAST_LEAVES: usize = 256;
+
+const LEAF_LABEL: &str = "leaf";
+
+/// The hash of a script as a leaf of a MAST
+pub fn mast_leaf_hash(script: &TariScript) -> HashValue {
+    let hash = MastHasher::new_with_label(LEAF_LABEL)
+        .chain(script.to_bytes())
+        .finalize();
+    slice_to_hash(hash.as_ref())
+}
+
+/// Verifies that `script` is the leaf at `node_index` of the MAST with the given root
+pub(crate) fn verify_mast_proof(root: &HashValue, script: &TariScript, node_index: u32, path: Vec<HashValue>) -> bool {
+    let path = path.into_iter().map(|hash| hash.to_vec()).collect();
+    BalancedBinaryMerkleProof::<MastHasher>::from_path(path, node_index)
+        .verify(&root.to_vec(), mast_leaf_hash(script).to_vec())
+}
+
+fn contains_execute_mast(script: &TariScript) -> bool {
+    script
+        .as_slice()
+        .iter()
+        .any(|opcode| matches!(opcode, Opcode::ExecuteMast(_)))
+}
+
+/// A set of alternative scripts that an output can be locked with, of which the spender executes one
+pub struct MastTree {
+    scripts: Vec<TariScript>,
+    tree: BalancedBinaryMerkleTree<MastHasher>,
+}
+
+impl MastTree {
+    /// Creates the tree of the given scripts, in order. Fails with `ValueExceedsBounds` if there are no scripts or
+    /// more than [MAX_MAST_LEAVES], and with `InvalidOpcode` if a script contains `ExecuteMast`.
+    pub fn new(scripts: Vec<TariScript>) -> Result<Self, ScriptError> {
+        if scripts.is_empty() || scripts.len() > MAX_MAST_LEAVES {
+            return Err(ScriptError::ValueExceedsBounds);
+        }
+        if scripts.iter().any(contains_execute_mast) {
+            return Err(ScriptError::InvalidOpcode);
+        }
+        let leaves = scripts.iter().map(|script| mast_leaf_hash(script).to_vec()).collect();
+        Ok(Self {
+            scripts,
+            tree: BalancedBinaryMerkleTree::create(leaves),
+        })
+    }
+
+    pub fn root(&self) -> HashValue {
+        slice_to_hash(&self.tree.get_merkle_root())
+    }
+
+    /// The script to lock an output with
+    pub fn locking_script(&self) -> TariScript {
+        crate::script!(ExecuteMast(Box::new(self.root()))).expect("a single opcode is a valid script")
+    }
+
+    pub fn scripts(&self) -> &[TariScript] {
+        &self.scripts
+    }
+
+    /// The input stack that executes the script at `index`, given the input stack of that script
+    pub fn spend_inputs(&self, index: usize, script_inputs: &ExecutionStack) -> Result<ExecutionStack, ScriptError> {
+        let script = self.scripts.get(index).ok_or(ScriptError::ValueExceedsBounds)?;
+        let proof = BalancedBinaryMerkleProof::generate_proof(&self.tree, index)
+            .map_err(|_| ScriptError::ValueExceedsBounds)?;
+
+        let mut items = script_inputs.as_slice().to_vec();
+        items.extend(
+            proof
+                .path()
+                .iter()
+                .rev()
+                .map(|hash| StackItem::Hash(slice_to_hash(hash))),
+        );
+        items.push(StackItem::Number(i64::try_from(proof.path().len())?));
+        items.push(StackItem::Number(i64::from(proof.node_index())));
+        items.push(StackItem::Script(script.clone()));
+        Ok(ExecutionStack::new(items))
+    }
+}
+
+/// Pops a revealed script and its merkle proof, verifies it against `root` and returns the script
+pub(crate) fn pop_mast_script(root: &HashValue, stack: &mut ExecutionStack) -> Result<TariScript, ScriptError> {
+    let script = match stack.pop().ok_or(ScriptError::StackUnderflow)? {
+        StackItem::Script(script) => script,
+        _ => return Err(ScriptError::IncompatibleTypes),
+    };
+    let node_index = stack.pop_into_number::<u32>()?;
+    let path_len = stack.pop_into_number::<usize>()?;
+    // Hashes are popped from the top of the stack, so the sibling of the leaf is on top
+    let path = stack
+        .pop_num_items(path_len)?
+        .into_iter()
+        .rev()
+        .map(|item| match item {
+            StackItem::Hash(hash) => Ok(hash),
+            _ => Err(ScriptError::IncompatibleTypes),
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if contains_execute_mast(&script) {
+        return Err(ScriptError::InvalidOpcode);
+    }
+    if !verify_mast_proof(root, &script, node_index, path) {
+        return Err(ScriptError::VerifyFailed);
+    }
+    Ok(script)
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use tari_crypto::ristretto::RistrettoPublicKey;
+    use tari_utilities::hex::Hex;
+
+    use super::*;
+    use crate::{inputs, ScriptContext};
+
+    const KEY: &str = "6c9cb4d3e57351462122310fa22c90b1e6dfb528d64615363d1261a75da3e401";
+
+    fn scripts() -> Vec<TariScript> {
+        vec![
+            TariScript::from_str(&format!("CheckHeightVerify(100) PushPubKey({})", KEY)).unwrap(),
+            TariScript::from_str("PushInt(2) Add PushInt(5) EqualVerify PushOne").unwrap(),
+            TariScript::from_str("Return").unwrap(),
+        ]
+    }
+
+    #[test]
+    fn it_executes_each_leaf() {
+        let tree = MastTree::new(scripts()).unwrap();
+        let script = tree.locking_script();
+        let context = ScriptContext::new(100, &[0; 32], &Default::default());
+
+        let inputs = tree.spend_inputs(0, &ExecutionStack::default()).unwrap();
+        assert_eq!(
+            script.execute_with_context(&inputs, &context).unwrap(),
+            StackItem::PublicKey(RistrettoPublicKey::from_hex(KEY).unwrap())
+        );
+
+        let inputs = tree.spend_inputs(1, &inputs!(3)).unwrap();
+        assert_eq!(script.execute(&inputs).unwrap(), StackItem::Number(1));
+        let inputs = tree.spend_inputs(1, &inputs!(4)).unwrap();
+        assert_eq!(script.execute(&inputs).unwrap_err(), ScriptError::VerifyFailed);
+
+        let inputs = tree.spend_inputs(2, &ExecutionStack::default()).unwrap();
+        assert_eq!(script.execute(&inputs).unwrap_err(), ScriptError::Return);
+
+        assert!(tree.spend_inputs(3, &ExecutionStack::default()).is_err());
+    }
+
+    #[test]
+    fn it_rejects_scripts_that_are_not_in_the_tree() {
+        let tree = MastTree::new(scripts()).unwrap();
+        let mut items = tree
+            .spend_inputs(0, &ExecutionStack::default())
+            .unwrap()
+            .as_slice()
+            .to_vec();
+        *items.last_mut().unwrap() = StackItem::Script(TariScript::from_str("PushOne").unwrap());
+        let err = tree.locking_script().execute(&ExecutionStack::new(items)).unwrap_err();
+        assert_eq!(err, ScriptError::VerifyFailed);
+    }
+
+    #[test]
+    fn it_handles_a_single_leaf() {
+        let script = TariScript::from_str("PushOne").unwrap();
+        let tree = MastTree::new(vec![script]).unwrap();
+        assert_eq!(tree.root(), mast_leaf_hash(&tree.scripts()[0]));
+        let inputs = tree.spend_inputs(0, &ExecutionStack::default()).unwrap();
+        assert_eq!(tree.locking_script().execute(&inputs).unwrap(), StackItem::Number(1));
+    }
+
+    #[test]
+    fn it_rejects_nested_trees() {
+        assert!(MastTree::new(vec![]).is_err());
+        let tree = MastTree::new(scripts()).unwrap();
+        assert_eq!(
+            MastTree::new(vec![tree.locking_script()]).unwrap_err(),
+            ScriptError::InvalidOpcode
+        );
+    }
+}
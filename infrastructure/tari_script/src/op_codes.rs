@@ -83,6 +83,7 @@ const OP_CHECK_HEIGHT_VERIFY: u8 = 0x66;
 const OP_CHECK_HEIGHT: u8 = 0x67;
 const OP_COMPARE_HEIGHT_VERIFY: u8 = 0x68;
 const OP_COMPARE_HEIGHT: u8 = 0x69;
+const OP_CHECK_RELATIVE_HEIGHT_VERIFY: u8 = 0x6a;
 
 // Opcode constants: Stack Manipulation
 const OP_DROP: u8 = 0x70;
@@ -125,6 +126,7 @@ const OP_RETURN: u8 = 0x60;
 const OP_IF_THEN: u8 = 0x61;
 const OP_ELSE: u8 = 0x62;
 const OP_END_IF: u8 = 0x63;
+const OP_EXECUTE_MAST: u8 = 0x6b;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Opcode {
@@ -148,6 +150,10 @@ pub enum Opcode {
     /// current height. Fails with `InvalidInput` if there is not a valid integer value on top of the stack. Fails
     /// with `StackUnderflow` if the stack is empty.
     CompareHeight,
+    /// Compares the number of blocks mined since the output being spent was mined to `depth`. Fails with
+    /// `VerifyFailed` if the current block height - the mined height of the output < `depth`. The check is skipped
+    /// if the mined height is not known, see `ScriptContext::with_mined_height`. Introduced in `OpcodeVersion::V1`.
+    CheckRelativeHeightVerify(u64),
 
     // Stack Manipulation
     /// No op. Does nothing. Never fails.
@@ -283,6 +289,15 @@ pub enum Opcode {
     Else,
     /// Marks the end of the `IfThen` statement.
     EndIf,
+    /// Executes one of the alternative scripts committed to by the merkle root `root`, see `MastTree`. Pops the
+    /// revealed script, then the index of its node in the tree, then the number of hashes in the merkle proof `n` and
+    /// then `n` proof hashes (the sibling of the leaf first). The revealed script is then executed with the rest of
+    /// the stack as its input, and the stack is replaced by the result.
+    /// Fails with `StackUnderflow` if the stack has too few items. Fails with `IncompatibleTypes` if the items are not
+    /// a script, two numbers and `n` hashes. Fails with `VerifyFailed` if the merkle proof is invalid. Fails with
+    /// `InvalidOpcode` if the revealed script contains `ExecuteMast`. Fails with the error of the revealed script if
+    /// it fails. Introduced in `OpcodeVersion::V1`.
+    ExecuteMast(Box<HashValue>),
 }
 
 impl Opcode {
@@ -324,6 +339,7 @@ impl Opcode {
             Opcode::IfThen |
             Opcode::Else |
             Opcode::EndIf => OpcodeVersion::V0,
+            Opcode::CheckRelativeHeightVerify(..) | Opcode::ExecuteMast(..) => OpcodeVersion::V1,
         }
     }
 
@@ -355,6 +371,10 @@ impl Opcode {
                 let (height, size) = u64::decode_var(&bytes[1..]).ok_or(ScriptError::InvalidData)?;
                 Ok((CheckHeight(height), &bytes[size + 1..]))
             },
+            OP_CHECK_RELATIVE_HEIGHT_VERIFY => {
+                let (depth, size) = u64::decode_var(&bytes[1..]).ok_or(ScriptError::InvalidData)?;
+                Ok((CheckRelativeHeightVerify(depth), &bytes[size + 1..]))
+            },
             OP_COMPARE_HEIGHT_VERIFY => Ok((CompareHeightVerify, &bytes[1..])),
             OP_COMPARE_HEIGHT => Ok((CompareHeight, &bytes[1..])),
             OP_NOP => Ok((Nop, &bytes[1..])),
@@ -437,6 +457,13 @@ impl Opcode {
             OP_IF_THEN => Ok((IfThen, &bytes[1..])),
             OP_ELSE => Ok((Else, &bytes[1..])),
             OP_END_IF => Ok((EndIf, &bytes[1..])),
+            OP_EXECUTE_MAST => {
+                if bytes.len() < 33 {
+                    return Err(ScriptError::InvalidData);
+                }
+                let root = slice_to_boxed_hash(&bytes[1..33]);
+                Ok((ExecuteMast(root), &bytes[33..]))
+            },
             _ => Err(ScriptError::InvalidOpcode),
         }
     }
@@ -478,6 +505,12 @@ impl Opcode {
                 let used = height.encode_var(&mut buf[..]);
                 array.extend_from_slice(&buf[0..used]);
             },
+            CheckRelativeHeightVerify(depth) => {
+                array.push(OP_CHECK_RELATIVE_HEIGHT_VERIFY);
+                let mut buf = [0u8; 10];
+                let used = depth.encode_var(&mut buf[..]);
+                array.extend_from_slice(&buf[0..used]);
+            },
             CompareHeightVerify => array.push(OP_COMPARE_HEIGHT_VERIFY),
             CompareHeight => array.push(OP_COMPARE_HEIGHT),
             Nop => array.push(OP_NOP),
@@ -553,6 +586,10 @@ impl Opcode {
             IfThen => array.push(OP_IF_THEN),
             Else => array.push(OP_ELSE),
             EndIf => array.push(OP_END_IF),
+            ExecuteMast(root) => {
+                array.push(OP_EXECUTE_MAST);
+                array.extend_from_slice(root.deref());
+            },
         };
 
         &array[n..]
@@ -566,6 +603,7 @@ impl fmt::Display for Opcode {
         match self {
             CheckHeightVerify(height) => write!(fmt, "CheckHeightVerify({})", *height),
             CheckHeight(height) => write!(fmt, "CheckHeight({})", *height),
+            CheckRelativeHeightVerify(depth) => write!(fmt, "CheckRelativeHeightVerify({})", *depth),
             CompareHeightVerify => write!(fmt, "CompareHeightVerify"),
             CompareHeight => write!(fmt, "CompareHeight"),
             Nop => write!(fmt, "Nop"),
@@ -630,6 +668,7 @@ impl fmt::Display for Opcode {
             IfThen => write!(fmt, "IfThen"),
             Else => write!(fmt, "Else"),
             EndIf => write!(fmt, "EndIf"),
+            ExecuteMast(root) => write!(fmt, "ExecuteMast({})", (*root).to_hex()),
         }
     }
 }
@@ -638,6 +677,7 @@ impl fmt::Display for Opcode {
 #[repr(u8)]
 pub enum OpcodeVersion {
     V0 = 0,
+    V1 = 1,
 }
 
 #[cfg(test)]
@@ -691,6 +731,11 @@ mod test {
         }
         test_check_height(&Opcode::CheckHeight(63), 0x67, "CheckHeight(63)");
         test_check_height(&Opcode::CheckHeightVerify(63), 0x66, "CheckHeightVerify(63)");
+        test_check_height(
+            &Opcode::CheckRelativeHeightVerify(63),
+            0x6a,
+            "CheckRelativeHeightVerify(63)",
+        );
     }
 
     #[test]
@@ -35,8 +35,10 @@ use tari_utilities::{
 };
 
 use crate::{
+    mast::pop_mast_script,
     op_codes::Message,
     slice_to_hash,
+    trace::{BranchState, BranchTrace, ExecutionTrace, TraceStep},
     CheckSigSchnorrSignature,
     ExecutionStack,
     HashValue,
@@ -56,11 +58,13 @@ macro_rules! script {
     }}
 }
 
-const MAX_MULTISIG_LIMIT: u8 = 32;
-const MAX_SCRIPT_BYTES: usize = 4096;
+/// The maximum number of public keys in a `CheckMultiSig*` opcode
+pub const MAX_MULTISIG_LIMIT: u8 = 32;
+pub(crate) const MAX_SCRIPT_BYTES: usize = 4096;
+pub(crate) const MAX_SCRIPT_OPCODES: usize = 128;
 
 /// The sized vector of opcodes that make up a script
-pub type ScriptOpcodes = MaxSizeVec<Opcode, 128>;
+pub type ScriptOpcodes = MaxSizeVec<Opcode, MAX_SCRIPT_OPCODES>;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct TariScript {
@@ -150,13 +154,62 @@ impl TariScript {
         let mut state = ExecutionState::default();
 
         for opcode in self.script.iter() {
-            if self.should_execute(opcode, &state)? {
-                self.execute_opcode(opcode, &mut stack, context, &mut state)?
-            } else {
-                continue;
+            self.execute_step(opcode, &mut stack, context, &mut state)?;
+        }
+
+        Self::finish_execution(stack, &state)
+    }
+
+    /// Execute the script with the given inputs and the provided context, recording the stack and branch state
+    /// around every opcode. The result is identical to that of [TariScript::execute_with_context]; execution is
+    /// slower, so this is intended for debugging.
+    pub fn execute_with_trace(&self, inputs: &ExecutionStack, context: &ScriptContext) -> ExecutionTrace {
+        let mut stack = inputs.clone();
+        let mut state = ExecutionState::default();
+        let mut steps = Vec::with_capacity(self.script.len());
+
+        for (index, opcode) in self.script.iter().enumerate() {
+            let stack_before = stack.as_slice().to_vec();
+            let executed = state.is_executing_clause(opcode);
+            let error = self.execute_step(opcode, &mut stack, context, &mut state).err();
+            steps.push(TraceStep {
+                index,
+                opcode: opcode.clone(),
+                executed,
+                stack_before,
+                stack_after: stack.as_slice().to_vec(),
+                branches: state.branch_traces(),
+                error: error.clone(),
+            });
+            if let Some(err) = error {
+                return ExecutionTrace {
+                    steps,
+                    result: Err(err),
+                };
             }
         }
 
+        ExecutionTrace {
+            steps,
+            result: Self::finish_execution(stack, &state),
+        }
+    }
+
+    /// Executes the opcode if it is in a branch that is being executed, or if it changes the branch state
+    fn execute_step(
+        &self,
+        opcode: &Opcode,
+        stack: &mut ExecutionStack,
+        context: &ScriptContext,
+        state: &mut ExecutionState,
+    ) -> Result<(), ScriptError> {
+        if self.should_execute(opcode, state)? {
+            self.execute_opcode(opcode, stack, context, state)?;
+        }
+        Ok(())
+    }
+
+    fn finish_execution(mut stack: ExecutionStack, state: &ExecutionState) -> Result<StackItem, ScriptError> {
         // the script has finished but there was an open IfThen or Else!
         if !state.if_stack.is_empty() {
             return Err(ScriptError::MissingOpcode);
@@ -242,6 +295,28 @@ impl TariScript {
         self.script.iter().map(|op| op.to_string()).collect()
     }
 
+    /// Formats the script in the canonical assembly syntax, with one opcode per line and the bodies of `IfThen` and
+    /// `Else` branches indented. The result can be parsed back into the same script using `TariScript::from_str`.
+    pub fn to_pretty_string(&self) -> String {
+        let mut depth = 0usize;
+        let mut s = String::new();
+        for opcode in self.script.iter() {
+            let indent = match opcode {
+                Opcode::Else | Opcode::EndIf => depth.saturating_sub(1),
+                _ => depth,
+            };
+            s.push_str(&"    ".repeat(indent));
+            s.push_str(&opcode.to_string());
+            s.push('\n');
+            match opcode {
+                Opcode::IfThen => depth += 1,
+                Opcode::EndIf => depth = depth.saturating_sub(1),
+                _ => {},
+            }
+        }
+        s
+    }
+
     // pending updates to Dalek/Digest
     fn execute_opcode(
         &self,
@@ -256,6 +331,7 @@ impl TariScript {
         match opcode {
             CheckHeightVerify(height) => TariScript::handle_check_height_verify(*height, ctx.block_height()),
             CheckHeight(height) => TariScript::handle_check_height(stack, *height, ctx.block_height()),
+            CheckRelativeHeightVerify(depth) => TariScript::handle_check_relative_height_verify(*depth, ctx),
             CompareHeightVerify => TariScript::handle_compare_height_verify(stack, ctx.block_height()),
             CompareHeight => TariScript::handle_compare_height(stack, ctx.block_height()),
             Nop => Ok(()),
@@ -332,6 +408,7 @@ impl TariScript {
             IfThen => TariScript::handle_if_then(stack, state),
             Else => TariScript::handle_else(state),
             EndIf => TariScript::handle_end_if(state),
+            ExecuteMast(root) => TariScript::handle_execute_mast(root, stack, ctx),
         }
     }
 
@@ -343,6 +420,28 @@ impl TariScript {
         }
     }
 
+    fn handle_check_relative_height_verify(depth: u64, ctx: &ScriptContext) -> Result<(), ScriptError> {
+        match ctx.mined_height() {
+            Some(mined_height) if ctx.block_height().saturating_sub(mined_height) < depth => {
+                Err(ScriptError::VerifyFailed)
+            },
+            _ => Ok(()),
+        }
+    }
+
+    /// Verifies the revealed script against the MAST root and executes it on the remainder of the stack, which is
+    /// replaced by the result.
+    fn handle_execute_mast(
+        root: &HashValue,
+        stack: &mut ExecutionStack,
+        ctx: &ScriptContext,
+    ) -> Result<(), ScriptError> {
+        let script = pop_mast_script(root, stack)?;
+        let result = script.execute_with_context(stack, ctx)?;
+        *stack = ExecutionStack::new(vec![result]);
+        Ok(())
+    }
+
     fn handle_check_height(stack: &mut ExecutionStack, height: u64, block_height: u64) -> Result<(), ScriptError> {
         let height = i64::try_from(height)?;
         let block_height = i64::try_from(block_height)?;
@@ -421,39 +520,19 @@ impl TariScript {
         if state.executing {
             let pred = stack.pop().ok_or(ScriptError::StackUnderflow)?;
             match pred {
-                StackItem::Number(1) => {
-                    // continue execution until Else opcode
-                    state.executing = true;
-                    let if_state = IfState {
-                        branch: Branch::ExecuteIf,
-                        else_expected: true,
-                    };
-                    state.if_stack.push(if_state);
-                    Ok(())
-                },
-                StackItem::Number(0) => {
-                    // skip execution until Else opcode
-                    state.executing = false;
-                    let if_state = IfState {
-                        branch: Branch::ExecuteElse,
-                        else_expected: true,
-                    };
-                    state.if_stack.push(if_state);
-                    Ok(())
-                },
-                _ => Err(ScriptError::InvalidInput),
+                // continue execution until Else opcode
+                StackItem::Number(1) => state.open_if_then(Some(true)),
+                // skip execution until Else opcode
+                StackItem::Number(0) => state.open_if_then(Some(false)),
+                _ => return Err(ScriptError::InvalidInput),
             }
         } else {
-            let if_state = IfState {
-                branch: Branch::NotExecuted,
-                else_expected: true,
-            };
-            state.if_stack.push(if_state);
-            Ok(())
+            state.open_if_then(None);
         }
+        Ok(())
     }
 
-    fn handle_else(state: &mut ExecutionState) -> Result<(), ScriptError> {
+    pub(crate) fn handle_else(state: &mut ExecutionState) -> Result<(), ScriptError> {
         let if_state = state.if_stack.last_mut().ok_or(ScriptError::InvalidOpcode)?;
 
         // check to make sure Else is expected
@@ -476,7 +555,7 @@ impl TariScript {
         Ok(())
     }
 
-    fn handle_end_if(state: &mut ExecutionState) -> Result<(), ScriptError> {
+    pub(crate) fn handle_end_if(state: &mut ExecutionState) -> Result<(), ScriptError> {
         // check to make sure EndIf is expected
         let if_state = state.if_stack.pop().ok_or(ScriptError::InvalidOpcode)?;
 
@@ -709,15 +788,15 @@ enum Branch {
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct IfState {
+pub(crate) struct IfState {
     branch: Branch,
     else_expected: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
-struct ExecutionState {
-    executing: bool,
-    if_stack: Vec<IfState>,
+pub(crate) struct ExecutionState {
+    pub(crate) executing: bool,
+    pub(crate) if_stack: Vec<IfState>,
 }
 
 impl Default for ExecutionState {
@@ -729,6 +808,49 @@ impl Default for ExecutionState {
     }
 }
 
+impl ExecutionState {
+    /// Opens an `IfThen` statement given its predicate, or `None` if the statement is in a clause that is not
+    /// executed.
+    pub(crate) fn open_if_then(&mut self, predicate: Option<bool>) {
+        let branch = match predicate {
+            Some(true) => Branch::ExecuteIf,
+            Some(false) => Branch::ExecuteElse,
+            None => Branch::NotExecuted,
+        };
+        self.executing = branch == Branch::ExecuteIf;
+        self.if_stack.push(IfState {
+            branch,
+            else_expected: true,
+        });
+    }
+
+    /// Whether the opcode is part of a clause that is executed. `Else` and `EndIf` belong to their statement, rather
+    /// than to the clause they close.
+    pub(crate) fn is_executing_clause(&self, opcode: &Opcode) -> bool {
+        match opcode {
+            Opcode::Else | Opcode::EndIf => self
+                .if_stack
+                .last()
+                .map_or(self.executing, |if_state| if_state.branch != Branch::NotExecuted),
+            _ => self.executing,
+        }
+    }
+
+    fn branch_traces(&self) -> Vec<BranchTrace> {
+        self.if_stack
+            .iter()
+            .map(|if_state| BranchTrace {
+                state: match if_state.branch {
+                    Branch::NotExecuted => BranchState::Skipped,
+                    Branch::ExecuteIf => BranchState::IfTaken,
+                    Branch::ExecuteElse => BranchState::ElseTaken,
+                },
+                in_else: !if_state.else_expected,
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod test {
     use blake2::Blake2b;
@@ -746,8 +868,11 @@ mod test {
         error::ScriptError,
         inputs,
         op_codes::{slice_to_boxed_hash, slice_to_boxed_message, HashValue, Message},
+        BranchState,
+        BranchTrace,
         CheckSigSchnorrSignature,
         ExecutionStack,
+        Opcode,
         Opcode::CheckMultiSigVerifyAggregatePubKey,
         ScriptContext,
         StackItem,
@@ -940,6 +1065,28 @@ mod test {
         }
     }
 
+    #[test]
+    fn op_check_relative_height() {
+        let script = script!(CheckRelativeHeightVerify(10)).unwrap();
+        let inputs = inputs!(1);
+
+        for block_height in 100..110 {
+            let ctx = context_with_height(block_height).with_mined_height(100);
+            let err = script.execute_with_context(&inputs, &ctx).unwrap_err();
+            assert!(matches!(err, ScriptError::VerifyFailed));
+        }
+
+        for block_height in 110..=120 {
+            let ctx = context_with_height(block_height).with_mined_height(100);
+            let result = script.execute_with_context(&inputs, &ctx).unwrap();
+            assert_eq!(result, Number(1));
+        }
+
+        // The check is deferred if the mined height is unknown
+        let result = script.execute_with_context(&inputs, &context_with_height(0)).unwrap();
+        assert_eq!(result, Number(1));
+    }
+
     #[test]
     fn op_compare_height() {
         let script = script!(CompareHeight).unwrap();
@@ -1885,4 +2032,63 @@ mod test {
         assert!(stack_item.is_ok());
         assert_eq!(stack_item.unwrap(), Number(-76))
     }
+
+    #[test]
+    fn execute_with_trace() {
+        let script =
+            script!(IfThen PushOne IfThen PushInt(420) Else PushInt(555) EndIf Else PushInt(66) EndIf).unwrap();
+        let inputs = inputs!(1);
+        let trace = script.execute_with_trace(&inputs, &ScriptContext::default());
+        assert_eq!(trace.result, script.execute(&inputs));
+        assert_eq!(trace.result, Ok(Number(420)));
+        assert_eq!(trace.steps.len(), script.size());
+        assert!(trace.failed_step().is_none());
+
+        let first = &trace.steps[0];
+        assert!(first.executed);
+        assert_eq!(first.stack_before, vec![Number(1)]);
+        assert!(first.stack_after.is_empty());
+        assert_eq!(first.branches, vec![BranchTrace {
+            state: BranchState::IfTaken,
+            in_else: false
+        }]);
+
+        // Else PushInt(555) of the inner statement
+        assert!(trace.steps[4].executed);
+        assert!(!trace.steps[5].executed);
+        assert_eq!(trace.steps[5].branches[1], BranchTrace {
+            state: BranchState::IfTaken,
+            in_else: true
+        });
+        assert!(!trace.steps[8].executed);
+        assert!(trace.steps[9].branches.is_empty());
+
+        let inputs = inputs!(0);
+        let trace = script.execute_with_trace(&inputs, &ScriptContext::default());
+        assert_eq!(trace.result, Ok(Number(66)));
+        // The inner statement is not executed at all
+        assert_eq!(trace.steps[2].branches[1].state, BranchState::Skipped);
+        assert!(!trace.steps[2].executed);
+    }
+
+    #[test]
+    fn execute_with_trace_failure() {
+        let script = script!(PushOne PushZero EqualVerify PushOne).unwrap();
+        let inputs = ExecutionStack::default();
+        let trace = script.execute_with_trace(&inputs, &ScriptContext::default());
+        assert_eq!(trace.result, Err(ScriptError::VerifyFailed));
+        assert_eq!(trace.steps.len(), 3);
+        let failed = trace.failed_step().unwrap();
+        assert_eq!(failed.index, 2);
+        assert_eq!(failed.opcode, Opcode::EqualVerify);
+        assert_eq!(failed.stack_before, vec![Number(1), Number(0)]);
+        assert!(trace.to_string().ends_with(
+            "Result: failed at #2 EqualVerify: A verification opcode failed, aborting the script immediately"
+        ));
+
+        let script = script!(PushOne PushOne).unwrap();
+        let trace = script.execute_with_trace(&inputs, &ScriptContext::default());
+        assert_eq!(trace.result, Err(ScriptError::NonUnitLengthStack));
+        assert!(trace.failed_step().is_none());
+    }
 }
@@ -29,6 +29,9 @@ pub struct ScriptContext {
     prev_block_hash: HashValue,
     /// The commitment of the UTXO that is attached to this script
     commitment: PedersenCommitment,
+    /// The height of the block the UTXO was mined in, if known. Validation that does not have access to the chain
+    /// leaves this unset, which skips relative height checks, and must be followed by validation that sets it.
+    mined_height: Option<u64>,
 }
 
 impl ScriptContext {
@@ -37,9 +40,17 @@ impl ScriptContext {
             block_height: height,
             prev_block_hash: *prev_hash,
             commitment: com.clone(),
+            mined_height: None,
         }
     }
 
+    /// Sets the height of the block the UTXO was mined in, which is required by relative height checks
+    #[must_use]
+    pub fn with_mined_height(mut self, mined_height: u64) -> Self {
+        self.mined_height = Some(mined_height);
+        self
+    }
+
     pub fn block_height(&self) -> u64 {
         self.block_height
     }
@@ -51,4 +62,8 @@ impl ScriptContext {
     pub fn commitment(&self) -> &PedersenCommitment {
         &self.commitment
     }
+
+    pub fn mined_height(&self) -> Option<u64> {
+        self.mined_height
+    }
 }
@@ -15,10 +15,10 @@
 // WHETHER IN CONTRACT, STRICT LIABILITY, OR TORT (INCLUDING NEGLIGENCE OR OTHERWISE) ARISING IN ANY WAY OUT OF THE
 // USE OF THIS SOFTWARE, EVEN IF ADVISED OF THE POSSIBILITY OF SUCH DAMAGE.
 
-use std::io;
+use std::{fmt, io};
 
 use borsh::{BorshDeserialize, BorshSerialize};
-use integer_encoding::{VarIntReader, VarIntWriter};
+use integer_encoding::{VarInt, VarIntReader, VarIntWriter};
 use tari_crypto::ristretto::{pedersen::PedersenCommitment, RistrettoPublicKey, RistrettoSecretKey};
 use tari_utilities::{
     hex::{from_hex, to_hex, Hex, HexError},
@@ -27,8 +27,10 @@ use tari_utilities::{
 
 use crate::{
     error::ScriptError,
-    op_codes::{HashValue, ScalarValue},
+    op_codes::{HashValue, OpcodeVersion, ScalarValue},
+    script::MAX_SCRIPT_BYTES,
     CheckSigSchnorrSignature,
+    TariScript,
 };
 
 pub const MAX_STACK_SIZE: usize = 255;
@@ -59,6 +61,7 @@ pub const TYPE_COMMITMENT: u8 = 3;
 pub const TYPE_PUBKEY: u8 = 4;
 pub const TYPE_SIG: u8 = 5;
 pub const TYPE_SCALAR: u8 = 6;
+pub const TYPE_SCRIPT: u8 = 7;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StackItem {
@@ -68,9 +71,25 @@ pub enum StackItem {
     Commitment(PedersenCommitment),
     PublicKey(RistrettoPublicKey),
     Signature(CheckSigSchnorrSignature),
+    /// A script revealed by the spender, e.g. a MAST leaf script executed by `ExecuteMast`
+    Script(TariScript),
 }
 
 impl StackItem {
+    /// The opcode version from which the item may be used. Scripts are only revealed to `ExecuteMast`, so they
+    /// share its version.
+    pub fn get_version(&self) -> OpcodeVersion {
+        match self {
+            StackItem::Number(_) |
+            StackItem::Hash(_) |
+            StackItem::Scalar(_) |
+            StackItem::Commitment(_) |
+            StackItem::PublicKey(_) |
+            StackItem::Signature(_) => OpcodeVersion::V0,
+            StackItem::Script(_) => OpcodeVersion::V1,
+        }
+    }
+
     /// Convert an input item into its binary representation and append it to the array. The function returns the byte
     /// slice that matches the item as a convenience
     pub fn to_bytes<'a>(&self, array: &'a mut Vec<u8>) -> &'a [u8] {
@@ -101,6 +120,14 @@ impl StackItem {
                 array.push(TYPE_SCALAR);
                 array.extend_from_slice(scalar);
             },
+            StackItem::Script(script) => {
+                let bytes = script.to_bytes();
+                array.push(TYPE_SCRIPT);
+                let mut buf = [0u8; 10];
+                let used = (bytes.len() as u64).encode_var(&mut buf[..]);
+                array.extend_from_slice(&buf[0..used]);
+                array.extend_from_slice(&bytes);
+            },
         };
         &array[n..]
     }
@@ -116,6 +143,7 @@ impl StackItem {
             TYPE_PUBKEY => StackItem::b_to_pubkey(&bytes[1..]),
             TYPE_SIG => StackItem::b_to_sig(&bytes[1..]),
             TYPE_SCALAR => StackItem::b_to_scalar(&bytes[1..]),
+            TYPE_SCRIPT => StackItem::b_to_script(&bytes[1..]),
             _ => None,
         }
     }
@@ -163,6 +191,16 @@ impl StackItem {
         Some((StackItem::PublicKey(p), &b[32..]))
     }
 
+    fn b_to_script(b: &[u8]) -> Option<(Self, &[u8])> {
+        let (len, size) = u64::decode_var(b)?;
+        let len = usize::try_from(len).ok()?;
+        if len > MAX_SCRIPT_BYTES || b.len() < size + len {
+            return None;
+        }
+        let script = TariScript::from_bytes(&b[size..size + len]).ok()?;
+        Some((StackItem::Script(script), &b[size + len..]))
+    }
+
     fn b_to_sig(b: &[u8]) -> Option<(Self, &[u8])> {
         if b.len() < 64 {
             return None;
@@ -174,11 +212,31 @@ impl StackItem {
     }
 }
 
+impl fmt::Display for StackItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StackItem::Number(n) => write!(f, "Number({})", n),
+            StackItem::Hash(h) => write!(f, "Hash({})", to_hex(h)),
+            StackItem::Scalar(s) => write!(f, "Scalar({})", to_hex(s)),
+            StackItem::Commitment(c) => write!(f, "Commitment({})", c.to_hex()),
+            StackItem::PublicKey(p) => write!(f, "PublicKey({})", p.to_hex()),
+            StackItem::Signature(s) => write!(
+                f,
+                "Signature({}, {})",
+                s.get_public_nonce().to_hex(),
+                s.get_signature().to_hex()
+            ),
+            StackItem::Script(s) => write!(f, "Script({})", s),
+        }
+    }
+}
+
 stack_item_from!(i64 => Number);
 stack_item_from!(PedersenCommitment => Commitment);
 stack_item_from!(RistrettoPublicKey => PublicKey);
 stack_item_from!(CheckSigSchnorrSignature => Signature);
 stack_item_from!(ScalarValue => Scalar);
+stack_item_from!(TariScript => Script);
 
 #[derive(Debug, Default, Clone, PartialEq, Eq)]
 pub struct ExecutionStack {
@@ -237,6 +295,11 @@ impl ExecutionStack {
         self.items.is_empty()
     }
 
+    /// Returns the items in the stack, from the bottom to the top of the stack
+    pub fn as_slice(&self) -> &[StackItem] {
+        &self.items
+    }
+
     /// Pops the top item in the stack. If the stack is not empty, `pop` returns the item, otherwise return `None` if
     /// it is empty.
     pub fn pop(&mut self) -> Option<StackItem> {
@@ -265,7 +328,7 @@ impl ExecutionStack {
 
         // check that all popped items are of the same variant
         // first count each variant
-        let counts = items.iter().fold([0; 6], counter);
+        let counts = items.iter().fold([0; 7], counter);
         // also check the n + 1 item
         let counts = counter(counts, &item);
 
@@ -356,34 +419,38 @@ impl Hex for ExecutionStack {
 
 /// Utility function that given a count of `StackItem` variants, adds 1 for the given item.
 #[allow(clippy::many_single_char_names)]
-fn counter(values: [u8; 6], item: &StackItem) -> [u8; 6] {
-    let [n, h, c, p, s, z] = values;
+fn counter(values: [u8; 7], item: &StackItem) -> [u8; 7] {
+    let [n, h, c, p, s, z, t] = values;
     #[allow(clippy::enum_glob_use)]
     use StackItem::*;
     match item {
         Number(_) => {
             let n = n + 1;
-            [n, h, c, p, s, z]
+            [n, h, c, p, s, z, t]
         },
         Hash(_) => {
             let h = h + 1;
-            [n, h, c, p, s, z]
+            [n, h, c, p, s, z, t]
         },
         Commitment(_) => {
             let c = c + 1;
-            [n, h, c, p, s, z]
+            [n, h, c, p, s, z, t]
         },
         PublicKey(_) => {
             let p = p + 1;
-            [n, h, c, p, s, z]
+            [n, h, c, p, s, z, t]
         },
         Signature(_) => {
             let s = s + 1;
-            [n, h, c, p, s, z]
+            [n, h, c, p, s, z, t]
         },
         Scalar(_) => {
             let z = z + 1;
-            [n, h, c, p, s, z]
+            [n, h, c, p, s, z, t]
+        },
+        Script(_) => {
+            let t = t + 1;
+            [n, h, c, p, s, z, t]
         },
     }
 }
@@ -418,6 +485,18 @@ mod test {
         assert_eq!(stack, stack2);
     }
 
+    #[test]
+    fn script_items_roundtrip() {
+        let script = crate::script!(Dup HashBlake256 PushZero Drop).unwrap();
+        let stack = ExecutionStack::new(vec![StackItem::Number(1), StackItem::Script(script)]);
+        let stack2 = ExecutionStack::from_bytes(&stack.to_bytes()).unwrap();
+        assert_eq!(stack, stack2);
+        // A script item whose length exceeds the bytes left is rejected
+        let mut bytes = stack.to_bytes();
+        bytes.pop();
+        assert!(ExecutionStack::from_bytes(&bytes).is_err());
+    }
+
     #[test]
     fn deserialisation() {
         let k =
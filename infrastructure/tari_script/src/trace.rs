@@ -0,0 +1,117 @@
+// Copyright 2024 The Tari Project
+// SPDX-License-Identifier: BSD-3-Clause
+
+use std::fmt;
+
+use crate::{Opcode, ScriptError, StackItem};
+
+/// Which part of an `IfThen` statement is executed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BranchState {
+    /// The predicate was 1, so the `IfThen` clause is executed and the `Else` clause is skipped
+    IfTaken,
+    /// The predicate was 0, so the `IfThen` clause is skipped and the `Else` clause is executed
+    ElseTaken,
+    /// The statement is nested in a clause that is skipped, so neither of its clauses are executed
+    Skipped,
+}
+
+/// The state of an open `IfThen` statement at a point of execution
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BranchTrace {
+    pub state: BranchState,
+    /// True once the `Else` opcode of the statement has been reached
+    pub in_else: bool,
+}
+
+impl fmt::Display for BranchTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let clause = if self.in_else { "Else" } else { "IfThen" };
+        match self.state {
+            BranchState::IfTaken => write!(f, "{} (if taken)", clause),
+            BranchState::ElseTaken => write!(f, "{} (else taken)", clause),
+            BranchState::Skipped => write!(f, "{} (skipped)", clause),
+        }
+    }
+}
+
+/// The effect of a single opcode during script execution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceStep {
+    /// The position of the opcode in the script
+    pub index: usize,
+    pub opcode: Opcode,
+    /// False if the opcode was skipped because it is in a clause that is not executed
+    pub executed: bool,
+    /// The stack before the opcode, from the bottom to the top of the stack
+    pub stack_before: Vec<StackItem>,
+    /// The stack after the opcode, from the bottom to the top of the stack
+    pub stack_after: Vec<StackItem>,
+    /// The open `IfThen` statements after the opcode, from the outermost to the innermost statement
+    pub branches: Vec<BranchTrace>,
+    /// The error raised by the opcode, which aborts execution
+    pub error: Option<ScriptError>,
+}
+
+/// A record of every opcode processed during script execution, as returned by `TariScript::execute_with_trace`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExecutionTrace {
+    pub steps: Vec<TraceStep>,
+    /// The result of the execution, identical to that of `TariScript::execute_with_context`
+    pub result: Result<StackItem, ScriptError>,
+}
+
+impl ExecutionTrace {
+    /// The step that aborted execution, if any
+    pub fn failed_step(&self) -> Option<&TraceStep> {
+        self.steps.iter().find(|step| step.error.is_some())
+    }
+}
+
+fn write_stack(f: &mut fmt::Formatter<'_>, items: &[StackItem]) -> fmt::Result {
+    write!(f, "[")?;
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", item)?;
+    }
+    write!(f, "]")
+}
+
+impl fmt::Display for TraceStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "#{:<3} {}", self.index, self.opcode)?;
+        if !self.executed {
+            return write!(f, " (skipped)");
+        }
+        write!(f, "\n     before: ")?;
+        write_stack(f, &self.stack_before)?;
+        if let Some(err) = &self.error {
+            write!(f, "\n     error:  {}", err)?;
+        } else {
+            write!(f, "\n     after:  ")?;
+            write_stack(f, &self.stack_after)?;
+        }
+        if !self.branches.is_empty() {
+            let branches = self.branches.iter().map(|b| b.to_string()).collect::<Vec<_>>();
+            write!(f, "\n     branch: {}", branches.join(" > "))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for ExecutionTrace {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        match &self.result {
+            Ok(item) => write!(f, "Result: success, {}", item),
+            Err(err) => match self.failed_step() {
+                Some(step) => write!(f, "Result: failed at #{} {}: {}", step.index, step.opcode, err),
+                None => write!(f, "Result: failed after the last opcode: {}", err),
+            },
+        }
+    }
+}
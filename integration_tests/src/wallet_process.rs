@@ -235,6 +235,9 @@ pub fn get_default_cli() -> Cli {
         profile_with_tokio_console: false,
         view_private_key: None,
         spend_key: None,
+        view_key_file: None,
+        restore_backup: None,
+        backup_passphrase: None,
     }
 }
 
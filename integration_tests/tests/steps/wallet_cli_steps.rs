@@ -154,6 +154,8 @@ async fn send_from_cli(world: &mut TariWorld, amount: u64, wallet_a: String, wal
         amount: MicroMinotari(amount),
         destination: wallet_b_address,
         payment_id: format!("Send amount {} from {} to {}", amount, wallet_a, wallet_b),
+        account: None,
+        input_label: None,
     };
     cli.command2 = Some(CliCommands::SendMinotari(args));
 
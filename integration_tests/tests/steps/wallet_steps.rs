@@ -112,7 +112,7 @@ async fn wait_for_wallet_to_have_micro_tari(world: &mut TariWorld, wallet: Strin
     for _ in 0..=num_retries {
         let _result = client.validate_all_transactions(ValidateRequest {}).await;
         curr_amount = client
-            .get_balance(GetBalanceRequest {})
+            .get_balance(GetBalanceRequest::default())
             .await
             .unwrap()
             .into_inner()
@@ -159,7 +159,7 @@ async fn wallet_detects_all_txs_as_mined_status(world: &mut TariWorld, wallet_na
     let mut client = create_wallet_client(world, wallet_name.clone()).await.unwrap();
 
     let mut completed_tx_stream = client
-        .get_completed_transactions(GetCompletedTransactionsRequest {})
+        .get_completed_transactions(GetCompletedTransactionsRequest::default())
         .await
         .unwrap()
         .into_inner();
@@ -493,7 +493,7 @@ async fn list_all_txs_for_wallet(world: &mut TariWorld, transaction_type: String
     }
     let mut client = create_wallet_client(world, wallet.clone()).await.unwrap();
 
-    let request = GetCompletedTransactionsRequest {};
+    let request = GetCompletedTransactionsRequest::default();
     let mut completed_txs = client.get_completed_transactions(request).await.unwrap().into_inner();
 
     while let Some(tx) = completed_txs.next().await {
@@ -542,7 +542,7 @@ async fn wallet_has_at_least_num_txs(world: &mut TariWorld, wallet: String, num_
 
     for _ in 0..num_retries {
         let mut txs = client
-            .get_completed_transactions(grpc::GetCompletedTransactionsRequest {})
+            .get_completed_transactions(grpc::GetCompletedTransactionsRequest::default())
             .await
             .unwrap()
             .into_inner();
@@ -624,7 +624,7 @@ async fn wait_for_wallet_to_have_less_than_micro_tari(world: &mut TariWorld, wal
     println!("Waiting for wallet {} to have less than {} uT", wallet, amount);
 
     let num_retries = 100;
-    let request = GetBalanceRequest {};
+    let request = GetBalanceRequest::default();
 
     for _ in 0..num_retries {
         let balance_res = client.get_balance(request).await.unwrap().into_inner();
@@ -712,6 +712,8 @@ async fn send_amount_from_source_wallet_to_dest_wallet_without_broadcast(
             dest_wallet.as_str()
         ))
         .to_bytes(),
+        account: String::new(),
+        input_label: String::new(),
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
@@ -773,6 +775,8 @@ async fn send_one_sided_transaction_from_source_wallet_to_dest_wallt(
             dest_wallet.as_str()
         ))
         .to_bytes(),
+        account: String::new(),
+        input_label: String::new(),
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
@@ -873,6 +877,8 @@ async fn send_amount_from_wallet_to_wallet_at_fee(
             fee_per_gram
         ))
         .to_bytes(),
+        account: String::new(),
+        input_label: String::new(),
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
@@ -950,7 +956,7 @@ async fn send_amount_from_wallet_to_wallet_at_fee(
 async fn wallet_detects_at_least_coinbase_transactions(world: &mut TariWorld, wallet_name: String, coinbases: u64) {
     let mut client = create_wallet_client(world, wallet_name.clone()).await.unwrap();
     let mut completed_tx_res = client
-        .get_completed_transactions(GetCompletedTransactionsRequest {})
+        .get_completed_transactions(GetCompletedTransactionsRequest::default())
         .await
         .unwrap()
         .into_inner();
@@ -1006,7 +1012,7 @@ async fn wallet_detects_at_least_coinbase_unconfirmed_transactions(
 ) {
     let mut client = create_wallet_client(world, wallet_name.clone()).await.unwrap();
     let mut completed_tx_res = client
-        .get_completed_transactions(GetCompletedTransactionsRequest {})
+        .get_completed_transactions(GetCompletedTransactionsRequest::default())
         .await
         .unwrap()
         .into_inner();
@@ -1221,7 +1227,7 @@ async fn wallets_should_have_at_least_num_spendable_coinbase_outs(
 
         'inner: for _ in 0..num_retries {
             let mut stream = client
-                .get_completed_transactions(GetCompletedTransactionsRequest {})
+                .get_completed_transactions(GetCompletedTransactionsRequest::default())
                 .await
                 .unwrap()
                 .into_inner();
@@ -1303,6 +1309,8 @@ async fn send_num_transactions_to_wallets_at_fee(
                 receiver_wallet.as_str()
             ))
             .to_bytes(),
+            account: String::new(),
+            input_label: String::new(),
         };
         let transfer_req = TransferRequest {
             recipients: vec![payment_recipient],
@@ -1445,6 +1453,8 @@ async fn transfer_tari_from_wallet_to_receiver(world: &mut TariWorld, amount: u6
             receiver.as_str()
         ))
         .to_bytes(),
+        account: String::new(),
+        input_label: String::new(),
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
@@ -1530,7 +1540,7 @@ async fn wallet_has_tari(world: &mut TariWorld, wallet: String, amount: u64) {
     for _ in 0..num_retries {
         let _result = wallet_client.validate_all_transactions(ValidateRequest {}).await;
         let balance_res = wallet_client
-            .get_balance(GetBalanceRequest {})
+            .get_balance(GetBalanceRequest::default())
             .await
             .unwrap()
             .into_inner();
@@ -1597,7 +1607,7 @@ async fn wallet_with_tari_connected_to_base_node(
     for _ in 0..num_retries {
         let _result = wallet_client.validate_all_transactions(ValidateRequest {}).await;
         let balance_res = wallet_client
-            .get_balance(GetBalanceRequest {})
+            .get_balance(GetBalanceRequest::default())
             .await
             .unwrap()
             .into_inner();
@@ -1640,6 +1650,8 @@ async fn transfer_from_wallet_to_two_recipients_at_fee(
             receiver1.as_str()
         ))
         .to_bytes(),
+        account: String::new(),
+        input_label: String::new(),
     };
 
     let payment_recipient2 = PaymentRecipient {
@@ -1654,6 +1666,8 @@ async fn transfer_from_wallet_to_two_recipients_at_fee(
             receiver2.as_str()
         ))
         .to_bytes(),
+        account: String::new(),
+        input_label: String::new(),
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient1, payment_recipient2],
@@ -1766,6 +1780,8 @@ async fn transfer_tari_to_self(world: &mut TariWorld, amount: u64, sender: Strin
         payment_type: 0, // normal mimblewimble payment type
         payment_id: PaymentId::open_from_str(&format!("transfer amount {} from {} to self", amount, sender.as_str()))
             .to_bytes(),
+        account: String::new(),
+        input_label: String::new(),
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
@@ -1852,6 +1868,8 @@ async fn htlc_transaction(world: &mut TariWorld, amount: u64, sender: String, re
             fee_per_gram
         ))
         .to_bytes(),
+        account: String::new(),
+        input_label: String::new(),
     };
 
     let atomic_swap_request = SendShaAtomicSwapRequest {
@@ -2082,7 +2100,7 @@ async fn wait_for_wallet_to_have_less_than_amount(world: &mut TariWorld, wallet:
     for _ in 0..=num_retries {
         let _result = client.validate_all_transactions(ValidateRequest {}).await;
         curr_amount = client
-            .get_balance(GetBalanceRequest {})
+            .get_balance(GetBalanceRequest::default())
             .await
             .unwrap()
             .into_inner()
@@ -2142,6 +2160,8 @@ async fn send_one_sided_stealth_transaction(
             receiver.as_str()
         ))
         .to_bytes(),
+        account: String::new(),
+        input_label: String::new(),
     };
     let transfer_req = TransferRequest {
         recipients: vec![payment_recipient],
@@ -2605,7 +2625,7 @@ async fn restart_wallet(world: &mut TariWorld, wallet: String) {
 async fn check_if_wallet_has_num_transactions(world: &mut TariWorld, wallet: String, num_txs: u64) {
     let mut client = create_wallet_client(world, wallet.clone()).await.unwrap();
     let mut get_completed_txs_res = client
-        .get_completed_transactions(GetCompletedTransactionsRequest {})
+        .get_completed_transactions(GetCompletedTransactionsRequest::default())
         .await
         .unwrap()
         .into_inner();
@@ -2669,6 +2689,8 @@ async fn multi_send_txs_from_wallet(
                 fee_per_gram
             ))
             .to_bytes(),
+            account: String::new(),
+            input_label: String::new(),
         };
 
         let transfer_req = TransferRequest {
@@ -2753,7 +2775,7 @@ async fn multi_send_txs_from_wallet(
 async fn check_if_last_imported_txs_are_invalid_in_wallet(world: &mut TariWorld, wallet: String) {
     let mut client = create_wallet_client(world, wallet.clone()).await.unwrap();
     let mut get_completed_txs_res = client
-        .get_completed_transactions(GetCompletedTransactionsRequest {})
+        .get_completed_transactions(GetCompletedTransactionsRequest::default())
         .await
         .unwrap()
         .into_inner();
@@ -2776,7 +2798,7 @@ async fn check_if_last_imported_txs_are_invalid_in_wallet(world: &mut TariWorld,
 async fn check_if_last_imported_txs_are_valid_in_wallet(world: &mut TariWorld, wallet: String) {
     let mut client = create_wallet_client(world, wallet.clone()).await.unwrap();
     let mut get_completed_txs_res = client
-        .get_completed_transactions(GetCompletedTransactionsRequest {})
+        .get_completed_transactions(GetCompletedTransactionsRequest::default())
         .await
         .unwrap()
         .into_inner();